@@ -0,0 +1,2001 @@
+set a = (0 + 1) * 2 - 0 % 1 + 0 * (0 - 0);
+set a = (1 + 2) * 3 - 3 % 2 + 1 * (1 - 1);
+set a = (2 + 3) * 4 - 6 % 3 + 2 * (2 - 2);
+set a = (3 + 4) * 5 - 9 % 4 + 3 * (3 - 0);
+set a = (4 + 5) * 6 - 12 % 5 + 4 * (4 - 1);
+set a = (5 + 6) * 2 - 15 % 6 + 5 * (5 - 2);
+set a = (6 + 7) * 3 - 18 % 7 + 6 * (6 - 0);
+set a = (7 + 8) * 4 - 21 % 1 + 7 * (7 - 1);
+set a = (8 + 9) * 5 - 24 % 2 + 8 * (8 - 2);
+set a = (9 + 10) * 6 - 27 % 3 + 9 * (9 - 0);
+set a = (10 + 11) * 2 - 30 % 4 + 10 * (10 - 1);
+set a = (11 + 12) * 3 - 33 % 5 + 0 * (11 - 2);
+set a = (12 + 13) * 4 - 36 % 6 + 1 * (12 - 0);
+set a = (13 + 14) * 5 - 39 % 7 + 2 * (0 - 1);
+set a = (14 + 15) * 6 - 42 % 1 + 3 * (1 - 2);
+set a = (15 + 16) * 2 - 45 % 2 + 4 * (2 - 0);
+set a = (16 + 17) * 3 - 48 % 3 + 5 * (3 - 1);
+set a = (17 + 1) * 4 - 51 % 4 + 6 * (4 - 2);
+set a = (18 + 2) * 5 - 54 % 5 + 7 * (5 - 0);
+set a = (19 + 3) * 6 - 57 % 6 + 8 * (6 - 1);
+set a = (20 + 4) * 2 - 60 % 7 + 9 * (7 - 2);
+set a = (21 + 5) * 3 - 63 % 1 + 10 * (8 - 0);
+set a = (22 + 6) * 4 - 66 % 2 + 0 * (9 - 1);
+set a = (23 + 7) * 5 - 69 % 3 + 1 * (10 - 2);
+set a = (24 + 8) * 6 - 72 % 4 + 2 * (11 - 0);
+set a = (25 + 9) * 2 - 75 % 5 + 3 * (12 - 1);
+set a = (26 + 10) * 3 - 78 % 6 + 4 * (0 - 2);
+set a = (27 + 11) * 4 - 81 % 7 + 5 * (1 - 0);
+set a = (28 + 12) * 5 - 84 % 1 + 6 * (2 - 1);
+set a = (29 + 13) * 6 - 87 % 2 + 7 * (3 - 2);
+set a = (30 + 14) * 2 - 90 % 3 + 8 * (4 - 0);
+set a = (31 + 15) * 3 - 93 % 4 + 9 * (5 - 1);
+set a = (32 + 16) * 4 - 96 % 5 + 10 * (6 - 2);
+set a = (33 + 17) * 5 - 99 % 6 + 0 * (7 - 0);
+set a = (34 + 1) * 6 - 102 % 7 + 1 * (8 - 1);
+set a = (35 + 2) * 2 - 105 % 1 + 2 * (9 - 2);
+set a = (36 + 3) * 3 - 108 % 2 + 3 * (10 - 0);
+set a = (37 + 4) * 4 - 111 % 3 + 4 * (11 - 1);
+set a = (38 + 5) * 5 - 114 % 4 + 5 * (12 - 2);
+set a = (39 + 6) * 6 - 117 % 5 + 6 * (0 - 0);
+set a = (40 + 7) * 2 - 120 % 6 + 7 * (1 - 1);
+set a = (41 + 8) * 3 - 123 % 7 + 8 * (2 - 2);
+set a = (42 + 9) * 4 - 126 % 1 + 9 * (3 - 0);
+set a = (43 + 10) * 5 - 129 % 2 + 10 * (4 - 1);
+set a = (44 + 11) * 6 - 132 % 3 + 0 * (5 - 2);
+set a = (45 + 12) * 2 - 135 % 4 + 1 * (6 - 0);
+set a = (46 + 13) * 3 - 138 % 5 + 2 * (7 - 1);
+set a = (47 + 14) * 4 - 141 % 6 + 3 * (8 - 2);
+set a = (48 + 15) * 5 - 144 % 7 + 4 * (9 - 0);
+set a = (49 + 16) * 6 - 147 % 1 + 5 * (10 - 1);
+set a = (50 + 17) * 2 - 150 % 2 + 6 * (11 - 2);
+set a = (51 + 1) * 3 - 153 % 3 + 7 * (12 - 0);
+set a = (52 + 2) * 4 - 156 % 4 + 8 * (0 - 1);
+set a = (53 + 3) * 5 - 159 % 5 + 9 * (1 - 2);
+set a = (54 + 4) * 6 - 162 % 6 + 10 * (2 - 0);
+set a = (55 + 5) * 2 - 165 % 7 + 0 * (3 - 1);
+set a = (56 + 6) * 3 - 168 % 1 + 1 * (4 - 2);
+set a = (57 + 7) * 4 - 171 % 2 + 2 * (5 - 0);
+set a = (58 + 8) * 5 - 174 % 3 + 3 * (6 - 1);
+set a = (59 + 9) * 6 - 177 % 4 + 4 * (7 - 2);
+set a = (60 + 10) * 2 - 180 % 5 + 5 * (8 - 0);
+set a = (61 + 11) * 3 - 183 % 6 + 6 * (9 - 1);
+set a = (62 + 12) * 4 - 186 % 7 + 7 * (10 - 2);
+set a = (63 + 13) * 5 - 189 % 1 + 8 * (11 - 0);
+set a = (64 + 14) * 6 - 192 % 2 + 9 * (12 - 1);
+set a = (65 + 15) * 2 - 195 % 3 + 10 * (0 - 2);
+set a = (66 + 16) * 3 - 198 % 4 + 0 * (1 - 0);
+set a = (67 + 17) * 4 - 201 % 5 + 1 * (2 - 1);
+set a = (68 + 1) * 5 - 204 % 6 + 2 * (3 - 2);
+set a = (69 + 2) * 6 - 207 % 7 + 3 * (4 - 0);
+set a = (70 + 3) * 2 - 210 % 1 + 4 * (5 - 1);
+set a = (71 + 4) * 3 - 213 % 2 + 5 * (6 - 2);
+set a = (72 + 5) * 4 - 216 % 3 + 6 * (7 - 0);
+set a = (73 + 6) * 5 - 219 % 4 + 7 * (8 - 1);
+set a = (74 + 7) * 6 - 222 % 5 + 8 * (9 - 2);
+set a = (75 + 8) * 2 - 225 % 6 + 9 * (10 - 0);
+set a = (76 + 9) * 3 - 228 % 7 + 10 * (11 - 1);
+set a = (77 + 10) * 4 - 231 % 1 + 0 * (12 - 2);
+set a = (78 + 11) * 5 - 234 % 2 + 1 * (0 - 0);
+set a = (79 + 12) * 6 - 237 % 3 + 2 * (1 - 1);
+set a = (80 + 13) * 2 - 240 % 4 + 3 * (2 - 2);
+set a = (81 + 14) * 3 - 243 % 5 + 4 * (3 - 0);
+set a = (82 + 15) * 4 - 246 % 6 + 5 * (4 - 1);
+set a = (83 + 16) * 5 - 249 % 7 + 6 * (5 - 2);
+set a = (84 + 17) * 6 - 252 % 1 + 7 * (6 - 0);
+set a = (85 + 1) * 2 - 255 % 2 + 8 * (7 - 1);
+set a = (86 + 2) * 3 - 258 % 3 + 9 * (8 - 2);
+set a = (87 + 3) * 4 - 261 % 4 + 10 * (9 - 0);
+set a = (88 + 4) * 5 - 264 % 5 + 0 * (10 - 1);
+set a = (89 + 5) * 6 - 267 % 6 + 1 * (11 - 2);
+set a = (90 + 6) * 2 - 270 % 7 + 2 * (12 - 0);
+set a = (91 + 7) * 3 - 273 % 1 + 3 * (0 - 1);
+set a = (92 + 8) * 4 - 276 % 2 + 4 * (1 - 2);
+set a = (93 + 9) * 5 - 279 % 3 + 5 * (2 - 0);
+set a = (94 + 10) * 6 - 282 % 4 + 6 * (3 - 1);
+set a = (95 + 11) * 2 - 285 % 5 + 7 * (4 - 2);
+set a = (96 + 12) * 3 - 288 % 6 + 8 * (5 - 0);
+set a = (97 + 13) * 4 - 291 % 7 + 9 * (6 - 1);
+set a = (98 + 14) * 5 - 294 % 1 + 10 * (7 - 2);
+set a = (99 + 15) * 6 - 297 % 2 + 0 * (8 - 0);
+set a = (100 + 16) * 2 - 300 % 3 + 1 * (9 - 1);
+set a = (101 + 17) * 3 - 303 % 4 + 2 * (10 - 2);
+set a = (102 + 1) * 4 - 306 % 5 + 3 * (11 - 0);
+set a = (103 + 2) * 5 - 309 % 6 + 4 * (12 - 1);
+set a = (104 + 3) * 6 - 312 % 7 + 5 * (0 - 2);
+set a = (105 + 4) * 2 - 315 % 1 + 6 * (1 - 0);
+set a = (106 + 5) * 3 - 318 % 2 + 7 * (2 - 1);
+set a = (107 + 6) * 4 - 321 % 3 + 8 * (3 - 2);
+set a = (108 + 7) * 5 - 324 % 4 + 9 * (4 - 0);
+set a = (109 + 8) * 6 - 327 % 5 + 10 * (5 - 1);
+set a = (110 + 9) * 2 - 330 % 6 + 0 * (6 - 2);
+set a = (111 + 10) * 3 - 333 % 7 + 1 * (7 - 0);
+set a = (112 + 11) * 4 - 336 % 1 + 2 * (8 - 1);
+set a = (113 + 12) * 5 - 339 % 2 + 3 * (9 - 2);
+set a = (114 + 13) * 6 - 342 % 3 + 4 * (10 - 0);
+set a = (115 + 14) * 2 - 345 % 4 + 5 * (11 - 1);
+set a = (116 + 15) * 3 - 348 % 5 + 6 * (12 - 2);
+set a = (117 + 16) * 4 - 351 % 6 + 7 * (0 - 0);
+set a = (118 + 17) * 5 - 354 % 7 + 8 * (1 - 1);
+set a = (119 + 1) * 6 - 357 % 1 + 9 * (2 - 2);
+set a = (120 + 2) * 2 - 360 % 2 + 10 * (3 - 0);
+set a = (121 + 3) * 3 - 363 % 3 + 0 * (4 - 1);
+set a = (122 + 4) * 4 - 366 % 4 + 1 * (5 - 2);
+set a = (123 + 5) * 5 - 369 % 5 + 2 * (6 - 0);
+set a = (124 + 6) * 6 - 372 % 6 + 3 * (7 - 1);
+set a = (125 + 7) * 2 - 375 % 7 + 4 * (8 - 2);
+set a = (126 + 8) * 3 - 378 % 1 + 5 * (9 - 0);
+set a = (127 + 9) * 4 - 381 % 2 + 6 * (10 - 1);
+set a = (128 + 10) * 5 - 384 % 3 + 7 * (11 - 2);
+set a = (129 + 11) * 6 - 387 % 4 + 8 * (12 - 0);
+set a = (130 + 12) * 2 - 390 % 5 + 9 * (0 - 1);
+set a = (131 + 13) * 3 - 393 % 6 + 10 * (1 - 2);
+set a = (132 + 14) * 4 - 396 % 7 + 0 * (2 - 0);
+set a = (133 + 15) * 5 - 399 % 1 + 1 * (3 - 1);
+set a = (134 + 16) * 6 - 402 % 2 + 2 * (4 - 2);
+set a = (135 + 17) * 2 - 405 % 3 + 3 * (5 - 0);
+set a = (136 + 1) * 3 - 408 % 4 + 4 * (6 - 1);
+set a = (137 + 2) * 4 - 411 % 5 + 5 * (7 - 2);
+set a = (138 + 3) * 5 - 414 % 6 + 6 * (8 - 0);
+set a = (139 + 4) * 6 - 417 % 7 + 7 * (9 - 1);
+set a = (140 + 5) * 2 - 420 % 1 + 8 * (10 - 2);
+set a = (141 + 6) * 3 - 423 % 2 + 9 * (11 - 0);
+set a = (142 + 7) * 4 - 426 % 3 + 10 * (12 - 1);
+set a = (143 + 8) * 5 - 429 % 4 + 0 * (0 - 2);
+set a = (144 + 9) * 6 - 432 % 5 + 1 * (1 - 0);
+set a = (145 + 10) * 2 - 435 % 6 + 2 * (2 - 1);
+set a = (146 + 11) * 3 - 438 % 7 + 3 * (3 - 2);
+set a = (147 + 12) * 4 - 441 % 1 + 4 * (4 - 0);
+set a = (148 + 13) * 5 - 444 % 2 + 5 * (5 - 1);
+set a = (149 + 14) * 6 - 447 % 3 + 6 * (6 - 2);
+set a = (150 + 15) * 2 - 450 % 4 + 7 * (7 - 0);
+set a = (151 + 16) * 3 - 453 % 5 + 8 * (8 - 1);
+set a = (152 + 17) * 4 - 456 % 6 + 9 * (9 - 2);
+set a = (153 + 1) * 5 - 459 % 7 + 10 * (10 - 0);
+set a = (154 + 2) * 6 - 462 % 1 + 0 * (11 - 1);
+set a = (155 + 3) * 2 - 465 % 2 + 1 * (12 - 2);
+set a = (156 + 4) * 3 - 468 % 3 + 2 * (0 - 0);
+set a = (157 + 5) * 4 - 471 % 4 + 3 * (1 - 1);
+set a = (158 + 6) * 5 - 474 % 5 + 4 * (2 - 2);
+set a = (159 + 7) * 6 - 477 % 6 + 5 * (3 - 0);
+set a = (160 + 8) * 2 - 480 % 7 + 6 * (4 - 1);
+set a = (161 + 9) * 3 - 483 % 1 + 7 * (5 - 2);
+set a = (162 + 10) * 4 - 486 % 2 + 8 * (6 - 0);
+set a = (163 + 11) * 5 - 489 % 3 + 9 * (7 - 1);
+set a = (164 + 12) * 6 - 492 % 4 + 10 * (8 - 2);
+set a = (165 + 13) * 2 - 495 % 5 + 0 * (9 - 0);
+set a = (166 + 14) * 3 - 498 % 6 + 1 * (10 - 1);
+set a = (167 + 15) * 4 - 501 % 7 + 2 * (11 - 2);
+set a = (168 + 16) * 5 - 504 % 1 + 3 * (12 - 0);
+set a = (169 + 17) * 6 - 507 % 2 + 4 * (0 - 1);
+set a = (170 + 1) * 2 - 510 % 3 + 5 * (1 - 2);
+set a = (171 + 2) * 3 - 513 % 4 + 6 * (2 - 0);
+set a = (172 + 3) * 4 - 516 % 5 + 7 * (3 - 1);
+set a = (173 + 4) * 5 - 519 % 6 + 8 * (4 - 2);
+set a = (174 + 5) * 6 - 522 % 7 + 9 * (5 - 0);
+set a = (175 + 6) * 2 - 525 % 1 + 10 * (6 - 1);
+set a = (176 + 7) * 3 - 528 % 2 + 0 * (7 - 2);
+set a = (177 + 8) * 4 - 531 % 3 + 1 * (8 - 0);
+set a = (178 + 9) * 5 - 534 % 4 + 2 * (9 - 1);
+set a = (179 + 10) * 6 - 537 % 5 + 3 * (10 - 2);
+set a = (180 + 11) * 2 - 540 % 6 + 4 * (11 - 0);
+set a = (181 + 12) * 3 - 543 % 7 + 5 * (12 - 1);
+set a = (182 + 13) * 4 - 546 % 1 + 6 * (0 - 2);
+set a = (183 + 14) * 5 - 549 % 2 + 7 * (1 - 0);
+set a = (184 + 15) * 6 - 552 % 3 + 8 * (2 - 1);
+set a = (185 + 16) * 2 - 555 % 4 + 9 * (3 - 2);
+set a = (186 + 17) * 3 - 558 % 5 + 10 * (4 - 0);
+set a = (187 + 1) * 4 - 561 % 6 + 0 * (5 - 1);
+set a = (188 + 2) * 5 - 564 % 7 + 1 * (6 - 2);
+set a = (189 + 3) * 6 - 567 % 1 + 2 * (7 - 0);
+set a = (190 + 4) * 2 - 570 % 2 + 3 * (8 - 1);
+set a = (191 + 5) * 3 - 573 % 3 + 4 * (9 - 2);
+set a = (192 + 6) * 4 - 576 % 4 + 5 * (10 - 0);
+set a = (193 + 7) * 5 - 579 % 5 + 6 * (11 - 1);
+set a = (194 + 8) * 6 - 582 % 6 + 7 * (12 - 2);
+set a = (195 + 9) * 2 - 585 % 7 + 8 * (0 - 0);
+set a = (196 + 10) * 3 - 588 % 1 + 9 * (1 - 1);
+set a = (197 + 11) * 4 - 591 % 2 + 10 * (2 - 2);
+set a = (198 + 12) * 5 - 594 % 3 + 0 * (3 - 0);
+set a = (199 + 13) * 6 - 597 % 4 + 1 * (4 - 1);
+set a = (200 + 14) * 2 - 600 % 5 + 2 * (5 - 2);
+set a = (201 + 15) * 3 - 603 % 6 + 3 * (6 - 0);
+set a = (202 + 16) * 4 - 606 % 7 + 4 * (7 - 1);
+set a = (203 + 17) * 5 - 609 % 1 + 5 * (8 - 2);
+set a = (204 + 1) * 6 - 612 % 2 + 6 * (9 - 0);
+set a = (205 + 2) * 2 - 615 % 3 + 7 * (10 - 1);
+set a = (206 + 3) * 3 - 618 % 4 + 8 * (11 - 2);
+set a = (207 + 4) * 4 - 621 % 5 + 9 * (12 - 0);
+set a = (208 + 5) * 5 - 624 % 6 + 10 * (0 - 1);
+set a = (209 + 6) * 6 - 627 % 7 + 0 * (1 - 2);
+set a = (210 + 7) * 2 - 630 % 1 + 1 * (2 - 0);
+set a = (211 + 8) * 3 - 633 % 2 + 2 * (3 - 1);
+set a = (212 + 9) * 4 - 636 % 3 + 3 * (4 - 2);
+set a = (213 + 10) * 5 - 639 % 4 + 4 * (5 - 0);
+set a = (214 + 11) * 6 - 642 % 5 + 5 * (6 - 1);
+set a = (215 + 12) * 2 - 645 % 6 + 6 * (7 - 2);
+set a = (216 + 13) * 3 - 648 % 7 + 7 * (8 - 0);
+set a = (217 + 14) * 4 - 651 % 1 + 8 * (9 - 1);
+set a = (218 + 15) * 5 - 654 % 2 + 9 * (10 - 2);
+set a = (219 + 16) * 6 - 657 % 3 + 10 * (11 - 0);
+set a = (220 + 17) * 2 - 660 % 4 + 0 * (12 - 1);
+set a = (221 + 1) * 3 - 663 % 5 + 1 * (0 - 2);
+set a = (222 + 2) * 4 - 666 % 6 + 2 * (1 - 0);
+set a = (223 + 3) * 5 - 669 % 7 + 3 * (2 - 1);
+set a = (224 + 4) * 6 - 672 % 1 + 4 * (3 - 2);
+set a = (225 + 5) * 2 - 675 % 2 + 5 * (4 - 0);
+set a = (226 + 6) * 3 - 678 % 3 + 6 * (5 - 1);
+set a = (227 + 7) * 4 - 681 % 4 + 7 * (6 - 2);
+set a = (228 + 8) * 5 - 684 % 5 + 8 * (7 - 0);
+set a = (229 + 9) * 6 - 687 % 6 + 9 * (8 - 1);
+set a = (230 + 10) * 2 - 690 % 7 + 10 * (9 - 2);
+set a = (231 + 11) * 3 - 693 % 1 + 0 * (10 - 0);
+set a = (232 + 12) * 4 - 696 % 2 + 1 * (11 - 1);
+set a = (233 + 13) * 5 - 699 % 3 + 2 * (12 - 2);
+set a = (234 + 14) * 6 - 702 % 4 + 3 * (0 - 0);
+set a = (235 + 15) * 2 - 705 % 5 + 4 * (1 - 1);
+set a = (236 + 16) * 3 - 708 % 6 + 5 * (2 - 2);
+set a = (237 + 17) * 4 - 711 % 7 + 6 * (3 - 0);
+set a = (238 + 1) * 5 - 714 % 1 + 7 * (4 - 1);
+set a = (239 + 2) * 6 - 717 % 2 + 8 * (5 - 2);
+set a = (240 + 3) * 2 - 720 % 3 + 9 * (6 - 0);
+set a = (241 + 4) * 3 - 723 % 4 + 10 * (7 - 1);
+set a = (242 + 5) * 4 - 726 % 5 + 0 * (8 - 2);
+set a = (243 + 6) * 5 - 729 % 6 + 1 * (9 - 0);
+set a = (244 + 7) * 6 - 732 % 7 + 2 * (10 - 1);
+set a = (245 + 8) * 2 - 735 % 1 + 3 * (11 - 2);
+set a = (246 + 9) * 3 - 738 % 2 + 4 * (12 - 0);
+set a = (247 + 10) * 4 - 741 % 3 + 5 * (0 - 1);
+set a = (248 + 11) * 5 - 744 % 4 + 6 * (1 - 2);
+set a = (249 + 12) * 6 - 747 % 5 + 7 * (2 - 0);
+set a = (250 + 13) * 2 - 750 % 6 + 8 * (3 - 1);
+set a = (251 + 14) * 3 - 753 % 7 + 9 * (4 - 2);
+set a = (252 + 15) * 4 - 756 % 1 + 10 * (5 - 0);
+set a = (253 + 16) * 5 - 759 % 2 + 0 * (6 - 1);
+set a = (254 + 17) * 6 - 762 % 3 + 1 * (7 - 2);
+set a = (255 + 1) * 2 - 765 % 4 + 2 * (8 - 0);
+set a = (256 + 2) * 3 - 768 % 5 + 3 * (9 - 1);
+set a = (257 + 3) * 4 - 771 % 6 + 4 * (10 - 2);
+set a = (258 + 4) * 5 - 774 % 7 + 5 * (11 - 0);
+set a = (259 + 5) * 6 - 777 % 1 + 6 * (12 - 1);
+set a = (260 + 6) * 2 - 780 % 2 + 7 * (0 - 2);
+set a = (261 + 7) * 3 - 783 % 3 + 8 * (1 - 0);
+set a = (262 + 8) * 4 - 786 % 4 + 9 * (2 - 1);
+set a = (263 + 9) * 5 - 789 % 5 + 10 * (3 - 2);
+set a = (264 + 10) * 6 - 792 % 6 + 0 * (4 - 0);
+set a = (265 + 11) * 2 - 795 % 7 + 1 * (5 - 1);
+set a = (266 + 12) * 3 - 798 % 1 + 2 * (6 - 2);
+set a = (267 + 13) * 4 - 801 % 2 + 3 * (7 - 0);
+set a = (268 + 14) * 5 - 804 % 3 + 4 * (8 - 1);
+set a = (269 + 15) * 6 - 807 % 4 + 5 * (9 - 2);
+set a = (270 + 16) * 2 - 810 % 5 + 6 * (10 - 0);
+set a = (271 + 17) * 3 - 813 % 6 + 7 * (11 - 1);
+set a = (272 + 1) * 4 - 816 % 7 + 8 * (12 - 2);
+set a = (273 + 2) * 5 - 819 % 1 + 9 * (0 - 0);
+set a = (274 + 3) * 6 - 822 % 2 + 10 * (1 - 1);
+set a = (275 + 4) * 2 - 825 % 3 + 0 * (2 - 2);
+set a = (276 + 5) * 3 - 828 % 4 + 1 * (3 - 0);
+set a = (277 + 6) * 4 - 831 % 5 + 2 * (4 - 1);
+set a = (278 + 7) * 5 - 834 % 6 + 3 * (5 - 2);
+set a = (279 + 8) * 6 - 837 % 7 + 4 * (6 - 0);
+set a = (280 + 9) * 2 - 840 % 1 + 5 * (7 - 1);
+set a = (281 + 10) * 3 - 843 % 2 + 6 * (8 - 2);
+set a = (282 + 11) * 4 - 846 % 3 + 7 * (9 - 0);
+set a = (283 + 12) * 5 - 849 % 4 + 8 * (10 - 1);
+set a = (284 + 13) * 6 - 852 % 5 + 9 * (11 - 2);
+set a = (285 + 14) * 2 - 855 % 6 + 10 * (12 - 0);
+set a = (286 + 15) * 3 - 858 % 7 + 0 * (0 - 1);
+set a = (287 + 16) * 4 - 861 % 1 + 1 * (1 - 2);
+set a = (288 + 17) * 5 - 864 % 2 + 2 * (2 - 0);
+set a = (289 + 1) * 6 - 867 % 3 + 3 * (3 - 1);
+set a = (290 + 2) * 2 - 870 % 4 + 4 * (4 - 2);
+set a = (291 + 3) * 3 - 873 % 5 + 5 * (5 - 0);
+set a = (292 + 4) * 4 - 876 % 6 + 6 * (6 - 1);
+set a = (293 + 5) * 5 - 879 % 7 + 7 * (7 - 2);
+set a = (294 + 6) * 6 - 882 % 1 + 8 * (8 - 0);
+set a = (295 + 7) * 2 - 885 % 2 + 9 * (9 - 1);
+set a = (296 + 8) * 3 - 888 % 3 + 10 * (10 - 2);
+set a = (297 + 9) * 4 - 891 % 4 + 0 * (11 - 0);
+set a = (298 + 10) * 5 - 894 % 5 + 1 * (12 - 1);
+set a = (299 + 11) * 6 - 897 % 6 + 2 * (0 - 2);
+set a = (300 + 12) * 2 - 900 % 7 + 3 * (1 - 0);
+set a = (301 + 13) * 3 - 903 % 1 + 4 * (2 - 1);
+set a = (302 + 14) * 4 - 906 % 2 + 5 * (3 - 2);
+set a = (303 + 15) * 5 - 909 % 3 + 6 * (4 - 0);
+set a = (304 + 16) * 6 - 912 % 4 + 7 * (5 - 1);
+set a = (305 + 17) * 2 - 915 % 5 + 8 * (6 - 2);
+set a = (306 + 1) * 3 - 918 % 6 + 9 * (7 - 0);
+set a = (307 + 2) * 4 - 921 % 7 + 10 * (8 - 1);
+set a = (308 + 3) * 5 - 924 % 1 + 0 * (9 - 2);
+set a = (309 + 4) * 6 - 927 % 2 + 1 * (10 - 0);
+set a = (310 + 5) * 2 - 930 % 3 + 2 * (11 - 1);
+set a = (311 + 6) * 3 - 933 % 4 + 3 * (12 - 2);
+set a = (312 + 7) * 4 - 936 % 5 + 4 * (0 - 0);
+set a = (313 + 8) * 5 - 939 % 6 + 5 * (1 - 1);
+set a = (314 + 9) * 6 - 942 % 7 + 6 * (2 - 2);
+set a = (315 + 10) * 2 - 945 % 1 + 7 * (3 - 0);
+set a = (316 + 11) * 3 - 948 % 2 + 8 * (4 - 1);
+set a = (317 + 12) * 4 - 951 % 3 + 9 * (5 - 2);
+set a = (318 + 13) * 5 - 954 % 4 + 10 * (6 - 0);
+set a = (319 + 14) * 6 - 957 % 5 + 0 * (7 - 1);
+set a = (320 + 15) * 2 - 960 % 6 + 1 * (8 - 2);
+set a = (321 + 16) * 3 - 963 % 7 + 2 * (9 - 0);
+set a = (322 + 17) * 4 - 966 % 1 + 3 * (10 - 1);
+set a = (323 + 1) * 5 - 969 % 2 + 4 * (11 - 2);
+set a = (324 + 2) * 6 - 972 % 3 + 5 * (12 - 0);
+set a = (325 + 3) * 2 - 975 % 4 + 6 * (0 - 1);
+set a = (326 + 4) * 3 - 978 % 5 + 7 * (1 - 2);
+set a = (327 + 5) * 4 - 981 % 6 + 8 * (2 - 0);
+set a = (328 + 6) * 5 - 984 % 7 + 9 * (3 - 1);
+set a = (329 + 7) * 6 - 987 % 1 + 10 * (4 - 2);
+set a = (330 + 8) * 2 - 990 % 2 + 0 * (5 - 0);
+set a = (331 + 9) * 3 - 993 % 3 + 1 * (6 - 1);
+set a = (332 + 10) * 4 - 996 % 4 + 2 * (7 - 2);
+set a = (333 + 11) * 5 - 999 % 5 + 3 * (8 - 0);
+set a = (334 + 12) * 6 - 1002 % 6 + 4 * (9 - 1);
+set a = (335 + 13) * 2 - 1005 % 7 + 5 * (10 - 2);
+set a = (336 + 14) * 3 - 1008 % 1 + 6 * (11 - 0);
+set a = (337 + 15) * 4 - 1011 % 2 + 7 * (12 - 1);
+set a = (338 + 16) * 5 - 1014 % 3 + 8 * (0 - 2);
+set a = (339 + 17) * 6 - 1017 % 4 + 9 * (1 - 0);
+set a = (340 + 1) * 2 - 1020 % 5 + 10 * (2 - 1);
+set a = (341 + 2) * 3 - 1023 % 6 + 0 * (3 - 2);
+set a = (342 + 3) * 4 - 1026 % 7 + 1 * (4 - 0);
+set a = (343 + 4) * 5 - 1029 % 1 + 2 * (5 - 1);
+set a = (344 + 5) * 6 - 1032 % 2 + 3 * (6 - 2);
+set a = (345 + 6) * 2 - 1035 % 3 + 4 * (7 - 0);
+set a = (346 + 7) * 3 - 1038 % 4 + 5 * (8 - 1);
+set a = (347 + 8) * 4 - 1041 % 5 + 6 * (9 - 2);
+set a = (348 + 9) * 5 - 1044 % 6 + 7 * (10 - 0);
+set a = (349 + 10) * 6 - 1047 % 7 + 8 * (11 - 1);
+set a = (350 + 11) * 2 - 1050 % 1 + 9 * (12 - 2);
+set a = (351 + 12) * 3 - 1053 % 2 + 10 * (0 - 0);
+set a = (352 + 13) * 4 - 1056 % 3 + 0 * (1 - 1);
+set a = (353 + 14) * 5 - 1059 % 4 + 1 * (2 - 2);
+set a = (354 + 15) * 6 - 1062 % 5 + 2 * (3 - 0);
+set a = (355 + 16) * 2 - 1065 % 6 + 3 * (4 - 1);
+set a = (356 + 17) * 3 - 1068 % 7 + 4 * (5 - 2);
+set a = (357 + 1) * 4 - 1071 % 1 + 5 * (6 - 0);
+set a = (358 + 2) * 5 - 1074 % 2 + 6 * (7 - 1);
+set a = (359 + 3) * 6 - 1077 % 3 + 7 * (8 - 2);
+set a = (360 + 4) * 2 - 1080 % 4 + 8 * (9 - 0);
+set a = (361 + 5) * 3 - 1083 % 5 + 9 * (10 - 1);
+set a = (362 + 6) * 4 - 1086 % 6 + 10 * (11 - 2);
+set a = (363 + 7) * 5 - 1089 % 7 + 0 * (12 - 0);
+set a = (364 + 8) * 6 - 1092 % 1 + 1 * (0 - 1);
+set a = (365 + 9) * 2 - 1095 % 2 + 2 * (1 - 2);
+set a = (366 + 10) * 3 - 1098 % 3 + 3 * (2 - 0);
+set a = (367 + 11) * 4 - 1101 % 4 + 4 * (3 - 1);
+set a = (368 + 12) * 5 - 1104 % 5 + 5 * (4 - 2);
+set a = (369 + 13) * 6 - 1107 % 6 + 6 * (5 - 0);
+set a = (370 + 14) * 2 - 1110 % 7 + 7 * (6 - 1);
+set a = (371 + 15) * 3 - 1113 % 1 + 8 * (7 - 2);
+set a = (372 + 16) * 4 - 1116 % 2 + 9 * (8 - 0);
+set a = (373 + 17) * 5 - 1119 % 3 + 10 * (9 - 1);
+set a = (374 + 1) * 6 - 1122 % 4 + 0 * (10 - 2);
+set a = (375 + 2) * 2 - 1125 % 5 + 1 * (11 - 0);
+set a = (376 + 3) * 3 - 1128 % 6 + 2 * (12 - 1);
+set a = (377 + 4) * 4 - 1131 % 7 + 3 * (0 - 2);
+set a = (378 + 5) * 5 - 1134 % 1 + 4 * (1 - 0);
+set a = (379 + 6) * 6 - 1137 % 2 + 5 * (2 - 1);
+set a = (380 + 7) * 2 - 1140 % 3 + 6 * (3 - 2);
+set a = (381 + 8) * 3 - 1143 % 4 + 7 * (4 - 0);
+set a = (382 + 9) * 4 - 1146 % 5 + 8 * (5 - 1);
+set a = (383 + 10) * 5 - 1149 % 6 + 9 * (6 - 2);
+set a = (384 + 11) * 6 - 1152 % 7 + 10 * (7 - 0);
+set a = (385 + 12) * 2 - 1155 % 1 + 0 * (8 - 1);
+set a = (386 + 13) * 3 - 1158 % 2 + 1 * (9 - 2);
+set a = (387 + 14) * 4 - 1161 % 3 + 2 * (10 - 0);
+set a = (388 + 15) * 5 - 1164 % 4 + 3 * (11 - 1);
+set a = (389 + 16) * 6 - 1167 % 5 + 4 * (12 - 2);
+set a = (390 + 17) * 2 - 1170 % 6 + 5 * (0 - 0);
+set a = (391 + 1) * 3 - 1173 % 7 + 6 * (1 - 1);
+set a = (392 + 2) * 4 - 1176 % 1 + 7 * (2 - 2);
+set a = (393 + 3) * 5 - 1179 % 2 + 8 * (3 - 0);
+set a = (394 + 4) * 6 - 1182 % 3 + 9 * (4 - 1);
+set a = (395 + 5) * 2 - 1185 % 4 + 10 * (5 - 2);
+set a = (396 + 6) * 3 - 1188 % 5 + 0 * (6 - 0);
+set a = (397 + 7) * 4 - 1191 % 6 + 1 * (7 - 1);
+set a = (398 + 8) * 5 - 1194 % 7 + 2 * (8 - 2);
+set a = (399 + 9) * 6 - 1197 % 1 + 3 * (9 - 0);
+set a = (400 + 10) * 2 - 1200 % 2 + 4 * (10 - 1);
+set a = (401 + 11) * 3 - 1203 % 3 + 5 * (11 - 2);
+set a = (402 + 12) * 4 - 1206 % 4 + 6 * (12 - 0);
+set a = (403 + 13) * 5 - 1209 % 5 + 7 * (0 - 1);
+set a = (404 + 14) * 6 - 1212 % 6 + 8 * (1 - 2);
+set a = (405 + 15) * 2 - 1215 % 7 + 9 * (2 - 0);
+set a = (406 + 16) * 3 - 1218 % 1 + 10 * (3 - 1);
+set a = (407 + 17) * 4 - 1221 % 2 + 0 * (4 - 2);
+set a = (408 + 1) * 5 - 1224 % 3 + 1 * (5 - 0);
+set a = (409 + 2) * 6 - 1227 % 4 + 2 * (6 - 1);
+set a = (410 + 3) * 2 - 1230 % 5 + 3 * (7 - 2);
+set a = (411 + 4) * 3 - 1233 % 6 + 4 * (8 - 0);
+set a = (412 + 5) * 4 - 1236 % 7 + 5 * (9 - 1);
+set a = (413 + 6) * 5 - 1239 % 1 + 6 * (10 - 2);
+set a = (414 + 7) * 6 - 1242 % 2 + 7 * (11 - 0);
+set a = (415 + 8) * 2 - 1245 % 3 + 8 * (12 - 1);
+set a = (416 + 9) * 3 - 1248 % 4 + 9 * (0 - 2);
+set a = (417 + 10) * 4 - 1251 % 5 + 10 * (1 - 0);
+set a = (418 + 11) * 5 - 1254 % 6 + 0 * (2 - 1);
+set a = (419 + 12) * 6 - 1257 % 7 + 1 * (3 - 2);
+set a = (420 + 13) * 2 - 1260 % 1 + 2 * (4 - 0);
+set a = (421 + 14) * 3 - 1263 % 2 + 3 * (5 - 1);
+set a = (422 + 15) * 4 - 1266 % 3 + 4 * (6 - 2);
+set a = (423 + 16) * 5 - 1269 % 4 + 5 * (7 - 0);
+set a = (424 + 17) * 6 - 1272 % 5 + 6 * (8 - 1);
+set a = (425 + 1) * 2 - 1275 % 6 + 7 * (9 - 2);
+set a = (426 + 2) * 3 - 1278 % 7 + 8 * (10 - 0);
+set a = (427 + 3) * 4 - 1281 % 1 + 9 * (11 - 1);
+set a = (428 + 4) * 5 - 1284 % 2 + 10 * (12 - 2);
+set a = (429 + 5) * 6 - 1287 % 3 + 0 * (0 - 0);
+set a = (430 + 6) * 2 - 1290 % 4 + 1 * (1 - 1);
+set a = (431 + 7) * 3 - 1293 % 5 + 2 * (2 - 2);
+set a = (432 + 8) * 4 - 1296 % 6 + 3 * (3 - 0);
+set a = (433 + 9) * 5 - 1299 % 7 + 4 * (4 - 1);
+set a = (434 + 10) * 6 - 1302 % 1 + 5 * (5 - 2);
+set a = (435 + 11) * 2 - 1305 % 2 + 6 * (6 - 0);
+set a = (436 + 12) * 3 - 1308 % 3 + 7 * (7 - 1);
+set a = (437 + 13) * 4 - 1311 % 4 + 8 * (8 - 2);
+set a = (438 + 14) * 5 - 1314 % 5 + 9 * (9 - 0);
+set a = (439 + 15) * 6 - 1317 % 6 + 10 * (10 - 1);
+set a = (440 + 16) * 2 - 1320 % 7 + 0 * (11 - 2);
+set a = (441 + 17) * 3 - 1323 % 1 + 1 * (12 - 0);
+set a = (442 + 1) * 4 - 1326 % 2 + 2 * (0 - 1);
+set a = (443 + 2) * 5 - 1329 % 3 + 3 * (1 - 2);
+set a = (444 + 3) * 6 - 1332 % 4 + 4 * (2 - 0);
+set a = (445 + 4) * 2 - 1335 % 5 + 5 * (3 - 1);
+set a = (446 + 5) * 3 - 1338 % 6 + 6 * (4 - 2);
+set a = (447 + 6) * 4 - 1341 % 7 + 7 * (5 - 0);
+set a = (448 + 7) * 5 - 1344 % 1 + 8 * (6 - 1);
+set a = (449 + 8) * 6 - 1347 % 2 + 9 * (7 - 2);
+set a = (450 + 9) * 2 - 1350 % 3 + 10 * (8 - 0);
+set a = (451 + 10) * 3 - 1353 % 4 + 0 * (9 - 1);
+set a = (452 + 11) * 4 - 1356 % 5 + 1 * (10 - 2);
+set a = (453 + 12) * 5 - 1359 % 6 + 2 * (11 - 0);
+set a = (454 + 13) * 6 - 1362 % 7 + 3 * (12 - 1);
+set a = (455 + 14) * 2 - 1365 % 1 + 4 * (0 - 2);
+set a = (456 + 15) * 3 - 1368 % 2 + 5 * (1 - 0);
+set a = (457 + 16) * 4 - 1371 % 3 + 6 * (2 - 1);
+set a = (458 + 17) * 5 - 1374 % 4 + 7 * (3 - 2);
+set a = (459 + 1) * 6 - 1377 % 5 + 8 * (4 - 0);
+set a = (460 + 2) * 2 - 1380 % 6 + 9 * (5 - 1);
+set a = (461 + 3) * 3 - 1383 % 7 + 10 * (6 - 2);
+set a = (462 + 4) * 4 - 1386 % 1 + 0 * (7 - 0);
+set a = (463 + 5) * 5 - 1389 % 2 + 1 * (8 - 1);
+set a = (464 + 6) * 6 - 1392 % 3 + 2 * (9 - 2);
+set a = (465 + 7) * 2 - 1395 % 4 + 3 * (10 - 0);
+set a = (466 + 8) * 3 - 1398 % 5 + 4 * (11 - 1);
+set a = (467 + 9) * 4 - 1401 % 6 + 5 * (12 - 2);
+set a = (468 + 10) * 5 - 1404 % 7 + 6 * (0 - 0);
+set a = (469 + 11) * 6 - 1407 % 1 + 7 * (1 - 1);
+set a = (470 + 12) * 2 - 1410 % 2 + 8 * (2 - 2);
+set a = (471 + 13) * 3 - 1413 % 3 + 9 * (3 - 0);
+set a = (472 + 14) * 4 - 1416 % 4 + 10 * (4 - 1);
+set a = (473 + 15) * 5 - 1419 % 5 + 0 * (5 - 2);
+set a = (474 + 16) * 6 - 1422 % 6 + 1 * (6 - 0);
+set a = (475 + 17) * 2 - 1425 % 7 + 2 * (7 - 1);
+set a = (476 + 1) * 3 - 1428 % 1 + 3 * (8 - 2);
+set a = (477 + 2) * 4 - 1431 % 2 + 4 * (9 - 0);
+set a = (478 + 3) * 5 - 1434 % 3 + 5 * (10 - 1);
+set a = (479 + 4) * 6 - 1437 % 4 + 6 * (11 - 2);
+set a = (480 + 5) * 2 - 1440 % 5 + 7 * (12 - 0);
+set a = (481 + 6) * 3 - 1443 % 6 + 8 * (0 - 1);
+set a = (482 + 7) * 4 - 1446 % 7 + 9 * (1 - 2);
+set a = (483 + 8) * 5 - 1449 % 1 + 10 * (2 - 0);
+set a = (484 + 9) * 6 - 1452 % 2 + 0 * (3 - 1);
+set a = (485 + 10) * 2 - 1455 % 3 + 1 * (4 - 2);
+set a = (486 + 11) * 3 - 1458 % 4 + 2 * (5 - 0);
+set a = (487 + 12) * 4 - 1461 % 5 + 3 * (6 - 1);
+set a = (488 + 13) * 5 - 1464 % 6 + 4 * (7 - 2);
+set a = (489 + 14) * 6 - 1467 % 7 + 5 * (8 - 0);
+set a = (490 + 15) * 2 - 1470 % 1 + 6 * (9 - 1);
+set a = (491 + 16) * 3 - 1473 % 2 + 7 * (10 - 2);
+set a = (492 + 17) * 4 - 1476 % 3 + 8 * (11 - 0);
+set a = (493 + 1) * 5 - 1479 % 4 + 9 * (12 - 1);
+set a = (494 + 2) * 6 - 1482 % 5 + 10 * (0 - 2);
+set a = (495 + 3) * 2 - 1485 % 6 + 0 * (1 - 0);
+set a = (496 + 4) * 3 - 1488 % 7 + 1 * (2 - 1);
+set a = (497 + 5) * 4 - 1491 % 1 + 2 * (3 - 2);
+set a = (498 + 6) * 5 - 1494 % 2 + 3 * (4 - 0);
+set a = (499 + 7) * 6 - 1497 % 3 + 4 * (5 - 1);
+set a = (500 + 8) * 2 - 1500 % 4 + 5 * (6 - 2);
+set a = (501 + 9) * 3 - 1503 % 5 + 6 * (7 - 0);
+set a = (502 + 10) * 4 - 1506 % 6 + 7 * (8 - 1);
+set a = (503 + 11) * 5 - 1509 % 7 + 8 * (9 - 2);
+set a = (504 + 12) * 6 - 1512 % 1 + 9 * (10 - 0);
+set a = (505 + 13) * 2 - 1515 % 2 + 10 * (11 - 1);
+set a = (506 + 14) * 3 - 1518 % 3 + 0 * (12 - 2);
+set a = (507 + 15) * 4 - 1521 % 4 + 1 * (0 - 0);
+set a = (508 + 16) * 5 - 1524 % 5 + 2 * (1 - 1);
+set a = (509 + 17) * 6 - 1527 % 6 + 3 * (2 - 2);
+set a = (510 + 1) * 2 - 1530 % 7 + 4 * (3 - 0);
+set a = (511 + 2) * 3 - 1533 % 1 + 5 * (4 - 1);
+set a = (512 + 3) * 4 - 1536 % 2 + 6 * (5 - 2);
+set a = (513 + 4) * 5 - 1539 % 3 + 7 * (6 - 0);
+set a = (514 + 5) * 6 - 1542 % 4 + 8 * (7 - 1);
+set a = (515 + 6) * 2 - 1545 % 5 + 9 * (8 - 2);
+set a = (516 + 7) * 3 - 1548 % 6 + 10 * (9 - 0);
+set a = (517 + 8) * 4 - 1551 % 7 + 0 * (10 - 1);
+set a = (518 + 9) * 5 - 1554 % 1 + 1 * (11 - 2);
+set a = (519 + 10) * 6 - 1557 % 2 + 2 * (12 - 0);
+set a = (520 + 11) * 2 - 1560 % 3 + 3 * (0 - 1);
+set a = (521 + 12) * 3 - 1563 % 4 + 4 * (1 - 2);
+set a = (522 + 13) * 4 - 1566 % 5 + 5 * (2 - 0);
+set a = (523 + 14) * 5 - 1569 % 6 + 6 * (3 - 1);
+set a = (524 + 15) * 6 - 1572 % 7 + 7 * (4 - 2);
+set a = (525 + 16) * 2 - 1575 % 1 + 8 * (5 - 0);
+set a = (526 + 17) * 3 - 1578 % 2 + 9 * (6 - 1);
+set a = (527 + 1) * 4 - 1581 % 3 + 10 * (7 - 2);
+set a = (528 + 2) * 5 - 1584 % 4 + 0 * (8 - 0);
+set a = (529 + 3) * 6 - 1587 % 5 + 1 * (9 - 1);
+set a = (530 + 4) * 2 - 1590 % 6 + 2 * (10 - 2);
+set a = (531 + 5) * 3 - 1593 % 7 + 3 * (11 - 0);
+set a = (532 + 6) * 4 - 1596 % 1 + 4 * (12 - 1);
+set a = (533 + 7) * 5 - 1599 % 2 + 5 * (0 - 2);
+set a = (534 + 8) * 6 - 1602 % 3 + 6 * (1 - 0);
+set a = (535 + 9) * 2 - 1605 % 4 + 7 * (2 - 1);
+set a = (536 + 10) * 3 - 1608 % 5 + 8 * (3 - 2);
+set a = (537 + 11) * 4 - 1611 % 6 + 9 * (4 - 0);
+set a = (538 + 12) * 5 - 1614 % 7 + 10 * (5 - 1);
+set a = (539 + 13) * 6 - 1617 % 1 + 0 * (6 - 2);
+set a = (540 + 14) * 2 - 1620 % 2 + 1 * (7 - 0);
+set a = (541 + 15) * 3 - 1623 % 3 + 2 * (8 - 1);
+set a = (542 + 16) * 4 - 1626 % 4 + 3 * (9 - 2);
+set a = (543 + 17) * 5 - 1629 % 5 + 4 * (10 - 0);
+set a = (544 + 1) * 6 - 1632 % 6 + 5 * (11 - 1);
+set a = (545 + 2) * 2 - 1635 % 7 + 6 * (12 - 2);
+set a = (546 + 3) * 3 - 1638 % 1 + 7 * (0 - 0);
+set a = (547 + 4) * 4 - 1641 % 2 + 8 * (1 - 1);
+set a = (548 + 5) * 5 - 1644 % 3 + 9 * (2 - 2);
+set a = (549 + 6) * 6 - 1647 % 4 + 10 * (3 - 0);
+set a = (550 + 7) * 2 - 1650 % 5 + 0 * (4 - 1);
+set a = (551 + 8) * 3 - 1653 % 6 + 1 * (5 - 2);
+set a = (552 + 9) * 4 - 1656 % 7 + 2 * (6 - 0);
+set a = (553 + 10) * 5 - 1659 % 1 + 3 * (7 - 1);
+set a = (554 + 11) * 6 - 1662 % 2 + 4 * (8 - 2);
+set a = (555 + 12) * 2 - 1665 % 3 + 5 * (9 - 0);
+set a = (556 + 13) * 3 - 1668 % 4 + 6 * (10 - 1);
+set a = (557 + 14) * 4 - 1671 % 5 + 7 * (11 - 2);
+set a = (558 + 15) * 5 - 1674 % 6 + 8 * (12 - 0);
+set a = (559 + 16) * 6 - 1677 % 7 + 9 * (0 - 1);
+set a = (560 + 17) * 2 - 1680 % 1 + 10 * (1 - 2);
+set a = (561 + 1) * 3 - 1683 % 2 + 0 * (2 - 0);
+set a = (562 + 2) * 4 - 1686 % 3 + 1 * (3 - 1);
+set a = (563 + 3) * 5 - 1689 % 4 + 2 * (4 - 2);
+set a = (564 + 4) * 6 - 1692 % 5 + 3 * (5 - 0);
+set a = (565 + 5) * 2 - 1695 % 6 + 4 * (6 - 1);
+set a = (566 + 6) * 3 - 1698 % 7 + 5 * (7 - 2);
+set a = (567 + 7) * 4 - 1701 % 1 + 6 * (8 - 0);
+set a = (568 + 8) * 5 - 1704 % 2 + 7 * (9 - 1);
+set a = (569 + 9) * 6 - 1707 % 3 + 8 * (10 - 2);
+set a = (570 + 10) * 2 - 1710 % 4 + 9 * (11 - 0);
+set a = (571 + 11) * 3 - 1713 % 5 + 10 * (12 - 1);
+set a = (572 + 12) * 4 - 1716 % 6 + 0 * (0 - 2);
+set a = (573 + 13) * 5 - 1719 % 7 + 1 * (1 - 0);
+set a = (574 + 14) * 6 - 1722 % 1 + 2 * (2 - 1);
+set a = (575 + 15) * 2 - 1725 % 2 + 3 * (3 - 2);
+set a = (576 + 16) * 3 - 1728 % 3 + 4 * (4 - 0);
+set a = (577 + 17) * 4 - 1731 % 4 + 5 * (5 - 1);
+set a = (578 + 1) * 5 - 1734 % 5 + 6 * (6 - 2);
+set a = (579 + 2) * 6 - 1737 % 6 + 7 * (7 - 0);
+set a = (580 + 3) * 2 - 1740 % 7 + 8 * (8 - 1);
+set a = (581 + 4) * 3 - 1743 % 1 + 9 * (9 - 2);
+set a = (582 + 5) * 4 - 1746 % 2 + 10 * (10 - 0);
+set a = (583 + 6) * 5 - 1749 % 3 + 0 * (11 - 1);
+set a = (584 + 7) * 6 - 1752 % 4 + 1 * (12 - 2);
+set a = (585 + 8) * 2 - 1755 % 5 + 2 * (0 - 0);
+set a = (586 + 9) * 3 - 1758 % 6 + 3 * (1 - 1);
+set a = (587 + 10) * 4 - 1761 % 7 + 4 * (2 - 2);
+set a = (588 + 11) * 5 - 1764 % 1 + 5 * (3 - 0);
+set a = (589 + 12) * 6 - 1767 % 2 + 6 * (4 - 1);
+set a = (590 + 13) * 2 - 1770 % 3 + 7 * (5 - 2);
+set a = (591 + 14) * 3 - 1773 % 4 + 8 * (6 - 0);
+set a = (592 + 15) * 4 - 1776 % 5 + 9 * (7 - 1);
+set a = (593 + 16) * 5 - 1779 % 6 + 10 * (8 - 2);
+set a = (594 + 17) * 6 - 1782 % 7 + 0 * (9 - 0);
+set a = (595 + 1) * 2 - 1785 % 1 + 1 * (10 - 1);
+set a = (596 + 2) * 3 - 1788 % 2 + 2 * (11 - 2);
+set a = (597 + 3) * 4 - 1791 % 3 + 3 * (12 - 0);
+set a = (598 + 4) * 5 - 1794 % 4 + 4 * (0 - 1);
+set a = (599 + 5) * 6 - 1797 % 5 + 5 * (1 - 2);
+set a = (600 + 6) * 2 - 1800 % 6 + 6 * (2 - 0);
+set a = (601 + 7) * 3 - 1803 % 7 + 7 * (3 - 1);
+set a = (602 + 8) * 4 - 1806 % 1 + 8 * (4 - 2);
+set a = (603 + 9) * 5 - 1809 % 2 + 9 * (5 - 0);
+set a = (604 + 10) * 6 - 1812 % 3 + 10 * (6 - 1);
+set a = (605 + 11) * 2 - 1815 % 4 + 0 * (7 - 2);
+set a = (606 + 12) * 3 - 1818 % 5 + 1 * (8 - 0);
+set a = (607 + 13) * 4 - 1821 % 6 + 2 * (9 - 1);
+set a = (608 + 14) * 5 - 1824 % 7 + 3 * (10 - 2);
+set a = (609 + 15) * 6 - 1827 % 1 + 4 * (11 - 0);
+set a = (610 + 16) * 2 - 1830 % 2 + 5 * (12 - 1);
+set a = (611 + 17) * 3 - 1833 % 3 + 6 * (0 - 2);
+set a = (612 + 1) * 4 - 1836 % 4 + 7 * (1 - 0);
+set a = (613 + 2) * 5 - 1839 % 5 + 8 * (2 - 1);
+set a = (614 + 3) * 6 - 1842 % 6 + 9 * (3 - 2);
+set a = (615 + 4) * 2 - 1845 % 7 + 10 * (4 - 0);
+set a = (616 + 5) * 3 - 1848 % 1 + 0 * (5 - 1);
+set a = (617 + 6) * 4 - 1851 % 2 + 1 * (6 - 2);
+set a = (618 + 7) * 5 - 1854 % 3 + 2 * (7 - 0);
+set a = (619 + 8) * 6 - 1857 % 4 + 3 * (8 - 1);
+set a = (620 + 9) * 2 - 1860 % 5 + 4 * (9 - 2);
+set a = (621 + 10) * 3 - 1863 % 6 + 5 * (10 - 0);
+set a = (622 + 11) * 4 - 1866 % 7 + 6 * (11 - 1);
+set a = (623 + 12) * 5 - 1869 % 1 + 7 * (12 - 2);
+set a = (624 + 13) * 6 - 1872 % 2 + 8 * (0 - 0);
+set a = (625 + 14) * 2 - 1875 % 3 + 9 * (1 - 1);
+set a = (626 + 15) * 3 - 1878 % 4 + 10 * (2 - 2);
+set a = (627 + 16) * 4 - 1881 % 5 + 0 * (3 - 0);
+set a = (628 + 17) * 5 - 1884 % 6 + 1 * (4 - 1);
+set a = (629 + 1) * 6 - 1887 % 7 + 2 * (5 - 2);
+set a = (630 + 2) * 2 - 1890 % 1 + 3 * (6 - 0);
+set a = (631 + 3) * 3 - 1893 % 2 + 4 * (7 - 1);
+set a = (632 + 4) * 4 - 1896 % 3 + 5 * (8 - 2);
+set a = (633 + 5) * 5 - 1899 % 4 + 6 * (9 - 0);
+set a = (634 + 6) * 6 - 1902 % 5 + 7 * (10 - 1);
+set a = (635 + 7) * 2 - 1905 % 6 + 8 * (11 - 2);
+set a = (636 + 8) * 3 - 1908 % 7 + 9 * (12 - 0);
+set a = (637 + 9) * 4 - 1911 % 1 + 10 * (0 - 1);
+set a = (638 + 10) * 5 - 1914 % 2 + 0 * (1 - 2);
+set a = (639 + 11) * 6 - 1917 % 3 + 1 * (2 - 0);
+set a = (640 + 12) * 2 - 1920 % 4 + 2 * (3 - 1);
+set a = (641 + 13) * 3 - 1923 % 5 + 3 * (4 - 2);
+set a = (642 + 14) * 4 - 1926 % 6 + 4 * (5 - 0);
+set a = (643 + 15) * 5 - 1929 % 7 + 5 * (6 - 1);
+set a = (644 + 16) * 6 - 1932 % 1 + 6 * (7 - 2);
+set a = (645 + 17) * 2 - 1935 % 2 + 7 * (8 - 0);
+set a = (646 + 1) * 3 - 1938 % 3 + 8 * (9 - 1);
+set a = (647 + 2) * 4 - 1941 % 4 + 9 * (10 - 2);
+set a = (648 + 3) * 5 - 1944 % 5 + 10 * (11 - 0);
+set a = (649 + 4) * 6 - 1947 % 6 + 0 * (12 - 1);
+set a = (650 + 5) * 2 - 1950 % 7 + 1 * (0 - 2);
+set a = (651 + 6) * 3 - 1953 % 1 + 2 * (1 - 0);
+set a = (652 + 7) * 4 - 1956 % 2 + 3 * (2 - 1);
+set a = (653 + 8) * 5 - 1959 % 3 + 4 * (3 - 2);
+set a = (654 + 9) * 6 - 1962 % 4 + 5 * (4 - 0);
+set a = (655 + 10) * 2 - 1965 % 5 + 6 * (5 - 1);
+set a = (656 + 11) * 3 - 1968 % 6 + 7 * (6 - 2);
+set a = (657 + 12) * 4 - 1971 % 7 + 8 * (7 - 0);
+set a = (658 + 13) * 5 - 1974 % 1 + 9 * (8 - 1);
+set a = (659 + 14) * 6 - 1977 % 2 + 10 * (9 - 2);
+set a = (660 + 15) * 2 - 1980 % 3 + 0 * (10 - 0);
+set a = (661 + 16) * 3 - 1983 % 4 + 1 * (11 - 1);
+set a = (662 + 17) * 4 - 1986 % 5 + 2 * (12 - 2);
+set a = (663 + 1) * 5 - 1989 % 6 + 3 * (0 - 0);
+set a = (664 + 2) * 6 - 1992 % 7 + 4 * (1 - 1);
+set a = (665 + 3) * 2 - 1995 % 1 + 5 * (2 - 2);
+set a = (666 + 4) * 3 - 1998 % 2 + 6 * (3 - 0);
+set a = (667 + 5) * 4 - 2001 % 3 + 7 * (4 - 1);
+set a = (668 + 6) * 5 - 2004 % 4 + 8 * (5 - 2);
+set a = (669 + 7) * 6 - 2007 % 5 + 9 * (6 - 0);
+set a = (670 + 8) * 2 - 2010 % 6 + 10 * (7 - 1);
+set a = (671 + 9) * 3 - 2013 % 7 + 0 * (8 - 2);
+set a = (672 + 10) * 4 - 2016 % 1 + 1 * (9 - 0);
+set a = (673 + 11) * 5 - 2019 % 2 + 2 * (10 - 1);
+set a = (674 + 12) * 6 - 2022 % 3 + 3 * (11 - 2);
+set a = (675 + 13) * 2 - 2025 % 4 + 4 * (12 - 0);
+set a = (676 + 14) * 3 - 2028 % 5 + 5 * (0 - 1);
+set a = (677 + 15) * 4 - 2031 % 6 + 6 * (1 - 2);
+set a = (678 + 16) * 5 - 2034 % 7 + 7 * (2 - 0);
+set a = (679 + 17) * 6 - 2037 % 1 + 8 * (3 - 1);
+set a = (680 + 1) * 2 - 2040 % 2 + 9 * (4 - 2);
+set a = (681 + 2) * 3 - 2043 % 3 + 10 * (5 - 0);
+set a = (682 + 3) * 4 - 2046 % 4 + 0 * (6 - 1);
+set a = (683 + 4) * 5 - 2049 % 5 + 1 * (7 - 2);
+set a = (684 + 5) * 6 - 2052 % 6 + 2 * (8 - 0);
+set a = (685 + 6) * 2 - 2055 % 7 + 3 * (9 - 1);
+set a = (686 + 7) * 3 - 2058 % 1 + 4 * (10 - 2);
+set a = (687 + 8) * 4 - 2061 % 2 + 5 * (11 - 0);
+set a = (688 + 9) * 5 - 2064 % 3 + 6 * (12 - 1);
+set a = (689 + 10) * 6 - 2067 % 4 + 7 * (0 - 2);
+set a = (690 + 11) * 2 - 2070 % 5 + 8 * (1 - 0);
+set a = (691 + 12) * 3 - 2073 % 6 + 9 * (2 - 1);
+set a = (692 + 13) * 4 - 2076 % 7 + 10 * (3 - 2);
+set a = (693 + 14) * 5 - 2079 % 1 + 0 * (4 - 0);
+set a = (694 + 15) * 6 - 2082 % 2 + 1 * (5 - 1);
+set a = (695 + 16) * 2 - 2085 % 3 + 2 * (6 - 2);
+set a = (696 + 17) * 3 - 2088 % 4 + 3 * (7 - 0);
+set a = (697 + 1) * 4 - 2091 % 5 + 4 * (8 - 1);
+set a = (698 + 2) * 5 - 2094 % 6 + 5 * (9 - 2);
+set a = (699 + 3) * 6 - 2097 % 7 + 6 * (10 - 0);
+set a = (700 + 4) * 2 - 2100 % 1 + 7 * (11 - 1);
+set a = (701 + 5) * 3 - 2103 % 2 + 8 * (12 - 2);
+set a = (702 + 6) * 4 - 2106 % 3 + 9 * (0 - 0);
+set a = (703 + 7) * 5 - 2109 % 4 + 10 * (1 - 1);
+set a = (704 + 8) * 6 - 2112 % 5 + 0 * (2 - 2);
+set a = (705 + 9) * 2 - 2115 % 6 + 1 * (3 - 0);
+set a = (706 + 10) * 3 - 2118 % 7 + 2 * (4 - 1);
+set a = (707 + 11) * 4 - 2121 % 1 + 3 * (5 - 2);
+set a = (708 + 12) * 5 - 2124 % 2 + 4 * (6 - 0);
+set a = (709 + 13) * 6 - 2127 % 3 + 5 * (7 - 1);
+set a = (710 + 14) * 2 - 2130 % 4 + 6 * (8 - 2);
+set a = (711 + 15) * 3 - 2133 % 5 + 7 * (9 - 0);
+set a = (712 + 16) * 4 - 2136 % 6 + 8 * (10 - 1);
+set a = (713 + 17) * 5 - 2139 % 7 + 9 * (11 - 2);
+set a = (714 + 1) * 6 - 2142 % 1 + 10 * (12 - 0);
+set a = (715 + 2) * 2 - 2145 % 2 + 0 * (0 - 1);
+set a = (716 + 3) * 3 - 2148 % 3 + 1 * (1 - 2);
+set a = (717 + 4) * 4 - 2151 % 4 + 2 * (2 - 0);
+set a = (718 + 5) * 5 - 2154 % 5 + 3 * (3 - 1);
+set a = (719 + 6) * 6 - 2157 % 6 + 4 * (4 - 2);
+set a = (720 + 7) * 2 - 2160 % 7 + 5 * (5 - 0);
+set a = (721 + 8) * 3 - 2163 % 1 + 6 * (6 - 1);
+set a = (722 + 9) * 4 - 2166 % 2 + 7 * (7 - 2);
+set a = (723 + 10) * 5 - 2169 % 3 + 8 * (8 - 0);
+set a = (724 + 11) * 6 - 2172 % 4 + 9 * (9 - 1);
+set a = (725 + 12) * 2 - 2175 % 5 + 10 * (10 - 2);
+set a = (726 + 13) * 3 - 2178 % 6 + 0 * (11 - 0);
+set a = (727 + 14) * 4 - 2181 % 7 + 1 * (12 - 1);
+set a = (728 + 15) * 5 - 2184 % 1 + 2 * (0 - 2);
+set a = (729 + 16) * 6 - 2187 % 2 + 3 * (1 - 0);
+set a = (730 + 17) * 2 - 2190 % 3 + 4 * (2 - 1);
+set a = (731 + 1) * 3 - 2193 % 4 + 5 * (3 - 2);
+set a = (732 + 2) * 4 - 2196 % 5 + 6 * (4 - 0);
+set a = (733 + 3) * 5 - 2199 % 6 + 7 * (5 - 1);
+set a = (734 + 4) * 6 - 2202 % 7 + 8 * (6 - 2);
+set a = (735 + 5) * 2 - 2205 % 1 + 9 * (7 - 0);
+set a = (736 + 6) * 3 - 2208 % 2 + 10 * (8 - 1);
+set a = (737 + 7) * 4 - 2211 % 3 + 0 * (9 - 2);
+set a = (738 + 8) * 5 - 2214 % 4 + 1 * (10 - 0);
+set a = (739 + 9) * 6 - 2217 % 5 + 2 * (11 - 1);
+set a = (740 + 10) * 2 - 2220 % 6 + 3 * (12 - 2);
+set a = (741 + 11) * 3 - 2223 % 7 + 4 * (0 - 0);
+set a = (742 + 12) * 4 - 2226 % 1 + 5 * (1 - 1);
+set a = (743 + 13) * 5 - 2229 % 2 + 6 * (2 - 2);
+set a = (744 + 14) * 6 - 2232 % 3 + 7 * (3 - 0);
+set a = (745 + 15) * 2 - 2235 % 4 + 8 * (4 - 1);
+set a = (746 + 16) * 3 - 2238 % 5 + 9 * (5 - 2);
+set a = (747 + 17) * 4 - 2241 % 6 + 10 * (6 - 0);
+set a = (748 + 1) * 5 - 2244 % 7 + 0 * (7 - 1);
+set a = (749 + 2) * 6 - 2247 % 1 + 1 * (8 - 2);
+set a = (750 + 3) * 2 - 2250 % 2 + 2 * (9 - 0);
+set a = (751 + 4) * 3 - 2253 % 3 + 3 * (10 - 1);
+set a = (752 + 5) * 4 - 2256 % 4 + 4 * (11 - 2);
+set a = (753 + 6) * 5 - 2259 % 5 + 5 * (12 - 0);
+set a = (754 + 7) * 6 - 2262 % 6 + 6 * (0 - 1);
+set a = (755 + 8) * 2 - 2265 % 7 + 7 * (1 - 2);
+set a = (756 + 9) * 3 - 2268 % 1 + 8 * (2 - 0);
+set a = (757 + 10) * 4 - 2271 % 2 + 9 * (3 - 1);
+set a = (758 + 11) * 5 - 2274 % 3 + 10 * (4 - 2);
+set a = (759 + 12) * 6 - 2277 % 4 + 0 * (5 - 0);
+set a = (760 + 13) * 2 - 2280 % 5 + 1 * (6 - 1);
+set a = (761 + 14) * 3 - 2283 % 6 + 2 * (7 - 2);
+set a = (762 + 15) * 4 - 2286 % 7 + 3 * (8 - 0);
+set a = (763 + 16) * 5 - 2289 % 1 + 4 * (9 - 1);
+set a = (764 + 17) * 6 - 2292 % 2 + 5 * (10 - 2);
+set a = (765 + 1) * 2 - 2295 % 3 + 6 * (11 - 0);
+set a = (766 + 2) * 3 - 2298 % 4 + 7 * (12 - 1);
+set a = (767 + 3) * 4 - 2301 % 5 + 8 * (0 - 2);
+set a = (768 + 4) * 5 - 2304 % 6 + 9 * (1 - 0);
+set a = (769 + 5) * 6 - 2307 % 7 + 10 * (2 - 1);
+set a = (770 + 6) * 2 - 2310 % 1 + 0 * (3 - 2);
+set a = (771 + 7) * 3 - 2313 % 2 + 1 * (4 - 0);
+set a = (772 + 8) * 4 - 2316 % 3 + 2 * (5 - 1);
+set a = (773 + 9) * 5 - 2319 % 4 + 3 * (6 - 2);
+set a = (774 + 10) * 6 - 2322 % 5 + 4 * (7 - 0);
+set a = (775 + 11) * 2 - 2325 % 6 + 5 * (8 - 1);
+set a = (776 + 12) * 3 - 2328 % 7 + 6 * (9 - 2);
+set a = (777 + 13) * 4 - 2331 % 1 + 7 * (10 - 0);
+set a = (778 + 14) * 5 - 2334 % 2 + 8 * (11 - 1);
+set a = (779 + 15) * 6 - 2337 % 3 + 9 * (12 - 2);
+set a = (780 + 16) * 2 - 2340 % 4 + 10 * (0 - 0);
+set a = (781 + 17) * 3 - 2343 % 5 + 0 * (1 - 1);
+set a = (782 + 1) * 4 - 2346 % 6 + 1 * (2 - 2);
+set a = (783 + 2) * 5 - 2349 % 7 + 2 * (3 - 0);
+set a = (784 + 3) * 6 - 2352 % 1 + 3 * (4 - 1);
+set a = (785 + 4) * 2 - 2355 % 2 + 4 * (5 - 2);
+set a = (786 + 5) * 3 - 2358 % 3 + 5 * (6 - 0);
+set a = (787 + 6) * 4 - 2361 % 4 + 6 * (7 - 1);
+set a = (788 + 7) * 5 - 2364 % 5 + 7 * (8 - 2);
+set a = (789 + 8) * 6 - 2367 % 6 + 8 * (9 - 0);
+set a = (790 + 9) * 2 - 2370 % 7 + 9 * (10 - 1);
+set a = (791 + 10) * 3 - 2373 % 1 + 10 * (11 - 2);
+set a = (792 + 11) * 4 - 2376 % 2 + 0 * (12 - 0);
+set a = (793 + 12) * 5 - 2379 % 3 + 1 * (0 - 1);
+set a = (794 + 13) * 6 - 2382 % 4 + 2 * (1 - 2);
+set a = (795 + 14) * 2 - 2385 % 5 + 3 * (2 - 0);
+set a = (796 + 15) * 3 - 2388 % 6 + 4 * (3 - 1);
+set a = (797 + 16) * 4 - 2391 % 7 + 5 * (4 - 2);
+set a = (798 + 17) * 5 - 2394 % 1 + 6 * (5 - 0);
+set a = (799 + 1) * 6 - 2397 % 2 + 7 * (6 - 1);
+set a = (800 + 2) * 2 - 2400 % 3 + 8 * (7 - 2);
+set a = (801 + 3) * 3 - 2403 % 4 + 9 * (8 - 0);
+set a = (802 + 4) * 4 - 2406 % 5 + 10 * (9 - 1);
+set a = (803 + 5) * 5 - 2409 % 6 + 0 * (10 - 2);
+set a = (804 + 6) * 6 - 2412 % 7 + 1 * (11 - 0);
+set a = (805 + 7) * 2 - 2415 % 1 + 2 * (12 - 1);
+set a = (806 + 8) * 3 - 2418 % 2 + 3 * (0 - 2);
+set a = (807 + 9) * 4 - 2421 % 3 + 4 * (1 - 0);
+set a = (808 + 10) * 5 - 2424 % 4 + 5 * (2 - 1);
+set a = (809 + 11) * 6 - 2427 % 5 + 6 * (3 - 2);
+set a = (810 + 12) * 2 - 2430 % 6 + 7 * (4 - 0);
+set a = (811 + 13) * 3 - 2433 % 7 + 8 * (5 - 1);
+set a = (812 + 14) * 4 - 2436 % 1 + 9 * (6 - 2);
+set a = (813 + 15) * 5 - 2439 % 2 + 10 * (7 - 0);
+set a = (814 + 16) * 6 - 2442 % 3 + 0 * (8 - 1);
+set a = (815 + 17) * 2 - 2445 % 4 + 1 * (9 - 2);
+set a = (816 + 1) * 3 - 2448 % 5 + 2 * (10 - 0);
+set a = (817 + 2) * 4 - 2451 % 6 + 3 * (11 - 1);
+set a = (818 + 3) * 5 - 2454 % 7 + 4 * (12 - 2);
+set a = (819 + 4) * 6 - 2457 % 1 + 5 * (0 - 0);
+set a = (820 + 5) * 2 - 2460 % 2 + 6 * (1 - 1);
+set a = (821 + 6) * 3 - 2463 % 3 + 7 * (2 - 2);
+set a = (822 + 7) * 4 - 2466 % 4 + 8 * (3 - 0);
+set a = (823 + 8) * 5 - 2469 % 5 + 9 * (4 - 1);
+set a = (824 + 9) * 6 - 2472 % 6 + 10 * (5 - 2);
+set a = (825 + 10) * 2 - 2475 % 7 + 0 * (6 - 0);
+set a = (826 + 11) * 3 - 2478 % 1 + 1 * (7 - 1);
+set a = (827 + 12) * 4 - 2481 % 2 + 2 * (8 - 2);
+set a = (828 + 13) * 5 - 2484 % 3 + 3 * (9 - 0);
+set a = (829 + 14) * 6 - 2487 % 4 + 4 * (10 - 1);
+set a = (830 + 15) * 2 - 2490 % 5 + 5 * (11 - 2);
+set a = (831 + 16) * 3 - 2493 % 6 + 6 * (12 - 0);
+set a = (832 + 17) * 4 - 2496 % 7 + 7 * (0 - 1);
+set a = (833 + 1) * 5 - 2499 % 1 + 8 * (1 - 2);
+set a = (834 + 2) * 6 - 2502 % 2 + 9 * (2 - 0);
+set a = (835 + 3) * 2 - 2505 % 3 + 10 * (3 - 1);
+set a = (836 + 4) * 3 - 2508 % 4 + 0 * (4 - 2);
+set a = (837 + 5) * 4 - 2511 % 5 + 1 * (5 - 0);
+set a = (838 + 6) * 5 - 2514 % 6 + 2 * (6 - 1);
+set a = (839 + 7) * 6 - 2517 % 7 + 3 * (7 - 2);
+set a = (840 + 8) * 2 - 2520 % 1 + 4 * (8 - 0);
+set a = (841 + 9) * 3 - 2523 % 2 + 5 * (9 - 1);
+set a = (842 + 10) * 4 - 2526 % 3 + 6 * (10 - 2);
+set a = (843 + 11) * 5 - 2529 % 4 + 7 * (11 - 0);
+set a = (844 + 12) * 6 - 2532 % 5 + 8 * (12 - 1);
+set a = (845 + 13) * 2 - 2535 % 6 + 9 * (0 - 2);
+set a = (846 + 14) * 3 - 2538 % 7 + 10 * (1 - 0);
+set a = (847 + 15) * 4 - 2541 % 1 + 0 * (2 - 1);
+set a = (848 + 16) * 5 - 2544 % 2 + 1 * (3 - 2);
+set a = (849 + 17) * 6 - 2547 % 3 + 2 * (4 - 0);
+set a = (850 + 1) * 2 - 2550 % 4 + 3 * (5 - 1);
+set a = (851 + 2) * 3 - 2553 % 5 + 4 * (6 - 2);
+set a = (852 + 3) * 4 - 2556 % 6 + 5 * (7 - 0);
+set a = (853 + 4) * 5 - 2559 % 7 + 6 * (8 - 1);
+set a = (854 + 5) * 6 - 2562 % 1 + 7 * (9 - 2);
+set a = (855 + 6) * 2 - 2565 % 2 + 8 * (10 - 0);
+set a = (856 + 7) * 3 - 2568 % 3 + 9 * (11 - 1);
+set a = (857 + 8) * 4 - 2571 % 4 + 10 * (12 - 2);
+set a = (858 + 9) * 5 - 2574 % 5 + 0 * (0 - 0);
+set a = (859 + 10) * 6 - 2577 % 6 + 1 * (1 - 1);
+set a = (860 + 11) * 2 - 2580 % 7 + 2 * (2 - 2);
+set a = (861 + 12) * 3 - 2583 % 1 + 3 * (3 - 0);
+set a = (862 + 13) * 4 - 2586 % 2 + 4 * (4 - 1);
+set a = (863 + 14) * 5 - 2589 % 3 + 5 * (5 - 2);
+set a = (864 + 15) * 6 - 2592 % 4 + 6 * (6 - 0);
+set a = (865 + 16) * 2 - 2595 % 5 + 7 * (7 - 1);
+set a = (866 + 17) * 3 - 2598 % 6 + 8 * (8 - 2);
+set a = (867 + 1) * 4 - 2601 % 7 + 9 * (9 - 0);
+set a = (868 + 2) * 5 - 2604 % 1 + 10 * (10 - 1);
+set a = (869 + 3) * 6 - 2607 % 2 + 0 * (11 - 2);
+set a = (870 + 4) * 2 - 2610 % 3 + 1 * (12 - 0);
+set a = (871 + 5) * 3 - 2613 % 4 + 2 * (0 - 1);
+set a = (872 + 6) * 4 - 2616 % 5 + 3 * (1 - 2);
+set a = (873 + 7) * 5 - 2619 % 6 + 4 * (2 - 0);
+set a = (874 + 8) * 6 - 2622 % 7 + 5 * (3 - 1);
+set a = (875 + 9) * 2 - 2625 % 1 + 6 * (4 - 2);
+set a = (876 + 10) * 3 - 2628 % 2 + 7 * (5 - 0);
+set a = (877 + 11) * 4 - 2631 % 3 + 8 * (6 - 1);
+set a = (878 + 12) * 5 - 2634 % 4 + 9 * (7 - 2);
+set a = (879 + 13) * 6 - 2637 % 5 + 10 * (8 - 0);
+set a = (880 + 14) * 2 - 2640 % 6 + 0 * (9 - 1);
+set a = (881 + 15) * 3 - 2643 % 7 + 1 * (10 - 2);
+set a = (882 + 16) * 4 - 2646 % 1 + 2 * (11 - 0);
+set a = (883 + 17) * 5 - 2649 % 2 + 3 * (12 - 1);
+set a = (884 + 1) * 6 - 2652 % 3 + 4 * (0 - 2);
+set a = (885 + 2) * 2 - 2655 % 4 + 5 * (1 - 0);
+set a = (886 + 3) * 3 - 2658 % 5 + 6 * (2 - 1);
+set a = (887 + 4) * 4 - 2661 % 6 + 7 * (3 - 2);
+set a = (888 + 5) * 5 - 2664 % 7 + 8 * (4 - 0);
+set a = (889 + 6) * 6 - 2667 % 1 + 9 * (5 - 1);
+set a = (890 + 7) * 2 - 2670 % 2 + 10 * (6 - 2);
+set a = (891 + 8) * 3 - 2673 % 3 + 0 * (7 - 0);
+set a = (892 + 9) * 4 - 2676 % 4 + 1 * (8 - 1);
+set a = (893 + 10) * 5 - 2679 % 5 + 2 * (9 - 2);
+set a = (894 + 11) * 6 - 2682 % 6 + 3 * (10 - 0);
+set a = (895 + 12) * 2 - 2685 % 7 + 4 * (11 - 1);
+set a = (896 + 13) * 3 - 2688 % 1 + 5 * (12 - 2);
+set a = (897 + 14) * 4 - 2691 % 2 + 6 * (0 - 0);
+set a = (898 + 15) * 5 - 2694 % 3 + 7 * (1 - 1);
+set a = (899 + 16) * 6 - 2697 % 4 + 8 * (2 - 2);
+set a = (900 + 17) * 2 - 2700 % 5 + 9 * (3 - 0);
+set a = (901 + 1) * 3 - 2703 % 6 + 10 * (4 - 1);
+set a = (902 + 2) * 4 - 2706 % 7 + 0 * (5 - 2);
+set a = (903 + 3) * 5 - 2709 % 1 + 1 * (6 - 0);
+set a = (904 + 4) * 6 - 2712 % 2 + 2 * (7 - 1);
+set a = (905 + 5) * 2 - 2715 % 3 + 3 * (8 - 2);
+set a = (906 + 6) * 3 - 2718 % 4 + 4 * (9 - 0);
+set a = (907 + 7) * 4 - 2721 % 5 + 5 * (10 - 1);
+set a = (908 + 8) * 5 - 2724 % 6 + 6 * (11 - 2);
+set a = (909 + 9) * 6 - 2727 % 7 + 7 * (12 - 0);
+set a = (910 + 10) * 2 - 2730 % 1 + 8 * (0 - 1);
+set a = (911 + 11) * 3 - 2733 % 2 + 9 * (1 - 2);
+set a = (912 + 12) * 4 - 2736 % 3 + 10 * (2 - 0);
+set a = (913 + 13) * 5 - 2739 % 4 + 0 * (3 - 1);
+set a = (914 + 14) * 6 - 2742 % 5 + 1 * (4 - 2);
+set a = (915 + 15) * 2 - 2745 % 6 + 2 * (5 - 0);
+set a = (916 + 16) * 3 - 2748 % 7 + 3 * (6 - 1);
+set a = (917 + 17) * 4 - 2751 % 1 + 4 * (7 - 2);
+set a = (918 + 1) * 5 - 2754 % 2 + 5 * (8 - 0);
+set a = (919 + 2) * 6 - 2757 % 3 + 6 * (9 - 1);
+set a = (920 + 3) * 2 - 2760 % 4 + 7 * (10 - 2);
+set a = (921 + 4) * 3 - 2763 % 5 + 8 * (11 - 0);
+set a = (922 + 5) * 4 - 2766 % 6 + 9 * (12 - 1);
+set a = (923 + 6) * 5 - 2769 % 7 + 10 * (0 - 2);
+set a = (924 + 7) * 6 - 2772 % 1 + 0 * (1 - 0);
+set a = (925 + 8) * 2 - 2775 % 2 + 1 * (2 - 1);
+set a = (926 + 9) * 3 - 2778 % 3 + 2 * (3 - 2);
+set a = (927 + 10) * 4 - 2781 % 4 + 3 * (4 - 0);
+set a = (928 + 11) * 5 - 2784 % 5 + 4 * (5 - 1);
+set a = (929 + 12) * 6 - 2787 % 6 + 5 * (6 - 2);
+set a = (930 + 13) * 2 - 2790 % 7 + 6 * (7 - 0);
+set a = (931 + 14) * 3 - 2793 % 1 + 7 * (8 - 1);
+set a = (932 + 15) * 4 - 2796 % 2 + 8 * (9 - 2);
+set a = (933 + 16) * 5 - 2799 % 3 + 9 * (10 - 0);
+set a = (934 + 17) * 6 - 2802 % 4 + 10 * (11 - 1);
+set a = (935 + 1) * 2 - 2805 % 5 + 0 * (12 - 2);
+set a = (936 + 2) * 3 - 2808 % 6 + 1 * (0 - 0);
+set a = (937 + 3) * 4 - 2811 % 7 + 2 * (1 - 1);
+set a = (938 + 4) * 5 - 2814 % 1 + 3 * (2 - 2);
+set a = (939 + 5) * 6 - 2817 % 2 + 4 * (3 - 0);
+set a = (940 + 6) * 2 - 2820 % 3 + 5 * (4 - 1);
+set a = (941 + 7) * 3 - 2823 % 4 + 6 * (5 - 2);
+set a = (942 + 8) * 4 - 2826 % 5 + 7 * (6 - 0);
+set a = (943 + 9) * 5 - 2829 % 6 + 8 * (7 - 1);
+set a = (944 + 10) * 6 - 2832 % 7 + 9 * (8 - 2);
+set a = (945 + 11) * 2 - 2835 % 1 + 10 * (9 - 0);
+set a = (946 + 12) * 3 - 2838 % 2 + 0 * (10 - 1);
+set a = (947 + 13) * 4 - 2841 % 3 + 1 * (11 - 2);
+set a = (948 + 14) * 5 - 2844 % 4 + 2 * (12 - 0);
+set a = (949 + 15) * 6 - 2847 % 5 + 3 * (0 - 1);
+set a = (950 + 16) * 2 - 2850 % 6 + 4 * (1 - 2);
+set a = (951 + 17) * 3 - 2853 % 7 + 5 * (2 - 0);
+set a = (952 + 1) * 4 - 2856 % 1 + 6 * (3 - 1);
+set a = (953 + 2) * 5 - 2859 % 2 + 7 * (4 - 2);
+set a = (954 + 3) * 6 - 2862 % 3 + 8 * (5 - 0);
+set a = (955 + 4) * 2 - 2865 % 4 + 9 * (6 - 1);
+set a = (956 + 5) * 3 - 2868 % 5 + 10 * (7 - 2);
+set a = (957 + 6) * 4 - 2871 % 6 + 0 * (8 - 0);
+set a = (958 + 7) * 5 - 2874 % 7 + 1 * (9 - 1);
+set a = (959 + 8) * 6 - 2877 % 1 + 2 * (10 - 2);
+set a = (960 + 9) * 2 - 2880 % 2 + 3 * (11 - 0);
+set a = (961 + 10) * 3 - 2883 % 3 + 4 * (12 - 1);
+set a = (962 + 11) * 4 - 2886 % 4 + 5 * (0 - 2);
+set a = (963 + 12) * 5 - 2889 % 5 + 6 * (1 - 0);
+set a = (964 + 13) * 6 - 2892 % 6 + 7 * (2 - 1);
+set a = (965 + 14) * 2 - 2895 % 7 + 8 * (3 - 2);
+set a = (966 + 15) * 3 - 2898 % 1 + 9 * (4 - 0);
+set a = (967 + 16) * 4 - 2901 % 2 + 10 * (5 - 1);
+set a = (968 + 17) * 5 - 2904 % 3 + 0 * (6 - 2);
+set a = (969 + 1) * 6 - 2907 % 4 + 1 * (7 - 0);
+set a = (970 + 2) * 2 - 2910 % 5 + 2 * (8 - 1);
+set a = (971 + 3) * 3 - 2913 % 6 + 3 * (9 - 2);
+set a = (972 + 4) * 4 - 2916 % 7 + 4 * (10 - 0);
+set a = (973 + 5) * 5 - 2919 % 1 + 5 * (11 - 1);
+set a = (974 + 6) * 6 - 2922 % 2 + 6 * (12 - 2);
+set a = (975 + 7) * 2 - 2925 % 3 + 7 * (0 - 0);
+set a = (976 + 8) * 3 - 2928 % 4 + 8 * (1 - 1);
+set a = (977 + 9) * 4 - 2931 % 5 + 9 * (2 - 2);
+set a = (978 + 10) * 5 - 2934 % 6 + 10 * (3 - 0);
+set a = (979 + 11) * 6 - 2937 % 7 + 0 * (4 - 1);
+set a = (980 + 12) * 2 - 2940 % 1 + 1 * (5 - 2);
+set a = (981 + 13) * 3 - 2943 % 2 + 2 * (6 - 0);
+set a = (982 + 14) * 4 - 2946 % 3 + 3 * (7 - 1);
+set a = (983 + 15) * 5 - 2949 % 4 + 4 * (8 - 2);
+set a = (984 + 16) * 6 - 2952 % 5 + 5 * (9 - 0);
+set a = (985 + 17) * 2 - 2955 % 6 + 6 * (10 - 1);
+set a = (986 + 1) * 3 - 2958 % 7 + 7 * (11 - 2);
+set a = (987 + 2) * 4 - 2961 % 1 + 8 * (12 - 0);
+set a = (988 + 3) * 5 - 2964 % 2 + 9 * (0 - 1);
+set a = (989 + 4) * 6 - 2967 % 3 + 10 * (1 - 2);
+set a = (990 + 5) * 2 - 2970 % 4 + 0 * (2 - 0);
+set a = (991 + 6) * 3 - 2973 % 5 + 1 * (3 - 1);
+set a = (992 + 7) * 4 - 2976 % 6 + 2 * (4 - 2);
+set a = (993 + 8) * 5 - 2979 % 7 + 3 * (5 - 0);
+set a = (994 + 9) * 6 - 2982 % 1 + 4 * (6 - 1);
+set a = (995 + 10) * 2 - 2985 % 2 + 5 * (7 - 2);
+set a = (996 + 11) * 3 - 2988 % 3 + 6 * (8 - 0);
+set a = (997 + 12) * 4 - 2991 % 4 + 7 * (9 - 1);
+set a = (998 + 13) * 5 - 2994 % 5 + 8 * (10 - 2);
+set a = (999 + 14) * 6 - 2997 % 6 + 9 * (11 - 0);
+set a = (1000 + 15) * 2 - 3000 % 7 + 10 * (12 - 1);
+set a = (1001 + 16) * 3 - 3003 % 1 + 0 * (0 - 2);
+set a = (1002 + 17) * 4 - 3006 % 2 + 1 * (1 - 0);
+set a = (1003 + 1) * 5 - 3009 % 3 + 2 * (2 - 1);
+set a = (1004 + 2) * 6 - 3012 % 4 + 3 * (3 - 2);
+set a = (1005 + 3) * 2 - 3015 % 5 + 4 * (4 - 0);
+set a = (1006 + 4) * 3 - 3018 % 6 + 5 * (5 - 1);
+set a = (1007 + 5) * 4 - 3021 % 7 + 6 * (6 - 2);
+set a = (1008 + 6) * 5 - 3024 % 1 + 7 * (7 - 0);
+set a = (1009 + 7) * 6 - 3027 % 2 + 8 * (8 - 1);
+set a = (1010 + 8) * 2 - 3030 % 3 + 9 * (9 - 2);
+set a = (1011 + 9) * 3 - 3033 % 4 + 10 * (10 - 0);
+set a = (1012 + 10) * 4 - 3036 % 5 + 0 * (11 - 1);
+set a = (1013 + 11) * 5 - 3039 % 6 + 1 * (12 - 2);
+set a = (1014 + 12) * 6 - 3042 % 7 + 2 * (0 - 0);
+set a = (1015 + 13) * 2 - 3045 % 1 + 3 * (1 - 1);
+set a = (1016 + 14) * 3 - 3048 % 2 + 4 * (2 - 2);
+set a = (1017 + 15) * 4 - 3051 % 3 + 5 * (3 - 0);
+set a = (1018 + 16) * 5 - 3054 % 4 + 6 * (4 - 1);
+set a = (1019 + 17) * 6 - 3057 % 5 + 7 * (5 - 2);
+set a = (1020 + 1) * 2 - 3060 % 6 + 8 * (6 - 0);
+set a = (1021 + 2) * 3 - 3063 % 7 + 9 * (7 - 1);
+set a = (1022 + 3) * 4 - 3066 % 1 + 10 * (8 - 2);
+set a = (1023 + 4) * 5 - 3069 % 2 + 0 * (9 - 0);
+set a = (1024 + 5) * 6 - 3072 % 3 + 1 * (10 - 1);
+set a = (1025 + 6) * 2 - 3075 % 4 + 2 * (11 - 2);
+set a = (1026 + 7) * 3 - 3078 % 5 + 3 * (12 - 0);
+set a = (1027 + 8) * 4 - 3081 % 6 + 4 * (0 - 1);
+set a = (1028 + 9) * 5 - 3084 % 7 + 5 * (1 - 2);
+set a = (1029 + 10) * 6 - 3087 % 1 + 6 * (2 - 0);
+set a = (1030 + 11) * 2 - 3090 % 2 + 7 * (3 - 1);
+set a = (1031 + 12) * 3 - 3093 % 3 + 8 * (4 - 2);
+set a = (1032 + 13) * 4 - 3096 % 4 + 9 * (5 - 0);
+set a = (1033 + 14) * 5 - 3099 % 5 + 10 * (6 - 1);
+set a = (1034 + 15) * 6 - 3102 % 6 + 0 * (7 - 2);
+set a = (1035 + 16) * 2 - 3105 % 7 + 1 * (8 - 0);
+set a = (1036 + 17) * 3 - 3108 % 1 + 2 * (9 - 1);
+set a = (1037 + 1) * 4 - 3111 % 2 + 3 * (10 - 2);
+set a = (1038 + 2) * 5 - 3114 % 3 + 4 * (11 - 0);
+set a = (1039 + 3) * 6 - 3117 % 4 + 5 * (12 - 1);
+set a = (1040 + 4) * 2 - 3120 % 5 + 6 * (0 - 2);
+set a = (1041 + 5) * 3 - 3123 % 6 + 7 * (1 - 0);
+set a = (1042 + 6) * 4 - 3126 % 7 + 8 * (2 - 1);
+set a = (1043 + 7) * 5 - 3129 % 1 + 9 * (3 - 2);
+set a = (1044 + 8) * 6 - 3132 % 2 + 10 * (4 - 0);
+set a = (1045 + 9) * 2 - 3135 % 3 + 0 * (5 - 1);
+set a = (1046 + 10) * 3 - 3138 % 4 + 1 * (6 - 2);
+set a = (1047 + 11) * 4 - 3141 % 5 + 2 * (7 - 0);
+set a = (1048 + 12) * 5 - 3144 % 6 + 3 * (8 - 1);
+set a = (1049 + 13) * 6 - 3147 % 7 + 4 * (9 - 2);
+set a = (1050 + 14) * 2 - 3150 % 1 + 5 * (10 - 0);
+set a = (1051 + 15) * 3 - 3153 % 2 + 6 * (11 - 1);
+set a = (1052 + 16) * 4 - 3156 % 3 + 7 * (12 - 2);
+set a = (1053 + 17) * 5 - 3159 % 4 + 8 * (0 - 0);
+set a = (1054 + 1) * 6 - 3162 % 5 + 9 * (1 - 1);
+set a = (1055 + 2) * 2 - 3165 % 6 + 10 * (2 - 2);
+set a = (1056 + 3) * 3 - 3168 % 7 + 0 * (3 - 0);
+set a = (1057 + 4) * 4 - 3171 % 1 + 1 * (4 - 1);
+set a = (1058 + 5) * 5 - 3174 % 2 + 2 * (5 - 2);
+set a = (1059 + 6) * 6 - 3177 % 3 + 3 * (6 - 0);
+set a = (1060 + 7) * 2 - 3180 % 4 + 4 * (7 - 1);
+set a = (1061 + 8) * 3 - 3183 % 5 + 5 * (8 - 2);
+set a = (1062 + 9) * 4 - 3186 % 6 + 6 * (9 - 0);
+set a = (1063 + 10) * 5 - 3189 % 7 + 7 * (10 - 1);
+set a = (1064 + 11) * 6 - 3192 % 1 + 8 * (11 - 2);
+set a = (1065 + 12) * 2 - 3195 % 2 + 9 * (12 - 0);
+set a = (1066 + 13) * 3 - 3198 % 3 + 10 * (0 - 1);
+set a = (1067 + 14) * 4 - 3201 % 4 + 0 * (1 - 2);
+set a = (1068 + 15) * 5 - 3204 % 5 + 1 * (2 - 0);
+set a = (1069 + 16) * 6 - 3207 % 6 + 2 * (3 - 1);
+set a = (1070 + 17) * 2 - 3210 % 7 + 3 * (4 - 2);
+set a = (1071 + 1) * 3 - 3213 % 1 + 4 * (5 - 0);
+set a = (1072 + 2) * 4 - 3216 % 2 + 5 * (6 - 1);
+set a = (1073 + 3) * 5 - 3219 % 3 + 6 * (7 - 2);
+set a = (1074 + 4) * 6 - 3222 % 4 + 7 * (8 - 0);
+set a = (1075 + 5) * 2 - 3225 % 5 + 8 * (9 - 1);
+set a = (1076 + 6) * 3 - 3228 % 6 + 9 * (10 - 2);
+set a = (1077 + 7) * 4 - 3231 % 7 + 10 * (11 - 0);
+set a = (1078 + 8) * 5 - 3234 % 1 + 0 * (12 - 1);
+set a = (1079 + 9) * 6 - 3237 % 2 + 1 * (0 - 2);
+set a = (1080 + 10) * 2 - 3240 % 3 + 2 * (1 - 0);
+set a = (1081 + 11) * 3 - 3243 % 4 + 3 * (2 - 1);
+set a = (1082 + 12) * 4 - 3246 % 5 + 4 * (3 - 2);
+set a = (1083 + 13) * 5 - 3249 % 6 + 5 * (4 - 0);
+set a = (1084 + 14) * 6 - 3252 % 7 + 6 * (5 - 1);
+set a = (1085 + 15) * 2 - 3255 % 1 + 7 * (6 - 2);
+set a = (1086 + 16) * 3 - 3258 % 2 + 8 * (7 - 0);
+set a = (1087 + 17) * 4 - 3261 % 3 + 9 * (8 - 1);
+set a = (1088 + 1) * 5 - 3264 % 4 + 10 * (9 - 2);
+set a = (1089 + 2) * 6 - 3267 % 5 + 0 * (10 - 0);
+set a = (1090 + 3) * 2 - 3270 % 6 + 1 * (11 - 1);
+set a = (1091 + 4) * 3 - 3273 % 7 + 2 * (12 - 2);
+set a = (1092 + 5) * 4 - 3276 % 1 + 3 * (0 - 0);
+set a = (1093 + 6) * 5 - 3279 % 2 + 4 * (1 - 1);
+set a = (1094 + 7) * 6 - 3282 % 3 + 5 * (2 - 2);
+set a = (1095 + 8) * 2 - 3285 % 4 + 6 * (3 - 0);
+set a = (1096 + 9) * 3 - 3288 % 5 + 7 * (4 - 1);
+set a = (1097 + 10) * 4 - 3291 % 6 + 8 * (5 - 2);
+set a = (1098 + 11) * 5 - 3294 % 7 + 9 * (6 - 0);
+set a = (1099 + 12) * 6 - 3297 % 1 + 10 * (7 - 1);
+set a = (1100 + 13) * 2 - 3300 % 2 + 0 * (8 - 2);
+set a = (1101 + 14) * 3 - 3303 % 3 + 1 * (9 - 0);
+set a = (1102 + 15) * 4 - 3306 % 4 + 2 * (10 - 1);
+set a = (1103 + 16) * 5 - 3309 % 5 + 3 * (11 - 2);
+set a = (1104 + 17) * 6 - 3312 % 6 + 4 * (12 - 0);
+set a = (1105 + 1) * 2 - 3315 % 7 + 5 * (0 - 1);
+set a = (1106 + 2) * 3 - 3318 % 1 + 6 * (1 - 2);
+set a = (1107 + 3) * 4 - 3321 % 2 + 7 * (2 - 0);
+set a = (1108 + 4) * 5 - 3324 % 3 + 8 * (3 - 1);
+set a = (1109 + 5) * 6 - 3327 % 4 + 9 * (4 - 2);
+set a = (1110 + 6) * 2 - 3330 % 5 + 10 * (5 - 0);
+set a = (1111 + 7) * 3 - 3333 % 6 + 0 * (6 - 1);
+set a = (1112 + 8) * 4 - 3336 % 7 + 1 * (7 - 2);
+set a = (1113 + 9) * 5 - 3339 % 1 + 2 * (8 - 0);
+set a = (1114 + 10) * 6 - 3342 % 2 + 3 * (9 - 1);
+set a = (1115 + 11) * 2 - 3345 % 3 + 4 * (10 - 2);
+set a = (1116 + 12) * 3 - 3348 % 4 + 5 * (11 - 0);
+set a = (1117 + 13) * 4 - 3351 % 5 + 6 * (12 - 1);
+set a = (1118 + 14) * 5 - 3354 % 6 + 7 * (0 - 2);
+set a = (1119 + 15) * 6 - 3357 % 7 + 8 * (1 - 0);
+set a = (1120 + 16) * 2 - 3360 % 1 + 9 * (2 - 1);
+set a = (1121 + 17) * 3 - 3363 % 2 + 10 * (3 - 2);
+set a = (1122 + 1) * 4 - 3366 % 3 + 0 * (4 - 0);
+set a = (1123 + 2) * 5 - 3369 % 4 + 1 * (5 - 1);
+set a = (1124 + 3) * 6 - 3372 % 5 + 2 * (6 - 2);
+set a = (1125 + 4) * 2 - 3375 % 6 + 3 * (7 - 0);
+set a = (1126 + 5) * 3 - 3378 % 7 + 4 * (8 - 1);
+set a = (1127 + 6) * 4 - 3381 % 1 + 5 * (9 - 2);
+set a = (1128 + 7) * 5 - 3384 % 2 + 6 * (10 - 0);
+set a = (1129 + 8) * 6 - 3387 % 3 + 7 * (11 - 1);
+set a = (1130 + 9) * 2 - 3390 % 4 + 8 * (12 - 2);
+set a = (1131 + 10) * 3 - 3393 % 5 + 9 * (0 - 0);
+set a = (1132 + 11) * 4 - 3396 % 6 + 10 * (1 - 1);
+set a = (1133 + 12) * 5 - 3399 % 7 + 0 * (2 - 2);
+set a = (1134 + 13) * 6 - 3402 % 1 + 1 * (3 - 0);
+set a = (1135 + 14) * 2 - 3405 % 2 + 2 * (4 - 1);
+set a = (1136 + 15) * 3 - 3408 % 3 + 3 * (5 - 2);
+set a = (1137 + 16) * 4 - 3411 % 4 + 4 * (6 - 0);
+set a = (1138 + 17) * 5 - 3414 % 5 + 5 * (7 - 1);
+set a = (1139 + 1) * 6 - 3417 % 6 + 6 * (8 - 2);
+set a = (1140 + 2) * 2 - 3420 % 7 + 7 * (9 - 0);
+set a = (1141 + 3) * 3 - 3423 % 1 + 8 * (10 - 1);
+set a = (1142 + 4) * 4 - 3426 % 2 + 9 * (11 - 2);
+set a = (1143 + 5) * 5 - 3429 % 3 + 10 * (12 - 0);
+set a = (1144 + 6) * 6 - 3432 % 4 + 0 * (0 - 1);
+set a = (1145 + 7) * 2 - 3435 % 5 + 1 * (1 - 2);
+set a = (1146 + 8) * 3 - 3438 % 6 + 2 * (2 - 0);
+set a = (1147 + 9) * 4 - 3441 % 7 + 3 * (3 - 1);
+set a = (1148 + 10) * 5 - 3444 % 1 + 4 * (4 - 2);
+set a = (1149 + 11) * 6 - 3447 % 2 + 5 * (5 - 0);
+set a = (1150 + 12) * 2 - 3450 % 3 + 6 * (6 - 1);
+set a = (1151 + 13) * 3 - 3453 % 4 + 7 * (7 - 2);
+set a = (1152 + 14) * 4 - 3456 % 5 + 8 * (8 - 0);
+set a = (1153 + 15) * 5 - 3459 % 6 + 9 * (9 - 1);
+set a = (1154 + 16) * 6 - 3462 % 7 + 10 * (10 - 2);
+set a = (1155 + 17) * 2 - 3465 % 1 + 0 * (11 - 0);
+set a = (1156 + 1) * 3 - 3468 % 2 + 1 * (12 - 1);
+set a = (1157 + 2) * 4 - 3471 % 3 + 2 * (0 - 2);
+set a = (1158 + 3) * 5 - 3474 % 4 + 3 * (1 - 0);
+set a = (1159 + 4) * 6 - 3477 % 5 + 4 * (2 - 1);
+set a = (1160 + 5) * 2 - 3480 % 6 + 5 * (3 - 2);
+set a = (1161 + 6) * 3 - 3483 % 7 + 6 * (4 - 0);
+set a = (1162 + 7) * 4 - 3486 % 1 + 7 * (5 - 1);
+set a = (1163 + 8) * 5 - 3489 % 2 + 8 * (6 - 2);
+set a = (1164 + 9) * 6 - 3492 % 3 + 9 * (7 - 0);
+set a = (1165 + 10) * 2 - 3495 % 4 + 10 * (8 - 1);
+set a = (1166 + 11) * 3 - 3498 % 5 + 0 * (9 - 2);
+set a = (1167 + 12) * 4 - 3501 % 6 + 1 * (10 - 0);
+set a = (1168 + 13) * 5 - 3504 % 7 + 2 * (11 - 1);
+set a = (1169 + 14) * 6 - 3507 % 1 + 3 * (12 - 2);
+set a = (1170 + 15) * 2 - 3510 % 2 + 4 * (0 - 0);
+set a = (1171 + 16) * 3 - 3513 % 3 + 5 * (1 - 1);
+set a = (1172 + 17) * 4 - 3516 % 4 + 6 * (2 - 2);
+set a = (1173 + 1) * 5 - 3519 % 5 + 7 * (3 - 0);
+set a = (1174 + 2) * 6 - 3522 % 6 + 8 * (4 - 1);
+set a = (1175 + 3) * 2 - 3525 % 7 + 9 * (5 - 2);
+set a = (1176 + 4) * 3 - 3528 % 1 + 10 * (6 - 0);
+set a = (1177 + 5) * 4 - 3531 % 2 + 0 * (7 - 1);
+set a = (1178 + 6) * 5 - 3534 % 3 + 1 * (8 - 2);
+set a = (1179 + 7) * 6 - 3537 % 4 + 2 * (9 - 0);
+set a = (1180 + 8) * 2 - 3540 % 5 + 3 * (10 - 1);
+set a = (1181 + 9) * 3 - 3543 % 6 + 4 * (11 - 2);
+set a = (1182 + 10) * 4 - 3546 % 7 + 5 * (12 - 0);
+set a = (1183 + 11) * 5 - 3549 % 1 + 6 * (0 - 1);
+set a = (1184 + 12) * 6 - 3552 % 2 + 7 * (1 - 2);
+set a = (1185 + 13) * 2 - 3555 % 3 + 8 * (2 - 0);
+set a = (1186 + 14) * 3 - 3558 % 4 + 9 * (3 - 1);
+set a = (1187 + 15) * 4 - 3561 % 5 + 10 * (4 - 2);
+set a = (1188 + 16) * 5 - 3564 % 6 + 0 * (5 - 0);
+set a = (1189 + 17) * 6 - 3567 % 7 + 1 * (6 - 1);
+set a = (1190 + 1) * 2 - 3570 % 1 + 2 * (7 - 2);
+set a = (1191 + 2) * 3 - 3573 % 2 + 3 * (8 - 0);
+set a = (1192 + 3) * 4 - 3576 % 3 + 4 * (9 - 1);
+set a = (1193 + 4) * 5 - 3579 % 4 + 5 * (10 - 2);
+set a = (1194 + 5) * 6 - 3582 % 5 + 6 * (11 - 0);
+set a = (1195 + 6) * 2 - 3585 % 6 + 7 * (12 - 1);
+set a = (1196 + 7) * 3 - 3588 % 7 + 8 * (0 - 2);
+set a = (1197 + 8) * 4 - 3591 % 1 + 9 * (1 - 0);
+set a = (1198 + 9) * 5 - 3594 % 2 + 10 * (2 - 1);
+set a = (1199 + 10) * 6 - 3597 % 3 + 0 * (3 - 2);
+set a = (1200 + 11) * 2 - 3600 % 4 + 1 * (4 - 0);
+set a = (1201 + 12) * 3 - 3603 % 5 + 2 * (5 - 1);
+set a = (1202 + 13) * 4 - 3606 % 6 + 3 * (6 - 2);
+set a = (1203 + 14) * 5 - 3609 % 7 + 4 * (7 - 0);
+set a = (1204 + 15) * 6 - 3612 % 1 + 5 * (8 - 1);
+set a = (1205 + 16) * 2 - 3615 % 2 + 6 * (9 - 2);
+set a = (1206 + 17) * 3 - 3618 % 3 + 7 * (10 - 0);
+set a = (1207 + 1) * 4 - 3621 % 4 + 8 * (11 - 1);
+set a = (1208 + 2) * 5 - 3624 % 5 + 9 * (12 - 2);
+set a = (1209 + 3) * 6 - 3627 % 6 + 10 * (0 - 0);
+set a = (1210 + 4) * 2 - 3630 % 7 + 0 * (1 - 1);
+set a = (1211 + 5) * 3 - 3633 % 1 + 1 * (2 - 2);
+set a = (1212 + 6) * 4 - 3636 % 2 + 2 * (3 - 0);
+set a = (1213 + 7) * 5 - 3639 % 3 + 3 * (4 - 1);
+set a = (1214 + 8) * 6 - 3642 % 4 + 4 * (5 - 2);
+set a = (1215 + 9) * 2 - 3645 % 5 + 5 * (6 - 0);
+set a = (1216 + 10) * 3 - 3648 % 6 + 6 * (7 - 1);
+set a = (1217 + 11) * 4 - 3651 % 7 + 7 * (8 - 2);
+set a = (1218 + 12) * 5 - 3654 % 1 + 8 * (9 - 0);
+set a = (1219 + 13) * 6 - 3657 % 2 + 9 * (10 - 1);
+set a = (1220 + 14) * 2 - 3660 % 3 + 10 * (11 - 2);
+set a = (1221 + 15) * 3 - 3663 % 4 + 0 * (12 - 0);
+set a = (1222 + 16) * 4 - 3666 % 5 + 1 * (0 - 1);
+set a = (1223 + 17) * 5 - 3669 % 6 + 2 * (1 - 2);
+set a = (1224 + 1) * 6 - 3672 % 7 + 3 * (2 - 0);
+set a = (1225 + 2) * 2 - 3675 % 1 + 4 * (3 - 1);
+set a = (1226 + 3) * 3 - 3678 % 2 + 5 * (4 - 2);
+set a = (1227 + 4) * 4 - 3681 % 3 + 6 * (5 - 0);
+set a = (1228 + 5) * 5 - 3684 % 4 + 7 * (6 - 1);
+set a = (1229 + 6) * 6 - 3687 % 5 + 8 * (7 - 2);
+set a = (1230 + 7) * 2 - 3690 % 6 + 9 * (8 - 0);
+set a = (1231 + 8) * 3 - 3693 % 7 + 10 * (9 - 1);
+set a = (1232 + 9) * 4 - 3696 % 1 + 0 * (10 - 2);
+set a = (1233 + 10) * 5 - 3699 % 2 + 1 * (11 - 0);
+set a = (1234 + 11) * 6 - 3702 % 3 + 2 * (12 - 1);
+set a = (1235 + 12) * 2 - 3705 % 4 + 3 * (0 - 2);
+set a = (1236 + 13) * 3 - 3708 % 5 + 4 * (1 - 0);
+set a = (1237 + 14) * 4 - 3711 % 6 + 5 * (2 - 1);
+set a = (1238 + 15) * 5 - 3714 % 7 + 6 * (3 - 2);
+set a = (1239 + 16) * 6 - 3717 % 1 + 7 * (4 - 0);
+set a = (1240 + 17) * 2 - 3720 % 2 + 8 * (5 - 1);
+set a = (1241 + 1) * 3 - 3723 % 3 + 9 * (6 - 2);
+set a = (1242 + 2) * 4 - 3726 % 4 + 10 * (7 - 0);
+set a = (1243 + 3) * 5 - 3729 % 5 + 0 * (8 - 1);
+set a = (1244 + 4) * 6 - 3732 % 6 + 1 * (9 - 2);
+set a = (1245 + 5) * 2 - 3735 % 7 + 2 * (10 - 0);
+set a = (1246 + 6) * 3 - 3738 % 1 + 3 * (11 - 1);
+set a = (1247 + 7) * 4 - 3741 % 2 + 4 * (12 - 2);
+set a = (1248 + 8) * 5 - 3744 % 3 + 5 * (0 - 0);
+set a = (1249 + 9) * 6 - 3747 % 4 + 6 * (1 - 1);
+set a = (1250 + 10) * 2 - 3750 % 5 + 7 * (2 - 2);
+set a = (1251 + 11) * 3 - 3753 % 6 + 8 * (3 - 0);
+set a = (1252 + 12) * 4 - 3756 % 7 + 9 * (4 - 1);
+set a = (1253 + 13) * 5 - 3759 % 1 + 10 * (5 - 2);
+set a = (1254 + 14) * 6 - 3762 % 2 + 0 * (6 - 0);
+set a = (1255 + 15) * 2 - 3765 % 3 + 1 * (7 - 1);
+set a = (1256 + 16) * 3 - 3768 % 4 + 2 * (8 - 2);
+set a = (1257 + 17) * 4 - 3771 % 5 + 3 * (9 - 0);
+set a = (1258 + 1) * 5 - 3774 % 6 + 4 * (10 - 1);
+set a = (1259 + 2) * 6 - 3777 % 7 + 5 * (11 - 2);
+set a = (1260 + 3) * 2 - 3780 % 1 + 6 * (12 - 0);
+set a = (1261 + 4) * 3 - 3783 % 2 + 7 * (0 - 1);
+set a = (1262 + 5) * 4 - 3786 % 3 + 8 * (1 - 2);
+set a = (1263 + 6) * 5 - 3789 % 4 + 9 * (2 - 0);
+set a = (1264 + 7) * 6 - 3792 % 5 + 10 * (3 - 1);
+set a = (1265 + 8) * 2 - 3795 % 6 + 0 * (4 - 2);
+set a = (1266 + 9) * 3 - 3798 % 7 + 1 * (5 - 0);
+set a = (1267 + 10) * 4 - 3801 % 1 + 2 * (6 - 1);
+set a = (1268 + 11) * 5 - 3804 % 2 + 3 * (7 - 2);
+set a = (1269 + 12) * 6 - 3807 % 3 + 4 * (8 - 0);
+set a = (1270 + 13) * 2 - 3810 % 4 + 5 * (9 - 1);
+set a = (1271 + 14) * 3 - 3813 % 5 + 6 * (10 - 2);
+set a = (1272 + 15) * 4 - 3816 % 6 + 7 * (11 - 0);
+set a = (1273 + 16) * 5 - 3819 % 7 + 8 * (12 - 1);
+set a = (1274 + 17) * 6 - 3822 % 1 + 9 * (0 - 2);
+set a = (1275 + 1) * 2 - 3825 % 2 + 10 * (1 - 0);
+set a = (1276 + 2) * 3 - 3828 % 3 + 0 * (2 - 1);
+set a = (1277 + 3) * 4 - 3831 % 4 + 1 * (3 - 2);
+set a = (1278 + 4) * 5 - 3834 % 5 + 2 * (4 - 0);
+set a = (1279 + 5) * 6 - 3837 % 6 + 3 * (5 - 1);
+set a = (1280 + 6) * 2 - 3840 % 7 + 4 * (6 - 2);
+set a = (1281 + 7) * 3 - 3843 % 1 + 5 * (7 - 0);
+set a = (1282 + 8) * 4 - 3846 % 2 + 6 * (8 - 1);
+set a = (1283 + 9) * 5 - 3849 % 3 + 7 * (9 - 2);
+set a = (1284 + 10) * 6 - 3852 % 4 + 8 * (10 - 0);
+set a = (1285 + 11) * 2 - 3855 % 5 + 9 * (11 - 1);
+set a = (1286 + 12) * 3 - 3858 % 6 + 10 * (12 - 2);
+set a = (1287 + 13) * 4 - 3861 % 7 + 0 * (0 - 0);
+set a = (1288 + 14) * 5 - 3864 % 1 + 1 * (1 - 1);
+set a = (1289 + 15) * 6 - 3867 % 2 + 2 * (2 - 2);
+set a = (1290 + 16) * 2 - 3870 % 3 + 3 * (3 - 0);
+set a = (1291 + 17) * 3 - 3873 % 4 + 4 * (4 - 1);
+set a = (1292 + 1) * 4 - 3876 % 5 + 5 * (5 - 2);
+set a = (1293 + 2) * 5 - 3879 % 6 + 6 * (6 - 0);
+set a = (1294 + 3) * 6 - 3882 % 7 + 7 * (7 - 1);
+set a = (1295 + 4) * 2 - 3885 % 1 + 8 * (8 - 2);
+set a = (1296 + 5) * 3 - 3888 % 2 + 9 * (9 - 0);
+set a = (1297 + 6) * 4 - 3891 % 3 + 10 * (10 - 1);
+set a = (1298 + 7) * 5 - 3894 % 4 + 0 * (11 - 2);
+set a = (1299 + 8) * 6 - 3897 % 5 + 1 * (12 - 0);
+set a = (1300 + 9) * 2 - 3900 % 6 + 2 * (0 - 1);
+set a = (1301 + 10) * 3 - 3903 % 7 + 3 * (1 - 2);
+set a = (1302 + 11) * 4 - 3906 % 1 + 4 * (2 - 0);
+set a = (1303 + 12) * 5 - 3909 % 2 + 5 * (3 - 1);
+set a = (1304 + 13) * 6 - 3912 % 3 + 6 * (4 - 2);
+set a = (1305 + 14) * 2 - 3915 % 4 + 7 * (5 - 0);
+set a = (1306 + 15) * 3 - 3918 % 5 + 8 * (6 - 1);
+set a = (1307 + 16) * 4 - 3921 % 6 + 9 * (7 - 2);
+set a = (1308 + 17) * 5 - 3924 % 7 + 10 * (8 - 0);
+set a = (1309 + 1) * 6 - 3927 % 1 + 0 * (9 - 1);
+set a = (1310 + 2) * 2 - 3930 % 2 + 1 * (10 - 2);
+set a = (1311 + 3) * 3 - 3933 % 3 + 2 * (11 - 0);
+set a = (1312 + 4) * 4 - 3936 % 4 + 3 * (12 - 1);
+set a = (1313 + 5) * 5 - 3939 % 5 + 4 * (0 - 2);
+set a = (1314 + 6) * 6 - 3942 % 6 + 5 * (1 - 0);
+set a = (1315 + 7) * 2 - 3945 % 7 + 6 * (2 - 1);
+set a = (1316 + 8) * 3 - 3948 % 1 + 7 * (3 - 2);
+set a = (1317 + 9) * 4 - 3951 % 2 + 8 * (4 - 0);
+set a = (1318 + 10) * 5 - 3954 % 3 + 9 * (5 - 1);
+set a = (1319 + 11) * 6 - 3957 % 4 + 10 * (6 - 2);
+set a = (1320 + 12) * 2 - 3960 % 5 + 0 * (7 - 0);
+set a = (1321 + 13) * 3 - 3963 % 6 + 1 * (8 - 1);
+set a = (1322 + 14) * 4 - 3966 % 7 + 2 * (9 - 2);
+set a = (1323 + 15) * 5 - 3969 % 1 + 3 * (10 - 0);
+set a = (1324 + 16) * 6 - 3972 % 2 + 4 * (11 - 1);
+set a = (1325 + 17) * 2 - 3975 % 3 + 5 * (12 - 2);
+set a = (1326 + 1) * 3 - 3978 % 4 + 6 * (0 - 0);
+set a = (1327 + 2) * 4 - 3981 % 5 + 7 * (1 - 1);
+set a = (1328 + 3) * 5 - 3984 % 6 + 8 * (2 - 2);
+set a = (1329 + 4) * 6 - 3987 % 7 + 9 * (3 - 0);
+set a = (1330 + 5) * 2 - 3990 % 1 + 10 * (4 - 1);
+set a = (1331 + 6) * 3 - 3993 % 2 + 0 * (5 - 2);
+set a = (1332 + 7) * 4 - 3996 % 3 + 1 * (6 - 0);
+set a = (1333 + 8) * 5 - 3999 % 4 + 2 * (7 - 1);
+set a = (1334 + 9) * 6 - 4002 % 5 + 3 * (8 - 2);
+set a = (1335 + 10) * 2 - 4005 % 6 + 4 * (9 - 0);
+set a = (1336 + 11) * 3 - 4008 % 7 + 5 * (10 - 1);
+set a = (1337 + 12) * 4 - 4011 % 1 + 6 * (11 - 2);
+set a = (1338 + 13) * 5 - 4014 % 2 + 7 * (12 - 0);
+set a = (1339 + 14) * 6 - 4017 % 3 + 8 * (0 - 1);
+set a = (1340 + 15) * 2 - 4020 % 4 + 9 * (1 - 2);
+set a = (1341 + 16) * 3 - 4023 % 5 + 10 * (2 - 0);
+set a = (1342 + 17) * 4 - 4026 % 6 + 0 * (3 - 1);
+set a = (1343 + 1) * 5 - 4029 % 7 + 1 * (4 - 2);
+set a = (1344 + 2) * 6 - 4032 % 1 + 2 * (5 - 0);
+set a = (1345 + 3) * 2 - 4035 % 2 + 3 * (6 - 1);
+set a = (1346 + 4) * 3 - 4038 % 3 + 4 * (7 - 2);
+set a = (1347 + 5) * 4 - 4041 % 4 + 5 * (8 - 0);
+set a = (1348 + 6) * 5 - 4044 % 5 + 6 * (9 - 1);
+set a = (1349 + 7) * 6 - 4047 % 6 + 7 * (10 - 2);
+set a = (1350 + 8) * 2 - 4050 % 7 + 8 * (11 - 0);
+set a = (1351 + 9) * 3 - 4053 % 1 + 9 * (12 - 1);
+set a = (1352 + 10) * 4 - 4056 % 2 + 10 * (0 - 2);
+set a = (1353 + 11) * 5 - 4059 % 3 + 0 * (1 - 0);
+set a = (1354 + 12) * 6 - 4062 % 4 + 1 * (2 - 1);
+set a = (1355 + 13) * 2 - 4065 % 5 + 2 * (3 - 2);
+set a = (1356 + 14) * 3 - 4068 % 6 + 3 * (4 - 0);
+set a = (1357 + 15) * 4 - 4071 % 7 + 4 * (5 - 1);
+set a = (1358 + 16) * 5 - 4074 % 1 + 5 * (6 - 2);
+set a = (1359 + 17) * 6 - 4077 % 2 + 6 * (7 - 0);
+set a = (1360 + 1) * 2 - 4080 % 3 + 7 * (8 - 1);
+set a = (1361 + 2) * 3 - 4083 % 4 + 8 * (9 - 2);
+set a = (1362 + 3) * 4 - 4086 % 5 + 9 * (10 - 0);
+set a = (1363 + 4) * 5 - 4089 % 6 + 10 * (11 - 1);
+set a = (1364 + 5) * 6 - 4092 % 7 + 0 * (12 - 2);
+set a = (1365 + 6) * 2 - 4095 % 1 + 1 * (0 - 0);
+set a = (1366 + 7) * 3 - 4098 % 2 + 2 * (1 - 1);
+set a = (1367 + 8) * 4 - 4101 % 3 + 3 * (2 - 2);
+set a = (1368 + 9) * 5 - 4104 % 4 + 4 * (3 - 0);
+set a = (1369 + 10) * 6 - 4107 % 5 + 5 * (4 - 1);
+set a = (1370 + 11) * 2 - 4110 % 6 + 6 * (5 - 2);
+set a = (1371 + 12) * 3 - 4113 % 7 + 7 * (6 - 0);
+set a = (1372 + 13) * 4 - 4116 % 1 + 8 * (7 - 1);
+set a = (1373 + 14) * 5 - 4119 % 2 + 9 * (8 - 2);
+set a = (1374 + 15) * 6 - 4122 % 3 + 10 * (9 - 0);
+set a = (1375 + 16) * 2 - 4125 % 4 + 0 * (10 - 1);
+set a = (1376 + 17) * 3 - 4128 % 5 + 1 * (11 - 2);
+set a = (1377 + 1) * 4 - 4131 % 6 + 2 * (12 - 0);
+set a = (1378 + 2) * 5 - 4134 % 7 + 3 * (0 - 1);
+set a = (1379 + 3) * 6 - 4137 % 1 + 4 * (1 - 2);
+set a = (1380 + 4) * 2 - 4140 % 2 + 5 * (2 - 0);
+set a = (1381 + 5) * 3 - 4143 % 3 + 6 * (3 - 1);
+set a = (1382 + 6) * 4 - 4146 % 4 + 7 * (4 - 2);
+set a = (1383 + 7) * 5 - 4149 % 5 + 8 * (5 - 0);
+set a = (1384 + 8) * 6 - 4152 % 6 + 9 * (6 - 1);
+set a = (1385 + 9) * 2 - 4155 % 7 + 10 * (7 - 2);
+set a = (1386 + 10) * 3 - 4158 % 1 + 0 * (8 - 0);
+set a = (1387 + 11) * 4 - 4161 % 2 + 1 * (9 - 1);
+set a = (1388 + 12) * 5 - 4164 % 3 + 2 * (10 - 2);
+set a = (1389 + 13) * 6 - 4167 % 4 + 3 * (11 - 0);
+set a = (1390 + 14) * 2 - 4170 % 5 + 4 * (12 - 1);
+set a = (1391 + 15) * 3 - 4173 % 6 + 5 * (0 - 2);
+set a = (1392 + 16) * 4 - 4176 % 7 + 6 * (1 - 0);
+set a = (1393 + 17) * 5 - 4179 % 1 + 7 * (2 - 1);
+set a = (1394 + 1) * 6 - 4182 % 2 + 8 * (3 - 2);
+set a = (1395 + 2) * 2 - 4185 % 3 + 9 * (4 - 0);
+set a = (1396 + 3) * 3 - 4188 % 4 + 10 * (5 - 1);
+set a = (1397 + 4) * 4 - 4191 % 5 + 0 * (6 - 2);
+set a = (1398 + 5) * 5 - 4194 % 6 + 1 * (7 - 0);
+set a = (1399 + 6) * 6 - 4197 % 7 + 2 * (8 - 1);
+set a = (1400 + 7) * 2 - 4200 % 1 + 3 * (9 - 2);
+set a = (1401 + 8) * 3 - 4203 % 2 + 4 * (10 - 0);
+set a = (1402 + 9) * 4 - 4206 % 3 + 5 * (11 - 1);
+set a = (1403 + 10) * 5 - 4209 % 4 + 6 * (12 - 2);
+set a = (1404 + 11) * 6 - 4212 % 5 + 7 * (0 - 0);
+set a = (1405 + 12) * 2 - 4215 % 6 + 8 * (1 - 1);
+set a = (1406 + 13) * 3 - 4218 % 7 + 9 * (2 - 2);
+set a = (1407 + 14) * 4 - 4221 % 1 + 10 * (3 - 0);
+set a = (1408 + 15) * 5 - 4224 % 2 + 0 * (4 - 1);
+set a = (1409 + 16) * 6 - 4227 % 3 + 1 * (5 - 2);
+set a = (1410 + 17) * 2 - 4230 % 4 + 2 * (6 - 0);
+set a = (1411 + 1) * 3 - 4233 % 5 + 3 * (7 - 1);
+set a = (1412 + 2) * 4 - 4236 % 6 + 4 * (8 - 2);
+set a = (1413 + 3) * 5 - 4239 % 7 + 5 * (9 - 0);
+set a = (1414 + 4) * 6 - 4242 % 1 + 6 * (10 - 1);
+set a = (1415 + 5) * 2 - 4245 % 2 + 7 * (11 - 2);
+set a = (1416 + 6) * 3 - 4248 % 3 + 8 * (12 - 0);
+set a = (1417 + 7) * 4 - 4251 % 4 + 9 * (0 - 1);
+set a = (1418 + 8) * 5 - 4254 % 5 + 10 * (1 - 2);
+set a = (1419 + 9) * 6 - 4257 % 6 + 0 * (2 - 0);
+set a = (1420 + 10) * 2 - 4260 % 7 + 1 * (3 - 1);
+set a = (1421 + 11) * 3 - 4263 % 1 + 2 * (4 - 2);
+set a = (1422 + 12) * 4 - 4266 % 2 + 3 * (5 - 0);
+set a = (1423 + 13) * 5 - 4269 % 3 + 4 * (6 - 1);
+set a = (1424 + 14) * 6 - 4272 % 4 + 5 * (7 - 2);
+set a = (1425 + 15) * 2 - 4275 % 5 + 6 * (8 - 0);
+set a = (1426 + 16) * 3 - 4278 % 6 + 7 * (9 - 1);
+set a = (1427 + 17) * 4 - 4281 % 7 + 8 * (10 - 2);
+set a = (1428 + 1) * 5 - 4284 % 1 + 9 * (11 - 0);
+set a = (1429 + 2) * 6 - 4287 % 2 + 10 * (12 - 1);
+set a = (1430 + 3) * 2 - 4290 % 3 + 0 * (0 - 2);
+set a = (1431 + 4) * 3 - 4293 % 4 + 1 * (1 - 0);
+set a = (1432 + 5) * 4 - 4296 % 5 + 2 * (2 - 1);
+set a = (1433 + 6) * 5 - 4299 % 6 + 3 * (3 - 2);
+set a = (1434 + 7) * 6 - 4302 % 7 + 4 * (4 - 0);
+set a = (1435 + 8) * 2 - 4305 % 1 + 5 * (5 - 1);
+set a = (1436 + 9) * 3 - 4308 % 2 + 6 * (6 - 2);
+set a = (1437 + 10) * 4 - 4311 % 3 + 7 * (7 - 0);
+set a = (1438 + 11) * 5 - 4314 % 4 + 8 * (8 - 1);
+set a = (1439 + 12) * 6 - 4317 % 5 + 9 * (9 - 2);
+set a = (1440 + 13) * 2 - 4320 % 6 + 10 * (10 - 0);
+set a = (1441 + 14) * 3 - 4323 % 7 + 0 * (11 - 1);
+set a = (1442 + 15) * 4 - 4326 % 1 + 1 * (12 - 2);
+set a = (1443 + 16) * 5 - 4329 % 2 + 2 * (0 - 0);
+set a = (1444 + 17) * 6 - 4332 % 3 + 3 * (1 - 1);
+set a = (1445 + 1) * 2 - 4335 % 4 + 4 * (2 - 2);
+set a = (1446 + 2) * 3 - 4338 % 5 + 5 * (3 - 0);
+set a = (1447 + 3) * 4 - 4341 % 6 + 6 * (4 - 1);
+set a = (1448 + 4) * 5 - 4344 % 7 + 7 * (5 - 2);
+set a = (1449 + 5) * 6 - 4347 % 1 + 8 * (6 - 0);
+set a = (1450 + 6) * 2 - 4350 % 2 + 9 * (7 - 1);
+set a = (1451 + 7) * 3 - 4353 % 3 + 10 * (8 - 2);
+set a = (1452 + 8) * 4 - 4356 % 4 + 0 * (9 - 0);
+set a = (1453 + 9) * 5 - 4359 % 5 + 1 * (10 - 1);
+set a = (1454 + 10) * 6 - 4362 % 6 + 2 * (11 - 2);
+set a = (1455 + 11) * 2 - 4365 % 7 + 3 * (12 - 0);
+set a = (1456 + 12) * 3 - 4368 % 1 + 4 * (0 - 1);
+set a = (1457 + 13) * 4 - 4371 % 2 + 5 * (1 - 2);
+set a = (1458 + 14) * 5 - 4374 % 3 + 6 * (2 - 0);
+set a = (1459 + 15) * 6 - 4377 % 4 + 7 * (3 - 1);
+set a = (1460 + 16) * 2 - 4380 % 5 + 8 * (4 - 2);
+set a = (1461 + 17) * 3 - 4383 % 6 + 9 * (5 - 0);
+set a = (1462 + 1) * 4 - 4386 % 7 + 10 * (6 - 1);
+set a = (1463 + 2) * 5 - 4389 % 1 + 0 * (7 - 2);
+set a = (1464 + 3) * 6 - 4392 % 2 + 1 * (8 - 0);
+set a = (1465 + 4) * 2 - 4395 % 3 + 2 * (9 - 1);
+set a = (1466 + 5) * 3 - 4398 % 4 + 3 * (10 - 2);
+set a = (1467 + 6) * 4 - 4401 % 5 + 4 * (11 - 0);
+set a = (1468 + 7) * 5 - 4404 % 6 + 5 * (12 - 1);
+set a = (1469 + 8) * 6 - 4407 % 7 + 6 * (0 - 2);
+set a = (1470 + 9) * 2 - 4410 % 1 + 7 * (1 - 0);
+set a = (1471 + 10) * 3 - 4413 % 2 + 8 * (2 - 1);
+set a = (1472 + 11) * 4 - 4416 % 3 + 9 * (3 - 2);
+set a = (1473 + 12) * 5 - 4419 % 4 + 10 * (4 - 0);
+set a = (1474 + 13) * 6 - 4422 % 5 + 0 * (5 - 1);
+set a = (1475 + 14) * 2 - 4425 % 6 + 1 * (6 - 2);
+set a = (1476 + 15) * 3 - 4428 % 7 + 2 * (7 - 0);
+set a = (1477 + 16) * 4 - 4431 % 1 + 3 * (8 - 1);
+set a = (1478 + 17) * 5 - 4434 % 2 + 4 * (9 - 2);
+set a = (1479 + 1) * 6 - 4437 % 3 + 5 * (10 - 0);
+set a = (1480 + 2) * 2 - 4440 % 4 + 6 * (11 - 1);
+set a = (1481 + 3) * 3 - 4443 % 5 + 7 * (12 - 2);
+set a = (1482 + 4) * 4 - 4446 % 6 + 8 * (0 - 0);
+set a = (1483 + 5) * 5 - 4449 % 7 + 9 * (1 - 1);
+set a = (1484 + 6) * 6 - 4452 % 1 + 10 * (2 - 2);
+set a = (1485 + 7) * 2 - 4455 % 2 + 0 * (3 - 0);
+set a = (1486 + 8) * 3 - 4458 % 3 + 1 * (4 - 1);
+set a = (1487 + 9) * 4 - 4461 % 4 + 2 * (5 - 2);
+set a = (1488 + 10) * 5 - 4464 % 5 + 3 * (6 - 0);
+set a = (1489 + 11) * 6 - 4467 % 6 + 4 * (7 - 1);
+set a = (1490 + 12) * 2 - 4470 % 7 + 5 * (8 - 2);
+set a = (1491 + 13) * 3 - 4473 % 1 + 6 * (9 - 0);
+set a = (1492 + 14) * 4 - 4476 % 2 + 7 * (10 - 1);
+set a = (1493 + 15) * 5 - 4479 % 3 + 8 * (11 - 2);
+set a = (1494 + 16) * 6 - 4482 % 4 + 9 * (12 - 0);
+set a = (1495 + 17) * 2 - 4485 % 5 + 10 * (0 - 1);
+set a = (1496 + 1) * 3 - 4488 % 6 + 0 * (1 - 2);
+set a = (1497 + 2) * 4 - 4491 % 7 + 1 * (2 - 0);
+set a = (1498 + 3) * 5 - 4494 % 1 + 2 * (3 - 1);
+set a = (1499 + 4) * 6 - 4497 % 2 + 3 * (4 - 2);
+set a = (1500 + 5) * 2 - 4500 % 3 + 4 * (5 - 0);
+set a = (1501 + 6) * 3 - 4503 % 4 + 5 * (6 - 1);
+set a = (1502 + 7) * 4 - 4506 % 5 + 6 * (7 - 2);
+set a = (1503 + 8) * 5 - 4509 % 6 + 7 * (8 - 0);
+set a = (1504 + 9) * 6 - 4512 % 7 + 8 * (9 - 1);
+set a = (1505 + 10) * 2 - 4515 % 1 + 9 * (10 - 2);
+set a = (1506 + 11) * 3 - 4518 % 2 + 10 * (11 - 0);
+set a = (1507 + 12) * 4 - 4521 % 3 + 0 * (12 - 1);
+set a = (1508 + 13) * 5 - 4524 % 4 + 1 * (0 - 2);
+set a = (1509 + 14) * 6 - 4527 % 5 + 2 * (1 - 0);
+set a = (1510 + 15) * 2 - 4530 % 6 + 3 * (2 - 1);
+set a = (1511 + 16) * 3 - 4533 % 7 + 4 * (3 - 2);
+set a = (1512 + 17) * 4 - 4536 % 1 + 5 * (4 - 0);
+set a = (1513 + 1) * 5 - 4539 % 2 + 6 * (5 - 1);
+set a = (1514 + 2) * 6 - 4542 % 3 + 7 * (6 - 2);
+set a = (1515 + 3) * 2 - 4545 % 4 + 8 * (7 - 0);
+set a = (1516 + 4) * 3 - 4548 % 5 + 9 * (8 - 1);
+set a = (1517 + 5) * 4 - 4551 % 6 + 10 * (9 - 2);
+set a = (1518 + 6) * 5 - 4554 % 7 + 0 * (10 - 0);
+set a = (1519 + 7) * 6 - 4557 % 1 + 1 * (11 - 1);
+set a = (1520 + 8) * 2 - 4560 % 2 + 2 * (12 - 2);
+set a = (1521 + 9) * 3 - 4563 % 3 + 3 * (0 - 0);
+set a = (1522 + 10) * 4 - 4566 % 4 + 4 * (1 - 1);
+set a = (1523 + 11) * 5 - 4569 % 5 + 5 * (2 - 2);
+set a = (1524 + 12) * 6 - 4572 % 6 + 6 * (3 - 0);
+set a = (1525 + 13) * 2 - 4575 % 7 + 7 * (4 - 1);
+set a = (1526 + 14) * 3 - 4578 % 1 + 8 * (5 - 2);
+set a = (1527 + 15) * 4 - 4581 % 2 + 9 * (6 - 0);
+set a = (1528 + 16) * 5 - 4584 % 3 + 10 * (7 - 1);
+set a = (1529 + 17) * 6 - 4587 % 4 + 0 * (8 - 2);
+set a = (1530 + 1) * 2 - 4590 % 5 + 1 * (9 - 0);
+set a = (1531 + 2) * 3 - 4593 % 6 + 2 * (10 - 1);
+set a = (1532 + 3) * 4 - 4596 % 7 + 3 * (11 - 2);
+set a = (1533 + 4) * 5 - 4599 % 1 + 4 * (12 - 0);
+set a = (1534 + 5) * 6 - 4602 % 2 + 5 * (0 - 1);
+set a = (1535 + 6) * 2 - 4605 % 3 + 6 * (1 - 2);
+set a = (1536 + 7) * 3 - 4608 % 4 + 7 * (2 - 0);
+set a = (1537 + 8) * 4 - 4611 % 5 + 8 * (3 - 1);
+set a = (1538 + 9) * 5 - 4614 % 6 + 9 * (4 - 2);
+set a = (1539 + 10) * 6 - 4617 % 7 + 10 * (5 - 0);
+set a = (1540 + 11) * 2 - 4620 % 1 + 0 * (6 - 1);
+set a = (1541 + 12) * 3 - 4623 % 2 + 1 * (7 - 2);
+set a = (1542 + 13) * 4 - 4626 % 3 + 2 * (8 - 0);
+set a = (1543 + 14) * 5 - 4629 % 4 + 3 * (9 - 1);
+set a = (1544 + 15) * 6 - 4632 % 5 + 4 * (10 - 2);
+set a = (1545 + 16) * 2 - 4635 % 6 + 5 * (11 - 0);
+set a = (1546 + 17) * 3 - 4638 % 7 + 6 * (12 - 1);
+set a = (1547 + 1) * 4 - 4641 % 1 + 7 * (0 - 2);
+set a = (1548 + 2) * 5 - 4644 % 2 + 8 * (1 - 0);
+set a = (1549 + 3) * 6 - 4647 % 3 + 9 * (2 - 1);
+set a = (1550 + 4) * 2 - 4650 % 4 + 10 * (3 - 2);
+set a = (1551 + 5) * 3 - 4653 % 5 + 0 * (4 - 0);
+set a = (1552 + 6) * 4 - 4656 % 6 + 1 * (5 - 1);
+set a = (1553 + 7) * 5 - 4659 % 7 + 2 * (6 - 2);
+set a = (1554 + 8) * 6 - 4662 % 1 + 3 * (7 - 0);
+set a = (1555 + 9) * 2 - 4665 % 2 + 4 * (8 - 1);
+set a = (1556 + 10) * 3 - 4668 % 3 + 5 * (9 - 2);
+set a = (1557 + 11) * 4 - 4671 % 4 + 6 * (10 - 0);
+set a = (1558 + 12) * 5 - 4674 % 5 + 7 * (11 - 1);
+set a = (1559 + 13) * 6 - 4677 % 6 + 8 * (12 - 2);
+set a = (1560 + 14) * 2 - 4680 % 7 + 9 * (0 - 0);
+set a = (1561 + 15) * 3 - 4683 % 1 + 10 * (1 - 1);
+set a = (1562 + 16) * 4 - 4686 % 2 + 0 * (2 - 2);
+set a = (1563 + 17) * 5 - 4689 % 3 + 1 * (3 - 0);
+set a = (1564 + 1) * 6 - 4692 % 4 + 2 * (4 - 1);
+set a = (1565 + 2) * 2 - 4695 % 5 + 3 * (5 - 2);
+set a = (1566 + 3) * 3 - 4698 % 6 + 4 * (6 - 0);
+set a = (1567 + 4) * 4 - 4701 % 7 + 5 * (7 - 1);
+set a = (1568 + 5) * 5 - 4704 % 1 + 6 * (8 - 2);
+set a = (1569 + 6) * 6 - 4707 % 2 + 7 * (9 - 0);
+set a = (1570 + 7) * 2 - 4710 % 3 + 8 * (10 - 1);
+set a = (1571 + 8) * 3 - 4713 % 4 + 9 * (11 - 2);
+set a = (1572 + 9) * 4 - 4716 % 5 + 10 * (12 - 0);
+set a = (1573 + 10) * 5 - 4719 % 6 + 0 * (0 - 1);
+set a = (1574 + 11) * 6 - 4722 % 7 + 1 * (1 - 2);
+set a = (1575 + 12) * 2 - 4725 % 1 + 2 * (2 - 0);
+set a = (1576 + 13) * 3 - 4728 % 2 + 3 * (3 - 1);
+set a = (1577 + 14) * 4 - 4731 % 3 + 4 * (4 - 2);
+set a = (1578 + 15) * 5 - 4734 % 4 + 5 * (5 - 0);
+set a = (1579 + 16) * 6 - 4737 % 5 + 6 * (6 - 1);
+set a = (1580 + 17) * 2 - 4740 % 6 + 7 * (7 - 2);
+set a = (1581 + 1) * 3 - 4743 % 7 + 8 * (8 - 0);
+set a = (1582 + 2) * 4 - 4746 % 1 + 9 * (9 - 1);
+set a = (1583 + 3) * 5 - 4749 % 2 + 10 * (10 - 2);
+set a = (1584 + 4) * 6 - 4752 % 3 + 0 * (11 - 0);
+set a = (1585 + 5) * 2 - 4755 % 4 + 1 * (12 - 1);
+set a = (1586 + 6) * 3 - 4758 % 5 + 2 * (0 - 2);
+set a = (1587 + 7) * 4 - 4761 % 6 + 3 * (1 - 0);
+set a = (1588 + 8) * 5 - 4764 % 7 + 4 * (2 - 1);
+set a = (1589 + 9) * 6 - 4767 % 1 + 5 * (3 - 2);
+set a = (1590 + 10) * 2 - 4770 % 2 + 6 * (4 - 0);
+set a = (1591 + 11) * 3 - 4773 % 3 + 7 * (5 - 1);
+set a = (1592 + 12) * 4 - 4776 % 4 + 8 * (6 - 2);
+set a = (1593 + 13) * 5 - 4779 % 5 + 9 * (7 - 0);
+set a = (1594 + 14) * 6 - 4782 % 6 + 10 * (8 - 1);
+set a = (1595 + 15) * 2 - 4785 % 7 + 0 * (9 - 2);
+set a = (1596 + 16) * 3 - 4788 % 1 + 1 * (10 - 0);
+set a = (1597 + 17) * 4 - 4791 % 2 + 2 * (11 - 1);
+set a = (1598 + 1) * 5 - 4794 % 3 + 3 * (12 - 2);
+set a = (1599 + 2) * 6 - 4797 % 4 + 4 * (0 - 0);
+set a = (1600 + 3) * 2 - 4800 % 5 + 5 * (1 - 1);
+set a = (1601 + 4) * 3 - 4803 % 6 + 6 * (2 - 2);
+set a = (1602 + 5) * 4 - 4806 % 7 + 7 * (3 - 0);
+set a = (1603 + 6) * 5 - 4809 % 1 + 8 * (4 - 1);
+set a = (1604 + 7) * 6 - 4812 % 2 + 9 * (5 - 2);
+set a = (1605 + 8) * 2 - 4815 % 3 + 10 * (6 - 0);
+set a = (1606 + 9) * 3 - 4818 % 4 + 0 * (7 - 1);
+set a = (1607 + 10) * 4 - 4821 % 5 + 1 * (8 - 2);
+set a = (1608 + 11) * 5 - 4824 % 6 + 2 * (9 - 0);
+set a = (1609 + 12) * 6 - 4827 % 7 + 3 * (10 - 1);
+set a = (1610 + 13) * 2 - 4830 % 1 + 4 * (11 - 2);
+set a = (1611 + 14) * 3 - 4833 % 2 + 5 * (12 - 0);
+set a = (1612 + 15) * 4 - 4836 % 3 + 6 * (0 - 1);
+set a = (1613 + 16) * 5 - 4839 % 4 + 7 * (1 - 2);
+set a = (1614 + 17) * 6 - 4842 % 5 + 8 * (2 - 0);
+set a = (1615 + 1) * 2 - 4845 % 6 + 9 * (3 - 1);
+set a = (1616 + 2) * 3 - 4848 % 7 + 10 * (4 - 2);
+set a = (1617 + 3) * 4 - 4851 % 1 + 0 * (5 - 0);
+set a = (1618 + 4) * 5 - 4854 % 2 + 1 * (6 - 1);
+set a = (1619 + 5) * 6 - 4857 % 3 + 2 * (7 - 2);
+set a = (1620 + 6) * 2 - 4860 % 4 + 3 * (8 - 0);
+set a = (1621 + 7) * 3 - 4863 % 5 + 4 * (9 - 1);
+set a = (1622 + 8) * 4 - 4866 % 6 + 5 * (10 - 2);
+set a = (1623 + 9) * 5 - 4869 % 7 + 6 * (11 - 0);
+set a = (1624 + 10) * 6 - 4872 % 1 + 7 * (12 - 1);
+set a = (1625 + 11) * 2 - 4875 % 2 + 8 * (0 - 2);
+set a = (1626 + 12) * 3 - 4878 % 3 + 9 * (1 - 0);
+set a = (1627 + 13) * 4 - 4881 % 4 + 10 * (2 - 1);
+set a = (1628 + 14) * 5 - 4884 % 5 + 0 * (3 - 2);
+set a = (1629 + 15) * 6 - 4887 % 6 + 1 * (4 - 0);
+set a = (1630 + 16) * 2 - 4890 % 7 + 2 * (5 - 1);
+set a = (1631 + 17) * 3 - 4893 % 1 + 3 * (6 - 2);
+set a = (1632 + 1) * 4 - 4896 % 2 + 4 * (7 - 0);
+set a = (1633 + 2) * 5 - 4899 % 3 + 5 * (8 - 1);
+set a = (1634 + 3) * 6 - 4902 % 4 + 6 * (9 - 2);
+set a = (1635 + 4) * 2 - 4905 % 5 + 7 * (10 - 0);
+set a = (1636 + 5) * 3 - 4908 % 6 + 8 * (11 - 1);
+set a = (1637 + 6) * 4 - 4911 % 7 + 9 * (12 - 2);
+set a = (1638 + 7) * 5 - 4914 % 1 + 10 * (0 - 0);
+set a = (1639 + 8) * 6 - 4917 % 2 + 0 * (1 - 1);
+set a = (1640 + 9) * 2 - 4920 % 3 + 1 * (2 - 2);
+set a = (1641 + 10) * 3 - 4923 % 4 + 2 * (3 - 0);
+set a = (1642 + 11) * 4 - 4926 % 5 + 3 * (4 - 1);
+set a = (1643 + 12) * 5 - 4929 % 6 + 4 * (5 - 2);
+set a = (1644 + 13) * 6 - 4932 % 7 + 5 * (6 - 0);
+set a = (1645 + 14) * 2 - 4935 % 1 + 6 * (7 - 1);
+set a = (1646 + 15) * 3 - 4938 % 2 + 7 * (8 - 2);
+set a = (1647 + 16) * 4 - 4941 % 3 + 8 * (9 - 0);
+set a = (1648 + 17) * 5 - 4944 % 4 + 9 * (10 - 1);
+set a = (1649 + 1) * 6 - 4947 % 5 + 10 * (11 - 2);
+set a = (1650 + 2) * 2 - 4950 % 6 + 0 * (12 - 0);
+set a = (1651 + 3) * 3 - 4953 % 7 + 1 * (0 - 1);
+set a = (1652 + 4) * 4 - 4956 % 1 + 2 * (1 - 2);
+set a = (1653 + 5) * 5 - 4959 % 2 + 3 * (2 - 0);
+set a = (1654 + 6) * 6 - 4962 % 3 + 4 * (3 - 1);
+set a = (1655 + 7) * 2 - 4965 % 4 + 5 * (4 - 2);
+set a = (1656 + 8) * 3 - 4968 % 5 + 6 * (5 - 0);
+set a = (1657 + 9) * 4 - 4971 % 6 + 7 * (6 - 1);
+set a = (1658 + 10) * 5 - 4974 % 7 + 8 * (7 - 2);
+set a = (1659 + 11) * 6 - 4977 % 1 + 9 * (8 - 0);
+set a = (1660 + 12) * 2 - 4980 % 2 + 10 * (9 - 1);
+set a = (1661 + 13) * 3 - 4983 % 3 + 0 * (10 - 2);
+set a = (1662 + 14) * 4 - 4986 % 4 + 1 * (11 - 0);
+set a = (1663 + 15) * 5 - 4989 % 5 + 2 * (12 - 1);
+set a = (1664 + 16) * 6 - 4992 % 6 + 3 * (0 - 2);
+set a = (1665 + 17) * 2 - 4995 % 7 + 4 * (1 - 0);
+set a = (1666 + 1) * 3 - 4998 % 1 + 5 * (2 - 1);
+set a = (1667 + 2) * 4 - 5001 % 2 + 6 * (3 - 2);
+set a = (1668 + 3) * 5 - 5004 % 3 + 7 * (4 - 0);
+set a = (1669 + 4) * 6 - 5007 % 4 + 8 * (5 - 1);
+set a = (1670 + 5) * 2 - 5010 % 5 + 9 * (6 - 2);
+set a = (1671 + 6) * 3 - 5013 % 6 + 10 * (7 - 0);
+set a = (1672 + 7) * 4 - 5016 % 7 + 0 * (8 - 1);
+set a = (1673 + 8) * 5 - 5019 % 1 + 1 * (9 - 2);
+set a = (1674 + 9) * 6 - 5022 % 2 + 2 * (10 - 0);
+set a = (1675 + 10) * 2 - 5025 % 3 + 3 * (11 - 1);
+set a = (1676 + 11) * 3 - 5028 % 4 + 4 * (12 - 2);
+set a = (1677 + 12) * 4 - 5031 % 5 + 5 * (0 - 0);
+set a = (1678 + 13) * 5 - 5034 % 6 + 6 * (1 - 1);
+set a = (1679 + 14) * 6 - 5037 % 7 + 7 * (2 - 2);
+set a = (1680 + 15) * 2 - 5040 % 1 + 8 * (3 - 0);
+set a = (1681 + 16) * 3 - 5043 % 2 + 9 * (4 - 1);
+set a = (1682 + 17) * 4 - 5046 % 3 + 10 * (5 - 2);
+set a = (1683 + 1) * 5 - 5049 % 4 + 0 * (6 - 0);
+set a = (1684 + 2) * 6 - 5052 % 5 + 1 * (7 - 1);
+set a = (1685 + 3) * 2 - 5055 % 6 + 2 * (8 - 2);
+set a = (1686 + 4) * 3 - 5058 % 7 + 3 * (9 - 0);
+set a = (1687 + 5) * 4 - 5061 % 1 + 4 * (10 - 1);
+set a = (1688 + 6) * 5 - 5064 % 2 + 5 * (11 - 2);
+set a = (1689 + 7) * 6 - 5067 % 3 + 6 * (12 - 0);
+set a = (1690 + 8) * 2 - 5070 % 4 + 7 * (0 - 1);
+set a = (1691 + 9) * 3 - 5073 % 5 + 8 * (1 - 2);
+set a = (1692 + 10) * 4 - 5076 % 6 + 9 * (2 - 0);
+set a = (1693 + 11) * 5 - 5079 % 7 + 10 * (3 - 1);
+set a = (1694 + 12) * 6 - 5082 % 1 + 0 * (4 - 2);
+set a = (1695 + 13) * 2 - 5085 % 2 + 1 * (5 - 0);
+set a = (1696 + 14) * 3 - 5088 % 3 + 2 * (6 - 1);
+set a = (1697 + 15) * 4 - 5091 % 4 + 3 * (7 - 2);
+set a = (1698 + 16) * 5 - 5094 % 5 + 4 * (8 - 0);
+set a = (1699 + 17) * 6 - 5097 % 6 + 5 * (9 - 1);
+set a = (1700 + 1) * 2 - 5100 % 7 + 6 * (10 - 2);
+set a = (1701 + 2) * 3 - 5103 % 1 + 7 * (11 - 0);
+set a = (1702 + 3) * 4 - 5106 % 2 + 8 * (12 - 1);
+set a = (1703 + 4) * 5 - 5109 % 3 + 9 * (0 - 2);
+set a = (1704 + 5) * 6 - 5112 % 4 + 10 * (1 - 0);
+set a = (1705 + 6) * 2 - 5115 % 5 + 0 * (2 - 1);
+set a = (1706 + 7) * 3 - 5118 % 6 + 1 * (3 - 2);
+set a = (1707 + 8) * 4 - 5121 % 7 + 2 * (4 - 0);
+set a = (1708 + 9) * 5 - 5124 % 1 + 3 * (5 - 1);
+set a = (1709 + 10) * 6 - 5127 % 2 + 4 * (6 - 2);
+set a = (1710 + 11) * 2 - 5130 % 3 + 5 * (7 - 0);
+set a = (1711 + 12) * 3 - 5133 % 4 + 6 * (8 - 1);
+set a = (1712 + 13) * 4 - 5136 % 5 + 7 * (9 - 2);
+set a = (1713 + 14) * 5 - 5139 % 6 + 8 * (10 - 0);
+set a = (1714 + 15) * 6 - 5142 % 7 + 9 * (11 - 1);
+set a = (1715 + 16) * 2 - 5145 % 1 + 10 * (12 - 2);
+set a = (1716 + 17) * 3 - 5148 % 2 + 0 * (0 - 0);
+set a = (1717 + 1) * 4 - 5151 % 3 + 1 * (1 - 1);
+set a = (1718 + 2) * 5 - 5154 % 4 + 2 * (2 - 2);
+set a = (1719 + 3) * 6 - 5157 % 5 + 3 * (3 - 0);
+set a = (1720 + 4) * 2 - 5160 % 6 + 4 * (4 - 1);
+set a = (1721 + 5) * 3 - 5163 % 7 + 5 * (5 - 2);
+set a = (1722 + 6) * 4 - 5166 % 1 + 6 * (6 - 0);
+set a = (1723 + 7) * 5 - 5169 % 2 + 7 * (7 - 1);
+set a = (1724 + 8) * 6 - 5172 % 3 + 8 * (8 - 2);
+set a = (1725 + 9) * 2 - 5175 % 4 + 9 * (9 - 0);
+set a = (1726 + 10) * 3 - 5178 % 5 + 10 * (10 - 1);
+set a = (1727 + 11) * 4 - 5181 % 6 + 0 * (11 - 2);
+set a = (1728 + 12) * 5 - 5184 % 7 + 1 * (12 - 0);
+set a = (1729 + 13) * 6 - 5187 % 1 + 2 * (0 - 1);
+set a = (1730 + 14) * 2 - 5190 % 2 + 3 * (1 - 2);
+set a = (1731 + 15) * 3 - 5193 % 3 + 4 * (2 - 0);
+set a = (1732 + 16) * 4 - 5196 % 4 + 5 * (3 - 1);
+set a = (1733 + 17) * 5 - 5199 % 5 + 6 * (4 - 2);
+set a = (1734 + 1) * 6 - 5202 % 6 + 7 * (5 - 0);
+set a = (1735 + 2) * 2 - 5205 % 7 + 8 * (6 - 1);
+set a = (1736 + 3) * 3 - 5208 % 1 + 9 * (7 - 2);
+set a = (1737 + 4) * 4 - 5211 % 2 + 10 * (8 - 0);
+set a = (1738 + 5) * 5 - 5214 % 3 + 0 * (9 - 1);
+set a = (1739 + 6) * 6 - 5217 % 4 + 1 * (10 - 2);
+set a = (1740 + 7) * 2 - 5220 % 5 + 2 * (11 - 0);
+set a = (1741 + 8) * 3 - 5223 % 6 + 3 * (12 - 1);
+set a = (1742 + 9) * 4 - 5226 % 7 + 4 * (0 - 2);
+set a = (1743 + 10) * 5 - 5229 % 1 + 5 * (1 - 0);
+set a = (1744 + 11) * 6 - 5232 % 2 + 6 * (2 - 1);
+set a = (1745 + 12) * 2 - 5235 % 3 + 7 * (3 - 2);
+set a = (1746 + 13) * 3 - 5238 % 4 + 8 * (4 - 0);
+set a = (1747 + 14) * 4 - 5241 % 5 + 9 * (5 - 1);
+set a = (1748 + 15) * 5 - 5244 % 6 + 10 * (6 - 2);
+set a = (1749 + 16) * 6 - 5247 % 7 + 0 * (7 - 0);
+set a = (1750 + 17) * 2 - 5250 % 1 + 1 * (8 - 1);
+set a = (1751 + 1) * 3 - 5253 % 2 + 2 * (9 - 2);
+set a = (1752 + 2) * 4 - 5256 % 3 + 3 * (10 - 0);
+set a = (1753 + 3) * 5 - 5259 % 4 + 4 * (11 - 1);
+set a = (1754 + 4) * 6 - 5262 % 5 + 5 * (12 - 2);
+set a = (1755 + 5) * 2 - 5265 % 6 + 6 * (0 - 0);
+set a = (1756 + 6) * 3 - 5268 % 7 + 7 * (1 - 1);
+set a = (1757 + 7) * 4 - 5271 % 1 + 8 * (2 - 2);
+set a = (1758 + 8) * 5 - 5274 % 2 + 9 * (3 - 0);
+set a = (1759 + 9) * 6 - 5277 % 3 + 10 * (4 - 1);
+set a = (1760 + 10) * 2 - 5280 % 4 + 0 * (5 - 2);
+set a = (1761 + 11) * 3 - 5283 % 5 + 1 * (6 - 0);
+set a = (1762 + 12) * 4 - 5286 % 6 + 2 * (7 - 1);
+set a = (1763 + 13) * 5 - 5289 % 7 + 3 * (8 - 2);
+set a = (1764 + 14) * 6 - 5292 % 1 + 4 * (9 - 0);
+set a = (1765 + 15) * 2 - 5295 % 2 + 5 * (10 - 1);
+set a = (1766 + 16) * 3 - 5298 % 3 + 6 * (11 - 2);
+set a = (1767 + 17) * 4 - 5301 % 4 + 7 * (12 - 0);
+set a = (1768 + 1) * 5 - 5304 % 5 + 8 * (0 - 1);
+set a = (1769 + 2) * 6 - 5307 % 6 + 9 * (1 - 2);
+set a = (1770 + 3) * 2 - 5310 % 7 + 10 * (2 - 0);
+set a = (1771 + 4) * 3 - 5313 % 1 + 0 * (3 - 1);
+set a = (1772 + 5) * 4 - 5316 % 2 + 1 * (4 - 2);
+set a = (1773 + 6) * 5 - 5319 % 3 + 2 * (5 - 0);
+set a = (1774 + 7) * 6 - 5322 % 4 + 3 * (6 - 1);
+set a = (1775 + 8) * 2 - 5325 % 5 + 4 * (7 - 2);
+set a = (1776 + 9) * 3 - 5328 % 6 + 5 * (8 - 0);
+set a = (1777 + 10) * 4 - 5331 % 7 + 6 * (9 - 1);
+set a = (1778 + 11) * 5 - 5334 % 1 + 7 * (10 - 2);
+set a = (1779 + 12) * 6 - 5337 % 2 + 8 * (11 - 0);
+set a = (1780 + 13) * 2 - 5340 % 3 + 9 * (12 - 1);
+set a = (1781 + 14) * 3 - 5343 % 4 + 10 * (0 - 2);
+set a = (1782 + 15) * 4 - 5346 % 5 + 0 * (1 - 0);
+set a = (1783 + 16) * 5 - 5349 % 6 + 1 * (2 - 1);
+set a = (1784 + 17) * 6 - 5352 % 7 + 2 * (3 - 2);
+set a = (1785 + 1) * 2 - 5355 % 1 + 3 * (4 - 0);
+set a = (1786 + 2) * 3 - 5358 % 2 + 4 * (5 - 1);
+set a = (1787 + 3) * 4 - 5361 % 3 + 5 * (6 - 2);
+set a = (1788 + 4) * 5 - 5364 % 4 + 6 * (7 - 0);
+set a = (1789 + 5) * 6 - 5367 % 5 + 7 * (8 - 1);
+set a = (1790 + 6) * 2 - 5370 % 6 + 8 * (9 - 2);
+set a = (1791 + 7) * 3 - 5373 % 7 + 9 * (10 - 0);
+set a = (1792 + 8) * 4 - 5376 % 1 + 10 * (11 - 1);
+set a = (1793 + 9) * 5 - 5379 % 2 + 0 * (12 - 2);
+set a = (1794 + 10) * 6 - 5382 % 3 + 1 * (0 - 0);
+set a = (1795 + 11) * 2 - 5385 % 4 + 2 * (1 - 1);
+set a = (1796 + 12) * 3 - 5388 % 5 + 3 * (2 - 2);
+set a = (1797 + 13) * 4 - 5391 % 6 + 4 * (3 - 0);
+set a = (1798 + 14) * 5 - 5394 % 7 + 5 * (4 - 1);
+set a = (1799 + 15) * 6 - 5397 % 1 + 6 * (5 - 2);
+set a = (1800 + 16) * 2 - 5400 % 2 + 7 * (6 - 0);
+set a = (1801 + 17) * 3 - 5403 % 3 + 8 * (7 - 1);
+set a = (1802 + 1) * 4 - 5406 % 4 + 9 * (8 - 2);
+set a = (1803 + 2) * 5 - 5409 % 5 + 10 * (9 - 0);
+set a = (1804 + 3) * 6 - 5412 % 6 + 0 * (10 - 1);
+set a = (1805 + 4) * 2 - 5415 % 7 + 1 * (11 - 2);
+set a = (1806 + 5) * 3 - 5418 % 1 + 2 * (12 - 0);
+set a = (1807 + 6) * 4 - 5421 % 2 + 3 * (0 - 1);
+set a = (1808 + 7) * 5 - 5424 % 3 + 4 * (1 - 2);
+set a = (1809 + 8) * 6 - 5427 % 4 + 5 * (2 - 0);
+set a = (1810 + 9) * 2 - 5430 % 5 + 6 * (3 - 1);
+set a = (1811 + 10) * 3 - 5433 % 6 + 7 * (4 - 2);
+set a = (1812 + 11) * 4 - 5436 % 7 + 8 * (5 - 0);
+set a = (1813 + 12) * 5 - 5439 % 1 + 9 * (6 - 1);
+set a = (1814 + 13) * 6 - 5442 % 2 + 10 * (7 - 2);
+set a = (1815 + 14) * 2 - 5445 % 3 + 0 * (8 - 0);
+set a = (1816 + 15) * 3 - 5448 % 4 + 1 * (9 - 1);
+set a = (1817 + 16) * 4 - 5451 % 5 + 2 * (10 - 2);
+set a = (1818 + 17) * 5 - 5454 % 6 + 3 * (11 - 0);
+set a = (1819 + 1) * 6 - 5457 % 7 + 4 * (12 - 1);
+set a = (1820 + 2) * 2 - 5460 % 1 + 5 * (0 - 2);
+set a = (1821 + 3) * 3 - 5463 % 2 + 6 * (1 - 0);
+set a = (1822 + 4) * 4 - 5466 % 3 + 7 * (2 - 1);
+set a = (1823 + 5) * 5 - 5469 % 4 + 8 * (3 - 2);
+set a = (1824 + 6) * 6 - 5472 % 5 + 9 * (4 - 0);
+set a = (1825 + 7) * 2 - 5475 % 6 + 10 * (5 - 1);
+set a = (1826 + 8) * 3 - 5478 % 7 + 0 * (6 - 2);
+set a = (1827 + 9) * 4 - 5481 % 1 + 1 * (7 - 0);
+set a = (1828 + 10) * 5 - 5484 % 2 + 2 * (8 - 1);
+set a = (1829 + 11) * 6 - 5487 % 3 + 3 * (9 - 2);
+set a = (1830 + 12) * 2 - 5490 % 4 + 4 * (10 - 0);
+set a = (1831 + 13) * 3 - 5493 % 5 + 5 * (11 - 1);
+set a = (1832 + 14) * 4 - 5496 % 6 + 6 * (12 - 2);
+set a = (1833 + 15) * 5 - 5499 % 7 + 7 * (0 - 0);
+set a = (1834 + 16) * 6 - 5502 % 1 + 8 * (1 - 1);
+set a = (1835 + 17) * 2 - 5505 % 2 + 9 * (2 - 2);
+set a = (1836 + 1) * 3 - 5508 % 3 + 10 * (3 - 0);
+set a = (1837 + 2) * 4 - 5511 % 4 + 0 * (4 - 1);
+set a = (1838 + 3) * 5 - 5514 % 5 + 1 * (5 - 2);
+set a = (1839 + 4) * 6 - 5517 % 6 + 2 * (6 - 0);
+set a = (1840 + 5) * 2 - 5520 % 7 + 3 * (7 - 1);
+set a = (1841 + 6) * 3 - 5523 % 1 + 4 * (8 - 2);
+set a = (1842 + 7) * 4 - 5526 % 2 + 5 * (9 - 0);
+set a = (1843 + 8) * 5 - 5529 % 3 + 6 * (10 - 1);
+set a = (1844 + 9) * 6 - 5532 % 4 + 7 * (11 - 2);
+set a = (1845 + 10) * 2 - 5535 % 5 + 8 * (12 - 0);
+set a = (1846 + 11) * 3 - 5538 % 6 + 9 * (0 - 1);
+set a = (1847 + 12) * 4 - 5541 % 7 + 10 * (1 - 2);
+set a = (1848 + 13) * 5 - 5544 % 1 + 0 * (2 - 0);
+set a = (1849 + 14) * 6 - 5547 % 2 + 1 * (3 - 1);
+set a = (1850 + 15) * 2 - 5550 % 3 + 2 * (4 - 2);
+set a = (1851 + 16) * 3 - 5553 % 4 + 3 * (5 - 0);
+set a = (1852 + 17) * 4 - 5556 % 5 + 4 * (6 - 1);
+set a = (1853 + 1) * 5 - 5559 % 6 + 5 * (7 - 2);
+set a = (1854 + 2) * 6 - 5562 % 7 + 6 * (8 - 0);
+set a = (1855 + 3) * 2 - 5565 % 1 + 7 * (9 - 1);
+set a = (1856 + 4) * 3 - 5568 % 2 + 8 * (10 - 2);
+set a = (1857 + 5) * 4 - 5571 % 3 + 9 * (11 - 0);
+set a = (1858 + 6) * 5 - 5574 % 4 + 10 * (12 - 1);
+set a = (1859 + 7) * 6 - 5577 % 5 + 0 * (0 - 2);
+set a = (1860 + 8) * 2 - 5580 % 6 + 1 * (1 - 0);
+set a = (1861 + 9) * 3 - 5583 % 7 + 2 * (2 - 1);
+set a = (1862 + 10) * 4 - 5586 % 1 + 3 * (3 - 2);
+set a = (1863 + 11) * 5 - 5589 % 2 + 4 * (4 - 0);
+set a = (1864 + 12) * 6 - 5592 % 3 + 5 * (5 - 1);
+set a = (1865 + 13) * 2 - 5595 % 4 + 6 * (6 - 2);
+set a = (1866 + 14) * 3 - 5598 % 5 + 7 * (7 - 0);
+set a = (1867 + 15) * 4 - 5601 % 6 + 8 * (8 - 1);
+set a = (1868 + 16) * 5 - 5604 % 7 + 9 * (9 - 2);
+set a = (1869 + 17) * 6 - 5607 % 1 + 10 * (10 - 0);
+set a = (1870 + 1) * 2 - 5610 % 2 + 0 * (11 - 1);
+set a = (1871 + 2) * 3 - 5613 % 3 + 1 * (12 - 2);
+set a = (1872 + 3) * 4 - 5616 % 4 + 2 * (0 - 0);
+set a = (1873 + 4) * 5 - 5619 % 5 + 3 * (1 - 1);
+set a = (1874 + 5) * 6 - 5622 % 6 + 4 * (2 - 2);
+set a = (1875 + 6) * 2 - 5625 % 7 + 5 * (3 - 0);
+set a = (1876 + 7) * 3 - 5628 % 1 + 6 * (4 - 1);
+set a = (1877 + 8) * 4 - 5631 % 2 + 7 * (5 - 2);
+set a = (1878 + 9) * 5 - 5634 % 3 + 8 * (6 - 0);
+set a = (1879 + 10) * 6 - 5637 % 4 + 9 * (7 - 1);
+set a = (1880 + 11) * 2 - 5640 % 5 + 10 * (8 - 2);
+set a = (1881 + 12) * 3 - 5643 % 6 + 0 * (9 - 0);
+set a = (1882 + 13) * 4 - 5646 % 7 + 1 * (10 - 1);
+set a = (1883 + 14) * 5 - 5649 % 1 + 2 * (11 - 2);
+set a = (1884 + 15) * 6 - 5652 % 2 + 3 * (12 - 0);
+set a = (1885 + 16) * 2 - 5655 % 3 + 4 * (0 - 1);
+set a = (1886 + 17) * 3 - 5658 % 4 + 5 * (1 - 2);
+set a = (1887 + 1) * 4 - 5661 % 5 + 6 * (2 - 0);
+set a = (1888 + 2) * 5 - 5664 % 6 + 7 * (3 - 1);
+set a = (1889 + 3) * 6 - 5667 % 7 + 8 * (4 - 2);
+set a = (1890 + 4) * 2 - 5670 % 1 + 9 * (5 - 0);
+set a = (1891 + 5) * 3 - 5673 % 2 + 10 * (6 - 1);
+set a = (1892 + 6) * 4 - 5676 % 3 + 0 * (7 - 2);
+set a = (1893 + 7) * 5 - 5679 % 4 + 1 * (8 - 0);
+set a = (1894 + 8) * 6 - 5682 % 5 + 2 * (9 - 1);
+set a = (1895 + 9) * 2 - 5685 % 6 + 3 * (10 - 2);
+set a = (1896 + 10) * 3 - 5688 % 7 + 4 * (11 - 0);
+set a = (1897 + 11) * 4 - 5691 % 1 + 5 * (12 - 1);
+set a = (1898 + 12) * 5 - 5694 % 2 + 6 * (0 - 2);
+set a = (1899 + 13) * 6 - 5697 % 3 + 7 * (1 - 0);
+set a = (1900 + 14) * 2 - 5700 % 4 + 8 * (2 - 1);
+set a = (1901 + 15) * 3 - 5703 % 5 + 9 * (3 - 2);
+set a = (1902 + 16) * 4 - 5706 % 6 + 10 * (4 - 0);
+set a = (1903 + 17) * 5 - 5709 % 7 + 0 * (5 - 1);
+set a = (1904 + 1) * 6 - 5712 % 1 + 1 * (6 - 2);
+set a = (1905 + 2) * 2 - 5715 % 2 + 2 * (7 - 0);
+set a = (1906 + 3) * 3 - 5718 % 3 + 3 * (8 - 1);
+set a = (1907 + 4) * 4 - 5721 % 4 + 4 * (9 - 2);
+set a = (1908 + 5) * 5 - 5724 % 5 + 5 * (10 - 0);
+set a = (1909 + 6) * 6 - 5727 % 6 + 6 * (11 - 1);
+set a = (1910 + 7) * 2 - 5730 % 7 + 7 * (12 - 2);
+set a = (1911 + 8) * 3 - 5733 % 1 + 8 * (0 - 0);
+set a = (1912 + 9) * 4 - 5736 % 2 + 9 * (1 - 1);
+set a = (1913 + 10) * 5 - 5739 % 3 + 10 * (2 - 2);
+set a = (1914 + 11) * 6 - 5742 % 4 + 0 * (3 - 0);
+set a = (1915 + 12) * 2 - 5745 % 5 + 1 * (4 - 1);
+set a = (1916 + 13) * 3 - 5748 % 6 + 2 * (5 - 2);
+set a = (1917 + 14) * 4 - 5751 % 7 + 3 * (6 - 0);
+set a = (1918 + 15) * 5 - 5754 % 1 + 4 * (7 - 1);
+set a = (1919 + 16) * 6 - 5757 % 2 + 5 * (8 - 2);
+set a = (1920 + 17) * 2 - 5760 % 3 + 6 * (9 - 0);
+set a = (1921 + 1) * 3 - 5763 % 4 + 7 * (10 - 1);
+set a = (1922 + 2) * 4 - 5766 % 5 + 8 * (11 - 2);
+set a = (1923 + 3) * 5 - 5769 % 6 + 9 * (12 - 0);
+set a = (1924 + 4) * 6 - 5772 % 7 + 10 * (0 - 1);
+set a = (1925 + 5) * 2 - 5775 % 1 + 0 * (1 - 2);
+set a = (1926 + 6) * 3 - 5778 % 2 + 1 * (2 - 0);
+set a = (1927 + 7) * 4 - 5781 % 3 + 2 * (3 - 1);
+set a = (1928 + 8) * 5 - 5784 % 4 + 3 * (4 - 2);
+set a = (1929 + 9) * 6 - 5787 % 5 + 4 * (5 - 0);
+set a = (1930 + 10) * 2 - 5790 % 6 + 5 * (6 - 1);
+set a = (1931 + 11) * 3 - 5793 % 7 + 6 * (7 - 2);
+set a = (1932 + 12) * 4 - 5796 % 1 + 7 * (8 - 0);
+set a = (1933 + 13) * 5 - 5799 % 2 + 8 * (9 - 1);
+set a = (1934 + 14) * 6 - 5802 % 3 + 9 * (10 - 2);
+set a = (1935 + 15) * 2 - 5805 % 4 + 10 * (11 - 0);
+set a = (1936 + 16) * 3 - 5808 % 5 + 0 * (12 - 1);
+set a = (1937 + 17) * 4 - 5811 % 6 + 1 * (0 - 2);
+set a = (1938 + 1) * 5 - 5814 % 7 + 2 * (1 - 0);
+set a = (1939 + 2) * 6 - 5817 % 1 + 3 * (2 - 1);
+set a = (1940 + 3) * 2 - 5820 % 2 + 4 * (3 - 2);
+set a = (1941 + 4) * 3 - 5823 % 3 + 5 * (4 - 0);
+set a = (1942 + 5) * 4 - 5826 % 4 + 6 * (5 - 1);
+set a = (1943 + 6) * 5 - 5829 % 5 + 7 * (6 - 2);
+set a = (1944 + 7) * 6 - 5832 % 6 + 8 * (7 - 0);
+set a = (1945 + 8) * 2 - 5835 % 7 + 9 * (8 - 1);
+set a = (1946 + 9) * 3 - 5838 % 1 + 10 * (9 - 2);
+set a = (1947 + 10) * 4 - 5841 % 2 + 0 * (10 - 0);
+set a = (1948 + 11) * 5 - 5844 % 3 + 1 * (11 - 1);
+set a = (1949 + 12) * 6 - 5847 % 4 + 2 * (12 - 2);
+set a = (1950 + 13) * 2 - 5850 % 5 + 3 * (0 - 0);
+set a = (1951 + 14) * 3 - 5853 % 6 + 4 * (1 - 1);
+set a = (1952 + 15) * 4 - 5856 % 7 + 5 * (2 - 2);
+set a = (1953 + 16) * 5 - 5859 % 1 + 6 * (3 - 0);
+set a = (1954 + 17) * 6 - 5862 % 2 + 7 * (4 - 1);
+set a = (1955 + 1) * 2 - 5865 % 3 + 8 * (5 - 2);
+set a = (1956 + 2) * 3 - 5868 % 4 + 9 * (6 - 0);
+set a = (1957 + 3) * 4 - 5871 % 5 + 10 * (7 - 1);
+set a = (1958 + 4) * 5 - 5874 % 6 + 0 * (8 - 2);
+set a = (1959 + 5) * 6 - 5877 % 7 + 1 * (9 - 0);
+set a = (1960 + 6) * 2 - 5880 % 1 + 2 * (10 - 1);
+set a = (1961 + 7) * 3 - 5883 % 2 + 3 * (11 - 2);
+set a = (1962 + 8) * 4 - 5886 % 3 + 4 * (12 - 0);
+set a = (1963 + 9) * 5 - 5889 % 4 + 5 * (0 - 1);
+set a = (1964 + 10) * 6 - 5892 % 5 + 6 * (1 - 2);
+set a = (1965 + 11) * 2 - 5895 % 6 + 7 * (2 - 0);
+set a = (1966 + 12) * 3 - 5898 % 7 + 8 * (3 - 1);
+set a = (1967 + 13) * 4 - 5901 % 1 + 9 * (4 - 2);
+set a = (1968 + 14) * 5 - 5904 % 2 + 10 * (5 - 0);
+set a = (1969 + 15) * 6 - 5907 % 3 + 0 * (6 - 1);
+set a = (1970 + 16) * 2 - 5910 % 4 + 1 * (7 - 2);
+set a = (1971 + 17) * 3 - 5913 % 5 + 2 * (8 - 0);
+set a = (1972 + 1) * 4 - 5916 % 6 + 3 * (9 - 1);
+set a = (1973 + 2) * 5 - 5919 % 7 + 4 * (10 - 2);
+set a = (1974 + 3) * 6 - 5922 % 1 + 5 * (11 - 0);
+set a = (1975 + 4) * 2 - 5925 % 2 + 6 * (12 - 1);
+set a = (1976 + 5) * 3 - 5928 % 3 + 7 * (0 - 2);
+set a = (1977 + 6) * 4 - 5931 % 4 + 8 * (1 - 0);
+set a = (1978 + 7) * 5 - 5934 % 5 + 9 * (2 - 1);
+set a = (1979 + 8) * 6 - 5937 % 6 + 10 * (3 - 2);
+set a = (1980 + 9) * 2 - 5940 % 7 + 0 * (4 - 0);
+set a = (1981 + 10) * 3 - 5943 % 1 + 1 * (5 - 1);
+set a = (1982 + 11) * 4 - 5946 % 2 + 2 * (6 - 2);
+set a = (1983 + 12) * 5 - 5949 % 3 + 3 * (7 - 0);
+set a = (1984 + 13) * 6 - 5952 % 4 + 4 * (8 - 1);
+set a = (1985 + 14) * 2 - 5955 % 5 + 5 * (9 - 2);
+set a = (1986 + 15) * 3 - 5958 % 6 + 6 * (10 - 0);
+set a = (1987 + 16) * 4 - 5961 % 7 + 7 * (11 - 1);
+set a = (1988 + 17) * 5 - 5964 % 1 + 8 * (12 - 2);
+set a = (1989 + 1) * 6 - 5967 % 2 + 9 * (0 - 0);
+set a = (1990 + 2) * 2 - 5970 % 3 + 10 * (1 - 1);
+set a = (1991 + 3) * 3 - 5973 % 4 + 0 * (2 - 2);
+set a = (1992 + 4) * 4 - 5976 % 5 + 1 * (3 - 0);
+set a = (1993 + 5) * 5 - 5979 % 6 + 2 * (4 - 1);
+set a = (1994 + 6) * 6 - 5982 % 7 + 3 * (5 - 2);
+set a = (1995 + 7) * 2 - 5985 % 1 + 4 * (6 - 0);
+set a = (1996 + 8) * 3 - 5988 % 2 + 5 * (7 - 1);
+set a = (1997 + 9) * 4 - 5991 % 3 + 6 * (8 - 2);
+set a = (1998 + 10) * 5 - 5994 % 4 + 7 * (9 - 0);
+set a = (1999 + 11) * 6 - 5997 % 5 + 8 * (10 - 1);
+print a;
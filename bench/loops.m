@@ -0,0 +1,11 @@
+set total = 0;
+set i = 0;
+while i < 300 {
+    set j = 0;
+    while j < 100 {
+        total += i * j % 7;
+        j += 1;
+    }
+    i += 1;
+}
+print total;
@@ -0,0 +1,1001 @@
+set v0 = 0;
+set v1 = v0 + 1;
+set v2 = v1 + 2;
+set v3 = v2 + 3;
+set v4 = v3 + 4;
+set v5 = v4 + 5;
+set v6 = v5 + 6;
+set v7 = v6 + 7;
+set v8 = v7 + 8;
+set v9 = v8 + 9;
+set v10 = v9 + 0;
+set v11 = v10 + 1;
+set v12 = v11 + 2;
+set v13 = v12 + 3;
+set v14 = v13 + 4;
+set v15 = v14 + 5;
+set v16 = v15 + 6;
+set v17 = v16 + 7;
+set v18 = v17 + 8;
+set v19 = v18 + 9;
+set v20 = v19 + 0;
+set v21 = v20 + 1;
+set v22 = v21 + 2;
+set v23 = v22 + 3;
+set v24 = v23 + 4;
+set v25 = v24 + 5;
+set v26 = v25 + 6;
+set v27 = v26 + 7;
+set v28 = v27 + 8;
+set v29 = v28 + 9;
+set v30 = v29 + 0;
+set v31 = v30 + 1;
+set v32 = v31 + 2;
+set v33 = v32 + 3;
+set v34 = v33 + 4;
+set v35 = v34 + 5;
+set v36 = v35 + 6;
+set v37 = v36 + 7;
+set v38 = v37 + 8;
+set v39 = v38 + 9;
+set v40 = v39 + 0;
+set v41 = v40 + 1;
+set v42 = v41 + 2;
+set v43 = v42 + 3;
+set v44 = v43 + 4;
+set v45 = v44 + 5;
+set v46 = v45 + 6;
+set v47 = v46 + 7;
+set v48 = v47 + 8;
+set v49 = v48 + 9;
+set v50 = v49 + 0;
+set v51 = v50 + 1;
+set v52 = v51 + 2;
+set v53 = v52 + 3;
+set v54 = v53 + 4;
+set v55 = v54 + 5;
+set v56 = v55 + 6;
+set v57 = v56 + 7;
+set v58 = v57 + 8;
+set v59 = v58 + 9;
+set v60 = v59 + 0;
+set v61 = v60 + 1;
+set v62 = v61 + 2;
+set v63 = v62 + 3;
+set v64 = v63 + 4;
+set v65 = v64 + 5;
+set v66 = v65 + 6;
+set v67 = v66 + 7;
+set v68 = v67 + 8;
+set v69 = v68 + 9;
+set v70 = v69 + 0;
+set v71 = v70 + 1;
+set v72 = v71 + 2;
+set v73 = v72 + 3;
+set v74 = v73 + 4;
+set v75 = v74 + 5;
+set v76 = v75 + 6;
+set v77 = v76 + 7;
+set v78 = v77 + 8;
+set v79 = v78 + 9;
+set v80 = v79 + 0;
+set v81 = v80 + 1;
+set v82 = v81 + 2;
+set v83 = v82 + 3;
+set v84 = v83 + 4;
+set v85 = v84 + 5;
+set v86 = v85 + 6;
+set v87 = v86 + 7;
+set v88 = v87 + 8;
+set v89 = v88 + 9;
+set v90 = v89 + 0;
+set v91 = v90 + 1;
+set v92 = v91 + 2;
+set v93 = v92 + 3;
+set v94 = v93 + 4;
+set v95 = v94 + 5;
+set v96 = v95 + 6;
+set v97 = v96 + 7;
+set v98 = v97 + 8;
+set v99 = v98 + 9;
+set v100 = v99 + 0;
+set v101 = v100 + 1;
+set v102 = v101 + 2;
+set v103 = v102 + 3;
+set v104 = v103 + 4;
+set v105 = v104 + 5;
+set v106 = v105 + 6;
+set v107 = v106 + 7;
+set v108 = v107 + 8;
+set v109 = v108 + 9;
+set v110 = v109 + 0;
+set v111 = v110 + 1;
+set v112 = v111 + 2;
+set v113 = v112 + 3;
+set v114 = v113 + 4;
+set v115 = v114 + 5;
+set v116 = v115 + 6;
+set v117 = v116 + 7;
+set v118 = v117 + 8;
+set v119 = v118 + 9;
+set v120 = v119 + 0;
+set v121 = v120 + 1;
+set v122 = v121 + 2;
+set v123 = v122 + 3;
+set v124 = v123 + 4;
+set v125 = v124 + 5;
+set v126 = v125 + 6;
+set v127 = v126 + 7;
+set v128 = v127 + 8;
+set v129 = v128 + 9;
+set v130 = v129 + 0;
+set v131 = v130 + 1;
+set v132 = v131 + 2;
+set v133 = v132 + 3;
+set v134 = v133 + 4;
+set v135 = v134 + 5;
+set v136 = v135 + 6;
+set v137 = v136 + 7;
+set v138 = v137 + 8;
+set v139 = v138 + 9;
+set v140 = v139 + 0;
+set v141 = v140 + 1;
+set v142 = v141 + 2;
+set v143 = v142 + 3;
+set v144 = v143 + 4;
+set v145 = v144 + 5;
+set v146 = v145 + 6;
+set v147 = v146 + 7;
+set v148 = v147 + 8;
+set v149 = v148 + 9;
+set v150 = v149 + 0;
+set v151 = v150 + 1;
+set v152 = v151 + 2;
+set v153 = v152 + 3;
+set v154 = v153 + 4;
+set v155 = v154 + 5;
+set v156 = v155 + 6;
+set v157 = v156 + 7;
+set v158 = v157 + 8;
+set v159 = v158 + 9;
+set v160 = v159 + 0;
+set v161 = v160 + 1;
+set v162 = v161 + 2;
+set v163 = v162 + 3;
+set v164 = v163 + 4;
+set v165 = v164 + 5;
+set v166 = v165 + 6;
+set v167 = v166 + 7;
+set v168 = v167 + 8;
+set v169 = v168 + 9;
+set v170 = v169 + 0;
+set v171 = v170 + 1;
+set v172 = v171 + 2;
+set v173 = v172 + 3;
+set v174 = v173 + 4;
+set v175 = v174 + 5;
+set v176 = v175 + 6;
+set v177 = v176 + 7;
+set v178 = v177 + 8;
+set v179 = v178 + 9;
+set v180 = v179 + 0;
+set v181 = v180 + 1;
+set v182 = v181 + 2;
+set v183 = v182 + 3;
+set v184 = v183 + 4;
+set v185 = v184 + 5;
+set v186 = v185 + 6;
+set v187 = v186 + 7;
+set v188 = v187 + 8;
+set v189 = v188 + 9;
+set v190 = v189 + 0;
+set v191 = v190 + 1;
+set v192 = v191 + 2;
+set v193 = v192 + 3;
+set v194 = v193 + 4;
+set v195 = v194 + 5;
+set v196 = v195 + 6;
+set v197 = v196 + 7;
+set v198 = v197 + 8;
+set v199 = v198 + 9;
+set v200 = v199 + 0;
+set v201 = v200 + 1;
+set v202 = v201 + 2;
+set v203 = v202 + 3;
+set v204 = v203 + 4;
+set v205 = v204 + 5;
+set v206 = v205 + 6;
+set v207 = v206 + 7;
+set v208 = v207 + 8;
+set v209 = v208 + 9;
+set v210 = v209 + 0;
+set v211 = v210 + 1;
+set v212 = v211 + 2;
+set v213 = v212 + 3;
+set v214 = v213 + 4;
+set v215 = v214 + 5;
+set v216 = v215 + 6;
+set v217 = v216 + 7;
+set v218 = v217 + 8;
+set v219 = v218 + 9;
+set v220 = v219 + 0;
+set v221 = v220 + 1;
+set v222 = v221 + 2;
+set v223 = v222 + 3;
+set v224 = v223 + 4;
+set v225 = v224 + 5;
+set v226 = v225 + 6;
+set v227 = v226 + 7;
+set v228 = v227 + 8;
+set v229 = v228 + 9;
+set v230 = v229 + 0;
+set v231 = v230 + 1;
+set v232 = v231 + 2;
+set v233 = v232 + 3;
+set v234 = v233 + 4;
+set v235 = v234 + 5;
+set v236 = v235 + 6;
+set v237 = v236 + 7;
+set v238 = v237 + 8;
+set v239 = v238 + 9;
+set v240 = v239 + 0;
+set v241 = v240 + 1;
+set v242 = v241 + 2;
+set v243 = v242 + 3;
+set v244 = v243 + 4;
+set v245 = v244 + 5;
+set v246 = v245 + 6;
+set v247 = v246 + 7;
+set v248 = v247 + 8;
+set v249 = v248 + 9;
+set v250 = v249 + 0;
+set v251 = v250 + 1;
+set v252 = v251 + 2;
+set v253 = v252 + 3;
+set v254 = v253 + 4;
+set v255 = v254 + 5;
+set v256 = v255 + 6;
+set v257 = v256 + 7;
+set v258 = v257 + 8;
+set v259 = v258 + 9;
+set v260 = v259 + 0;
+set v261 = v260 + 1;
+set v262 = v261 + 2;
+set v263 = v262 + 3;
+set v264 = v263 + 4;
+set v265 = v264 + 5;
+set v266 = v265 + 6;
+set v267 = v266 + 7;
+set v268 = v267 + 8;
+set v269 = v268 + 9;
+set v270 = v269 + 0;
+set v271 = v270 + 1;
+set v272 = v271 + 2;
+set v273 = v272 + 3;
+set v274 = v273 + 4;
+set v275 = v274 + 5;
+set v276 = v275 + 6;
+set v277 = v276 + 7;
+set v278 = v277 + 8;
+set v279 = v278 + 9;
+set v280 = v279 + 0;
+set v281 = v280 + 1;
+set v282 = v281 + 2;
+set v283 = v282 + 3;
+set v284 = v283 + 4;
+set v285 = v284 + 5;
+set v286 = v285 + 6;
+set v287 = v286 + 7;
+set v288 = v287 + 8;
+set v289 = v288 + 9;
+set v290 = v289 + 0;
+set v291 = v290 + 1;
+set v292 = v291 + 2;
+set v293 = v292 + 3;
+set v294 = v293 + 4;
+set v295 = v294 + 5;
+set v296 = v295 + 6;
+set v297 = v296 + 7;
+set v298 = v297 + 8;
+set v299 = v298 + 9;
+set v300 = v299 + 0;
+set v301 = v300 + 1;
+set v302 = v301 + 2;
+set v303 = v302 + 3;
+set v304 = v303 + 4;
+set v305 = v304 + 5;
+set v306 = v305 + 6;
+set v307 = v306 + 7;
+set v308 = v307 + 8;
+set v309 = v308 + 9;
+set v310 = v309 + 0;
+set v311 = v310 + 1;
+set v312 = v311 + 2;
+set v313 = v312 + 3;
+set v314 = v313 + 4;
+set v315 = v314 + 5;
+set v316 = v315 + 6;
+set v317 = v316 + 7;
+set v318 = v317 + 8;
+set v319 = v318 + 9;
+set v320 = v319 + 0;
+set v321 = v320 + 1;
+set v322 = v321 + 2;
+set v323 = v322 + 3;
+set v324 = v323 + 4;
+set v325 = v324 + 5;
+set v326 = v325 + 6;
+set v327 = v326 + 7;
+set v328 = v327 + 8;
+set v329 = v328 + 9;
+set v330 = v329 + 0;
+set v331 = v330 + 1;
+set v332 = v331 + 2;
+set v333 = v332 + 3;
+set v334 = v333 + 4;
+set v335 = v334 + 5;
+set v336 = v335 + 6;
+set v337 = v336 + 7;
+set v338 = v337 + 8;
+set v339 = v338 + 9;
+set v340 = v339 + 0;
+set v341 = v340 + 1;
+set v342 = v341 + 2;
+set v343 = v342 + 3;
+set v344 = v343 + 4;
+set v345 = v344 + 5;
+set v346 = v345 + 6;
+set v347 = v346 + 7;
+set v348 = v347 + 8;
+set v349 = v348 + 9;
+set v350 = v349 + 0;
+set v351 = v350 + 1;
+set v352 = v351 + 2;
+set v353 = v352 + 3;
+set v354 = v353 + 4;
+set v355 = v354 + 5;
+set v356 = v355 + 6;
+set v357 = v356 + 7;
+set v358 = v357 + 8;
+set v359 = v358 + 9;
+set v360 = v359 + 0;
+set v361 = v360 + 1;
+set v362 = v361 + 2;
+set v363 = v362 + 3;
+set v364 = v363 + 4;
+set v365 = v364 + 5;
+set v366 = v365 + 6;
+set v367 = v366 + 7;
+set v368 = v367 + 8;
+set v369 = v368 + 9;
+set v370 = v369 + 0;
+set v371 = v370 + 1;
+set v372 = v371 + 2;
+set v373 = v372 + 3;
+set v374 = v373 + 4;
+set v375 = v374 + 5;
+set v376 = v375 + 6;
+set v377 = v376 + 7;
+set v378 = v377 + 8;
+set v379 = v378 + 9;
+set v380 = v379 + 0;
+set v381 = v380 + 1;
+set v382 = v381 + 2;
+set v383 = v382 + 3;
+set v384 = v383 + 4;
+set v385 = v384 + 5;
+set v386 = v385 + 6;
+set v387 = v386 + 7;
+set v388 = v387 + 8;
+set v389 = v388 + 9;
+set v390 = v389 + 0;
+set v391 = v390 + 1;
+set v392 = v391 + 2;
+set v393 = v392 + 3;
+set v394 = v393 + 4;
+set v395 = v394 + 5;
+set v396 = v395 + 6;
+set v397 = v396 + 7;
+set v398 = v397 + 8;
+set v399 = v398 + 9;
+set v400 = v399 + 0;
+set v401 = v400 + 1;
+set v402 = v401 + 2;
+set v403 = v402 + 3;
+set v404 = v403 + 4;
+set v405 = v404 + 5;
+set v406 = v405 + 6;
+set v407 = v406 + 7;
+set v408 = v407 + 8;
+set v409 = v408 + 9;
+set v410 = v409 + 0;
+set v411 = v410 + 1;
+set v412 = v411 + 2;
+set v413 = v412 + 3;
+set v414 = v413 + 4;
+set v415 = v414 + 5;
+set v416 = v415 + 6;
+set v417 = v416 + 7;
+set v418 = v417 + 8;
+set v419 = v418 + 9;
+set v420 = v419 + 0;
+set v421 = v420 + 1;
+set v422 = v421 + 2;
+set v423 = v422 + 3;
+set v424 = v423 + 4;
+set v425 = v424 + 5;
+set v426 = v425 + 6;
+set v427 = v426 + 7;
+set v428 = v427 + 8;
+set v429 = v428 + 9;
+set v430 = v429 + 0;
+set v431 = v430 + 1;
+set v432 = v431 + 2;
+set v433 = v432 + 3;
+set v434 = v433 + 4;
+set v435 = v434 + 5;
+set v436 = v435 + 6;
+set v437 = v436 + 7;
+set v438 = v437 + 8;
+set v439 = v438 + 9;
+set v440 = v439 + 0;
+set v441 = v440 + 1;
+set v442 = v441 + 2;
+set v443 = v442 + 3;
+set v444 = v443 + 4;
+set v445 = v444 + 5;
+set v446 = v445 + 6;
+set v447 = v446 + 7;
+set v448 = v447 + 8;
+set v449 = v448 + 9;
+set v450 = v449 + 0;
+set v451 = v450 + 1;
+set v452 = v451 + 2;
+set v453 = v452 + 3;
+set v454 = v453 + 4;
+set v455 = v454 + 5;
+set v456 = v455 + 6;
+set v457 = v456 + 7;
+set v458 = v457 + 8;
+set v459 = v458 + 9;
+set v460 = v459 + 0;
+set v461 = v460 + 1;
+set v462 = v461 + 2;
+set v463 = v462 + 3;
+set v464 = v463 + 4;
+set v465 = v464 + 5;
+set v466 = v465 + 6;
+set v467 = v466 + 7;
+set v468 = v467 + 8;
+set v469 = v468 + 9;
+set v470 = v469 + 0;
+set v471 = v470 + 1;
+set v472 = v471 + 2;
+set v473 = v472 + 3;
+set v474 = v473 + 4;
+set v475 = v474 + 5;
+set v476 = v475 + 6;
+set v477 = v476 + 7;
+set v478 = v477 + 8;
+set v479 = v478 + 9;
+set v480 = v479 + 0;
+set v481 = v480 + 1;
+set v482 = v481 + 2;
+set v483 = v482 + 3;
+set v484 = v483 + 4;
+set v485 = v484 + 5;
+set v486 = v485 + 6;
+set v487 = v486 + 7;
+set v488 = v487 + 8;
+set v489 = v488 + 9;
+set v490 = v489 + 0;
+set v491 = v490 + 1;
+set v492 = v491 + 2;
+set v493 = v492 + 3;
+set v494 = v493 + 4;
+set v495 = v494 + 5;
+set v496 = v495 + 6;
+set v497 = v496 + 7;
+set v498 = v497 + 8;
+set v499 = v498 + 9;
+set v500 = v499 + 0;
+set v501 = v500 + 1;
+set v502 = v501 + 2;
+set v503 = v502 + 3;
+set v504 = v503 + 4;
+set v505 = v504 + 5;
+set v506 = v505 + 6;
+set v507 = v506 + 7;
+set v508 = v507 + 8;
+set v509 = v508 + 9;
+set v510 = v509 + 0;
+set v511 = v510 + 1;
+set v512 = v511 + 2;
+set v513 = v512 + 3;
+set v514 = v513 + 4;
+set v515 = v514 + 5;
+set v516 = v515 + 6;
+set v517 = v516 + 7;
+set v518 = v517 + 8;
+set v519 = v518 + 9;
+set v520 = v519 + 0;
+set v521 = v520 + 1;
+set v522 = v521 + 2;
+set v523 = v522 + 3;
+set v524 = v523 + 4;
+set v525 = v524 + 5;
+set v526 = v525 + 6;
+set v527 = v526 + 7;
+set v528 = v527 + 8;
+set v529 = v528 + 9;
+set v530 = v529 + 0;
+set v531 = v530 + 1;
+set v532 = v531 + 2;
+set v533 = v532 + 3;
+set v534 = v533 + 4;
+set v535 = v534 + 5;
+set v536 = v535 + 6;
+set v537 = v536 + 7;
+set v538 = v537 + 8;
+set v539 = v538 + 9;
+set v540 = v539 + 0;
+set v541 = v540 + 1;
+set v542 = v541 + 2;
+set v543 = v542 + 3;
+set v544 = v543 + 4;
+set v545 = v544 + 5;
+set v546 = v545 + 6;
+set v547 = v546 + 7;
+set v548 = v547 + 8;
+set v549 = v548 + 9;
+set v550 = v549 + 0;
+set v551 = v550 + 1;
+set v552 = v551 + 2;
+set v553 = v552 + 3;
+set v554 = v553 + 4;
+set v555 = v554 + 5;
+set v556 = v555 + 6;
+set v557 = v556 + 7;
+set v558 = v557 + 8;
+set v559 = v558 + 9;
+set v560 = v559 + 0;
+set v561 = v560 + 1;
+set v562 = v561 + 2;
+set v563 = v562 + 3;
+set v564 = v563 + 4;
+set v565 = v564 + 5;
+set v566 = v565 + 6;
+set v567 = v566 + 7;
+set v568 = v567 + 8;
+set v569 = v568 + 9;
+set v570 = v569 + 0;
+set v571 = v570 + 1;
+set v572 = v571 + 2;
+set v573 = v572 + 3;
+set v574 = v573 + 4;
+set v575 = v574 + 5;
+set v576 = v575 + 6;
+set v577 = v576 + 7;
+set v578 = v577 + 8;
+set v579 = v578 + 9;
+set v580 = v579 + 0;
+set v581 = v580 + 1;
+set v582 = v581 + 2;
+set v583 = v582 + 3;
+set v584 = v583 + 4;
+set v585 = v584 + 5;
+set v586 = v585 + 6;
+set v587 = v586 + 7;
+set v588 = v587 + 8;
+set v589 = v588 + 9;
+set v590 = v589 + 0;
+set v591 = v590 + 1;
+set v592 = v591 + 2;
+set v593 = v592 + 3;
+set v594 = v593 + 4;
+set v595 = v594 + 5;
+set v596 = v595 + 6;
+set v597 = v596 + 7;
+set v598 = v597 + 8;
+set v599 = v598 + 9;
+set v600 = v599 + 0;
+set v601 = v600 + 1;
+set v602 = v601 + 2;
+set v603 = v602 + 3;
+set v604 = v603 + 4;
+set v605 = v604 + 5;
+set v606 = v605 + 6;
+set v607 = v606 + 7;
+set v608 = v607 + 8;
+set v609 = v608 + 9;
+set v610 = v609 + 0;
+set v611 = v610 + 1;
+set v612 = v611 + 2;
+set v613 = v612 + 3;
+set v614 = v613 + 4;
+set v615 = v614 + 5;
+set v616 = v615 + 6;
+set v617 = v616 + 7;
+set v618 = v617 + 8;
+set v619 = v618 + 9;
+set v620 = v619 + 0;
+set v621 = v620 + 1;
+set v622 = v621 + 2;
+set v623 = v622 + 3;
+set v624 = v623 + 4;
+set v625 = v624 + 5;
+set v626 = v625 + 6;
+set v627 = v626 + 7;
+set v628 = v627 + 8;
+set v629 = v628 + 9;
+set v630 = v629 + 0;
+set v631 = v630 + 1;
+set v632 = v631 + 2;
+set v633 = v632 + 3;
+set v634 = v633 + 4;
+set v635 = v634 + 5;
+set v636 = v635 + 6;
+set v637 = v636 + 7;
+set v638 = v637 + 8;
+set v639 = v638 + 9;
+set v640 = v639 + 0;
+set v641 = v640 + 1;
+set v642 = v641 + 2;
+set v643 = v642 + 3;
+set v644 = v643 + 4;
+set v645 = v644 + 5;
+set v646 = v645 + 6;
+set v647 = v646 + 7;
+set v648 = v647 + 8;
+set v649 = v648 + 9;
+set v650 = v649 + 0;
+set v651 = v650 + 1;
+set v652 = v651 + 2;
+set v653 = v652 + 3;
+set v654 = v653 + 4;
+set v655 = v654 + 5;
+set v656 = v655 + 6;
+set v657 = v656 + 7;
+set v658 = v657 + 8;
+set v659 = v658 + 9;
+set v660 = v659 + 0;
+set v661 = v660 + 1;
+set v662 = v661 + 2;
+set v663 = v662 + 3;
+set v664 = v663 + 4;
+set v665 = v664 + 5;
+set v666 = v665 + 6;
+set v667 = v666 + 7;
+set v668 = v667 + 8;
+set v669 = v668 + 9;
+set v670 = v669 + 0;
+set v671 = v670 + 1;
+set v672 = v671 + 2;
+set v673 = v672 + 3;
+set v674 = v673 + 4;
+set v675 = v674 + 5;
+set v676 = v675 + 6;
+set v677 = v676 + 7;
+set v678 = v677 + 8;
+set v679 = v678 + 9;
+set v680 = v679 + 0;
+set v681 = v680 + 1;
+set v682 = v681 + 2;
+set v683 = v682 + 3;
+set v684 = v683 + 4;
+set v685 = v684 + 5;
+set v686 = v685 + 6;
+set v687 = v686 + 7;
+set v688 = v687 + 8;
+set v689 = v688 + 9;
+set v690 = v689 + 0;
+set v691 = v690 + 1;
+set v692 = v691 + 2;
+set v693 = v692 + 3;
+set v694 = v693 + 4;
+set v695 = v694 + 5;
+set v696 = v695 + 6;
+set v697 = v696 + 7;
+set v698 = v697 + 8;
+set v699 = v698 + 9;
+set v700 = v699 + 0;
+set v701 = v700 + 1;
+set v702 = v701 + 2;
+set v703 = v702 + 3;
+set v704 = v703 + 4;
+set v705 = v704 + 5;
+set v706 = v705 + 6;
+set v707 = v706 + 7;
+set v708 = v707 + 8;
+set v709 = v708 + 9;
+set v710 = v709 + 0;
+set v711 = v710 + 1;
+set v712 = v711 + 2;
+set v713 = v712 + 3;
+set v714 = v713 + 4;
+set v715 = v714 + 5;
+set v716 = v715 + 6;
+set v717 = v716 + 7;
+set v718 = v717 + 8;
+set v719 = v718 + 9;
+set v720 = v719 + 0;
+set v721 = v720 + 1;
+set v722 = v721 + 2;
+set v723 = v722 + 3;
+set v724 = v723 + 4;
+set v725 = v724 + 5;
+set v726 = v725 + 6;
+set v727 = v726 + 7;
+set v728 = v727 + 8;
+set v729 = v728 + 9;
+set v730 = v729 + 0;
+set v731 = v730 + 1;
+set v732 = v731 + 2;
+set v733 = v732 + 3;
+set v734 = v733 + 4;
+set v735 = v734 + 5;
+set v736 = v735 + 6;
+set v737 = v736 + 7;
+set v738 = v737 + 8;
+set v739 = v738 + 9;
+set v740 = v739 + 0;
+set v741 = v740 + 1;
+set v742 = v741 + 2;
+set v743 = v742 + 3;
+set v744 = v743 + 4;
+set v745 = v744 + 5;
+set v746 = v745 + 6;
+set v747 = v746 + 7;
+set v748 = v747 + 8;
+set v749 = v748 + 9;
+set v750 = v749 + 0;
+set v751 = v750 + 1;
+set v752 = v751 + 2;
+set v753 = v752 + 3;
+set v754 = v753 + 4;
+set v755 = v754 + 5;
+set v756 = v755 + 6;
+set v757 = v756 + 7;
+set v758 = v757 + 8;
+set v759 = v758 + 9;
+set v760 = v759 + 0;
+set v761 = v760 + 1;
+set v762 = v761 + 2;
+set v763 = v762 + 3;
+set v764 = v763 + 4;
+set v765 = v764 + 5;
+set v766 = v765 + 6;
+set v767 = v766 + 7;
+set v768 = v767 + 8;
+set v769 = v768 + 9;
+set v770 = v769 + 0;
+set v771 = v770 + 1;
+set v772 = v771 + 2;
+set v773 = v772 + 3;
+set v774 = v773 + 4;
+set v775 = v774 + 5;
+set v776 = v775 + 6;
+set v777 = v776 + 7;
+set v778 = v777 + 8;
+set v779 = v778 + 9;
+set v780 = v779 + 0;
+set v781 = v780 + 1;
+set v782 = v781 + 2;
+set v783 = v782 + 3;
+set v784 = v783 + 4;
+set v785 = v784 + 5;
+set v786 = v785 + 6;
+set v787 = v786 + 7;
+set v788 = v787 + 8;
+set v789 = v788 + 9;
+set v790 = v789 + 0;
+set v791 = v790 + 1;
+set v792 = v791 + 2;
+set v793 = v792 + 3;
+set v794 = v793 + 4;
+set v795 = v794 + 5;
+set v796 = v795 + 6;
+set v797 = v796 + 7;
+set v798 = v797 + 8;
+set v799 = v798 + 9;
+set v800 = v799 + 0;
+set v801 = v800 + 1;
+set v802 = v801 + 2;
+set v803 = v802 + 3;
+set v804 = v803 + 4;
+set v805 = v804 + 5;
+set v806 = v805 + 6;
+set v807 = v806 + 7;
+set v808 = v807 + 8;
+set v809 = v808 + 9;
+set v810 = v809 + 0;
+set v811 = v810 + 1;
+set v812 = v811 + 2;
+set v813 = v812 + 3;
+set v814 = v813 + 4;
+set v815 = v814 + 5;
+set v816 = v815 + 6;
+set v817 = v816 + 7;
+set v818 = v817 + 8;
+set v819 = v818 + 9;
+set v820 = v819 + 0;
+set v821 = v820 + 1;
+set v822 = v821 + 2;
+set v823 = v822 + 3;
+set v824 = v823 + 4;
+set v825 = v824 + 5;
+set v826 = v825 + 6;
+set v827 = v826 + 7;
+set v828 = v827 + 8;
+set v829 = v828 + 9;
+set v830 = v829 + 0;
+set v831 = v830 + 1;
+set v832 = v831 + 2;
+set v833 = v832 + 3;
+set v834 = v833 + 4;
+set v835 = v834 + 5;
+set v836 = v835 + 6;
+set v837 = v836 + 7;
+set v838 = v837 + 8;
+set v839 = v838 + 9;
+set v840 = v839 + 0;
+set v841 = v840 + 1;
+set v842 = v841 + 2;
+set v843 = v842 + 3;
+set v844 = v843 + 4;
+set v845 = v844 + 5;
+set v846 = v845 + 6;
+set v847 = v846 + 7;
+set v848 = v847 + 8;
+set v849 = v848 + 9;
+set v850 = v849 + 0;
+set v851 = v850 + 1;
+set v852 = v851 + 2;
+set v853 = v852 + 3;
+set v854 = v853 + 4;
+set v855 = v854 + 5;
+set v856 = v855 + 6;
+set v857 = v856 + 7;
+set v858 = v857 + 8;
+set v859 = v858 + 9;
+set v860 = v859 + 0;
+set v861 = v860 + 1;
+set v862 = v861 + 2;
+set v863 = v862 + 3;
+set v864 = v863 + 4;
+set v865 = v864 + 5;
+set v866 = v865 + 6;
+set v867 = v866 + 7;
+set v868 = v867 + 8;
+set v869 = v868 + 9;
+set v870 = v869 + 0;
+set v871 = v870 + 1;
+set v872 = v871 + 2;
+set v873 = v872 + 3;
+set v874 = v873 + 4;
+set v875 = v874 + 5;
+set v876 = v875 + 6;
+set v877 = v876 + 7;
+set v878 = v877 + 8;
+set v879 = v878 + 9;
+set v880 = v879 + 0;
+set v881 = v880 + 1;
+set v882 = v881 + 2;
+set v883 = v882 + 3;
+set v884 = v883 + 4;
+set v885 = v884 + 5;
+set v886 = v885 + 6;
+set v887 = v886 + 7;
+set v888 = v887 + 8;
+set v889 = v888 + 9;
+set v890 = v889 + 0;
+set v891 = v890 + 1;
+set v892 = v891 + 2;
+set v893 = v892 + 3;
+set v894 = v893 + 4;
+set v895 = v894 + 5;
+set v896 = v895 + 6;
+set v897 = v896 + 7;
+set v898 = v897 + 8;
+set v899 = v898 + 9;
+set v900 = v899 + 0;
+set v901 = v900 + 1;
+set v902 = v901 + 2;
+set v903 = v902 + 3;
+set v904 = v903 + 4;
+set v905 = v904 + 5;
+set v906 = v905 + 6;
+set v907 = v906 + 7;
+set v908 = v907 + 8;
+set v909 = v908 + 9;
+set v910 = v909 + 0;
+set v911 = v910 + 1;
+set v912 = v911 + 2;
+set v913 = v912 + 3;
+set v914 = v913 + 4;
+set v915 = v914 + 5;
+set v916 = v915 + 6;
+set v917 = v916 + 7;
+set v918 = v917 + 8;
+set v919 = v918 + 9;
+set v920 = v919 + 0;
+set v921 = v920 + 1;
+set v922 = v921 + 2;
+set v923 = v922 + 3;
+set v924 = v923 + 4;
+set v925 = v924 + 5;
+set v926 = v925 + 6;
+set v927 = v926 + 7;
+set v928 = v927 + 8;
+set v929 = v928 + 9;
+set v930 = v929 + 0;
+set v931 = v930 + 1;
+set v932 = v931 + 2;
+set v933 = v932 + 3;
+set v934 = v933 + 4;
+set v935 = v934 + 5;
+set v936 = v935 + 6;
+set v937 = v936 + 7;
+set v938 = v937 + 8;
+set v939 = v938 + 9;
+set v940 = v939 + 0;
+set v941 = v940 + 1;
+set v942 = v941 + 2;
+set v943 = v942 + 3;
+set v944 = v943 + 4;
+set v945 = v944 + 5;
+set v946 = v945 + 6;
+set v947 = v946 + 7;
+set v948 = v947 + 8;
+set v949 = v948 + 9;
+set v950 = v949 + 0;
+set v951 = v950 + 1;
+set v952 = v951 + 2;
+set v953 = v952 + 3;
+set v954 = v953 + 4;
+set v955 = v954 + 5;
+set v956 = v955 + 6;
+set v957 = v956 + 7;
+set v958 = v957 + 8;
+set v959 = v958 + 9;
+set v960 = v959 + 0;
+set v961 = v960 + 1;
+set v962 = v961 + 2;
+set v963 = v962 + 3;
+set v964 = v963 + 4;
+set v965 = v964 + 5;
+set v966 = v965 + 6;
+set v967 = v966 + 7;
+set v968 = v967 + 8;
+set v969 = v968 + 9;
+set v970 = v969 + 0;
+set v971 = v970 + 1;
+set v972 = v971 + 2;
+set v973 = v972 + 3;
+set v974 = v973 + 4;
+set v975 = v974 + 5;
+set v976 = v975 + 6;
+set v977 = v976 + 7;
+set v978 = v977 + 8;
+set v979 = v978 + 9;
+set v980 = v979 + 0;
+set v981 = v980 + 1;
+set v982 = v981 + 2;
+set v983 = v982 + 3;
+set v984 = v983 + 4;
+set v985 = v984 + 5;
+set v986 = v985 + 6;
+set v987 = v986 + 7;
+set v988 = v987 + 8;
+set v989 = v988 + 9;
+set v990 = v989 + 0;
+set v991 = v990 + 1;
+set v992 = v991 + 2;
+set v993 = v992 + 3;
+set v994 = v993 + 4;
+set v995 = v994 + 5;
+set v996 = v995 + 6;
+set v997 = v996 + 7;
+set v998 = v997 + 8;
+set v999 = v998 + 9;
+print v999;
@@ -0,0 +1,21 @@
+//! Times the lexer, parser, tree interpreter and vm on the programs in bench/, with
+//! `cargo bench`. It only needs std, so it's a plain main instead of a harness
+
+use mahou::bench::{bench, corpus, report, CountingAllocator, Timings};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// How many times each part runs, the middle time is the one shown
+const ITERATIONS: usize = 10;
+
+fn main() {
+    let mut results: Vec<(String, Timings)> = Vec::new();
+    for (name, program) in corpus() {
+        match bench(&program, ITERATIONS) {
+            Ok(timings) => results.push((name.to_string(), timings)),
+            Err(message) => panic!("bench/{}.m failed: {}", name, message),
+        }
+    }
+    print!("{}", report(&results));
+}
@@ -0,0 +1,33 @@
+/* Using mahou from C
+ *
+ *   cargo build --release --features mahou-ffi
+ *   cc ffi/example.c -Iffi -Ltarget/release -lmahou -o example
+ *   LD_LIBRARY_PATH=target/release ./example
+ */
+
+#include <stdio.h>
+#include "mahou.h"
+
+int main(void) {
+    MahouEngine *engine = mahou_new_engine();
+    mahou_engine_set_int(engine, "x", 3);
+    mahou_engine_set_string(engine, "name", "c");
+
+    char *value = mahou_engine_eval(engine, "set y = x * 2;\n\"hello {name}, y is {y}\"");
+    if (value == NULL) {
+        fprintf(stderr, "%s", mahou_last_error());
+        return 1;
+    }
+    printf("%s\n", value);
+    mahou_free_string(value);
+
+    char *y = mahou_engine_get(engine, "y");
+    printf("y = %s\n", y);
+    mahou_free_string(y);
+
+    if (mahou_engine_eval(engine, "print x / \"a\";") == NULL) {
+        fprintf(stderr, "%s", mahou_last_error());
+    }
+    mahou_free_engine(engine);
+    return 0;
+}
@@ -0,0 +1,46 @@
+/* The functions for embedding mahou, from src/ffi.rs. Build the library with
+ * `cargo build --release --features mahou-ffi` and link against libmahou.
+ * Strings given back by mahou have to go back to mahou_free_string */
+
+#ifndef MAHOU_H
+#define MAHOU_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An engine keeps its variables and functions between each eval */
+typedef struct MahouEngine MahouEngine;
+
+/* Run a whole program once with stdin and stdout, like `mahou --eval`. Gives
+ * back the code it exited with, or -1 if it stopped with an error */
+int mahou_eval(const char *source);
+
+/* A new engine, which has to go to mahou_free_engine once it's done */
+MahouEngine *mahou_new_engine(void);
+void mahou_free_engine(MahouEngine *engine);
+
+/* Run some of the program on the engine, giving back the value of the expression
+ * at the end the way it would print, or NULL if it stopped with an error */
+char *mahou_engine_eval(MahouEngine *engine, const char *source);
+
+/* Set a variable on the engine, giving back 0 or -1 if it couldn't */
+int mahou_engine_set_int(MahouEngine *engine, const char *name, int64_t value);
+int mahou_engine_set_float(MahouEngine *engine, const char *name, double value);
+int mahou_engine_set_string(MahouEngine *engine, const char *name, const char *value);
+
+/* The variable the way it would print, or NULL if it isn't set */
+char *mahou_engine_get(MahouEngine *engine, const char *name);
+
+/* The error from the last function that failed on this thread, or NULL */
+const char *mahou_last_error(void);
+
+void mahou_free_string(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
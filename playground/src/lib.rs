@@ -0,0 +1,49 @@
+//! The compiler built for the browser, so the playground can lex, parse, compile
+//! and run programs without a server. Nothing here touches stdin or stdout, the
+//! program's input is empty and what it prints is kept and given back
+
+use mahou::error::MahouError;
+use std::io;
+use wasm_bindgen::prelude::*;
+
+/// The python for the program, or the rendered error
+#[wasm_bindgen]
+pub fn compile_to_python(source: &str) -> Result<String, JsValue> {
+    mahou::compile(source).map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// Run the program with the interpreter, giving back what it printed with any
+/// error at the end
+#[wasm_bindgen]
+pub fn run(source: &str) -> String {
+    let mut output: Vec<u8> = Vec::new();
+    let ran: Result<i32, MahouError> = mahou::run(source, io::empty(), &mut output);
+    let mut printed: String = String::from_utf8_lossy(&output).to_string();
+    match ran {
+        Ok(0) => {}
+        Ok(code) => printed.push_str(&format!("exited with code {}\n", code)),
+        Err(error) => printed.push_str(&error.to_string()),
+    }
+    printed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The same functions the page calls, run on the host. An error is a JsValue,
+    // which can only be made on wasm, so these only go through the ones that work
+    #[test]
+    fn run_test() {
+        assert_eq!(run("set a = 2;\nprint a * 3;"), "6\n");
+        assert_eq!(run("exit 3;"), "exited with code 3\n");
+        assert!(run("print 1 / 0;").contains("division by zero"));
+        // The clock builtins are there off of wasm
+        assert_eq!(run("print elapsed() >= 0;"), "True\n");
+    }
+
+    #[test]
+    fn compile_test() {
+        assert_eq!(compile_to_python("print 1 + 2;").unwrap(), "print(1 + 2)\n");
+    }
+}
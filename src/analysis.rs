@@ -0,0 +1,506 @@
+//! Checks that look for mistakes in the program without running it
+
+use crate::ast::{walk_expr, walk_stmt, Expr, Stmt, Visit};
+use crate::formatter::dict_braces;
+use crate::intern::Text;
+use crate::interpreter::int_mod;
+use crate::lexer::{hole_tokens, Token, Tokens};
+use crate::parser::ParseError;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// Where a jump in the block lands, if the offset is a constant that stays inside of it
+pub fn jump_target(index: usize, offset: &Expr, len: usize) -> Option<usize> {
+    let target: i64 = (index as i64).checked_add(fold_int(offset)?)?;
+    if target >= 0 && target as usize <= len {
+        Some(target as usize)
+    } else {
+        None
+    }
+}
+
+/// Work out the value of an expression made only of whole numbers, if it has one
+pub fn fold_int(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Numeric(number) => number.parse().ok(),
+        Expr::Unary {
+            operator: Tokens::Minus,
+            expr,
+        } => fold_int(expr)?.checked_neg(),
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => {
+            let (left, right): (i64, i64) = (fold_int(left)?, fold_int(right)?);
+            match operator {
+                Tokens::Plus => left.checked_add(right),
+                Tokens::Minus => left.checked_sub(right),
+                Tokens::Multiply => left.checked_mul(right),
+                Tokens::Divide => left.checked_div(right),
+                Tokens::Modulo => int_mod(left, right),
+                Tokens::Power => left.checked_pow(u32::try_from(right).ok()?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Finds division of whole numbers that doesn't come out even. `/` always gives
+/// a float, so `7 / 2` is 3.5 and not the 3 someone used to int math might expect
+pub struct IntDivisionLint {
+    warnings: Vec<ParseError>,
+}
+
+impl Visit for IntDivisionLint {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Binary {
+            left,
+            operator: Tokens::Divide,
+            right,
+            position,
+        } = expr
+        {
+            if let (Some(left), Some(right)) = (fold_int(left), fold_int(right)) {
+                if right != 0 && left % right != 0 {
+                    self.warnings.push(ParseError {
+                        code: "W0007",
+                        message: format!(
+                            "{} / {} is {}, `/` always gives a float, use floor for {}",
+                            left,
+                            right,
+                            left as f64 / right as f64,
+                            (left as f64 / right as f64).floor()
+                        ),
+                        line_num: position.line_num,
+                        char_num: position.char_num,
+                    });
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Warn about every constant division that doesn't divide evenly
+pub fn check_int_division(stmts: &[Stmt]) -> Vec<ParseError> {
+    let mut lint: IntDivisionLint = IntDivisionLint {
+        warnings: Vec::new(),
+    };
+    for stmt in stmts {
+        lint.visit_stmt(stmt);
+    }
+    lint.warnings
+}
+
+/// Finds dividing by a number that's always zero, which would only fail once
+/// the python runs. One in the body of a try is left alone, since the catch is
+/// there for it
+pub struct ZeroDivisionCheck {
+    error: Option<ParseError>,
+}
+
+impl Visit for ZeroDivisionCheck {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Try { handler, .. } => {
+                for stmt in handler {
+                    self.visit_stmt(stmt);
+                }
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Binary {
+            operator: operator @ (Tokens::Divide | Tokens::Modulo),
+            right,
+            position,
+            ..
+        } = expr
+        {
+            let is_zero: bool = match &**right {
+                Expr::Numeric(number) => number.parse::<f64>() == Ok(0.0),
+                right => fold_int(right) == Some(0),
+            };
+            if is_zero && self.error.is_none() {
+                let message: &str = match operator {
+                    Tokens::Divide => "division by zero",
+                    _ => "modulo by zero",
+                };
+                self.error = Some(ParseError {
+                    code: "E0201",
+                    message: message.to_string(),
+                    line_num: position.line_num,
+                    char_num: position.char_num,
+                });
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Give back an error for the first division by a constant zero
+pub fn check_division_by_zero(stmts: &[Stmt]) -> Result<(), ParseError> {
+    let mut check: ZeroDivisionCheck = ZeroDivisionCheck { error: None };
+    for stmt in stmts {
+        check.visit_stmt(stmt);
+    }
+    match check.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Make sure every variable is set before it gets read, and only read inside of
+/// the block it was first set in. A function can be called after any global is
+/// set, so what it reads only has to be set outside of every block by the end
+pub fn check_undefined(tokens: &[Token]) -> Result<(), ParseError> {
+    // Sets keep this check linear even when there are lots of variables, with one
+    // for each block that's open and the first one for outside of all of them
+    let mut scopes: Vec<HashSet<Text>> = vec![HashSet::new()];
+    // Which of the scopes are the bodies of functions
+    let mut functions: Vec<usize> = Vec::new();
+    // What the functions read that wasn't set yet, for checking against the globals
+    let mut later: Vec<Token> = Vec::new();
+    let mut current_line: Vec<&Token> = Vec::new();
+    let dicts: HashSet<usize> = dict_braces(tokens);
+
+    for (index, tok) in tokens.iter().enumerate() {
+        current_line.push(tok);
+        // Braces end the part of a line that belongs to a statement, like `if a {`,
+        // unless they're a dict, and the last statement doesn't need a semicolon
+        let is_last: bool = index + 1 == tokens.len();
+        let ends_line: bool = match tok.token {
+            Tokens::Semi => true,
+            Tokens::LeftBrace | Tokens::RightBrace => !dicts.contains(&index),
+            _ => false,
+        };
+        if !is_last && !ends_line {
+            continue;
+        }
+
+        // What a match arm matches is a literal or `_`, so its statement starts
+        // after the `=>`
+        if let Some(arrow) = current_line.iter().position(|x| x.token == Tokens::Arrow) {
+            current_line.drain(..=arrow);
+        }
+        // Nothing is left of it when the `=>` ended the program
+        let first: Option<Tokens> = current_line.first().map(|x| x.token);
+
+        // The parameters of a function are set when it gets called
+        if first == Some(Tokens::Func) {
+            functions.push(scopes.len());
+            scopes.push(HashSet::new());
+            for param in current_line.iter().skip(2) {
+                if param.token == Tokens::Identifier {
+                    scopes.last_mut().unwrap().insert(param.part.to_owned());
+                }
+            }
+            current_line = Vec::new();
+            continue;
+        }
+
+        // The names after global are the ones outside of every block
+        if first == Some(Tokens::Global) {
+            for name in current_line.iter().skip(1) {
+                if name.token == Tokens::Identifier {
+                    scopes[0].insert(name.part.to_owned());
+                }
+            }
+            current_line = Vec::new();
+            continue;
+        }
+
+        // The name after use is a file, not a variable
+        if first == Some(Tokens::Use) {
+            current_line = Vec::new();
+            continue;
+        }
+
+        // A set declares its names, but only after the value has been read,
+        // so the names are not in scope inside of their own initializer.
+        // Everything before the last `=` is a name, like in `set a = b = 0;`.
+        // Setting an item like `set xs[0] = 1;` only reads the list
+        let is_index: bool =
+            matches!(current_line.get(2), Some(x) if x.token == Tokens::LeftBracket);
+        // The name after for is only set inside of the loop, so it's left for the block
+        let is_for: bool = first == Some(Tokens::For);
+        // The name after catch is only set in the handler, like a for's
+        let is_catch: bool = first == Some(Tokens::Catch);
+        // `input x;` sets x without reading anything
+        let is_input: bool = first == Some(Tokens::Input)
+            && matches!(current_line.get(1), Some(x) if x.token == Tokens::Identifier);
+        let (names, reads): (Vec<&Token>, &[&Token]) =
+            match current_line.iter().rposition(|x| x.token == Tokens::Assign) {
+                _ if is_for => (Vec::new(), current_line.get(2..).unwrap_or(&[])),
+                _ if is_catch => (Vec::new(), &[]),
+                _ if is_input => (vec![current_line[1]], &[]),
+                Some(last) if matches!(first, Some(Tokens::Set | Tokens::Const)) && !is_index => (
+                    current_line[1..last]
+                        .iter()
+                        .copied()
+                        .filter(|x| x.token == Tokens::Identifier)
+                        .collect(),
+                    &current_line[last + 1..],
+                ),
+                _ => (Vec::new(), &current_line[..]),
+            };
+
+        // The names in the holes of a string like `"{a}"` are read too
+        let holes: Vec<Token> = reads
+            .iter()
+            .filter(|x| x.token == Tokens::Format)
+            .flat_map(|x| hole_tokens(x))
+            .collect();
+        let reads: Vec<&Token> = reads.iter().copied().chain(holes.iter()).collect();
+        for (index, read) in reads.iter().enumerate() {
+            // The name of a function being called or a constant like `$PI` isn't a variable
+            let is_call: bool =
+                matches!(reads.get(index + 1), Some(x) if x.token == Tokens::LeftParen);
+            let is_constant: bool = index > 0 && reads[index - 1].token == Tokens::Var;
+            // Names from a module like `shapes.area` get checked when it's loaded
+            let is_module: bool = read.part.contains('.');
+            if read.token != Tokens::Identifier
+                || is_call
+                || is_constant
+                || is_module
+                || scopes.iter().any(|x| x.contains(&read.part))
+            {
+                continue;
+            }
+            if !functions.is_empty() {
+                later.push((*read).clone());
+                continue;
+            }
+            let message: String = if names.iter().any(|x| x.part == read.part) {
+                format!("variable '{}' used in its own initializer", read.part)
+            } else {
+                format!("variable '{}' is not defined", read.part)
+            };
+            return Err(ParseError {
+                code: "E0200",
+                message,
+                line_num: read.line_num(),
+                char_num: read.char_num(),
+            });
+        }
+
+        // A name that's already set somewhere gets changed there instead
+        for name in names {
+            if !scopes.iter().any(|x| x.contains(&name.part)) {
+                scopes.last_mut().unwrap().insert(name.part.to_owned());
+            }
+        }
+        match tok.token {
+            _ if dicts.contains(&index) => {}
+            Tokens::LeftBrace => {
+                let mut scope: HashSet<Text> = HashSet::new();
+                if is_for || is_catch {
+                    scope.insert(current_line[1].part.to_owned());
+                }
+                scopes.push(scope);
+            }
+            Tokens::RightBrace if scopes.len() > 1 => {
+                scopes.pop();
+                if functions.last() == Some(&scopes.len()) {
+                    functions.pop();
+                }
+            }
+            _ => {}
+        }
+        current_line = Vec::new();
+    }
+    match later.iter().find(|x| !scopes[0].contains(&x.part)) {
+        Some(read) => Err(ParseError {
+            code: "E0200",
+            message: format!("variable '{}' is not defined", read.part),
+            line_num: read.line_num(),
+            char_num: read.char_num(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Find identifiers longer than max, which usually means a separator is missing
+pub fn check_ident_length(tokens: &[Token], max: usize) -> Vec<ParseError> {
+    tokens
+        .iter()
+        .filter(|x| x.token == Tokens::Identifier && x.part.chars().count() > max)
+        .map(|x| ParseError {
+            code: "W0001",
+            message: format!(
+                "identifier '{}' is {} characters long, more than the limit of {}",
+                x.part,
+                x.part.chars().count(),
+                max
+            ),
+            line_num: x.line_num(),
+            char_num: x.char_num(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::render_warning;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    #[test]
+    fn int_division_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = 7 / 2;\nset b = 8 / 2;\nset c = a / 2;\nprint (1 + 2) / 2;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let warnings: Vec<(&str, String, i64, i64)> = check_int_division(&stmts)
+            .into_iter()
+            .map(|x| (x.code, x.message, x.line_num, x.char_num))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    "W0007",
+                    "7 / 2 is 3.5, `/` always gives a float, use floor for 3".to_string(),
+                    1,
+                    11
+                ),
+                (
+                    "W0007",
+                    "3 / 2 is 1.5, `/` always gives a float, use floor for 1".to_string(),
+                    4,
+                    15
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn division_by_zero_test() {
+        let parse = |contents: &str| -> Vec<Stmt> {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            new_parser(lexer.tokens).parse().unwrap()
+        };
+        assert_eq!(
+            check_division_by_zero(&parse("print 1 / 2;\nprint 1 / a;")),
+            Ok(())
+        );
+
+        let error: ParseError =
+            check_division_by_zero(&parse("print 1;\nprint 1 / (2 - 2);")).unwrap_err();
+        assert_eq!(error.message, "division by zero");
+        assert_eq!((error.line_num, error.char_num), (2, 9));
+
+        let error: ParseError = check_division_by_zero(&parse("print 5 % 0.0;")).unwrap_err();
+        assert_eq!(error.message, "modulo by zero");
+
+        // The catch of a try is there for it, but not for one in the catch itself
+        assert_eq!(
+            check_division_by_zero(&parse("try { print 1 / 0; } catch e { print e; }")),
+            Ok(())
+        );
+        let error: ParseError =
+            check_division_by_zero(&parse("try { print 1; } catch e { print e / 0; }"))
+                .unwrap_err();
+        assert_eq!(error.char_num, 36);
+    }
+
+    #[test]
+    fn check_ident_length_test() {
+        let mut lexer: Lexer = new_lexer("set count = 1;\nset countcountcount = count;");
+        lexer.lexer().unwrap();
+        let warnings: Vec<ParseError> = check_ident_length(&lexer.tokens, 8);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "identifier 'countcountcount' is 15 characters long, more than the limit of 8"
+        );
+        assert_eq!(warnings[0].line_num, 2);
+
+        assert_eq!(check_ident_length(&lexer.tokens, 15), vec![]);
+        assert!(render_warning("set a", 1, 5, "long", false).starts_with("warning: long\n"));
+    }
+
+    #[test]
+    fn check_undefined_test() {
+        let mut lexer: Lexer = new_lexer("set a = 0;\nset b = a + 1;\nprint b;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut lexer: Lexer = new_lexer("set a = a + 1;");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(error.message, "variable 'a' used in its own initializer");
+
+        let mut lexer: Lexer = new_lexer("set a = 0;\nset a = a + 1;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut lexer: Lexer = new_lexer("print b;");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(error.message, "variable 'b' is not defined");
+
+        // A variable set for the first time in a block goes away when it ends
+        let mut lexer: Lexer = new_lexer("set a = 1;\nif a {\n  set b = a;\n}\nprint b;");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(error.message, "variable 'b' is not defined");
+
+        let mut lexer: Lexer = new_lexer("for i in 0..3 {\n  print i;\n}\nprint i;");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(error.message, "variable 'i' is not defined");
+
+        // The name after catch is only set in the handler, and a match arm's
+        // statement starts after the `=>`
+        let mut lexer: Lexer = new_lexer("try {} catch e { print e; }\nprint e;");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(
+            (error.message.as_str(), error.line_num),
+            ("variable 'e' is not defined", 2)
+        );
+        let mut lexer: Lexer = new_lexer("match 1 { 1 => set b = 2; _ => print 3; }");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        // The parser gets to say what's wrong with it
+        let mut lexer: Lexer = new_lexer("if 1 =>");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut lexer: Lexer = new_lexer("set a = 1;\nif a {\n  set a = 2;\n}\nprint a;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut lexer: Lexer =
+            new_lexer("func f() {\n  global total;\n  set total = 1;\n}\nprint total;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        // Parameters are set inside of the function
+        let mut lexer: Lexer = new_lexer("func add(a, b) {\n  return a + b + c;\n}");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(error.message, "variable 'c' is not defined");
+
+        // A function can read a global that's set after it, but not one that's
+        // only set in a block
+        let mut lexer: Lexer = new_lexer("func f() { return y; }\nset y = 2;\nprint f();");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut lexer: Lexer =
+            new_lexer("func f() {\n  return y;\n}\nif 1 {\n  set y = 2;\n}\nprint f();");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(
+            (error.message.as_str(), error.line_num, error.char_num),
+            ("variable 'y' is not defined", 2, 10)
+        );
+    }
+}
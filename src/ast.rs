@@ -0,0 +1,526 @@
+//! The tree the parser builds, which everything after it works from,
+//! like the codegen and the interpreter
+
+use crate::lexer::Tokens;
+
+/// Where a part of an expression is in the source, so errors found while
+/// running can point to it
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct Position {
+    pub line_num: i64,
+    pub char_num: i64,
+}
+
+/// A single expression, like the value on the right side of a set
+#[derive(PartialEq, Debug, Clone)]
+pub enum Expr {
+    Numeric(String),
+    Str(String),
+    Bool(bool),
+    Identifier(String, Position),
+    /// A built in constant like `$PI`, without the `$`
+    Constant(String),
+    Input,
+    /// Calling a function like `len(a)`, which is passed on to python as it is,
+    /// the position is the name for when the call goes wrong
+    Call {
+        name: String,
+        args: Vec<Expr>,
+        position: Position,
+    },
+    Unary {
+        operator: Tokens,
+        expr: Box<Expr>,
+    },
+    /// A list of values like `[1, 2, 3]`
+    List(Vec<Expr>),
+    /// A string with values put into it like `"x is {x}"`, the text between the
+    /// holes is a Str and each hole is the expression in it
+    Format(Vec<Expr>),
+    /// The keys and values of a dict like `{"a": 1}`, in the order they're written
+    Dict(Vec<(Expr, Expr)>),
+    /// Getting an item out of a list, a dict or a string like `xs[0]`, the position is the `[`
+    Index {
+        target: Box<Expr>,
+        index: Box<Expr>,
+        position: Position,
+    },
+    /// The ints from the start up to before the end like `0..10`, the position is the `..`
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        position: Position,
+    },
+    /// The position is the operator's, like for a division by zero
+    Binary {
+        left: Box<Expr>,
+        operator: Tokens,
+        right: Box<Expr>,
+        position: Position,
+    },
+    /// Picking one of two values like `a > b ? a : b`, only the one picked is worked out
+    Ternary {
+        condition: Box<Expr>,
+        then: Box<Expr>,
+        otherwise: Box<Expr>,
+    },
+}
+
+/// A single statement, each line of source becomes one of these
+#[derive(PartialEq, Debug, Clone)]
+pub enum Stmt {
+    Set {
+        name: String,
+        value: Expr,
+    },
+    /// Setting a name with `const X = 10;`, which nothing after it can set again
+    Const {
+        name: String,
+        value: Expr,
+    },
+    SetChain {
+        names: Vec<String>,
+        value: Expr,
+    },
+    SetMany {
+        names: Vec<String>,
+        values: Vec<Expr>,
+    },
+    /// Set an item of a list, `set grid[1][2] = 0;` has two indexes,
+    /// the position is the first `[`
+    SetIndex {
+        name: String,
+        indexes: Vec<Expr>,
+        value: Expr,
+        position: Position,
+    },
+    /// More than one value gets printed on the same line with a space between, like python
+    Print {
+        exprs: Vec<Expr>,
+    },
+    /// Move by a number of statements in the same block, `jump 1` just goes to the next one
+    Jump {
+        offset: Expr,
+    },
+    AugAssign {
+        name: String,
+        operator: Tokens,
+        value: Expr,
+    },
+    /// An if with the block after `else` if it has one, `else if` is an if inside of it
+    If {
+        condition: Expr,
+        body: Vec<Stmt>,
+        else_body: Option<Vec<Stmt>>,
+    },
+    While {
+        condition: Expr,
+        body: Vec<Stmt>,
+    },
+    /// Run the first arm whose value is equal to the value, like
+    /// `match x { 1 => print "one"; _ => print "more"; }`. The value is only worked
+    /// out once, and nothing runs if no arm matches. The position is the `match`
+    Match {
+        value: Expr,
+        arms: Vec<MatchArm>,
+        position: Position,
+    },
+    /// Run the body, and if it has an error run the handler with the name set to
+    /// the error's message, like `try { print 1 / 0; } catch err { print err; }`
+    Try {
+        body: Vec<Stmt>,
+        name: String,
+        handler: Vec<Stmt>,
+    },
+    /// Run the body for each item like `for x in xs { print x; }`, the position
+    /// is the `in`, for when what comes after it can't be looped over
+    For {
+        name: String,
+        iterable: Expr,
+        body: Vec<Stmt>,
+        position: Position,
+    },
+    /// A function like `func add(a, b) { return a + b; }`
+    Func {
+        name: String,
+        params: Vec<String>,
+        body: Vec<Stmt>,
+    },
+    /// Leave the function, `return;` without a value gives back None like python
+    Return {
+        value: Option<Expr>,
+    },
+    /// Leave the loop it's in, the position is the keyword's for when it isn't in one
+    Break {
+        position: Position,
+    },
+    /// Go on to the next time through the loop it's in
+    Continue {
+        position: Position,
+    },
+    /// Sets of these names after this go to the variables outside of every block
+    /// and function, like python's global
+    Global {
+        names: Vec<String>,
+    },
+    /// Stop the whole program with a code for the shell, `exit;` is the same as `exit 0;`.
+    /// The position is the `exit`, for when the code isn't an int
+    Exit {
+        code: Option<Expr>,
+        position: Position,
+    },
+    /// `assert a > 0;` stops the program when the value is false, and `assert_eq a, 1;`
+    /// when the two aren't equal, which is when expected is there. The position is
+    /// the keyword's
+    Assert {
+        value: Expr,
+        expected: Option<Expr>,
+        position: Position,
+    },
+    /// Run another file like `use shapes;`, with the position of the name for when
+    /// it can't be loaded. Loading the modules puts the file's statements here instead
+    Use {
+        name: String,
+        position: Position,
+    },
+    Expr(Expr),
+    /// Lines from a custom statement that get outputted as they are
+    Lines(Vec<String>),
+}
+
+/// One arm of a match, a pattern of None is the `_` that matches anything. The
+/// position is the pattern's, for the warnings about arms that can't run
+#[derive(PartialEq, Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Option<Expr>,
+    pub body: Vec<Stmt>,
+    pub position: Position,
+}
+
+/// Walks the statements and expressions, each analysis only overrides the
+/// visit methods for the nodes it cares about and calls the walk to keep going
+pub trait Visit {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        walk_stmt(self, stmt);
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+}
+
+/// Visit everything inside of a statement
+pub fn walk_stmt<V: Visit + ?Sized>(visitor: &mut V, stmt: &Stmt) {
+    match stmt {
+        Stmt::Set { value, .. }
+        | Stmt::Const { value, .. }
+        | Stmt::SetChain { value, .. }
+        | Stmt::AugAssign { value, .. } => visitor.visit_expr(value),
+        Stmt::SetMany { values, .. } => {
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        Stmt::SetIndex { indexes, value, .. } => {
+            for index in indexes {
+                visitor.visit_expr(index);
+            }
+            visitor.visit_expr(value);
+        }
+        Stmt::Print { exprs } => {
+            for expr in exprs {
+                visitor.visit_expr(expr);
+            }
+        }
+        Stmt::Jump { offset: expr } | Stmt::Expr(expr) => visitor.visit_expr(expr),
+        Stmt::If {
+            condition,
+            body,
+            else_body,
+        } => {
+            visitor.visit_expr(condition);
+            for stmt in body.iter().chain(else_body.iter().flatten()) {
+                visitor.visit_stmt(stmt);
+            }
+        }
+        Stmt::While {
+            condition: expr,
+            body,
+        }
+        | Stmt::For {
+            iterable: expr,
+            body,
+            ..
+        } => {
+            visitor.visit_expr(expr);
+            for stmt in body {
+                visitor.visit_stmt(stmt);
+            }
+        }
+        Stmt::Match { value, arms, .. } => {
+            visitor.visit_expr(value);
+            for arm in arms {
+                if let Some(pattern) = &arm.pattern {
+                    visitor.visit_expr(pattern);
+                }
+                for stmt in &arm.body {
+                    visitor.visit_stmt(stmt);
+                }
+            }
+        }
+        Stmt::Try { body, handler, .. } => {
+            for stmt in body.iter().chain(handler) {
+                visitor.visit_stmt(stmt);
+            }
+        }
+        Stmt::Func { body, .. } => {
+            for stmt in body {
+                visitor.visit_stmt(stmt);
+            }
+        }
+        Stmt::Return { value } | Stmt::Exit { code: value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        Stmt::Assert {
+            value, expected, ..
+        } => {
+            visitor.visit_expr(value);
+            if let Some(expected) = expected {
+                visitor.visit_expr(expected);
+            }
+        }
+        Stmt::Global { .. }
+        | Stmt::Break { .. }
+        | Stmt::Continue { .. }
+        | Stmt::Use { .. }
+        | Stmt::Lines(_) => {}
+    }
+}
+
+/// Visit everything inside of an expression
+pub fn walk_expr<V: Visit + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Numeric(_)
+        | Expr::Str(_)
+        | Expr::Bool(_)
+        | Expr::Identifier(..)
+        | Expr::Constant(_)
+        | Expr::Input => {}
+        Expr::Call { args, .. } | Expr::List(args) | Expr::Format(args) => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        Expr::Dict(entries) => {
+            for (key, value) in entries {
+                visitor.visit_expr(key);
+                visitor.visit_expr(value);
+            }
+        }
+        Expr::Index { target, index, .. } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(index);
+        }
+        Expr::Unary { expr, .. } => visitor.visit_expr(expr),
+        Expr::Range {
+            start: left,
+            end: right,
+            ..
+        }
+        | Expr::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => {
+            visitor.visit_expr(condition);
+            visitor.visit_expr(then);
+            visitor.visit_expr(otherwise);
+        }
+    }
+}
+
+/// Like Visit, but the nodes can be changed, for passes that rewrite the tree
+pub trait VisitMut {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt);
+    }
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+}
+
+/// Visit everything inside of a statement so it can be changed
+pub fn walk_stmt_mut<V: VisitMut + ?Sized>(visitor: &mut V, stmt: &mut Stmt) {
+    match stmt {
+        Stmt::Set { value, .. }
+        | Stmt::Const { value, .. }
+        | Stmt::SetChain { value, .. }
+        | Stmt::AugAssign { value, .. } => visitor.visit_expr_mut(value),
+        Stmt::SetMany { values, .. } => {
+            for value in values {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Stmt::SetIndex { indexes, value, .. } => {
+            for index in indexes {
+                visitor.visit_expr_mut(index);
+            }
+            visitor.visit_expr_mut(value);
+        }
+        Stmt::Print { exprs } => {
+            for expr in exprs {
+                visitor.visit_expr_mut(expr);
+            }
+        }
+        Stmt::Jump { offset: expr } | Stmt::Expr(expr) => visitor.visit_expr_mut(expr),
+        Stmt::If {
+            condition,
+            body,
+            else_body,
+        } => {
+            visitor.visit_expr_mut(condition);
+            for stmt in body.iter_mut().chain(else_body.iter_mut().flatten()) {
+                visitor.visit_stmt_mut(stmt);
+            }
+        }
+        Stmt::While {
+            condition: expr,
+            body,
+        }
+        | Stmt::For {
+            iterable: expr,
+            body,
+            ..
+        } => {
+            visitor.visit_expr_mut(expr);
+            for stmt in body {
+                visitor.visit_stmt_mut(stmt);
+            }
+        }
+        Stmt::Match { value, arms, .. } => {
+            visitor.visit_expr_mut(value);
+            for arm in arms {
+                if let Some(pattern) = &mut arm.pattern {
+                    visitor.visit_expr_mut(pattern);
+                }
+                for stmt in &mut arm.body {
+                    visitor.visit_stmt_mut(stmt);
+                }
+            }
+        }
+        Stmt::Try { body, handler, .. } => {
+            for stmt in body.iter_mut().chain(handler) {
+                visitor.visit_stmt_mut(stmt);
+            }
+        }
+        Stmt::Func { body, .. } => {
+            for stmt in body {
+                visitor.visit_stmt_mut(stmt);
+            }
+        }
+        Stmt::Return { value } | Stmt::Exit { code: value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Stmt::Assert {
+            value, expected, ..
+        } => {
+            visitor.visit_expr_mut(value);
+            if let Some(expected) = expected {
+                visitor.visit_expr_mut(expected);
+            }
+        }
+        Stmt::Global { .. }
+        | Stmt::Break { .. }
+        | Stmt::Continue { .. }
+        | Stmt::Use { .. }
+        | Stmt::Lines(_) => {}
+    }
+}
+
+/// Visit everything inside of an expression so it can be changed
+pub fn walk_expr_mut<V: VisitMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    match expr {
+        Expr::Numeric(_)
+        | Expr::Str(_)
+        | Expr::Bool(_)
+        | Expr::Identifier(..)
+        | Expr::Constant(_)
+        | Expr::Input => {}
+        Expr::Call { args, .. } | Expr::List(args) | Expr::Format(args) => {
+            for arg in args {
+                visitor.visit_expr_mut(arg);
+            }
+        }
+        Expr::Dict(entries) => {
+            for (key, value) in entries {
+                visitor.visit_expr_mut(key);
+                visitor.visit_expr_mut(value);
+            }
+        }
+        Expr::Index { target, index, .. } => {
+            visitor.visit_expr_mut(target);
+            visitor.visit_expr_mut(index);
+        }
+        Expr::Unary { expr, .. } => visitor.visit_expr_mut(expr),
+        Expr::Range {
+            start: left,
+            end: right,
+            ..
+        }
+        | Expr::Binary { left, right, .. } => {
+            visitor.visit_expr_mut(left);
+            visitor.visit_expr_mut(right);
+        }
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => {
+            visitor.visit_expr_mut(condition);
+            visitor.visit_expr_mut(then);
+            visitor.visit_expr_mut(otherwise);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    #[test]
+    fn visit_test() {
+        /// Count how many binary operators there are
+        struct BinaryCounter {
+            count: usize,
+        }
+
+        impl Visit for BinaryCounter {
+            fn visit_expr(&mut self, expr: &Expr) {
+                if let Expr::Binary { .. } = expr {
+                    self.count += 1;
+                }
+                walk_expr(self, expr);
+            }
+        }
+
+        let mut lexer: Lexer =
+            new_lexer("set a = 1 + 2 * 3;\nwhile a < 10 {\n  a += -(a - 1);\n}\nprint a;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        let mut counter: BinaryCounter = BinaryCounter { count: 0 };
+        for stmt in &stmts {
+            counter.visit_stmt(stmt);
+        }
+        assert_eq!(counter.count, 4);
+    }
+}
@@ -0,0 +1,270 @@
+//! Times each part of getting a program to run, for `mahou bench` and `cargo bench`.
+//! The programs in bench/ are made by corpus, so there's always something to compare
+//! the tree interpreter and the vm with
+
+use crate::ast::Stmt;
+use crate::bytecode::{compile_program, new_vm, FunctionCode, Vm};
+use crate::interpreter::{new_interpreter, Interpret, Interpreter};
+use crate::lexer::{new_lexer, Lex, Lexer};
+use crate::parse_source;
+use crate::parser::{new_parser, Parse};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// How long each part took, the middle time out of every time it was run
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Timings {
+    pub lex: Duration,
+    pub parse: Duration,
+    pub tree: Duration,
+    pub compile: Duration,
+    pub vm: Duration,
+    /// How many times lexing and parsing it asked for memory, when the counting
+    /// allocator is the one being used and 0 when it isn't
+    pub lex_allocations: usize,
+    pub parse_allocations: usize,
+}
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, but it counts how many times it's asked for memory.
+/// `mahou` and `cargo bench` use it as their `#[global_allocator]`
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout)
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(pointer, layout, size)
+    }
+}
+
+/// How many allocations the part made, counting every thread
+fn allocations<T>(part: impl FnOnce() -> T) -> usize {
+    let start: usize = ALLOCATIONS.load(Ordering::Relaxed);
+    let _ = part();
+    ALLOCATIONS.load(Ordering::Relaxed) - start
+}
+
+/// A lot of times through nested loops
+fn loops() -> String {
+    "set total = 0;\nset i = 0;\nwhile i < 300 {\n    set j = 0;\n    while j < 100 {\n        \
+     total += i * j % 7;\n        j += 1;\n    }\n    i += 1;\n}\nprint total;\n"
+        .to_string()
+}
+
+/// A long program of math, mostly for the lexer and parser
+fn arithmetic() -> String {
+    let mut program: String = String::new();
+    for n in 0..2000 {
+        program.push_str(&format!(
+            "set a = ({} + {}) * {} - {} % {} + {} * ({} - {});\n",
+            n,
+            n % 17 + 1,
+            n % 5 + 2,
+            n * 3,
+            n % 7 + 1,
+            n % 11,
+            n % 13,
+            n % 3
+        ));
+    }
+    program.push_str("print a;\n");
+    program
+}
+
+/// Every variable set from the one before it, so there are a lot of names to look up
+fn variables() -> String {
+    let mut program: String = String::from("set v0 = 0;\n");
+    for n in 1..1000 {
+        program.push_str(&format!("set v{} = v{} + {};\n", n, n - 1, n % 10));
+    }
+    program.push_str("print v999;\n");
+    program
+}
+
+/// A function calling itself a lot
+fn calls() -> String {
+    "func fib(n) {\n    if n < 2 {\n        return n;\n    }\n    return fib(n - 1) + fib(n - 2);\n}\n\
+     print fib(18);\n"
+        .to_string()
+}
+
+/// The programs in bench/, by the name of their file without the .m
+pub fn corpus() -> Vec<(&'static str, String)> {
+    vec![
+        ("loops", loops()),
+        ("arithmetic", arithmetic()),
+        ("variables", variables()),
+        ("calls", calls()),
+    ]
+}
+
+/// The one in the middle, so one slow run doesn't change it much
+pub fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    times.get(times.len() / 2).copied().unwrap_or_default()
+}
+
+/// Run the part the number of times, giving back the middle time
+fn time<T>(
+    iterations: usize,
+    mut part: impl FnMut() -> Result<T, String>,
+) -> Result<Duration, String> {
+    let mut times: Vec<Duration> = Vec::new();
+    for _ in 0..iterations.max(1) {
+        let start: Instant = Instant::now();
+        part()?;
+        times.push(start.elapsed());
+    }
+    Ok(median(times))
+}
+
+/// Time lexing, parsing, running on the tree interpreter, compiling to bytecode
+/// and running on the vm. What the program prints is thrown away
+pub fn bench(contents: &str, iterations: usize) -> Result<Timings, String> {
+    let lex: Duration = time(iterations, || {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().map_err(|error| error.message)?;
+        Ok(lexer)
+    })?;
+    let lex_allocations: usize = allocations(|| new_lexer(contents).lexer());
+    let mut lexer: Lexer = new_lexer(contents);
+    lexer.lexer().map_err(|error| error.message)?;
+    let parse_allocations: usize = allocations(|| new_parser(&lexer.tokens).parse());
+    let parse: Duration = time(iterations, || {
+        new_parser(&lexer.tokens)
+            .parse()
+            .map_err(|error| error.message)
+    })?;
+
+    let stmts: Vec<Stmt> = parse_source(contents, None, false)?;
+    let tree: Duration = time(iterations, || {
+        let mut interpreter: Interpreter<&[u8], io::Sink> = new_interpreter(&b""[..], io::sink());
+        interpreter.run(&stmts).map_err(|error| error.message)
+    })?;
+    let compile: Duration = time(iterations, || {
+        compile_program(&stmts).map_err(|error| error.message)
+    })?;
+    let program: FunctionCode = compile_program(&stmts).map_err(|error| error.message)?;
+    let vm: Duration = time(iterations, || {
+        let mut vm: Vm<&[u8], io::Sink> = new_vm(&b""[..], io::sink());
+        vm.run(&program).map_err(|error| error.message)
+    })?;
+    Ok(Timings {
+        lex,
+        parse,
+        tree,
+        compile,
+        vm,
+        lex_allocations,
+        parse_allocations,
+    })
+}
+
+fn millis(time: Duration) -> String {
+    format!("{:.3}ms", time.as_secs_f64() * 1000.0)
+}
+
+/// A row for each program, with how many times faster the vm ran it than the tree
+/// interpreter did
+pub fn report(results: &[(String, Timings)]) -> String {
+    let width: usize = results
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let mut report: String = format!(
+        "{:<width$} {:>11} {:>11} {:>11} {:>11} {:>11} {:>8} {:>10} {:>12}\n",
+        "program",
+        "lex",
+        "parse",
+        "tree",
+        "compile",
+        "vm",
+        "vm/tree",
+        "lex allocs",
+        "parse allocs",
+        width = width
+    );
+    for (name, timings) in results {
+        let speedup: f64 = timings.tree.as_secs_f64() / timings.vm.as_secs_f64().max(1e-9);
+        report.push_str(&format!(
+            "{:<width$} {:>11} {:>11} {:>11} {:>11} {:>11} {:>7.2}x {:>10} {:>12}\n",
+            name,
+            millis(timings.lex),
+            millis(timings.parse),
+            millis(timings.tree),
+            millis(timings.compile),
+            millis(timings.vm),
+            speedup,
+            timings.lex_allocations,
+            timings.parse_allocations,
+            width = width
+        ));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build, run, run_bytecode};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn corpus_test() {
+        // The files are what corpus makes, and both ways of running them agree
+        for (name, program) in corpus() {
+            let path: PathBuf =
+                Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("bench/{}.m", name));
+            assert_eq!(
+                fs::read_to_string(path).unwrap(),
+                program,
+                "bench/{}.m",
+                name
+            );
+
+            let mut tree: Vec<u8> = Vec::new();
+            run(&program, &b""[..], &mut tree).unwrap();
+            let mut vm: Vec<u8> = Vec::new();
+            run_bytecode(&build(&program, None).unwrap(), &b""[..], &mut vm).unwrap();
+            assert_eq!(tree, vm, "bench/{}.m", name);
+        }
+
+        assert_eq!(
+            median(vec![
+                Duration::from_millis(9),
+                Duration::from_millis(1),
+                Duration::from_millis(3)
+            ]),
+            Duration::from_millis(3)
+        );
+        let timings: Timings = Timings {
+            lex: Duration::from_millis(1),
+            parse: Duration::from_millis(2),
+            tree: Duration::from_millis(30),
+            compile: Duration::from_millis(1),
+            vm: Duration::from_millis(10),
+            lex_allocations: 120,
+            parse_allocations: 45,
+        };
+        assert_eq!(
+            report(&[("loops".to_string(), timings)]),
+            "program         lex       parse        tree     compile          vm  vm/tree lex allocs parse allocs\n\
+             loops       1.000ms     2.000ms    30.000ms     1.000ms    10.000ms    3.00x        120           45\n"
+        );
+    }
+}
@@ -0,0 +1,2096 @@
+//! Compiles the statements into instructions for a stack machine, and the
+//! machine that runs them, so a program doesn't have to walk the tree each time
+
+use crate::ast::{Expr, Position, Stmt};
+use crate::dump::json_string;
+use crate::formatter::expr_source;
+use crate::interpreter::{
+    assertion_failure, aug_binary, catchable, constant, exit_with, index, is_aug_assign, loop_item,
+    loop_items, negate, new_dict, new_list, new_range, new_scopes, not_loaded, parse_number,
+    print_failed, runtime_error, show, store_index, unary, RuntimeError, Scopes, Value,
+    MAX_CALL_DEPTH,
+};
+use crate::lexer::{tokenize, Tokens};
+use crate::log;
+use crate::parser::{constant_value, operator_text, precedence, ARGUMENT_CONSTANTS};
+use crate::stdlib::{call, elapsed, input, random, start_random, start_time};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// What runs the program with --run, walking the tree or compiling it for the vm
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Engine {
+    Tree,
+    Vm,
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(engine: &str) -> Result<Self, Self::Err> {
+        match engine {
+            "tree" => Ok(Engine::Tree),
+            "vm" => Ok(Engine::Vm),
+            _ => Err(format!("unknown backend '{}', expected tree or vm", engine)),
+        }
+    }
+}
+
+/// A single instruction, most of them take their values off of the stack
+/// and push what they make back onto it
+#[derive(PartialEq, Debug, Clone)]
+pub enum Instr {
+    Push(Value),
+    /// Push the value of a variable, with where it was read for the error if it isn't set
+    Load(String, Option<Position>),
+    Store(String),
+    Dup,
+    Pop,
+    Binary(Tokens, Option<Position>),
+    Negate,
+    /// Push True if the value on top of the stack is false, and False if it's true
+    Not,
+    Input,
+    Print,
+    /// Call a function with this many arguments off the top of the stack, with
+    /// where it's called for when a builtin errors
+    Call(String, usize, Option<Position>),
+    Jump(usize),
+    JumpIfFalse(usize),
+    /// Take the offset of a jump off the stack and go to the start of that statement,
+    /// `targets` has where each statement of the block starts and then its end
+    JumpBy {
+        index: usize,
+        targets: Vec<usize>,
+    },
+    /// Make the function so it can be called from then on
+    Func(Rc<FunctionCode>),
+    Return,
+    /// Count a statement or a time through a loop against max_steps
+    Step,
+    /// Start and end the scope of the body of an if or while
+    EnterBlock,
+    ExitBlock,
+    /// Sets of the name go to the globals from here on in this call, from `global`
+    Global(String),
+    /// Make a list out of this many values off the top of the stack
+    MakeList(usize),
+    /// Take an index and what it's indexing off the stack and push the item
+    Index(Option<Position>),
+    /// Take an index, a list and a value off the stack and set the item to the value
+    StoreIndex(Option<Position>),
+    /// Make a dict out of this many keys and values off the top of the stack,
+    /// each key pushed right before its value
+    MakeDict(usize),
+    /// Take the end and the start of a range off the stack and push the range
+    MakeRange(Option<Position>),
+    /// Turn the value on top of the stack into what a for loop goes through
+    LoopItems(Option<Position>),
+    /// With what to go through and how far the loop is on top of the stack, push
+    /// the next item, or take both off and jump to the target when there are no more
+    ForNext(usize),
+    /// Join this many values off the top of the stack into one string, like they print
+    Concat(usize),
+    /// Stop the program with the code on top of the stack, from `exit`
+    Exit(Option<Position>),
+    /// Push `$ARGS` or `$ARGC`, which are only known once the program runs
+    Argument(String),
+    /// Take a value off the stack and stop if it isn't truthy, from `assert`,
+    /// with the source of the value for the message
+    Assert(String, Option<Position>),
+    /// Take two values off the stack and stop if they aren't equal, from `assert_eq`
+    AssertEq(Option<Position>),
+    /// Start a try, an error from here on goes to the target with its message pushed
+    Try(usize),
+    /// The try's body is done without an error, so errors stop going to its target
+    EndTry,
+    /// `return f(x);` in a function, which runs f in place of the function instead of
+    /// inside of it so recursion that ends in a call doesn't go any deeper. A builtin
+    /// gets called and returned like a call would be
+    TailCall(String, usize, Option<Position>),
+}
+
+/// The name of the instruction and what it works on, like `LOAD a`
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instr::Push(Value::Str(text)) => write!(f, "PUSH {}", json_string(text)),
+            Instr::Push(value) => write!(f, "PUSH {}", value),
+            Instr::Load(name, _) => write!(f, "LOAD {}", name),
+            Instr::Store(name) => write!(f, "STORE {}", name),
+            Instr::Dup => write!(f, "DUP"),
+            Instr::Pop => write!(f, "POP"),
+            Instr::Binary(operator, _) => write!(f, "BINARY {}", operator_text(*operator)),
+            Instr::Negate => write!(f, "NEGATE"),
+            Instr::Not => write!(f, "NOT"),
+            Instr::Input => write!(f, "INPUT"),
+            Instr::Print => write!(f, "PRINT"),
+            Instr::Call(name, count, _) => write!(f, "CALL {} {}", name, count),
+            Instr::Jump(target) => write!(f, "JMP {}", target),
+            Instr::JumpIfFalse(target) => write!(f, "JMP_IF_FALSE {}", target),
+            Instr::JumpBy { index, targets } => {
+                let targets: Vec<String> = targets.iter().map(|x| x.to_string()).collect();
+                write!(f, "JMP_BY {} [{}]", index, targets.join(", "))
+            }
+            Instr::Func(function) => {
+                write!(f, "FUNC {}({})", function.name, function.params.join(", "))
+            }
+            Instr::Return => write!(f, "RETURN"),
+            Instr::Step => write!(f, "STEP"),
+            Instr::EnterBlock => write!(f, "ENTER_BLOCK"),
+            Instr::ExitBlock => write!(f, "EXIT_BLOCK"),
+            Instr::Global(name) => write!(f, "GLOBAL {}", name),
+            Instr::MakeList(count) => write!(f, "MAKE_LIST {}", count),
+            Instr::Concat(count) => write!(f, "CONCAT {}", count),
+            Instr::Index(_) => write!(f, "INDEX"),
+            Instr::StoreIndex(_) => write!(f, "STORE_INDEX"),
+            Instr::MakeDict(count) => write!(f, "MAKE_DICT {}", count),
+            Instr::MakeRange(_) => write!(f, "MAKE_RANGE"),
+            Instr::LoopItems(_) => write!(f, "LOOP_ITEMS"),
+            Instr::ForNext(target) => write!(f, "FOR_NEXT {}", target),
+            Instr::Exit(_) => write!(f, "EXIT"),
+            Instr::Argument(name) => write!(f, "ARGUMENT {}", name),
+            Instr::Assert(source, _) => write!(f, "ASSERT {}", json_string(source)),
+            Instr::AssertEq(_) => write!(f, "ASSERT_EQ"),
+            Instr::Try(target) => write!(f, "TRY {}", target),
+            Instr::EndTry => write!(f, "END_TRY"),
+            Instr::TailCall(name, count, _) => write!(f, "TAIL_CALL {} {}", name, count),
+        }
+    }
+}
+
+/// A function made with `func`, already compiled
+#[derive(PartialEq, Debug)]
+pub struct FunctionCode {
+    pub name: String,
+    pub params: Vec<String>,
+    pub code: Vec<Instr>,
+}
+
+/// Shows the instructions one per line with their address, for looking at what got compiled
+impl fmt::Display for FunctionCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (address, instr) in self.code.iter().enumerate() {
+            writeln!(f, "{:4} {}", address, instr)?;
+            // The code of a function goes under it, indented
+            if let Instr::Func(function) = instr {
+                for line in function.to_string().lines() {
+                    writeln!(f, "     {}", line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the instructions so far, jumps point to where in here they go
+pub struct Compiler {
+    code: Vec<Instr>,
+    /// How many scopes the code being compiled is inside of
+    blocks: usize,
+    /// The loops the code being compiled is inside of, the innermost last
+    loops: Vec<Loop>,
+    /// How many try bodies the code being compiled is inside of
+    tries: usize,
+    /// Whether it's the body of a function, where a return can be a tail call
+    function: bool,
+}
+
+/// A loop that's being compiled, with the jumps out of it that need to be patched
+/// once where they go is known
+struct Loop {
+    /// How many scopes there were outside of the loop's own
+    blocks: usize,
+    /// What the loop keeps on the stack, a for has its items and how far along it is
+    stack: usize,
+    /// How many tries there were outside of the loop, the ones inside get ended
+    /// by a break or continue
+    tries: usize,
+    breaks: Vec<usize>,
+    continues: Vec<usize>,
+}
+
+fn new_compiler() -> Compiler {
+    Compiler {
+        code: Vec::new(),
+        blocks: 0,
+        loops: Vec::new(),
+        tries: 0,
+        function: false,
+    }
+}
+
+impl Compiler {
+    fn emit(&mut self, instr: Instr) -> usize {
+        self.code.push(instr);
+        self.code.len() - 1
+    }
+    /// Point a jump that was emitted before where it goes was known
+    fn patch(&mut self, address: usize, target: usize) {
+        match &mut self.code[address] {
+            Instr::Jump(to) | Instr::JumpIfFalse(to) | Instr::ForNext(to) | Instr::Try(to) => {
+                *to = target
+            }
+            _ => {}
+        }
+    }
+    /// Compile the body of an if or while, in a scope of its own. A jump to the
+    /// end of the block lands on the instruction that ends the scope
+    fn scoped_block(&mut self, stmts: &[Stmt]) -> Result<(), RuntimeError> {
+        self.emit(Instr::EnterBlock);
+        self.blocks += 1;
+        self.block(stmts)?;
+        self.blocks -= 1;
+        self.emit(Instr::ExitBlock);
+        Ok(())
+    }
+    /// Compile the body of a loop, which is the scope at start. A continue lands on
+    /// the end of that scope and a break after the end of the whole loop, so both
+    /// get patched once the loop is done
+    fn loop_body(&mut self, stmts: &[Stmt], stack: usize) -> Result<Loop, RuntimeError> {
+        self.loops.push(Loop {
+            blocks: self.blocks,
+            stack,
+            tries: self.tries,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        });
+        self.blocks += 1;
+        let body: Result<(), RuntimeError> = self.block(stmts);
+        self.blocks -= 1;
+        let lp: Loop = self.loops.pop().unwrap();
+        body?;
+        for address in &lp.continues {
+            self.patch(*address, self.code.len());
+        }
+        self.emit(Instr::ExitBlock);
+        Ok(lp)
+    }
+    /// Leave the scopes inside of the loop, and for a break the loop's own scope and
+    /// what it has on the stack too
+    fn loop_exit(&mut self, is_break: bool) -> Result<(), RuntimeError> {
+        let (blocks, stack, tries): (usize, usize, usize) = match self.loops.last() {
+            Some(lp) => (self.blocks - lp.blocks, lp.stack, self.tries - lp.tries),
+            None => {
+                return Err(runtime_error(
+                    "break and continue can only be used in a loop".to_string(),
+                ))
+            }
+        };
+        let (blocks, stack): (usize, usize) = if is_break {
+            (blocks, stack)
+        } else {
+            (blocks - 1, 0)
+        };
+        for _ in 0..tries {
+            self.emit(Instr::EndTry);
+        }
+        for _ in 0..blocks {
+            self.emit(Instr::ExitBlock);
+        }
+        for _ in 0..stack {
+            self.emit(Instr::Pop);
+        }
+        let address: usize = self.emit(Instr::Jump(0));
+        let lp: &mut Loop = self.loops.last_mut().unwrap();
+        if is_break {
+            lp.breaks.push(address);
+        } else {
+            lp.continues.push(address);
+        }
+        Ok(())
+    }
+    /// Compile a block, a jump moves between the statements in it like it does in the interpreter
+    fn block(&mut self, stmts: &[Stmt]) -> Result<(), RuntimeError> {
+        let mut starts: Vec<usize> = Vec::new();
+        let mut jumps: Vec<usize> = Vec::new();
+        for (index, stmt) in stmts.iter().enumerate() {
+            starts.push(self.emit(Instr::Step));
+            if let Stmt::Jump { offset } = stmt {
+                self.expr(offset)?;
+                jumps.push(self.emit(Instr::JumpBy {
+                    index,
+                    targets: Vec::new(),
+                }));
+            } else {
+                self.stmt(stmt)?;
+            }
+        }
+        starts.push(self.code.len());
+        for address in jumps {
+            if let Instr::JumpBy { targets, .. } = &mut self.code[address] {
+                *targets = starts.clone();
+            }
+        }
+        Ok(())
+    }
+    fn stmt(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        match stmt {
+            Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                self.expr(value)?;
+                self.emit(Instr::Store(name.to_owned()));
+            }
+            Stmt::SetChain { names, value } => {
+                self.expr(value)?;
+                for (index, name) in names.iter().enumerate() {
+                    if index + 1 < names.len() {
+                        self.emit(Instr::Dup);
+                    }
+                    self.emit(Instr::Store(name.to_owned()));
+                }
+            }
+            // All of the values get pushed first, so the last one comes off first
+            Stmt::SetMany { names, values } => {
+                for value in values {
+                    self.expr(value)?;
+                }
+                for name in names.iter().rev() {
+                    self.emit(Instr::Store(name.to_owned()));
+                }
+            }
+            // The value goes under the list and the index, so it's worked out first
+            Stmt::SetIndex {
+                name,
+                indexes,
+                value,
+                position,
+            } => {
+                self.expr(value)?;
+                self.emit(Instr::Load(name.to_owned(), None));
+                let (last, rest) = indexes.split_last().unwrap();
+                for index in rest {
+                    self.expr(index)?;
+                    self.emit(Instr::Index(Some(*position)));
+                }
+                self.expr(last)?;
+                self.emit(Instr::StoreIndex(Some(*position)));
+            }
+            // More than one value gets joined into one string with spaces first
+            Stmt::Print { exprs } => {
+                for (index, expr) in exprs.iter().enumerate() {
+                    if index > 0 {
+                        self.emit(Instr::Push(Value::Str(" ".into())));
+                    }
+                    self.expr(expr)?;
+                }
+                if exprs.len() > 1 {
+                    self.emit(Instr::Concat(exprs.len() * 2 - 1));
+                }
+                self.emit(Instr::Print);
+            }
+            Stmt::Jump { .. } => {
+                return Err(runtime_error(
+                    "jump can only be run as part of a block".to_string(),
+                ))
+            }
+            Stmt::AugAssign {
+                name,
+                operator,
+                value,
+            } => {
+                // Kept as `+=` so a list can be added to where it is
+                self.emit(Instr::Load(name.to_owned(), None));
+                self.expr(value)?;
+                self.emit(Instr::Binary(*operator, None));
+                self.emit(Instr::Store(name.to_owned()));
+            }
+            Stmt::If {
+                condition,
+                body,
+                else_body,
+            } => {
+                self.expr(condition)?;
+                let to_else: usize = self.emit(Instr::JumpIfFalse(0));
+                self.scoped_block(body)?;
+                match else_body {
+                    Some(else_body) => {
+                        let to_end: usize = self.emit(Instr::Jump(0));
+                        self.patch(to_else, self.code.len());
+                        self.scoped_block(else_body)?;
+                        self.patch(to_end, self.code.len());
+                    }
+                    None => self.patch(to_else, self.code.len()),
+                }
+            }
+            Stmt::While { condition, body } => {
+                let start: usize = self.code.len();
+                self.expr(condition)?;
+                let to_end: usize = self.emit(Instr::JumpIfFalse(0));
+                self.emit(Instr::Step);
+                self.emit(Instr::EnterBlock);
+                let lp: Loop = self.loop_body(body, 0)?;
+                self.emit(Instr::Jump(start));
+                self.patch(to_end, self.code.len());
+                for address in lp.breaks {
+                    self.patch(address, self.code.len());
+                }
+            }
+            // The value stays on the stack while it's checked against each arm, and
+            // comes off before the body so a break in it doesn't have to know it's there
+            Stmt::Match { value, arms, .. } => {
+                self.expr(value)?;
+                let mut to_end: Vec<usize> = Vec::new();
+                let mut matched_all: bool = false;
+                for arm in arms {
+                    match &arm.pattern {
+                        Some(pattern) => {
+                            self.emit(Instr::Dup);
+                            self.expr(pattern)?;
+                            self.emit(Instr::Binary(Tokens::Equal, None));
+                            let to_next: usize = self.emit(Instr::JumpIfFalse(0));
+                            self.emit(Instr::Pop);
+                            self.scoped_block(&arm.body)?;
+                            to_end.push(self.emit(Instr::Jump(0)));
+                            self.patch(to_next, self.code.len());
+                        }
+                        // Nothing after a `_` can run
+                        None => {
+                            self.emit(Instr::Pop);
+                            self.scoped_block(&arm.body)?;
+                            matched_all = true;
+                            break;
+                        }
+                    }
+                }
+                if !matched_all {
+                    self.emit(Instr::Pop);
+                }
+                for address in to_end {
+                    self.patch(address, self.code.len());
+                }
+            }
+            // An error in the body goes to the handler with its message on the stack,
+            // which gets stored in the handler's own scope
+            Stmt::Try {
+                body,
+                name,
+                handler,
+            } => {
+                let start: usize = self.emit(Instr::Try(0));
+                self.tries += 1;
+                let compiled: Result<(), RuntimeError> = self.scoped_block(body);
+                self.tries -= 1;
+                compiled?;
+                self.emit(Instr::EndTry);
+                let to_end: usize = self.emit(Instr::Jump(0));
+                self.patch(start, self.code.len());
+                self.emit(Instr::EnterBlock);
+                self.emit(Instr::Store(name.to_owned()));
+                self.blocks += 1;
+                self.block(handler)?;
+                self.blocks -= 1;
+                self.emit(Instr::ExitBlock);
+                self.patch(to_end, self.code.len());
+            }
+            // What to go through and how far along the loop is stay on the stack
+            // under everything the body does
+            Stmt::For {
+                name,
+                iterable,
+                body,
+                position,
+            } => {
+                self.expr(iterable)?;
+                self.emit(Instr::LoopItems(Some(*position)));
+                self.emit(Instr::Push(Value::Int(0)));
+                let start: usize = self.emit(Instr::ForNext(0));
+                self.emit(Instr::Step);
+                self.emit(Instr::EnterBlock);
+                self.emit(Instr::Store(name.to_owned()));
+                let lp: Loop = self.loop_body(body, 2)?;
+                self.emit(Instr::Jump(start));
+                self.patch(start, self.code.len());
+                for address in lp.breaks {
+                    self.patch(address, self.code.len());
+                }
+            }
+            Stmt::Func { name, params, body } => {
+                let mut compiler: Compiler = new_compiler();
+                compiler.function = true;
+                compiler.block(body)?;
+                compiler.emit(Instr::Push(Value::None));
+                compiler.emit(Instr::Return);
+                self.emit(Instr::Func(Rc::new(FunctionCode {
+                    name: name.to_owned(),
+                    params: params.to_owned(),
+                    code: compiler.code,
+                })));
+            }
+            // Inside of a try the function has to stay around to catch what the call does
+            Stmt::Return {
+                value:
+                    Some(Expr::Call {
+                        name,
+                        args,
+                        position,
+                    }),
+            } if self.function && self.tries == 0 => {
+                for arg in args {
+                    self.expr(arg)?;
+                }
+                self.emit(Instr::TailCall(
+                    name.to_owned(),
+                    args.len(),
+                    Some(*position),
+                ));
+            }
+            Stmt::Return { value } => {
+                match value {
+                    Some(value) => self.expr(value)?,
+                    None => {
+                        self.emit(Instr::Push(Value::None));
+                    }
+                }
+                self.emit(Instr::Return);
+            }
+            Stmt::Break { .. } => self.loop_exit(true)?,
+            Stmt::Continue { .. } => self.loop_exit(false)?,
+            Stmt::Global { names } => {
+                for name in names {
+                    self.emit(Instr::Global(name.to_owned()));
+                }
+            }
+            Stmt::Exit { code, position } => {
+                match code {
+                    Some(code) => self.expr(code)?,
+                    None => {
+                        self.emit(Instr::Push(Value::Int(0)));
+                    }
+                }
+                self.emit(Instr::Exit(Some(*position)));
+            }
+            Stmt::Assert {
+                value,
+                expected: None,
+                position,
+            } => {
+                self.expr(value)?;
+                self.emit(Instr::Assert(expr_source(value), Some(*position)));
+            }
+            Stmt::Assert {
+                value,
+                expected: Some(expected),
+                position,
+            } => {
+                self.expr(value)?;
+                self.expr(expected)?;
+                self.emit(Instr::AssertEq(Some(*position)));
+            }
+            Stmt::Expr(expr) => {
+                self.expr(expr)?;
+                self.emit(Instr::Pop);
+            }
+            Stmt::Lines(_) => {
+                return Err(runtime_error(
+                    "custom statements can only be outputted, not run".to_string(),
+                ))
+            }
+            Stmt::Use { name, position } => return Err(not_loaded(name, *position)),
+        }
+        Ok(())
+    }
+    fn expr(&mut self, expr: &Expr) -> Result<(), RuntimeError> {
+        match expr {
+            Expr::Numeric(number) => {
+                self.emit(Instr::Push(parse_number(number)?));
+            }
+            Expr::Bool(value) => {
+                self.emit(Instr::Push(Value::Bool(*value)));
+            }
+            Expr::Str(text) => {
+                self.emit(Instr::Push(Value::Str(text.as_str().into())));
+            }
+            Expr::Identifier(name, position) => {
+                self.emit(Instr::Load(name.to_owned(), Some(*position)));
+            }
+            Expr::Constant(name) if ARGUMENT_CONSTANTS.contains(&name.as_str()) => {
+                self.emit(Instr::Argument(name.to_owned()));
+            }
+            Expr::Constant(name) => {
+                let value: f64 = constant_value(name)
+                    .ok_or_else(|| runtime_error(format!("unknown constant '${}'", name)))?;
+                self.emit(Instr::Push(Value::Float(value)));
+            }
+            Expr::Input => {
+                self.emit(Instr::Input);
+            }
+            Expr::Call {
+                name,
+                args,
+                position,
+            } => {
+                for arg in args {
+                    self.expr(arg)?;
+                }
+                self.emit(Instr::Call(name.to_owned(), args.len(), Some(*position)));
+            }
+            Expr::List(items) => {
+                for item in items {
+                    self.expr(item)?;
+                }
+                self.emit(Instr::MakeList(items.len()));
+            }
+            Expr::Format(parts) => {
+                for part in parts {
+                    self.expr(part)?;
+                }
+                self.emit(Instr::Concat(parts.len()));
+            }
+            Expr::Dict(entries) => {
+                for (key, value) in entries {
+                    self.expr(key)?;
+                    self.expr(value)?;
+                }
+                self.emit(Instr::MakeDict(entries.len()));
+            }
+            Expr::Range {
+                start,
+                end,
+                position,
+            } => {
+                self.expr(start)?;
+                self.expr(end)?;
+                self.emit(Instr::MakeRange(Some(*position)));
+            }
+            Expr::Index {
+                target,
+                index,
+                position,
+            } => {
+                self.expr(target)?;
+                self.expr(index)?;
+                self.emit(Instr::Index(Some(*position)));
+            }
+            Expr::Unary {
+                operator: Tokens::Not,
+                expr,
+            } => {
+                self.expr(expr)?;
+                self.emit(Instr::Not);
+            }
+            Expr::Unary { expr, .. } => {
+                self.expr(expr)?;
+                self.emit(Instr::Negate);
+            }
+            // The left side stays on the stack as the value if it decides it,
+            // otherwise it's taken off and the right side is the value
+            Expr::Binary {
+                left,
+                operator: Tokens::And,
+                right,
+                ..
+            } => {
+                self.expr(left)?;
+                self.emit(Instr::Dup);
+                let to_end: usize = self.emit(Instr::JumpIfFalse(0));
+                self.emit(Instr::Pop);
+                self.expr(right)?;
+                self.patch(to_end, self.code.len());
+            }
+            Expr::Binary {
+                left,
+                operator: Tokens::Or,
+                right,
+                ..
+            } => {
+                self.expr(left)?;
+                self.emit(Instr::Dup);
+                let to_right: usize = self.emit(Instr::JumpIfFalse(0));
+                let to_end: usize = self.emit(Instr::Jump(0));
+                self.patch(to_right, self.code.len());
+                self.emit(Instr::Pop);
+                self.expr(right)?;
+                self.patch(to_end, self.code.len());
+            }
+            Expr::Ternary {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.expr(condition)?;
+                let to_otherwise: usize = self.emit(Instr::JumpIfFalse(0));
+                self.expr(then)?;
+                let to_end: usize = self.emit(Instr::Jump(0));
+                self.patch(to_otherwise, self.code.len());
+                self.expr(otherwise)?;
+                self.patch(to_end, self.code.len());
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                position,
+            } => {
+                self.expr(left)?;
+                self.expr(right)?;
+                self.emit(Instr::Binary(*operator, Some(*position)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compile the whole program, which runs like a function called `main`, and run
+/// the peephole pass over it
+pub fn compile_program(stmts: &[Stmt]) -> Result<FunctionCode, RuntimeError> {
+    compile_unoptimized(stmts).map(|program| peephole(&program, true))
+}
+
+/// The program the way the compiler makes it, before the peephole pass, for
+/// `--no-peephole` to show what the pass changed
+pub fn compile_unoptimized(stmts: &[Stmt]) -> Result<FunctionCode, RuntimeError> {
+    let mut compiler: Compiler = new_compiler();
+    compiler.block(stmts)?;
+    Ok(FunctionCode {
+        name: "main".to_string(),
+        params: Vec::new(),
+        code: compiler.code,
+    })
+}
+
+/// Clean up what the compiler made, looking at a few instructions at a time and
+/// going again until nothing changes. The functions inside get it too, `main` is
+/// whether it's the program outside of them
+pub fn peephole(function: &FunctionCode, main: bool) -> FunctionCode {
+    let mut code: Vec<Instr> = function
+        .code
+        .iter()
+        .map(|instr| match instr {
+            Instr::Func(inner) => Instr::Func(Rc::new(peephole(inner, false))),
+            instr => instr.clone(),
+        })
+        .collect();
+    while peephole_once(&mut code, main) {}
+    FunctionCode {
+        name: function.name.clone(),
+        params: function.params.clone(),
+        code,
+    }
+}
+
+/// Every address something can jump to, which can be one past the end
+fn jump_targets(code: &[Instr]) -> Vec<bool> {
+    let mut targets: Vec<bool> = vec![false; code.len() + 1];
+    for instr in code {
+        match instr {
+            Instr::Jump(to) | Instr::JumpIfFalse(to) | Instr::ForNext(to) | Instr::Try(to) => {
+                targets[*to] = true
+            }
+            Instr::JumpBy { targets: to, .. } => to.iter().for_each(|x| targets[*x] = true),
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// One go over the code, giving back whether it changed anything. Only the first
+/// instruction of a pattern can be jumped to, otherwise it's left alone
+fn peephole_once(code: &mut Vec<Instr>, main: bool) -> bool {
+    let targets: Vec<bool> = jump_targets(code);
+    let mut removed: Vec<bool> = vec![false; code.len()];
+    let mut changed: bool = false;
+    let mut address: usize = 0;
+    while address < code.len() {
+        let free = |count: usize| -> bool {
+            address + count < code.len() && (1..=count).all(|x| !targets[address + x])
+        };
+        match &code[address..] {
+            // Two constants and an operator become what it works out to, an error
+            // is left for when it runs
+            [Instr::Push(left), Instr::Push(right), Instr::Binary(operator, _), ..]
+                if free(2) && is_constant(left) && is_constant(right) =>
+            {
+                if let Ok(value) = aug_binary(left.clone(), *operator, right.clone()) {
+                    code[address] = Instr::Push(value);
+                    removed[address + 1] = true;
+                    removed[address + 2] = true;
+                    changed = true;
+                    address += 3;
+                    continue;
+                }
+            }
+            // Outside of a function the variable is set wherever it already is, so
+            // `set x = x;` does nothing. In one it could make a local out of a global
+            [Instr::Load(name, _), Instr::Store(same), ..] if main && name == same && free(1) => {
+                removed[address] = true;
+                removed[address + 1] = true;
+                changed = true;
+                address += 2;
+                continue;
+            }
+            [Instr::EnterBlock, Instr::ExitBlock, ..] if free(1) => {
+                removed[address] = true;
+                removed[address + 1] = true;
+                changed = true;
+                address += 2;
+                continue;
+            }
+            [Instr::Jump(to), ..] if *to == address + 1 => {
+                removed[address] = true;
+                changed = true;
+            }
+            [Instr::JumpIfFalse(to), ..] if *to == address + 1 => {
+                code[address] = Instr::Pop;
+                changed = true;
+            }
+            // Nothing runs after a jump or a return until somewhere that's jumped to
+            [Instr::Jump(_) | Instr::Return, ..] => {
+                let mut dead: usize = address + 1;
+                while dead < code.len() && !targets[dead] {
+                    removed[dead] = true;
+                    changed = true;
+                    dead += 1;
+                }
+                address = dead;
+                continue;
+            }
+            _ => {}
+        }
+        address += 1;
+    }
+    if !changed {
+        return false;
+    }
+    // Where each address ends up, an instruction that's gone goes to the one after it
+    let mut moved: Vec<usize> = Vec::with_capacity(code.len() + 1);
+    let mut kept: usize = 0;
+    for gone in removed.iter().chain([&false]) {
+        moved.push(kept);
+        if !gone {
+            kept += 1;
+        }
+    }
+    let old: Vec<Instr> = std::mem::take(code);
+    for (instr, gone) in old.into_iter().zip(removed) {
+        if gone {
+            continue;
+        }
+        code.push(match instr {
+            Instr::Jump(to) => Instr::Jump(moved[to]),
+            Instr::JumpIfFalse(to) => Instr::JumpIfFalse(moved[to]),
+            Instr::ForNext(to) => Instr::ForNext(moved[to]),
+            Instr::Try(to) => Instr::Try(moved[to]),
+            Instr::JumpBy { index, targets } => Instr::JumpBy {
+                index,
+                targets: targets.iter().map(|x| moved[*x]).collect(),
+            },
+            instr => instr,
+        });
+    }
+    true
+}
+
+/// A value that's the same each time it's pushed, a list would be shared
+fn is_constant(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Int(_) | Value::Float(_) | Value::Str(_) | Value::Bool(_) | Value::None
+    )
+}
+
+/// The first bytes of every .mhc file, so other files don't get run by mistake
+pub const MAGIC: &[u8; 4] = b"MHC\0";
+
+/// Goes up each time the way instructions are written changes, old files then
+/// have to be built again
+pub const FORMAT_VERSION: u16 = 12;
+
+/// Write the compiled program as bytes for a .mhc file, starting with the magic
+/// number and the version of the format
+pub fn encode_program(program: &FunctionCode) -> Vec<u8> {
+    let mut bytes: Vec<u8> = MAGIC.to_vec();
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    encode_function(&mut bytes, program);
+    bytes
+}
+
+fn encode_function(bytes: &mut Vec<u8>, function: &FunctionCode) {
+    encode_string(bytes, &function.name);
+    encode_len(bytes, function.params.len());
+    for param in &function.params {
+        encode_string(bytes, param);
+    }
+    encode_len(bytes, function.code.len());
+    for instr in &function.code {
+        encode_instr(bytes, instr);
+    }
+}
+
+fn encode_len(bytes: &mut Vec<u8>, len: usize) {
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn encode_string(bytes: &mut Vec<u8>, text: &str) {
+    encode_len(bytes, text.len());
+    bytes.extend_from_slice(text.as_bytes());
+}
+
+fn encode_position(bytes: &mut Vec<u8>, position: &Option<Position>) {
+    match position {
+        Some(position) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&position.line_num.to_le_bytes());
+            bytes.extend_from_slice(&position.char_num.to_le_bytes());
+        }
+        None => bytes.push(0),
+    }
+}
+
+fn encode_value(bytes: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Int(number) => {
+            bytes.push(0);
+            bytes.extend_from_slice(&number.to_le_bytes());
+        }
+        Value::Float(number) => {
+            bytes.push(1);
+            bytes.extend_from_slice(&number.to_bits().to_le_bytes());
+        }
+        Value::Str(text) => {
+            bytes.push(2);
+            encode_string(bytes, text);
+        }
+        Value::Bool(value) => {
+            bytes.push(3);
+            bytes.push(*value as u8);
+        }
+        Value::None => bytes.push(4),
+        Value::List(items) => {
+            bytes.push(5);
+            let items = items.borrow();
+            encode_len(bytes, items.len());
+            for item in items.iter() {
+                encode_value(bytes, item);
+            }
+        }
+        Value::Dict(entries) => {
+            bytes.push(6);
+            let entries = entries.borrow();
+            encode_len(bytes, entries.len());
+            for (key, value) in entries.iter() {
+                encode_value(bytes, key);
+                encode_value(bytes, value);
+            }
+        }
+        Value::Range(start, end) => {
+            bytes.push(7);
+            bytes.extend_from_slice(&start.to_le_bytes());
+            bytes.extend_from_slice(&end.to_le_bytes());
+        }
+    }
+}
+
+/// Each instruction is a byte for which one it is, then what it works on
+fn encode_instr(bytes: &mut Vec<u8>, instr: &Instr) {
+    match instr {
+        Instr::Push(value) => {
+            bytes.push(0);
+            encode_value(bytes, value);
+        }
+        Instr::Load(name, position) => {
+            bytes.push(1);
+            encode_string(bytes, name);
+            encode_position(bytes, position);
+        }
+        Instr::Store(name) => {
+            bytes.push(2);
+            encode_string(bytes, name);
+        }
+        Instr::Dup => bytes.push(3),
+        Instr::Pop => bytes.push(4),
+        // The operator is written the way it looks in the source, like `+`
+        Instr::Binary(operator, position) => {
+            bytes.push(5);
+            encode_string(bytes, operator_text(*operator));
+            encode_position(bytes, position);
+        }
+        Instr::Negate => bytes.push(6),
+        Instr::Input => bytes.push(7),
+        Instr::Print => bytes.push(8),
+        Instr::Call(name, count, position) => {
+            bytes.push(9);
+            encode_string(bytes, name);
+            encode_len(bytes, *count);
+            encode_position(bytes, position);
+        }
+        Instr::Jump(target) => {
+            bytes.push(10);
+            encode_len(bytes, *target);
+        }
+        Instr::JumpIfFalse(target) => {
+            bytes.push(11);
+            encode_len(bytes, *target);
+        }
+        Instr::JumpBy { index, targets } => {
+            bytes.push(12);
+            encode_len(bytes, *index);
+            encode_len(bytes, targets.len());
+            for target in targets {
+                encode_len(bytes, *target);
+            }
+        }
+        Instr::Func(function) => {
+            bytes.push(13);
+            encode_function(bytes, function);
+        }
+        Instr::Return => bytes.push(14),
+        Instr::Step => bytes.push(15),
+        Instr::EnterBlock => bytes.push(16),
+        Instr::ExitBlock => bytes.push(17),
+        Instr::Global(name) => {
+            bytes.push(18);
+            encode_string(bytes, name);
+        }
+        Instr::MakeList(count) => {
+            bytes.push(19);
+            encode_len(bytes, *count);
+        }
+        Instr::Index(position) => {
+            bytes.push(20);
+            encode_position(bytes, position);
+        }
+        Instr::StoreIndex(position) => {
+            bytes.push(21);
+            encode_position(bytes, position);
+        }
+        Instr::MakeDict(count) => {
+            bytes.push(22);
+            encode_len(bytes, *count);
+        }
+        Instr::MakeRange(position) => {
+            bytes.push(23);
+            encode_position(bytes, position);
+        }
+        Instr::LoopItems(position) => {
+            bytes.push(24);
+            encode_position(bytes, position);
+        }
+        Instr::ForNext(target) => {
+            bytes.push(25);
+            encode_len(bytes, *target);
+        }
+        Instr::Concat(count) => {
+            bytes.push(26);
+            encode_len(bytes, *count);
+        }
+        Instr::Exit(position) => {
+            bytes.push(27);
+            encode_position(bytes, position);
+        }
+        Instr::Argument(name) => {
+            bytes.push(28);
+            encode_string(bytes, name);
+        }
+        Instr::Not => bytes.push(29),
+        Instr::Assert(source, position) => {
+            bytes.push(30);
+            encode_string(bytes, source);
+            encode_position(bytes, position);
+        }
+        Instr::AssertEq(position) => {
+            bytes.push(31);
+            encode_position(bytes, position);
+        }
+        Instr::Try(target) => {
+            bytes.push(32);
+            encode_len(bytes, *target);
+        }
+        Instr::EndTry => bytes.push(33),
+        Instr::TailCall(name, count, position) => {
+            bytes.push(34);
+            encode_string(bytes, name);
+            encode_len(bytes, *count);
+            encode_position(bytes, position);
+        }
+    }
+}
+
+/// Reads the parts of a .mhc file back out in the order they were written
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], String> {
+        let end: usize = self.index + count;
+        let taken: &[u8] = self
+            .bytes
+            .get(self.index..end)
+            .ok_or_else(|| "the bytecode file ends too early".to_string())?;
+        self.index = end;
+        Ok(taken)
+    }
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+    fn len(&mut self) -> Result<usize, String> {
+        let mut buffer: [u8; 4] = [0; 4];
+        buffer.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buffer) as usize)
+    }
+    fn int(&mut self) -> Result<i64, String> {
+        let mut buffer: [u8; 8] = [0; 8];
+        buffer.copy_from_slice(self.take(8)?);
+        Ok(i64::from_le_bytes(buffer))
+    }
+    fn string(&mut self) -> Result<String, String> {
+        let len: usize = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| "the bytecode file has a string that isn't utf-8".to_string())
+    }
+    fn position(&mut self) -> Result<Option<Position>, String> {
+        match self.byte()? {
+            0 => Ok(None),
+            _ => Ok(Some(Position {
+                line_num: self.int()?,
+                char_num: self.int()?,
+            })),
+        }
+    }
+    fn value(&mut self) -> Result<Value, String> {
+        match self.byte()? {
+            0 => Ok(Value::Int(self.int()?)),
+            1 => Ok(Value::Float(f64::from_bits(self.int()? as u64))),
+            2 => Ok(Value::Str(self.string()?.into())),
+            3 => Ok(Value::Bool(self.byte()? != 0)),
+            4 => Ok(Value::None),
+            5 => Ok(new_list(
+                (0..self.len()?)
+                    .map(|_| self.value())
+                    .collect::<Result<Vec<Value>, String>>()?,
+            )),
+            6 => Ok(new_dict(
+                (0..self.len()?)
+                    .map(|_| Ok((self.value()?, self.value()?)))
+                    .collect::<Result<Vec<(Value, Value)>, String>>()?,
+            )),
+            7 => Ok(Value::Range(self.int()?, self.int()?)),
+            kind => Err(format!(
+                "unknown kind of value {} in the bytecode file",
+                kind
+            )),
+        }
+    }
+    fn operator(&mut self) -> Result<Tokens, String> {
+        let text: String = self.string()?;
+        let operator: Tokens = tokenize(&text);
+        if (precedence(operator).is_some() || is_aug_assign(operator))
+            && operator_text(operator) == text
+        {
+            Ok(operator)
+        } else {
+            Err(format!("unknown operator '{}' in the bytecode file", text))
+        }
+    }
+    fn function(&mut self) -> Result<FunctionCode, String> {
+        let name: String = self.string()?;
+        let params: Vec<String> = (0..self.len()?)
+            .map(|_| self.string())
+            .collect::<Result<Vec<String>, String>>()?;
+        let code: Vec<Instr> = (0..self.len()?)
+            .map(|_| self.instr())
+            .collect::<Result<Vec<Instr>, String>>()?;
+        Ok(FunctionCode { name, params, code })
+    }
+    fn instr(&mut self) -> Result<Instr, String> {
+        Ok(match self.byte()? {
+            0 => Instr::Push(self.value()?),
+            1 => Instr::Load(self.string()?, self.position()?),
+            2 => Instr::Store(self.string()?),
+            3 => Instr::Dup,
+            4 => Instr::Pop,
+            5 => Instr::Binary(self.operator()?, self.position()?),
+            6 => Instr::Negate,
+            7 => Instr::Input,
+            8 => Instr::Print,
+            9 => Instr::Call(self.string()?, self.len()?, self.position()?),
+            10 => Instr::Jump(self.len()?),
+            11 => Instr::JumpIfFalse(self.len()?),
+            12 => {
+                let index: usize = self.len()?;
+                let targets: Vec<usize> =
+                    (0..self.len()?)
+                        .map(|_| self.len())
+                        .collect::<Result<Vec<usize>, String>>()?;
+                Instr::JumpBy { index, targets }
+            }
+            13 => Instr::Func(Rc::new(self.function()?)),
+            14 => Instr::Return,
+            15 => Instr::Step,
+            16 => Instr::EnterBlock,
+            17 => Instr::ExitBlock,
+            18 => Instr::Global(self.string()?),
+            19 => Instr::MakeList(self.len()?),
+            20 => Instr::Index(self.position()?),
+            21 => Instr::StoreIndex(self.position()?),
+            22 => Instr::MakeDict(self.len()?),
+            23 => Instr::MakeRange(self.position()?),
+            24 => Instr::LoopItems(self.position()?),
+            25 => Instr::ForNext(self.len()?),
+            26 => Instr::Concat(self.len()?),
+            27 => Instr::Exit(self.position()?),
+            28 => Instr::Argument(self.string()?),
+            29 => Instr::Not,
+            30 => Instr::Assert(self.string()?, self.position()?),
+            31 => Instr::AssertEq(self.position()?),
+            32 => Instr::Try(self.len()?),
+            33 => Instr::EndTry,
+            34 => Instr::TailCall(self.string()?, self.len()?, self.position()?),
+            kind => return Err(format!("unknown instruction {} in the bytecode file", kind)),
+        })
+    }
+}
+
+/// Read a program written by encode_program, checking that it's a .mhc file
+/// made with the same version of the format first
+pub fn decode_program(bytes: &[u8]) -> Result<FunctionCode, String> {
+    if !bytes.starts_with(MAGIC) {
+        return Err("not a mahou bytecode file".to_string());
+    }
+    let mut decoder: Decoder = Decoder { bytes, index: 4 };
+    let version: u16 = u16::from(decoder.byte()?) | u16::from(decoder.byte()?) << 8;
+    if version != FORMAT_VERSION {
+        return Err(format!(
+            "the bytecode file is version {} but this mahou reads version {}, build it again",
+            version, FORMAT_VERSION
+        ));
+    }
+    let program: FunctionCode = decoder.function()?;
+    if decoder.index != bytes.len() {
+        return Err("the bytecode file has extra bytes at the end".to_string());
+    }
+    verify(&program)?;
+    Ok(program)
+}
+
+/// How many values the instruction takes off the stack and how many it pushes.
+/// A ForNext that has run out takes off one more than this says
+fn stack_effect(instr: &Instr) -> (usize, usize) {
+    match instr {
+        Instr::Push(_) | Instr::Load(..) | Instr::Input | Instr::Argument(_) => (0, 1),
+        Instr::Store(_) | Instr::Pop | Instr::Print | Instr::JumpIfFalse(_) => (1, 0),
+        Instr::JumpBy { .. } | Instr::Return | Instr::Exit(_) | Instr::Assert(..) => (1, 0),
+        Instr::Dup => (1, 2),
+        Instr::Binary(..) | Instr::Index(_) | Instr::MakeRange(_) => (2, 1),
+        Instr::Negate | Instr::Not | Instr::LoopItems(_) => (1, 1),
+        Instr::Call(_, count, _) | Instr::MakeList(count) | Instr::Concat(count) => (*count, 1),
+        Instr::TailCall(_, count, _) => (*count, 0),
+        Instr::MakeDict(count) => (count.saturating_mul(2), 1),
+        Instr::AssertEq(_) => (2, 0),
+        Instr::StoreIndex(_) => (3, 0),
+        Instr::ForNext(_) => (2, 3),
+        Instr::Jump(_) | Instr::Func(_) | Instr::Step | Instr::EnterBlock => (0, 0),
+        Instr::ExitBlock | Instr::Global(_) | Instr::Try(_) | Instr::EndTry => (0, 0),
+    }
+}
+
+/// Check that code from a file never takes more off the stack than it put there,
+/// however it goes through its jumps, and that the jumps stay inside of it. The
+/// vm trusts that the code does what the compiler would make, so a file that was
+/// changed after it was built is refused when it's read instead of crashing it
+fn verify(function: &FunctionCode) -> Result<(), String> {
+    let code: &[Instr] = &function.code;
+    // The least there can be on the stack at each instruction, from every way there
+    let mut least: Vec<Option<usize>> = vec![None; code.len() + 1];
+    let mut next: Vec<(usize, usize)> = vec![(0, 0)];
+    while let Some((pc, depth)) = next.pop() {
+        let instr: &Instr = match least.get(pc) {
+            None => {
+                return Err(format!(
+                    "the bytecode file has a jump to {} in {}, which only has {} instructions",
+                    pc,
+                    function.name,
+                    code.len()
+                ))
+            }
+            Some(Some(seen)) if *seen <= depth => continue,
+            Some(_) if pc == code.len() => {
+                least[pc] = Some(depth);
+                continue;
+            }
+            Some(_) => &code[pc],
+        };
+        least[pc] = Some(depth);
+        let (takes, gives): (usize, usize) = stack_effect(instr);
+        let after: usize = depth.checked_sub(takes).ok_or_else(|| {
+            format!(
+                "the bytecode file takes more off the stack than it has at {} in {}",
+                pc, function.name
+            )
+        })? + gives;
+        match instr {
+            Instr::Jump(target) => next.push((*target, after)),
+            Instr::JumpIfFalse(target) => next.extend([(*target, after), (pc + 1, after)]),
+            Instr::JumpBy { targets, .. } => next.extend(targets.iter().map(|x| (*x, after))),
+            Instr::ForNext(target) => next.extend([(*target, depth - 2), (pc + 1, after)]),
+            Instr::Try(target) => next.extend([(*target, after + 1), (pc + 1, after)]),
+            Instr::Return | Instr::TailCall(..) | Instr::Exit(_) => {}
+            Instr::Func(inner) => {
+                verify(inner)?;
+                next.push((pc + 1, after));
+            }
+            _ => next.push((pc + 1, after)),
+        }
+    }
+    Ok(())
+}
+
+/// Where an error in a try's body goes, and how much of the stack and how many
+/// scopes there were when it started
+struct Catch {
+    target: usize,
+    stack: usize,
+    blocks: usize,
+}
+
+/// The stack machine, with the variables and where input comes from and print goes to
+pub struct Vm<R: BufRead, W: Write> {
+    pub functions: HashMap<String, Rc<FunctionCode>>,
+    /// The variables of the globals and of the blocks and function calls that are running
+    scopes: Scopes,
+    stack: Vec<Value>,
+    /// Stop the program after this many steps, so a jump or while that loops forever ends
+    pub max_steps: Option<u64>,
+    steps: u64,
+    /// How deep function calls can go before it stops with an error
+    pub max_depth: usize,
+    /// What the program was given after `--`, for `$ARGS` and `$ARGC`
+    pub args: Vec<String>,
+    /// When the vm was made, for `elapsed()`, if there is a clock
+    started: Option<Instant>,
+    /// Where `rand()` and `rand_int()` are at, set it to a seed to pick the same
+    /// numbers every run
+    pub random: u64,
+    /// The function a tail call is leaving the running one for, which gets run in
+    /// its place with the same call's scopes
+    tail: Option<Rc<FunctionCode>>,
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Vm<R, W> {
+    /// Run the compiled program from the start
+    pub fn run(&mut self, program: &FunctionCode) -> Result<(), RuntimeError> {
+        self.execute(&program.code)?;
+        Ok(())
+    }
+    /// Run instructions until they end or return, giving back what got returned.
+    /// An error inside of a try goes on from its handler instead, with the stack
+    /// and the scopes back to how they were when the try started. After a tail call
+    /// it goes on from the start of the function the call was to
+    fn execute(&mut self, code: &[Instr]) -> Result<Value, RuntimeError> {
+        let mut pc: usize = 0;
+        let mut catches: Vec<Catch> = Vec::new();
+        let base: usize = self.stack.len();
+        let mut function: Option<Rc<FunctionCode>> = None;
+        loop {
+            let code: &[Instr] = function.as_ref().map_or(code, |x| &x.code);
+            let error: RuntimeError = match self.execute_from(code, &mut pc, &mut catches) {
+                Ok(_) if self.tail.is_some() => {
+                    function = self.tail.take();
+                    self.stack.truncate(base);
+                    pc = 0;
+                    continue;
+                }
+                Err(error) if catchable(&error, self.steps, self.max_steps) => error,
+                result => return result,
+            };
+            let catch: Catch = match catches.pop() {
+                Some(catch) => catch,
+                None => return Err(error),
+            };
+            self.stack.truncate(catch.stack);
+            while self.scopes.blocks() > catch.blocks {
+                self.scopes.pop_block();
+            }
+            self.stack.push(Value::Str(error.message.into()));
+            pc = catch.target;
+        }
+    }
+    fn execute_from(
+        &mut self,
+        code: &[Instr],
+        pc: &mut usize,
+        catches: &mut Vec<Catch>,
+    ) -> Result<Value, RuntimeError> {
+        while let Some(instr) = code.get(*pc) {
+            log::trace(|| format!("{:>4} {}", pc, instr));
+            *pc += 1;
+            match instr {
+                Instr::Push(value) => self.stack.push(value.clone()),
+                Instr::Load(name, position) => {
+                    let value: Value = self.variable(name).map_err(|error| match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    })?;
+                    self.stack.push(value);
+                }
+                Instr::Store(name) => {
+                    let value: Value = self.pop();
+                    self.assign(name, value);
+                }
+                Instr::Dup => {
+                    let value: Value = self.pop();
+                    self.stack.push(value.clone());
+                    self.stack.push(value);
+                }
+                Instr::Pop => {
+                    self.pop();
+                }
+                Instr::Binary(operator, position) => {
+                    let right: Value = self.pop();
+                    let left: Value = self.pop();
+                    let value: Value =
+                        aug_binary(left, *operator, right).map_err(|error| match position {
+                            Some(position) => error.at(*position),
+                            None => error,
+                        })?;
+                    self.stack.push(value);
+                }
+                Instr::Negate => {
+                    let value: Value = negate(self.pop())?;
+                    self.stack.push(value);
+                }
+                Instr::Not => {
+                    let value: Value = unary(Tokens::Not, self.pop())?;
+                    self.stack.push(value);
+                }
+                // Like python, input gives back the line as a string without the newline
+                Instr::Input => {
+                    let value: Value = input(&mut self.input, &mut self.output, &[])?;
+                    self.stack.push(value);
+                }
+                Instr::Print => self.print()?,
+                Instr::Call(name, count, position) => {
+                    let value: Value = self.call(name, *count, *position)?;
+                    self.stack.push(value);
+                }
+                Instr::Jump(target) => *pc = *target,
+                Instr::JumpIfFalse(target) => {
+                    if !self.pop().is_truthy() {
+                        *pc = *target;
+                    }
+                }
+                Instr::JumpBy { index, targets } => {
+                    let offset: i64 = match self.pop() {
+                        Value::Int(offset) => offset,
+                        other => {
+                            return Err(runtime_error(format!(
+                                "jump needs an int, not {}",
+                                other.type_name()
+                            )))
+                        }
+                    };
+                    let target: usize = usize::try_from(*index as i64 + offset).map_err(|_| {
+                        runtime_error(format!(
+                            "jump {} goes before the start of the block",
+                            offset
+                        ))
+                    })?;
+                    *pc = *targets.get(target).ok_or_else(|| {
+                        runtime_error(format!("jump {} goes past the end of the block", offset))
+                    })?;
+                }
+                Instr::Func(function) => {
+                    self.functions
+                        .insert(function.name.to_owned(), Rc::clone(function));
+                }
+                Instr::Return => return Ok(self.pop()),
+                Instr::TailCall(name, count, position) => {
+                    return self.tail_call(name, *count, *position)
+                }
+                Instr::Argument(name) => {
+                    let value: Value = constant(name, &self.args)?;
+                    self.stack.push(value);
+                }
+                Instr::Exit(position) => {
+                    let error: RuntimeError = exit_with(&self.pop());
+                    return Err(match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    });
+                }
+                Instr::Assert(source, position) => {
+                    let value: Value = self.pop();
+                    self.asserted(assertion_failure(source, &value, None), position)?;
+                }
+                Instr::AssertEq(position) => {
+                    let expected: Value = self.pop();
+                    let value: Value = self.pop();
+                    self.asserted(assertion_failure("", &value, Some(&expected)), position)?;
+                }
+                Instr::Step => self.step()?,
+                Instr::EnterBlock => self.scopes.push_block(),
+                Instr::ExitBlock => self.scopes.pop_block(),
+                Instr::Global(name) => self.scopes.declare_global(name),
+                Instr::MakeList(count) => {
+                    let items: Vec<Value> = self.stack.split_off(self.stack.len() - count);
+                    self.stack.push(new_list(items));
+                }
+                Instr::Concat(count) => self.concat(*count)?,
+                Instr::MakeDict(count) => {
+                    let items: Vec<Value> = self.stack.split_off(self.stack.len() - count * 2);
+                    let dict: Value = new_dict(Vec::new());
+                    for entry in items.chunks(2) {
+                        store_index(&dict, &entry[0], entry[1].clone())?;
+                    }
+                    self.stack.push(dict);
+                }
+                Instr::MakeRange(position) => {
+                    let end: Value = self.pop();
+                    let start: Value = self.pop();
+                    let range: Value = new_range(start, end).map_err(|error| match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    })?;
+                    self.stack.push(range);
+                }
+                Instr::LoopItems(position) => {
+                    let value: Value = self.pop();
+                    let items: Value = loop_items(value).map_err(|error| match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    })?;
+                    self.stack.push(items);
+                }
+                Instr::ForNext(target) => {
+                    let place: usize = match self.pop() {
+                        Value::Int(place) => place as usize,
+                        other => return Err(not_a_place(other)),
+                    };
+                    let items: &Value = self.stack.last().expect("the stack should not be empty");
+                    match loop_item(items, place) {
+                        Some(item) => {
+                            self.stack.push(Value::Int(place as i64 + 1));
+                            self.stack.push(item);
+                        }
+                        None => {
+                            self.pop();
+                            *pc = *target;
+                        }
+                    }
+                }
+                Instr::Index(position) => {
+                    let place: Value = self.pop();
+                    let target: Value = self.pop();
+                    let value: Value = index(&target, &place).map_err(|error| match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    })?;
+                    self.stack.push(value);
+                }
+                Instr::StoreIndex(position) => {
+                    let place: Value = self.pop();
+                    let target: Value = self.pop();
+                    let value: Value = self.pop();
+                    store_index(&target, &place, value).map_err(|error| match position {
+                        Some(position) => error.at(*position),
+                        None => error,
+                    })?;
+                }
+                Instr::Try(target) => catches.push(Catch {
+                    target: *target,
+                    stack: self.stack.len(),
+                    blocks: self.scopes.blocks(),
+                }),
+                Instr::EndTry => {
+                    catches.pop();
+                }
+            }
+        }
+        Ok(Value::None)
+    }
+    /// These are out of execute so their strings don't make every call take more stack
+    fn print(&mut self) -> Result<(), RuntimeError> {
+        let value: String = show(&self.pop())?;
+        writeln!(self.output, "{}", value).map_err(print_failed)
+    }
+    fn concat(&mut self, count: usize) -> Result<(), RuntimeError> {
+        let parts: Vec<Value> = self.stack.split_off(self.stack.len() - count);
+        let text: String = parts
+            .iter()
+            .map(show)
+            .collect::<Result<String, RuntimeError>>()?;
+        self.stack.push(Value::Str(text.into()));
+        Ok(())
+    }
+    /// The compiler only makes code that pushes before it pops
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("the stack should not be empty")
+    }
+    /// Stop at the assertion if it failed
+    fn asserted(
+        &self,
+        failure: Option<String>,
+        position: &Option<Position>,
+    ) -> Result<(), RuntimeError> {
+        match (failure, position) {
+            (Some(message), Some(position)) => Err(runtime_error(message).at(*position)),
+            (Some(message), None) => Err(runtime_error(message)),
+            (None, _) => Ok(()),
+        }
+    }
+    fn step(&mut self) -> Result<(), RuntimeError> {
+        self.steps += 1;
+        match self.max_steps {
+            Some(max) if self.steps > max => Err(runtime_error(format!(
+                "stopped after {} steps, the program might loop forever",
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+    fn variable(&self, name: &str) -> Result<Value, RuntimeError> {
+        self.scopes
+            .get(name)
+            .cloned()
+            .ok_or_else(|| runtime_error(format!("variable '{}' is not defined", name)))
+    }
+    fn assign(&mut self, name: &str, value: Value) {
+        self.scopes.set(name, value);
+    }
+    fn call(
+        &mut self,
+        name: &str,
+        count: usize,
+        position: Option<Position>,
+    ) -> Result<Value, RuntimeError> {
+        let args: Vec<Value> = self.stack.split_off(self.stack.len() - count);
+        // Functions from the program come before the builtins, like in python
+        let value: Result<Value, RuntimeError> = match self.functions.get(name).cloned() {
+            Some(function) => self.call_function(&function, args),
+            None if name == "input" => input(&mut self.input, &mut self.output, &args),
+            None if name == "elapsed" => elapsed(self.started, &args),
+            None if matches!(name, "rand" | "rand_int") => random(&mut self.random, name, &args),
+            None => call(name, args),
+        };
+        value.map_err(|error| match position {
+            Some(position) => error.at(position),
+            None => error,
+        })
+    }
+    fn call_function(
+        &mut self,
+        function: &FunctionCode,
+        args: Vec<Value>,
+    ) -> Result<Value, RuntimeError> {
+        check_args(function, &args)?;
+        if self.scopes.depth() >= self.max_depth {
+            return Err(runtime_error(
+                "maximum recursion depth exceeded".to_string(),
+            ));
+        }
+
+        // A return from inside of a for loop leaves what the loop was going through
+        // on the stack, which the caller doesn't know about
+        let base: usize = self.stack.len();
+        self.scopes.push_call(&function.params, args);
+        let result: Result<Value, RuntimeError> = self.execute(&function.code);
+        self.scopes.pop_call();
+        self.stack.truncate(base);
+        result
+    }
+    /// Give the running call the arguments of the function being called, for execute
+    /// to run it next. Outside of a function there's no call to take over
+    fn tail_call(
+        &mut self,
+        name: &str,
+        count: usize,
+        position: Option<Position>,
+    ) -> Result<Value, RuntimeError> {
+        let function: Rc<FunctionCode> = match self.functions.get(name).cloned() {
+            Some(function) if self.scopes.depth() > 0 => function,
+            _ => return self.call(name, count, position),
+        };
+        let args: Vec<Value> = self.stack.split_off(self.stack.len() - count);
+        check_args(&function, &args).map_err(|error| match position {
+            Some(position) => error.at(position),
+            None => error,
+        })?;
+        self.scopes.pop_call();
+        self.scopes.push_call(&function.params, args);
+        self.tail = Some(function);
+        Ok(Value::None)
+    }
+}
+
+/// The compiler keeps how far a loop is on the stack under its item, so only a
+/// .mhc file that was changed by hand can have something else there
+fn not_a_place(value: Value) -> RuntimeError {
+    runtime_error(format!(
+        "the bytecode is broken, a for loop got {} for how far it is",
+        value.type_name()
+    ))
+}
+
+fn check_args(function: &FunctionCode, args: &[Value]) -> Result<(), RuntimeError> {
+    match args.len() == function.params.len() {
+        true => Ok(()),
+        false => Err(runtime_error(format!(
+            "{}() takes {} argument{} but {} {} given",
+            function.name,
+            function.params.len(),
+            if function.params.len() == 1 { "" } else { "s" },
+            args.len(),
+            if args.len() == 1 { "was" } else { "were" }
+        ))),
+    }
+}
+
+/// Remove the boiler plate of making a vm
+pub fn new_vm<R: BufRead, W: Write>(input: R, output: W) -> Vm<R, W> {
+    Vm {
+        functions: HashMap::new(),
+        scopes: new_scopes(),
+        stack: Vec::new(),
+        max_steps: None,
+        steps: 0,
+        max_depth: MAX_CALL_DEPTH,
+        args: Vec::new(),
+        started: start_time(),
+        random: start_random(),
+        tail: None,
+        input,
+        output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{new_interpreter, Interpret};
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    fn parse(contents: &str) -> Vec<Stmt> {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        new_parser(lexer.tokens).parse().unwrap()
+    }
+
+    /// Run the program on the vm and with the interpreter, they should always agree
+    fn run(contents: &str, input: &str) -> Result<String, RuntimeError> {
+        let stmts: Vec<Stmt> = parse(contents);
+        let mut tree_output: Vec<u8> = Vec::new();
+        let tree: Result<(), RuntimeError> =
+            new_interpreter(input.as_bytes(), &mut tree_output).run(&stmts);
+
+        let mut output: Vec<u8> = Vec::new();
+        let program: FunctionCode = compile_program(&stmts)?;
+        let result: Result<(), RuntimeError> = new_vm(input.as_bytes(), &mut output).run(&program);
+        assert_eq!(result, tree);
+        assert_eq!(output, tree_output);
+        result?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn vm_test() {
+        assert_eq!(
+            run("set a = 1 + 2 * 3;\nprint a;\nprint -a ** 2;", ""),
+            Ok("7\n-49\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a, b = 1, 2;\nset a, b = b, a;\nset c = d = a;\nprint c + d * 10;",
+                ""
+            ),
+            Ok("22\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set i = 0;\nwhile i < 3 {\n  if i == 1 { print \"one\"; } else { print i; }\n  i += 1;\n}",
+                ""
+            ),
+            Ok("0\none\n2\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "func fib(n) { if n < 2 { return n; } return fib(n - 1) + fib(n - 2); }\nprint fib(10);",
+                ""
+            ),
+            Ok("55\n".to_string())
+        );
+        assert_eq!(
+            run("set a = input;\nprint len(a);", "hello\n"),
+            Ok("5\n".to_string())
+        );
+        // Jumps move between the statements of the block they're in
+        assert_eq!(
+            run("set i = 0;\ni += 1;\nprint i;\nif i < 3 { jump 1; }\njump -4 * (i < 3) + 1;\nprint \"done\";", ""),
+            Ok("1\n2\n3\ndone\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set total = 0;\nfunc add(n) {\n  global total;\n  if n > 0 { set total = total + n; }\n}\nadd(2);\nadd(3);\nprint total;",
+                ""
+            ),
+            Ok("5\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = 0;\nwhile a < 2 {\n  set b = a * 10;\n  a += 1;\n  print b;\n}\nprint a;",
+                ""
+            ),
+            Ok("0\n10\n2\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set grid = [[1, 2], [3]];\nset row = grid[0];\nset grid[0][1] = len(grid) * 10;\nprint row;\nprint grid[-1][0] + row[1];",
+                ""
+            ),
+            Ok("[1, 20]\n23\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set m = {\"a\": [1], \"b\": 2};\nset m[\"a\"][0] = m[\"b\"];\nset m[\"c\"] = \"c\" in m;\nprint m;",
+                ""
+            ),
+            Ok("{'a': [2], 'b': 2, 'c': False}\n".to_string())
+        );
+        assert_eq!(
+            run("set n = 2;\nprint \"{n} squared is {n ** 2}\";", ""),
+            Ok("2 squared is 4\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = 0;\nprint a and 1 / a;\nprint a or \"b\";\nprint not a == 1 and 2;",
+                ""
+            ),
+            Ok("0\nb\n2\n".to_string())
+        );
+        assert_eq!(
+            run("set a = 0;\nprint a ? 1 / a : a == 0 ? \"zero\" : 1;", ""),
+            Ok("zero\n".to_string())
+        );
+        // A break leaves the scopes it's in and takes what the for has off the stack
+        assert_eq!(
+            run(
+                "for x in 0..4 {\n  if x == 1 { continue; }\n  for y in [1, 2] {\n    if x == 2 { set z = y; break; }\n    print x * y;\n  }\n  if x == 3 { break; }\n}\nset n = 0;\nwhile n < 9 { n += 1; if n > 2 { break; } }\nprint n;",
+                ""
+            ),
+            Ok("0\n0\n3\n6\n3\n".to_string())
+        );
+        // Returning from inside a loop leaves its items behind on the stack
+        assert_eq!(
+            run(
+                "func first(xs) { for x in xs { return x; } }\nfor i in 0..3 {\n  for c in \"ab\" { print first([i, c]); }\n}",
+                ""
+            ),
+            Ok("0\n0\n1\n1\n2\n2\n".to_string())
+        );
+    }
+
+    #[test]
+    fn disassemble_test() {
+        let program: FunctionCode =
+            compile_unoptimized(&parse("func f(a) { return -a; }\nprint f(\"x\") + 1;")).unwrap();
+        assert_eq!(
+            program.to_string(),
+            "   0 STEP\n   1 FUNC f(a)\n        0 STEP\n        1 LOAD a\n        2 NEGATE\n        3 RETURN\n        4 PUSH None\n        5 RETURN\n   2 STEP\n   3 PUSH \"x\"\n   4 CALL f 1\n   5 PUSH 1\n   6 BINARY +\n   7 PRINT\n"
+        );
+    }
+
+    #[test]
+    fn peephole_test() {
+        let contents: &str = "set x = 1 + 2 * 3;\nset x = x;\nif x > 2 {\n    print x;\n} else {\n}\nfunc f(a) {\n    set a = a;\n    return a;\n}\nprint f(x) + \"a\" * 2;\n";
+        assert_eq!(
+            compile_program(&parse(contents)).unwrap().to_string(),
+            "   0 STEP\n   1 PUSH 7\n   2 STORE x\n   3 STEP\n   4 STEP\n   5 LOAD x\n   6 PUSH 2\n   7 BINARY >\n   8 JMP_IF_FALSE 14\n   9 ENTER_BLOCK\n  10 STEP\n  11 LOAD x\n  12 PRINT\n  13 EXIT_BLOCK\n  14 STEP\n  15 FUNC f(a)\n        0 STEP\n        1 LOAD a\n        2 STORE a\n        3 STEP\n        4 LOAD a\n        5 RETURN\n  16 STEP\n  17 LOAD x\n  18 CALL f 1\n  19 PUSH \"aa\"\n  20 BINARY +\n  21 PRINT\n"
+        );
+        // Only the first instruction of a pattern can be jumped to, and it still
+        // runs the same with the jumps moved
+        let contents: &str = "set i = 0;\nwhile i < 3 {\n    i += 1;\n    if i == 2 {\n        continue;\n    }\n    print i + 0 * 1;\n}\nprint \"a\" - 1;\n";
+        let program: FunctionCode = compile_program(&parse(contents)).unwrap();
+        let listing: String = program.to_string();
+        assert!(listing.contains("  23 JMP 29\n"));
+        assert!(listing.contains("  25 LOAD i\n  26 PUSH 0\n  27 BINARY +\n"));
+        assert!(listing.contains("  32 PUSH \"a\"\n  33 PUSH 1\n  34 BINARY -\n"));
+        let mut output: Vec<u8> = Vec::new();
+        let error: RuntimeError = new_vm(&b""[..], &mut output).run(&program).unwrap_err();
+        assert_eq!(error.message, "can't use '-' on a string and an int");
+        assert_eq!(output, b"1\n3\n");
+    }
+
+    #[test]
+    fn tail_call_test() {
+        let contents: &str = "func count(n, total) {\n    if n == 0 {\n        return total;\n    }\n    return count(n - 1, total + n);\n}\nprint count(100000, 0);\n";
+        let program: FunctionCode = compile_program(&parse(contents)).unwrap();
+        assert!(program.to_string().contains("TAIL_CALL count 2"));
+        // Each call takes the place of the one before, so it goes far deeper than
+        // calls can, which the interpreter doesn't do
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(new_vm(&b""[..], &mut output).run(&program), Ok(()));
+        assert_eq!(output, b"5000050000\n");
+        assert_eq!(
+            new_interpreter(&b""[..], Vec::new())
+                .run(&parse(contents))
+                .unwrap_err()
+                .message,
+            "maximum recursion depth exceeded"
+        );
+
+        // Inside of a try it's a call so the try can still catch it
+        let program: FunctionCode = compile_program(&parse(
+            "func f(n) {\n    try {\n        return f(n);\n    } catch e {\n        return e;\n    }\n}\n",
+        ))
+        .unwrap();
+        assert!(!program.to_string().contains("TAIL_CALL"));
+        assert_eq!(
+            run("func f(s) { return len(s); }\nprint f(\"abc\");", ""),
+            Ok("3\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "func f(a) { return g(a); }\nfunc g() { return 1; }\nprint f(1);",
+                ""
+            )
+            .unwrap_err()
+            .position
+            .map(|x| x.char_num),
+            Some(20)
+        );
+    }
+
+    #[test]
+    fn encode_test() {
+        let program: FunctionCode = compile_program(&parse(
+            "func f(a, b) { return a ** b / 2.5; }\nset s = \"hi\\n\";\nprint f(2, -3);\njump 1;\nprint s;",
+        ))
+        .unwrap();
+        let bytes: Vec<u8> = encode_program(&program);
+        assert!(bytes.starts_with(b"MHC\0\x0c\x00"));
+        assert_eq!(decode_program(&bytes), Ok(program));
+
+        // Anything that isn't a whole file from this version gets refused
+        assert_eq!(
+            decode_program(b"print 1;"),
+            Err("not a mahou bytecode file".to_string())
+        );
+        let mut old: Vec<u8> = bytes.clone();
+        old[4] = 0;
+        assert!(decode_program(&old)
+            .unwrap_err()
+            .starts_with("the bytecode file is version 0"));
+        assert_eq!(
+            decode_program(&bytes[..bytes.len() - 1]),
+            Err("the bytecode file ends too early".to_string())
+        );
+
+        // Or code the compiler wouldn't make, which the vm would crash on
+        let decode = |code: Vec<Instr>| -> Result<FunctionCode, String> {
+            decode_program(&encode_program(&FunctionCode {
+                name: "main".to_string(),
+                params: Vec::new(),
+                code,
+            }))
+        };
+        let push: Instr = Instr::Push(Value::Int(1));
+        assert!(decode(vec![push.clone(), Instr::MakeList(1), Instr::Print]).is_ok());
+        assert_eq!(
+            decode(vec![push.clone(), Instr::Call("len".to_string(), 2, None)]),
+            Err("the bytecode file takes more off the stack than it has at 1 in main".to_string())
+        );
+        assert_eq!(
+            decode(vec![
+                push.clone(),
+                Instr::JumpIfFalse(3),
+                push.clone(),
+                Instr::Pop,
+                Instr::Pop
+            ]),
+            Err("the bytecode file takes more off the stack than it has at 4 in main".to_string())
+        );
+        assert_eq!(
+            decode(vec![push, Instr::Jump(9)]),
+            Err(
+                "the bytecode file has a jump to 9 in main, which only has 2 instructions"
+                    .to_string()
+            )
+        );
+        let inner: FunctionCode = FunctionCode {
+            name: "f".to_string(),
+            params: Vec::new(),
+            code: vec![Instr::Return],
+        };
+        assert_eq!(
+            decode(vec![Instr::Func(Rc::new(inner))]),
+            Err("the bytecode file takes more off the stack than it has at 0 in f".to_string())
+        );
+    }
+
+    #[test]
+    fn vm_error_test() {
+        let message = |contents: &str| -> String { run(contents, "").unwrap_err().message };
+        assert_eq!(message("set a = 0;\nprint 1 / a;"), "division by zero");
+        assert_eq!(
+            message("jump -1;"),
+            "jump -1 goes before the start of the block"
+        );
+        assert_eq!(message("jump 3;"), "jump 3 goes past the end of the block");
+        assert_eq!(
+            message("func f(a) { return a; }\nprint f();"),
+            "f() takes 1 argument but 0 were given"
+        );
+        assert_eq!(
+            message("func f() { f(); }\nprint f();"),
+            "maximum recursion depth exceeded"
+        );
+
+        // A try gets the stack and the scopes back to how they were, and a break
+        // out of one stops it from catching
+        assert_eq!(
+            run(
+                "set a = 0;\nfor x in [1, 2] {\n  try { print x + [1][x]; } catch e { set b = 1; print e; }\n}\n\
+                 for x in [1] { try { break; } catch e { print e; } }\nprint 1 / a;",
+                ""
+            )
+            .unwrap_err()
+            .message,
+            "division by zero"
+        );
+        assert_eq!(
+            run(
+                "func f(n) { for x in [n] { return [n][n]; } }\ntry { print f(1); } catch e { print e; }\nprint 3;",
+                ""
+            ),
+            Ok("list index out of range\n3\n".to_string())
+        );
+
+        let stmts: Vec<Stmt> = parse("while 1 { }");
+        let mut vm: Vm<&[u8], Vec<u8>> = new_vm(&b""[..], Vec::new());
+        vm.max_steps = Some(100);
+        assert_eq!(
+            vm.run(&compile_program(&stmts).unwrap())
+                .unwrap_err()
+                .message,
+            "stopped after 100 steps, the program might loop forever"
+        );
+    }
+
+    #[test]
+    fn nesting_test() {
+        assert_eq!(
+            run(
+                "set xs = [];\nset i = 0;\nwhile i < 200000 { set xs = [xs]; i++; }\nprint len(xs);",
+                ""
+            ),
+            Ok("1\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set xs = [];\nset i = 0;\nwhile i < 5000 { set xs = [xs]; i++; }\nprint \"{xs}\";",
+                ""
+            )
+            .unwrap_err()
+            .message,
+            "can't show lists and dicts nested more than 1000 deep"
+        );
+    }
+}
@@ -0,0 +1,3526 @@
+//! Turns the statements into python, or one of the other backends
+
+use crate::analysis::jump_target;
+use crate::ast::{walk_expr, walk_stmt, Expr, MatchArm, Position, Stmt, Visit};
+use crate::debugger::LineTable;
+use crate::dump::json_string;
+use crate::formatter::expr_source;
+use crate::lexer::{is_float_literal, Tokens};
+use crate::parser::{
+    constant_value, is_comparison, is_literal, is_right_associative, operator_text, precedence,
+    ARGUMENT_CONSTANTS, NOT_PRECEDENCE,
+};
+use crate::spacer;
+use crate::stdlib::{program_functions, BUILTINS};
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+#[cfg(feature = "black")]
+use std::io::Write;
+#[cfg(feature = "black")]
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+/// What the program gets turned into, one of the backends or the tokens
+/// or tree from along the way
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Emit {
+    Tokens,
+    Ast,
+    Bytecode,
+    Python,
+    Js,
+    C,
+    Wasm,
+    Pseudocode,
+    /// The source colored by its tokens, as HTML or for a terminal
+    Highlight,
+}
+
+impl Emit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Emit::Tokens => "tokens",
+            Emit::Ast => "ast",
+            Emit::Bytecode => "bytecode",
+            Emit::Python => "python",
+            Emit::Js => "javascript",
+            Emit::C => "c",
+            Emit::Wasm => "wasm",
+            Emit::Pseudocode => "pseudocode",
+            Emit::Highlight => "highlight",
+        }
+    }
+    /// What a file of it ends in, for how each one comes out when it isn't going
+    /// to a terminal
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Emit::Tokens => "json",
+            Emit::Ast | Emit::Bytecode | Emit::Pseudocode => "txt",
+            Emit::Python => "py",
+            Emit::Js => "js",
+            Emit::C => "c",
+            Emit::Wasm => "wat",
+            Emit::Highlight => "html",
+        }
+    }
+    /// The backend that makes the code, tokens, ast and bytecode don't have one.
+    /// The statements are for backends that look at the whole program first
+    pub fn backend(&self, print_style: PrintStyle, stmts: &[Stmt]) -> Option<Box<dyn Backend>> {
+        match self {
+            Emit::Tokens | Emit::Ast | Emit::Bytecode | Emit::Highlight => None,
+            Emit::Python => Some(Box::new(PythonBackend::new(print_style, stmts))),
+            Emit::Js => Some(Box::new(JsBackend::new(stmts))),
+            Emit::C => Some(Box::new(CBackend::new(stmts))),
+            Emit::Wasm => Some(Box::new(WasmBackend::new(stmts))),
+            Emit::Pseudocode => Some(Box::new(PseudocodeBackend)),
+        }
+    }
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(emit: &str) -> Result<Self, Self::Err> {
+        match emit {
+            "tokens" => Ok(Emit::Tokens),
+            "ast" => Ok(Emit::Ast),
+            "bytecode" => Ok(Emit::Bytecode),
+            "python" => Ok(Emit::Python),
+            "js" | "javascript" => Ok(Emit::Js),
+            "c" => Ok(Emit::C),
+            "wasm" | "wat" => Ok(Emit::Wasm),
+            "pseudocode" => Ok(Emit::Pseudocode),
+            "highlight" => Ok(Emit::Highlight),
+            _ => Err(format!(
+                "unknown emit '{}', expected tokens, ast, bytecode, python, js, c, wasm, pseudocode or highlight",
+                emit
+            )),
+        }
+    }
+}
+
+/// The two ways python can print, print(x) in python 3 and print x in python 2
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PrintStyle {
+    Function,
+    Statement,
+}
+
+impl FromStr for PrintStyle {
+    type Err = String;
+
+    fn from_str(style: &str) -> Result<Self, Self::Err> {
+        match style {
+            "function" => Ok(PrintStyle::Function),
+            "statement" => Ok(PrintStyle::Statement),
+            _ => Err(format!(
+                "unknown print style '{}', expected function or statement",
+                style
+            )),
+        }
+    }
+}
+
+/// Turn an expression into python
+pub fn python_expr(expr: &Expr) -> String {
+    infix_expr(expr, &PythonBackend::new(PrintStyle::Function, &[]))
+}
+
+/// Turn an expression into infix text with the backend spelling input and the
+/// constants, adding parentheses only where precedence needs them. Python parses
+/// the same operators as javascript, so every backend gets its expressions from here
+pub fn infix_expr(expr: &Expr, backend: &dyn Backend) -> String {
+    match expr {
+        Expr::Numeric(number) => number.to_owned(),
+        Expr::Str(text) => json_string(text),
+        Expr::Bool(value) => backend.boolean(*value),
+        Expr::Identifier(name, _) => backend.variable(name),
+        Expr::Constant(name) => backend.constant(name),
+        Expr::Input => backend.input(),
+        Expr::Call { name, args, .. } => {
+            let args: Vec<String> = args.iter().map(|x| infix_expr(x, backend)).collect();
+            backend.call(name, &args)
+        }
+        Expr::List(items) => {
+            let items: Vec<String> = items.iter().map(|x| infix_expr(x, backend)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Expr::Format(parts) => backend.format_string(parts),
+        Expr::Dict(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}: {}",
+                        infix_expr(key, backend),
+                        infix_expr(value, backend)
+                    )
+                })
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Expr::Range { start, end, .. } => {
+            backend.range(&infix_expr(start, backend), &infix_expr(end, backend))
+        }
+        // Indexing binds tighter than any operator, so `(a + b)[0]` keeps them
+        Expr::Index { target, index, .. } => match **target {
+            Expr::Unary { .. } | Expr::Binary { .. } | Expr::Ternary { .. } => format!(
+                "({})[{}]",
+                infix_expr(target, backend),
+                infix_expr(index, backend)
+            ),
+            _ => format!(
+                "{}[{}]",
+                infix_expr(target, backend),
+                infix_expr(index, backend)
+            ),
+        },
+        Expr::Unary { operator, expr } => {
+            // A word like `not` needs a space after it
+            let text: String = match backend.operator(*operator) {
+                word if word.ends_with(char::is_alphabetic) => format!("{} ", word),
+                symbol => symbol.to_string(),
+            };
+            match **expr {
+                // `--a` would be a decrement in javascript, so a double negation is `-(-a)`
+                Expr::Unary { .. } | Expr::Ternary { .. } => {
+                    format!("{}({})", text, infix_expr(expr, backend))
+                }
+                Expr::Binary {
+                    operator: inner, ..
+                } if inner != Tokens::Power || backend.unary_power_parens() => {
+                    format!("{}({})", text, infix_expr(expr, backend))
+                }
+                _ => format!("{}{}", text, infix_expr(expr, backend)),
+            }
+        }
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } if backend.operator_function(left, *operator, right).is_some() => format!(
+            "{}({}, {})",
+            backend.operator_function(left, *operator, right).unwrap(),
+            infix_expr(left, backend),
+            infix_expr(right, backend)
+        ),
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => {
+            let prec: u8 = precedence(*operator).unwrap_or(0);
+            // Python chains comparisons, so `(a < b) < c` has to keep its parentheses
+            let chains: bool = is_comparison(*operator);
+            let right_associative: bool = is_right_associative(*operator);
+            let wrap = |child: &Expr, is_right: bool| -> String {
+                let text: String = infix_expr(child, backend);
+                match child {
+                    Expr::Binary {
+                        left,
+                        operator,
+                        right,
+                        ..
+                    } if backend.operator_function(left, *operator, right).is_none() => {
+                        let child_prec: u8 = precedence(*operator).unwrap_or(0);
+                        // The side that doesn't match how the operator associates needs them
+                        let other_side: bool = is_right != right_associative;
+                        if child_prec < prec || ((other_side || chains) && child_prec == prec) {
+                            format!("({})", text)
+                        } else {
+                            text
+                        }
+                    }
+                    // `(-2) ** 2` would be `-(2 ** 2)` in python without them
+                    Expr::Unary { .. } if right_associative && !is_right => format!("({})", text),
+                    // `(not a) == b` would be `not (a == b)` without them
+                    Expr::Unary {
+                        operator: Tokens::Not,
+                        ..
+                    } if prec >= NOT_PRECEDENCE => format!("({})", text),
+                    Expr::Ternary { .. } => format!("({})", text),
+                    _ => text,
+                }
+            };
+            format!(
+                "{} {} {}",
+                wrap(left, false),
+                backend.operator(*operator),
+                wrap(right, true)
+            )
+        }
+        // Only a `?:` at the end can go without parentheses, in python too
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => {
+            let wrap = |child: &Expr| -> String {
+                match child {
+                    Expr::Ternary { .. } => format!("({})", infix_expr(child, backend)),
+                    _ => infix_expr(child, backend),
+                }
+            };
+            backend.ternary(
+                &wrap(condition),
+                &wrap(then),
+                &infix_expr(otherwise, backend),
+            )
+        }
+    }
+}
+
+/// How an operator is written in a language with C's syntax, where the
+/// logical ones are symbols instead of words
+pub fn c_operator(operator: Tokens) -> &'static str {
+    match operator {
+        Tokens::And => "&&",
+        Tokens::Or => "||",
+        Tokens::Not => "!",
+        _ => operator_text(operator),
+    }
+}
+
+/// The inside of a string with values put into it, the text escaped like any other
+/// string with its braces doubled and each value in braces, like `x is {x}`
+pub fn format_inside(parts: &[Expr], hole: impl Fn(&Expr) -> String) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            Expr::Str(text) => {
+                let quoted: String = json_string(text);
+                quoted[1..quoted.len() - 1]
+                    .replace('{', "{{")
+                    .replace('}', "}}")
+            }
+            _ => format!("{{{}}}", hole(part)),
+        })
+        .collect()
+}
+
+/// An output language, each method turns one piece of a statement into a line
+/// and the codegen takes care of walking the statements and indenting the blocks
+pub trait Backend {
+    fn expr(&self, expr: &Expr) -> String;
+    fn input(&self) -> String;
+    fn constant(&self, name: &str) -> String;
+    /// A bool literal, written like python's by default
+    fn boolean(&self, value: bool) -> String {
+        if value { "True" } else { "False" }.to_string()
+    }
+    /// The import line an expression needs, if it needs one
+    fn import(&self, _expr: &Expr) -> Option<String> {
+        None
+    }
+    /// The import line a statement needs apart from the ones for its expressions
+    fn stmt_import(&self, _stmt: &Stmt) -> Option<String> {
+        None
+    }
+    /// What a variable is called in the output, for languages where some of the
+    /// names mahou allows can't be used
+    fn variable(&self, name: &str) -> String {
+        name.to_string()
+    }
+    /// What a function from the program is called in the output
+    fn function(&self, name: &str) -> String {
+        name.to_string()
+    }
+    /// Calling a function, the backend turns a builtin into what its language calls it
+    fn call(&self, name: &str, args: &[String]) -> String {
+        format!("{}({})", name, args.join(", "))
+    }
+    /// A string with values put into it, written the same as mahou by default
+    fn format_string(&self, parts: &[Expr]) -> String {
+        format!("\"{}\"", format_inside(parts, |x| self.expr(x)))
+    }
+    /// How a binary operator is spelled, most backends write it the same as mahou
+    fn operator(&self, operator: Tokens) -> &'static str {
+        operator_text(operator)
+    }
+    /// A function to call with both sides instead of using the operator, for
+    /// when the language's operator does something different than python's
+    fn operator_function(
+        &self,
+        _left: &Expr,
+        _operator: Tokens,
+        _right: &Expr,
+    ) -> Option<&'static str> {
+        None
+    }
+    /// Whether `-2 ** 2` has to be written `-(2 ** 2)`, which it does in javascript
+    fn unary_power_parens(&self) -> bool {
+        false
+    }
+    fn set(&self, name: &str, value: &str) -> String;
+    fn set_chain(&self, names: &[String], value: &str) -> String;
+    fn set_many(&self, names: &[String], values: &[String]) -> String;
+    /// Setting an item of a list, the target is already indexed like `xs[0]`,
+    /// which most backends set the same way as a variable
+    fn set_index(&self, target: &str, value: &str) -> String {
+        self.set(target, value)
+    }
+    fn print(&self, expr: &str) -> String;
+    /// Print with the expression itself, for backends that need to know what kind of value it is
+    fn print_expr(&self, expr: &Expr) -> String {
+        self.print(&self.expr(expr))
+    }
+    /// Print more than one value with a space between, which is the same as
+    /// printing a string with each of them put into it
+    fn print_many(&self, exprs: &[Expr]) -> String {
+        if let [expr] = exprs {
+            return self.print_expr(expr);
+        }
+        let mut parts: Vec<Expr> = Vec::new();
+        for (index, expr) in exprs.iter().enumerate() {
+            if index > 0 {
+                parts.push(Expr::Str(" ".to_string()));
+            }
+            match expr {
+                Expr::Format(inside) => parts.extend(inside.iter().cloned()),
+                _ => parts.push(expr.clone()),
+            }
+        }
+        self.print_expr(&Expr::Format(parts))
+    }
+    fn jump(&self, offset: &str) -> String;
+    /// Going straight to a label, for backends that can lower a jump with a
+    /// constant offset into one
+    fn goto(&self, _label: &str) -> Option<String> {
+        None
+    }
+    fn label(&self, label: &str) -> String {
+        format!("{}:", label)
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String;
+    fn if_start(&self, condition: &str) -> String;
+    fn if_end(&self) -> Option<String>;
+    fn else_start(&self) -> String;
+    /// Going on to another condition in the same if, like python's `elif`. A
+    /// backend without one gets an if inside of the else instead
+    fn elif_start(&self, _condition: &str) -> Option<String> {
+        None
+    }
+    /// The line a try starts with. A backend without a way to catch errors gives
+    /// None, and only the body is output so an error still stops the program
+    fn try_start(&self) -> Option<String> {
+        None
+    }
+    /// The line between the body and the handler, with the name the error goes in
+    fn catch_start(&self, _name: &str) -> Option<String> {
+        None
+    }
+    /// The first line of the handler, for a language where what gets caught
+    /// has to be turned into its message
+    fn caught(&self, _name: &str) -> Option<String> {
+        None
+    }
+    fn try_end(&self) -> Option<String> {
+        self.if_end()
+    }
+    fn while_start(&self, condition: &str) -> String;
+    fn while_end(&self) -> Option<String>;
+    /// This gets the expression itself, so a backend without python's for loop
+    /// can count through a range like `0..10` instead
+    fn for_start(&self, name: &str, iterable: &Expr) -> String;
+    fn for_end(&self) -> Option<String>;
+    /// A range as a value like `0..10`, most backends spell it the same as python
+    fn range(&self, start: &str, end: &str) -> String {
+        format!("range({}, {})", start, end)
+    }
+    /// Picking one of two values, most backends have C's `?:` for it
+    fn ternary(&self, condition: &str, then: &str, otherwise: &str) -> String {
+        format!("{} ? {} : {}", condition, then, otherwise)
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String;
+    /// This is asked for after the body, so a backend that keeps track of the
+    /// function it's in knows here that it's over
+    fn func_end(&self) -> Option<String>;
+    fn return_value(&self, value: Option<&str>) -> String;
+    /// Leaving the loop for a break or going around again for a continue. The depth
+    /// is how many ifs are between it and the loop, for wasm where a branch counts
+    /// every block it goes out of
+    fn loop_exit(&self, is_break: bool, _depth: usize) -> String {
+        if is_break { "break" } else { "continue" }.to_string()
+    }
+    /// Stopping the whole program with the exit code, from anywhere in it
+    fn exit(&self, code: &str) -> String;
+    /// Stopping with the message when the condition is false, from `assert` and `assert_eq`
+    fn assert(&self, condition: &str, message: &str) -> String;
+    /// The line for `global`, backends that declare every variable don't need one
+    fn global(&self, _names: &[String]) -> Option<String> {
+        None
+    }
+    fn empty_block(&self) -> Option<String>;
+    /// An expression on its own line, where what it gives back isn't used
+    fn expr_stmt(&self, expr: &str) -> String {
+        expr.to_string()
+    }
+    /// The lines that declare the variables a program or function sets, before
+    /// anything else in it, for languages that need them declared
+    fn declare(&self, _names: &[String]) -> Vec<String> {
+        Vec::new()
+    }
+    /// Lines that always go at the top, in this order before the imports
+    fn header(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Lines that always go at the very end
+    fn footer(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// The start of the function the program runs in, for languages like C where
+    /// the functions go outside of it. The block gets closed like a function
+    fn main_start(&self) -> Option<String> {
+        None
+    }
+}
+
+/// What `parse` is in python, since int and float both error on text that isn't one
+const PYTHON_PARSE: &str = "def parse(text):\n    try:\n        return int(text)\n    except ValueError:\n        try:\n            return float(text)\n        except ValueError:\n            return text";
+
+/// Python's % takes the sign of the right side, javascript's takes the left's
+const JS_MOD: &str = "function mahou_mod(a, b) {\n    return ((a % b) + b) % b;\n}";
+
+/// The same as python's parse, a number only if all of the text is one
+const JS_PARSE: &str = "function parse(text) {\n    const number = Number(text);\n    return typeof text === \"string\" && (text.trim() === \"\" || Number.isNaN(number)) ? text : number;\n}";
+
+/// The time the program started, for elapsed
+const PYTHON_ELAPSED: &str = "import time\nmahou_start = time.perf_counter()";
+
+/// Node and browsers can only wait without giving up the thread through Atomics
+const JS_SLEEP: &str = "function sleep(ms) {\n    Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, ms);\n}";
+
+/// Javascript's + would turn both lists into text and join that
+const JS_CONCAT: &str = "function mahou_concat(a, b) {\n    return a.concat(b);\n}";
+
+/// `+=` on a list adds to the same one, so every variable sharing it sees the items
+const JS_EXTEND: &str = "function mahou_extend(list, items) {\n    for (const item of items) {\n        list.push(item);\n    }\n}";
+
+/// Shows a value the way the interpreter prints it, like `[1, 'a']` and `True`
+const JS_SHOW: &str = "function mahou_show(value, seen = []) {\n    if (seen.includes(value)) {\n        return Array.isArray(value) ? \"[...]\" : \"{...}\";\n    }\n    if (Array.isArray(value)) {\n        return \"[\" + value.map((x) => mahou_show(x, [...seen, value])).join(\", \") + \"]\";\n    }\n    if (typeof value === \"boolean\") {\n        return value ? \"True\" : \"False\";\n    }\n    if (value === undefined || value === null) {\n        return \"None\";\n    }\n    if (typeof value === \"object\") {\n        const inside = [...seen, value];\n        return \"{\" + Object.entries(value).map(([k, v]) => mahou_show(k, inside) + \": \" + mahou_show(v, inside)).join(\", \") + \"}\";\n    }\n    if (typeof value === \"string\" && seen.length > 0) {\n        const text = JSON.stringify(value).slice(1, -1).replaceAll(\"\\\\\\\"\", \"\\\"\");\n        return value.includes(\"'\") && !value.includes(\"\\\"\") ? \"\\\"\" + text + \"\\\"\" : \"'\" + text.replaceAll(\"'\", \"\\\\'\") + \"'\";\n    }\n    return String(value);\n}";
+
+/// Node has no prompt, so all of stdin is read the first time and handed out a
+/// line at a time. Past the end it's an empty line, the same as the interpreter
+const JS_INPUT: &str = "function mahou_input(text = \"\") {\n    process.stdout.write(String(text));\n    if (mahou_input.lines === undefined) {\n        mahou_input.lines = require(\"fs\").readFileSync(0, \"utf8\").split(\"\\n\");\n    }\n    return (mahou_input.lines.shift() ?? \"\").replace(/\\r$/, \"\");\n}";
+
+/// Either end can be picked, like python's randint
+const JS_RAND_INT: &str = "function rand_int(low, high) {\n    return low + Math.floor(Math.random() * (high - low + 1));\n}";
+
+/// A variable that isn't set is an empty string, the import is inside so it
+/// stays with the other helpers
+const PYTHON_ENV: &str = "def env(name):\n    import os\n    return os.environ.get(name, \"\")";
+
+/// Python needs the file opened and closed around reading or writing it
+const PYTHON_READ_FILE: &str =
+    "def read_file(path):\n    with open(path) as file:\n        return file.read()";
+const PYTHON_WRITE_FILE: &str =
+    "def write_file(path, text):\n    with open(path, \"w\") as file:\n        file.write(text)";
+
+/// Python's keywords and the names the output uses on its own, like range for
+/// a for loop and math for `$PI`, which nothing from the program can be called
+const PYTHON_RESERVED: [&str; 49] = [
+    "False",
+    "None",
+    "True",
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "elif",
+    "else",
+    "except",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "is",
+    "lambda",
+    "nonlocal",
+    "not",
+    "or",
+    "pass",
+    "raise",
+    "return",
+    "try",
+    "while",
+    "with",
+    "yield",
+    "print",
+    "range",
+    "len",
+    "input",
+    "int",
+    "float",
+    "math",
+    "sys",
+    "random",
+    "time",
+    "open",
+    "SystemExit",
+    "ValueError",
+    "NotImplementedError",
+];
+
+/// Javascript's reserved words and the names the output uses on its own, like
+/// Math for the builtins and the helpers that get put at the top
+const JS_RESERVED: [&str; 67] = [
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+    "arguments",
+    "eval",
+    "undefined",
+    "NaN",
+    "Infinity",
+    "Math",
+    "String",
+    "Number",
+    "Array",
+    "Date",
+    "Error",
+    "console",
+    "process",
+    "require",
+    "performance",
+    "mahou_input",
+    "setTimeout",
+    "mahou_mod",
+    "mahou_concat",
+    "mahou_show",
+    "mahou_extend",
+];
+
+/// A name without the `_`s at the end, and how many there were
+fn stem(name: &str) -> (&str, usize) {
+    let stem: &str = name.trim_end_matches('_');
+    (stem, name.len() - stem.len())
+}
+
+/// The names of the variables in the program without the `_`s at the end
+fn variable_stems(stmts: &[Stmt]) -> BTreeSet<String> {
+    let mut names: VariableNames = VariableNames::default();
+    for stmt in stmts {
+        names.visit_stmt(stmt);
+    }
+    names.names.iter().map(|x| stem(x).0.to_string()).collect()
+}
+
+/// Python and javascript have one name for both a function and a variable and
+/// mahou has two, so a name that would clash gets `_`s put on the end. A variable
+/// gets an odd number of them and a function an even number, so they can't clash
+/// with each other or with a name that already ended in `_`
+fn mangle_variable(reserved: &[&str], functions: &[String], name: &str) -> String {
+    let (stem, count): (&str, usize) = stem(name);
+    let clashes: bool = reserved.contains(&stem)
+        || BUILTINS.contains(&stem)
+        || functions.iter().any(|x| self::stem(x).0 == stem);
+    match clashes {
+        true => format!("{}{}", stem, "_".repeat(count * 2 + 1)),
+        false => name.to_string(),
+    }
+}
+
+/// What a function from the program is called, see mangle_variable
+fn mangle_function(reserved: &[&str], variables: &BTreeSet<String>, name: &str) -> String {
+    let (stem, count): (&str, usize) = stem(name);
+    if reserved.contains(&stem) {
+        format!("{}{}", stem, "_".repeat(count * 2 + 2))
+    } else if variables.contains(stem) {
+        format!("{}{}", stem, "_".repeat(count * 2))
+    } else {
+        name.to_string()
+    }
+}
+
+/// Every name the program uses for a variable, anywhere in it
+#[derive(Default)]
+struct VariableNames {
+    names: BTreeSet<String>,
+}
+
+impl Visit for VariableNames {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Set { name, .. }
+            | Stmt::Const { name, .. }
+            | Stmt::SetIndex { name, .. }
+            | Stmt::AugAssign { name, .. }
+            | Stmt::For { name, .. } => {
+                self.names.insert(name.to_owned());
+            }
+            Stmt::SetChain { names, .. }
+            | Stmt::SetMany { names, .. }
+            | Stmt::Global { names }
+            | Stmt::Func { params: names, .. } => self.names.extend(names.iter().cloned()),
+            _ => {}
+        }
+        walk_stmt(self, stmt);
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Identifier(name, _) = expr {
+            self.names.insert(name.to_owned());
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// The default backend, outputs python
+pub struct PythonBackend {
+    print_style: PrintStyle,
+    functions: Vec<String>,
+    /// The names of the variables without the `_`s at the end
+    variables: BTreeSet<String>,
+}
+
+impl PythonBackend {
+    pub fn new(print_style: PrintStyle, stmts: &[Stmt]) -> PythonBackend {
+        PythonBackend {
+            print_style,
+            functions: program_functions(stmts),
+            variables: variable_stems(stmts),
+        }
+    }
+}
+
+impl Backend for PythonBackend {
+    fn expr(&self, expr: &Expr) -> String {
+        infix_expr(expr, self)
+    }
+    fn variable(&self, name: &str) -> String {
+        mangle_variable(&PYTHON_RESERVED, &self.functions, name)
+    }
+    fn function(&self, name: &str) -> String {
+        mangle_function(&PYTHON_RESERVED, &self.variables, name)
+    }
+    fn call(&self, name: &str, args: &[String]) -> String {
+        match (self.functions.iter().any(|x| x == name), name, args) {
+            (true, _, _) => format!("{}({})", self.function(name), args.join(", ")),
+            // The places go right into the format when they're a number
+            (false, "fmt", [value, places]) if places.parse::<u32>().is_ok() => {
+                format!("format({}, \".{}f\")", value, places)
+            }
+            (false, "fmt", [value, places]) => {
+                format!("format({}, \".\" + str({}) + \"f\")", value, places)
+            }
+            (false, "now", _) => format!("time.time({})", args.join(", ")),
+            (false, "sleep", [ms]) => format!("time.sleep({} / 1000)", ms),
+            (false, "elapsed", []) => "(time.perf_counter() - mahou_start)".to_string(),
+            (false, "rand", _) => format!("random.random({})", args.join(", ")),
+            (false, "rand_int", _) => format!("random.randint({})", args.join(", ")),
+            (false, _, _) => format!("{}({})", name, args.join(", ")),
+        }
+    }
+    fn input(&self) -> String {
+        "input()".to_string()
+    }
+    // A value with fmt uses the places in the f-string itself, like `{x:.2f}`
+    fn format_string(&self, parts: &[Expr]) -> String {
+        let hole = |part: &Expr| match part {
+            Expr::Call { name, args, .. }
+                if name == "fmt" && args.len() == 2 && !self.functions.contains(name) =>
+            {
+                let places: String = match &args[1] {
+                    Expr::Numeric(number) => number.to_owned(),
+                    places => format!("{{{}}}", self.expr(places)),
+                };
+                format!("{}:.{}f", self.expr(&args[0]), places)
+            }
+            _ => self.expr(part),
+        };
+        format!("f\"{}\"", format_inside(parts, hole))
+    }
+    fn constant(&self, name: &str) -> String {
+        match name {
+            "ARGS" => "sys.argv[1:]".to_string(),
+            "ARGC" => "len(sys.argv[1:])".to_string(),
+            _ => format!("math.{}", name.to_lowercase()),
+        }
+    }
+    // Importing them by name lets a function from the program with the same name
+    // still be called instead
+    fn import(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Constant(name) if ARGUMENT_CONSTANTS.contains(&name.as_str()) => {
+                Some("import sys".to_string())
+            }
+            Expr::Constant(_) => Some("import math".to_string()),
+            Expr::Call { name, .. } if name == "sqrt" || name == "floor" => {
+                Some(format!("from math import {}", name))
+            }
+            Expr::Call { name, .. } if name == "parse" => Some(PYTHON_PARSE.to_string()),
+            Expr::Call { name, .. } if name == "read_file" => Some(PYTHON_READ_FILE.to_string()),
+            Expr::Call { name, .. } if name == "write_file" => Some(PYTHON_WRITE_FILE.to_string()),
+            Expr::Call { name, .. } if name == "env" => Some(PYTHON_ENV.to_string()),
+            Expr::Call { name, .. } if name == "rand" || name == "rand_int" => {
+                Some("import random".to_string())
+            }
+            Expr::Call { name, .. } if name == "now" || name == "sleep" => {
+                Some("import time".to_string())
+            }
+            Expr::Call { name, .. } if name == "elapsed" => Some(PYTHON_ELAPSED.to_string()),
+            _ => None,
+        }
+    }
+    fn set(&self, name: &str, value: &str) -> String {
+        format!("{} = {}", name, value)
+    }
+    fn set_chain(&self, names: &[String], value: &str) -> String {
+        format!("{} = {}", names.join(" = "), value)
+    }
+    fn set_many(&self, names: &[String], values: &[String]) -> String {
+        format!("{} = {}", names.join(", "), values.join(", "))
+    }
+    fn print(&self, expr: &str) -> String {
+        match self.print_style {
+            PrintStyle::Function => format!("print({})", expr),
+            PrintStyle::Statement => format!("print {}", expr),
+        }
+    }
+    // Python's print already puts a space between what it's given
+    fn print_many(&self, exprs: &[Expr]) -> String {
+        let exprs: Vec<String> = exprs.iter().map(|x| self.expr(x)).collect();
+        self.print(&exprs.join(", "))
+    }
+    /// Python has nothing like jump, so the program stops if it gets there
+    fn jump(&self, offset: &str) -> String {
+        format!(
+            "raise NotImplementedError(\"jump {} only works with mahou --run\")",
+            offset
+        )
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String {
+        format!("{} {} {}", name, operator_text(operator), value)
+    }
+    fn if_start(&self, condition: &str) -> String {
+        format!("if {}:", condition)
+    }
+    fn if_end(&self) -> Option<String> {
+        None
+    }
+    fn else_start(&self) -> String {
+        "else:".to_string()
+    }
+    fn elif_start(&self, condition: &str) -> Option<String> {
+        Some(format!("elif {}:", condition))
+    }
+    fn try_start(&self) -> Option<String> {
+        Some("try:".to_string())
+    }
+    fn catch_start(&self, name: &str) -> Option<String> {
+        Some(format!("except Exception as {}:", name))
+    }
+    fn caught(&self, name: &str) -> Option<String> {
+        Some(format!("{} = str({})", name, name))
+    }
+    fn while_start(&self, condition: &str) -> String {
+        format!("while {}:", condition)
+    }
+    fn while_end(&self) -> Option<String> {
+        None
+    }
+    fn for_start(&self, name: &str, iterable: &Expr) -> String {
+        format!("for {} in {}:", name, self.expr(iterable))
+    }
+    fn for_end(&self) -> Option<String> {
+        None
+    }
+    fn ternary(&self, condition: &str, then: &str, otherwise: &str) -> String {
+        format!("{} if {} else {}", then, condition, otherwise)
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String {
+        format!("def {}({}):", name, params.join(", "))
+    }
+    fn func_end(&self) -> Option<String> {
+        None
+    }
+    fn return_value(&self, value: Option<&str>) -> String {
+        match value {
+            Some(value) => format!("return {}", value),
+            None => "return".to_string(),
+        }
+    }
+    // The same as sys.exit without needing the import
+    fn exit(&self, code: &str) -> String {
+        format!("raise SystemExit({})", code)
+    }
+    fn assert(&self, condition: &str, message: &str) -> String {
+        format!("assert {}, {}", condition, json_string(message))
+    }
+    /// Python needs `pass` if the block is empty
+    fn global(&self, names: &[String]) -> Option<String> {
+        Some(format!("global {}", names.join(", ")))
+    }
+    fn empty_block(&self) -> Option<String> {
+        Some("pass".to_string())
+    }
+}
+
+/// Outputs the program as the kind of pseudocode used in class, like `SET a TO 1`
+pub struct PseudocodeBackend;
+
+impl Backend for PseudocodeBackend {
+    fn expr(&self, expr: &Expr) -> String {
+        infix_expr(expr, self)
+    }
+    fn input(&self) -> String {
+        "INPUT".to_string()
+    }
+    fn boolean(&self, value: bool) -> String {
+        if value { "TRUE" } else { "FALSE" }.to_string()
+    }
+    fn constant(&self, name: &str) -> String {
+        name.to_string()
+    }
+    fn set(&self, name: &str, value: &str) -> String {
+        format!("SET {} TO {}", name, value)
+    }
+    fn set_chain(&self, names: &[String], value: &str) -> String {
+        format!("SET {} TO {}", names.join(" AND "), value)
+    }
+    fn set_many(&self, names: &[String], values: &[String]) -> String {
+        format!("SET {} TO {}", names.join(", "), values.join(", "))
+    }
+    fn print(&self, expr: &str) -> String {
+        format!("DISPLAY {}", expr)
+    }
+    fn print_many(&self, exprs: &[Expr]) -> String {
+        let exprs: Vec<String> = exprs.iter().map(|x| self.expr(x)).collect();
+        self.print(&exprs.join(", "))
+    }
+    fn jump(&self, offset: &str) -> String {
+        format!("JUMP {}", offset)
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String {
+        match operator {
+            Tokens::PlusAssign => format!("ADD {} TO {}", value, name),
+            Tokens::MinusAssign => format!("SUBTRACT {} FROM {}", value, name),
+            Tokens::MultiplyAssign => format!("MULTIPLY {} BY {}", name, value),
+            _ => format!("DIVIDE {} BY {}", name, value),
+        }
+    }
+    fn if_start(&self, condition: &str) -> String {
+        format!("IF {} THEN", condition)
+    }
+    fn if_end(&self) -> Option<String> {
+        Some("END IF".to_string())
+    }
+    fn else_start(&self) -> String {
+        "ELSE".to_string()
+    }
+    fn elif_start(&self, condition: &str) -> Option<String> {
+        Some(format!("ELSE IF {} THEN", condition))
+    }
+    fn try_start(&self) -> Option<String> {
+        Some("TRY".to_string())
+    }
+    fn catch_start(&self, name: &str) -> Option<String> {
+        Some(format!("CATCH {}", name))
+    }
+    fn try_end(&self) -> Option<String> {
+        Some("END TRY".to_string())
+    }
+    fn while_start(&self, condition: &str) -> String {
+        format!("WHILE {} DO", condition)
+    }
+    fn while_end(&self) -> Option<String> {
+        Some("END WHILE".to_string())
+    }
+    fn for_start(&self, name: &str, iterable: &Expr) -> String {
+        match iterable {
+            Expr::Range { start, end, .. } => format!(
+                "FOR {} FROM {} TO {} - 1 DO",
+                name,
+                self.expr(start),
+                self.expr(end)
+            ),
+            _ => format!("FOR EACH {} IN {} DO", name, self.expr(iterable)),
+        }
+    }
+    fn for_end(&self) -> Option<String> {
+        Some("END FOR".to_string())
+    }
+    fn ternary(&self, condition: &str, then: &str, otherwise: &str) -> String {
+        format!("IF {} THEN {} ELSE {}", condition, then, otherwise)
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String {
+        format!("PROCEDURE {}({})", name, params.join(", "))
+    }
+    fn func_end(&self) -> Option<String> {
+        Some("END PROCEDURE".to_string())
+    }
+    fn return_value(&self, value: Option<&str>) -> String {
+        match value {
+            Some(value) => format!("RETURN {}", value),
+            None => "RETURN".to_string(),
+        }
+    }
+    fn loop_exit(&self, is_break: bool, _depth: usize) -> String {
+        if is_break { "BREAK" } else { "CONTINUE" }.to_string()
+    }
+    fn exit(&self, code: &str) -> String {
+        format!("EXIT {}", code)
+    }
+    fn assert(&self, condition: &str, _message: &str) -> String {
+        format!("ASSERT {}", condition)
+    }
+    fn empty_block(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Whether an expression is a list, as far as the variables set to one say
+fn is_list(expr: &Expr, lists: &BTreeSet<String>) -> bool {
+    match expr {
+        Expr::List(_) | Expr::Range { .. } => true,
+        Expr::Identifier(name, _) => lists.contains(name),
+        Expr::Binary {
+            left,
+            operator: Tokens::Plus,
+            right,
+            ..
+        } => is_list(left, lists) || is_list(right, lists),
+        _ => false,
+    }
+}
+
+/// Every variable that gets set to a list somewhere, in the order they're set
+/// so one set to another list is a list too
+#[derive(Default)]
+struct ListNames {
+    lists: BTreeSet<String>,
+}
+
+impl Visit for ListNames {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        let set: Vec<(&String, &Expr)> = match stmt {
+            Stmt::Set { name, value } | Stmt::Const { name, value } => vec![(name, value)],
+            Stmt::AugAssign {
+                name,
+                operator: Tokens::PlusAssign,
+                value,
+            } => vec![(name, value)],
+            Stmt::SetChain { names, value } => names.iter().map(|x| (x, value)).collect(),
+            Stmt::SetMany { names, values } => names.iter().zip(values).collect(),
+            _ => Vec::new(),
+        };
+        for (name, value) in set {
+            if is_list(value, &self.lists) {
+                self.lists.insert(name.to_owned());
+            }
+        }
+        walk_stmt(self, stmt);
+    }
+}
+
+/// Outputs javascript that can be run with node or in the browser
+pub struct JsBackend {
+    functions: Vec<String>,
+    /// The names of the variables without the `_`s at the end
+    variables: BTreeSet<String>,
+    /// The variables that are lists, which `+` has to join with mahou_concat and
+    /// `+=` with mahou_extend
+    lists: BTreeSet<String>,
+}
+
+impl JsBackend {
+    pub fn new(stmts: &[Stmt]) -> JsBackend {
+        let mut lists: ListNames = ListNames::default();
+        for stmt in stmts {
+            lists.visit_stmt(stmt);
+        }
+        JsBackend {
+            functions: program_functions(stmts),
+            variables: variable_stems(stmts),
+            lists: lists.lists,
+        }
+    }
+    /// Whether printing the value needs mahou_show, which is anything but the
+    /// numbers and text that console.log already shows the same as mahou
+    fn shows(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Numeric(_) | Expr::Str(_) | Expr::Format(_) | Expr::Input => false,
+            Expr::Constant(name) => name == "ARGS",
+            Expr::Unary {
+                operator: Tokens::Minus,
+                ..
+            } => false,
+            Expr::Binary {
+                operator: Tokens::Plus,
+                ..
+            } => is_list(expr, &self.lists),
+            Expr::Binary { operator, .. } => !matches!(
+                operator,
+                Tokens::Minus | Tokens::Multiply | Tokens::Divide | Tokens::Modulo | Tokens::Power
+            ),
+            Expr::Call { name, .. } if !self.functions.contains(name) => !matches!(
+                name.as_str(),
+                "len"
+                    | "str"
+                    | "int"
+                    | "float"
+                    | "fmt"
+                    | "abs"
+                    | "sqrt"
+                    | "floor"
+                    | "pow"
+                    | "env"
+                    | "read_file"
+                    | "rand"
+                    | "rand_int"
+                    | "now"
+                    | "elapsed"
+            ),
+            Expr::Ternary {
+                then, otherwise, ..
+            } => self.shows(then) || self.shows(otherwise),
+            _ => true,
+        }
+    }
+}
+
+impl Backend for JsBackend {
+    fn expr(&self, expr: &Expr) -> String {
+        infix_expr(expr, self)
+    }
+    // Most of the builtins are on Math
+    fn call(&self, name: &str, args: &[String]) -> String {
+        let joined: String = args.join(", ");
+        match name {
+            _ if self.functions.iter().any(|x| x == name) => {
+                format!("{}({})", self.function(name), joined)
+            }
+            "abs" | "min" | "max" | "pow" | "sqrt" | "floor" => {
+                format!("Math.{}({})", name, joined)
+            }
+            "str" => format!("String({})", joined),
+            "int" => format!("Math.trunc(Number({}))", joined),
+            "float" => format!("Number({})", joined),
+            "fmt" if args.len() == 2 => format!("Number({}).toFixed({})", args[0], args[1]),
+            "input" => format!("mahou_input({})", joined),
+            "len" if args.len() == 1 => format!("({}).length", joined),
+            // Files are read and written with node's fs
+            "read_file" => format!("require(\"fs\").readFileSync({}, \"utf8\")", joined),
+            "write_file" => format!("require(\"fs\").writeFileSync({})", joined),
+            "env" => format!("(process.env[{}] ?? \"\")", joined),
+            "rand" => format!("Math.random({})", joined),
+            // performance.now is from when the page or node started
+            "now" => format!("(Date.now({}) / 1000)", joined),
+            "elapsed" => format!("(performance.now({}) / 1000)", joined),
+            _ => format!("{}({})", name, joined),
+        }
+    }
+    fn input(&self) -> String {
+        "mahou_input()".to_string()
+    }
+    fn variable(&self, name: &str) -> String {
+        mangle_variable(&JS_RESERVED, &self.functions, name)
+    }
+    fn function(&self, name: &str) -> String {
+        mangle_function(&JS_RESERVED, &self.variables, name)
+    }
+    fn boolean(&self, value: bool) -> String {
+        value.to_string()
+    }
+    fn import(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Call { name, .. } if name == "parse" && !self.functions.contains(name) => {
+                Some(JS_PARSE.to_string())
+            }
+            Expr::Call { name, .. } if name == "input" && !self.functions.contains(name) => {
+                Some(JS_INPUT.to_string())
+            }
+            Expr::Input => Some(JS_INPUT.to_string()),
+            Expr::Call { name, .. } if name == "rand_int" && !self.functions.contains(name) => {
+                Some(JS_RAND_INT.to_string())
+            }
+            Expr::Call { name, .. } if name == "sleep" && !self.functions.contains(name) => {
+                Some(JS_SLEEP.to_string())
+            }
+            Expr::Binary {
+                operator: Tokens::Modulo,
+                ..
+            } => Some(JS_MOD.to_string()),
+            Expr::Binary {
+                operator: Tokens::Plus,
+                ..
+            } if is_list(expr, &self.lists) => Some(JS_CONCAT.to_string()),
+            Expr::Format(parts) if parts.iter().any(|x| self.shows(x)) => Some(JS_SHOW.to_string()),
+            _ => None,
+        }
+    }
+    fn stmt_import(&self, stmt: &Stmt) -> Option<String> {
+        match stmt {
+            Stmt::Print { exprs } if exprs.iter().any(|x| self.shows(x)) => {
+                Some(JS_SHOW.to_string())
+            }
+            Stmt::AugAssign {
+                name,
+                operator: Tokens::PlusAssign,
+                ..
+            } if self.lists.contains(name) => Some(JS_EXTEND.to_string()),
+            _ => None,
+        }
+    }
+    // A template literal, where the text can't have a backtick or `${` in it
+    fn format_string(&self, parts: &[Expr]) -> String {
+        let inside: Vec<String> = parts
+            .iter()
+            .map(|part| match part {
+                Expr::Str(text) => {
+                    let quoted: String = json_string(text);
+                    quoted[1..quoted.len() - 1]
+                        .replace('`', "\\`")
+                        .replace("${", "\\${")
+                }
+                _ if self.shows(part) => format!("${{mahou_show({})}}", self.expr(part)),
+                _ => format!("${{{}}}", self.expr(part)),
+            })
+            .collect();
+        format!("`{}`", inside.concat())
+    }
+    fn constant(&self, name: &str) -> String {
+        match name {
+            "ARGS" => "process.argv.slice(2)".to_string(),
+            "ARGC" => "process.argv.slice(2).length".to_string(),
+            _ => format!("Math.{}", name),
+        }
+    }
+    // Loose equality would make "1" == 1 true, which isn't what python does
+    fn operator(&self, operator: Tokens) -> &'static str {
+        match operator {
+            Tokens::Equal => "===",
+            Tokens::NotEqual => "!==",
+            _ => c_operator(operator),
+        }
+    }
+    fn operator_function(
+        &self,
+        left: &Expr,
+        operator: Tokens,
+        right: &Expr,
+    ) -> Option<&'static str> {
+        match operator {
+            Tokens::Modulo => Some("mahou_mod"),
+            Tokens::Plus if is_list(left, &self.lists) || is_list(right, &self.lists) => {
+                Some("mahou_concat")
+            }
+            _ => None,
+        }
+    }
+    fn unary_power_parens(&self) -> bool {
+        true
+    }
+    // The variables are all declared with let at the top, so a set is only assigning
+    fn set(&self, name: &str, value: &str) -> String {
+        format!("{} = {};", name, value)
+    }
+    fn set_chain(&self, names: &[String], value: &str) -> String {
+        format!("{} = {};", names.join(" = "), value)
+    }
+    fn set_many(&self, names: &[String], values: &[String]) -> String {
+        format!("[{}] = [{}];", names.join(", "), values.join(", "))
+    }
+    fn print(&self, expr: &str) -> String {
+        format!("console.log({});", expr)
+    }
+    fn print_expr(&self, expr: &Expr) -> String {
+        match self.shows(expr) {
+            true => self.print(&format!("mahou_show({})", self.expr(expr))),
+            false => self.print(&self.expr(expr)),
+        }
+    }
+    fn jump(&self, offset: &str) -> String {
+        format!(
+            "throw new Error(\"jump {} only works with mahou --run\");",
+            offset
+        )
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String {
+        match operator {
+            Tokens::PlusAssign if self.lists.iter().any(|x| self.variable(x) == name) => {
+                format!("mahou_extend({}, {});", name, value)
+            }
+            _ => format!("{} {} {};", name, operator_text(operator), value),
+        }
+    }
+    fn if_start(&self, condition: &str) -> String {
+        format!("if ({}) {{", condition)
+    }
+    fn if_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    fn else_start(&self) -> String {
+        "} else {".to_string()
+    }
+    fn elif_start(&self, condition: &str) -> Option<String> {
+        Some(format!("}} else if ({}) {{", condition))
+    }
+    fn try_start(&self) -> Option<String> {
+        Some("try {".to_string())
+    }
+    fn catch_start(&self, name: &str) -> Option<String> {
+        Some(format!("}} catch ({}) {{", name))
+    }
+    fn caught(&self, name: &str) -> Option<String> {
+        Some(format!("{} = {}.message;", name, name))
+    }
+    fn while_start(&self, condition: &str) -> String {
+        format!("while ({}) {{", condition)
+    }
+    fn while_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    // Javascript only has python's kind of loop for arrays and strings
+    fn for_start(&self, name: &str, iterable: &Expr) -> String {
+        match iterable {
+            Expr::Range { start, end, .. } => format!(
+                "for ({} = {}; {} < {}; {}++) {{",
+                name,
+                self.expr(start),
+                name,
+                self.expr(end),
+                name
+            ),
+            _ => format!("for ({} of {}) {{", name, self.expr(iterable)),
+        }
+    }
+    fn for_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    fn range(&self, start: &str, end: &str) -> String {
+        format!(
+            "Array.from({{ length: {} - {} }}, (_, i) => {} + i)",
+            end, start, start
+        )
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String {
+        format!("function {}({}) {{", name, params.join(", "))
+    }
+    fn func_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    fn return_value(&self, value: Option<&str>) -> String {
+        match value {
+            Some(value) => format!("return {};", value),
+            None => "return;".to_string(),
+        }
+    }
+    fn loop_exit(&self, is_break: bool, _depth: usize) -> String {
+        if is_break { "break;" } else { "continue;" }.to_string()
+    }
+    fn exit(&self, code: &str) -> String {
+        format!("process.exit({});", code)
+    }
+    fn assert(&self, condition: &str, message: &str) -> String {
+        format!(
+            "if (!({})) throw new Error({});",
+            condition,
+            json_string(message)
+        )
+    }
+    fn empty_block(&self) -> Option<String> {
+        None
+    }
+    // Declaring them at the top means they can still be read after the block
+    // they were set in, like in python
+    fn declare(&self, names: &[String]) -> Vec<String> {
+        if names.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!("let {};", names.join(", "))]
+        }
+    }
+}
+
+/// The types a variable can have in the C output, a variable that's given more
+/// than one kind of value gets the widest one
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum CType {
+    Int,
+    Double,
+    Str,
+}
+
+impl CType {
+    fn name(&self) -> &'static str {
+        match self {
+            CType::Int => "long long",
+            CType::Double => "double",
+            CType::Str => "const char",
+        }
+    }
+    fn format(&self) -> &'static str {
+        match self {
+            CType::Int => "%lld",
+            CType::Double => "%g",
+            CType::Str => "%s",
+        }
+    }
+}
+
+/// Reads all of a file into a string that's never freed, stopping the program if it can't
+const C_FMT: &str = "char *mahou_fmt(double number, long long places) {\n    int size = snprintf(NULL, 0, \"%.*f\", (int)places, number);\n    char *text = malloc(size + 1);\n    snprintf(text, size + 1, \"%.*f\", (int)places, number);\n    return text;\n}";
+const C_READ_FILE: &str = "char *mahou_read_file(const char *path) {\n    FILE *file = fopen(path, \"rb\");\n    if (!file) {\n        fprintf(stderr, \"error: can't read %s\\n\", path);\n        exit(1);\n    }\n    fseek(file, 0, SEEK_END);\n    long size = ftell(file);\n    rewind(file);\n    char *text = malloc(size + 1);\n    text[fread(text, 1, size, file)] = 0;\n    fclose(file);\n    return text;\n}";
+const C_WRITE_FILE: &str = "void mahou_write_file(const char *path, const char *text) {\n    FILE *file = fopen(path, \"w\");\n    if (!file) {\n        fprintf(stderr, \"error: can't write %s\\n\", path);\n        exit(1);\n    }\n    fputs(text, file);\n    fclose(file);\n}";
+
+/// getenv gives back NULL for a variable that isn't set, which can't be printed
+const C_ENV: &str = "const char *mahou_env(const char *name) {\n    const char *value = getenv(name);\n    return value ? value : \"\";\n}";
+
+/// C's rand starts from the same seed every run unless it's given one with srand
+const C_RAND_INT: &str = "long long mahou_rand_int(long long low, long long high) {\n    return low + rand() % (high - low + 1);\n}";
+
+/// C's % and fmod keep the sign of the left side, in mahou like in python the
+/// remainder takes the sign of the right side. fmod needs math.h, which goes
+/// with it so it's only there once
+const C_MOD: &str = "long long mahou_mod(long long a, long long b) {\n    long long rem = a % b;\n    return rem != 0 && (rem < 0) != (b < 0) ? rem + b : rem;\n}";
+const C_FMOD: &str = "#include <math.h>\ndouble mahou_fmod(double a, double b) {\n    double rem = fmod(a, b);\n    return rem != 0 && (rem < 0) != (b < 0) ? rem + b : rem;\n}";
+
+/// Reads a number for `input`, since there's nothing to hold a line in
+const C_INPUT: &str =
+    "double mahou_input(void) {\n    double value = 0;\n    scanf(\"%lf\", &value);\n    return value;\n}";
+
+/// Outputs C that can be built with any C compiler, with the types of the
+/// variables worked out from what they get set to
+pub struct CBackend {
+    types: HashMap<String, CType>,
+    functions: Vec<String>,
+}
+
+/// Work out the type of every variable, going over the program again until
+/// nothing changes since a variable can be set from one that comes after it
+pub fn infer_c_types(stmts: &[Stmt]) -> HashMap<String, CType> {
+    let mut backend: CBackend = CBackend {
+        types: HashMap::new(),
+        functions: Vec::new(),
+    };
+    loop {
+        let before: HashMap<String, CType> = backend.types.clone();
+        backend.infer(stmts);
+        if backend.types == before {
+            return backend.types;
+        }
+    }
+}
+
+impl CBackend {
+    pub fn new(stmts: &[Stmt]) -> CBackend {
+        CBackend {
+            types: infer_c_types(stmts),
+            functions: program_functions(stmts),
+        }
+    }
+    /// Widen each variable to fit what the statements set it to
+    fn infer(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                    self.widen(name, self.expr_type(value))
+                }
+                Stmt::SetChain { names, value } => {
+                    for name in names {
+                        self.widen(name, self.expr_type(value));
+                    }
+                }
+                Stmt::SetMany { names, values } => {
+                    for (name, value) in names.iter().zip(values) {
+                        self.widen(name, self.expr_type(value));
+                    }
+                }
+                // Dividing always gives a double, like in python
+                Stmt::AugAssign {
+                    name,
+                    operator,
+                    value,
+                } => {
+                    let current: CType = self.variable_type(name);
+                    let kind: CType = match operator {
+                        Tokens::DivideAssign => CType::Double,
+                        _ => current.max(self.expr_type(value)),
+                    };
+                    self.widen(name, kind);
+                }
+                Stmt::If {
+                    body, else_body, ..
+                } => {
+                    self.infer(body);
+                    self.infer(else_body.as_deref().unwrap_or(&[]));
+                }
+                Stmt::Match {
+                    value,
+                    arms,
+                    position,
+                } => {
+                    if let Some(temp) = match_subject(value, *position) {
+                        self.widen(&temp, self.expr_type(value));
+                    }
+                    for arm in arms {
+                        self.infer(&arm.body);
+                    }
+                }
+                Stmt::Try { body, handler, .. } => {
+                    self.infer(body);
+                    self.infer(handler);
+                }
+                Stmt::While { body, .. } => self.infer(body),
+                // Counting through a range only needs an int
+                Stmt::For {
+                    name,
+                    iterable,
+                    body,
+                    ..
+                } => {
+                    let kind: CType = match iterable {
+                        Expr::Range { .. } => CType::Int,
+                        _ => CType::Double,
+                    };
+                    self.widen(name, kind);
+                    self.infer(body);
+                }
+                // The parameters don't say what they take, so they're all doubles
+                Stmt::Func { params, body, .. } => {
+                    for param in params {
+                        self.widen(param, CType::Double);
+                    }
+                    self.infer(body);
+                }
+                _ => {}
+            }
+        }
+    }
+    fn widen(&mut self, name: &str, kind: CType) {
+        let current: CType = self.variable_type(name);
+        self.types.insert(name.to_owned(), current.max(kind));
+    }
+    fn variable_type(&self, name: &str) -> CType {
+        self.types.get(name).copied().unwrap_or(CType::Int)
+    }
+    fn expr_type(&self, expr: &Expr) -> CType {
+        match expr {
+            Expr::Numeric(number) if is_float_literal(number) => CType::Double,
+            Expr::Numeric(_) | Expr::Bool(_) => CType::Int,
+            Expr::Str(_) | Expr::Format(_) => CType::Str,
+            Expr::Identifier(name, _) => self.variable_type(name),
+            Expr::Constant(_)
+            | Expr::Input
+            | Expr::List(_)
+            | Expr::Dict(_)
+            | Expr::Range { .. }
+            | Expr::Index { .. } => CType::Double,
+            Expr::Call { name, args, .. } => match name.as_str() {
+                _ if !self.is_builtin(name) => CType::Double,
+                "len" | "int" | "floor" | "rand_int" => CType::Int,
+                "str" | "read_file" | "env" | "fmt" => CType::Str,
+                "abs" | "parse" => args
+                    .first()
+                    .map(|x| self.expr_type(x))
+                    .unwrap_or(CType::Int),
+                _ => CType::Double,
+            },
+            Expr::Unary {
+                operator: Tokens::Not,
+                ..
+            } => CType::Int,
+            Expr::Unary { expr, .. } => self.expr_type(expr),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => match operator {
+                operator if is_comparison(*operator) => CType::Int,
+                Tokens::And | Tokens::Or => CType::Int,
+                Tokens::Divide | Tokens::Power => CType::Double,
+                _ => self.expr_type(left).max(self.expr_type(right)),
+            },
+            Expr::Ternary {
+                then, otherwise, ..
+            } => self.expr_type(then).max(self.expr_type(otherwise)),
+        }
+    }
+    fn is_builtin(&self, name: &str) -> bool {
+        !self.functions.iter().any(|x| x == name)
+    }
+}
+
+impl Backend for CBackend {
+    fn expr(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Constant(name) => self.constant(name),
+            Expr::Call { name, args, .. } => {
+                let args: Vec<String> = args.iter().map(|x| self.expr(x)).collect();
+                let args: String = args.join(", ");
+                match name.as_str() {
+                    _ if !self.is_builtin(name) => format!("{}({})", name, args),
+                    "len" => format!("(long long)strlen({})", args),
+                    "int" => format!("(long long)({})", args),
+                    "float" => format!("(double)({})", args),
+                    "abs" if self.expr_type(expr) == CType::Int => format!("llabs({})", args),
+                    "abs" => format!("fabs({})", args),
+                    "min" => format!("fmin({})", args),
+                    "max" => format!("fmax({})", args),
+                    "floor" => format!("(long long)floor({})", args),
+                    // Input already reads a number
+                    "parse" => format!("({})", args),
+                    "read_file" | "write_file" | "env" | "fmt" => {
+                        format!("mahou_{}({})", name, args)
+                    }
+                    "rand" => "((double)rand() / ((double)RAND_MAX + 1))".to_string(),
+                    "rand_int" => format!("mahou_rand_int({})", args),
+                    "input" if args.is_empty() => self.input(),
+                    // The prompt gets printed first, then mahou_input reads the number
+                    "input" => format!("(printf(\"%s\", {}), mahou_input())", args),
+                    _ => format!("{}({})", name, args),
+                }
+            }
+            Expr::Unary { operator, expr } => match **expr {
+                Expr::Unary { .. } | Expr::Binary { .. } | Expr::Ternary { .. } => {
+                    format!("{}({})", c_operator(*operator), self.expr(expr))
+                }
+                _ => format!("{}{}", c_operator(*operator), self.expr(expr)),
+            },
+            // C has no power operator, and % only works on ints and truncates
+            Expr::Binary {
+                left,
+                operator: Tokens::Power,
+                right,
+                ..
+            } => format!("pow({}, {})", self.expr(left), self.expr(right)),
+            Expr::Binary {
+                left,
+                operator: Tokens::Modulo,
+                right,
+                ..
+            } => {
+                let helper: &str = match self.expr_type(expr) {
+                    CType::Double => "mahou_fmod",
+                    _ => "mahou_mod",
+                };
+                format!("{}({}, {})", helper, self.expr(left), self.expr(right))
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                let prec: u8 = precedence(*operator).unwrap_or(0);
+                let wrap = |child: &Expr, is_right: bool| -> String {
+                    let text: String = self.expr(child);
+                    match child {
+                        Expr::Binary {
+                            operator: Tokens::Power,
+                            ..
+                        } => text,
+                        Expr::Binary { operator, .. } => {
+                            let child_prec: u8 = precedence(*operator).unwrap_or(0);
+                            if child_prec < prec || (child_prec == prec && is_right) {
+                                format!("({})", text)
+                            } else {
+                                text
+                            }
+                        }
+                        Expr::Ternary { .. } => format!("({})", text),
+                        _ => text,
+                    }
+                };
+                // Dividing two ints gives a double, like in python
+                let left_text: String = match operator {
+                    Tokens::Divide
+                        if self.expr_type(left) == CType::Int
+                            && self.expr_type(right) == CType::Int =>
+                    {
+                        format!("(double){}", wrap(left, false))
+                    }
+                    _ => wrap(left, false),
+                };
+                format!(
+                    "{} {} {}",
+                    left_text,
+                    c_operator(*operator),
+                    wrap(right, true)
+                )
+            }
+            Expr::Ternary {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let wrap = |child: &Expr| -> String {
+                    match child {
+                        Expr::Ternary { .. } => format!("({})", self.expr(child)),
+                        _ => self.expr(child),
+                    }
+                };
+                self.ternary(&wrap(condition), &wrap(then), &self.expr(otherwise))
+            }
+            _ => infix_expr(expr, self),
+        }
+    }
+    fn input(&self) -> String {
+        "mahou_input()".to_string()
+    }
+    // C has no bools, comparisons give an int too
+    fn boolean(&self, value: bool) -> String {
+        (value as i64).to_string()
+    }
+    fn constant(&self, name: &str) -> String {
+        format!("{:?}", constant_value(name).unwrap_or(0.0))
+    }
+    fn import(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Input => Some(C_INPUT.to_string()),
+            Expr::Call { name, .. } if self.is_builtin(name) => match name.as_str() {
+                "len" => Some("#include <string.h>".to_string()),
+                "abs" | "min" | "max" | "pow" | "sqrt" | "floor" => {
+                    Some("#include <math.h>".to_string())
+                }
+                "input" => Some(C_INPUT.to_string()),
+                "read_file" => Some(C_READ_FILE.to_string()),
+                "write_file" => Some(C_WRITE_FILE.to_string()),
+                "env" => Some(C_ENV.to_string()),
+                "fmt" => Some(C_FMT.to_string()),
+                "rand_int" => Some(C_RAND_INT.to_string()),
+                _ => None,
+            },
+            Expr::Binary {
+                operator: Tokens::Power,
+                ..
+            } => Some("#include <math.h>".to_string()),
+            Expr::Binary {
+                operator: Tokens::Modulo,
+                ..
+            } => Some(match self.expr_type(expr) {
+                CType::Double => C_FMOD.to_string(),
+                _ => C_MOD.to_string(),
+            }),
+            _ => None,
+        }
+    }
+    fn set(&self, name: &str, value: &str) -> String {
+        format!("{} = {};", name, value)
+    }
+    fn set_chain(&self, names: &[String], value: &str) -> String {
+        format!("{} = {};", names.join(" = "), value)
+    }
+    // The values all get worked out before any of them are set, like in python
+    fn set_many(&self, names: &[String], values: &[String]) -> String {
+        let temps: Vec<String> = names
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(index, (name, value))| {
+                let kind: CType = self.variable_type(name);
+                let star: &str = if kind == CType::Str { "*" } else { "" };
+                format!("{} {}set_{} = {};", kind.name(), star, index, value)
+            })
+            .collect();
+        let sets: Vec<String> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| format!("{} = set_{};", name, index))
+            .collect();
+        format!("{{ {} {} }}", temps.join(" "), sets.join(" "))
+    }
+    fn print(&self, expr: &str) -> String {
+        format!("printf(\"%g\\n\", (double)({}));", expr)
+    }
+    // Number literals are only ints in C, so they get made as wide as the format
+    fn print_expr(&self, expr: &Expr) -> String {
+        let parts: &[Expr] = match expr {
+            Expr::Format(parts) => parts,
+            _ => std::slice::from_ref(expr),
+        };
+        let mut format: String = String::new();
+        let mut values: Vec<String> = Vec::new();
+        for part in parts {
+            match (expr, part) {
+                // The text of a string with values in it goes right into the format
+                (Expr::Format(_), Expr::Str(text)) => format.push_str(&text.replace('%', "%%")),
+                _ => {
+                    let kind: CType = self.expr_type(part);
+                    format.push_str(kind.format());
+                    values.push(match kind {
+                        CType::Int => format!("(long long)({})", self.expr(part)),
+                        _ => self.expr(part),
+                    });
+                }
+            }
+        }
+        format.push('\n');
+        let args: Vec<String> = std::iter::once(json_string(&format))
+            .chain(values)
+            .collect();
+        format!("printf({});", args.join(", "))
+    }
+    // Only jumps with a constant offset that stays in the block can become a goto
+    fn jump(&self, offset: &str) -> String {
+        format!(
+            "fprintf(stderr, \"jump {} only works with mahou --run\\n\"); exit(1);",
+            offset
+        )
+    }
+    fn goto(&self, label: &str) -> Option<String> {
+        Some(format!("goto {};", label))
+    }
+    // The empty statement lets a label go at the end of a block
+    fn label(&self, label: &str) -> String {
+        format!("{}: ;", label)
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String {
+        format!("{} {} {};", name, operator_text(operator), value)
+    }
+    fn if_start(&self, condition: &str) -> String {
+        format!("if ({}) {{", condition)
+    }
+    fn if_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    fn else_start(&self) -> String {
+        "} else {".to_string()
+    }
+    fn elif_start(&self, condition: &str) -> Option<String> {
+        Some(format!("}} else if ({}) {{", condition))
+    }
+    fn while_start(&self, condition: &str) -> String {
+        format!("while ({}) {{", condition)
+    }
+    fn while_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    // Only a range can be looped over, since there are no lists
+    fn for_start(&self, name: &str, iterable: &Expr) -> String {
+        match iterable {
+            Expr::Range { start, end, .. } => format!(
+                "for ({} = {}; {} < {}; {}++) {{",
+                name,
+                self.expr(start),
+                name,
+                self.expr(end),
+                name
+            ),
+            _ => format!(
+                "while (0) {{ /* for {} in {} only works with mahou --run */",
+                name,
+                self.expr(iterable)
+            ),
+        }
+    }
+    fn for_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String {
+        let params: Vec<String> = params.iter().map(|x| format!("double {}", x)).collect();
+        let params: String = if params.is_empty() {
+            "void".to_string()
+        } else {
+            params.join(", ")
+        };
+        format!("double {}({}) {{", name, params)
+    }
+    fn func_end(&self) -> Option<String> {
+        Some("}".to_string())
+    }
+    // Every function gives back a double, so a bare return gives back 0 instead of None
+    fn return_value(&self, value: Option<&str>) -> String {
+        format!("return {};", value.unwrap_or("0"))
+    }
+    fn loop_exit(&self, is_break: bool, _depth: usize) -> String {
+        if is_break { "break;" } else { "continue;" }.to_string()
+    }
+    fn exit(&self, code: &str) -> String {
+        format!("exit({});", code)
+    }
+    fn assert(&self, condition: &str, message: &str) -> String {
+        format!(
+            "if (!({})) {{ fprintf(stderr, \"%s\\n\", {}); exit(1); }}",
+            condition,
+            json_string(message)
+        )
+    }
+    fn empty_block(&self) -> Option<String> {
+        None
+    }
+    fn declare(&self, names: &[String]) -> Vec<String> {
+        [CType::Int, CType::Double, CType::Str]
+            .iter()
+            .filter_map(|kind| {
+                let star: &str = if *kind == CType::Str { "*" } else { "" };
+                let names: Vec<String> = names
+                    .iter()
+                    .filter(|x| self.variable_type(x) == *kind)
+                    .map(|x| format!("{}{}", star, x))
+                    .collect();
+                if names.is_empty() {
+                    None
+                } else {
+                    Some(format!("{} {};", kind.name(), names.join(", ")))
+                }
+            })
+            .collect()
+    }
+    fn expr_stmt(&self, expr: &str) -> String {
+        format!("{};", expr)
+    }
+    fn header(&self) -> Vec<String> {
+        vec![
+            "#include <stdio.h>".to_string(),
+            "#include <stdlib.h>".to_string(),
+        ]
+    }
+    fn main_start(&self) -> Option<String> {
+        Some("int main(void) {".to_string())
+    }
+}
+
+/// Finds the strings a program prints and whether it uses `**`, `%` or the other
+/// builtins that need a function, which the wasm module needs to know before
+/// it's generated
+struct WasmScan<'a> {
+    functions: &'a [String],
+    strings: Vec<String>,
+    power: bool,
+    modulo: bool,
+    math: bool,
+    exit: bool,
+}
+
+impl Visit for WasmScan<'_> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        if let Stmt::Exit { .. } = stmt {
+            self.exit = true;
+        }
+        walk_stmt(self, stmt);
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Str(text) if !self.strings.contains(text) => self.strings.push(text.to_owned()),
+            Expr::Binary {
+                operator: Tokens::Power,
+                ..
+            } => self.power = true,
+            Expr::Binary {
+                operator: Tokens::Modulo,
+                ..
+            } => self.modulo = true,
+            Expr::Call { name, .. } if !self.functions.contains(name) => match name.as_str() {
+                "pow" => self.power = true,
+                "abs" | "min" | "max" => self.math = true,
+                _ => {}
+            },
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Outputs a WebAssembly text module. Every value is an i64, printing calls the
+/// `print` function the host gives in `env`, and the program runs when the host
+/// calls the exported `main`. Floats, constants, lists and strings other than printing
+/// one become `unreachable` since there's no i64 for them
+pub struct WasmBackend {
+    functions: Vec<String>,
+    /// The printed strings, in the order they're put in memory
+    strings: Vec<String>,
+    power: bool,
+    modulo: bool,
+    math: bool,
+    /// Whether the program has an `exit`, which needs the host's exit imported
+    exit: bool,
+    /// The variables of the function being generated, which are locals. Outside
+    /// of a function this is None and every variable is a global
+    locals: RefCell<Option<Vec<String>>>,
+}
+
+impl WasmBackend {
+    pub fn new(stmts: &[Stmt]) -> WasmBackend {
+        let functions: Vec<String> = program_functions(stmts);
+        let mut scan: WasmScan = WasmScan {
+            functions: &functions,
+            strings: Vec::new(),
+            power: false,
+            modulo: false,
+            math: false,
+            exit: false,
+        };
+        for stmt in stmts {
+            scan.visit_stmt(stmt);
+        }
+        let (strings, power, modulo, math, exit) =
+            (scan.strings, scan.power, scan.modulo, scan.math, scan.exit);
+        WasmBackend {
+            functions,
+            strings,
+            power,
+            modulo,
+            math,
+            exit,
+            locals: RefCell::new(None),
+        }
+    }
+    /// Whether the variable is a `local` or a `global` where it's used
+    fn scope(&self, name: &str) -> &'static str {
+        match &*self.locals.borrow() {
+            Some(locals) if locals.iter().any(|x| x == name) => "local",
+            _ => "global",
+        }
+    }
+    fn get(&self, name: &str) -> String {
+        format!("({}.get ${})", self.scope(name), name)
+    }
+    /// Where the string starts in memory and how many bytes it is
+    fn string_at(&self, text: &str) -> Option<(usize, usize)> {
+        let mut offset: usize = 0;
+        for string in &self.strings {
+            if string == text {
+                return Some((offset, string.len()));
+            }
+            offset += string.len();
+        }
+        None
+    }
+}
+
+/// Write the bytes for a data segment, anything that isn't plain ascii is a `\hh` escape
+fn wat_string(text: &str) -> String {
+    let mut escaped: String = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b' '..=b'~' if byte != b'"' && byte != b'\\' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\{:02x}", byte)),
+        }
+    }
+    format!("\"{}\"", escaped)
+}
+
+/// Raises an i64 to a power by multiplying, a negative power gives 1
+const WASM_POWER: &str = "(func $mahou_pow (param $base i64) (param $exp i64) (result i64)
+    (local $result i64)
+    (local.set $result (i64.const 1))
+    (block (loop
+        (br_if 1 (i64.le_s (local.get $exp) (i64.const 0)))
+        (local.set $result (i64.mul (local.get $result) (local.get $base)))
+        (local.set $exp (i64.sub (local.get $exp) (i64.const 1)))
+        (br 0)))
+    (local.get $result))";
+
+/// The remainder with the sign of the right side like python's, where wasm's
+/// `rem_s` has the left side's. It's moved over by the right side when the two
+/// signs are different instead of adding first, which could overflow
+const WASM_MOD: &str = "(func $mahou_mod (param $a i64) (param $b i64) (result i64)
+    (local $rem i64)
+    (local.set $rem (i64.rem_s (local.get $a) (local.get $b)))
+    (select (i64.add (local.get $rem) (local.get $b)) (local.get $rem)
+        (i32.and (i64.ne (local.get $rem) (i64.const 0))
+            (i64.lt_s (i64.xor (local.get $rem) (local.get $b)) (i64.const 0)))))";
+
+/// abs, min and max on i64s, which wasm only has for floats
+const WASM_MATH: &str = "(func $mahou_abs (param $x i64) (result i64)
+    (select (i64.sub (i64.const 0) (local.get $x)) (local.get $x)
+        (i64.lt_s (local.get $x) (i64.const 0))))
+(func $mahou_min (param $a i64) (param $b i64) (result i64)
+    (select (local.get $a) (local.get $b) (i64.lt_s (local.get $a) (local.get $b))))
+(func $mahou_max (param $a i64) (param $b i64) (result i64)
+    (select (local.get $a) (local.get $b) (i64.gt_s (local.get $a) (local.get $b))))";
+
+impl Backend for WasmBackend {
+    fn expr(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Numeric(number) => match number.parse::<i64>() {
+                Ok(number) => format!("(i64.const {})", number),
+                Err(_) => "(unreachable)".to_string(),
+            },
+            Expr::Bool(value) => format!("(i64.const {})", *value as i64),
+            Expr::Identifier(name, _) => self.get(name),
+            Expr::Input => self.input(),
+            Expr::Call { name, args, .. } => {
+                let args: Vec<String> = args.iter().map(|x| self.expr(x)).collect();
+                match name.as_str() {
+                    _ if self.functions.contains(name) => {
+                        let call: Vec<String> = std::iter::once(format!("call ${}", name))
+                            .chain(args)
+                            .collect();
+                        format!("({})", call.join(" "))
+                    }
+                    // Everything is already an int
+                    "int" | "floor" | "parse" if args.len() == 1 => args[0].to_owned(),
+                    "abs" if args.len() == 1 => format!("(call $mahou_abs {})", args[0]),
+                    "pow" | "min" | "max" if args.len() == 2 => {
+                        format!("(call $mahou_{} {} {})", name, args[0], args[1])
+                    }
+                    "input" if args.is_empty() => self.input(),
+                    _ => "(unreachable)".to_string(),
+                }
+            }
+            Expr::Unary {
+                operator: Tokens::Not,
+                expr,
+            } => format!("(i64.extend_i32_u (i64.eqz {}))", self.expr(expr)),
+            Expr::Unary { expr, .. } => format!("(i64.sub (i64.const 0) {})", self.expr(expr)),
+            // Only the side that's needed is run, and like in C what comes out is 0 or 1
+            Expr::Binary {
+                left,
+                operator: Tokens::And,
+                right,
+                ..
+            } => format!(
+                "(if (result i64) (i64.eqz {}) (then (i64.const 0)) (else (i64.extend_i32_u (i64.ne {} (i64.const 0)))))",
+                self.expr(left),
+                self.expr(right)
+            ),
+            Expr::Binary {
+                left,
+                operator: Tokens::Or,
+                right,
+                ..
+            } => format!(
+                "(if (result i64) (i64.eqz {}) (then (i64.extend_i32_u (i64.ne {} (i64.const 0)))) (else (i64.const 1)))",
+                self.expr(left),
+                self.expr(right)
+            ),
+            Expr::Binary {
+                left,
+                operator: Tokens::Power,
+                right,
+                ..
+            } => format!("(call $mahou_pow {} {})", self.expr(left), self.expr(right)),
+            Expr::Binary {
+                left,
+                operator: Tokens::Modulo,
+                right,
+                ..
+            } => format!("(call $mahou_mod {} {})", self.expr(left), self.expr(right)),
+            // Comparisons give an i32, which gets widened so every value is an i64
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => {
+                let text: String = format!(
+                    "({} {} {})",
+                    self.operator(*operator),
+                    self.expr(left),
+                    self.expr(right)
+                );
+                if is_comparison(*operator) {
+                    format!("(i64.extend_i32_u {})", text)
+                } else {
+                    text
+                }
+            }
+            Expr::Ternary {
+                condition,
+                then,
+                otherwise,
+            } => format!(
+                "(if (result i64) (i64.ne {} (i64.const 0)) (then {}) (else {}))",
+                self.expr(condition),
+                self.expr(then),
+                self.expr(otherwise)
+            ),
+            Expr::Str(_)
+            | Expr::Format(_)
+            | Expr::Constant(_)
+            | Expr::List(_)
+            | Expr::Dict(_)
+            | Expr::Range { .. }
+            | Expr::Index { .. } => "(unreachable)".to_string(),
+        }
+    }
+    fn input(&self) -> String {
+        "(call $input)".to_string()
+    }
+    fn constant(&self, _name: &str) -> String {
+        "(unreachable)".to_string()
+    }
+    fn import(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Input => {
+                Some("(import \"env\" \"input\" (func $input (result i64)))".to_string())
+            }
+            Expr::Call { name, args, .. } if name == "input" && args.is_empty() => {
+                self.import(&Expr::Input)
+            }
+            Expr::Str(_) => {
+                Some("(import \"env\" \"print_str\" (func $print_str (param i32 i32)))".to_string())
+            }
+            _ => None,
+        }
+    }
+    // Division is on ints, so `7 / 2` is 3
+    fn operator(&self, operator: Tokens) -> &'static str {
+        match operator {
+            Tokens::Plus | Tokens::PlusAssign => "i64.add",
+            Tokens::Minus | Tokens::MinusAssign => "i64.sub",
+            Tokens::Multiply | Tokens::MultiplyAssign => "i64.mul",
+            Tokens::Divide | Tokens::DivideAssign => "i64.div_s",
+            Tokens::LessThan => "i64.lt_s",
+            Tokens::GreaterThan => "i64.gt_s",
+            Tokens::LessEqual => "i64.le_s",
+            Tokens::GreaterEqual => "i64.ge_s",
+            Tokens::Equal => "i64.eq",
+            Tokens::NotEqual => "i64.ne",
+            _ => "unreachable",
+        }
+    }
+    fn set(&self, name: &str, value: &str) -> String {
+        format!("({}.set ${} {})", self.scope(name), name, value)
+    }
+    // The last one gets the value and the rest are set from it
+    fn set_chain(&self, names: &[String], value: &str) -> String {
+        let (last, rest) = names.split_last().expect("a chain always has names");
+        let sets: Vec<String> = std::iter::once(self.set(last, value))
+            .chain(rest.iter().map(|x| self.set(x, &self.get(last))))
+            .collect();
+        sets.join(" ")
+    }
+    // The values all go on the stack first, then get set from the last one back
+    fn set_many(&self, names: &[String], values: &[String]) -> String {
+        let sets: Vec<String> = names
+            .iter()
+            .rev()
+            .map(|x| format!("({}.set ${})", self.scope(x), x))
+            .collect();
+        format!("{} {}", values.join(" "), sets.join(" "))
+    }
+    fn set_index(&self, target: &str, _value: &str) -> String {
+        format!(
+            "(unreachable) ;; set {} only works with mahou --run",
+            target
+        )
+    }
+    fn print(&self, expr: &str) -> String {
+        format!("(call $print {})", expr)
+    }
+    fn print_expr(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Str(text) => match self.string_at(text) {
+                Some((offset, len)) => format!(
+                    "(call $print_str (i32.const {}) (i32.const {}))",
+                    offset, len
+                ),
+                None => self.print(&self.expr(expr)),
+            },
+            _ => self.print(&self.expr(expr)),
+        }
+    }
+    fn jump(&self, offset: &str) -> String {
+        format!(
+            "(unreachable) ;; jump {} only works with mahou --run",
+            offset
+        )
+    }
+    fn aug_assign(&self, name: &str, operator: Tokens, value: &str) -> String {
+        self.set(
+            name,
+            &format!("({} {} {})", self.operator(operator), self.get(name), value),
+        )
+    }
+    fn if_start(&self, condition: &str) -> String {
+        format!("(if (i64.ne {} (i64.const 0)) (then", condition)
+    }
+    fn if_end(&self) -> Option<String> {
+        Some("))".to_string())
+    }
+    fn else_start(&self) -> String {
+        ") (else".to_string()
+    }
+    // Branching to the block leaves the loop, branching to the loop goes around again
+    fn while_start(&self, condition: &str) -> String {
+        format!("(block (loop (br_if 1 (i64.eqz {}))", condition)
+    }
+    fn while_end(&self) -> Option<String> {
+        Some("(br 0)))".to_string())
+    }
+    // The name starts one before the range, so it can go up at the start of each
+    // time through, and only a range works since there are no lists
+    fn for_start(&self, name: &str, iterable: &Expr) -> String {
+        match iterable {
+            Expr::Range { start, end, .. } => format!(
+                "{} (block (loop {} (br_if 1 (i64.ge_s {} {}))",
+                self.set(
+                    name,
+                    &format!("(i64.sub {} (i64.const 1))", self.expr(start))
+                ),
+                self.set(name, &format!("(i64.add {} (i64.const 1))", self.get(name))),
+                self.get(name),
+                self.expr(end)
+            ),
+            _ => format!(
+                "(unreachable) (block (loop ;; for {} only works with mahou --run on a range",
+                name
+            ),
+        }
+    }
+    fn for_end(&self) -> Option<String> {
+        self.while_end()
+    }
+    fn func_start(&self, name: &str, params: &[String]) -> String {
+        *self.locals.borrow_mut() = Some(params.to_vec());
+        let params: Vec<String> = params
+            .iter()
+            .map(|x| format!("(param ${} i64) ", x))
+            .collect();
+        format!("(func ${} {}(result i64)", name, params.concat())
+    }
+    // Falling off the end gives back 0
+    fn func_end(&self) -> Option<String> {
+        *self.locals.borrow_mut() = None;
+        Some("(i64.const 0))".to_string())
+    }
+    fn return_value(&self, value: Option<&str>) -> String {
+        format!("(return {})", value.unwrap_or("(i64.const 0)"))
+    }
+    // The loop is inside of the block that leaves it, so a break goes out of one more
+    fn loop_exit(&self, is_break: bool, depth: usize) -> String {
+        format!("(br {})", depth + is_break as usize)
+    }
+    /// The host's exit gets the code, it isn't expected to come back
+    fn exit(&self, code: &str) -> String {
+        format!("(call $exit {})", code)
+    }
+    /// There's no way to show the message without the host, so it just traps
+    fn assert(&self, condition: &str, _message: &str) -> String {
+        format!("(if (i64.eqz {}) (then (unreachable)))", condition)
+    }
+    fn empty_block(&self) -> Option<String> {
+        None
+    }
+    fn expr_stmt(&self, expr: &str) -> String {
+        format!("(drop {})", expr)
+    }
+    fn declare(&self, names: &[String]) -> Vec<String> {
+        match &mut *self.locals.borrow_mut() {
+            Some(locals) => {
+                locals.extend(names.iter().cloned());
+                names
+                    .iter()
+                    .map(|x| format!("(local ${} i64)", x))
+                    .collect()
+            }
+            None => names
+                .iter()
+                .map(|x| format!("(global ${} (mut i64) (i64.const 0))", x))
+                .collect(),
+        }
+    }
+    fn header(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![
+            "(module".to_string(),
+            "(import \"env\" \"print\" (func $print (param i64)))".to_string(),
+        ];
+        if self.exit {
+            lines.push("(import \"env\" \"exit\" (func $exit (param i64)))".to_string());
+        }
+        lines
+    }
+    fn footer(&self) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        if !self.strings.is_empty() {
+            lines.push("(memory (export \"memory\") 1)".to_string());
+            lines.push(format!(
+                "(data (i32.const 0) {})",
+                wat_string(&self.strings.concat())
+            ));
+        }
+        if self.power {
+            lines.push(WASM_POWER.to_string());
+        }
+        if self.modulo {
+            lines.push(WASM_MOD.to_string());
+        }
+        if self.math {
+            lines.push(WASM_MATH.to_string());
+        }
+        lines.push(")".to_string());
+        lines
+    }
+    fn main_start(&self) -> Option<String> {
+        Some("(func $main (export \"main\") (result i64)".to_string())
+    }
+}
+
+/// The variable a match's value goes in before the arms compare with it, like
+/// `match_3_1` for one on line 3. A variable or a literal is used as it is
+pub fn match_subject(value: &Expr, position: Position) -> Option<String> {
+    match value {
+        Expr::Identifier(..) => None,
+        value if is_literal(value) => None,
+        _ => Some(format!("match_{}_{}", position.line_num, position.char_num)),
+    }
+}
+
+/// Every name in a `global`, going into functions and blocks too
+pub fn global_names(stmts: &[Stmt]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for stmt in stmts {
+        let found: Vec<String> = match stmt {
+            Stmt::Global { names } => names.to_vec(),
+            Stmt::If {
+                body, else_body, ..
+            } => {
+                let mut found: Vec<String> = global_names(body);
+                found.extend(global_names(else_body.as_deref().unwrap_or(&[])));
+                found
+            }
+            Stmt::Match { arms, .. } => arms.iter().flat_map(|x| global_names(&x.body)).collect(),
+            Stmt::Try { body, handler, .. } => {
+                let mut found: Vec<String> = global_names(body);
+                found.extend(global_names(handler));
+                found
+            }
+            Stmt::While { body, .. } | Stmt::For { body, .. } | Stmt::Func { body, .. } => {
+                global_names(body)
+            }
+            _ => Vec::new(),
+        };
+        for name in found {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Every variable the statements set, in the order they're first set, without
+/// going into functions since those get their own
+pub fn assigned_names(stmts: &[Stmt]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for stmt in stmts {
+        let found: Vec<String> = match stmt {
+            Stmt::Set { name, .. } | Stmt::Const { name, .. } => vec![name.to_owned()],
+            Stmt::SetChain { names, .. } | Stmt::SetMany { names, .. } => names.to_vec(),
+            Stmt::If {
+                body, else_body, ..
+            } => {
+                let mut found: Vec<String> = assigned_names(body);
+                found.extend(assigned_names(else_body.as_deref().unwrap_or(&[])));
+                found
+            }
+            Stmt::Match {
+                value,
+                arms,
+                position,
+            } => {
+                let mut found: Vec<String> = match_subject(value, *position).into_iter().collect();
+                for arm in arms {
+                    found.extend(assigned_names(&arm.body));
+                }
+                found
+            }
+            Stmt::Try {
+                body,
+                name,
+                handler,
+            } => {
+                let mut found: Vec<String> = assigned_names(body);
+                found.push(name.to_owned());
+                found.extend(assigned_names(handler));
+                found
+            }
+            Stmt::While { body, .. } => assigned_names(body),
+            Stmt::For { name, body, .. } => {
+                let mut found: Vec<String> = vec![name.to_owned()];
+                found.extend(assigned_names(body));
+                found
+            }
+            _ => Vec::new(),
+        };
+        for name in found {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Keeps track of how deeply nested the code being generated is
+pub struct Codegen<'a> {
+    backend: &'a dyn Backend,
+    indent: usize,
+    output: String,
+    /// How many labels have been made, so each one gets its own name
+    labels: usize,
+    /// How many lines have been pushed
+    lines: usize,
+    /// Where the statements are in the source, for making the map
+    table: Option<&'a LineTable>,
+    /// The output line each statement starts on and its line in the source
+    map: Vec<(usize, i64)>,
+    /// How many ifs are around the code being generated inside of each loop it's in
+    loops: Vec<usize>,
+}
+
+impl Codegen<'_> {
+    /// Push a single line at the current indentation level
+    fn line(&mut self, text: &str) {
+        self.output.push_str(&spacer(self.indent * 4, ' '));
+        self.output.push_str(text);
+        self.output.push('\n');
+        self.lines += text.matches('\n').count() + 1;
+    }
+    /// Push an indented block, then the line that closes it if the backend has one
+    fn block(&mut self, stmts: &[Stmt], end: Option<String>) {
+        self.indent += 1;
+        if stmts.is_empty() {
+            if let Some(empty) = self.backend.empty_block() {
+                self.line(&empty);
+            }
+        }
+        self.stmts(stmts);
+        self.indent -= 1;
+        if let Some(end) = end {
+            self.line(&end);
+        }
+    }
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        self.block_stmts(stmts, false);
+    }
+    /// Push the statements of a block, with a label in front of each one a jump
+    /// goes to if the backend has goto. The functions can be left out when they
+    /// were already put somewhere else
+    fn block_stmts(&mut self, stmts: &[Stmt], skip_funcs: bool) {
+        let labels: HashMap<usize, String> = self.jump_labels(stmts);
+        for (index, stmt) in stmts.iter().enumerate() {
+            if let Some(label) = labels.get(&index) {
+                self.line(&self.backend.label(label));
+            }
+            match stmt {
+                Stmt::Func { .. } if skip_funcs => {}
+                Stmt::Jump { offset } => {
+                    let goto: Option<String> = jump_target(index, offset, stmts.len())
+                        .and_then(|target| labels.get(&target))
+                        .and_then(|label| self.backend.goto(label));
+                    match goto {
+                        Some(goto) => self.line(&goto),
+                        None => self.stmt(stmt),
+                    }
+                }
+                stmt => self.stmt(stmt),
+            }
+        }
+        // A jump to just past the last statement ends the block
+        if let Some(label) = labels.get(&stmts.len()) {
+            self.line(&self.backend.label(label));
+        }
+    }
+    /// Name each place in the block that a jump with a constant offset goes to
+    fn jump_labels(&mut self, stmts: &[Stmt]) -> HashMap<usize, String> {
+        let mut labels: HashMap<usize, String> = HashMap::new();
+        if self.backend.goto("").is_none() {
+            return labels;
+        }
+        for (index, stmt) in stmts.iter().enumerate() {
+            if let Stmt::Jump { offset } = stmt {
+                if let Some(target) = jump_target(index, offset, stmts.len()) {
+                    if let Entry::Vacant(entry) = labels.entry(target) {
+                        self.labels += 1;
+                        entry.insert(format!("jump_{}", self.labels));
+                    }
+                }
+            }
+        }
+        labels
+    }
+    /// The first arm of a match is an if, each one after it is an elif or an if
+    /// inside of the else, and the `_` is the last else
+    fn match_chain(&mut self, subject: &Expr, arms: &[MatchArm], first: bool) {
+        let backend: &dyn Backend = self.backend;
+        let (arm, rest): (&MatchArm, &[MatchArm]) = match arms.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        // A `_` can only be first when it's the only arm
+        let condition: String = backend.expr(&match &arm.pattern {
+            Some(pattern) => Expr::Binary {
+                left: Box::new(subject.clone()),
+                operator: Tokens::Equal,
+                right: Box::new(pattern.clone()),
+                position: arm.position,
+            },
+            None => Expr::Bool(true),
+        });
+        let elif: Option<String> = if first {
+            None
+        } else {
+            backend.elif_start(&condition)
+        };
+        let opens: bool = elif.is_none();
+        if opens {
+            if !first {
+                self.line(&backend.else_start());
+                self.indent += 1;
+            }
+            if let Some(ifs) = self.loops.last_mut() {
+                *ifs += 1;
+            }
+        }
+        self.line(&elif.unwrap_or_else(|| backend.if_start(&condition)));
+        self.block(&arm.body, None);
+        match rest {
+            [] => {}
+            [wildcard] if wildcard.pattern.is_none() => {
+                self.line(&backend.else_start());
+                self.block(&wildcard.body, None);
+            }
+            rest => self.match_chain(subject, rest, false),
+        }
+        if opens {
+            if let Some(end) = backend.if_end() {
+                self.line(&end);
+            }
+            if !first {
+                self.indent -= 1;
+            }
+            if let Some(ifs) = self.loops.last_mut() {
+                *ifs -= 1;
+            }
+        }
+    }
+    fn stmt(&mut self, stmt: &Stmt) {
+        if let Some(line) = self.table.and_then(|x| x.line(stmt)) {
+            self.map.push((self.lines + 1, line));
+        }
+        let backend: &dyn Backend = self.backend;
+        let variables = |names: &[String]| -> Vec<String> {
+            names.iter().map(|x| backend.variable(x)).collect()
+        };
+        let line: String = match stmt {
+            Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                backend.set(&backend.variable(name), &backend.expr(value))
+            }
+            Stmt::SetChain { names, value } => {
+                backend.set_chain(&variables(names), &backend.expr(value))
+            }
+            Stmt::SetMany { names, values } => {
+                let values: Vec<String> = values.iter().map(|x| backend.expr(x)).collect();
+                backend.set_many(&variables(names), &values)
+            }
+            Stmt::SetIndex {
+                name,
+                indexes,
+                value,
+                ..
+            } => {
+                let indexes: Vec<String> = indexes
+                    .iter()
+                    .map(|x| format!("[{}]", backend.expr(x)))
+                    .collect();
+                let target: String = format!("{}{}", backend.variable(name), indexes.concat());
+                backend.set_index(&target, &backend.expr(value))
+            }
+            Stmt::Print { exprs } => backend.print_many(exprs),
+            Stmt::Jump { offset } => backend.jump(&backend.expr(offset)),
+            Stmt::AugAssign {
+                name,
+                operator,
+                value,
+            } => backend.aug_assign(&backend.variable(name), *operator, &backend.expr(value)),
+            Stmt::If {
+                condition,
+                body,
+                else_body,
+            } => {
+                self.line(&backend.if_start(&backend.expr(condition)));
+                if let Some(ifs) = self.loops.last_mut() {
+                    *ifs += 1;
+                }
+                // The else line closes the first block, so only the last one gets the end
+                match else_body {
+                    Some(else_body) => {
+                        self.block(body, None);
+                        self.line(&backend.else_start());
+                        self.block(else_body, backend.if_end());
+                    }
+                    None => self.block(body, backend.if_end()),
+                }
+                if let Some(ifs) = self.loops.last_mut() {
+                    *ifs -= 1;
+                }
+                return;
+            }
+            // The value goes in a variable first unless it's fine to use for each arm
+            Stmt::Match {
+                value,
+                arms,
+                position,
+            } => {
+                let subject: Expr = match match_subject(value, *position) {
+                    Some(temp) => {
+                        self.line(&backend.set(&backend.variable(&temp), &backend.expr(value)));
+                        Expr::Identifier(temp, *position)
+                    }
+                    None => value.clone(),
+                };
+                // Nothing after the `_` can run
+                let end: usize = arms
+                    .iter()
+                    .position(|x| x.pattern.is_none())
+                    .map_or(arms.len(), |x| x + 1);
+                self.match_chain(&subject, &arms[..end], true);
+                return;
+            }
+            Stmt::While { condition, body } => {
+                self.line(&backend.while_start(&backend.expr(condition)));
+                self.loops.push(0);
+                self.block(body, backend.while_end());
+                self.loops.pop();
+                return;
+            }
+            Stmt::Try {
+                body,
+                name,
+                handler,
+            } => {
+                let name: String = backend.variable(name);
+                match (backend.try_start(), backend.catch_start(&name)) {
+                    (Some(start), Some(catch)) => {
+                        self.line(&start);
+                        self.block(body, None);
+                        self.line(&catch);
+                        if let Some(caught) = backend.caught(&name) {
+                            self.indent += 1;
+                            self.line(&caught);
+                            self.indent -= 1;
+                        }
+                        self.block(handler, backend.try_end());
+                    }
+                    // The body runs like it isn't in a try, and the handler never can
+                    _ => self.stmts(body),
+                }
+                return;
+            }
+            Stmt::For {
+                name,
+                iterable,
+                body,
+                ..
+            } => {
+                self.line(&backend.for_start(&backend.variable(name), iterable));
+                self.loops.push(0);
+                self.block(body, backend.for_end());
+                self.loops.pop();
+                return;
+            }
+            Stmt::Func { name, params, body } => {
+                self.line(&backend.func_start(&backend.function(name), &variables(params)));
+                // The parameters are already declared by the function, and the
+                // globals are declared outside of it
+                let globals: Vec<String> = global_names(body);
+                let locals: Vec<String> = assigned_names(body)
+                    .into_iter()
+                    .filter(|x| !params.contains(x) && !globals.contains(x))
+                    .collect();
+                self.indent += 1;
+                for declare in backend.declare(&variables(&locals)) {
+                    self.line(&declare);
+                }
+                self.indent -= 1;
+                self.block(body, None);
+                if let Some(end) = backend.func_end() {
+                    self.line(&end);
+                }
+                return;
+            }
+            Stmt::Return { value } => {
+                let value: Option<String> = value.as_ref().map(|x| backend.expr(x));
+                backend.return_value(value.as_deref())
+            }
+            Stmt::Break { .. } => backend.loop_exit(true, self.loops.last().copied().unwrap_or(0)),
+            Stmt::Continue { .. } => {
+                backend.loop_exit(false, self.loops.last().copied().unwrap_or(0))
+            }
+            Stmt::Global { names } => match backend.global(&variables(names)) {
+                Some(line) => line,
+                None => return,
+            },
+            Stmt::Exit { code, .. } => backend.exit(&match code {
+                Some(code) => backend.expr(code),
+                None => backend.expr(&Expr::Numeric("0".to_string())),
+            }),
+            // assert_eq is an assert that the two are equal
+            Stmt::Assert {
+                value,
+                expected,
+                position,
+            } => {
+                let condition: Expr = match expected {
+                    Some(expected) => Expr::Binary {
+                        left: Box::new(value.clone()),
+                        operator: Tokens::Equal,
+                        right: Box::new(expected.clone()),
+                        position: *position,
+                    },
+                    None => value.clone(),
+                };
+                backend.assert(
+                    &backend.expr(&condition),
+                    &format!("assertion failed: {}", expr_source(&condition)),
+                )
+            }
+            // The module's statements go here once it's loaded, so there's nothing to output
+            Stmt::Use { .. } => return,
+            Stmt::Expr(expr) => backend.expr_stmt(&backend.expr(expr)),
+            Stmt::Lines(lines) => {
+                for line in lines {
+                    self.line(line);
+                }
+                return;
+            }
+        };
+        self.line(&line);
+    }
+}
+
+/// Finds every import the statements need, kept sorted and without repeats
+pub struct ImportCollector<'a> {
+    backend: &'a dyn Backend,
+    imports: BTreeSet<String>,
+}
+
+impl Visit for ImportCollector<'_> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        if let Some(import) = self.backend.stmt_import(stmt) {
+            self.imports.insert(import);
+        }
+        walk_stmt(self, stmt);
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Some(import) = self.backend.import(expr) {
+            self.imports.insert(import);
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Turn the statements into source for the backend, one line per statement,
+/// with the imports they need once each at the top
+pub fn codegen(stmts: &[Stmt], backend: &dyn Backend) -> String {
+    generate(stmts, backend, None).0
+}
+
+/// The same as codegen, along with the output line each statement the table knows
+/// about starts on and the line it came from, in the order they're output
+pub fn codegen_mapped(
+    stmts: &[Stmt],
+    backend: &dyn Backend,
+    table: &LineTable,
+) -> (String, Vec<(usize, i64)>) {
+    generate(stmts, backend, Some(table))
+}
+
+fn generate(
+    stmts: &[Stmt],
+    backend: &dyn Backend,
+    table: Option<&LineTable>,
+) -> (String, Vec<(usize, i64)>) {
+    let mut collector: ImportCollector = ImportCollector {
+        backend,
+        imports: BTreeSet::new(),
+    };
+    for stmt in stmts {
+        collector.visit_stmt(stmt);
+    }
+
+    let mut codegen: Codegen = Codegen {
+        backend,
+        indent: 0,
+        output: String::new(),
+        labels: 0,
+        lines: 0,
+        table,
+        map: Vec::new(),
+        loops: Vec::new(),
+    };
+    for header in backend.header() {
+        codegen.line(&header);
+    }
+    for import in &collector.imports {
+        // One that starts another, like `import time`, is already there
+        let inside: bool = collector
+            .imports
+            .iter()
+            .any(|x| x.starts_with(&format!("{}\n", import)));
+        if !inside {
+            codegen.line(import);
+        }
+    }
+    // A variable that's only set in a function after `global` still needs declaring
+    let mut globals: Vec<String> = assigned_names(stmts);
+    for name in global_names(stmts) {
+        if !globals.contains(&name) {
+            globals.push(name);
+        }
+    }
+    let globals: Vec<String> = globals.iter().map(|x| backend.variable(x)).collect();
+    for declare in backend.declare(&globals) {
+        codegen.line(&declare);
+    }
+    match backend.main_start() {
+        // The functions go first, then everything else goes inside of main
+        Some(main_start) => {
+            for stmt in stmts.iter().filter(|x| matches!(x, Stmt::Func { .. })) {
+                codegen.stmt(stmt);
+            }
+            codegen.line(&main_start);
+            codegen.indent += 1;
+            codegen.block_stmts(stmts, true);
+            codegen.indent -= 1;
+            if let Some(end) = backend.func_end() {
+                codegen.line(&end);
+            }
+        }
+        None => codegen.stmts(stmts),
+    }
+    for footer in backend.footer() {
+        codegen.line(&footer);
+    }
+    (codegen.output, codegen.map)
+}
+
+/// Turn the statements into python source, one line per statement
+pub fn python_codegen(stmts: &[Stmt], print_style: PrintStyle) -> String {
+    codegen(stmts, &PythonBackend::new(print_style, stmts))
+}
+
+/// Run the python through black, the result is an error if black can't be run
+#[cfg(feature = "black")]
+pub fn format_python(python: &str) -> Result<String, String> {
+    let mut child = Command::new("black")
+        .args(["--quiet", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("could not run black: {}", error))?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(python.as_bytes())
+        .map_err(|error| format!("could not write to black: {}", error))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("could not read from black: {}", error))?;
+
+    if !output.status.success() {
+        return Err("black could not format the python".to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|_| "black gave back invalid utf-8".to_string())
+}
+
+#[cfg(not(feature = "black"))]
+pub fn format_python(_python: &str) -> Result<String, String> {
+    Err("mahou was built without the black feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_undefined;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse, Parser};
+
+    #[test]
+    fn print_style_test() {
+        let mut lexer: Lexer = new_lexer("print a;");
+        lexer.lexer().unwrap();
+
+        let mut parser: Parser = new_parser(&lexer.tokens);
+        assert_eq!(parser.python(), Ok(vec!["print(a)".to_string()]));
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        parser.print_style = PrintStyle::Statement;
+        assert_eq!(parser.python(), Ok(vec!["print a".to_string()]));
+
+        assert_eq!("statement".parse(), Ok(PrintStyle::Statement));
+        assert!("bogus".parse::<PrintStyle>().is_err());
+    }
+
+    #[test]
+    fn import_test() {
+        let mut lexer: Lexer =
+            new_lexer("set r = 2;\nset c = 2 * $PI * r;\nset a = $PI * r ** 2 + $E;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "import math\nr = 2\nc = 2 * math.pi * r\na = math.pi * r ** 2 + math.e\n"
+        );
+        // Javascript has the constants without importing anything
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)).lines().next(),
+            Some("let r, c, a;")
+        );
+
+        // Nothing gets imported when no constants are used
+        assert_eq!(python_codegen(&stmts[..1], PrintStyle::Function), "r = 2\n");
+
+        let mut lexer: Lexer = new_lexer("print sqrt(2) + floor($PI) + floor(1);");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "from math import floor\nfrom math import sqrt\nimport math\nprint(sqrt(2) + floor(math.pi) + floor(1))\n"
+        );
+
+        // Parse is defined once at the top for `input x;`
+        let mut lexer: Lexer = new_lexer("input a;\ninput b;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let python: String = python_codegen(&stmts, PrintStyle::Function);
+        assert!(python.starts_with("def parse(text):\n    try:\n"));
+        assert!(
+            python.ends_with("            return text\na = parse(input())\nb = parse(input())\n")
+        );
+        let js: String = codegen(&stmts, &JsBackend::new(&stmts));
+        assert!(js.contains("readFileSync(0, \"utf8\")"));
+        assert!(js.ends_with("a = parse(mahou_input());\nb = parse(mahou_input());\n"));
+
+        let mut lexer: Lexer = new_lexer("print env(\"HOME\");");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert!(python_codegen(&stmts, PrintStyle::Function)
+            .starts_with("def env(name):\n    import os\n"));
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("console.log((process.env[\"HOME\"] ?? \"\"));\n"));
+
+        let mut lexer: Lexer = new_lexer("print rand_int(1, 6) + rand();");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "import random\nprint(random.randint(1, 6) + random.random())\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("console.log(rand_int(1, 6) + Math.random());\n"));
+
+        // elapsed already imports time for the others
+        let mut lexer: Lexer = new_lexer("set time = now();\nsleep(5);\nprint elapsed();");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "import time\nmahou_start = time.perf_counter()\ntime_ = time.time()\n\
+             time.sleep(5 / 1000)\nprint((time.perf_counter() - mahou_start))\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).ends_with(
+            "time = (Date.now() / 1000);\nsleep(5)\nconsole.log((performance.now() / 1000));\n"
+        ));
+
+        // The arguments come from sys instead of math
+        let mut lexer: Lexer = new_lexer("print $ARGS[0] + str($ARGC);");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "import sys\nprint(sys.argv[1:][0] + str(len(sys.argv[1:])))\n"
+        );
+
+        let mut lexer: Lexer = new_lexer("print $TAU;");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            new_parser(lexer.tokens).parse().unwrap_err().message,
+            "unknown constant '$TAU'"
+        );
+    }
+
+    #[test]
+    fn backend_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = 0;\nwhile a < 3 {\n  a += 1;\n  if a > 1 {}\n}\nprint a * 2;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        let python: PythonBackend = PythonBackend::new(PrintStyle::Function, &stmts);
+        assert_eq!(
+            codegen(&stmts, &python),
+            "a = 0\nwhile a < 3:\n    a += 1\n    if a > 1:\n        pass\nprint(a * 2)\n"
+        );
+        assert_eq!(
+            codegen(&stmts, &PseudocodeBackend),
+            "SET a TO 0\nWHILE a < 3 DO\n    ADD 1 TO a\n    IF a > 1 THEN\n    END IF\nEND WHILE\nDISPLAY a * 2\n"
+        );
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "let a;\na = 0;\nwhile (a < 3) {\n    a += 1;\n    if (a > 1) {\n    }\n}\nconsole.log(a * 2);\n"
+        );
+        // Javascript's % is made to work like python's, and a minus before ** needs parentheses
+        let mut lexer: Lexer = new_lexer("print -a ** 2 + (a + 1) % 3 * 2;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &python),
+            "print(-a ** 2 + (a + 1) % 3 * 2)\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).ends_with(
+            "    return ((a % b) + b) % b;\n}\nconsole.log(-(a ** 2) + mahou_mod(a + 1, 3) * 2);\n"
+        ));
+        // A negative remainder comes out the same, -7 % 3 is 2 everywhere
+        let mut lexer: Lexer = new_lexer("print -7 % 3;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(codegen(&stmts, &python), "print(-7 % 3)\n");
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("}\nconsole.log(mahou_mod(-7, 3));\n"));
+        let wasm: String = codegen(&stmts, &WasmBackend::new(&stmts));
+        assert!(wasm.contains(
+            "(call $print (call $mahou_mod (i64.sub (i64.const 0) (i64.const 7)) (i64.const 3)))"
+        ));
+        assert!(wasm.contains("(func $mahou_mod") && !wasm.contains("(func $mahou_pow"));
+        let c: String = codegen(&stmts, &CBackend::new(&stmts));
+        assert!(c.contains("long long mahou_mod(long long a, long long b) {"));
+        assert!(c.contains("printf(\"%lld\\n\", (long long)(mahou_mod(-7, 3)));"));
+        let mut lexer: Lexer = new_lexer("print [true, false];");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(codegen(&stmts, &python), "print([True, False])\n");
+        // Javascript would show them as `[ true, false ]`
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("    return String(value);\n}\nconsole.log(mahou_show([true, false]));\n"));
+        let mut lexer: Lexer = new_lexer("print not a or a and (not a) < 1;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &python),
+            "print(not a or a and (not a) < 1)\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("}\nconsole.log(mahou_show(!a || a && (!a) < 1));\n"));
+
+        // exit goes to whatever stops the program in each language
+        let mut lexer: Lexer = new_lexer("exit 2;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(codegen(&stmts, &python), "raise SystemExit(2)\n");
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "process.exit(2);\n"
+        );
+        assert!(codegen(&stmts, &CBackend::new(&stmts)).contains("\n    exit(2);\n"));
+        assert!(codegen(&stmts, &WasmBackend::new(&stmts))
+            .contains("(import \"env\" \"exit\" (func $exit (param i64)))"));
+        assert_eq!("pseudocode".parse(), Ok(Emit::Pseudocode));
+        assert_eq!(Emit::Js.extension(), "js");
+        assert_eq!("ast".parse(), Ok(Emit::Ast));
+        assert!(Emit::Tokens.backend(PrintStyle::Function, &stmts).is_none());
+
+        let mut lexer: Lexer = new_lexer("func f(a) { return a * 2; }\nprint f(1);");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        // What a function gives back could be anything, so it's shown like a list would be
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).ends_with(
+            "}\nfunction f(a) {\n    return a * 2;\n}\nconsole.log(mahou_show(f(1)));\n"
+        ));
+        assert_eq!(
+            codegen(&stmts, &PseudocodeBackend),
+            "PROCEDURE f(a)\n    RETURN a * 2\nEND PROCEDURE\nDISPLAY f(1)\n"
+        );
+
+        // Each function declares its own variables, but not its parameters
+        let mut lexer: Lexer =
+            new_lexer("func f(a) { set a = 1; set b = a; }\nwhile 1 { set c, d = 1, 2; }");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "let c, d;\nfunction f(a) {\n    let b;\n    a = 1;\n    b = a;\n}\nwhile (1) {\n    [c, d] = [1, 2];\n}\n"
+        );
+
+        let mut lexer: Lexer = new_lexer("if a { print 1; } else { print 2; }");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "if (a) {\n    console.log(1);\n} else {\n    console.log(2);\n}\n"
+        );
+        assert_eq!(
+            codegen(&stmts, &PseudocodeBackend),
+            "IF a THEN\n    DISPLAY 1\nELSE\n    DISPLAY 2\nEND IF\n"
+        );
+
+        let mut lexer: Lexer = new_lexer("print a == 1;\nprint a != 2;\nprint a <= 3;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).ends_with(
+            "}\nconsole.log(mahou_show(a === 1));\nconsole.log(mahou_show(a !== 2));\nconsole.log(mahou_show(a <= 3));\n"
+        ));
+
+        let mut lexer: Lexer = new_lexer("print \"`{a}` costs ${b}\";");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .ends_with("}\nconsole.log(`\\`${mahou_show(a)}\\` costs $${mahou_show(b)}`);\n"));
+        assert_eq!(
+            codegen(&stmts, &PseudocodeBackend),
+            "DISPLAY \"`{a}` costs ${b}\"\n"
+        );
+
+        // The builtins become javascript's, unless the program has its own
+        let mut lexer: Lexer =
+            new_lexer("func sqrt(a) { return a; }\nprint sqrt(len(\"ab\") + abs(int(\"-2\")));");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)).lines().last(),
+            Some("console.log(mahou_show(sqrt((\"ab\").length + Math.abs(Math.trunc(Number(\"-2\"))))));")
+        );
+    }
+
+    #[test]
+    fn c_backend_test() {
+        let mut lexer: Lexer = new_lexer(
+            "func sq(a) { return a * a; }\nset i = 0;\nset s = \"hi\";\ni += 1;\nprint sq(i) / 2;\nif i < 3 { jump 1; }\njump -3;\nprint s;",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &CBackend::new(&stmts)),
+            "#include <stdio.h>\n#include <stdlib.h>\nlong long i;\nconst char *s;\ndouble sq(double a) {\n    return a * a;\n}\nint main(void) {\n    i = 0;\n    s = \"hi\";\n    jump_1: ;\n    i += 1;\n    printf(\"%g\\n\", sq(i) / 2);\n    if (i < 3) {\n        goto jump_2;\n        jump_2: ;\n    }\n    goto jump_1;\n    printf(\"%s\\n\", s);\n}\n"
+        );
+
+        // A variable set to an int and then a float is a double
+        let mut lexer: Lexer = new_lexer("set a = 1;\nset b = a;\nset a = 2.5;\nset c = 2 ** 3;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let types: HashMap<String, CType> = infer_c_types(&stmts);
+        assert_eq!(types["a"], CType::Double);
+        assert_eq!(types["b"], CType::Double);
+        assert_eq!(types["c"], CType::Double);
+
+        // Each value in a string gets the format for its type
+        let mut lexer: Lexer = new_lexer("set n = 1;\nprint \"{n}% of {n / 3}\";");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert!(codegen(&stmts, &CBackend::new(&stmts))
+            .contains("printf(\"%lld%% of %g\\n\", (long long)(n), (double)n / 3);"));
+
+        let mut lexer: Lexer = new_lexer("set n = floor(sqrt(10));\nprint pow(n, 2);");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &CBackend::new(&stmts)),
+            "#include <stdio.h>\n#include <stdlib.h>\n#include <math.h>\nlong long n;\nint main(void) {\n    n = (long long)floor(sqrt(10));\n    printf(\"%g\\n\", pow(n, 2));\n}\n"
+        );
+        // Files go through helpers, and a call on its own line ends with ;
+        let mut lexer: Lexer = new_lexer("write_file(\"a.txt\", read_file(\"b.txt\"));");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let c: String = codegen(&stmts, &CBackend::new(&stmts));
+        assert!(c.contains("char *mahou_read_file(const char *path) {"));
+        assert!(c.contains("    mahou_write_file(\"a.txt\", mahou_read_file(\"b.txt\"));\n"));
+        // % takes the sign of the right side like the interpreter, for doubles too,
+        // and math.h comes with fmod only once
+        let mut lexer: Lexer = new_lexer("set x = 7.5;\nprint 7 % -3, x % -2, pow(x, 2);");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let c: String = codegen(&stmts, &CBackend::new(&stmts));
+        assert!(c.starts_with("#include <stdio.h>\n#include <stdlib.h>\n#include <math.h>\ndouble mahou_fmod(double a, double b) {\n    double rem = fmod(a, b);\n"));
+        assert_eq!(c.matches("#include <math.h>").count(), 1);
+        assert!(c.contains("mahou_mod(7, -3)") && c.contains("mahou_fmod(x, -2)"));
+        assert_eq!("c".parse(), Ok(Emit::C));
+    }
+
+    #[test]
+    fn wasm_backend_test() {
+        let mut lexer: Lexer = new_lexer(
+            "func add(a, b) { set c = a + b; return c; }\nset c = 2;\nwhile c < 4 { c += 1; }\nprint add(c, 2 ** 3);\nprint \"hi\";",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let wasm: String = codegen(&stmts, &WasmBackend::new(&stmts));
+        // The c in the function is its own local, the one outside is a global
+        assert_eq!(
+            wasm.lines().take(16).collect::<Vec<&str>>().join("\n"),
+            "(module
+(import \"env\" \"print\" (func $print (param i64)))
+(import \"env\" \"print_str\" (func $print_str (param i32 i32)))
+(global $c (mut i64) (i64.const 0))
+(func $add (param $a i64) (param $b i64) (result i64)
+    (local $c i64)
+    (local.set $c (i64.add (local.get $a) (local.get $b)))
+    (return (local.get $c))
+(i64.const 0))
+(func $main (export \"main\") (result i64)
+    (global.set $c (i64.const 2))
+    (block (loop (br_if 1 (i64.eqz (i64.extend_i32_u (i64.lt_s (global.get $c) (i64.const 4)))))
+        (global.set $c (i64.add (global.get $c) (i64.const 1)))
+    (br 0)))
+    (call $print (call $add (global.get $c) (call $mahou_pow (i64.const 2) (i64.const 3))))
+    (call $print_str (i32.const 0) (i32.const 2))"
+        );
+        assert!(wasm.contains("(data (i32.const 0) \"hi\")\n(func $mahou_pow"));
+        assert!(wasm.ends_with("(local.get $result))\n)\n"));
+        assert_eq!(wat_string("a\"\\\n"), "\"a\\22\\5c\\0a\"");
+        assert_eq!("wat".parse(), Ok(Emit::Wasm));
+
+        let mut lexer: Lexer = new_lexer("print max(abs(-2), pow(2, floor(3)));");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let wasm: String = codegen(&stmts, &WasmBackend::new(&stmts));
+        assert!(wasm.contains("(call $print (call $mahou_max (call $mahou_abs (i64.sub (i64.const 0) (i64.const 2))) (call $mahou_pow (i64.const 2) (i64.const 3))))"));
+        assert!(wasm.contains("(func $mahou_pow") && wasm.contains("(func $mahou_max"));
+    }
+
+    #[test]
+    fn multiple_targets_test() {
+        let mut lexer: Lexer = new_lexer("set a = input;\nif a > 1 {\n  print a ** 2;\n}");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        let targets: Vec<Emit> = vec!["python".parse().unwrap(), "js".parse().unwrap()];
+        let outputs: Vec<String> = targets
+            .iter()
+            .map(|x| {
+                codegen(
+                    &stmts,
+                    x.backend(PrintStyle::Function, &stmts).unwrap().as_ref(),
+                )
+            })
+            .collect();
+        assert_eq!(outputs[0], "a = input()\nif a > 1:\n    print(a ** 2)\n");
+        assert!(outputs[1].ends_with(
+            "}\nlet a;\na = mahou_input();\nif (a > 1) {\n    console.log(a ** 2);\n}\n"
+        ));
+    }
+
+    #[test]
+    fn python_codegen_test() {
+        let mut lexer: Lexer = new_lexer("set a = 0;\na += 1;\nprint a - 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        let stmts: Vec<Stmt> = parser.parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "a = 0\na += 1\nprint(a - 1)\n"
+        );
+
+        let mut lexer: Lexer =
+            new_lexer("set total = 0;\nfunc add(n) {\n  global total;\n  total += n;\n}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        let stmts: Vec<Stmt> = parser.parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "total = 0\ndef add(n):\n    global total\n    total += n\n"
+        );
+    }
+
+    #[test]
+    fn python_names_test() {
+        // A function and a variable can share a name in mahou but not in python
+        let mut lexer: Lexer = new_lexer(
+            "func f(is) {\n  return is;\n}\nset f = f(1);\nset f_ = 2;\nset class = len([f, f_]);",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "def f(is_):\n    return is_\nf_ = f(1)\nf___ = 2\nclass_ = len([f_, f___])\n"
+        );
+        let backend: PythonBackend = PythonBackend::new(PrintStyle::Function, &stmts);
+        assert_eq!(backend.function("def"), "def__");
+        assert_eq!(backend.variable("total"), "total");
+    }
+
+    #[test]
+    fn js_lists_test() {
+        // Javascript's + on two lists would give "5,23"
+        let mut lexer: Lexer =
+            new_lexer("set a = [5, 2];\na += [3];\nset b = a + [4];\nprint len(b), a;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let js: String = codegen(&stmts, &JsBackend::new(&stmts));
+        assert!(js.starts_with("function mahou_concat(a, b) {\n    return a.concat(b);\n}\n"));
+        assert!(js.contains("function mahou_extend(list, items) {\n"));
+        assert!(js.ends_with(
+            "a = [5, 2];\nmahou_extend(a, [3]);\nb = mahou_concat(a, [4]);\n\
+             console.log(`${(b).length} ${mahou_show(a)}`);\n"
+        ));
+        // Adding numbers stays the same and doesn't need either helper
+        let mut lexer: Lexer = new_lexer("set n = 1;\nn += 2;\nprint n + 1;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "let n;\nn = 1;\nn += 2;\nconsole.log(n + 1);\n"
+        );
+    }
+
+    #[test]
+    fn js_names_test() {
+        // The same as python, but with javascript's reserved words
+        let mut lexer: Lexer = new_lexer(
+            "func new(class) {\n  return class;\n}\nset new = new(1);\nset lambda = new;",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            codegen(&stmts, &JsBackend::new(&stmts)),
+            "let new_, lambda;\nfunction new__(class_) {\n    return class_;\n}\nnew_ = new__(1);\nlambda = new_;\n"
+        );
+        let backend: JsBackend = JsBackend::new(&stmts);
+        assert_eq!(backend.variable("Math"), "Math_");
+        assert_eq!(backend.variable("total"), "total");
+    }
+
+    #[test]
+    fn print_many_test() {
+        let mut lexer: Lexer =
+            new_lexer("print a, \"|\", \"b is {b}\";\nprint fmt(a, 2), \"{fmt(a, n)}\";");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "print(a, \"|\", f\"b is {b}\")\nprint(format(a, \".2f\"), f\"{a:.{n}f}\")\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).ends_with(
+            "}\nconsole.log(`${mahou_show(a)} | b is ${mahou_show(b)}`);\nconsole.log(`${Number(a).toFixed(2)} ${Number(a).toFixed(n)}`);\n"
+        ));
+    }
+
+    #[test]
+    fn loop_exit_test() {
+        let mut lexer: Lexer = new_lexer("while a {\n  if b { continue; }\n  break;\n}");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "while a:\n    if b:\n        continue\n    break\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts))
+            .contains("while (a) {\n    if (b) {\n        continue;\n    }\n    break;\n}\n"));
+        // The if is a block of its own to wasm, so the continue goes out of it first
+        let wasm: String = codegen(&stmts, &WasmBackend::new(&stmts));
+        assert!(
+            wasm.contains("(then\n            (br 1)\n        ))\n        (br 1)\n    (br 0)))")
+        );
+    }
+
+    #[test]
+    fn ternary_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = b > 1 ? c : d ? 2 : 3;\nprint -(a ? 1 : 2) * 3, (a ? b : c) ? 1 : 2;",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            python_codegen(&stmts, PrintStyle::Function),
+            "a = c if b > 1 else 2 if d else 3\nprint(-(1 if a else 2) * 3, 1 if (b if a else c) else 2)\n"
+        );
+        assert!(codegen(&stmts, &JsBackend::new(&stmts)).contains(
+            "a = b > 1 ? c : d ? 2 : 3;\nconsole.log(`${-(a ? 1 : 2) * 3} ${(a ? b : c) ? 1 : 2}`);\n"
+        ));
+        assert!(
+            codegen(&stmts, &CBackend::new(&stmts)).contains("    a = b > 1 ? c : d ? 2 : 3;\n")
+        );
+        assert_eq!(
+            codegen(&stmts, &PseudocodeBackend).lines().next(),
+            Some("SET a TO IF b > 1 THEN c ELSE IF d THEN 2 ELSE 3")
+        );
+    }
+
+    #[test]
+    fn python_indent_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = 1;\nif a {\n  if a {\n    print a;\n  }\n  a += 1;\n  if a {}\n}\nprint a;",
+        );
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 1".to_string(),
+                "if a:".to_string(),
+                "    if a:".to_string(),
+                "        print(a)".to_string(),
+                "    a += 1".to_string(),
+                "    if a:".to_string(),
+                "        pass".to_string(),
+                "print(a)".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("if a { print a;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "expected '}' to end the block"
+        );
+    }
+
+    #[cfg(feature = "black")]
+    #[test]
+    fn format_python_test() {
+        // Only run when black is installed
+        if Command::new("black").arg("--version").output().is_err() {
+            return;
+        }
+        assert_eq!(format_python("a=(1)\n"), Ok("a = 1\n".to_string()));
+    }
+
+    #[cfg(not(feature = "black"))]
+    #[test]
+    fn format_python_test() {
+        assert!(format_python("a = 1\n").is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Runs a program one statement at a time, for `mahou debug test.m`. It's a hook on
+//! the interpreter, so before a statement runs it can stop and read commands until
+//! it's told to keep going. The commands come from the same place as the program's
+//! input
+
+use crate::ast::{walk_stmt, Expr, Position, Stmt, Visit};
+use crate::dump::stmt_sexpr;
+use crate::interpreter::{exit_with, repr, runtime_error, Hook, Inspect, RuntimeError, Value};
+use crate::lexer::{new_lexer, Lex, Lexer};
+use crate::parser::{first_line, new_parser, Parse, ParseError, Parser};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+
+/// What each command does, shown by `help`
+pub const HELP: &str = "\
+step, s        run this statement and stop at the next one, the same as an empty line
+continue, c    run until a breakpoint
+break, b N     stop at line N, or show the breakpoints without N
+delete, d N    take away the breakpoint at line N
+print, p EXPR  show the value of an expression, like p a + 1
+vars           show every variable that can be seen from here
+list, l        show the lines around this one
+quit, q        stop the program
+Anything else gets run as a statement where the program is, like set a = 5;
+";
+
+/// Finds the line of the statement the interpreter is running, for anything watching it
+pub struct LineTable {
+    /// The lines of the program, for showing a statement
+    source: Vec<String>,
+    /// The line each statement starts on, by where the statement is in memory
+    lines: HashMap<*const Stmt, i64>,
+    /// The lines of the statements inside of each func. Running one copies its body,
+    /// so the copy gets the lines once it's been defined
+    bodies: HashMap<*const Stmt, Vec<i64>>,
+    /// The func that just ran, along with its statement
+    defined: Option<(String, *const Stmt)>,
+}
+
+/// Make the table for the statements, starts is where each one starts from the parser
+pub fn new_line_table(contents: &str, stmts: &[Stmt], starts: &[Position]) -> LineTable {
+    let mut table: LineTable = LineTable {
+        source: contents.lines().map(|x| x.to_string()).collect(),
+        lines: HashMap::new(),
+        bodies: HashMap::new(),
+        defined: None,
+    };
+    table.add_lines(stmts, starts.iter().map(|x| x.line_num));
+    table
+}
+
+pub struct Debugger {
+    lines: LineTable,
+    pub breakpoints: BTreeSet<i64>,
+    /// Stop at the next statement, which is on at the start
+    stepping: bool,
+    /// The commands ran out, so the rest of the program runs without stopping
+    finished: bool,
+}
+
+/// Make a debugger for the statements, starts is where each one starts from the parser
+pub fn new_debugger(contents: &str, stmts: &[Stmt], starts: &[Position]) -> Debugger {
+    Debugger {
+        lines: new_line_table(contents, stmts, starts),
+        breakpoints: BTreeSet::new(),
+        stepping: true,
+        finished: false,
+    }
+}
+
+/// Goes over the statements in the same order the parser started them, giving each
+/// the next line
+struct LineWalker<'a, I: Iterator<Item = i64>> {
+    table: &'a mut LineTable,
+    next: I,
+    seen: Vec<i64>,
+}
+
+impl<'a, I: Iterator<Item = i64>> Visit for LineWalker<'a, I> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        let line: i64 = match self.next.next() {
+            Some(line) => line,
+            None => return,
+        };
+        self.table.lines.insert(stmt as *const Stmt, line);
+        let start: usize = self.seen.len();
+        self.seen.push(line);
+        walk_stmt(self, stmt);
+        if let Stmt::Func { .. } = stmt {
+            let body: Vec<i64> = self.seen[start + 1..].to_vec();
+            self.table.bodies.insert(stmt as *const Stmt, body);
+        }
+    }
+}
+
+impl LineTable {
+    fn add_lines(&mut self, stmts: &[Stmt], lines: impl Iterator<Item = i64>) {
+        let mut walker: LineWalker<_> = LineWalker {
+            table: self,
+            next: lines,
+            seen: Vec::new(),
+        };
+        for stmt in stmts {
+            walker.visit_stmt(stmt);
+        }
+    }
+    /// The line the statement is on, if it's one from the program
+    pub fn line(&self, stmt: &Stmt) -> Option<i64> {
+        self.lines.get(&(stmt as *const Stmt)).copied()
+    }
+    /// Call before each statement runs, so when a func gets defined its copied
+    /// body gets the lines of the one in the program
+    pub fn visit(&mut self, interpreter: &dyn Inspect, stmt: &Stmt) {
+        if let Some((name, func)) = self.defined.take() {
+            let lines: Vec<i64> = self.bodies.get(&func).cloned().unwrap_or_default();
+            if let Some(function) = interpreter.functions().get(&name) {
+                self.add_lines(&function.body, lines.into_iter());
+            }
+        }
+        if let Stmt::Func { name, .. } = stmt {
+            self.defined = Some((name.to_owned(), stmt as *const Stmt));
+        }
+    }
+    /// Where the statement is, like `line 3: set a = a + 1;`
+    pub fn location(&self, stmt: &Stmt) -> String {
+        let line: Option<i64> = self.line(stmt);
+        match line.and_then(|x| self.source.get(x as usize - 1).map(|text| (x, text))) {
+            Some((line, text)) => format!("line {}: {}", line, text.trim()),
+            None => first_line(&stmt_sexpr(stmt, 0)).to_string(),
+        }
+    }
+}
+
+impl Debugger {
+    /// The lines around the one it stopped at, with an arrow at it and a star at breakpoints
+    fn listing(&self, line: i64) -> String {
+        let first: i64 = (line - 2).max(1);
+        let last: i64 = (line + 2).min(self.lines.source.len() as i64);
+        (first..=last)
+            .map(|x| {
+                let marker: &str = match (x == line, self.breakpoints.contains(&x)) {
+                    (true, _) => "->",
+                    (false, true) => " *",
+                    (false, false) => "  ",
+                };
+                format!(
+                    "{} {:>3} | {}\n",
+                    marker,
+                    x,
+                    self.lines.source[x as usize - 1]
+                )
+            })
+            .collect()
+    }
+    /// Do one command, giving back true if the program should keep going
+    pub fn command(
+        &mut self,
+        interpreter: &mut dyn Inspect,
+        command: &str,
+        line: Option<i64>,
+    ) -> Result<bool, RuntimeError> {
+        let (name, rest): (&str, &str) = match command.trim().split_once(' ') {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (command.trim(), ""),
+        };
+        let message: String = match (name, rest) {
+            ("" | "s" | "step", "") => {
+                self.stepping = true;
+                return Ok(true);
+            }
+            ("c" | "continue", "") => return Ok(true),
+            ("q" | "quit", "") => return Err(exit_with(&Value::Int(0))),
+            ("h" | "help", "") => HELP.to_string(),
+            ("b" | "break", "") if self.breakpoints.is_empty() => "no breakpoints\n".to_string(),
+            ("b" | "break", "") => {
+                let lines: Vec<String> = self.breakpoints.iter().map(|x| x.to_string()).collect();
+                format!("breakpoints at line {}\n", lines.join(", "))
+            }
+            ("b" | "break" | "d" | "delete", number) => match number.parse::<i64>() {
+                Ok(number) if name.starts_with('b') => {
+                    self.breakpoints.insert(number);
+                    format!("breakpoint at line {}\n", number)
+                }
+                Ok(number) if self.breakpoints.remove(&number) => {
+                    format!("took away the breakpoint at line {}\n", number)
+                }
+                Ok(number) => format!("there's no breakpoint at line {}\n", number),
+                Err(_) => format!("'{}' isn't a line number\n", number),
+            },
+            ("p" | "print", expr) if !expr.is_empty() => match evaluate(interpreter, expr) {
+                Ok(value) => format!("{}\n", repr(&value)),
+                Err(message) => format!("error: {}\n", message),
+            },
+            ("vars", "") => interpreter
+                .names()
+                .iter()
+                .filter_map(|name| {
+                    let value: Value = interpreter.variable(name).ok()?;
+                    Some(format!("{} = {}\n", name, repr(&value)))
+                })
+                .collect(),
+            ("l" | "list", "") => match line {
+                Some(line) => self.listing(line),
+                None => "this statement isn't from the program\n".to_string(),
+            },
+            _ => match execute(interpreter, command) {
+                Ok(()) => String::new(),
+                Err(message) => format!("error: {}\n", message),
+            },
+        };
+        write!(interpreter.output(), "{}", message)
+            .map_err(|error| runtime_error(format!("could not print: {}", error)))?;
+        Ok(false)
+    }
+}
+
+impl Hook for Debugger {
+    fn before(&mut self, interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError> {
+        self.lines.visit(interpreter, stmt);
+        let line: Option<i64> = self.lines.line(stmt);
+        let breakpoint: bool = line.is_some_and(|x| self.breakpoints.contains(&x));
+        if self.finished || !(self.stepping || breakpoint) {
+            return Ok(());
+        }
+        self.stepping = false;
+
+        let stopped: String = self.lines.location(stmt);
+        let mut text: String = if breakpoint {
+            format!("breakpoint, {}\n", stopped)
+        } else {
+            format!("{}\n", stopped)
+        };
+        loop {
+            text.push_str("(mahou) ");
+            let output: &mut dyn Write = interpreter.output();
+            write!(output, "{}", text)
+                .and_then(|_| output.flush())
+                .map_err(|error| runtime_error(format!("could not print: {}", error)))?;
+            text.clear();
+
+            let mut command: String = String::new();
+            let read: usize = interpreter
+                .input()
+                .read_line(&mut command)
+                .map_err(|error| runtime_error(format!("could not read a command: {}", error)))?;
+            if read == 0 {
+                self.finished = true;
+                return Ok(());
+            }
+            if self.command(interpreter, &command, line)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn lex(source: &str) -> Result<Parser<'static>, String> {
+    let mut lexer: Lexer = new_lexer(source);
+    lexer.lexer().map_err(|error| error.message)?;
+    Ok(new_parser(lexer.tokens))
+}
+
+/// Work out an expression where the program is
+fn evaluate(interpreter: &mut dyn Inspect, expr: &str) -> Result<Value, String> {
+    // So it can be typed like the print statement too
+    let mut parser: Parser = lex(expr.trim_end_matches(';'))?;
+    let expr: Expr = parser.expression(0).map_err(|error| error.message)?;
+    if let Some(tok) = parser.peek() {
+        return Err(format!(
+            "expected the end of the expression, found '{}'",
+            tok.part
+        ));
+    }
+    interpreter.eval(&expr).map_err(|error| error.message)
+}
+
+/// Run statements where the program is, like `set a = 5;`
+fn execute(interpreter: &mut dyn Inspect, source: &str) -> Result<(), String> {
+    let stmts: Vec<Stmt> = lex(source)?
+        .parse()
+        .map_err(|error: ParseError| error.message)?;
+    for stmt in &stmts {
+        interpreter.exec(stmt).map_err(|error| error.message)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{new_interpreter, Interpret, Interpreter};
+    use crate::modules::parse_file_with_starts;
+
+    /// Run the program in the debugger with the commands, giving back everything printed
+    fn debug(contents: &str, commands: &str) -> String {
+        let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+            parse_file_with_starts(contents, None, false).unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+                new_interpreter(commands.as_bytes(), &mut output);
+            interpreter.hook = Some(Box::new(new_debugger(contents, &stmts, &starts)));
+            let _ = interpreter.run(&stmts);
+        }
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn step_test() {
+        assert_eq!(
+            debug("set a = 1;\nprint a;", "s\np a + 1\n\n"),
+            "line 1: set a = 1;\n(mahou) line 2: print a;\n(mahou) 2\n(mahou) 1\n"
+        );
+
+        // Setting a variable changes what the program sees
+        assert_eq!(
+            debug("set a = 1;\nprint a;", "s\nset a = \"x\";\nvars\nc\n"),
+            "line 1: set a = 1;\n(mahou) line 2: print a;\n(mahou) (mahou) a = 'x'\n(mahou) x\n"
+        );
+
+        assert_eq!(
+            debug("print 1;\nprint 2;", "q\n"),
+            "line 1: print 1;\n(mahou) "
+        );
+    }
+
+    #[test]
+    fn breakpoint_test() {
+        // A breakpoint inside of a func works once the func is defined
+        let program: &str = "func f(x) {\n    print x;\n}\nf(1);\nif 1 {\n    f(2);\n}";
+        assert_eq!(
+            debug(program, "b 2\nb 6\nc\nc\nc\np x\nc\n"),
+            "line 1: func f(x) {\n(mahou) breakpoint at line 2\n(mahou) breakpoint at line 6\n\
+             (mahou) breakpoint, line 2: print x;\n(mahou) 1\nbreakpoint, line 6: f(2);\n\
+             (mahou) breakpoint, line 2: print x;\n(mahou) 2\n(mahou) 2\n"
+        );
+
+        // The commands running out lets the rest of the program run
+        assert_eq!(
+            debug("print 1;\nprint 2;", "d 2\n"),
+            "line 1: print 1;\n(mahou) there's no breakpoint at line 2\n(mahou) 1\n2\n"
+        );
+    }
+}
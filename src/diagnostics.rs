@@ -0,0 +1,459 @@
+//! Renders errors and warnings with the line they happened on. Each kind of error
+//! has a code, the ones starting with E00 come from the lexer, E01 from the parser,
+//! E02 from the checks that run before parsing, E03 from running the program
+//! and W for warnings. Each warning also has a name, so it can be turned off with
+//! a `# allow(unused_variable)` comment or made an error in the `[lints]` table
+
+use crate::lexer::{
+    ends_token, is_char_whitespace, new_lexer, Lex, LexError, Lexer, Token, Tokens,
+};
+use crate::parser::ParseError;
+use crate::spacer;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The code of each warning and its name
+pub const LINTS: &[(&str, &str)] = &[
+    ("W0001", "long_identifier"),
+    ("W0002", "read_before_set"),
+    ("W0003", "unused_variable"),
+    ("W0004", "defined_twice"),
+    ("W0005", "unreachable_arm"),
+    ("W0006", "missing_wildcard"),
+    ("W0007", "uneven_division"),
+];
+
+/// An error or warning with where it happened, ready to be rendered
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Diagnostic<'a> {
+    pub level: &'a str,
+    pub code: Option<&'a str>,
+    pub message: &'a str,
+    pub line_num: i64,
+    pub char_num: i64,
+}
+
+impl<'a> From<&'a LexError> for Diagnostic<'a> {
+    fn from(error: &'a LexError) -> Self {
+        Diagnostic {
+            level: "error",
+            code: Some(error.code),
+            message: &error.message,
+            line_num: error.line_num,
+            char_num: error.char_num,
+        }
+    }
+}
+
+impl<'a> From<&'a ParseError> for Diagnostic<'a> {
+    fn from(error: &'a ParseError) -> Self {
+        Diagnostic {
+            level: "error",
+            code: Some(error.code),
+            message: &error.message,
+            line_num: error.line_num,
+            char_num: error.char_num,
+        }
+    }
+}
+
+impl Diagnostic<'_> {
+    /// Turn a diagnostic for something that stops the program into a warning
+    pub fn warning(self) -> Self {
+        Diagnostic {
+            level: "warning",
+            ..self
+        }
+    }
+    /// Show the message with the file and line of source it's about, and the
+    /// token it starts at underlined. The line in the gutter counts the same way
+    /// as the one after the arrow
+    pub fn render(&self, contents: &str, filename: Option<&str>, zero_based: bool) -> String {
+        let line_index: usize = (self.line_num.max(1) - 1) as usize;
+        let line: &str = contents.lines().nth(line_index).unwrap_or("");
+        // The caret can sit just past the end of the line, like for a missing semicolon
+        let column: usize = (self.char_num.max(1) as usize).min(line.chars().count() + 1);
+        // Tabs before the caret stay tabs so it lines up however wide they're shown
+        let padding: String = line
+            .chars()
+            .take(column - 1)
+            .map(|x| if x == '\t' { '\t' } else { ' ' })
+            .collect();
+        let shown_line: i64 = shown_position(self.line_num, zero_based);
+        let gutter: String = spacer(shown_line.to_string().len(), ' ');
+
+        let mut rendered: String = match self.code {
+            Some(code) => format!("{}[{}]: {}\n", self.level, code, self.message),
+            None => format!("{}: {}\n", self.level, self.message),
+        };
+        let position: String = format!(
+            "{}:{}",
+            shown_line,
+            shown_position(column as i64, zero_based)
+        );
+        match filename {
+            Some(filename) => {
+                rendered.push_str(&format!("{}--> {}:{}\n", gutter, filename, position))
+            }
+            None => rendered.push_str(&format!("{}--> {}\n", gutter, position)),
+        }
+        rendered.push_str(&format!("{} |\n", gutter));
+        rendered.push_str(&format!("{} | {}\n", shown_line, line));
+        rendered.push_str(&format!(
+            "{} | {}{}\n",
+            gutter,
+            padding,
+            spacer(underline_len(line, column), '^')
+        ));
+        rendered
+    }
+}
+
+/// How many characters the token starting at the column covers, so all of it gets
+/// underlined. Anything that isn't the start of a token just gets the one caret
+pub fn underline_len(line: &str, column: usize) -> usize {
+    let chars: Vec<char> = line.chars().skip(column - 1).collect();
+    match chars.first() {
+        None => 1,
+        Some(ch) if is_char_whitespace(*ch) => 1,
+        // Strings go up to their closing quote, or the end of the line if there isn't one
+        Some('"') => {
+            let mut escaped: bool = false;
+            for (index, ch) in chars.iter().enumerate().skip(1) {
+                match ch {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => return index + 1,
+                    _ => escaped = false,
+                }
+            }
+            chars.len()
+        }
+        Some(_) => {
+            let mut len: usize = 1;
+            while len < chars.len() && !ends_token(chars[len - 1], chars[len]) {
+                len += 1;
+            }
+            len
+        }
+    }
+}
+
+/// Convert a one-based line or character number to how it gets shown
+pub fn shown_position(num: i64, zero_based: bool) -> i64 {
+    if zero_based {
+        num - 1
+    } else {
+        num
+    }
+}
+
+/// What to do about a warning, from the `[lints]` table
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LintLevel {
+    /// Don't show it at all
+    Allow,
+    /// Show it as a warning and keep going
+    Warn,
+    /// Show it as an error and stop
+    Deny,
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(level: &str) -> Result<Self, String> {
+        match level {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => Err(format!("a lint is allow, warn or deny, not {}", level)),
+        }
+    }
+}
+
+/// The name of the warning with the code, like unused_variable for W0003
+pub fn lint_name(code: &str) -> Option<&'static str> {
+    LINTS
+        .iter()
+        .find(|(x, _)| *x == code)
+        .map(|(_, name)| *name)
+}
+
+/// Which warnings get shown, stop everything or get left out
+#[derive(PartialEq, Debug, Clone)]
+pub struct Lints {
+    levels: BTreeMap<String, LintLevel>,
+    /// The line each `# allow(...)` is for and a lint it names
+    allowed: Vec<(i64, String)>,
+}
+
+/// The lints for the program, with the levels from the `[lints]` table
+pub fn new_lints(contents: &str, levels: BTreeMap<String, LintLevel>) -> Lints {
+    Lints {
+        levels,
+        allowed: allow_comments(contents),
+    }
+}
+
+impl Lints {
+    /// A warning on a line with an `# allow` is left out, otherwise it's up to
+    /// the table, and anything not in it is a warning
+    pub fn level(&self, warning: &ParseError) -> LintLevel {
+        let name: &str = match lint_name(warning.code) {
+            Some(name) => name,
+            None => return LintLevel::Warn,
+        };
+        if self
+            .allowed
+            .iter()
+            .any(|(line, x)| *line == warning.line_num && x == name)
+        {
+            return LintLevel::Allow;
+        }
+        self.levels.get(name).copied().unwrap_or(LintLevel::Warn)
+    }
+    /// Render the warnings the way their levels say, a denied one as an error.
+    /// Without show_warnings the ones that are only warnings are counted but left out
+    pub fn report(
+        &self,
+        warnings: &[ParseError],
+        contents: &str,
+        filename: Option<&str>,
+        zero_based: bool,
+        show_warnings: bool,
+    ) -> LintReport {
+        let mut report: LintReport = LintReport {
+            rendered: String::new(),
+            warnings: 0,
+            denied: 0,
+        };
+        for warning in warnings {
+            let diagnostic: Diagnostic = match self.level(warning) {
+                LintLevel::Allow => continue,
+                LintLevel::Warn => {
+                    report.warnings += 1;
+                    if !show_warnings {
+                        continue;
+                    }
+                    Diagnostic::from(warning).warning()
+                }
+                LintLevel::Deny => {
+                    report.denied += 1;
+                    Diagnostic::from(warning)
+                }
+            };
+            report
+                .rendered
+                .push_str(&diagnostic.render(contents, filename, zero_based));
+        }
+        report
+    }
+}
+
+/// What the lints made of a program's warnings, with how many were only warnings
+/// and how many were denied, which stops the program
+#[derive(PartialEq, Debug, Clone)]
+pub struct LintReport {
+    pub rendered: String,
+    pub warnings: usize,
+    pub denied: usize,
+}
+
+/// The lints each `# allow(unused_variable, read_before_set)` comment names, with the line
+/// it's for. At the end of a line that's its own line, and on a line by itself
+/// it's the next line with code on it
+pub fn allow_comments(contents: &str) -> Vec<(i64, String)> {
+    let mut lexer: Lexer = new_lexer(contents);
+    lexer.keep_comments = true;
+    // Anything that doesn't lex already has an error, so the tokens before it do
+    let _ = lexer.lexer();
+    let tokens: &[Token] = &lexer.tokens;
+    let mut allowed: Vec<(i64, String)> = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        let names: &str = match token
+            .part
+            .strip_prefix('#')
+            .map(|x| x.trim())
+            .and_then(|x| x.strip_prefix("allow("))
+            .and_then(|x| x.strip_suffix(')'))
+        {
+            Some(names) if token.token == Tokens::Comment => names,
+            _ => continue,
+        };
+        let code = |x: &&Token| x.token != Tokens::Comment;
+        let line: i64 = match tokens[..index].iter().rfind(code) {
+            Some(before) if before.span.end_line == token.line_num() => token.line_num(),
+            _ => match tokens[index + 1..].iter().find(code) {
+                Some(after) => after.line_num(),
+                None => continue,
+            },
+        };
+        for name in names.split(',') {
+            allowed.push((line, name.trim().to_string()));
+        }
+    }
+    allowed
+}
+
+/// Show the message with the line of source it's about and a caret under the column
+pub fn render_error(
+    contents: &str,
+    line_num: i64,
+    char_num: i64,
+    msg: &str,
+    zero_based: bool,
+) -> String {
+    render_diagnostic("error", contents, line_num, char_num, msg, zero_based)
+}
+
+/// The same as render_error, but for something that doesn't stop the program
+pub fn render_warning(
+    contents: &str,
+    line_num: i64,
+    char_num: i64,
+    msg: &str,
+    zero_based: bool,
+) -> String {
+    render_diagnostic("warning", contents, line_num, char_num, msg, zero_based)
+}
+
+/// Positions come in one-based like everywhere else, `zero_based` only changes
+/// the numbers that are shown, for editors that count from zero
+pub fn render_diagnostic(
+    level: &str,
+    contents: &str,
+    line_num: i64,
+    char_num: i64,
+    msg: &str,
+    zero_based: bool,
+) -> String {
+    let diagnostic: Diagnostic = Diagnostic {
+        level,
+        code: None,
+        message: msg,
+        line_num,
+        char_num,
+    };
+    diagnostic.render(contents, None, zero_based)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dump::{token_row, tokens_json, MIN_COLUMN_WIDTHS};
+    use crate::lexer::{new_lexer, Lex, Lexer, Token};
+
+    #[test]
+    fn render_error_test() {
+        let rendered: String = render_error("set a = (1 + 2;", 1, 9, "unmatched '('", false);
+        assert_eq!(
+            rendered,
+            "error: unmatched '('\n --> 1:9\n  |\n1 | set a = (1 + 2;\n  |         ^\n"
+        );
+
+        // Columns past the end of the line get clamped to just after it
+        let rendered: String = render_error("print a\nset b", 2, 40, "bad", false);
+        assert!(rendered.contains("2 | set b\n"));
+        assert!(rendered.ends_with("  |      ^\n"));
+    }
+
+    #[test]
+    fn diagnostic_test() {
+        let contents: &str = "set total = 1;\nprint totl + \"a b\";";
+        let diagnostic: Diagnostic = Diagnostic {
+            level: "error",
+            code: Some("E0200"),
+            message: "variable 'totl' is not defined",
+            line_num: 2,
+            char_num: 7,
+        };
+        assert_eq!(
+            diagnostic.render(contents, Some("main.m"), false),
+            concat!(
+                "error[E0200]: variable 'totl' is not defined\n",
+                " --> main.m:2:7\n",
+                "  |\n",
+                "2 | print totl + \"a b\";\n",
+                "  |       ^^^^\n",
+            )
+        );
+        assert!(diagnostic
+            .warning()
+            .render(contents, None, true)
+            .starts_with("warning[E0200]: variable 'totl' is not defined\n --> 1:6\n"));
+
+        assert_eq!(underline_len("print totl + \"a b\";", 14), 5);
+        assert_eq!(underline_len("a += 1", 3), 2);
+        assert_eq!(underline_len("print", 6), 1);
+    }
+
+    #[test]
+    fn lints_test() {
+        let contents: &str = "set a = 1; # allow(unused_variable)\n\n# allow(defined_twice, long_identifier)\nset b = \"# allow(unused_variable)\";\n";
+        assert_eq!(
+            allow_comments(contents),
+            vec![
+                (1, "unused_variable".to_string()),
+                (4, "defined_twice".to_string()),
+                (4, "long_identifier".to_string())
+            ]
+        );
+
+        let warning = |code: &'static str, line_num: i64| ParseError {
+            code,
+            message: String::new(),
+            line_num,
+            char_num: 1,
+        };
+        let mut levels: BTreeMap<String, LintLevel> = BTreeMap::new();
+        levels.insert("unused_variable".to_string(), LintLevel::Deny);
+        let lints: Lints = new_lints(contents, levels);
+        assert_eq!(lints.level(&warning("W0003", 1)), LintLevel::Allow);
+        assert_eq!(lints.level(&warning("W0003", 4)), LintLevel::Deny);
+        assert_eq!(lints.level(&warning("W0004", 4)), LintLevel::Allow);
+        assert_eq!(lints.level(&warning("W0002", 4)), LintLevel::Warn);
+    }
+
+    #[test]
+    fn tab_test() {
+        // The caret keeps the tabs in front of it so it's under the right character
+        let rendered: String = render_error("if a {\n\tprint b;\n}", 2, 8, "no b", false);
+        assert!(rendered.contains(" --> 2:8\n"));
+        assert!(rendered.ends_with("2 | \tprint b;\n  | \t      ^\n"));
+    }
+
+    #[test]
+    fn zero_based_test() {
+        let rendered: String = render_error("set a = (1 + 2;", 1, 9, "unmatched '('", true);
+        assert_eq!(
+            rendered,
+            "error: unmatched '('\n --> 0:8\n  |\n0 | set a = (1 + 2;\n  |         ^\n"
+        );
+        // The gutter is as wide as the line it shows, which is 9 for the tenth
+        let contents: String = format!("{}print (b;\n", "set a = 1;\n".repeat(9));
+        assert_eq!(
+            render_error(&contents, 10, 7, "unmatched '('", true),
+            "error: unmatched '('\n --> 9:6\n  |\n9 | print (b;\n  |       ^\n"
+        );
+        assert_eq!(
+            render_error(&contents, 10, 7, "unmatched '('", false),
+            "error: unmatched '('\n  --> 10:7\n   |\n10 | print (b;\n   |       ^\n"
+        );
+
+        let contents: &str = "set a;\nprint a;";
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let token: &Token = &lexer.tokens[4];
+        assert_eq!(
+            token_row(token, MIN_COLUMN_WIDTHS, false, false, false),
+            "Identifier    a         2:7"
+        );
+        assert_eq!(
+            token_row(token, MIN_COLUMN_WIDTHS, false, true, false),
+            "Identifier    a         1:6"
+        );
+        assert!(tokens_json(contents, &lexer.tokens, true).contains("\"line\":1,\"column\":6"));
+        // Only the output changes
+        assert_eq!((token.line_num(), token.char_num()), (2, 7));
+    }
+}
@@ -0,0 +1,264 @@
+//! `mahou doc`, a reference for the functions and constants in a project, made
+//! from the `##` comments right above them, as markdown or as a page of HTML.
+//! Comments with one `#` are left out, so they can still be notes for whoever
+//! reads the code. A `##` comment at the very top of a file is about the whole file
+
+use crate::ast::Stmt;
+use crate::diagnostics::Diagnostic;
+use crate::formatter::expr_source;
+use crate::highlight::escape_html;
+use crate::lexer::{new_lexer, Lex, Lexer, Token, Tokens};
+use crate::modules::{parse_file, EXTENSION};
+use crate::testing::is_test_file;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A function or constant and what its comment says about it
+#[derive(PartialEq, Debug, Clone)]
+pub struct Item {
+    pub name: String,
+    /// How it's written, like `func area(w, h)` or `const MAX = 10`
+    pub signature: String,
+    /// A line for each line of the comment, an empty one between paragraphs
+    pub doc: Vec<String>,
+    pub line: i64,
+}
+
+/// Everything in one file
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModuleDoc {
+    pub file: String,
+    pub doc: Vec<String>,
+    pub items: Vec<Item>,
+}
+
+/// The text of a doc comment without the `##`, or none if it's another comment
+fn doc_text(tok: &Token) -> Option<&str> {
+    let text: &str = tok.part.strip_prefix("##")?;
+    Some(text.strip_prefix(' ').unwrap_or(text).trim_end())
+}
+
+/// The `##` lines that end right above the token at the index, from the first.
+/// A comment after some code on its line goes with that code instead
+fn doc_above(tokens: &[Token], index: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line: i64 = tokens[index].span.start_line;
+    for (place, tok) in tokens[..index].iter().enumerate().rev() {
+        let after_code: bool = place > 0 && tokens[place - 1].span.end_line == tok.span.start_line;
+        match doc_text(tok) {
+            Some(text) if tok.span.start_line == line - 1 && !after_code => {
+                lines.push(text.to_string());
+                line -= 1;
+            }
+            _ => break,
+        }
+    }
+    lines.reverse();
+    lines
+}
+
+/// Find the functions and constants outside of every block in the program and
+/// their doc comments. An error is already rendered for showing
+pub fn module_doc(contents: &str, file: &str) -> Result<ModuleDoc, String> {
+    let stmts: Vec<Stmt> = parse_file(contents, Some(file), false)?;
+    let mut lexer: Lexer = new_lexer(contents);
+    lexer.keep_comments = true;
+    lexer
+        .lexer()
+        .map_err(|error| Diagnostic::from(&error).render(contents, Some(file), false))?;
+    let tokens: &[Token] = &lexer.tokens;
+
+    // Where each name is first made with `func` or `const`, the parser already
+    // said it's a name right after
+    let mut found: HashMap<&str, usize> = HashMap::new();
+    for (index, tok) in tokens.iter().enumerate() {
+        if matches!(tok.token, Tokens::Func | Tokens::Const) {
+            if let Some(name) = tokens.get(index + 1) {
+                found.entry(&*name.part).or_insert(index);
+            }
+        }
+    }
+    let items: Vec<Item> = stmts
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Func { name, params, .. } => {
+                Some((name, format!("func {}({})", name, params.join(", "))))
+            }
+            Stmt::Const { name, value } => {
+                Some((name, format!("const {} = {}", name, expr_source(value))))
+            }
+            _ => None,
+        })
+        .map(|(name, signature)| {
+            let index: Option<usize> = found.get(name.as_str()).copied();
+            Item {
+                name: name.to_owned(),
+                signature,
+                doc: index.map_or_else(Vec::new, |x| doc_above(tokens, x)),
+                line: index.map_or(0, |x| tokens[x].span.start_line),
+            }
+        })
+        .collect();
+
+    // The comment at the top is the file's, unless it's right above the first item
+    let mut doc: Vec<String> = Vec::new();
+    for (place, tok) in tokens.iter().enumerate() {
+        match doc_text(tok) {
+            Some(text) if doc.len() as i64 + 1 == tok.span.start_line => doc.push(text.to_string()),
+            _ => {
+                if matches!(tok.token, Tokens::Func | Tokens::Const)
+                    && doc_above(tokens, place) == doc
+                {
+                    doc.clear();
+                }
+                break;
+            }
+        }
+    }
+    Ok(ModuleDoc {
+        file: file.to_string(),
+        doc,
+        items,
+    })
+}
+
+/// Every program in the paths sorted, a folder is looked through all the way down
+/// besides its hidden folders. Tests aren't part of what a project gives to use
+pub fn source_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            find_sources(path, &mut files)?;
+        } else {
+            files.push(path.to_owned());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn find_sources(folder: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let path: PathBuf = entry?.path();
+        let hidden: bool = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .is_some_and(|x| x.starts_with('.'));
+        if path.is_dir() && !hidden {
+            find_sources(&path, files)?;
+        } else if path.extension().is_some_and(|x| x == EXTENSION) && !is_test_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The reference as markdown, a heading for each file and one for each item in it
+pub fn markdown(title: &str, modules: &[ModuleDoc]) -> String {
+    let mut output: String = format!("# {}\n", title);
+    for module in modules {
+        output.push_str(&format!("\n## {}\n", module.file));
+        if !module.doc.is_empty() {
+            output.push_str(&format!("\n{}\n", module.doc.join("\n")));
+        }
+        for item in &module.items {
+            output.push_str(&format!("\n### `{}`\n", item.signature));
+            if !item.doc.is_empty() {
+                output.push_str(&format!("\n{}\n", item.doc.join("\n")));
+            }
+        }
+    }
+    output
+}
+
+/// The lines of a comment as HTML paragraphs, split where there's an empty line
+fn paragraphs(lines: &[String]) -> String {
+    lines
+        .split(|x| x.is_empty())
+        .filter(|x| !x.is_empty())
+        .map(|x| format!("<p>{}</p>\n", escape_html(&x.join("\n"))))
+        .collect()
+}
+
+/// The reference as a page of HTML, each item with an id like `shapes.m-area` to
+/// link to
+pub fn html(title: &str, modules: &[ModuleDoc]) -> String {
+    let title: String = escape_html(title);
+    let mut output: String = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, title
+    );
+    for module in modules {
+        let file: String = escape_html(&module.file);
+        output.push_str(&format!("<h2 id=\"{}\">{}</h2>\n", file, file));
+        output.push_str(&paragraphs(&module.doc));
+        for item in &module.items {
+            output.push_str(&format!(
+                "<h3 id=\"{}-{}\"><code>{}</code></h3>\n",
+                file,
+                item.name,
+                escape_html(&item.signature)
+            ));
+            output.push_str(&paragraphs(&item.doc));
+        }
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_doc_test() {
+        let contents: &str = "## Shapes and how big they are\n\n# how many sides, not a doc\nconst SIDES = 4;\n\n## The area of a rectangle\n##\n## It can be <0 too\nfunc area(w, h) {\n    return w * h; ## not the next one's\n}\nfunc perimeter(w, h) {\n    return 2 * (w + h);\n}\n";
+        let module: ModuleDoc = module_doc(contents, "shapes.m").unwrap();
+        assert_eq!(module.doc, vec!["Shapes and how big they are"]);
+        assert_eq!(
+            module.items,
+            vec![
+                Item {
+                    name: "SIDES".to_string(),
+                    signature: "const SIDES = 4".to_string(),
+                    doc: Vec::new(),
+                    line: 4,
+                },
+                Item {
+                    name: "area".to_string(),
+                    signature: "func area(w, h)".to_string(),
+                    doc: vec![
+                        "The area of a rectangle".to_string(),
+                        String::new(),
+                        "It can be <0 too".to_string()
+                    ],
+                    line: 9,
+                },
+                Item {
+                    name: "perimeter".to_string(),
+                    signature: "func perimeter(w, h)".to_string(),
+                    doc: Vec::new(),
+                    line: 12,
+                },
+            ]
+        );
+        // Right above the first function it's that function's
+        let module: ModuleDoc = module_doc("## Says hi\nfunc hi() {\n}\n", "hi.m").unwrap();
+        assert_eq!(module.doc, Vec::<String>::new());
+        assert_eq!(module.items[0].doc, vec!["Says hi"]);
+        assert!(module_doc("func (", "bad.m")
+            .unwrap_err()
+            .contains("bad.m:1"));
+
+        let modules: Vec<ModuleDoc> = vec![module_doc(contents, "shapes.m").unwrap()];
+        assert_eq!(
+            markdown("shapes", &modules),
+            "# shapes\n\n## shapes.m\n\nShapes and how big they are\n\n### `const SIDES = 4`\n\n### `func area(w, h)`\n\nThe area of a rectangle\n\nIt can be <0 too\n\n### `func perimeter(w, h)`\n"
+        );
+        let page: String = html("shapes", &modules);
+        assert!(page.contains("<h3 id=\"shapes.m-area\"><code>func area(w, h)</code></h3>\n<p>The area of a rectangle</p>\n<p>It can be &lt;0 too</p>\n"));
+    }
+}
@@ -0,0 +1,913 @@
+//! Shows the tokens as a table or as JSON, and the tree as JSON or S-expressions
+
+use crate::ast::{Expr, Position, Stmt};
+use crate::diagnostics::shown_position;
+use crate::lexer::{describe, new_lexer, Lex, LexError, Lexer, Token, TokenKind, Tokens};
+use crate::parser::operator_text;
+use crate::spacer;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// How the tokens are shown with `--emit tokens`, and the tree with `--emit ast`
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    Table,
+    Json,
+    /// Only for the tree, like `(set a (+ 1 2))`
+    Sexpr,
+    /// Only for highlighting, as spans with a class for each kind of token
+    Html,
+    /// Only for highlighting, with colors for a terminal
+    Ansi,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "sexpr" => Ok(Format::Sexpr),
+            "html" => Ok(Format::Html),
+            "ansi" => Ok(Format::Ansi),
+            _ => Err(format!(
+                "unknown format '{}', expected table, json, sexpr, html or ansi",
+                format
+            )),
+        }
+    }
+}
+
+/// Quote a string for JSON, escaping anything that can't go in as is.
+/// Python and javascript read the same escapes, so their strings use this too
+pub fn json_string(text: &str) -> String {
+    let mut quoted: String = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            ch if (ch as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Turn the tokens into a JSON array, one object per token, for editors and other tools.
+/// The column counts from the start of the line and the offset is in bytes from
+/// the start of the source, which always counts from zero
+pub fn tokens_json(contents: &str, tokens: &[Token], zero_based: bool) -> String {
+    // Where each line starts in bytes
+    let mut line_starts: Vec<usize> = vec![0];
+    for (index, ch) in contents.char_indices() {
+        if ch == '\n' {
+            line_starts.push(index + 1);
+        }
+    }
+
+    let objects: Vec<String> = tokens
+        .iter()
+        .map(|x| {
+            let line_start: usize = line_starts
+                .get((x.line_num() - 1) as usize)
+                .copied()
+                .unwrap_or(0);
+            let offset: usize = contents[line_start..]
+                .char_indices()
+                .nth((x.char_num() - 1) as usize)
+                .map_or(contents.len(), |(index, _)| line_start + index);
+            format!(
+                "{{\"kind\":{},\"token\":{},\"text\":{},\"line\":{},\"column\":{},\"offset\":{}}}",
+                json_string(&format!("{:?}", TokenKind::from(x.token))),
+                json_string(&format!("{:?}", x.token)),
+                json_string(&x.part),
+                shown_position(x.line_num(), zero_based),
+                shown_position(x.char_num(), zero_based),
+                offset
+            )
+        })
+        .collect();
+    format!("[{}]", objects.join(","))
+}
+
+/// The widths of the type and part columns when every token fits in them
+pub const MIN_COLUMN_WIDTHS: (usize, usize) = (14, 10);
+
+/// How wide the type and part columns of the token table need to be, so the
+/// longest of each still has two spaces before the next column
+pub fn column_widths(tokens: &[Token]) -> (usize, usize) {
+    tokens
+        .iter()
+        .fold(MIN_COLUMN_WIDTHS, |(type_width, part_width), x| {
+            (
+                type_width.max(format!("{:?}", x.token).len() + 2),
+                part_width.max(x.part.chars().count() + 2),
+            )
+        })
+}
+
+/// The ANSI color the type of a token is shown in, by its kind
+pub fn kind_color(kind: TokenKind) -> &'static str {
+    match kind {
+        TokenKind::Keyword => "\x1b[35m",
+        TokenKind::Operator => "\x1b[33m",
+        TokenKind::Literal => "\x1b[32m",
+        TokenKind::Identifier => "\x1b[36m",
+        TokenKind::Constant => "\x1b[34m",
+        TokenKind::Punctuation | TokenKind::Trivia => "\x1b[90m",
+    }
+}
+
+/// Make the row of the token table for a single token, with the columns as wide
+/// as the widths. With color the type is colored by what kind of token it is
+pub fn token_row(
+    tok: &Token,
+    widths: (usize, usize),
+    describe_token: bool,
+    zero_based: bool,
+    color: bool,
+) -> String {
+    let token_text: String = format!("{:?}", tok.token);
+    let first: String = spacer(widths.0.saturating_sub(token_text.len()), ' ');
+    let second: String = spacer(widths.1.saturating_sub(tok.part.chars().count()), ' ');
+    let token_text: String = if color {
+        format!(
+            "{}{}\x1b[0m",
+            kind_color(TokenKind::from(tok.token)),
+            token_text
+        )
+    } else {
+        token_text
+    };
+    let mut row: String = format!(
+        "{}{}{}{}{}:{}",
+        token_text,
+        first,
+        tok.part,
+        second,
+        shown_position(tok.line_num(), zero_based),
+        shown_position(tok.char_num(), zero_based)
+    );
+    if describe_token {
+        row = format!("{} - {}", row, describe(tok.token));
+    }
+    row
+}
+
+/// Make the whole token table, with the column names at the top and the columns
+/// wide enough for the longest token
+pub fn token_table(
+    tokens: &[Token],
+    describe_token: bool,
+    zero_based: bool,
+    color: bool,
+) -> String {
+    let widths: (usize, usize) = column_widths(tokens);
+    let label: String = format!(
+        "Type{}Part{}Line",
+        spacer(widths.0 - "Type".len(), ' '),
+        spacer(widths.1 - "Part".len(), ' ')
+    );
+    let rule: String = spacer(widths.0 + widths.1 + "Line".len(), '-');
+    let mut table: String = format!("{}\n{}\n", label, rule);
+    for tok in tokens {
+        table.push_str(&token_row(tok, widths, describe_token, zero_based, color));
+        table.push('\n');
+    }
+    table.push_str(&rule);
+    table.push('\n');
+    table
+}
+
+/// Count how many of each kind of token there are, like `Identifier: 2, Set: 1`,
+/// sorted by the name of the token so the output is always the same
+pub fn token_stats(tokens: &[Token]) -> String {
+    let mut counts: HashMap<Tokens, usize> = HashMap::new();
+    for tok in tokens {
+        *counts.entry(tok.token).or_insert(0) += 1;
+    }
+    let mut stats: Vec<String> = counts
+        .iter()
+        .map(|(token, count)| format!("{:?}: {}", token, count))
+        .collect();
+    stats.sort();
+    stats.join(", ")
+}
+
+/// Only lex the program and give back the tokens as JSON or as the table
+pub fn dump_tokens(
+    contents: &str,
+    json: bool,
+    describe_token: bool,
+    zero_based: bool,
+    color: bool,
+) -> Result<String, LexError> {
+    let mut lexer: Lexer = new_lexer(contents);
+    lexer.lexer()?;
+    if json {
+        Ok(tokens_json(contents, &lexer.tokens, zero_based))
+    } else {
+        Ok(token_table(
+            &lexer.tokens,
+            describe_token,
+            zero_based,
+            color,
+        ))
+    }
+}
+
+/// A JSON value, so the tree can be printed with indents
+#[derive(PartialEq, Debug, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    /// Print it with two spaces of indent for each level, empty arrays stay on one line
+    pub fn pretty(&self, indent: usize) -> String {
+        let inner: String = spacer(indent + 2, ' ');
+        let outer: String = spacer(indent, ' ');
+        match self {
+            Json::Null => "null".to_string(),
+            Json::Bool(value) => value.to_string(),
+            Json::Number(number) => number.to_string(),
+            Json::Str(text) => json_string(text),
+            Json::Array(values) if values.is_empty() => "[]".to_string(),
+            Json::Array(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|x| format!("{}{}", inner, x.pretty(indent + 2)))
+                    .collect();
+                format!("[\n{}\n{}]", values.join(",\n"), outer)
+            }
+            Json::Object(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(key, x)| {
+                        format!("{}{}: {}", inner, json_string(key), x.pretty(indent + 2))
+                    })
+                    .collect();
+                format!("{{\n{}\n{}}}", fields.join(",\n"), outer)
+            }
+        }
+    }
+}
+
+fn json_names(names: &[String]) -> Json {
+    Json::Array(names.iter().map(|x| Json::Str(x.to_owned())).collect())
+}
+
+fn json_block(stmts: &[Stmt]) -> Json {
+    Json::Array(stmts.iter().map(stmt_json).collect())
+}
+
+/// A statement as an object, with `type` saying which one it is
+pub fn stmt_json(stmt: &Stmt) -> Json {
+    let fields: Vec<(&'static str, Json)> = match stmt {
+        Stmt::Set { name, value } => vec![
+            ("type", Json::Str("Set".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("value", expr_json(value)),
+        ],
+        Stmt::Const { name, value } => vec![
+            ("type", Json::Str("Const".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("value", expr_json(value)),
+        ],
+        Stmt::SetChain { names, value } => vec![
+            ("type", Json::Str("SetChain".to_string())),
+            ("names", json_names(names)),
+            ("value", expr_json(value)),
+        ],
+        Stmt::SetMany { names, values } => vec![
+            ("type", Json::Str("SetMany".to_string())),
+            ("names", json_names(names)),
+            (
+                "values",
+                Json::Array(values.iter().map(expr_json).collect()),
+            ),
+        ],
+        Stmt::SetIndex {
+            name,
+            indexes,
+            value,
+            ..
+        } => vec![
+            ("type", Json::Str("SetIndex".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            (
+                "indexes",
+                Json::Array(indexes.iter().map(expr_json).collect()),
+            ),
+            ("value", expr_json(value)),
+        ],
+        Stmt::Print { exprs } => vec![
+            ("type", Json::Str("Print".to_string())),
+            ("exprs", Json::Array(exprs.iter().map(expr_json).collect())),
+        ],
+        Stmt::Jump { offset } => vec![
+            ("type", Json::Str("Jump".to_string())),
+            ("offset", expr_json(offset)),
+        ],
+        Stmt::AugAssign {
+            name,
+            operator,
+            value,
+        } => vec![
+            ("type", Json::Str("AugAssign".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("operator", Json::Str(operator_text(*operator).to_string())),
+            ("value", expr_json(value)),
+        ],
+        Stmt::If {
+            condition,
+            body,
+            else_body,
+        } => vec![
+            ("type", Json::Str("If".to_string())),
+            ("condition", expr_json(condition)),
+            ("body", json_block(body)),
+            (
+                "else_body",
+                else_body.as_deref().map_or(Json::Null, json_block),
+            ),
+        ],
+        Stmt::While { condition, body } => vec![
+            ("type", Json::Str("While".to_string())),
+            ("condition", expr_json(condition)),
+            ("body", json_block(body)),
+        ],
+        Stmt::Match { value, arms, .. } => vec![
+            ("type", Json::Str("Match".to_string())),
+            ("value", expr_json(value)),
+            (
+                "arms",
+                Json::Array(
+                    arms.iter()
+                        .map(|arm| {
+                            Json::Object(vec![
+                                (
+                                    "pattern",
+                                    arm.pattern.as_ref().map_or(Json::Null, expr_json),
+                                ),
+                                ("body", json_block(&arm.body)),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ],
+        Stmt::Try {
+            body,
+            name,
+            handler,
+        } => vec![
+            ("type", Json::Str("Try".to_string())),
+            ("body", json_block(body)),
+            ("name", Json::Str(name.to_owned())),
+            ("handler", json_block(handler)),
+        ],
+        Stmt::For {
+            name,
+            iterable,
+            body,
+            ..
+        } => vec![
+            ("type", Json::Str("For".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("iterable", expr_json(iterable)),
+            ("body", json_block(body)),
+        ],
+        Stmt::Func { name, params, body } => vec![
+            ("type", Json::Str("Func".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("params", json_names(params)),
+            ("body", json_block(body)),
+        ],
+        Stmt::Return { value } => vec![
+            ("type", Json::Str("Return".to_string())),
+            ("value", value.as_ref().map_or(Json::Null, expr_json)),
+        ],
+        Stmt::Break { .. } => vec![("type", Json::Str("Break".to_string()))],
+        Stmt::Continue { .. } => vec![("type", Json::Str("Continue".to_string()))],
+        Stmt::Global { names } => vec![
+            ("type", Json::Str("Global".to_string())),
+            ("names", json_names(names)),
+        ],
+        Stmt::Use { name, .. } => vec![
+            ("type", Json::Str("Use".to_string())),
+            ("name", Json::Str(name.to_owned())),
+        ],
+        Stmt::Exit { code, .. } => vec![
+            ("type", Json::Str("Exit".to_string())),
+            ("code", code.as_ref().map_or(Json::Null, expr_json)),
+        ],
+        Stmt::Assert {
+            value, expected, ..
+        } => vec![
+            ("type", Json::Str("Assert".to_string())),
+            ("value", expr_json(value)),
+            ("expected", expected.as_ref().map_or(Json::Null, expr_json)),
+        ],
+        Stmt::Expr(expr) => vec![
+            ("type", Json::Str("Expr".to_string())),
+            ("expr", expr_json(expr)),
+        ],
+        Stmt::Lines(lines) => vec![
+            ("type", Json::Str("Lines".to_string())),
+            ("lines", json_names(lines)),
+        ],
+    };
+    Json::Object(fields)
+}
+
+/// An expression as an object, the identifiers and operators have where they are
+pub fn expr_json(expr: &Expr) -> Json {
+    let position = |position: &Position| {
+        vec![
+            ("line_num", Json::Number(position.line_num)),
+            ("char_num", Json::Number(position.char_num)),
+        ]
+    };
+    let mut fields: Vec<(&'static str, Json)> = match expr {
+        Expr::Numeric(number) => vec![
+            ("type", Json::Str("Numeric".to_string())),
+            ("value", Json::Str(number.to_owned())),
+        ],
+        Expr::Str(text) => vec![
+            ("type", Json::Str("Str".to_string())),
+            ("value", Json::Str(text.to_owned())),
+        ],
+        Expr::Bool(value) => vec![
+            ("type", Json::Str("Bool".to_string())),
+            ("value", Json::Bool(*value)),
+        ],
+        Expr::Identifier(name, _) => vec![
+            ("type", Json::Str("Identifier".to_string())),
+            ("name", Json::Str(name.to_owned())),
+        ],
+        Expr::Constant(name) => vec![
+            ("type", Json::Str("Constant".to_string())),
+            ("name", Json::Str(name.to_owned())),
+        ],
+        Expr::Input => vec![("type", Json::Str("Input".to_string()))],
+        Expr::Call { name, args, .. } => vec![
+            ("type", Json::Str("Call".to_string())),
+            ("name", Json::Str(name.to_owned())),
+            ("args", Json::Array(args.iter().map(expr_json).collect())),
+        ],
+        Expr::List(items) => vec![
+            ("type", Json::Str("List".to_string())),
+            ("items", Json::Array(items.iter().map(expr_json).collect())),
+        ],
+        Expr::Format(parts) => vec![
+            ("type", Json::Str("Format".to_string())),
+            ("parts", Json::Array(parts.iter().map(expr_json).collect())),
+        ],
+        Expr::Dict(entries) => vec![
+            ("type", Json::Str("Dict".to_string())),
+            (
+                "entries",
+                Json::Array(
+                    entries
+                        .iter()
+                        .map(|(key, value)| Json::Array(vec![expr_json(key), expr_json(value)]))
+                        .collect(),
+                ),
+            ),
+        ],
+        Expr::Index { target, index, .. } => vec![
+            ("type", Json::Str("Index".to_string())),
+            ("target", expr_json(target)),
+            ("index", expr_json(index)),
+        ],
+        Expr::Range { start, end, .. } => vec![
+            ("type", Json::Str("Range".to_string())),
+            ("start", expr_json(start)),
+            ("end", expr_json(end)),
+        ],
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => vec![
+            ("type", Json::Str("Ternary".to_string())),
+            ("condition", expr_json(condition)),
+            ("then", expr_json(then)),
+            ("otherwise", expr_json(otherwise)),
+        ],
+        Expr::Unary { operator, expr } => vec![
+            ("type", Json::Str("Unary".to_string())),
+            ("operator", Json::Str(operator_text(*operator).to_string())),
+            ("expr", expr_json(expr)),
+        ],
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => vec![
+            ("type", Json::Str("Binary".to_string())),
+            ("operator", Json::Str(operator_text(*operator).to_string())),
+            ("left", expr_json(left)),
+            ("right", expr_json(right)),
+        ],
+    };
+    match expr {
+        Expr::Identifier(_, at)
+        | Expr::Index { position: at, .. }
+        | Expr::Range { position: at, .. }
+        | Expr::Binary { position: at, .. } => fields.extend(position(at)),
+        _ => {}
+    }
+    Json::Object(fields)
+}
+
+/// The whole tree as pretty printed JSON, an array with an object for each statement
+pub fn ast_json(stmts: &[Stmt]) -> String {
+    json_block(stmts).pretty(0)
+}
+
+/// An expression as an S-expression like `(+ a 1)`, numbers and names are bare
+pub fn expr_sexpr(expr: &Expr) -> String {
+    match expr {
+        Expr::Numeric(number) => number.to_owned(),
+        Expr::Str(text) => json_string(text),
+        Expr::Bool(value) => value.to_string(),
+        Expr::Identifier(name, _) => name.to_owned(),
+        Expr::Constant(name) => format!("${}", name),
+        Expr::Input => "input".to_string(),
+        Expr::Call { name, args, .. } => {
+            let mut parts: Vec<String> = vec!["call".to_string(), name.to_owned()];
+            parts.extend(args.iter().map(expr_sexpr));
+            format!("({})", parts.join(" "))
+        }
+        Expr::List(items) => {
+            let mut parts: Vec<String> = vec!["list".to_string()];
+            parts.extend(items.iter().map(expr_sexpr));
+            format!("({})", parts.join(" "))
+        }
+        Expr::Format(pieces) => {
+            let mut parts: Vec<String> = vec!["format".to_string()];
+            parts.extend(pieces.iter().map(expr_sexpr));
+            format!("({})", parts.join(" "))
+        }
+        Expr::Dict(entries) => {
+            let mut parts: Vec<String> = vec!["dict".to_string()];
+            parts.extend(
+                entries
+                    .iter()
+                    .map(|(key, value)| format!("({} {})", expr_sexpr(key), expr_sexpr(value))),
+            );
+            format!("({})", parts.join(" "))
+        }
+        Expr::Index { target, index, .. } => {
+            format!("(index {} {})", expr_sexpr(target), expr_sexpr(index))
+        }
+        Expr::Range { start, end, .. } => {
+            format!("(range {} {})", expr_sexpr(start), expr_sexpr(end))
+        }
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => format!(
+            "(? {} {} {})",
+            expr_sexpr(condition),
+            expr_sexpr(then),
+            expr_sexpr(otherwise)
+        ),
+        Expr::Unary { operator, expr } => {
+            format!("({} {})", operator_text(*operator), expr_sexpr(expr))
+        }
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => format!(
+            "({} {} {})",
+            operator_text(*operator),
+            expr_sexpr(left),
+            expr_sexpr(right)
+        ),
+    }
+}
+
+/// A statement as an S-expression, the statements in a block each go on their own
+/// line with two more spaces of indent
+pub fn stmt_sexpr(stmt: &Stmt, indent: usize) -> String {
+    let block = |name: &str, stmts: &[Stmt]| -> String {
+        let mut text: String = format!("\n{}({}", spacer(indent + 2, ' '), name);
+        for stmt in stmts {
+            text.push_str(&format!(
+                "\n{}{}",
+                spacer(indent + 4, ' '),
+                stmt_sexpr(stmt, indent + 4)
+            ));
+        }
+        text.push(')');
+        text
+    };
+    match stmt {
+        Stmt::Set { name, value } => format!("(set {} {})", name, expr_sexpr(value)),
+        Stmt::Const { name, value } => format!("(const {} {})", name, expr_sexpr(value)),
+        Stmt::SetChain { names, value } => {
+            format!("(set ({}) {})", names.join(" "), expr_sexpr(value))
+        }
+        Stmt::SetMany { names, values } => {
+            let values: Vec<String> = values.iter().map(expr_sexpr).collect();
+            format!("(set ({}) ({}))", names.join(" "), values.join(" "))
+        }
+        // `set grid[1][2] = 0;` is `(set (index (index grid 1) 2) 0)`
+        Stmt::SetIndex {
+            name,
+            indexes,
+            value,
+            ..
+        } => {
+            let target: String = indexes.iter().fold(name.to_owned(), |target, index| {
+                format!("(index {} {})", target, expr_sexpr(index))
+            });
+            format!("(set {} {})", target, expr_sexpr(value))
+        }
+        Stmt::Print { exprs } => {
+            let exprs: Vec<String> = exprs.iter().map(expr_sexpr).collect();
+            format!("(print {})", exprs.join(" "))
+        }
+        Stmt::Jump { offset } => format!("(jump {})", expr_sexpr(offset)),
+        Stmt::AugAssign {
+            name,
+            operator,
+            value,
+        } => format!(
+            "({} {} {})",
+            operator_text(*operator),
+            name,
+            expr_sexpr(value)
+        ),
+        Stmt::If {
+            condition,
+            body,
+            else_body,
+        } => {
+            let mut text: String = format!("(if {}{}", expr_sexpr(condition), block("then", body));
+            if let Some(else_body) = else_body {
+                text.push_str(&block("else", else_body));
+            }
+            text.push(')');
+            text
+        }
+        Stmt::While { condition, body } => {
+            format!("(while {}{})", expr_sexpr(condition), block("do", body))
+        }
+        // Each arm is its pattern, or `_`, with its body
+        Stmt::Match { value, arms, .. } => {
+            let mut text: String = format!("(match {}", expr_sexpr(value));
+            for arm in arms {
+                let pattern: String = arm.pattern.as_ref().map_or("_".to_string(), expr_sexpr);
+                text.push_str(&block(&pattern, &arm.body));
+            }
+            text.push(')');
+            text
+        }
+        Stmt::Try {
+            body,
+            name,
+            handler,
+        } => format!(
+            "(try{}{})",
+            block("do", body),
+            block(&format!("catch {}", name), handler)
+        ),
+        Stmt::For {
+            name,
+            iterable,
+            body,
+            ..
+        } => format!(
+            "(for {} {}{})",
+            name,
+            expr_sexpr(iterable),
+            block("do", body)
+        ),
+        Stmt::Func { name, params, body } => {
+            format!(
+                "(func {} ({}){})",
+                name,
+                params.join(" "),
+                block("do", body)
+            )
+        }
+        Stmt::Return { value: Some(value) } => format!("(return {})", expr_sexpr(value)),
+        Stmt::Return { value: None } => "(return)".to_string(),
+        Stmt::Break { .. } => "(break)".to_string(),
+        Stmt::Continue { .. } => "(continue)".to_string(),
+        Stmt::Global { names } => format!("(global {})", names.join(" ")),
+        Stmt::Use { name, .. } => format!("(use {})", name),
+        Stmt::Exit {
+            code: Some(code), ..
+        } => format!("(exit {})", expr_sexpr(code)),
+        Stmt::Exit { code: None, .. } => "(exit)".to_string(),
+        Stmt::Assert {
+            value,
+            expected: Some(expected),
+            ..
+        } => format!("(assert_eq {} {})", expr_sexpr(value), expr_sexpr(expected)),
+        Stmt::Assert {
+            value,
+            expected: None,
+            ..
+        } => format!("(assert {})", expr_sexpr(value)),
+        Stmt::Expr(expr) => expr_sexpr(expr),
+        Stmt::Lines(lines) => {
+            let lines: Vec<String> = lines.iter().map(|x| json_string(x)).collect();
+            format!("(lines {})", lines.join(" "))
+        }
+    }
+}
+
+/// The whole tree as S-expressions, one statement a line
+pub fn ast_sexpr(stmts: &[Stmt]) -> String {
+    let mut text: String = String::new();
+    for stmt in stmts {
+        text.push_str(&stmt_sexpr(stmt, 0));
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+    use crate::parser::{new_parser, Parse};
+
+    #[test]
+    fn describe_test() {
+        let mut lexer: Lexer = new_lexer("set a");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            token_row(&lexer.tokens[0], MIN_COLUMN_WIDTHS, true, false, false),
+            "Set           set       1:1 - variable declaration keyword"
+        );
+        assert_eq!(
+            token_row(&lexer.tokens[0], MIN_COLUMN_WIDTHS, false, false, false),
+            "Set           set       1:1"
+        );
+    }
+
+    #[test]
+    fn token_table_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1;");
+        lexer.lexer().unwrap();
+        assert_eq!(column_widths(&lexer.tokens), MIN_COLUMN_WIDTHS);
+
+        // A long token makes its column wider instead of running into the next one
+        let mut lexer: Lexer = new_lexer("set a_long_variable_name = 1;\nx *= 2;");
+        lexer.lexer().unwrap();
+        assert_eq!(column_widths(&lexer.tokens), (16, 22));
+        let table: String = token_table(&lexer.tokens, false, false, false);
+        assert!(table.starts_with(&format!(
+            "Type            Part                  Line\n{}\n",
+            spacer(42, '-')
+        )));
+        assert!(table.contains("\nIdentifier      a_long_variable_name  1:5\n"));
+        assert!(table.contains("\nMultiplyAssign  *=                    2:3\n"));
+
+        // The colors don't count toward the width
+        assert_eq!(
+            token_row(&lexer.tokens[0], MIN_COLUMN_WIDTHS, false, false, true),
+            "\x1b[35mSet\x1b[0m           set       1:1"
+        );
+    }
+
+    #[test]
+    fn tokens_json_test() {
+        let mut lexer: Lexer = new_lexer("set a");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            tokens_json("set a", &lexer.tokens, false),
+            concat!(
+                "[{\"kind\":\"Keyword\",\"token\":\"Set\",\"text\":\"set\",\"line\":1,\"column\":1,\"offset\":0},",
+                "{\"kind\":\"Identifier\",\"token\":\"Identifier\",\"text\":\"a\",\"line\":1,\"column\":5,\"offset\":4}]"
+            )
+        );
+        assert_eq!(tokens_json("", &[], false), "[]");
+
+        // The column starts over on each line, and the offset counts bytes
+        let contents: &str = "set é = 1;\nprint é;";
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let json: String = tokens_json(contents, &lexer.tokens, false);
+        assert!(json.contains("\"text\":\"=\",\"line\":1,\"column\":7,\"offset\":7}"));
+        assert!(json.contains("\"text\":\";\",\"line\":2,\"column\":8,\"offset\":20}"));
+        assert_eq!(json_string("a\"b\\\n"), "\"a\\\"b\\\\\\n\"");
+    }
+
+    #[test]
+    fn ast_json_test() {
+        let mut lexer: Lexer = new_lexer("set a = b + 1;\nif a {}");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            ast_json(&stmts),
+            r#"[
+  {
+    "type": "Set",
+    "name": "a",
+    "value": {
+      "type": "Binary",
+      "operator": "+",
+      "left": {
+        "type": "Identifier",
+        "name": "b",
+        "line_num": 1,
+        "char_num": 9
+      },
+      "right": {
+        "type": "Numeric",
+        "value": "1"
+      },
+      "line_num": 1,
+      "char_num": 11
+    }
+  },
+  {
+    "type": "If",
+    "condition": {
+      "type": "Identifier",
+      "name": "a",
+      "line_num": 2,
+      "char_num": 4
+    },
+    "body": [],
+    "else_body": null
+  }
+]"#
+        );
+        assert_eq!(ast_json(&[]), "[]");
+    }
+
+    #[test]
+    fn ast_sexpr_test() {
+        let mut lexer: Lexer = new_lexer(
+            "func f(a, b) {\n  if a < b { print -a; } else { a += len(\"x\"); }\n}\nset c, d = 1, $PI;",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            ast_sexpr(&stmts),
+            concat!(
+                "(func f (a b)\n",
+                "  (do\n",
+                "    (if (< a b)\n",
+                "      (then\n",
+                "        (print (- a)))\n",
+                "      (else\n",
+                "        (+= a (call len \"x\"))))))\n",
+                "(set (c d) (1 $PI))\n"
+            )
+        );
+    }
+
+    #[test]
+    fn token_stats_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1;\nset b = a + 2;\nprint a * b;");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            token_stats(&lexer.tokens),
+            "Assign: 2, Identifier: 5, Multiply: 1, Numeric: 2, Plus: 1, Print: 1, Semi: 3, Set: 2"
+        );
+        assert_eq!(token_stats(&[]), "");
+    }
+
+    #[test]
+    fn dump_tokens_test() {
+        // The missing parenthesis doesn't matter since nothing gets parsed
+        let contents: &str = "set a = (1;";
+        assert!(check(contents, None, false).is_err());
+
+        let table: String = dump_tokens(contents, false, false, false, false).unwrap();
+        assert!(table.starts_with("Type          Part      Line\n"));
+        assert!(table.contains("LeftParen     (         1:9\n"));
+        assert_eq!(table.lines().count(), 9);
+
+        let json: String = dump_tokens(contents, true, false, false, false).unwrap();
+        assert!(json.contains("\"token\":\"LeftParen\""));
+        assert_eq!(Format::from_str("json"), Ok(Format::Json));
+        assert!(Format::from_str("xml").is_err());
+
+        assert!(dump_tokens("set a = 3x;", false, false, false, false).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Running mahou from inside of a rust program as a scripting layer. An `Engine`
+//! keeps its variables and functions between each `eval`, the host can set and get
+//! variables, and give the program rust closures to call like builtins
+
+use crate::analysis::check_division_by_zero;
+use crate::ast::Stmt;
+use crate::diagnostics::Diagnostic;
+use crate::interpreter::{new_interpreter, Interpret, Interpreter, RuntimeError, Value};
+use crate::lexer::{new_lexer, Lex, Lexer};
+use crate::modules::load_modules;
+use crate::parser::{new_parser, Parse, ParseError, Parser};
+use crate::semantic::{check_constants, check_loops, Consts};
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+/// What errors from `eval` say the program is called
+const FILENAME: &str = "<eval>";
+
+/// An interpreter that stays around, so each `eval` sees what the ones before it set
+pub struct Engine<R: BufRead, W: Write> {
+    interpreter: Interpreter<R, W>,
+    /// The consts from the evals so far, which the next ones can't set either
+    consts: Consts,
+}
+
+pub fn new_engine<R: BufRead, W: Write>(input: R, output: W) -> Engine<R, W> {
+    Engine {
+        interpreter: new_interpreter(input, output),
+        consts: Consts::new(),
+    }
+}
+
+/// An engine whose input and output can be anything, and changed to something of
+/// another type with `replace_io`, like sending a REPL's output to a buffer for a while
+pub type BoxedEngine = Engine<Box<dyn BufRead>, Box<dyn Write>>;
+
+pub fn new_boxed_engine(
+    input: impl BufRead + 'static,
+    output: impl Write + 'static,
+) -> BoxedEngine {
+    new_engine(Box::new(input), Box::new(output))
+}
+
+impl<R: BufRead, W: Write> Engine<R, W> {
+    /// Set a global variable, like `engine.set_var("x", 3)`
+    pub fn set_var(&mut self, name: &str, value: impl Into<Value>) {
+        self.interpreter.set_global(name, value.into());
+    }
+    /// The global variable, if the host or the program has set it
+    pub fn get_var(&self, name: &str) -> Option<Value> {
+        self.interpreter.global(name).cloned()
+    }
+    /// Let the program call the closure by the name, with the values it was given.
+    /// A function from the program with the same name comes first
+    pub fn register(
+        &mut self,
+        name: &str,
+        function: impl Fn(&[Value]) -> Result<Value, String> + 'static,
+    ) {
+        self.interpreter
+            .natives
+            .insert(name.to_string(), Rc::new(function));
+    }
+    /// Where `print` is writing, for reading what the program printed
+    pub fn output(&self) -> &W {
+        self.interpreter.output()
+    }
+    /// Read and print somewhere else from now on, giving back the old input and output
+    pub fn replace_io(&mut self, input: R, output: W) -> (R, W) {
+        self.interpreter.replace_io(input, output)
+    }
+    /// Run some of the program. If it ends in an expression its value is given
+    /// back, otherwise it's none. An error is already rendered for showing
+    pub fn eval(&mut self, source: &str) -> Result<Value, String> {
+        let mut consts: Consts = self.consts.clone();
+        let mut stmts: Vec<Stmt> = parse(source, &mut consts)?;
+        self.consts = consts;
+        let last: Option<Stmt> = match stmts.last() {
+            Some(Stmt::Expr(_)) => stmts.pop(),
+            _ => None,
+        };
+        let ran: Result<Value, RuntimeError> =
+            self.interpreter.run(&stmts).and_then(|()| match &last {
+                Some(Stmt::Expr(expr)) => self.interpreter.eval(expr),
+                _ => Ok(Value::None),
+            });
+        match ran {
+            Ok(value) => Ok(value),
+            Err(RuntimeError { exit: Some(0), .. }) => Ok(Value::None),
+            Err(RuntimeError {
+                exit: Some(code), ..
+            }) => Err(format!("the program exited with code {}", code)),
+            Err(error) => Err(error.render(source, Some(FILENAME), false)),
+        }
+    }
+}
+
+/// Lex, check and parse like for a file, besides checking for variables that
+/// aren't defined since the host could have set them. The consts are added to
+fn parse(source: &str, consts: &mut Consts) -> Result<Vec<Stmt>, String> {
+    let mut lexer: Lexer = new_lexer(source);
+    if let Err(error) = lexer.lexer() {
+        return Err(Diagnostic::from(&error).render(source, Some(FILENAME), false));
+    }
+    let render = |error: &ParseError| -> String {
+        Diagnostic::from(error).render(source, Some(FILENAME), false)
+    };
+    let mut parser: Parser = new_parser(&lexer.tokens);
+    let stmts: Vec<Stmt> = parser
+        .parse_all()
+        .map_err(|errors| errors.iter().map(render).collect::<String>())?;
+    check_constants(&stmts, &lexer.tokens, consts).map_err(|error| render(&error))?;
+    check_division_by_zero(&stmts).map_err(|error| render(&error))?;
+    check_loops(&stmts).map_err(|error| render(&error))?;
+    load_modules(stmts, source, Some(FILENAME), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::io;
+
+    #[test]
+    fn engine_test() {
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut engine: Engine<io::Empty, &mut Vec<u8>> = new_engine(io::empty(), &mut output);
+            engine.set_var("x", 3);
+            assert_eq!(engine.eval("print x * 2;"), Ok(Value::None));
+            assert_eq!(engine.eval("set y = x + 1;\ny * 10"), Ok(Value::Int(40)));
+            assert_eq!(engine.get_var("y"), Some(Value::Int(4)));
+            assert_eq!(engine.get_var("z"), None);
+
+            // Functions stay around for the next eval
+            engine
+                .eval("func twice(n) {\n    return n * 2;\n}\n")
+                .unwrap();
+            assert_eq!(engine.eval("twice(y)"), Ok(Value::Int(8)));
+            assert!(engine.eval("print 1 +;").is_err());
+            assert!(engine
+                .eval("print x / \"a\";")
+                .unwrap_err()
+                .contains("<eval>:1"));
+            assert_eq!(engine.eval("exit;"), Ok(Value::None));
+
+            // A const stays one in the evals after it
+            engine.eval("const LIMIT = 3;").unwrap();
+            assert!(engine
+                .eval("set LIMIT = 4;")
+                .unwrap_err()
+                .contains("can't set 'LIMIT' again, it's a const from line 1:7"));
+            assert_eq!(engine.get_var("LIMIT"), Some(Value::Int(3)));
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "6\n");
+    }
+
+    #[test]
+    fn io_test() {
+        let mut engine: Engine<&[u8], Vec<u8>> = new_engine(&b"bo\n"[..], Vec::new());
+        engine.eval("print \"hi \" + input(\"name? \");").unwrap();
+        assert_eq!(engine.output(), b"name? hi bo\n");
+        let (_, output): (&[u8], Vec<u8>) = engine.replace_io(&b""[..], Vec::new());
+        assert_eq!(output, b"name? hi bo\n");
+        engine.eval("print 1;").unwrap();
+        assert_eq!(engine.output(), b"1\n");
+
+        let printed: Rc<RefCell<Vec<u8>>> = Rc::new(RefCell::new(Vec::new()));
+        let mut engine: BoxedEngine = new_boxed_engine(io::empty(), Shared(printed.clone()));
+        engine.eval("print 2;").unwrap();
+        engine.replace_io(Box::new(&b"3\n"[..]), Box::new(io::sink()));
+        engine.eval("print input();").unwrap();
+        assert_eq!(*printed.borrow(), b"2\n");
+    }
+
+    /// Output that the test can still read after it's boxed
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(bytes)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn register_test() {
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut engine: Engine<io::Empty, &mut Vec<u8>> = new_engine(io::empty(), &mut output);
+            engine.register("greet", |args: &[Value]| match args {
+                [name] => Ok(Value::from(format!("hello {}", name))),
+                _ => Err(format!("greet takes 1 argument, got {}", args.len())),
+            });
+            engine.eval("print greet(\"mahou\");").unwrap();
+            assert!(engine
+                .eval("greet();")
+                .unwrap_err()
+                .contains("greet takes 1 argument, got 0"));
+            // The program's own function comes first
+            engine
+                .eval("func greet(n) {\n    return n;\n}\nprint greet(1);\n")
+                .unwrap();
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "hello mahou\n1\n");
+    }
+}
@@ -0,0 +1,148 @@
+//! The one error type for everything mahou can fail at, and catching the panics
+//! that would otherwise show a rust backtrace. A panic is always a bug in mahou,
+//! never something wrong with the program, so it gets said that way instead
+
+use std::any::Any;
+use std::fmt;
+use std::io;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum MahouError {
+    /// Something wrong with the program, already rendered with where it is
+    Diagnostic(String),
+    /// A file that couldn't be read or written, with which one it was doing
+    Io {
+        action: String,
+        path: String,
+        message: String,
+    },
+    /// A thread that couldn't be started, with why
+    Thread(String),
+    /// A bug in mahou, with what it said when it panicked
+    Panic(String),
+}
+
+impl MahouError {
+    /// The action is what it was doing to the file, like "read" or "write"
+    pub fn io(action: &str, path: &str, error: &io::Error) -> MahouError {
+        MahouError::Io {
+            action: action.to_string(),
+            path: path.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The others end in a newline, the way rendered diagnostics already do
+impl fmt::Display for MahouError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MahouError::Diagnostic(rendered) => write!(f, "{}", rendered),
+            MahouError::Io {
+                action,
+                path,
+                message,
+            } => writeln!(f, "error: couldn't {} {}: {}", action, path, message),
+            MahouError::Thread(message) => writeln!(f, "error: {}", message),
+            MahouError::Panic(message) => {
+                writeln!(
+                    f,
+                    "error: mahou crashed, which is a bug in mahou and not the program: {}",
+                    message
+                )?;
+                writeln!(
+                    f,
+                    "  = help: please report it along with the program that caused it"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MahouError {}
+
+/// The errors the library gives back are already rendered
+impl From<String> for MahouError {
+    fn from(rendered: String) -> Self {
+        MahouError::Diagnostic(rendered)
+    }
+}
+
+/// What a panic said, which is a `&str` or a `String` for the ones from `panic!`
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "it panicked".to_string(),
+    }
+}
+
+/// Run f, turning a panic in it into an error instead of unwinding out, like
+/// across the C functions where that can't happen
+pub fn guard<T>(f: impl FnOnce() -> Result<T, MahouError>) -> Result<T, MahouError> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(MahouError::Panic(panic_message(payload.as_ref()))))
+}
+
+/// Call the handler with the error whenever anything panics from now on, in place
+/// of rust printing the panic and its backtrace. Setting another one replaces it
+pub fn set_panic_handler(handler: impl Fn(&MahouError) + Send + Sync + 'static) {
+    panic::set_hook(Box::new(move |info: &PanicHookInfo| {
+        let message: String = match info.location() {
+            Some(place) => format!(
+                "{} at {}:{}",
+                panic_message(info.payload()),
+                place.file(),
+                place.line()
+            ),
+            None => panic_message(info.payload()),
+        };
+        handler(&MahouError::Panic(message));
+    }));
+}
+
+/// The handler the CLI uses, printing the error to stderr like any other one
+pub fn install_panic_handler() {
+    set_panic_handler(|error: &MahouError| eprint!("{}", error));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_test() {
+        assert_eq!(guard(|| Ok(1)), Ok(1));
+        assert_eq!(
+            guard::<()>(|| Err(MahouError::from("error: bad\n".to_string()))),
+            Err(MahouError::Diagnostic("error: bad\n".to_string()))
+        );
+        // Quiet so the test output doesn't get the panic, then back to rust's own
+        set_panic_handler(|_: &MahouError| {});
+        let caught: Result<(), MahouError> = guard(|| panic!("index {} is too far", 3));
+        let _ = panic::take_hook();
+        assert_eq!(
+            caught,
+            Err(MahouError::Panic("index 3 is too far".to_string()))
+        );
+        assert!(caught
+            .unwrap_err()
+            .to_string()
+            .starts_with("error: mahou crashed, which is a bug in mahou"));
+
+        let missing: io::Error = io::Error::new(io::ErrorKind::NotFound, "not found");
+        assert_eq!(
+            MahouError::io("read", "a.m", &missing).to_string(),
+            "error: couldn't read a.m: not found\n"
+        );
+        let denied: io::Error = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(
+            MahouError::io("write", "a.py", &denied).to_string(),
+            "error: couldn't write a.py: denied\n"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Functions for embedding mahou from C or anything else that can call C, built
+//! into the cdylib with `--features mahou-ffi`. `ffi/mahou.h` is the header for
+//! them. Strings given back are made by mahou and have to go back to
+//! `mahou_free_string`, and a function that failed leaves its error for
+//! `mahou_last_error`. A bug that would panic is given back as an error too, since
+//! it can't unwind into C
+
+use crate::embed::{new_engine, Engine};
+use crate::error::{guard, MahouError};
+use crate::interpreter::Value;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::io::{self, BufRead, Read, Stdout};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+/// An engine reading from stdin and printing to stdout, which C only sees as a pointer
+pub type MahouEngine = Engine<SharedStdin, Stdout>;
+
+/// Stdin read a line at a time, only locking it while a line is being read. An
+/// engine holding the lock itself would hang the next one made, and `mahou_eval`
+#[derive(Default)]
+pub struct SharedStdin {
+    line: Vec<u8>,
+    read: usize,
+}
+
+impl Read for SharedStdin {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let available: &[u8] = self.fill_buf()?;
+        let count: usize = available.len().min(buffer.len());
+        buffer[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+impl BufRead for SharedStdin {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.read == self.line.len() {
+            self.line.clear();
+            self.read = 0;
+            io::stdin().lock().read_until(b'\n', &mut self.line)?;
+        }
+        Ok(&self.line[self.read..])
+    }
+    fn consume(&mut self, amount: usize) {
+        self.read = (self.read + amount).min(self.line.len());
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(error: String) {
+    LAST_ERROR.with(|x| *x.borrow_mut() = Some(c_string(error)));
+}
+
+/// A C string can't have a nul inside of it, so any are left out
+fn c_string(text: String) -> CString {
+    CString::new(text.replace('\0', "")).unwrap_or_default()
+}
+
+/// The text of a string from C, or an error if it's null or isn't utf-8
+unsafe fn text<'a>(pointer: *const c_char) -> Result<&'a str, String> {
+    if pointer.is_null() {
+        return Err("got a null string".to_string());
+    }
+    CStr::from_ptr(pointer)
+        .to_str()
+        .map_err(|_| "got a string that isn't utf-8".to_string())
+}
+
+/// Run a whole program once with stdin and stdout, like `mahou --eval`. Gives
+/// back the code it exited with, or -1 if it stopped with an error
+///
+/// # Safety
+/// The source has to be a string ending in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_eval(source: *const c_char) -> c_int {
+    let ran: Result<i32, MahouError> = guard(|| {
+        let source: &str = text(source)?;
+        crate::eval(source, None, SharedStdin::default(), io::stdout())
+    });
+    ran.unwrap_or_else(|error| {
+        fail(error.to_string());
+        -1
+    })
+}
+
+/// A new engine, which has to go to `mahou_free_engine` once it's done
+#[no_mangle]
+pub extern "C" fn mahou_new_engine() -> *mut MahouEngine {
+    Box::into_raw(Box::new(new_engine(SharedStdin::default(), io::stdout())))
+}
+
+/// # Safety
+/// The engine has to be from `mahou_new_engine` and not freed yet, or null
+#[no_mangle]
+pub unsafe extern "C" fn mahou_free_engine(engine: *mut MahouEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Run some of the program on the engine, giving back the value of the expression
+/// at the end the way it would print, or null if it stopped with an error
+///
+/// # Safety
+/// The engine has to be from `mahou_new_engine` and the source has to end in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_engine_eval(
+    engine: *mut MahouEngine,
+    source: *const c_char,
+) -> *mut c_char {
+    let engine: &mut MahouEngine = match engine.as_mut() {
+        Some(engine) => engine,
+        None => {
+            fail("got a null engine".to_string());
+            return ptr::null_mut();
+        }
+    };
+    match guard(|| Ok(engine.eval(text(source)?)?)) {
+        Ok(value) => c_string(value.to_string()).into_raw(),
+        Err(error) => {
+            fail(error.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Set the variable on the engine, giving back 0 or -1 if it couldn't
+unsafe fn set(engine: *mut MahouEngine, name: *const c_char, value: Value) -> c_int {
+    match (engine.as_mut(), text(name)) {
+        (Some(engine), Ok(name)) => {
+            engine.set_var(name, value);
+            0
+        }
+        (None, _) => {
+            fail("got a null engine".to_string());
+            -1
+        }
+        (_, Err(error)) => {
+            fail(error);
+            -1
+        }
+    }
+}
+
+/// # Safety
+/// The engine has to be from `mahou_new_engine` and the name has to end in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_engine_set_int(
+    engine: *mut MahouEngine,
+    name: *const c_char,
+    value: i64,
+) -> c_int {
+    set(engine, name, Value::Int(value))
+}
+
+/// # Safety
+/// The engine has to be from `mahou_new_engine` and the name has to end in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_engine_set_float(
+    engine: *mut MahouEngine,
+    name: *const c_char,
+    value: f64,
+) -> c_int {
+    set(engine, name, Value::Float(value))
+}
+
+/// # Safety
+/// The engine has to be from `mahou_new_engine`, and the name and value have to
+/// end in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_engine_set_string(
+    engine: *mut MahouEngine,
+    name: *const c_char,
+    value: *const c_char,
+) -> c_int {
+    match text(value) {
+        Ok(value) => set(engine, name, Value::from(value)),
+        Err(error) => {
+            fail(error);
+            -1
+        }
+    }
+}
+
+/// The variable the way it would print, or null if it isn't set
+///
+/// # Safety
+/// The engine has to be from `mahou_new_engine` and the name has to end in a nul
+#[no_mangle]
+pub unsafe extern "C" fn mahou_engine_get(
+    engine: *mut MahouEngine,
+    name: *const c_char,
+) -> *mut c_char {
+    match (engine.as_ref(), text(name)) {
+        (Some(engine), Ok(name)) => match engine.get_var(name) {
+            Some(value) => c_string(value.to_string()).into_raw(),
+            None => {
+                fail(format!("variable '{}' is not defined", name));
+                ptr::null_mut()
+            }
+        },
+        (None, _) => {
+            fail("got a null engine".to_string());
+            ptr::null_mut()
+        }
+        (_, Err(error)) => {
+            fail(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// The error from the last function that failed on this thread, or null. It stays
+/// around until another one fails
+#[no_mangle]
+pub extern "C" fn mahou_last_error() -> *const c_char {
+    LAST_ERROR.with(|x| x.borrow().as_ref().map_or(ptr::null(), |x| x.as_ptr()))
+}
+
+/// # Safety
+/// The string has to be one given back by mahou and not freed yet, or null
+#[no_mangle]
+pub unsafe extern "C" fn mahou_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take the string given back, the same way C would have to
+    unsafe fn take(text: *mut c_char) -> Option<String> {
+        if text.is_null() {
+            return None;
+        }
+        let owned: String = CStr::from_ptr(text).to_str().unwrap().to_string();
+        mahou_free_string(text);
+        Some(owned)
+    }
+
+    #[test]
+    fn engine_test() {
+        let name: CString = CString::new("x").unwrap();
+        unsafe {
+            let engine: *mut MahouEngine = mahou_new_engine();
+            assert_eq!(mahou_engine_set_int(engine, name.as_ptr(), 4), 0);
+            let source: CString = CString::new("set y = x * 2.5;\n[x, y]").unwrap();
+            assert_eq!(
+                take(mahou_engine_eval(engine, source.as_ptr())),
+                Some("[4, 10.0]".to_string())
+            );
+            let y: CString = CString::new("y").unwrap();
+            assert_eq!(
+                take(mahou_engine_get(engine, y.as_ptr())),
+                Some("10.0".to_string())
+            );
+
+            let source: CString = CString::new("x / \"a\"").unwrap();
+            assert_eq!(take(mahou_engine_eval(engine, source.as_ptr())), None);
+            let error: &str = CStr::from_ptr(mahou_last_error()).to_str().unwrap();
+            assert!(error.contains("<eval>:1"));
+            assert_eq!(mahou_engine_set_int(ptr::null_mut(), name.as_ptr(), 1), -1);
+            mahou_free_engine(engine);
+        }
+    }
+
+    #[test]
+    fn two_engines_test() {
+        // Neither keeps stdin locked, which used to hang here
+        unsafe {
+            let first: *mut MahouEngine = mahou_new_engine();
+            let second: *mut MahouEngine = mahou_new_engine();
+            let source: CString = CString::new("1 + 1").unwrap();
+            assert_eq!(
+                take(mahou_engine_eval(first, source.as_ptr())),
+                Some("2".to_string())
+            );
+            assert_eq!(
+                take(mahou_engine_eval(second, source.as_ptr())),
+                Some("2".to_string())
+            );
+            let program: CString = CString::new("set a = 1;").unwrap();
+            assert_eq!(mahou_eval(program.as_ptr()), 0);
+            mahou_free_engine(first);
+            mahou_free_engine(second);
+        }
+    }
+
+    #[test]
+    fn header_test() {
+        // Every function here needs to be in the header for C to see it
+        let header: &str = include_str!("../ffi/mahou.h");
+        for line in include_str!("ffi.rs").lines() {
+            if let Some(rest) = line.split("extern \"C\" fn ").nth(1) {
+                let name: &str = rest.split('(').next().unwrap();
+                assert!(
+                    header.contains(&format!(" {}(", name))
+                        || header.contains(&format!("*{}(", name)),
+                    "{} isn't in ffi/mahou.h",
+                    name
+                );
+            }
+        }
+    }
+}
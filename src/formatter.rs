@@ -0,0 +1,596 @@
+//! Prints mahou source back out with consistent spacing
+
+use crate::ast::{Expr, Stmt};
+use crate::dump::json_string;
+use crate::lexer::{shebang, Token, Tokens};
+use crate::parser::{
+    is_right_associative, operator_text, precedence, NOT_PRECEDENCE, POWER_PRECEDENCE,
+};
+use crate::spacer;
+use std::collections::HashSet;
+
+/// Check if a token can be the end of a value, so a minus after it is subtraction
+pub fn ends_value(token: Tokens) -> bool {
+    matches!(
+        token,
+        Tokens::Numeric
+            | Tokens::Float
+            | Tokens::Bool
+            | Tokens::Str
+            | Tokens::Format
+            | Tokens::Identifier
+            | Tokens::Input
+            | Tokens::RightParen
+            | Tokens::RightBracket
+    )
+}
+
+/// Find the braces that are part of a dict instead of a block, by where they are.
+/// A block brace comes right after a value like `if a {`, after else, or after a
+/// dict like `if m == {} {`, and a dict brace comes anywhere else
+pub fn dict_braces(tokens: &[Token]) -> HashSet<usize> {
+    let mut dicts: HashSet<usize> = HashSet::new();
+    // Whether each brace that's still open is a dict
+    let mut open: Vec<bool> = Vec::new();
+    for (index, tok) in tokens.iter().enumerate() {
+        let is_dict: bool = match tok.token {
+            Tokens::LeftBrace => {
+                let after_block: bool = index > 0 && {
+                    let prev: &Token = &tokens[index - 1];
+                    matches!(prev.token, Tokens::Else | Tokens::Try | Tokens::Arrow)
+                        || ends_value(prev.token)
+                        || (prev.token == Tokens::RightBrace && dicts.contains(&(index - 1)))
+                };
+                open.push(!after_block);
+                !after_block
+            }
+            Tokens::RightBrace => open.pop().unwrap_or(false),
+            _ => false,
+        };
+        if is_dict {
+            dicts.insert(index);
+        }
+    }
+    dicts
+}
+
+/// Print the tokens back out as mahou with one space around operators, a newline
+/// after each statement and two spaces of indent in blocks. The lexer drops comments,
+/// so they don't make it into the formatted source
+pub fn format_source(tokens: &[Token]) -> String {
+    let mut source: String = String::new();
+    let mut indent: usize = 0;
+    let mut line_start: bool = true;
+    // Negation and `$` stick to what comes after them, like `-a` and `$PI`
+    let mut sticky: bool = false;
+    let mut prev: Option<Tokens> = None;
+    // A dict stays on the line it's on, like `{"a": 1}`
+    let dicts: HashSet<usize> = dict_braces(tokens);
+    let mut prev_dict: bool = false;
+    // How many `?` are waiting for their `:` inside each bracket that's open, a `:`
+    // that goes with one gets spaces like `a ? b : c` instead of sticking like a dict's
+    let mut questions: Vec<usize> = vec![0];
+
+    for (index, tok) in tokens.iter().enumerate() {
+        let is_dict: bool = dicts.contains(&index);
+        let ternary_colon: bool =
+            tok.token == Tokens::Colon && questions.last().is_some_and(|x| *x > 0);
+        // The comma after a match arm is optional, so it's left out
+        if tok.token == Tokens::Comma
+            && (prev == Some(Tokens::Semi) || (prev == Some(Tokens::RightBrace) && !prev_dict))
+        {
+            continue;
+        }
+        // Keep else and catch on the same line as the brace before it, like `} else {`
+        if matches!(tok.token, Tokens::Else | Tokens::Catch) && prev == Some(Tokens::RightBrace) {
+            source.pop();
+            line_start = false;
+        }
+        if tok.token == Tokens::RightBrace && !is_dict {
+            indent = indent.saturating_sub(1);
+            if !line_start {
+                source.push('\n');
+                line_start = true;
+            }
+        }
+        // Nothing goes between a function name and its parenthesis, like `len(a)`,
+        // or between a value and its index, like `xs[0]`, or around a range like `0..10`
+        // Two minuses next to each other would be `--`, so they keep a space
+        let tight: bool = (sticky && !(tok.token == Tokens::Minus && prev == Some(Tokens::Minus)))
+            || matches!(
+                tok.token,
+                Tokens::Semi
+                    | Tokens::Comma
+                    | Tokens::Range
+                    | Tokens::Increment
+                    | Tokens::Decrement
+                    | Tokens::RightParen
+                    | Tokens::RightBracket
+            )
+            || matches!(
+                prev,
+                Some(Tokens::LeftParen | Tokens::LeftBracket | Tokens::Range)
+            )
+            || (tok.token == Tokens::Colon && !ternary_colon)
+            || (is_dict && tok.token == Tokens::RightBrace)
+            || (prev_dict && prev == Some(Tokens::LeftBrace))
+            || (tok.token == Tokens::LeftParen
+                && matches!(prev, Some(Tokens::Identifier | Tokens::Input)))
+            || (tok.token == Tokens::LeftBracket && matches!(prev, Some(x) if ends_value(x)));
+        if line_start {
+            source.push_str(&spacer(indent * 2, ' '));
+        } else if !tight {
+            source.push(' ');
+        }
+
+        // A brace in a string without holes has to be doubled again, or it would be one
+        if tok.token == Tokens::Str {
+            let text: String = json_string(&tok.part);
+            source.push_str(&text.replace('{', "{{").replace('}', "}}"));
+        } else if tok.token == Tokens::Format {
+            source.push_str(&format!("\"{}\"", tok.part));
+        } else {
+            source.push_str(&tok.part);
+        }
+
+        // A dict's closing brace ends a value too, like `{} - 1`
+        let after_value: bool = matches!(prev, Some(x) if ends_value(x))
+            || (prev_dict && prev == Some(Tokens::RightBrace));
+        sticky = tok.token == Tokens::Var || (tok.token == Tokens::Minus && !after_value);
+        line_start = !is_dict
+            && matches!(
+                tok.token,
+                Tokens::Semi | Tokens::LeftBrace | Tokens::RightBrace
+            );
+        if line_start {
+            source.push('\n');
+        }
+        if tok.token == Tokens::LeftBrace && !is_dict {
+            indent += 1;
+        }
+        match tok.token {
+            Tokens::LeftParen | Tokens::LeftBracket | Tokens::LeftBrace => questions.push(0),
+            Tokens::RightParen | Tokens::RightBracket | Tokens::RightBrace
+                if questions.len() > 1 =>
+            {
+                questions.pop();
+            }
+            Tokens::Question => *questions.last_mut().unwrap() += 1,
+            Tokens::Colon if ternary_colon => *questions.last_mut().unwrap() -= 1,
+            _ => {}
+        }
+        prev = Some(tok.token);
+        prev_dict = is_dict;
+    }
+    // The last statement might not have a semicolon to end the line
+    if !line_start {
+        source.push('\n');
+    }
+    source
+}
+
+/// Put the `#!` line a script starts with back on top of the formatted source. It's
+/// a comment to the lexer, so it would be dropped like the rest of them
+pub fn keep_shebang(contents: &str, formatted: String) -> String {
+    match shebang(contents) {
+        Some(line) => format!("{}\n{}", line, formatted),
+        None => formatted,
+    }
+}
+
+/// A string the way it's written in mahou, with only the escapes the lexer knows
+fn quote(text: &str) -> String {
+    let mut quoted: String = String::new();
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '{' => quoted.push_str("{{"),
+            '}' => quoted.push_str("}}"),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted
+}
+
+/// How tight an expression holds together, anything below what's around it needs
+/// parentheses. Values and indexes can go anywhere
+fn binding(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Binary { operator, .. } => precedence(*operator).unwrap_or(0),
+        Expr::Range { .. } => precedence(Tokens::Range).unwrap_or(0),
+        Expr::Unary {
+            operator: Tokens::Not,
+            ..
+        } => NOT_PRECEDENCE,
+        Expr::Unary { .. } => POWER_PRECEDENCE,
+        Expr::Ternary { .. } => 0,
+        _ => u8::MAX,
+    }
+}
+
+/// One side of an operator that binds as tight as prec. A `not` or a `-` keeps
+/// going until an operator looser than what it goes around, so it needs parentheses
+/// under anything at least that tight, and on the right a `-` would run into a
+/// minus before it like `a - -b`
+fn operand(expr: &Expr, prec: u8, right: bool) -> String {
+    let text: String = expr_source(expr);
+    let wrap: bool = match expr {
+        Expr::Unary { operator, .. } => {
+            prec >= binding(expr) || (right && *operator == Tokens::Minus)
+        }
+        _ => binding(expr) < prec,
+    };
+    if wrap {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+/// An expression as mahou source, with parentheses only where it would be read
+/// differently without them
+pub fn expr_source(expr: &Expr) -> String {
+    let list = |items: &[Expr]| -> String {
+        items
+            .iter()
+            .map(expr_source)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    match expr {
+        Expr::Numeric(number) => number.to_owned(),
+        Expr::Str(text) => format!("\"{}\"", quote(text)),
+        Expr::Bool(value) => value.to_string(),
+        Expr::Identifier(name, _) => name.to_owned(),
+        Expr::Constant(name) => format!("${}", name),
+        Expr::Input => "input".to_string(),
+        Expr::Call { name, args, .. } => format!("{}({})", name, list(args)),
+        Expr::List(items) => format!("[{}]", list(items)),
+        Expr::Format(parts) => {
+            let inside: String = parts
+                .iter()
+                .map(|part| match part {
+                    Expr::Str(text) => quote(text),
+                    _ => format!("{{{}}}", expr_source(part)),
+                })
+                .collect();
+            format!("\"{}\"", inside)
+        }
+        Expr::Dict(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(key, value)| format!("{}: {}", expr_source(key), expr_source(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Expr::Index { target, index, .. } => {
+            format!(
+                "{}[{}]",
+                operand(target, u8::MAX, false),
+                expr_source(index)
+            )
+        }
+        Expr::Range { start, end, .. } => {
+            let prec: u8 = binding(expr);
+            format!(
+                "{}..{}",
+                operand(start, prec, false),
+                operand(end, prec + 1, true)
+            )
+        }
+        // `-(-a)` keeps the two minuses from being read as `--`
+        Expr::Unary { operator, expr } => {
+            let inner: String = expr_source(expr);
+            let wrap: bool = match operator {
+                Tokens::Not => binding(expr) < NOT_PRECEDENCE,
+                _ => matches!(**expr, Expr::Unary { .. }) || binding(expr) < POWER_PRECEDENCE,
+            };
+            let inner: String = if wrap { format!("({})", inner) } else { inner };
+            match operator {
+                Tokens::Not => format!("not {}", inner),
+                _ => format!("-{}", inner),
+            }
+        }
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => {
+            let prec: u8 = binding(expr);
+            let (left_prec, right_prec): (u8, u8) = if is_right_associative(*operator) {
+                (prec + 1, prec)
+            } else {
+                (prec, prec + 1)
+            };
+            format!(
+                "{} {} {}",
+                operand(left, left_prec, false),
+                operator_text(*operator),
+                operand(right, right_prec, true)
+            )
+        }
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => format!(
+            "{} ? {} : {}",
+            operand(condition, 1, false),
+            operand(then, 1, false),
+            expr_source(otherwise)
+        ),
+    }
+}
+
+/// A statement as mahou source, blocks have two more spaces of indent for each
+/// one they're inside of like format_source does
+pub fn stmt_source(stmt: &Stmt, indent: usize) -> String {
+    let spaces: String = spacer(indent * 2, ' ');
+    let block = |stmts: &[Stmt]| -> String {
+        let mut text: String = String::from("{\n");
+        for stmt in stmts {
+            text.push_str(&stmt_source(stmt, indent + 1));
+        }
+        text.push_str(&spaces);
+        text.push('}');
+        text
+    };
+    let list = |items: &[Expr]| -> String {
+        items
+            .iter()
+            .map(expr_source)
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let text: String = match stmt {
+        Stmt::Set { name, value } => format!("set {} = {};", name, expr_source(value)),
+        Stmt::Const { name, value } => format!("const {} = {};", name, expr_source(value)),
+        Stmt::SetChain { names, value } => {
+            format!("set {} = {};", names.join(" = "), expr_source(value))
+        }
+        Stmt::SetMany { names, values } => {
+            format!("set {} = {};", names.join(", "), list(values))
+        }
+        Stmt::SetIndex {
+            name,
+            indexes,
+            value,
+            ..
+        } => {
+            let indexes: String = indexes
+                .iter()
+                .map(|index| format!("[{}]", expr_source(index)))
+                .collect();
+            format!("set {}{} = {};", name, indexes, expr_source(value))
+        }
+        Stmt::Print { exprs } => {
+            let exprs: Vec<String> = exprs.iter().map(expr_source).collect();
+            format!("print {};", exprs.join(", "))
+        }
+        Stmt::Jump { offset } => format!("jump {};", expr_source(offset)),
+        Stmt::AugAssign {
+            name,
+            operator,
+            value,
+        } => format!(
+            "{} {} {};",
+            name,
+            operator_text(*operator),
+            expr_source(value)
+        ),
+        Stmt::If {
+            condition,
+            body,
+            else_body,
+        } => {
+            let mut text: String = format!("if {} {}", expr_source(condition), block(body));
+            match else_body.as_deref() {
+                // An if that's all there is in the else is written as `else if`
+                Some([else_if @ Stmt::If { .. }]) => {
+                    text.push_str(" else ");
+                    text.push_str(stmt_source(else_if, indent).trim());
+                }
+                Some(else_body) => {
+                    text.push_str(" else ");
+                    text.push_str(&block(else_body));
+                }
+                None => {}
+            }
+            text
+        }
+        Stmt::While { condition, body } => {
+            format!("while {} {}", expr_source(condition), block(body))
+        }
+        Stmt::For {
+            name,
+            iterable,
+            body,
+            ..
+        } => format!("for {} in {} {}", name, expr_source(iterable), block(body)),
+        Stmt::Try {
+            body,
+            name,
+            handler,
+        } => format!("try {} catch {} {}", block(body), name, block(handler)),
+        Stmt::Func { name, params, body } => {
+            format!("func {}({}) {}", name, params.join(", "), block(body))
+        }
+        Stmt::Match { value, arms, .. } => {
+            let mut text: String = format!("match {} {{\n", expr_source(value));
+            for arm in arms {
+                let pattern: String = arm.pattern.as_ref().map_or("_".to_string(), expr_source);
+                text.push_str(&format!("{}  {} => ", spaces, pattern));
+                // An arm with one statement doesn't need braces around it
+                match arm.body.as_slice() {
+                    [stmt] => text.push_str(stmt_source(stmt, indent + 1).trim_start()),
+                    body => {
+                        text.push_str("{\n");
+                        for stmt in body {
+                            text.push_str(&stmt_source(stmt, indent + 2));
+                        }
+                        text.push_str(&format!("{}  }}\n", spaces));
+                    }
+                }
+            }
+            text.push_str(&spaces);
+            text.push('}');
+            text
+        }
+        Stmt::Return { value: Some(value) } => format!("return {};", expr_source(value)),
+        Stmt::Return { value: None } => "return;".to_string(),
+        Stmt::Break { .. } => "break;".to_string(),
+        Stmt::Continue { .. } => "continue;".to_string(),
+        Stmt::Global { names } => format!("global {};", names.join(", ")),
+        Stmt::Use { name, .. } => format!("use {};", name),
+        Stmt::Exit {
+            code: Some(code), ..
+        } => format!("exit {};", expr_source(code)),
+        Stmt::Exit { code: None, .. } => "exit;".to_string(),
+        Stmt::Assert {
+            value,
+            expected: Some(expected),
+            ..
+        } => format!(
+            "assert_eq {}, {};",
+            expr_source(value),
+            expr_source(expected)
+        ),
+        Stmt::Assert {
+            value,
+            expected: None,
+            ..
+        } => format!("assert {};", expr_source(value)),
+        Stmt::Expr(expr) => format!("{};", expr_source(expr)),
+        // These are already the output language, so they can only go in as comments
+        Stmt::Lines(lines) => lines
+            .iter()
+            .map(|line| format!("# {}", line))
+            .collect::<Vec<String>>()
+            .join(&format!("\n{}", spaces)),
+    };
+    format!("{}{}\n", spaces, text)
+}
+
+/// The whole tree as mahou source, so `ast_source` of what the parser gives back
+/// is the same program
+pub fn ast_source(stmts: &[Stmt]) -> String {
+    stmts.iter().map(|stmt| stmt_source(stmt, 0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dump::ast_sexpr;
+    use crate::generate::new_generator;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    fn tokens(contents: &str) -> Vec<Token> {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        lexer.tokens
+    }
+
+    fn parse(contents: &str) -> Vec<Stmt> {
+        new_parser(tokens(contents))
+            .parse()
+            .unwrap_or_else(|error| panic!("{}\n{}", error.message, contents))
+    }
+
+    #[test]
+    fn format_source_test() {
+        let format = |contents: &str| -> String {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            format_source(&lexer.tokens)
+        };
+        assert_eq!(format("set   a=1 ;"), "set a = 1;\n");
+        assert_eq!(format("a+=1"), "a += 1\n");
+        assert_eq!(format("print max( a,f() )"), "print max(a, f())\n");
+        assert_eq!(
+            format("set xs=[ 1,[2] ] ;print xs [0]-xs[1][0]"),
+            "set xs = [1, [2]];\nprint xs[0] - xs[1][0]\n"
+        );
+        assert_eq!(
+            format("set m={ \"a\" :1,2:{}} ;if m=={}{print \"a\" in m;}"),
+            "set m = {\"a\": 1, 2: {}};\nif m == {} {\n  print \"a\" in m;\n}\n"
+        );
+        assert_eq!(
+            format("for i in 0 .. n+1{print i;}"),
+            "for i in 0..n + 1 {\n  print i;\n}\n"
+        );
+        assert_eq!(
+            format("print \"{{a}}\"+\"x {a+1}\" ;"),
+            "print \"{{a}}\" + \"x {a+1}\";\n"
+        );
+        assert_eq!(
+            format("if a {print 1;}\nelse{print 2;}"),
+            "if a {\n  print 1;\n} else {\n  print 2;\n}\n"
+        );
+
+        let messy: &str = "set a,b = -1,( 2+$PI )*-a ;while a<3{ print  \"a\tb\" ;if a>1{}a+=1;}";
+        let formatted: String = format(messy);
+        assert_eq!(
+            formatted,
+            concat!(
+                "set a, b = -1, (2 + $PI) * -a;\n",
+                "while a < 3 {\n",
+                "  print \"a\\tb\";\n",
+                "  if a > 1 {\n",
+                "  }\n",
+                "  a += 1;\n",
+                "}\n",
+            )
+        );
+        assert_eq!(format("print {}- -a;"), "print {} - -a;\n");
+        assert_eq!(format("print - -a;"), "print - -a;\n");
+        assert_eq!(
+            format("try{print 1;}catch e{print e;}"),
+            "try {\n  print 1;\n} catch e {\n  print e;\n}\n"
+        );
+        assert_eq!(
+            format("match a{1=>{print 1;},_=>print 2;,}"),
+            "match a {\n  1 => {\n    print 1;\n  }\n  _ => print 2;\n}\n"
+        );
+        assert_eq!(
+            format("set a=b?{1:2}:c?-1:{b?1:2 :3};"),
+            "set a = b ? {1: 2} : c ? -1 : {b ? 1 : 2: 3};\n"
+        );
+
+        // Formatting twice doesn't change anything
+        assert_eq!(format(&formatted), formatted);
+        assert_eq!(format(""), "");
+    }
+
+    #[test]
+    fn ast_source_test() {
+        assert_eq!(
+            ast_source(&parse(
+                "set a = -(2 ** 2) + (-2) ** 2 - -a;if not (a and b) == c {} else if (0..3)[1] {}"
+            )),
+            "set a = -2 ** 2 + (-2) ** 2 - (-a);\nif not (a and b) == c {\n} else if (0..3)[1] {\n}\n"
+        );
+
+        // Printing a random tree and parsing it again gives back the same tree,
+        // and so does formatting what was printed
+        let mut generator = new_generator(0x51_7cc1_b727_220a);
+        for _ in 0..500 {
+            let stmts: Vec<Stmt> = generator.program(6);
+            let source: String = ast_source(&stmts);
+            assert_eq!(ast_sexpr(&parse(&source)), ast_sexpr(&stmts), "{}", source);
+
+            let formatted: String = format_source(&tokens(&source));
+            assert_eq!(
+                ast_sexpr(&parse(&formatted)),
+                ast_sexpr(&stmts),
+                "{}",
+                formatted
+            );
+            assert_eq!(format_source(&tokens(&formatted)), formatted);
+        }
+    }
+}
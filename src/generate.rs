@@ -0,0 +1,312 @@
+//! Makes random programs that the parser would take, for testing that printing
+//! a tree and parsing it again gives back the same tree. The same seed always
+//! makes the same programs, so a failure can be run again
+
+use crate::ast::{Expr, MatchArm, Position, Stmt};
+use crate::lexer::Tokens;
+
+const NAMES: [&str; 6] = ["a", "b", "total", "xs", "n2", "item_count"];
+const FUNCTIONS: [&str; 4] = ["len", "max", "f", "range"];
+const CONSTANTS: [&str; 3] = ["PI", "E", "ARGC"];
+const NUMBERS: [&str; 6] = ["0", "1", "42", "1.5", "0.25", "1000000"];
+/// Text for strings, with the characters that have to be escaped or doubled
+const TEXTS: [&str; 6] = ["", "hi", "a b", "say \"x\"", "tab\tand\\n", "{braces}"];
+const OPERATORS: [Tokens; 16] = [
+    Tokens::Or,
+    Tokens::And,
+    Tokens::LessThan,
+    Tokens::GreaterThan,
+    Tokens::LessEqual,
+    Tokens::GreaterEqual,
+    Tokens::Equal,
+    Tokens::NotEqual,
+    Tokens::In,
+    Tokens::Plus,
+    Tokens::Minus,
+    Tokens::Multiply,
+    Tokens::Divide,
+    Tokens::Modulo,
+    Tokens::Power,
+    Tokens::Range,
+];
+const COMPOUND: [Tokens; 4] = [
+    Tokens::PlusAssign,
+    Tokens::MinusAssign,
+    Tokens::MultiplyAssign,
+    Tokens::DivideAssign,
+];
+
+pub struct Generator {
+    seed: u64,
+}
+
+/// Make a generator, the seed can't be zero so zero is moved to one
+pub fn new_generator(seed: u64) -> Generator {
+    Generator { seed: seed.max(1) }
+}
+
+impl Generator {
+    /// A xorshift step, so there's nothing to depend on
+    fn next(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+    fn below(&mut self, count: usize) -> usize {
+        (self.next() % count as u64) as usize
+    }
+    fn pick<T: Clone>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())].clone()
+    }
+    fn name(&mut self) -> String {
+        self.pick(&NAMES).to_string()
+    }
+    fn names(&mut self) -> Vec<String> {
+        (0..self.below(3) + 1).map(|_| self.name()).collect()
+    }
+    fn exprs(&mut self, depth: usize, plain: bool) -> Vec<Expr> {
+        (0..self.below(3))
+            .map(|_| self.expr(depth, plain))
+            .collect()
+    }
+    fn leaf(&mut self, plain: bool) -> Expr {
+        match self.below(if plain { 5 } else { 6 }) {
+            0 => Expr::Numeric(self.pick(&NUMBERS).to_string()),
+            1 => Expr::Bool(self.below(2) == 0),
+            2 => Expr::Constant(self.pick(&CONSTANTS).to_string()),
+            3 => Expr::Identifier(self.name(), Position::default()),
+            4 => Expr::Input,
+            _ => Expr::Str(self.pick(&TEXTS).to_string()),
+        }
+    }
+    /// Pieces of a string with holes, the text between holes is never empty and
+    /// there's always at least one hole or it would just be a string
+    fn format(&mut self, depth: usize) -> Expr {
+        let mut parts: Vec<Expr> = Vec::new();
+        for _ in 0..self.below(2) + 1 {
+            if self.below(2) == 0 {
+                parts.push(Expr::Str(self.pick(&TEXTS[1..]).to_string()));
+            }
+            parts.push(self.expr(depth, true));
+        }
+        if self.below(2) == 0 {
+            parts.push(Expr::Str(self.pick(&TEXTS[1..]).to_string()));
+        }
+        Expr::Format(parts)
+    }
+    /// An expression nested at most depth deep. A plain one can go in the hole of
+    /// a string, so it has no strings or dicts with quotes and braces of their own
+    pub fn expr(&mut self, depth: usize, plain: bool) -> Expr {
+        if depth == 0 {
+            return self.leaf(plain);
+        }
+        let depth: usize = depth - 1;
+        match self.below(if plain { 8 } else { 10 }) {
+            0 => self.leaf(plain),
+            1 => Expr::Call {
+                name: self.pick(&FUNCTIONS).to_string(),
+                args: self.exprs(depth, plain),
+                position: Position::default(),
+            },
+            2 => Expr::Unary {
+                operator: if self.below(2) == 0 {
+                    Tokens::Minus
+                } else {
+                    Tokens::Not
+                },
+                expr: Box::new(self.expr(depth, plain)),
+            },
+            3 => Expr::List(self.exprs(depth, plain)),
+            4 => Expr::Index {
+                target: Box::new(self.expr(depth, plain)),
+                index: Box::new(self.expr(depth, plain)),
+                position: Position::default(),
+            },
+            5 | 6 => {
+                let left: Box<Expr> = Box::new(self.expr(depth, plain));
+                let right: Box<Expr> = Box::new(self.expr(depth, plain));
+                match self.pick(&OPERATORS) {
+                    Tokens::Range => Expr::Range {
+                        start: left,
+                        end: right,
+                        position: Position::default(),
+                    },
+                    operator => Expr::Binary {
+                        left,
+                        operator,
+                        right,
+                        position: Position::default(),
+                    },
+                }
+            }
+            7 => Expr::Ternary {
+                condition: Box::new(self.expr(depth, plain)),
+                then: Box::new(self.expr(depth, plain)),
+                otherwise: Box::new(self.expr(depth, plain)),
+            },
+            8 => self.format(depth),
+            _ => Expr::Dict(
+                (0..self.below(3))
+                    .map(|_| (self.expr(depth, false), self.expr(depth, false)))
+                    .collect(),
+            ),
+        }
+    }
+    /// What a match arm can match, a `_` is only ever the last one
+    fn pattern(&mut self) -> Expr {
+        match self.below(3) {
+            0 => Expr::Numeric(self.pick(&NUMBERS).to_string()),
+            1 => Expr::Bool(self.below(2) == 0),
+            _ => Expr::Str(self.pick(&TEXTS).to_string()),
+        }
+    }
+    fn block(&mut self, depth: usize, in_func: bool) -> Vec<Stmt> {
+        (0..self.below(3))
+            .map(|_| self.stmt(depth, in_func, false))
+            .collect()
+    }
+    /// A statement with blocks nested at most depth deep. A return or a global
+    /// only comes in a function, and a func or use only at the top
+    pub fn stmt(&mut self, depth: usize, in_func: bool, top: bool) -> Stmt {
+        let kinds: usize = if depth == 0 { 11 } else { 17 };
+        match self.below(kinds) {
+            0 => Stmt::Set {
+                name: self.name(),
+                value: self.expr(3, false),
+            },
+            1 => Stmt::Const {
+                name: self.name().to_uppercase(),
+                value: self.expr(2, false),
+            },
+            2 => Stmt::SetChain {
+                names: vec![self.name(), self.name()],
+                value: self.expr(2, false),
+            },
+            3 => {
+                let names: Vec<String> = vec![self.name(), self.name()];
+                let values: Vec<Expr> = names.iter().map(|_| self.expr(2, false)).collect();
+                Stmt::SetMany { names, values }
+            }
+            4 => Stmt::SetIndex {
+                name: self.name(),
+                indexes: (0..self.below(2) + 1)
+                    .map(|_| self.expr(2, false))
+                    .collect(),
+                value: self.expr(2, false),
+                position: Position::default(),
+            },
+            5 => Stmt::Print {
+                exprs: vec![self.expr(3, false)],
+            },
+            6 => Stmt::Jump {
+                offset: self.expr(1, false),
+            },
+            7 => Stmt::AugAssign {
+                name: self.name(),
+                operator: self.pick(&COMPOUND),
+                value: self.expr(2, false),
+            },
+            8 => Stmt::Exit {
+                code: if self.below(2) == 0 {
+                    Some(self.expr(1, false))
+                } else {
+                    None
+                },
+                position: Position::default(),
+            },
+            9 if in_func => Stmt::Return {
+                value: if self.below(2) == 0 {
+                    Some(self.expr(2, false))
+                } else {
+                    None
+                },
+            },
+            9 if top => Stmt::Use {
+                name: self.name(),
+                position: Position::default(),
+            },
+            10 if in_func => Stmt::Global {
+                names: self.names(),
+            },
+            9 | 10 => Stmt::Expr(Expr::Call {
+                name: self.pick(&FUNCTIONS).to_string(),
+                args: self.exprs(2, false),
+                position: Position::default(),
+            }),
+            11 => Stmt::If {
+                condition: self.expr(2, false),
+                body: self.block(depth - 1, in_func),
+                else_body: match self.below(3) {
+                    0 => None,
+                    1 => Some(self.block(depth - 1, in_func)),
+                    _ => Some(vec![self.stmt(depth - 1, in_func, false)]),
+                },
+            },
+            12 => Stmt::While {
+                condition: self.expr(2, false),
+                body: self.block(depth - 1, in_func),
+            },
+            13 if top => Stmt::Func {
+                name: self.pick(&FUNCTIONS).to_string(),
+                // A name can only be a parameter once
+                params: NAMES[..self.below(3)]
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect(),
+                body: self.block(depth - 1, true),
+            },
+            14 => {
+                let mut arms: Vec<MatchArm> = (0..self.below(3))
+                    .map(|_| MatchArm {
+                        pattern: Some(self.pattern()),
+                        body: self.block(depth - 1, in_func),
+                        position: Position::default(),
+                    })
+                    .collect();
+                if self.below(2) == 0 {
+                    arms.push(MatchArm {
+                        pattern: None,
+                        body: self.block(depth - 1, in_func),
+                        position: Position::default(),
+                    });
+                }
+                Stmt::Match {
+                    value: self.expr(2, false),
+                    arms,
+                    position: Position::default(),
+                }
+            }
+            15 => Stmt::Try {
+                body: self.block(depth - 1, in_func),
+                name: self.name(),
+                handler: self.block(depth - 1, in_func),
+            },
+            _ => Stmt::For {
+                name: self.name(),
+                iterable: self.expr(2, false),
+                body: self.block(depth - 1, in_func),
+                position: Position::default(),
+            },
+        }
+    }
+    /// A program of up to size statements at the top
+    pub fn program(&mut self, size: usize) -> Vec<Stmt> {
+        (0..self.below(size + 1))
+            .map(|_| self.stmt(2, false, true))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_test() {
+        // The same seed makes the same program
+        let program: Vec<Stmt> = new_generator(7).program(20);
+        assert_eq!(new_generator(7).program(20), program);
+        assert_ne!(new_generator(8).program(20), program);
+    }
+}
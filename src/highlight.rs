@@ -0,0 +1,142 @@
+//! Colors mahou source by what the lexer says each token is, as HTML for docs and
+//! blog posts or as ANSI colors for a terminal, for `--emit highlight`. Everything
+//! between the tokens like spaces is kept as it is, so the code looks the same
+
+use crate::dump::kind_color;
+use crate::lexer::{new_lexer, Lex, LexError, Lexer, Token, TokenKind, Tokens};
+
+/// The CSS class a token gets, strings and numbers are told apart even though
+/// they're both literals
+pub fn class(token: Tokens) -> &'static str {
+    match token {
+        Tokens::Str | Tokens::Format => "mh-string",
+        Tokens::Numeric | Tokens::Float => "mh-number",
+        Tokens::Bool => "mh-bool",
+        token => match TokenKind::from(token) {
+            TokenKind::Keyword => "mh-keyword",
+            TokenKind::Operator => "mh-operator",
+            TokenKind::Literal => "mh-literal",
+            TokenKind::Identifier => "mh-identifier",
+            TokenKind::Constant => "mh-constant",
+            TokenKind::Punctuation => "mh-punctuation",
+            TokenKind::Trivia => "mh-comment",
+        },
+    }
+}
+
+/// Escape the text to go inside of HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Where each token starts and ends in the characters of the source, the end
+/// is one past its last character
+fn ranges(chars: &[char], tokens: &[Token]) -> Vec<(usize, usize)> {
+    let mut line_starts: Vec<usize> = vec![0];
+    for (index, ch) in chars.iter().enumerate() {
+        if *ch == '\n' {
+            line_starts.push(index + 1);
+        }
+    }
+    let at = |line: i64, column: i64| -> usize {
+        let start: usize = line_starts.get(line as usize - 1).copied().unwrap_or(0);
+        (start + column as usize).min(chars.len())
+    };
+    tokens
+        .iter()
+        .map(|x| {
+            (
+                at(x.span.start_line, x.span.start_col - 1),
+                at(x.span.end_line, x.span.end_col),
+            )
+        })
+        .collect()
+}
+
+/// The source colored by its tokens, comments included. As HTML each token is a
+/// span with its class inside of `<pre class="mahou">`, otherwise it's ANSI colors
+pub fn highlight(contents: &str, html: bool) -> Result<String, LexError> {
+    let mut lexer: Lexer = new_lexer(contents);
+    lexer.keep_comments = true;
+    lexer.lexer()?;
+
+    let chars: Vec<char> = contents.chars().collect();
+    let text = |start: usize, end: usize| -> String {
+        let text: String = chars[start..end].iter().collect();
+        if html {
+            escape_html(&text)
+        } else {
+            text
+        }
+    };
+    let mut highlighted: String = String::new();
+    if html {
+        highlighted.push_str("<pre class=\"mahou\"><code>");
+    }
+    let mut last: usize = 0;
+    let mut prev: Option<Tokens> = None;
+    for (tok, (start, end)) in lexer.tokens.iter().zip(ranges(&chars, &lexer.tokens)) {
+        if start < last {
+            continue;
+        }
+        highlighted.push_str(&text(last, start));
+        // The name after `$` is part of the constant, like `$PI`
+        let token: Tokens = match prev {
+            Some(Tokens::Var) => Tokens::Var,
+            _ => tok.token,
+        };
+        if html {
+            highlighted.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                class(token),
+                text(start, end)
+            ));
+        } else {
+            highlighted.push_str(&format!(
+                "{}{}\x1b[0m",
+                kind_color(TokenKind::from(token)),
+                text(start, end)
+            ));
+        }
+        last = end;
+        prev = Some(tok.token);
+    }
+    highlighted.push_str(&text(last, chars.len()));
+    if html {
+        highlighted.push_str("</code></pre>\n");
+    }
+    Ok(highlighted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_test() {
+        assert_eq!(
+            highlight("set a = \"<{a}>\";  # hi\nprint $PI;", true).unwrap(),
+            concat!(
+                "<pre class=\"mahou\"><code>",
+                "<span class=\"mh-keyword\">set</span> ",
+                "<span class=\"mh-identifier\">a</span> ",
+                "<span class=\"mh-operator\">=</span> ",
+                "<span class=\"mh-string\">&quot;&lt;{a}&gt;&quot;</span>",
+                "<span class=\"mh-punctuation\">;</span>  ",
+                "<span class=\"mh-comment\"># hi</span>\n",
+                "<span class=\"mh-keyword\">print</span> ",
+                "<span class=\"mh-constant\">$</span><span class=\"mh-constant\">PI</span>",
+                "<span class=\"mh-punctuation\">;</span>",
+                "</code></pre>\n",
+            )
+        );
+        assert_eq!(
+            highlight("print 1;\n", false).unwrap(),
+            "\x1b[35mprint\x1b[0m \x1b[32m1\x1b[0m\x1b[90m;\x1b[0m\n"
+        );
+        assert!(highlight("set a = \"x;", true).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Keeping each different text once. A lexer keeps the text of its tokens this way,
+//! so a name that's used a thousand times in a file is one string that all of its
+//! tokens share, and the scopes of a running program give each variable's name a
+//! number to look it up by. Everything an interner has goes away along with it
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// Which text it is in the interner it came from, two symbols from the same one
+/// are the same text if they're the same number
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// A hasher for names and symbols that's a lot quicker than the default one, which
+/// is made to hold up against someone picking keys that collide. The texts in a
+/// program are whatever its author wrote, so that isn't something to guard against
+#[derive(Default, Clone, Copy)]
+pub struct NameHasher(u64);
+
+impl NameHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for NameHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word: [u8; 8] = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+        for byte in chunks.remainder() {
+            self.add(u64::from(*byte));
+        }
+    }
+    fn write_u8(&mut self, number: u8) {
+        self.add(u64::from(number));
+    }
+    fn write_u32(&mut self, number: u32) {
+        self.add(u64::from(number));
+    }
+}
+
+type Names = BuildHasherDefault<NameHasher>;
+pub type SymbolMap<V> = HashMap<Symbol, V, Names>;
+pub type SymbolSet = HashSet<Symbol, Names>;
+
+/// Text that's shared by everything that has it instead of copied
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Text(Rc<str>);
+
+/// Every text it was given and the symbol for each, in the order they came
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    texts: Vec<Text>,
+    symbols: HashMap<Text, Symbol, Names>,
+}
+
+impl Interner {
+    /// The symbol for the text, which is only added the first time
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(text) {
+            return *symbol;
+        }
+        let symbol: Symbol = Symbol(self.texts.len() as u32);
+        let text: Text = Text::from(text);
+        self.texts.push(text.clone());
+        self.symbols.insert(text, symbol);
+        symbol
+    }
+    /// The text shared with everything else that got it from here
+    pub fn text(&mut self, text: &str) -> Text {
+        let symbol: Symbol = self.intern(text);
+        self.texts[symbol.0 as usize].clone()
+    }
+    /// The symbol if the text has one already, without adding it
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.symbols.get(text).copied()
+    }
+    /// The text of a symbol from this interner
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.texts[symbol.0 as usize]
+    }
+    /// How many different texts there are, for seeing how much got shared
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+}
+
+impl Text {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Text {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Hashed like its str so a map of them can be looked up with one
+impl Hash for Text {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl Borrow<str> for Text {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Text of its own, for a token that didn't come from a lexer
+impl From<&str> for Text {
+    fn from(text: &str) -> Text {
+        Text(Rc::from(text))
+    }
+}
+
+impl PartialEq<str> for Text {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Text {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Text {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+/// Shown as its text, so a token in a test failure still says what it is
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_test() {
+        let mut interner: Interner = Interner::default();
+        let total: Symbol = interner.intern("total");
+        assert_eq!(interner.intern("total"), total);
+        assert_ne!(interner.intern("totals"), total);
+        assert_eq!(interner.resolve(total), "total");
+        assert_eq!(interner.get("total"), Some(total));
+        assert_eq!(interner.get("count"), None);
+        assert_eq!(interner.len(), 2);
+
+        // Every copy of the text is the same string
+        let text: Text = interner.text("total");
+        assert!(Rc::ptr_eq(&text.0, &interner.text("total").0));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(text, "total");
+        assert_eq!(format!("{} {:?}", text, text), "total \"total\"");
+        assert!(text.starts_with("tot"));
+
+        // Another interner has its own
+        let mut other: Interner = Interner::default();
+        assert_eq!(other.intern("count"), total);
+        assert_eq!(other.resolve(total), "count");
+
+        let mut values: SymbolMap<i64> = SymbolMap::default();
+        for number in 0..100 {
+            values.insert(interner.intern(&number.to_string()), number);
+        }
+        assert_eq!(values.get(&interner.intern("42")), Some(&42));
+        assert_eq!(values.len(), 100);
+    }
+}
@@ -0,0 +1,2496 @@
+//! Runs the statements directly, so programs work without python installed
+
+use crate::ast::{Expr, MatchArm, Position, Stmt};
+use crate::diagnostics::Diagnostic;
+use crate::dump::stmt_sexpr;
+use crate::formatter::expr_source;
+use crate::intern::{Interner, Symbol, SymbolMap, SymbolSet};
+use crate::lexer::{is_float_literal, Tokens};
+use crate::log;
+use crate::parser::{constant_value, first_line, operator_text};
+use crate::stdlib::{call, elapsed, input, random, start_random, start_time};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::time::Instant;
+
+/// How deep function calls can go before giving up unless `--max-depth` says
+/// otherwise, lower than python's limit since each call also goes through a few
+/// functions of the interpreter itself
+pub const MAX_CALL_DEPTH: usize = 200;
+
+/// About how much of the real stack one call takes, a debug build uses around
+/// 11KB going through eval and exec for each one
+pub const STACK_PER_CALL: usize = 16 * 1024;
+
+/// How big a thread's stack needs to be for calls to go max_depth deep, never
+/// less than the 8MB the main thread gets
+pub fn call_stack_size(max_depth: usize) -> usize {
+    max_depth
+        .saturating_mul(STACK_PER_CALL)
+        .saturating_add(1024 * 1024)
+        .max(8 * 1024 * 1024)
+}
+
+/// How deep lists and dicts can be inside each other for them to be shown or
+/// compared, which goes one level further into rust's stack each time
+pub const MAX_NESTING: usize = 1000;
+
+/// A value while the program is running, following how python treats them
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    /// A string can't be changed, so every copy of it shares the same text
+    Str(Rc<str>),
+    Bool(bool),
+    /// Like python, a list is shared, so changing it through one variable
+    /// changes it for every variable that has it. They're counted references
+    /// without a collector, so a list or dict that ends up inside of itself stays
+    /// around until the program is done even once nothing has it
+    List(Rc<RefCell<Items>>),
+    /// The keys and values in the order the keys were first set, shared like a list.
+    /// Keys are found by equality, so `1` and `1.0` are the same key like in python
+    Dict(Rc<RefCell<Entries>>),
+    /// The ints from the start up to before the end, like python's range, which
+    /// are only made one at a time as a loop goes through them
+    Range(i64, i64),
+    /// What a function gives back when it doesn't return anything
+    None,
+}
+
+/// The items of a list, which work like the Vec they're in
+#[derive(Debug, Clone, Default)]
+pub struct Items(pub Vec<Value>);
+
+/// The keys and values of a dict, which work like the Vec they're in
+#[derive(Debug, Clone, Default)]
+pub struct Entries(pub Vec<(Value, Value)>);
+
+impl Deref for Items {
+    type Target = Vec<Value>;
+    fn deref(&self) -> &Vec<Value> {
+        &self.0
+    }
+}
+
+impl DerefMut for Items {
+    fn deref_mut(&mut self) -> &mut Vec<Value> {
+        &mut self.0
+    }
+}
+
+impl Deref for Entries {
+    type Target = Vec<(Value, Value)>;
+    fn deref(&self) -> &Vec<(Value, Value)> {
+        &self.0
+    }
+}
+
+impl DerefMut for Entries {
+    fn deref_mut(&mut self) -> &mut Vec<(Value, Value)> {
+        &mut self.0
+    }
+}
+
+/// Dropping a list that's nested a few hundred thousand deep the way rust does
+/// would go that deep into the stack, so the lists and dicts that are going away
+/// with it are taken apart one at a time instead
+fn drop_values(mut values: Vec<Value>) {
+    while let Some(value) = values.pop() {
+        match value {
+            Value::List(items) => {
+                if let Ok(items) = Rc::try_unwrap(items) {
+                    values.append(&mut items.into_inner().0);
+                }
+            }
+            Value::Dict(entries) => {
+                if let Ok(entries) = Rc::try_unwrap(entries) {
+                    for (key, value) in entries.into_inner().0.drain(..) {
+                        values.push(key);
+                        values.push(value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for Items {
+    fn drop(&mut self) {
+        drop_values(std::mem::take(&mut self.0));
+    }
+}
+
+impl Drop for Entries {
+    fn drop(&mut self) {
+        let entries: Vec<(Value, Value)> = std::mem::take(&mut self.0);
+        drop_values(
+            entries
+                .into_iter()
+                .flat_map(|(key, value)| [key, value])
+                .collect(),
+        );
+    }
+}
+
+/// Remove the boiler plate of wrapping the items of a new list
+pub fn new_list(items: Vec<Value>) -> Value {
+    Value::List(Rc::new(RefCell::new(Items(items))))
+}
+
+/// Remove the boiler plate of wrapping the entries of a new dict
+pub fn new_dict(entries: Vec<(Value, Value)>) -> Value {
+    Value::Dict(Rc::new(RefCell::new(Entries(entries))))
+}
+
+/// Exactly the same value, so unlike `==` in a program an int is never equal to a
+/// float. The lists in lists are gone through one pair at a time instead of
+/// recursing, the same as dropping them
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        let mut pairs: Vec<(Value, Value)> = vec![(self.clone(), other.clone())];
+        while let Some(pair) = pairs.pop() {
+            let same: bool = match pair {
+                (Value::List(a), Value::List(b)) => {
+                    let (a, b) = (a.borrow(), b.borrow());
+                    pairs.extend(a.iter().cloned().zip(b.iter().cloned()));
+                    a.len() == b.len()
+                }
+                (Value::Dict(a), Value::Dict(b)) => {
+                    let (a, b) = (a.borrow(), b.borrow());
+                    for ((key, value), (other_key, other)) in a.iter().zip(b.iter()) {
+                        pairs.push((key.clone(), other_key.clone()));
+                        pairs.push((value.clone(), other.clone()));
+                    }
+                    a.len() == b.len()
+                }
+                (Value::Int(a), Value::Int(b)) => a == b,
+                (Value::Float(a), Value::Float(b)) => a == b,
+                (Value::Str(a), Value::Str(b)) => a == b,
+                (Value::Bool(a), Value::Bool(b)) => a == b,
+                (Value::Range(a, b), Value::Range(c, d)) => a == c && b == d,
+                (Value::None, Value::None) => true,
+                _ => false,
+            };
+            if !same {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Value {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Value {
+        Value::Float(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::Str(value.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::Str(value.into())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(items: Vec<Value>) -> Value {
+        new_list(items)
+    }
+}
+
+impl Value {
+    /// Everything but zero, the empty string and false counts as true, like python
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Int(number) => *number != 0,
+            Value::Float(number) => *number != 0.0,
+            Value::Str(text) => !text.is_empty(),
+            Value::Bool(value) => *value,
+            Value::List(items) => !items.borrow().is_empty(),
+            Value::Dict(entries) => !entries.borrow().is_empty(),
+            Value::Range(start, end) => start < end,
+            Value::None => false,
+        }
+    }
+    /// The number as a float, if it's a number at all
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Int(number) => Some(*number as f64),
+            Value::Float(number) => Some(*number),
+            Value::Bool(value) => Some(*value as i64 as f64),
+            Value::Str(_) | Value::List(_) | Value::Dict(_) | Value::Range(..) | Value::None => {
+                None
+            }
+        }
+    }
+    /// The type of value for error messages, like `an int`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "an int",
+            Value::Float(_) => "a float",
+            Value::Str(_) => "a string",
+            Value::Bool(_) => "a bool",
+            Value::List(_) => "a list",
+            Value::Dict(_) => "a dict",
+            Value::Range(..) => "a range",
+            Value::None => "None",
+        }
+    }
+}
+
+/// Values print the same way python prints them, so `--run` and the python agree
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(number) => write!(f, "{}", number),
+            Value::Float(number) if number.is_nan() => write!(f, "nan"),
+            // Whole floats keep a `.0` so they can be told apart from ints
+            Value::Float(number) if number.is_finite() && number.fract() == 0.0 => {
+                if number.abs() < 1e16 {
+                    write!(f, "{:.1}", number)
+                } else {
+                    write!(f, "{:e}", number)
+                }
+            }
+            Value::Float(number) => write!(f, "{}", number),
+            Value::Str(text) => write!(f, "{}", text),
+            Value::List(_) | Value::Dict(_) => {
+                write!(f, "{}", repr_inside(self, &mut Vec::new(), &mut false))
+            }
+            Value::Range(start, end) => write!(f, "range({}, {})", start, end),
+            Value::Bool(true) => write!(f, "True"),
+            Value::Bool(false) => write!(f, "False"),
+            Value::None => write!(f, "None"),
+        }
+    }
+}
+
+/// Show a value like python's repr, which is how the items of a list are printed
+pub fn repr(value: &Value) -> String {
+    match value {
+        Value::Str(text) => {
+            // Python only uses double quotes when that saves escaping a single quote
+            let quote: char = if text.contains('\'') && !text.contains('"') {
+                '"'
+            } else {
+                '\''
+            };
+            let mut shown: String = quote.to_string();
+            for ch in text.chars() {
+                match ch {
+                    '\\' => shown.push_str("\\\\"),
+                    '\n' => shown.push_str("\\n"),
+                    '\t' => shown.push_str("\\t"),
+                    '\r' => shown.push_str("\\r"),
+                    _ if ch == quote => {
+                        shown.push('\\');
+                        shown.push(ch);
+                    }
+                    _ => shown.push(ch),
+                }
+            }
+            shown.push(quote);
+            shown
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Where a list or dict is kept, which is the same for every variable sharing it
+fn address<T>(shared: &Rc<RefCell<T>>) -> usize {
+    Rc::as_ptr(shared) as usize
+}
+
+/// Show the value the way print does, or an error when it has lists or dicts in
+/// it more than MAX_NESTING deep, which Display only shows as far as `[...]`
+pub fn show(value: &Value) -> Result<String, RuntimeError> {
+    let mut too_deep: bool = false;
+    let shown: String = match value {
+        Value::List(_) | Value::Dict(_) => repr_inside(value, &mut Vec::new(), &mut too_deep),
+        _ => value.to_string(),
+    };
+    if too_deep {
+        return Err(runtime_error(format!(
+            "can't show lists and dicts nested more than {} deep",
+            MAX_NESTING
+        )));
+    }
+    Ok(shown)
+}
+
+/// Show a value like repr, where inside is the lists and dicts it's in. One that
+/// has itself in it shows up as `[...]` or `{...}` there like in python, instead
+/// of going on forever, and so does one that's too deep, which sets too_deep.
+/// The items are shown the way they would be written, so strings get quotes
+fn repr_inside(value: &Value, inside: &mut Vec<usize>, too_deep: &mut bool) -> String {
+    let (place, again): (usize, &str) = match value {
+        Value::List(items) => (address(items), "[...]"),
+        Value::Dict(entries) => (address(entries), "{...}"),
+        _ => return repr(value),
+    };
+    if inside.len() >= MAX_NESTING {
+        *too_deep = true;
+        return again.to_string();
+    }
+    if inside.contains(&place) {
+        return again.to_string();
+    }
+    inside.push(place);
+    let shown: String = match value {
+        Value::List(items) => {
+            let items: Vec<String> = items
+                .borrow()
+                .iter()
+                .map(|x| repr_inside(x, inside, too_deep))
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Dict(entries) => {
+            let entries: Vec<String> = entries
+                .borrow()
+                .iter()
+                .map(|(key, value)| {
+                    format!("{}: {}", repr(key), repr_inside(value, inside, too_deep))
+                })
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        _ => unreachable!(),
+    };
+    inside.pop();
+    shown
+}
+
+/// Turn an index into a place in something with a length, counting back from
+/// the end when it's negative like python
+fn position_in(index: &Value, len: usize, what: &str) -> Result<usize, RuntimeError> {
+    let number: i64 = match index {
+        Value::Int(number) => *number,
+        Value::Bool(value) => *value as i64,
+        other => {
+            return Err(runtime_error(format!(
+                "{} indexes must be ints, not {}",
+                what,
+                other.type_name()
+            )))
+        }
+    };
+    let place: i64 = if number < 0 {
+        number + len as i64
+    } else {
+        number
+    };
+    if place < 0 || place >= len as i64 {
+        return Err(runtime_error(format!("{} index out of range", what)));
+    }
+    Ok(place as usize)
+}
+
+/// Lists and dicts can change, so they can't be the key of a dict
+fn check_key(key: &Value) -> Result<(), RuntimeError> {
+    match key {
+        Value::List(_) | Value::Dict(_) => Err(runtime_error(format!(
+            "{} can't be the key of a dict",
+            key.type_name()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Get the item at the index of a list, the value of a key in a dict, or the
+/// character at the index of a string
+pub fn index(target: &Value, index: &Value) -> Result<Value, RuntimeError> {
+    match target {
+        Value::Dict(entries) => {
+            check_key(index)?;
+            entries
+                .borrow()
+                .iter()
+                .find(|(key, _)| equal(key, index))
+                .map(|(_, value)| value.clone())
+                .ok_or_else(|| runtime_error(format!("key {} is not in the dict", repr(index))))
+        }
+        Value::List(items) => {
+            let items = items.borrow();
+            let place: usize = position_in(index, items.len(), "list")?;
+            Ok(items[place].clone())
+        }
+        Value::Str(text) => {
+            let place: usize = position_in(index, text.chars().count(), "string")?;
+            Ok(Value::Str(
+                text.chars().nth(place).unwrap().to_string().into(),
+            ))
+        }
+        Value::Range(start, end) => {
+            let place: usize = position_in(index, range_len(*start, *end), "range")?;
+            Ok(Value::Int(start + place as i64))
+        }
+        other => Err(runtime_error(format!("can't index {}", other.type_name()))),
+    }
+}
+
+/// Make the range for `start..end`, which only works on ints
+pub fn new_range(start: Value, end: Value) -> Result<Value, RuntimeError> {
+    match (&start, &end) {
+        (Value::Int(start), Value::Int(end)) => Ok(Value::Range(*start, *end)),
+        _ => Err(runtime_error(format!(
+            "a range needs ints, not {} and {}",
+            start.type_name(),
+            end.type_name()
+        ))),
+    }
+}
+
+/// How many ints a range has, an end before the start has none
+pub fn range_len(start: i64, end: i64) -> usize {
+    end.saturating_sub(start).max(0) as usize
+}
+
+/// What a for loop goes through one index at a time. A list is gone through as it
+/// changes like in python, a dict by its keys when the loop starts and a string
+/// by its characters
+pub fn loop_items(value: Value) -> Result<Value, RuntimeError> {
+    match value {
+        Value::List(_) | Value::Range(..) => Ok(value),
+        Value::Dict(_) | Value::Str(_) => call("list", vec![value]),
+        other => Err(runtime_error(format!(
+            "can't loop over {}",
+            other.type_name()
+        ))),
+    }
+}
+
+/// The item at the place in what loop_items gave back, or None once the loop is done
+pub fn loop_item(items: &Value, place: usize) -> Option<Value> {
+    match items {
+        Value::List(items) => items.borrow().get(place).cloned(),
+        Value::Range(start, end) if place < range_len(*start, *end) => {
+            Some(Value::Int(start + place as i64))
+        }
+        _ => None,
+    }
+}
+
+/// Change the item at the index of a list or the value of a key in a dict, adding
+/// the key if it's new. Strings can't be changed like in python
+pub fn store_index(target: &Value, index: &Value, value: Value) -> Result<(), RuntimeError> {
+    match target {
+        Value::Dict(entries) => {
+            check_key(index)?;
+            let mut entries = entries.borrow_mut();
+            match entries.iter_mut().find(|(key, _)| equal(key, index)) {
+                Some(entry) => entry.1 = value,
+                None => entries.push((index.clone(), value)),
+            }
+            Ok(())
+        }
+        Value::List(items) => {
+            let mut items = items.borrow_mut();
+            let place: usize = position_in(index, items.len(), "list")?;
+            items[place] = value;
+            Ok(())
+        }
+        other => Err(runtime_error(format!(
+            "can't set an index of {}",
+            other.type_name()
+        ))),
+    }
+}
+
+/// An error found while running the program
+#[derive(PartialEq, Debug, Clone)]
+pub struct RuntimeError {
+    pub message: String,
+    /// Where in the source it happened, if it came from a part of the tree that knows
+    pub position: Option<Position>,
+    /// The code from an `exit`, which isn't really an error but stops everything the same way
+    pub exit: Option<i32>,
+}
+
+pub fn runtime_error(message: String) -> RuntimeError {
+    RuntimeError {
+        message,
+        position: None,
+        exit: None,
+    }
+}
+
+/// Whether a try can catch the error. An exit isn't really one, and running out
+/// of steps has to stop the program even from inside of a try
+pub fn catchable(error: &RuntimeError, steps: u64, max_steps: Option<u64>) -> bool {
+    error.exit.is_none() && max_steps.is_none_or(|max| steps <= max)
+}
+
+/// What a print that couldn't write unwinds with. Once whatever reads the output
+/// has stopped, like `| head`, the program stops quietly the way an exit does
+pub fn print_failed(error: io::Error) -> RuntimeError {
+    match error.kind() {
+        io::ErrorKind::BrokenPipe => RuntimeError {
+            message: "the output was closed".to_string(),
+            position: None,
+            exit: Some(0),
+        },
+        _ => runtime_error(format!("could not print: {}", error)),
+    }
+}
+
+/// What an `exit` with that code unwinds with. Anything that isn't an int from 0 to 255
+/// is an error, since that's all a shell can see
+pub fn exit_with(code: &Value) -> RuntimeError {
+    match code {
+        Value::Int(code) if (0..=255).contains(code) => RuntimeError {
+            message: format!("exited with code {}", code),
+            position: None,
+            exit: Some(*code as i32),
+        },
+        Value::Int(code) => runtime_error(format!(
+            "the exit code has to be from 0 to 255, not {}",
+            code
+        )),
+        other => runtime_error(format!("exit needs an int, not {}", other.type_name())),
+    }
+}
+
+/// An `assert` or `assert_eq` that ran, for `mahou test`
+#[derive(PartialEq, Debug, Clone)]
+pub struct Assertion {
+    pub position: Position,
+    /// Why it failed, or None if it passed
+    pub failure: Option<String>,
+}
+
+/// Why an assertion failed, or None if it passed. Like python an `assert` passes for
+/// anything truthy, and a failed `assert_eq` shows both of the values
+pub fn assertion_failure(source: &str, value: &Value, expected: Option<&Value>) -> Option<String> {
+    match expected {
+        Some(expected) if !equal(value, expected) => Some(format!(
+            "assertion failed: {} != {}",
+            repr(value),
+            repr(expected)
+        )),
+        Some(_) => None,
+        None if value.is_truthy() => None,
+        None => Some(format!("assertion failed: {}", source)),
+    }
+}
+
+impl RuntimeError {
+    /// Point the error at a place in the source, unless something inside of it already did
+    pub fn at(self, position: Position) -> Self {
+        RuntimeError {
+            position: self.position.or(Some(position)),
+            ..self
+        }
+    }
+    /// Show the error with the line it happened on, like the errors from parsing
+    pub fn render(&self, contents: &str, filename: Option<&str>, zero_based: bool) -> String {
+        match self.position {
+            Some(position) => Diagnostic {
+                level: "error",
+                code: Some("E0300"),
+                message: &self.message,
+                line_num: position.line_num,
+                char_num: position.char_num,
+            }
+            .render(contents, filename, zero_based),
+            None => format!("error[E0300]: {}\n", self.message),
+        }
+    }
+}
+
+/// Walk the statements and run them
+pub trait Interpret {
+    fn run(&mut self, stmts: &[Stmt]) -> Result<(), RuntimeError>;
+    fn exec(&mut self, stmt: &Stmt) -> Result<(), RuntimeError>;
+    fn eval(&mut self, expr: &Expr) -> Result<Value, RuntimeError>;
+}
+
+/// A function from rust given to the interpreter, it gets the values of the arguments
+pub type Native = Rc<dyn Fn(&[Value]) -> Result<Value, String>>;
+
+/// A function made with `func`, kept around until it gets called
+#[derive(PartialEq, Debug)]
+pub struct Function {
+    pub params: Vec<String>,
+    pub body: Vec<Stmt>,
+}
+
+/// The variables that can be seen from where the program is. Each block being run
+/// has its own scope that goes away when the block ends, and each function call
+/// starts a new list of them so it can't change the variables of whoever called it.
+/// The globals are under every scope. Each name gets a symbol the first time it's
+/// set, so finding a variable only hashes its name once however many scopes there are
+#[derive(Debug)]
+pub struct Scopes {
+    names: Interner,
+    globals: SymbolMap<Value>,
+    /// The first one is for outside of any function, then one for each call that
+    /// is running, the last one is the current call
+    calls: Vec<CallScopes>,
+}
+
+#[derive(Debug, Default)]
+struct CallScopes {
+    blocks: Vec<SymbolMap<Value>>,
+    /// The names `global` said to set in the globals
+    globals: SymbolSet,
+}
+
+impl Scopes {
+    /// How many function calls are running
+    pub fn depth(&self) -> usize {
+        self.calls.len() - 1
+    }
+    fn current(&mut self) -> &mut CallScopes {
+        self.calls
+            .last_mut()
+            .expect("there is always the scope outside of functions")
+    }
+    /// Find the variable in the innermost scope that has it, then in the globals.
+    /// A name that was never set doesn't have a symbol yet
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        let symbol: Symbol = self.names.get(name)?;
+        let current: &CallScopes = self.calls.last()?;
+        if current.globals.contains(&symbol) {
+            return self.globals.get(&symbol);
+        }
+        current
+            .blocks
+            .iter()
+            .rev()
+            .find_map(|x| x.get(&symbol))
+            .or_else(|| self.globals.get(&symbol))
+    }
+    /// Change the variable where it already is, or make it in the current block.
+    /// A function can only change a global after saying `global`, like python
+    pub fn set(&mut self, name: &str, value: Value) {
+        let in_function: bool = self.depth() > 0;
+        let symbol: Symbol = self.names.intern(name);
+        let globals: &mut SymbolMap<Value> = &mut self.globals;
+        let current: &mut CallScopes = self
+            .calls
+            .last_mut()
+            .expect("there is always the scope outside of functions");
+        if current.globals.contains(&symbol) {
+            globals.insert(symbol, value);
+        } else if let Some(scope) = current
+            .blocks
+            .iter_mut()
+            .rev()
+            .find(|x| x.contains_key(&symbol))
+        {
+            scope.insert(symbol, value);
+        } else {
+            match current.blocks.last_mut() {
+                Some(scope) if in_function || !globals.contains_key(&symbol) => {
+                    scope.insert(symbol, value);
+                }
+                _ => {
+                    globals.insert(symbol, value);
+                }
+            }
+        }
+    }
+    /// The variable outside of every function and block
+    pub fn global(&self, name: &str) -> Option<&Value> {
+        self.globals.get(&self.names.get(name)?)
+    }
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        let symbol: Symbol = self.names.intern(name);
+        self.globals.insert(symbol, value);
+    }
+    /// How many block scopes the current call has
+    pub fn blocks(&self) -> usize {
+        self.calls.last().map_or(0, |x| x.blocks.len())
+    }
+    pub fn push_block(&mut self) {
+        self.current().blocks.push(SymbolMap::default());
+    }
+    pub fn pop_block(&mut self) {
+        self.current().blocks.pop();
+    }
+    /// Start a function call, with the parameters set to the arguments as its first scope
+    pub fn push_call(&mut self, params: &[String], args: Vec<Value>) {
+        let locals: SymbolMap<Value> = params
+            .iter()
+            .map(|x| self.names.intern(x))
+            .zip(args)
+            .collect();
+        self.calls.push(CallScopes {
+            blocks: vec![locals],
+            globals: SymbolSet::default(),
+        });
+    }
+    pub fn pop_call(&mut self) {
+        self.calls.pop();
+    }
+    pub fn declare_global(&mut self, name: &str) {
+        let symbol: Symbol = self.names.intern(name);
+        self.current().globals.insert(symbol);
+    }
+    /// Every variable that can be seen from where the program is, sorted by name
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .globals
+            .keys()
+            .chain(
+                self.calls
+                    .last()
+                    .into_iter()
+                    .flat_map(|x| x.blocks.iter().flat_map(|x| x.keys())),
+            )
+            .map(|x| self.names.resolve(*x).to_string())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// Loading the modules takes the uses out before anything runs, so one that's
+/// still there can't be run
+pub fn not_loaded(name: &str, position: Position) -> RuntimeError {
+    runtime_error(format!(
+        "the module '{}' has to be loaded before it can run",
+        name
+    ))
+    .at(position)
+}
+
+/// Remove the boiler plate of making the scopes, starting outside of any function
+pub fn new_scopes() -> Scopes {
+    Scopes {
+        names: Interner::default(),
+        globals: SymbolMap::default(),
+        calls: vec![CallScopes::default()],
+    }
+}
+
+/// Gets called around each statement that runs, with the interpreter so it can look
+/// at and change the variables. Giving back an error stops the program
+pub trait Hook {
+    fn before(&mut self, interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError>;
+    /// Once the statement is done, which doesn't happen if it had an error
+    fn after(&mut self, _interpreter: &mut dyn Inspect, _stmt: &Stmt) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+}
+
+/// What a hook can do with the interpreter, along with running statements
+pub trait Inspect: Interpret {
+    fn variable(&self, name: &str) -> Result<Value, RuntimeError>;
+    fn assign(&mut self, name: &str, value: Value);
+    /// Every variable that can be seen from where the program is
+    fn names(&self) -> Vec<String>;
+    fn functions(&self) -> &HashMap<String, Rc<Function>>;
+    fn input(&mut self) -> &mut dyn BufRead;
+    fn output(&mut self) -> &mut dyn Write;
+}
+
+/// How a loop is being left from inside of it
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum LoopExit {
+    Break,
+    Continue,
+}
+
+/// The variables set so far, and where input comes from and print goes to
+pub struct Interpreter<R: BufRead, W: Write> {
+    pub functions: HashMap<String, Rc<Function>>,
+    /// The variables of the globals and of the blocks and function calls that are running
+    scopes: Scopes,
+    /// What the running function is giving back, once a return has been reached
+    returning: Option<Value>,
+    /// A break or continue that's leaving the blocks inside of its loop
+    leaving: Option<LoopExit>,
+    /// Stop the program after this many steps, so a jump or while that loops forever ends
+    pub max_steps: Option<u64>,
+    steps: u64,
+    /// How deep function calls can go before it stops with an error
+    pub max_depth: usize,
+    /// What the program was given after `--`, for `$ARGS` and `$ARGC`
+    pub args: Vec<String>,
+    /// Something to call around each statement, like the debugger
+    pub hook: Option<Box<dyn Hook>>,
+    /// Every assertion that ran, when they're being kept for `mahou test` instead
+    /// of a failed one stopping the program
+    pub assertions: Option<Vec<Assertion>>,
+    /// Functions from rust that the program can call like builtins, for embedding
+    pub natives: HashMap<String, Native>,
+    /// When the interpreter was made, for `elapsed()`, if there is a clock
+    started: Option<Instant>,
+    /// Where `rand()` and `rand_int()` are at, set it to a seed to pick the same
+    /// numbers every run
+    pub random: u64,
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Interpret for Interpreter<R, W> {
+    /// Run a block of statements, a jump moves around inside of the block it's in
+    fn run(&mut self, stmts: &[Stmt]) -> Result<(), RuntimeError> {
+        let mut index: usize = 0;
+        while let Some(stmt) = stmts.get(index) {
+            self.before(stmt)?;
+            index = match stmt {
+                Stmt::Jump { offset } => self.jump(offset, index, stmts.len())?,
+                _ => {
+                    self.exec(stmt)?;
+                    index + 1
+                }
+            };
+            self.call_hook(stmt, true)?;
+            // A return leaves every block until it gets back to the function call,
+            // and a break or continue until it gets back to the loop
+            if self.returning.is_some() || self.leaving.is_some() {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+    fn exec(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        match stmt {
+            Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                let value: Value = self.eval(value)?;
+                self.assign(name, value);
+            }
+            Stmt::SetChain { names, value } => {
+                let value: Value = self.eval(value)?;
+                for name in names {
+                    self.assign(name, value.clone());
+                }
+            }
+            // All of the values get worked out before any are set, so `set a, b = b, a;` swaps
+            Stmt::SetMany { names, values } => self.set_many(names, values)?,
+            // The value is worked out before the list, like python
+            Stmt::SetIndex {
+                name,
+                indexes,
+                value,
+                position,
+            } => self.set_index(name, indexes, value, *position)?,
+            Stmt::Print { exprs } => self.print(exprs)?,
+            Stmt::Jump { .. } => {
+                return Err(runtime_error(
+                    "jump can only be run as part of a block".to_string(),
+                ))
+            }
+            Stmt::AugAssign {
+                name,
+                operator,
+                value,
+            } => self.aug_assign(name, *operator, value)?,
+            Stmt::If {
+                condition,
+                body,
+                else_body,
+            } => {
+                if self.eval(condition)?.is_truthy() {
+                    self.run_block(body)?;
+                } else if let Some(else_body) = else_body {
+                    self.run_block(else_body)?;
+                }
+            }
+            Stmt::While { condition, body } => self.while_loop(condition, body)?,
+            Stmt::Match { value, arms, .. } => self.match_arms(value, arms)?,
+            Stmt::Try {
+                body,
+                name,
+                handler,
+            } => self.try_catch(body, name, handler)?,
+            Stmt::For {
+                name,
+                iterable,
+                body,
+                position,
+            } => self.for_loop(name, iterable, body, *position)?,
+            Stmt::Func { name, params, body } => self.define(name, params, body),
+            Stmt::Return { value } => {
+                let value: Value = match value {
+                    Some(value) => self.eval(value)?,
+                    None => Value::None,
+                };
+                self.returning = Some(value);
+            }
+            Stmt::Break { .. } => self.leaving = Some(LoopExit::Break),
+            Stmt::Continue { .. } => self.leaving = Some(LoopExit::Continue),
+            Stmt::Global { names } => {
+                for name in names {
+                    self.scopes.declare_global(name);
+                }
+            }
+            Stmt::Expr(expr) => {
+                self.eval(expr)?;
+            }
+            Stmt::Lines(_) => {
+                return Err(runtime_error(
+                    "custom statements can only be outputted, not run".to_string(),
+                ))
+            }
+            Stmt::Use { name, position } => return Err(not_loaded(name, *position)),
+            Stmt::Exit { code, position } => self.exit(code.as_ref(), *position)?,
+            Stmt::Assert {
+                value,
+                expected,
+                position,
+            } => self.assert(value, expected.as_ref(), *position)?,
+        }
+        Ok(())
+    }
+    fn eval(&mut self, expr: &Expr) -> Result<Value, RuntimeError> {
+        match expr {
+            Expr::Numeric(number) => parse_number(number),
+            Expr::Str(text) => Ok(Value::Str(text.as_str().into())),
+            Expr::Bool(value) => Ok(Value::Bool(*value)),
+            Expr::Identifier(name, position) => {
+                self.variable(name).map_err(|error| error.at(*position))
+            }
+            Expr::Constant(name) => constant(name, &self.args),
+            Expr::Input => self.read_input(),
+            Expr::Call {
+                name,
+                args,
+                position,
+            } => self.call(name, args, *position),
+            Expr::List(items) => self.list(items),
+            Expr::Format(parts) => self.format(parts),
+            Expr::Dict(entries) => self.dict(entries),
+            Expr::Index {
+                target,
+                index,
+                position,
+            } => self.index(target, index, *position),
+            Expr::Range {
+                start,
+                end,
+                position,
+            } => self.range(start, end, *position),
+            Expr::Unary { operator, expr } => unary(*operator, self.eval(expr)?),
+            // The right side only runs if the left doesn't already decide it
+            Expr::Binary {
+                left,
+                operator: operator @ (Tokens::And | Tokens::Or),
+                right,
+                ..
+            } => self.logical(left, *operator, right),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                position,
+            } => {
+                let left: Value = self.eval(left)?;
+                let right: Value = self.eval(right)?;
+                binary(left, *operator, right).map_err(|error| error.at(*position))
+            }
+            Expr::Ternary {
+                condition,
+                then,
+                otherwise,
+            } => self.ternary(condition, then, otherwise),
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Inspect for Interpreter<R, W> {
+    fn variable(&self, name: &str) -> Result<Value, RuntimeError> {
+        Interpreter::variable(self, name)
+    }
+    /// Set the variable like a set statement where the program is would
+    fn assign(&mut self, name: &str, value: Value) {
+        Interpreter::assign(self, name, value);
+    }
+    fn names(&self) -> Vec<String> {
+        self.scopes.names()
+    }
+    fn functions(&self) -> &HashMap<String, Rc<Function>> {
+        &self.functions
+    }
+    fn input(&mut self) -> &mut dyn BufRead {
+        &mut self.input
+    }
+    fn output(&mut self) -> &mut dyn Write {
+        &mut self.output
+    }
+}
+
+impl<R: BufRead, W: Write> Interpreter<R, W> {
+    /// Where the program is printing to, like the buffer when its output is being kept
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+    /// The variable outside of every function and block, if it's set
+    pub fn global(&self, name: &str) -> Option<&Value> {
+        self.scopes.global(name)
+    }
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.scopes.set_global(name, value);
+    }
+    /// Read and print somewhere else from now on, giving back the old input and output
+    pub fn replace_io(&mut self, input: R, output: W) -> (R, W) {
+        (
+            std::mem::replace(&mut self.input, input),
+            std::mem::replace(&mut self.output, output),
+        )
+    }
+    /// Count a statement or a time through a loop against max_steps
+    fn step(&mut self) -> Result<(), RuntimeError> {
+        self.steps += 1;
+        match self.max_steps {
+            Some(max) if self.steps > max => Err(runtime_error(format!(
+                "stopped after {} steps, the program might loop forever",
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+    /// Count the statement, then let the hook look at it
+    fn before(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        self.step()?;
+        log::trace(|| format!("running {}", first_line(&stmt_sexpr(stmt, 0))));
+        self.call_hook(stmt, false)
+    }
+    /// Where a jump from the index goes to in a block of len statements
+    fn jump(&mut self, offset: &Expr, index: usize, len: usize) -> Result<usize, RuntimeError> {
+        let offset: i64 = match self.eval(offset)? {
+            Value::Int(offset) => offset,
+            other => {
+                return Err(runtime_error(format!(
+                    "jump needs an int, not {}",
+                    other.type_name()
+                )))
+            }
+        };
+        let index: usize = usize::try_from(index as i64 + offset).map_err(|_| {
+            runtime_error(format!(
+                "jump {} goes before the start of the block",
+                offset
+            ))
+        })?;
+        // Jumping to just past the last statement ends the block, anything further is a mistake
+        if index > len {
+            return Err(runtime_error(format!(
+                "jump {} goes past the end of the block",
+                offset
+            )));
+        }
+        Ok(index)
+    }
+    /// The hook is taken out while it runs so it can have the interpreter
+    fn call_hook(&mut self, stmt: &Stmt, after: bool) -> Result<(), RuntimeError> {
+        let mut hook: Box<dyn Hook> = match self.hook.take() {
+            Some(hook) => hook,
+            None => return Ok(()),
+        };
+        let result: Result<(), RuntimeError> = if after {
+            hook.after(self, stmt)
+        } else {
+            hook.before(self, stmt)
+        };
+        self.hook = Some(hook);
+        result
+    }
+    /// Run the body of an if or while in a scope of its own
+    fn run_block(&mut self, stmts: &[Stmt]) -> Result<(), RuntimeError> {
+        self.scopes.push_block();
+        let result: Result<(), RuntimeError> = self.run(stmts);
+        self.scopes.pop_block();
+        result
+    }
+    // These are kept out of exec and eval so a deep recursion doesn't need
+    // room for them on every call
+    fn logical(
+        &mut self,
+        left: &Expr,
+        operator: Tokens,
+        right: &Expr,
+    ) -> Result<Value, RuntimeError> {
+        let left: Value = self.eval(left)?;
+        if left.is_truthy() == (operator == Tokens::Or) {
+            return Ok(left);
+        }
+        self.eval(right)
+    }
+    fn ternary(
+        &mut self,
+        condition: &Expr,
+        then: &Expr,
+        otherwise: &Expr,
+    ) -> Result<Value, RuntimeError> {
+        if self.eval(condition)?.is_truthy() {
+            self.eval(then)
+        } else {
+            self.eval(otherwise)
+        }
+    }
+    fn set_many(&mut self, names: &[String], values: &[Expr]) -> Result<(), RuntimeError> {
+        let values: Vec<Value> = values
+            .iter()
+            .map(|x| self.eval(x))
+            .collect::<Result<Vec<Value>, RuntimeError>>()?;
+        for (name, value) in names.iter().zip(values) {
+            self.assign(name, value);
+        }
+        Ok(())
+    }
+    /// The handler gets the message without where it happened, and is in a scope
+    /// of its own with the name in it like a for loop's body
+    fn try_catch(
+        &mut self,
+        body: &[Stmt],
+        name: &str,
+        handler: &[Stmt],
+    ) -> Result<(), RuntimeError> {
+        let error: RuntimeError = match self.run_block(body) {
+            Err(error) if catchable(&error, self.steps, self.max_steps) => error,
+            result => return result,
+        };
+        self.scopes.push_block();
+        self.assign(name, Value::Str(error.message.into()));
+        let result: Result<(), RuntimeError> = self.run(handler);
+        self.scopes.pop_block();
+        result
+    }
+    fn match_arms(&mut self, value: &Expr, arms: &[MatchArm]) -> Result<(), RuntimeError> {
+        let value: Value = self.eval(value)?;
+        for arm in arms {
+            let matched: bool = match &arm.pattern {
+                Some(pattern) => checked_equal(&value, &self.eval(pattern)?)?,
+                None => true,
+            };
+            if matched {
+                return self.run_block(&arm.body);
+            }
+        }
+        Ok(())
+    }
+    fn while_loop(&mut self, condition: &Expr, body: &[Stmt]) -> Result<(), RuntimeError> {
+        while self.eval(condition)?.is_truthy() {
+            self.step()?;
+            self.run_block(body)?;
+            if self.returning.is_some() || self.leaving.take() == Some(LoopExit::Break) {
+                break;
+            }
+        }
+        Ok(())
+    }
+    fn define(&mut self, name: &str, params: &[String], body: &[Stmt]) {
+        let function: Function = Function {
+            params: params.to_owned(),
+            body: body.to_owned(),
+        };
+        self.functions.insert(name.to_owned(), Rc::new(function));
+    }
+    fn exit(&mut self, code: Option<&Expr>, position: Position) -> Result<(), RuntimeError> {
+        let code: Value = match code {
+            Some(code) => self.eval(code)?,
+            None => Value::Int(0),
+        };
+        Err(exit_with(&code).at(position))
+    }
+    fn assert(
+        &mut self,
+        value: &Expr,
+        expected: Option<&Expr>,
+        position: Position,
+    ) -> Result<(), RuntimeError> {
+        let result: Value = self.eval(value)?;
+        let expected: Option<Value> = match expected {
+            Some(expected) => Some(self.eval(expected)?),
+            None => None,
+        };
+        let failure: Option<String> =
+            assertion_failure(&expr_source(value), &result, expected.as_ref());
+        match &mut self.assertions {
+            Some(assertions) => {
+                assertions.push(Assertion { position, failure });
+                Ok(())
+            }
+            None => match failure {
+                Some(message) => Err(runtime_error(message).at(position)),
+                None => Ok(()),
+            },
+        }
+    }
+    fn aug_assign(
+        &mut self,
+        name: &str,
+        operator: Tokens,
+        value: &Expr,
+    ) -> Result<(), RuntimeError> {
+        let current: Value = self.variable(name)?;
+        let value: Value = self.eval(value)?;
+        let result: Value = aug_binary(current, operator, value)?;
+        self.assign(name, result);
+        Ok(())
+    }
+    fn print(&mut self, exprs: &[Expr]) -> Result<(), RuntimeError> {
+        let mut values: Vec<String> = Vec::new();
+        for expr in exprs {
+            values.push(show(&self.eval(expr)?)?);
+        }
+        writeln!(self.output, "{}", values.join(" ")).map_err(print_failed)
+    }
+    /// Like python, input gives back the line as a string without the newline
+    fn call(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        position: Position,
+    ) -> Result<Value, RuntimeError> {
+        let args: Vec<Value> = args
+            .iter()
+            .map(|x| self.eval(x))
+            .collect::<Result<Vec<Value>, RuntimeError>>()?;
+        // Functions from the program come before the ones from rust and the builtins, like in python
+        match self.functions.get(name).cloned() {
+            Some(function) => self.call_function(name, &function, args),
+            None => self.call_builtin(name, args),
+        }
+        .map_err(|error| error.at(position))
+    }
+    fn call_builtin(&mut self, name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+        match self.natives.get(name) {
+            Some(native) => native(&args).map_err(runtime_error),
+            None if name == "input" => input(&mut self.input, &mut self.output, &args),
+            None if name == "elapsed" => elapsed(self.started, &args),
+            None if matches!(name, "rand" | "rand_int") => random(&mut self.random, name, &args),
+            None => call(name, args),
+        }
+    }
+    /// Each part is put into the string the way it would print, like python's str
+    fn format(&mut self, parts: &[Expr]) -> Result<Value, RuntimeError> {
+        let mut text: String = String::new();
+        for part in parts {
+            text.push_str(&show(&self.eval(part)?)?);
+        }
+        Ok(Value::Str(text.into()))
+    }
+    fn read_input(&mut self) -> Result<Value, RuntimeError> {
+        input(&mut self.input, &mut self.output, &[])
+    }
+    fn set_index(
+        &mut self,
+        name: &str,
+        indexes: &[Expr],
+        value: &Expr,
+        position: Position,
+    ) -> Result<(), RuntimeError> {
+        let value: Value = self.eval(value)?;
+        let mut target: Value = self.variable(name)?;
+        let (last, rest) = indexes.split_last().unwrap();
+        for place in rest {
+            let place: Value = self.eval(place)?;
+            target = index(&target, &place).map_err(|error| error.at(position))?;
+        }
+        let last: Value = self.eval(last)?;
+        store_index(&target, &last, value).map_err(|error| error.at(position))
+    }
+    fn list(&mut self, items: &[Expr]) -> Result<Value, RuntimeError> {
+        Ok(new_list(
+            items
+                .iter()
+                .map(|x| self.eval(x))
+                .collect::<Result<Vec<Value>, RuntimeError>>()?,
+        ))
+    }
+    /// A key that shows up twice keeps the last value, like python
+    fn dict(&mut self, entries: &[(Expr, Expr)]) -> Result<Value, RuntimeError> {
+        let dict: Value = new_dict(Vec::new());
+        for (key, value) in entries {
+            let key: Value = self.eval(key)?;
+            let value: Value = self.eval(value)?;
+            store_index(&dict, &key, value)?;
+        }
+        Ok(dict)
+    }
+    fn index(
+        &mut self,
+        target: &Expr,
+        place: &Expr,
+        position: Position,
+    ) -> Result<Value, RuntimeError> {
+        let target: Value = self.eval(target)?;
+        let place: Value = self.eval(place)?;
+        index(&target, &place).map_err(|error| error.at(position))
+    }
+    fn range(
+        &mut self,
+        start: &Expr,
+        end: &Expr,
+        position: Position,
+    ) -> Result<Value, RuntimeError> {
+        let start: Value = self.eval(start)?;
+        let end: Value = self.eval(end)?;
+        new_range(start, end).map_err(|error| error.at(position))
+    }
+    /// The name is set at the start of each time through like a set, so it only
+    /// lasts until the loop ends unless it was already set before it
+    fn for_loop(
+        &mut self,
+        name: &str,
+        iterable: &Expr,
+        body: &[Stmt],
+        position: Position,
+    ) -> Result<(), RuntimeError> {
+        let items: Value = self.eval(iterable)?;
+        let items: Value = loop_items(items).map_err(|error| error.at(position))?;
+        let mut place: usize = 0;
+        while let Some(item) = loop_item(&items, place) {
+            place += 1;
+            self.step()?;
+            self.scopes.push_block();
+            self.assign(name, item);
+            let result: Result<(), RuntimeError> = self.run(body);
+            self.scopes.pop_block();
+            result?;
+            if self.returning.is_some() || self.leaving.take() == Some(LoopExit::Break) {
+                break;
+            }
+        }
+        Ok(())
+    }
+    fn variable(&self, name: &str) -> Result<Value, RuntimeError> {
+        self.scopes
+            .get(name)
+            .cloned()
+            .ok_or_else(|| runtime_error(format!("variable '{}' is not defined", name)))
+    }
+    fn assign(&mut self, name: &str, value: Value) {
+        self.scopes.set(name, value);
+    }
+    fn call_function(
+        &mut self,
+        name: &str,
+        function: &Function,
+        args: Vec<Value>,
+    ) -> Result<Value, RuntimeError> {
+        if args.len() != function.params.len() {
+            return Err(runtime_error(format!(
+                "{}() takes {} argument{} but {} {} given",
+                name,
+                function.params.len(),
+                if function.params.len() == 1 { "" } else { "s" },
+                args.len(),
+                if args.len() == 1 { "was" } else { "were" }
+            )));
+        }
+        if self.scopes.depth() >= self.max_depth {
+            return Err(runtime_error(
+                "maximum recursion depth exceeded".to_string(),
+            ));
+        }
+
+        self.scopes.push_call(&function.params, args);
+        let result: Result<(), RuntimeError> = self.run(&function.body);
+        self.scopes.pop_call();
+        let value: Option<Value> = self.returning.take();
+        result?;
+        Ok(value.unwrap_or(Value::None))
+    }
+}
+
+/// Remove the boiler plate of making an interpreter
+pub fn new_interpreter<R: BufRead, W: Write>(input: R, output: W) -> Interpreter<R, W> {
+    Interpreter {
+        functions: HashMap::new(),
+        scopes: new_scopes(),
+        returning: None,
+        leaving: None,
+        max_steps: None,
+        steps: 0,
+        max_depth: MAX_CALL_DEPTH,
+        args: Vec::new(),
+        hook: None,
+        assertions: None,
+        natives: HashMap::new(),
+        started: start_time(),
+        random: start_random(),
+        input,
+        output,
+    }
+}
+
+/// The value of a constant, the ones for the arguments are made from what the program was given
+pub fn constant(name: &str, args: &[String]) -> Result<Value, RuntimeError> {
+    match name {
+        "ARGS" => Ok(new_list(
+            args.iter().map(|x| Value::Str(x.as_str().into())).collect(),
+        )),
+        "ARGC" => Ok(Value::Int(args.len() as i64)),
+        _ => constant_value(name)
+            .map(Value::Float)
+            .ok_or_else(|| runtime_error(format!("unknown constant '${}'", name))),
+    }
+}
+
+/// Numbers with a dot or an exponent in them are floats, the rest are ints
+pub fn parse_number(number: &str) -> Result<Value, RuntimeError> {
+    let value: Option<Value> = if is_float_literal(number) {
+        number.parse().ok().map(Value::Float)
+    } else {
+        number.parse().ok().map(Value::Int)
+    };
+    value.ok_or_else(|| runtime_error(format!("invalid number '{}'", number)))
+}
+
+/// Flip the sign of a number, a bool counts as 0 or 1 like python
+pub fn negate(value: Value) -> Result<Value, RuntimeError> {
+    match value {
+        Value::Int(number) => number
+            .checked_neg()
+            .map(Value::Int)
+            .ok_or_else(|| runtime_error("integer overflow".to_string())),
+        Value::Float(number) => Ok(Value::Float(-number)),
+        Value::Bool(value) => Ok(Value::Int(-(value as i64))),
+        Value::Str(_) => Err(runtime_error("can't negate a string".to_string())),
+        Value::List(_) => Err(runtime_error("can't negate a list".to_string())),
+        Value::Dict(_) => Err(runtime_error("can't negate a dict".to_string())),
+        Value::Range(..) => Err(runtime_error("can't negate a range".to_string())),
+        Value::None => Err(runtime_error("can't negate None".to_string())),
+    }
+}
+
+/// Do the math for a binary operator, following python for what each type does
+/// `-` or `not` on a value
+pub fn unary(operator: Tokens, value: Value) -> Result<Value, RuntimeError> {
+    match operator {
+        Tokens::Not => Ok(Value::Bool(!value.is_truthy())),
+        _ => negate(value),
+    }
+}
+
+/// Run `+=` or one of the others on what the variable has. Like python, `+=` on a
+/// list adds the items to the end of that same list, so every variable sharing it
+/// sees them. Any other operator is the same as it would be without the `=`
+pub fn aug_binary(current: Value, operator: Tokens, value: Value) -> Result<Value, RuntimeError> {
+    if let (Tokens::PlusAssign, Value::List(items), Value::List(more)) =
+        (operator, &current, &value)
+    {
+        // Copied first since `a += a` adds the list to itself
+        let more: Vec<Value> = more.borrow().to_vec();
+        items.borrow_mut().extend(more);
+        return Ok(current);
+    }
+    let operator: Tokens = match operator {
+        Tokens::PlusAssign => Tokens::Plus,
+        Tokens::MinusAssign => Tokens::Minus,
+        Tokens::MultiplyAssign => Tokens::Multiply,
+        Tokens::DivideAssign => Tokens::Divide,
+        other => other,
+    };
+    binary(current, operator, value)
+}
+
+/// Whether the operator is `+=` or one of the others that set the variable
+pub fn is_aug_assign(operator: Tokens) -> bool {
+    matches!(
+        operator,
+        Tokens::PlusAssign | Tokens::MinusAssign | Tokens::MultiplyAssign | Tokens::DivideAssign
+    )
+}
+
+pub fn binary(left: Value, operator: Tokens, right: Value) -> Result<Value, RuntimeError> {
+    let overflow = || runtime_error("integer overflow".to_string());
+    let mismatch = |left: &Value, right: &Value| {
+        runtime_error(format!(
+            "can't use '{}' on {} and {}",
+            operator_text(operator),
+            left.type_name(),
+            right.type_name()
+        ))
+    };
+
+    // Any two values can be checked for equality, a string is just never equal to a number.
+    // Like python, `and` and `or` give back one of the values instead of a bool
+    match operator {
+        Tokens::And if left.is_truthy() => return Ok(right),
+        Tokens::Or if !left.is_truthy() => return Ok(right),
+        Tokens::And | Tokens::Or => return Ok(left),
+        Tokens::Equal => return Ok(Value::Bool(checked_equal(&left, &right)?)),
+        Tokens::NotEqual => return Ok(Value::Bool(!checked_equal(&left, &right)?)),
+        Tokens::In => {
+            return match contains(&right, &left)? {
+                Some(found) => Ok(Value::Bool(found)),
+                None => Err(mismatch(&left, &right)),
+            }
+        }
+        _ => {}
+    }
+
+    match (&left, &right) {
+        (Value::Str(a), Value::Str(b)) => match operator {
+            Tokens::Plus => return Ok(Value::Str(format!("{}{}", a, b).into())),
+            Tokens::LessThan => return Ok(Value::Bool(a < b)),
+            Tokens::GreaterThan => return Ok(Value::Bool(a > b)),
+            Tokens::LessEqual => return Ok(Value::Bool(a <= b)),
+            Tokens::GreaterEqual => return Ok(Value::Bool(a >= b)),
+            _ => return Err(mismatch(&left, &right)),
+        },
+        (Value::Str(text), Value::Int(count)) | (Value::Int(count), Value::Str(text))
+            if operator == Tokens::Multiply =>
+        {
+            let count: usize = repeat_count(text.len(), *count)?;
+            return Ok(Value::Str(text.repeat(count).into()));
+        }
+        (Value::Str(_), _) | (_, Value::Str(_)) => return Err(mismatch(&left, &right)),
+        // Adding lists makes a new one, the two being added stay the same
+        (Value::List(a), Value::List(b)) if operator == Tokens::Plus => {
+            let mut items: Vec<Value> = a.borrow().to_vec();
+            items.extend(b.borrow().iter().cloned());
+            return Ok(new_list(items));
+        }
+        (Value::List(items), Value::Int(count)) | (Value::Int(count), Value::List(items))
+            if operator == Tokens::Multiply =>
+        {
+            let items = items.borrow();
+            let count: usize = repeat_count(items.len(), *count)?;
+            let mut repeated: Vec<Value> = Vec::with_capacity(items.len() * count);
+            for _ in 0..count {
+                repeated.extend(items.iter().cloned());
+            }
+            return Ok(new_list(repeated));
+        }
+        (Value::List(_), _)
+        | (_, Value::List(_))
+        | (Value::Dict(_), _)
+        | (_, Value::Dict(_))
+        | (Value::Range(..), _)
+        | (_, Value::Range(..)) => return Err(mismatch(&left, &right)),
+        (Value::None, _) | (_, Value::None) => return Err(mismatch(&left, &right)),
+        _ => {}
+    }
+
+    // Both sides are ints, or bools which count as ints in python. They don't go
+    // through a float, which can't hold every big int
+    let int = |value: &Value| -> Option<i64> {
+        match value {
+            Value::Int(number) => Some(*number),
+            Value::Bool(value) => Some(*value as i64),
+            _ => None,
+        }
+    };
+    let ints: Option<(i64, i64)> = int(&left).zip(int(&right));
+    if let Some((a, b)) = ints {
+        return match operator {
+            Tokens::Plus => a.checked_add(b).map(Value::Int).ok_or_else(overflow),
+            Tokens::Minus => a.checked_sub(b).map(Value::Int).ok_or_else(overflow),
+            Tokens::Multiply => a.checked_mul(b).map(Value::Int).ok_or_else(overflow),
+            Tokens::Divide if b == 0 => Err(runtime_error("division by zero".to_string())),
+            Tokens::Divide => Ok(Value::Float(a as f64 / b as f64)),
+            Tokens::Modulo if b == 0 => Err(runtime_error("modulo by zero".to_string())),
+            Tokens::Modulo => int_mod(a, b).map(Value::Int).ok_or_else(overflow),
+            Tokens::Power if b < 0 => Ok(Value::Float((a as f64).powf(b as f64))),
+            Tokens::Power => u32::try_from(b)
+                .ok()
+                .and_then(|b| a.checked_pow(b))
+                .map(Value::Int)
+                .ok_or_else(overflow),
+            Tokens::LessThan => Ok(Value::Bool(a < b)),
+            Tokens::GreaterThan => Ok(Value::Bool(a > b)),
+            Tokens::LessEqual => Ok(Value::Bool(a <= b)),
+            Tokens::GreaterEqual => Ok(Value::Bool(a >= b)),
+            _ => Err(mismatch(&left, &right)),
+        };
+    }
+
+    let (a, b): (f64, f64) = (left.as_float().unwrap(), right.as_float().unwrap());
+    match operator {
+        Tokens::Plus => Ok(Value::Float(a + b)),
+        Tokens::Minus => Ok(Value::Float(a - b)),
+        Tokens::Multiply => Ok(Value::Float(a * b)),
+        Tokens::Divide if b == 0.0 => Err(runtime_error("division by zero".to_string())),
+        Tokens::Divide => Ok(Value::Float(a / b)),
+        Tokens::Modulo if b == 0.0 => Err(runtime_error("modulo by zero".to_string())),
+        Tokens::Modulo => Ok(Value::Float(float_mod(a, b))),
+        Tokens::Power => Ok(Value::Float(a.powf(b))),
+        Tokens::LessThan => Ok(Value::Bool(a < b)),
+        Tokens::GreaterThan => Ok(Value::Bool(a > b)),
+        Tokens::LessEqual => Ok(Value::Bool(a <= b)),
+        Tokens::GreaterEqual => Ok(Value::Bool(a >= b)),
+        _ => Err(mismatch(&left, &right)),
+    }
+}
+
+/// The most bytes or items repeating a string or a list can make
+pub const MAX_REPEAT: usize = 1 << 26;
+
+/// How many times to repeat something of the length, a count below zero is none
+/// like python. Too many is an error instead of trying to make all of it
+fn repeat_count(len: usize, count: i64) -> Result<usize, RuntimeError> {
+    let count: usize = usize::try_from(count.max(0)).unwrap_or(usize::MAX);
+    if len == 0 {
+        return Ok(0);
+    }
+    match len.checked_mul(count) {
+        Some(total) if total <= MAX_REPEAT => Ok(count),
+        _ => Err(runtime_error("repetition too large".to_string())),
+    }
+}
+
+/// The remainder with the sign of the right side like python. It only gets moved
+/// over by the right side when the signs are different, where that can't overflow
+pub fn int_mod(a: i64, b: i64) -> Option<i64> {
+    let rem: i64 = a.checked_rem(b)?;
+    if rem != 0 && (rem < 0) != (b < 0) {
+        rem.checked_add(b)
+    } else {
+        Some(rem)
+    }
+}
+
+/// The same as int_mod for floats, which keeps all of the precision of `%`
+pub fn float_mod(a: f64, b: f64) -> f64 {
+    let rem: f64 = a % b;
+    if rem != 0.0 && (rem < 0.0) != (b < 0.0) {
+        rem + b
+    } else {
+        rem
+    }
+}
+
+/// Check two values for equality like python, where `1 == 1.0` and `True == 1`
+fn equal(left: &Value, right: &Value) -> bool {
+    equal_inside(left, right, &mut Vec::new(), &mut false)
+}
+
+/// Equality for `==` and match, which is an error for lists or dicts nested more
+/// than MAX_NESTING deep instead of only not being equal
+pub fn checked_equal(left: &Value, right: &Value) -> Result<bool, RuntimeError> {
+    let mut too_deep: bool = false;
+    let same: bool = equal_inside(left, right, &mut Vec::new(), &mut too_deep);
+    if too_deep {
+        return Err(runtime_error(format!(
+            "can't compare lists and dicts nested more than {} deep",
+            MAX_NESTING
+        )));
+    }
+    Ok(same)
+}
+
+/// Check for equality where comparing is the pairs of lists and dicts already being
+/// compared further in. A list is always equal to itself, and a pair that comes
+/// back around while comparing ones that have themselves in them counts as equal,
+/// since nothing that was compared on the way there was different. Going too deep
+/// sets too_deep and counts as different
+fn equal_inside(
+    left: &Value,
+    right: &Value,
+    comparing: &mut Vec<(usize, usize)>,
+    too_deep: &mut bool,
+) -> bool {
+    let pair: (usize, usize) = match (left, right) {
+        (Value::List(a), Value::List(b)) => (address(a), address(b)),
+        (Value::Dict(a), Value::Dict(b)) => (address(a), address(b)),
+        _ => return equal_values(left, right),
+    };
+    if pair.0 == pair.1 || comparing.contains(&pair) {
+        return true;
+    }
+    if comparing.len() >= MAX_NESTING {
+        *too_deep = true;
+        return false;
+    }
+    comparing.push(pair);
+    let same: bool = match (left, right) {
+        (Value::List(a), Value::List(b)) => {
+            let (a, b) = (a.borrow(), b.borrow());
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b.iter())
+                    .all(|(x, y)| equal_inside(x, y, comparing, too_deep))
+        }
+        // The order the keys were set in doesn't matter, and keys can't be lists
+        (Value::Dict(a), Value::Dict(b)) => {
+            let (a, b) = (a.borrow(), b.borrow());
+            a.len() == b.len()
+                && a.iter().all(|(key, value)| {
+                    b.iter().any(|(other_key, other)| {
+                        equal(key, other_key) && equal_inside(value, other, comparing, too_deep)
+                    })
+                })
+        }
+        _ => unreachable!(),
+    };
+    comparing.pop();
+    same
+}
+
+/// Equality for everything besides two lists or two dicts
+fn equal_values(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Str(a), Value::Str(b)) => a == b,
+        (Value::Str(_), _) | (_, Value::Str(_)) => false,
+        (Value::List(_), _) | (_, Value::List(_)) => false,
+        (Value::Dict(_), _) | (_, Value::Dict(_)) => false,
+        // Two ranges are equal when they have the same ints, so every empty one is the same
+        (Value::Range(a, b), Value::Range(c, d)) => {
+            (range_len(*a, *b) == 0 && range_len(*c, *d) == 0) || (a == c && b == d)
+        }
+        (Value::Range(..), _) | (_, Value::Range(..)) => false,
+        (Value::None, other) | (other, Value::None) => *other == Value::None,
+        (Value::Int(a), Value::Int(b)) => a == b,
+        _ => left.as_float() == right.as_float(),
+    }
+}
+
+/// Check if the value is an item of a list or a range, a part of a string or a key
+/// of a dict, for `in`. Nothing else can have values in it
+fn contains(container: &Value, value: &Value) -> Result<Option<bool>, RuntimeError> {
+    let found: bool = match (container, value) {
+        (Value::Range(start, end), Value::Int(number)) => start <= number && number < end,
+        (Value::Range(start, end), _) => value
+            .as_float()
+            .is_some_and(|x| x.fract() == 0.0 && x >= *start as f64 && x < *end as f64),
+        (Value::List(items), _) => items.borrow().iter().any(|x| equal(x, value)),
+        (Value::Str(text), Value::Str(part)) => text.contains(&**part),
+        (Value::Dict(entries), _) => {
+            check_key(value)?;
+            entries.borrow().iter().any(|(key, _)| equal(key, value))
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(found))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_undefined;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    /// Run the program and give back what it printed
+    fn run(contents: &str, input: &str) -> Result<String, RuntimeError> {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        let mut output: Vec<u8> = Vec::new();
+        new_interpreter(input.as_bytes(), &mut output).run(&stmts)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn interpreter_test() {
+        assert_eq!(
+            run("set a = 0;\na += 1;\nprint a;", ""),
+            Ok("1\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = 10;\na -= 4;\na *= 3;\nprint a;\na /= 4;\nprint a;",
+                ""
+            ),
+            Ok("18\n4.5\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = 7;\nprint a / 2;\nprint a % -3;\nprint 2 ** 10;\nprint -2 ** 2;",
+                ""
+            ),
+            Ok("3.5\n-2\n1024\n-4\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = -5;\nprint -a;\nprint - -a;\nprint -(a + 1);\nprint 2 - -3;",
+                ""
+            ),
+            Ok("5\n-5\n4\n5\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a, b = 1, 2;\nset a, b = b, a;\nprint a * 10 + b;\nprint a < b;",
+                ""
+            ),
+            Ok("21\nFalse\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "print 1 == 1.0;\nprint 2 != 2;\nprint \"a\" == 1;\nprint 3 >= 3;\nprint \"b\" <= \"a\";\nprint (1 < 2) == 1;",
+                ""
+            ),
+            Ok("True\nFalse\nFalse\nTrue\nFalse\nTrue\n".to_string())
+        );
+        assert_eq!(
+            run("set s = \"ab\" + \"c\";\nprint s * 2;\nprint len(s);", ""),
+            Ok("abcabc\n3\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set s = \"say \\\"hi\\\"\";\ns += \"\\n\\tdone\";\nprint s;",
+                ""
+            ),
+            Ok("say \"hi\"\n\tdone\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "print max(1, 5.5, 3);\nprint abs(-4);\nprint 4 / 2;\nprint 0.1 + 2;\nprint 1e-3 * 2;\nprint 2.5e1;",
+                ""
+            ),
+            Ok("5.5\n4\n2.0\n2.1\n0.002\n25.0\n".to_string())
+        );
+        assert_eq!(
+            run("set r = 2;\nprint $PI * r ** 2;\nprint $E;", ""),
+            Ok("12.566370614359172\n2.718281828459045\n".to_string())
+        );
+    }
+
+    #[test]
+    fn precedence_test() {
+        assert_eq!(
+            run(
+                "print 1 + 2 * 3;\nprint 10 - 4 - 3;\nprint 2 * 3 % 4;\nprint 1 + 2 < 2 * 2;\nprint 2 ** 3 ** 2;",
+                ""
+            ),
+            Ok("7\n3\n2\nTrue\n512\n".to_string())
+        );
+        // % goes with * and /, ** goes before all of them, and % takes the sign of the right side
+        assert_eq!(
+            run(
+                "print 12 / 4 % 2 * 3;\nprint 2 * 3 ** 2 / 6;\nprint -7 % 3;\nprint 7 % -3;\nprint 7.5 % 2;",
+                ""
+            ),
+            Ok("3.0\n3.0\n2\n-2\n1.5\n".to_string())
+        );
+        // Big ints don't overflow on the way, and a float's remainder keeps its precision
+        assert_eq!(
+            run(
+                "print 5 % 9223372036854775807;\nprint -5 % 9223372036854775807;\nprint 9223372036854775806 % 9223372036854775807;\nprint (1 / 1.5) % 3;\nprint -7.5 % 2;",
+                ""
+            ),
+            Ok("5\n9223372036854775802\n9223372036854775806\n0.6666666666666666\n0.5\n".to_string())
+        );
+        assert_eq!(
+            run("print [] * 9223372036854775807;\nprint \"\" * 9223372036854775807;\nprint [1, 2] * -1;", ""),
+            Ok("[]\n\n[]\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set a = (1 + 2) * 3;\nprint a;\nprint 10 - (4 - 3);\nprint ((2));",
+                ""
+            ),
+            Ok("9\n9\n2\n".to_string())
+        );
+    }
+
+    #[test]
+    fn control_flow_test() {
+        assert_eq!(
+            run(
+                "set a = 0;\nwhile a < 3 {\n  a += 1;\n  if a > 1 {\n    print a;\n  }\n}",
+                ""
+            ),
+            Ok("2\n3\n".to_string())
+        );
+
+        assert_eq!(
+            run(
+                "set a = 0;\nwhile a < 3 {\n  if a == 0 { print \"zero\"; } else if a == 1 { print \"one\"; } else { print a; }\n  a += 1;\n}",
+                ""
+            ),
+            Ok("zero\none\n2\n".to_string())
+        );
+
+        // A jump only moves inside of the block it's in
+        assert_eq!(
+            run("set a = 1;\njump 2;\nprint 0;\nprint a;", ""),
+            Ok("1\n".to_string())
+        );
+        assert_eq!(
+            run("if 1 { jump 2; print 0; }\nprint 1;", ""),
+            Ok("1\n".to_string())
+        );
+        assert_eq!(
+            run("set a = 0;\na += 1;\njump (a < 3) * -2 + 1;\nprint a;", ""),
+            Ok("3\n".to_string())
+        );
+    }
+
+    #[test]
+    fn logical_test() {
+        assert_eq!(
+            run(
+                "print 1 and 2;\nprint 0 and 2;\nprint \"\" or \"b\";\nprint not [];\nprint not 1 == 2;",
+                ""
+            ),
+            Ok("2\n0\nb\nTrue\nTrue\n".to_string())
+        );
+        // The right side only runs if the left doesn't decide it
+        assert_eq!(
+            run(
+                "set a = 0;\nprint a and 1 / a;\nprint 1 or 1 / a;\nif a < 1 and not {} { print \"yes\"; }",
+                ""
+            ),
+            Ok("0\n1\nyes\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set i = 0;\nwhile i < 10 and not i == 3 { i += 1; }\nprint i;",
+                ""
+            ),
+            Ok("3\n".to_string())
+        );
+    }
+
+    #[test]
+    fn loop_exit_test() {
+        assert_eq!(
+            run(
+                "set i = 0;\nwhile true {\n  i += 1;\n  if i % 2 == 0 { continue; }\n  if i > 5 { break; }\n  print i;\n}\nprint i;",
+                ""
+            ),
+            Ok("1\n3\n5\n7\n".to_string())
+        );
+        // Only the inner loop is left, and a return still leaves the function
+        assert_eq!(
+            run(
+                "func f() {\n  for x in 0..3 {\n    for y in 0..3 {\n      if y == 1 { break; }\n      print x;\n    }\n    if x == 1 { return x; }\n  }\n}\nprint f();",
+                ""
+            ),
+            Ok("0\n1\n1\n".to_string())
+        );
+    }
+
+    #[test]
+    fn ternary_test() {
+        // Only the side that's picked runs
+        assert_eq!(
+            run(
+                "set a = 0;\nprint a ? 1 / a : \"zero\";\nset b = a == 0 ? [] : 1 / a;\nprint b ? 1 : a > 0 ? 2 : 3;",
+                ""
+            ),
+            Ok("zero\n3\n".to_string())
+        );
+    }
+
+    #[test]
+    fn bool_test() {
+        // A bool is 1 or 0 in math like in python
+        assert_eq!(
+            run(
+                "set a = true;\nprint a;\nprint [a, false];\nprint a + 1;\nprint false * 2.5;\nprint true == 1;\nprint \"{false}\";",
+                ""
+            ),
+            Ok("True\n[True, False]\n2\n0.0\nTrue\nFalse\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set done = false;\nwhile not done { print \"once\"; set done = true; }",
+                ""
+            ),
+            Ok("once\n".to_string())
+        );
+    }
+
+    #[test]
+    fn exit_test() {
+        let exit = |contents: &str| -> RuntimeError { run(contents, "").unwrap_err() };
+        assert_eq!(exit("exit;").exit, Some(0));
+        assert_eq!(exit("set a = 2;\nwhile 1 { exit a * 3; }").exit, Some(6));
+        assert_eq!(
+            exit("exit 256;").message,
+            "the exit code has to be from 0 to 255, not 256"
+        );
+        assert_eq!(
+            exit("\nexit \"no\";"),
+            RuntimeError {
+                message: "exit needs an int, not a string".to_string(),
+                position: Some(Position {
+                    line_num: 2,
+                    char_num: 1
+                }),
+                exit: None
+            }
+        );
+    }
+
+    #[test]
+    fn function_test() {
+        assert_eq!(
+            run(
+                "func fib(n) {\n  if n < 2 { return n; }\n  return fib(n - 1) + fib(n - 2);\n}\nprint fib(10);",
+                ""
+            ),
+            Ok("55\n".to_string())
+        );
+
+        // Setting a variable in a function doesn't change the one outside of it
+        assert_eq!(
+            run(
+                "set a = 1;\nfunc f(b) {\n  set a = b * 2;\n  print a;\n}\nprint f(5);\nprint a;",
+                ""
+            ),
+            Ok("10\nNone\n1\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "func first(n) {\n  while 1 {\n    if n > 3 { return n; }\n    n += 1;\n  }\n}\nprint first(0);",
+                ""
+            ),
+            Ok("4\n".to_string())
+        );
+
+        let message = |contents: &str| -> String { run(contents, "").unwrap_err().message };
+        assert_eq!(
+            message("func f(a, b) { return a; }\nprint f(1);"),
+            "f() takes 2 arguments but 1 was given"
+        );
+        assert_eq!(
+            message("func f(n) { return f(n + 1); }\nprint f(0);"),
+            "maximum recursion depth exceeded"
+        );
+        assert_eq!(
+            message("func f() { return; }\nprint f() + 1;"),
+            "can't use '+' on None and an int"
+        );
+    }
+
+    #[test]
+    fn scope_test() {
+        // Setting a variable that's already outside of the block changes that one
+        assert_eq!(
+            run(
+                "set a = 0;\nwhile a < 2 {\n  set b = a * 10;\n  a += 1;\n  print b;\n}\nprint a;",
+                ""
+            ),
+            Ok("0\n10\n2\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "set total = 0;\nfunc add(n) {\n  global total;\n  total += n;\n}\nadd(2);\nadd(3);\nprint total;",
+                ""
+            ),
+            Ok("5\n".to_string())
+        );
+
+        // A variable set for the first time in a block is gone once it ends,
+        // which check_undefined would already stop before it's run
+        let mut lexer: Lexer = new_lexer("if 1 { set b = 1; }\nprint b;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        let error: RuntimeError = new_interpreter("".as_bytes(), &mut output)
+            .run(&stmts)
+            .unwrap_err();
+        assert_eq!(error.message, "variable 'b' is not defined");
+    }
+
+    #[test]
+    fn input_test() {
+        assert_eq!(
+            run("set name = input;\nprint \"hi \" + name;", "mahou\n"),
+            Ok("hi mahou\n".to_string())
+        );
+        assert_eq!(
+            run("set n = int(input);\nprint n * 2;", "21\n"),
+            Ok("42\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "input a;\ninput b;\ninput c;\nprint a * 2;\nprint b / 2;\nprint c;",
+                "21\n5.0\nbob\n"
+            ),
+            Ok("42\n2.5\nbob\n".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_test() {
+        let mut lexer: Lexer = new_lexer("print $ARGC;\nfor a in $ARGS { print a + \"!\"; }");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+            new_interpreter(&b""[..], &mut output);
+        interpreter.args = vec!["a".to_string(), "b c".to_string()];
+        interpreter.run(&stmts).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "2\na!\nb c!\n");
+
+        // Without any there's an empty list
+        assert_eq!(
+            run("print $ARGS;\nprint $ARGC;", ""),
+            Ok("[]\n0\n".to_string())
+        );
+    }
+
+    #[test]
+    fn runtime_error_test() {
+        let message = |contents: &str| -> String { run(contents, "").unwrap_err().message };
+        assert_eq!(message("print 1 / 0;"), "division by zero");
+        assert_eq!(
+            message("print \"a\" - 1;"),
+            "can't use '-' on a string and an int"
+        );
+        assert_eq!(
+            message("print 9223372036854775807 + 1;"),
+            "integer overflow"
+        );
+        assert_eq!(
+            message("print (-9223372036854775807 - 1) % -1;"),
+            "integer overflow"
+        );
+        assert_eq!(
+            message("print \"ab\" * 9223372036854775807;"),
+            "repetition too large"
+        );
+        assert_eq!(
+            message("print [1] * 9223372036854775807;"),
+            "repetition too large"
+        );
+        assert_eq!(message("print foo(1);"), "unknown function 'foo'");
+        assert_eq!(message("print len(1, 2);"), "len() can't take 2 arguments");
+        assert_eq!(
+            message("jump -1;"),
+            "jump -1 goes before the start of the block"
+        );
+        assert_eq!(
+            message("jump 3;\nprint 1;"),
+            "jump 3 goes past the end of the block"
+        );
+
+        // Errors from a variable or an operator know where they happened
+        let position =
+            |contents: &str| -> Option<Position> { run(contents, "").unwrap_err().position };
+        assert_eq!(
+            position("set a = 0;\nprint 2 / a;"),
+            Some(Position {
+                line_num: 2,
+                char_num: 9
+            })
+        );
+        assert_eq!(
+            position("set b = \"a\";\nprint b + 1;"),
+            Some(Position {
+                line_num: 2,
+                char_num: 9
+            })
+        );
+        // A builtin that goes wrong points at its name
+        assert_eq!(
+            position("print len(1, 2);"),
+            Some(Position {
+                line_num: 1,
+                char_num: 7
+            })
+        );
+    }
+
+    #[test]
+    fn list_test() {
+        assert_eq!(
+            run(
+                "set xs = [1, 2.5, \"a\", [\"it's\"]];\nprint xs;\nprint xs[0] + xs[-3];\nprint len(xs);\nprint \"abc\"[-1];",
+                ""
+            ),
+            Ok("[1, 2.5, 'a', [\"it's\"]]\n3.5\n4\nc\n".to_string())
+        );
+        // A list is shared between the variables that have it, like python
+        assert_eq!(
+            run(
+                "set a = [0, 0];\nset b = a;\nset b[1] = 5;\nset grid = [a, [1]];\nset grid[0][0] = 3;\nprint a;\nprint a == [3, 5.0];",
+                ""
+            ),
+            Ok("[3, 5]\nTrue\n".to_string())
+        );
+        // `+=` adds to the same list, and ones that have themselves in them can still
+        // be printed and compared
+        assert_eq!(
+            run(
+                "set a = [1];\nset b = a;\nb += [2];\nset a[0] = a;\nset c = [1, 2];\nset c[0] = c;\nprint a;\nprint a == c, a == b;",
+                ""
+            ),
+            Ok("[[...], 2]\nTrue True\n".to_string())
+        );
+        assert_eq!(
+            run(
+                "print [1] + [2] * 2;\nprint max([4, 9, 2]);\nif [] { print 1; } else { print 0; }",
+                ""
+            ),
+            Ok("[1, 2, 2]\n9\n0\n".to_string())
+        );
+
+        let error = |contents: &str| -> RuntimeError { run(contents, "").unwrap_err() };
+        assert_eq!(
+            error("set xs = [1];\nprint xs[1];"),
+            RuntimeError {
+                message: "list index out of range".to_string(),
+                position: Some(Position {
+                    line_num: 2,
+                    char_num: 9
+                }),
+                exit: None
+            }
+        );
+        assert_eq!(
+            error("set xs = [1];\nprint xs[\"0\"];").message,
+            "list indexes must be ints, not a string"
+        );
+        assert_eq!(
+            error("set s = \"ab\";\nset s[0] = \"c\";").message,
+            "can't set an index of a string"
+        );
+        assert_eq!(error("print 1[0];").message, "can't index an int");
+    }
+
+    #[test]
+    fn dict_test() {
+        assert_eq!(
+            run(
+                "set m = {\"a\": 1, 2: [3], \"a\": 4};\nset m[\"b\"] = 5;\nset m[2][0] = 6;\nprint m;\nprint m[\"a\"] + len(m);\nprint list(m);",
+                ""
+            ),
+            Ok("{'a': 4, 2: [6], 'b': 5}\n7\n['a', 2, 'b']\n".to_string())
+        );
+        // The order keys were set in doesn't change if two dicts are equal
+        assert_eq!(
+            run(
+                "set m = {1: 2, 3: 4};\nprint m == {3: 4, 1.0: 2};\nprint 3 in m;\nprint 2 in [1, 2];\nprint \"ell\" in \"hello\";\nprint list(\"ab\");\nprint max(m);",
+                ""
+            ),
+            Ok("True\nTrue\nTrue\nTrue\n['a', 'b']\n3\n".to_string())
+        );
+
+        let error = |contents: &str| -> RuntimeError { run(contents, "").unwrap_err() };
+        assert_eq!(
+            error("set m = {\"a\": 1};\nprint m[\"b\"];"),
+            RuntimeError {
+                message: "key 'b' is not in the dict".to_string(),
+                position: Some(Position {
+                    line_num: 2,
+                    char_num: 8
+                }),
+                exit: None
+            }
+        );
+        assert_eq!(
+            error("print [1] in {};").message,
+            "a list can't be the key of a dict"
+        );
+        assert_eq!(
+            error("print 1 in \"1\";").message,
+            "can't use 'in' on an int and a string"
+        );
+    }
+
+    #[test]
+    fn for_test() {
+        assert_eq!(
+            run(
+                "set total = 0;\nfor i in 0..5 { total += i; }\nfor c in \"ab\" { print c; }\nfor k in {\"x\": 1} { print k; }\nprint total;\nprint list(2..4);\nprint range(3);",
+                ""
+            ),
+            Ok("a\nb\nx\n10\n[2, 3]\nrange(0, 3)\n".to_string())
+        );
+        // A value set for the list while looping over it is seen by later items
+        assert_eq!(
+            run(
+                "set xs = [1, 2, 3];\nfor x in xs {\n  if x == 1 { set xs[2] = 9; }\n  print x;\n}\nfunc first(ys) {\n  for y in ys { return y; }\n}\nprint first(5..9);",
+                ""
+            ),
+            Ok("1\n2\n9\n5\n".to_string())
+        );
+
+        let error = |contents: &str| -> RuntimeError { run(contents, "").unwrap_err() };
+        assert_eq!(
+            error("for i in 3 {}"),
+            RuntimeError {
+                message: "can't loop over an int".to_string(),
+                position: Some(Position {
+                    line_num: 1,
+                    char_num: 7
+                }),
+                exit: None
+            }
+        );
+        assert_eq!(
+            error("print 0..1.5;").message,
+            "a range needs ints, not an int and a float"
+        );
+    }
+
+    #[test]
+    fn format_string_test() {
+        assert_eq!(
+            run(
+                "set x = 3;\nset xs = [x, \"a\"];\nprint \"{x} / 2 is {x / 2}, {{xs}} is {xs}\";\nprint \"{x > 1}{len(xs)}\";",
+                ""
+            ),
+            Ok("3 / 2 is 1.5, {xs} is [3, 'a']\nTrue2\n".to_string())
+        );
+        assert_eq!(
+            run("set s = \"a\";\nprint \"{s - 1}\";", "").unwrap_err(),
+            RuntimeError {
+                message: "can't use '-' on a string and an int".to_string(),
+                position: Some(Position {
+                    line_num: 2,
+                    char_num: 11
+                }),
+                exit: None
+            }
+        );
+    }
+
+    #[test]
+    fn builtin_test() {
+        assert_eq!(
+            run(
+                "set n = input(\"n? \");\nprint sqrt(int(n)) + floor(-0.5);\nprint pow(2, 3) + abs(-1);",
+                "16\n"
+            ),
+            Ok("n? 3.0\n9\n".to_string())
+        );
+        // A function from the program with the name of a builtin is called instead
+        assert_eq!(
+            run("func sqrt(x) { return x; }\nprint sqrt(4);", ""),
+            Ok("4\n".to_string())
+        );
+        assert_eq!(
+            run("print sqrt(-4);", "").unwrap_err().message,
+            "math domain error"
+        );
+    }
+
+    #[test]
+    fn max_steps_test() {
+        let steps = |contents: &str, max: u64| -> Result<String, RuntimeError> {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+            let mut output: Vec<u8> = Vec::new();
+            let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+                new_interpreter(&b""[..], &mut output);
+            interpreter.max_steps = Some(max);
+            interpreter.run(&stmts)?;
+            Ok(String::from_utf8(output).unwrap())
+        };
+        assert_eq!(
+            steps("print 1;\njump 0;", 100),
+            Err(runtime_error(
+                "stopped after 100 steps, the program might loop forever".to_string()
+            ))
+        );
+        assert!(steps("while 1 {}", 100).is_err());
+        assert_eq!(steps("print 1;\nprint 2;", 2), Ok("1\n2\n".to_string()));
+    }
+
+    #[test]
+    fn max_depth_test() {
+        let depth = |n: i64, max: usize| -> Result<String, RuntimeError> {
+            let contents: String = format!(
+                "func f(n) {{\n  if n == 0 {{ return 0; }}\n  return 1 + f(n - 1);\n}}\nprint f({});",
+                n
+            );
+            let mut lexer: Lexer = new_lexer(&contents);
+            lexer.lexer().unwrap();
+            let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+            let mut output: Vec<u8> = Vec::new();
+            let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+                new_interpreter(&b""[..], &mut output);
+            interpreter.max_depth = max;
+            interpreter.run(&stmts)?;
+            Ok(String::from_utf8(output).unwrap())
+        };
+        assert_eq!(depth(2, 3), Ok("2\n".to_string()));
+        let error: RuntimeError = depth(3, 3).unwrap_err();
+        assert_eq!(error.message, "maximum recursion depth exceeded");
+        assert_eq!(error.position.map(|x| x.line_num), Some(3));
+        // Far deeper than the stack a test thread gets would allow
+        assert_eq!(
+            crate::with_call_stack(3000, || depth(2500, 3000)).unwrap(),
+            Ok("2500\n".to_string())
+        );
+    }
+
+    #[test]
+    fn try_test() {
+        assert_eq!(
+            run(
+                "set a = 0;\ntry {\n  print 1;\n  print 1 / a;\n  print 2;\n} catch err {\n  print err;\n}\nprint 3;",
+                ""
+            ),
+            Ok("1\ndivision by zero\n3\n".to_string())
+        );
+        // It comes back out of the function calls, and an exit isn't caught
+        assert_eq!(
+            run(
+                "func f(n) { return [n][n]; }\ntry { print f(0); print f(1); } catch err { print err; }",
+                ""
+            ),
+            Ok("0\nlist index out of range\n".to_string())
+        );
+        assert_eq!(
+            run("try { exit 2; } catch err { print err; }", "").map_err(|x| x.exit),
+            Err(Some(2))
+        );
+        assert_eq!(
+            run("try { print 1; } catch err { print err; }", ""),
+            Ok("1\n".to_string())
+        );
+    }
+
+    #[test]
+    fn nesting_test() {
+        // Going away at the end, which takes it apart one list at a time
+        let nest = |depth: usize, then: &str| -> String {
+            format!(
+                "set xs = [];\nset ys = [];\nset i = 0;\nwhile i < {} {{ set xs = [xs]; set ys = [{{\"a\": ys}}]; i++; }}\n{}",
+                depth, then
+            )
+        };
+        assert_eq!(
+            run(&nest(200000, "print len(xs);"), ""),
+            Ok("1\n".to_string())
+        );
+        assert_eq!(
+            run(&nest(3, "print xs, ys;\nprint xs == [[[[]]]];"), ""),
+            Ok("[[[[]]]] [{'a': [{'a': [{'a': []}]}]}]\nTrue\n".to_string())
+        );
+        let message = |contents: &str| -> String { run(contents, "").unwrap_err().message };
+        assert_eq!(
+            message(&nest(5000, "print xs;")),
+            "can't show lists and dicts nested more than 1000 deep"
+        );
+        assert_eq!(
+            message(&nest(5000, "print str(ys);")),
+            "can't show lists and dicts nested more than 1000 deep"
+        );
+        assert_eq!(
+            message(&nest(5000, "set zs = xs;\nprint xs == [zs];")),
+            "can't compare lists and dicts nested more than 1000 deep"
+        );
+        // Display doesn't fail, so it only goes as far as the limit
+        let mut deep: Value = new_list(Vec::new());
+        for _ in 0..5000 {
+            deep = new_list(vec![deep]);
+        }
+        assert!(deep.to_string().contains("[[[...]]]"));
+        assert_eq!(deep.clone(), deep);
+        assert_ne!(deep, new_list(Vec::new()));
+    }
+}
@@ -0,0 +1,166 @@
+//! Other words for the keywords, so a class can write `imprimir "hola";` for
+//! `print "hola";`. The words come from the `[keywords]` table of mahou.toml or
+//! a file given with `--keywords`, like `imprimir = "print"`, and the lexer looks
+//! at them before its own words. The english keywords still work, so modules
+//! and the examples don't have to change
+
+use crate::lexer::{is_identifier, tokenize, Tokens};
+use crate::project::{at, read_toml};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// The keywords that are words, the ones that can be given another one
+pub const KEYWORDS: [&str; 28] = [
+    "set",
+    "let",
+    "const",
+    "jump",
+    "print",
+    "if",
+    "else",
+    "while",
+    "match",
+    "try",
+    "catch",
+    "for",
+    "func",
+    "return",
+    "break",
+    "continue",
+    "global",
+    "use",
+    "input",
+    "exit",
+    "assert",
+    "assert_eq",
+    "in",
+    "and",
+    "or",
+    "not",
+    "true",
+    "false",
+];
+
+// Like the log level there's one table for everything that lexes, but one per
+// thread so tests with different words don't see each other's
+thread_local! {
+    static ALIASES: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Use these words from now on, the key is the new word and the value the keyword
+pub fn set_aliases(aliases: BTreeMap<String, String>) {
+    ALIASES.with(|x| *x.borrow_mut() = aliases);
+}
+
+/// The words being used now, for giving the same ones to another thread
+pub fn aliases() -> BTreeMap<String, String> {
+    ALIASES.with(|x| x.borrow().clone())
+}
+
+/// The keyword a word stands for if it was given as another word for one
+pub fn alias(word: &str) -> Option<&'static str> {
+    ALIASES.with(|x| {
+        let keyword: String = x.borrow().get(word)?.to_string();
+        KEYWORDS.iter().copied().find(|x| *x == keyword)
+    })
+}
+
+/// Add a word for a keyword, it has to be a name that isn't already a keyword
+pub fn add_alias(
+    aliases: &mut BTreeMap<String, String>,
+    word: &str,
+    keyword: &str,
+) -> Result<(), String> {
+    if !KEYWORDS.contains(&keyword) {
+        return Err(format!(
+            "{} isn't a keyword that can have another word, expected one of {}",
+            keyword,
+            KEYWORDS.join(", ")
+        ));
+    }
+    if KEYWORDS.contains(&word) {
+        return Err(format!("{} is already a keyword", word));
+    }
+    if !is_identifier(word) || word.contains('.') || tokenize(word) != Tokens::Identifier {
+        return Err(format!(
+            "{} can't be a word for {}, it has to be a name",
+            word, keyword
+        ));
+    }
+    aliases.insert(word.to_string(), keyword.to_string());
+    Ok(())
+}
+
+/// Read a file of words for keywords, a `[keywords]` table like in mahou.toml
+pub fn parse_keywords(contents: &str, filename: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut aliases: BTreeMap<String, String> = BTreeMap::new();
+    for entry in read_toml(contents, filename)? {
+        if entry.table != "keywords" {
+            return Err(at(
+                filename,
+                entry.line,
+                "keywords go in the [keywords] table",
+            ));
+        }
+        add_alias(&mut aliases, &entry.key, &entry.value)
+            .map_err(|message| at(filename, entry.line, &message))?;
+    }
+    Ok(aliases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+
+    #[test]
+    fn keywords_test() {
+        let aliases: BTreeMap<String, String> = parse_keywords(
+            "[keywords]\nimprimir = \"print\"\nsi = \"if\"\nverdadero = \"true\"\n",
+            "es.toml",
+        )
+        .unwrap();
+        set_aliases(aliases);
+        assert_eq!(alias("imprimir"), Some("print"));
+        assert_eq!(alias("print"), None);
+
+        let mut lexer: Lexer = new_lexer("si verdadero { imprimir 1; }\nprint 2;");
+        lexer.lexer().unwrap();
+        let tokens: Vec<Tokens> = lexer.tokens.iter().map(|x| x.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Tokens::If,
+                Tokens::Bool,
+                Tokens::LeftBrace,
+                Tokens::Print,
+                Tokens::Numeric,
+                Tokens::Semi,
+                Tokens::RightBrace,
+                Tokens::Print,
+                Tokens::Numeric,
+                Tokens::Semi,
+            ]
+        );
+        // What a bool is comes from its text, so it's the keyword's
+        assert_eq!(lexer.tokens[1].part, "true");
+        assert_eq!(lexer.tokens[3].part, "imprimir");
+        assert_eq!(
+            crate::compile("si verdadero { imprimir 1; }"),
+            Ok("if True:\n    print(1)\n".to_string())
+        );
+        set_aliases(BTreeMap::new());
+
+        assert_eq!(
+            parse_keywords("[keywords]\nif = \"print\"\n", "es.toml"),
+            Err("error: es.toml line 2: if is already a keyword\n".to_string())
+        );
+        assert!(parse_keywords("[keywords]\nhacer = \"do\"\n", "es.toml")
+            .unwrap_err()
+            .starts_with("error: es.toml line 2: do isn't a keyword"));
+        assert_eq!(
+            parse_keywords("imprimir = \"print\"\n", "es.toml"),
+            Err("error: es.toml line 1: keywords go in the [keywords] table\n".to_string())
+        );
+    }
+}
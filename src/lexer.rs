@@ -0,0 +1,1866 @@
+//! Turns the source into tokens, each with the text it came from and where it is
+
+use crate::intern::{Interner, Text};
+use crate::keywords::alias;
+use crate::log;
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+/// Check if a given character is whitespace
+pub fn is_char_whitespace(ch: char) -> bool {
+    matches!(ch, '\t' | ' ' | '\n')
+}
+
+/// Check if a character is an symbol
+pub fn is_char_symbol(ch: char) -> bool {
+    matches!(
+        ch,
+        '+' | '-'
+            | '*'
+            | '/'
+            | '>'
+            | '<'
+            | '='
+            | '!'
+            | ';'
+            | '$'
+            | '{'
+            | '}'
+            | '('
+            | ')'
+            | '['
+            | ']'
+            | ','
+            | ':'
+            | '?'
+            | '%'
+    )
+}
+
+/// Check if a character can be the first half of a compound assignment like `+=`
+pub fn is_char_compound(ch: char) -> bool {
+    matches!(ch, '+' | '-' | '*' | '/')
+}
+
+/// Check if a character is in between 0 and 9
+pub fn is_char_numeric(ch: char) -> bool {
+    ch.is_ascii_digit()
+}
+
+/// Check if the part so far is a number in the middle of the sign of its exponent,
+/// so `1e-3` stays one token instead of becoming `1e - 3`
+pub fn continues_exponent(part: &str, next: char) -> bool {
+    let (rest, wanted): (&str, bool) = match part.strip_suffix(|x: char| x == '+' || x == '-') {
+        Some(rest) => (rest, is_char_numeric(next)),
+        None => (part, matches!(next, '+' | '-')),
+    };
+    let mantissa: &str = match rest.strip_suffix(|x: char| x == 'e' || x == 'E') {
+        Some(mantissa) => mantissa,
+        None => return false,
+    };
+    wanted
+        && mantissa.starts_with(|x: char| is_char_numeric(x))
+        && mantissa.chars().all(|x| is_char_numeric(x) || x == '.')
+}
+
+/// Check if the part ends right before or at the `..` of a range like `0..10`, which
+/// needs the character after next too since `0.5` is one token
+pub fn ends_at_range(part: &str, next: char, after: char) -> bool {
+    part == ".." || (!part.ends_with('.') && next == '.' && after == '.')
+}
+
+/// Check if a number has a fraction or an exponent, like `3.14` or `1e-3`
+pub fn is_float_literal(part: &str) -> bool {
+    part.starts_with(|x: char| is_char_numeric(x) || x == '.')
+        && part.contains(['.', 'e', 'E'])
+        && part.parse::<f64>().is_ok()
+}
+
+/// Check if the current character or the next character will end the token
+pub fn ends_token(cur: char, next: char) -> bool {
+    // Keep operators like `+=` together instead of splitting them into `+` and `=`
+    if is_char_compound(cur) && next == '=' {
+        return false;
+    }
+    // The same goes for comparisons like `<=` and `==`, and the `=>` of a match arm
+    if matches!(cur, '<' | '>' | '=' | '!') && next == '=' || cur == '=' && next == '>' {
+        return false;
+    }
+    // Keep the power operator `**` together, and `++` and `--`
+    if matches!(cur, '*' | '+' | '-') && next == cur {
+        return false;
+    }
+    if is_char_whitespace(next) || next == '#' || next == '"' {
+        return true;
+    }
+    if is_char_symbol(cur) {
+        return true;
+    }
+    if is_char_symbol(next) {
+        return true;
+    }
+    false
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Tokens {
+    Assign,
+    Var,
+    Set,
+    Const,
+    Jump,
+    Print,
+    If,
+    Else,
+    While,
+    Match,
+    Try,
+    Catch,
+    For,
+    Func,
+    Return,
+    Break,
+    Continue,
+    Global,
+    Use,
+    Input,
+    Exit,
+    Assert,
+    AssertEq,
+    Minus,
+    Plus,
+    Divide,
+    Multiply,
+    Modulo,
+    Power,
+    LessThan,
+    GreaterThan,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+    Not,
+    PlusAssign,
+    MinusAssign,
+    MultiplyAssign,
+    DivideAssign,
+    Increment,
+    Decrement,
+    Semi,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Question,
+    /// The `=>` between the pattern of a match arm and what it runs
+    Arrow,
+    In,
+    Range,
+    Identifier,
+    Numeric,
+    Float,
+    Str,
+    /// A string with values put into it like `"x is {x}"`, the part is the inside
+    /// of it as written so the holes can be found again
+    Format,
+    /// `true` or `false`, the part is which one
+    Bool,
+    /// Only made when the lexer is keeping comments, the parser never sees these
+    Comment,
+}
+
+/// A broad category of token that stays the same as new `Tokens` get added. A new
+/// kind can still come along, like `Constant` and `Trivia` did, so a match on it
+/// outside of mahou needs a `_` arm
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum TokenKind {
+    Keyword,
+    Operator,
+    Literal,
+    Identifier,
+    /// The `$` in front of a built in constant like `$PI`
+    Constant,
+    Punctuation,
+    /// Comments, which don't change what the program does
+    Trivia,
+}
+
+impl From<Tokens> for TokenKind {
+    fn from(token: Tokens) -> Self {
+        match token {
+            Tokens::Set
+            | Tokens::Const
+            | Tokens::Jump
+            | Tokens::Print
+            | Tokens::If
+            | Tokens::Else
+            | Tokens::While
+            | Tokens::Match
+            | Tokens::Try
+            | Tokens::Catch
+            | Tokens::For
+            | Tokens::Func
+            | Tokens::Return
+            | Tokens::Break
+            | Tokens::Continue
+            | Tokens::Global
+            | Tokens::Use
+            | Tokens::Input
+            | Tokens::Exit
+            | Tokens::Assert
+            | Tokens::AssertEq => TokenKind::Keyword,
+            Tokens::Assign
+            | Tokens::Minus
+            | Tokens::Plus
+            | Tokens::Divide
+            | Tokens::Multiply
+            | Tokens::Modulo
+            | Tokens::Power
+            | Tokens::LessThan
+            | Tokens::GreaterThan
+            | Tokens::LessEqual
+            | Tokens::GreaterEqual
+            | Tokens::Equal
+            | Tokens::NotEqual
+            | Tokens::PlusAssign
+            | Tokens::MinusAssign
+            | Tokens::MultiplyAssign
+            | Tokens::DivideAssign
+            | Tokens::Increment
+            | Tokens::Decrement
+            | Tokens::In
+            | Tokens::And
+            | Tokens::Or
+            | Tokens::Not
+            | Tokens::Range
+            | Tokens::Question => TokenKind::Operator,
+            Tokens::Numeric | Tokens::Float | Tokens::Str | Tokens::Format | Tokens::Bool => {
+                TokenKind::Literal
+            }
+            Tokens::Identifier => TokenKind::Identifier,
+            Tokens::Var => TokenKind::Constant,
+            Tokens::Semi
+            | Tokens::LeftBrace
+            | Tokens::RightBrace
+            | Tokens::LeftParen
+            | Tokens::RightParen
+            | Tokens::LeftBracket
+            | Tokens::RightBracket
+            | Tokens::Comma
+            | Tokens::Colon
+            | Tokens::Arrow => TokenKind::Punctuation,
+            Tokens::Comment => TokenKind::Trivia,
+        }
+    }
+}
+
+/// Where a token starts and ends, both ends are included so `set` at the start
+/// of the file covers columns 1 to 3
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Span {
+    pub start_line: i64,
+    pub start_col: i64,
+    pub end_line: i64,
+    pub end_col: i64,
+}
+
+/// This is the structure that represents a single token
+#[derive(PartialEq, Debug, Clone)]
+pub struct Token {
+    /// The token's text, which is only kept once for all the tokens from the same
+    /// lexer that have it
+    pub part: Text,
+    pub token: Tokens,
+    pub span: Span,
+}
+
+impl Token {
+    /// The line the token starts on
+    pub fn line_num(&self) -> i64 {
+        self.span.start_line
+    }
+    /// The character the token starts on
+    pub fn char_num(&self) -> i64 {
+        self.span.start_col
+    }
+}
+
+/// The `#!/usr/bin/env -S mahou run` line that lets a script run on its own, which is
+/// only one if it's the very first line. It starts with `#` so it lexes as a comment
+pub fn shebang(contents: &str) -> Option<&str> {
+    if contents.starts_with("#!") {
+        contents.lines().next()
+    } else {
+        None
+    }
+}
+
+/// Given a string reference that has been identified as a single token, find what token it is
+pub fn tokenize(part: &str) -> Tokens {
+    let token: Tokens = match part {
+        "-" => Tokens::Minus,
+        "+" => Tokens::Plus,
+        "/" => Tokens::Divide,
+        "*" => Tokens::Multiply,
+        "%" => Tokens::Modulo,
+        "**" => Tokens::Power,
+        "=" => Tokens::Assign,
+        "+=" => Tokens::PlusAssign,
+        "++" => Tokens::Increment,
+        "--" => Tokens::Decrement,
+        "-=" => Tokens::MinusAssign,
+        "*=" => Tokens::MultiplyAssign,
+        "/=" => Tokens::DivideAssign,
+        "$" => Tokens::Var,
+        ";" => Tokens::Semi,
+        "set" => Tokens::Set,
+        "let" => Tokens::Set,
+        "const" => Tokens::Const,
+        "jump" => Tokens::Jump,
+        "print" => Tokens::Print,
+        "if" => Tokens::If,
+        "else" => Tokens::Else,
+        "while" => Tokens::While,
+        "match" => Tokens::Match,
+        "try" => Tokens::Try,
+        "catch" => Tokens::Catch,
+        "for" => Tokens::For,
+        "func" => Tokens::Func,
+        "return" => Tokens::Return,
+        "break" => Tokens::Break,
+        "continue" => Tokens::Continue,
+        "global" => Tokens::Global,
+        "use" => Tokens::Use,
+        "input" => Tokens::Input,
+        "exit" => Tokens::Exit,
+        "assert" => Tokens::Assert,
+        "assert_eq" => Tokens::AssertEq,
+        "<" => Tokens::LessThan,
+        ">" => Tokens::GreaterThan,
+        "<=" => Tokens::LessEqual,
+        ">=" => Tokens::GreaterEqual,
+        "==" => Tokens::Equal,
+        "!=" => Tokens::NotEqual,
+        "{" => Tokens::LeftBrace,
+        "}" => Tokens::RightBrace,
+        "(" => Tokens::LeftParen,
+        ")" => Tokens::RightParen,
+        "[" => Tokens::LeftBracket,
+        "]" => Tokens::RightBracket,
+        "," => Tokens::Comma,
+        ":" => Tokens::Colon,
+        "?" => Tokens::Question,
+        "=>" => Tokens::Arrow,
+        "in" => Tokens::In,
+        "and" => Tokens::And,
+        "or" => Tokens::Or,
+        "not" => Tokens::Not,
+        "true" | "false" => Tokens::Bool,
+        ".." => Tokens::Range,
+        _ if !part.is_empty() && part.chars().all(is_char_numeric) => Tokens::Numeric,
+        _ if is_float_literal(part) => Tokens::Float,
+        // Anything else is a name, the lexer checks that it's a valid one
+        _ => Tokens::Identifier,
+    };
+    token
+}
+
+/// The token for a part along with its text, with the other words for keywords
+/// from the keywords table looked at first. A bool gets the keyword as its text,
+/// since that's what says which one it is
+fn word_token(part: &str) -> (Tokens, &str) {
+    match alias(part) {
+        Some(keyword) if tokenize(keyword) == Tokens::Bool => (Tokens::Bool, keyword),
+        Some(keyword) => (tokenize(keyword), part),
+        None => (tokenize(part), part),
+    }
+}
+
+/// Check if the part can be a name, a letter or `_` and then any letters,
+/// digits or `_`, so `a1` is a name but `1a` isn't. A name from a module like
+/// `shapes.area` is two of them with a `.` between
+pub fn is_identifier(part: &str) -> bool {
+    let is_name = |name: &str| {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(x) if x.is_alphabetic() || x == '_')
+            && chars.all(|x| x.is_alphanumeric() || x == '_')
+    };
+    match part.split_once('.') {
+        Some((module, name)) => is_name(module) && is_name(name),
+        None => is_name(part),
+    }
+}
+
+/// Lex without keeping track of where each token is, for when only the
+/// kinds and text matter, like a quick syntax check
+pub fn lex_fast(src: &str) -> Vec<(Tokens, String)> {
+    let mut tokens: Vec<(Tokens, String)> = Vec::new();
+    // Parts never have whitespace inside of them, so each one is a slice of
+    // the source and doesn't need to be built up one character at a time
+    let mut start: Option<usize> = None;
+    let mut chars = src.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        if ch == '#' {
+            while chars.next_if(|(_, x)| *x != '\n').is_some() {}
+            continue;
+        }
+        if ch == '/' && matches!(chars.peek(), Some((_, '*'))) {
+            chars.next();
+            let mut last: char = ' ';
+            for (_, x) in chars.by_ref() {
+                if last == '*' && x == '/' {
+                    break;
+                }
+                last = x;
+            }
+            continue;
+        }
+        if ch == '"' {
+            let mut end: usize = src.len();
+            while let Some((quote_index, x)) = chars.next() {
+                match x {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => {
+                        end = quote_index;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let raw: &str = &src[index + 1..end];
+            match format_pieces(raw) {
+                Ok(pieces) if pieces.iter().any(|x| matches!(x, FormatPiece::Hole(..))) => {
+                    tokens.push((Tokens::Format, raw.to_string()))
+                }
+                // Without any holes, `{{` is still only one brace
+                Ok(pieces) => {
+                    let text: String = pieces
+                        .into_iter()
+                        .map(|x| match x {
+                            FormatPiece::Text(text) => text,
+                            FormatPiece::Hole(..) => String::new(),
+                        })
+                        .collect();
+                    tokens.push((Tokens::Str, unescape(&text).unwrap_or(text)));
+                }
+                Err(_) => tokens.push((
+                    Tokens::Str,
+                    unescape(raw).unwrap_or_else(|_| raw.to_string()),
+                )),
+            }
+            continue;
+        }
+        if is_char_whitespace(ch) {
+            continue;
+        }
+        let part_start: usize = *start.get_or_insert(index);
+        let (next_index, next): (usize, char) = *chars.peek().unwrap_or(&(src.len(), ' '));
+        let part: &str = &src[part_start..next_index];
+        let after: char = chars.clone().nth(1).map_or(' ', |(_, x)| x);
+        if (ends_token(ch, next) && !continues_exponent(part, next))
+            || ends_at_range(part, next, after)
+        {
+            let (token, text): (Tokens, &str) = word_token(part);
+            tokens.push((token, text.to_string()));
+            start = None;
+        }
+    }
+    tokens
+}
+
+/// Strip everything that formatting can change from the tokens, so two programs
+/// that only differ in spacing or line breaks normalize to the same thing.
+/// Only identifiers, numbers and bools keep their text, since it's all the same for the rest
+pub fn normalize_tokens(tokens: &[Token]) -> Vec<(Tokens, Option<Text>)> {
+    tokens
+        .iter()
+        .map(|x| match x.token {
+            Tokens::Identifier | Tokens::Numeric | Tokens::Float | Tokens::Bool => {
+                (x.token, Some(x.part.clone()))
+            }
+            _ => (x.token, None),
+        })
+        .collect()
+}
+
+/// Given a string, find what tokens it's made up of
+pub trait Lex {
+    fn lexer(&mut self) -> Result<(), LexError>;
+}
+
+/// The parts of data needed to make tokens
+pub struct Lexer {
+    contents: String,
+    pub tokens: Vec<Token>,
+    /// Push comments as Comment tokens instead of dropping them, for tools like a formatter
+    pub keep_comments: bool,
+    /// The byte each line starts at, kept up to date by relex
+    line_starts: Vec<usize>,
+    /// Whether the tokens are of all of the contents, they stop early after an error
+    complete: bool,
+    /// The text of the tokens, kept for lexing again after a change
+    interner: Interner,
+}
+
+impl Lex for Lexer {
+    /// Takes the contents and pushes each token from the stream, stopping at the first error
+    fn lexer(&mut self) -> Result<(), LexError> {
+        let mut stream: TokenStream = new_token_stream(&self.contents);
+        stream.keep_comments = self.keep_comments;
+        stream.interner = std::mem::take(&mut self.interner);
+        self.complete = false;
+        while let Some(token) = stream.next() {
+            let token: Token = match token {
+                Ok(token) => token,
+                Err(error) => {
+                    self.interner = stream.interner;
+                    return Err(error);
+                }
+            };
+            log::debug(|| {
+                format!(
+                    "lexed {:?} {:?} at {}:{}",
+                    token.token,
+                    token.part,
+                    token.line_num(),
+                    token.char_num()
+                )
+            });
+            self.tokens.push(token);
+        }
+        self.interner = stream.interner;
+        log::info(|| format!("lexed {} tokens", self.tokens.len()));
+        self.complete = true;
+        Ok(())
+    }
+}
+
+/// Where each line starts in the text, the first one at 0
+fn find_line_starts(text: &str) -> Vec<usize> {
+    let mut starts: Vec<usize> = vec![0];
+    starts.extend(text.match_indices('\n').map(|(index, _)| index + 1));
+    starts
+}
+
+impl Lexer {
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+    /// The byte a line and column start at, both from 1 like in a span. A column
+    /// past the end of the line is the end of it
+    pub fn offset(&self, line: i64, column: i64) -> usize {
+        let start: usize = match self.line_starts.get((line.max(1) - 1) as usize) {
+            Some(start) => *start,
+            None => return self.contents.len(),
+        };
+        let rest: &str = &self.contents[start..];
+        let end: usize = rest.find('\n').unwrap_or(rest.len());
+        start
+            + rest[..end]
+                .char_indices()
+                .nth((column.max(1) - 1) as usize)
+                .map_or(end, |(index, _)| index)
+    }
+    /// The line and column a byte is at, both from 1
+    pub fn position(&self, offset: usize) -> (i64, i64) {
+        let line: usize = self.line_starts.partition_point(|x| *x <= offset);
+        let start: usize = self.line_starts[line - 1];
+        let column: usize = self.contents[start..offset.min(self.contents.len())]
+            .chars()
+            .count();
+        (line as i64, column as i64 + 1)
+    }
+    /// Replace the bytes in range with the text and lex again only as much as the
+    /// change could have changed, for an editor that lexes on every key. It starts
+    /// a couple of tokens before the change, since a token can look a little past
+    /// its end, and stops once a token is the same as one after the change was.
+    /// The tokens after that only get moved. After an error everything is lexed
+    /// again the next time, since the tokens stopped at it
+    pub fn relex(&mut self, range: Range<usize>, text: &str) -> Result<(), LexError> {
+        let start_of = |lexer: &Lexer, token: &Token| -> usize {
+            lexer.offset(token.line_num(), token.char_num())
+        };
+        let first: usize = self
+            .tokens
+            .iter()
+            .position(|x| start_of(self, x) >= range.start)
+            .unwrap_or(self.tokens.len());
+        let restart: usize = first.saturating_sub(2);
+        let tail: usize = self.tokens[first..]
+            .iter()
+            .position(|x| start_of(self, x) >= range.end)
+            .map_or(self.tokens.len(), |x| first + x);
+        let old_end: (i64, i64) = self.position(range.end);
+
+        // Only the lines in the range change, the ones after it move over
+        let removed: Range<usize> = self.line_starts.partition_point(|x| *x <= range.start)
+            ..self.line_starts.partition_point(|x| *x <= range.end);
+        let moved: isize = text.len() as isize - range.len() as isize;
+        for start in &mut self.line_starts[removed.end..] {
+            *start = (*start as isize + moved) as usize;
+        }
+        let added: Vec<usize> = text
+            .match_indices('\n')
+            .map(|(index, _)| range.start + index + 1)
+            .collect();
+        self.line_starts.splice(removed, added);
+        self.contents.replace_range(range.clone(), text);
+
+        if !self.complete {
+            self.tokens.clear();
+            return self.lexer();
+        }
+        let new_end: (i64, i64) = self.position(range.start + text.len());
+        let shift = |(line, column): (i64, i64)| -> (i64, i64) {
+            if line == old_end.0 {
+                (new_end.0, column - old_end.1 + new_end.1)
+            } else {
+                (line + new_end.0 - old_end.0, column)
+            }
+        };
+        let mut after: Vec<Token> = self.tokens.split_off(tail);
+        for token in &mut after {
+            let (start_line, start_col): (i64, i64) =
+                shift((token.span.start_line, token.span.start_col));
+            let (end_line, end_col): (i64, i64) = shift((token.span.end_line, token.span.end_col));
+            token.span = Span {
+                start_line,
+                start_col,
+                end_line,
+                end_col,
+            };
+        }
+        // The tokens before the change are where they were, so where to start is too.
+        // If there isn't one before it, it starts from the top
+        let (offset, line_num, column): (usize, i64, i64) = match self.tokens.get(restart) {
+            Some(token) if restart < first => {
+                (start_of(self, token), token.line_num(), token.char_num())
+            }
+            _ => (0, 1, 1),
+        };
+        self.tokens.truncate(restart);
+
+        let mut stream: TokenStream = new_token_stream(&self.contents[offset..]);
+        stream.line_num = line_num;
+        stream.column = column;
+        stream.keep_comments = self.keep_comments;
+        stream.interner = std::mem::take(&mut self.interner);
+        let mut old = after.into_iter().peekable();
+        while let Some(token) = stream.next() {
+            let token: Token = match token {
+                Ok(token) => token,
+                Err(error) => {
+                    self.interner = stream.interner;
+                    self.complete = false;
+                    return Err(error);
+                }
+            };
+            let at: (i64, i64) = (token.line_num(), token.char_num());
+            if at >= new_end {
+                while old
+                    .peek()
+                    .is_some_and(|x| (x.line_num(), x.char_num()) < at)
+                {
+                    old.next();
+                }
+                // Everything from here on lexes the same as it did
+                if old.peek() == Some(&token) {
+                    self.interner = stream.interner;
+                    self.tokens.extend(old);
+                    return Ok(());
+                }
+            }
+            self.tokens.push(token);
+        }
+        self.interner = stream.interner;
+        Ok(())
+    }
+}
+
+/// Lexes the source lazily, only reading as far as it needs to for the next token.
+/// Nothing gets copied but the text of each token
+pub struct TokenStream<'a> {
+    src: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    line_num: i64,
+    /// The column of the next character, which starts over after each newline
+    column: i64,
+    /// Give back comments as Comment tokens instead of skipping them
+    pub keep_comments: bool,
+    /// Set after an error, so nothing else comes out after it
+    done: bool,
+    /// Where the text of the tokens is kept, so the same text is only kept once
+    pub interner: Interner,
+}
+
+impl TokenStream<'_> {
+    /// Move past the next character, giving back where it was along with its line and column
+    fn bump(&mut self) -> Option<(usize, char, i64, i64)> {
+        let (index, ch): (usize, char) = self.chars.next()?;
+        let place: (usize, char, i64, i64) = (index, ch, self.line_num, self.column);
+        if ch == '\n' {
+            self.line_num += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(place)
+    }
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, x)| *x)
+    }
+    /// The byte index of the next character, or the end of the source
+    fn next_index(&mut self) -> usize {
+        self.chars.peek().map_or(self.src.len(), |(x, _)| *x)
+    }
+    /// Read the rest of a string after its opening quote, with the escapes
+    /// already turned into the characters they stand for
+    fn string(&mut self, start: usize, line_num: i64, column: i64) -> Result<Token, LexError> {
+        let unterminated: LexError = LexError {
+            code: "E0001",
+            message: "unterminated string".to_string(),
+            line_num,
+            char_num: column,
+        };
+        loop {
+            match self.bump() {
+                // Strings have to end on the same line they start on
+                None | Some((_, '\n', _, _)) => return Err(unterminated),
+                Some((end, '"', _, end_col)) => {
+                    let raw: &str = &self.src[start + 1..end];
+                    let at = |code: &'static str, (message, offset): (String, usize)| LexError {
+                        code,
+                        message,
+                        line_num,
+                        char_num: column + offset as i64 + 1,
+                    };
+                    unescape(raw).map_err(|x| at("E0002", x))?;
+                    let mut token: Tokens = Tokens::Str;
+                    let mut text: String = String::new();
+                    // The holes get lexed here so a mistake in one is found like any other
+                    for piece in format_pieces(raw).map_err(|x| at("E0006", x))? {
+                        match piece {
+                            FormatPiece::Hole(offset, hole) => {
+                                lex_hole(&hole, line_num, column + offset as i64 + 1)?;
+                                token = Tokens::Format;
+                            }
+                            FormatPiece::Text(piece) => text.push_str(&piece),
+                        }
+                    }
+                    let part: Text = match token {
+                        Tokens::Format => self.interner.text(raw),
+                        _ => self.interner.text(&unescape(&text).unwrap_or(text)),
+                    };
+                    return Ok(Token {
+                        token,
+                        part,
+                        span: Span {
+                            start_line: line_num,
+                            start_col: column,
+                            end_line: line_num,
+                            end_col,
+                        },
+                    });
+                }
+                // Skip the character after an escape, so `\"` doesn't end the string
+                Some((_, '\\', _, _)) => {
+                    if matches!(self.bump(), None | Some((_, '\n', _, _))) {
+                        return Err(unterminated);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    /// Read the rest of a `#` comment up to the end of the line or a `/* */` comment up
+    /// to where it closes. The newline after a `#` comment is left
+    fn comment(&mut self, start: usize, line_num: i64, column: i64) -> Result<Token, LexError> {
+        let mut end_col: i64 = column;
+        if &self.src[start..=start] == "#" {
+            while matches!(self.peek(), Some(x) if x != '\n') {
+                end_col = self.bump().map_or(end_col, |x| x.3);
+            }
+        } else {
+            // Past the `*` of the opening
+            self.bump();
+            let mut last: char = ' ';
+            loop {
+                match self.bump() {
+                    Some((_, '/', _, col)) if last == '*' => {
+                        end_col = col;
+                        break;
+                    }
+                    Some((_, ch, _, _)) => last = ch,
+                    None => {
+                        return Err(LexError {
+                            code: "E0003",
+                            message: "unterminated comment".to_string(),
+                            line_num,
+                            char_num: column,
+                        })
+                    }
+                }
+            }
+        }
+        let end: usize = self.next_index();
+        Ok(Token {
+            part: self.interner.text(&self.src[start..end]),
+            token: Tokens::Comment,
+            span: Span {
+                start_line: line_num,
+                start_col: column,
+                // The line count has already moved past any newlines in the comment
+                end_line: self.line_num,
+                end_col,
+            },
+        })
+    }
+    /// Read the rest of a part that doesn't have whitespace in it, like a name,
+    /// a number or an operator
+    fn part(
+        &mut self,
+        start: usize,
+        ch: char,
+        line_num: i64,
+        column: i64,
+    ) -> Result<Token, LexError> {
+        let mut current: char = ch;
+        let mut end_col: i64 = column;
+        loop {
+            let next: char = self.peek().unwrap_or(' ');
+            let end: usize = self.next_index();
+            let part: &str = &self.src[start..end];
+            let after: char = self.chars.clone().nth(1).map_or(' ', |(_, x)| x);
+            if (ends_token(current, next) && !continues_exponent(part, next))
+                || ends_at_range(part, next, after)
+            {
+                let (token, text): (Tokens, &str) = word_token(part);
+                if is_invalid_number(part) {
+                    return Err(LexError {
+                        code: "E0004",
+                        message: format!("invalid number '{}'", part),
+                        line_num,
+                        char_num: column,
+                    });
+                }
+                if token == Tokens::Identifier && !is_identifier(part) {
+                    return Err(LexError {
+                        code: "E0005",
+                        message: format!("invalid name '{}'", part),
+                        line_num,
+                        char_num: column,
+                    });
+                }
+                return Ok(Token {
+                    token,
+                    part: self.interner.text(text),
+                    span: Span {
+                        start_line: line_num,
+                        start_col: column,
+                        end_line: line_num,
+                        end_col,
+                    },
+                });
+            }
+            // There's always a next character here, since the end of the source ends the part
+            if let Some((_, ch, _, col)) = self.bump() {
+                current = ch;
+                end_col = col;
+            }
+        }
+    }
+}
+
+impl Iterator for TokenStream<'_> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            let (index, ch, line_num, column): (usize, char, i64, i64) = self.bump()?;
+            let token: Result<Token, LexError> = match ch {
+                _ if is_char_whitespace(ch) => continue,
+                '#' => self.comment(index, line_num, column),
+                '/' if self.peek() == Some('*') => self.comment(index, line_num, column),
+                // Strings get read all at once, since the spaces inside of them are kept
+                '"' => self.string(index, line_num, column),
+                _ => self.part(index, ch, line_num, column),
+            };
+            match token {
+                Ok(token) if token.token == Tokens::Comment && !self.keep_comments => continue,
+                Ok(token) => return Some(Ok(token)),
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+/// Make a stream of the tokens in the source, which lexes as it's read
+pub fn new_token_stream(src: &str) -> TokenStream<'_> {
+    TokenStream {
+        src,
+        chars: src.char_indices().peekable(),
+        line_num: 1,
+        column: 1,
+        keep_comments: false,
+        done: false,
+        interner: Interner::default(),
+    }
+}
+
+/// Turn the escapes in the inside of a string into the characters they stand for,
+/// an unknown escape gives back the message and which character it starts on
+pub fn unescape(raw: &str) -> Result<String, (String, usize)> {
+    let mut text: String = String::new();
+    let mut chars = raw.chars().enumerate();
+    while let Some((offset, ch)) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some((_, 'n')) => text.push('\n'),
+            Some((_, 't')) => text.push('\t'),
+            Some((_, '\\')) => text.push('\\'),
+            Some((_, '"')) => text.push('"'),
+            Some((_, other)) => return Err((format!("unknown escape '\\{}'", other), offset)),
+            None => return Err(("unterminated string".to_string(), offset)),
+        }
+    }
+    Ok(text)
+}
+
+/// A piece of the inside of a string, the text with its escapes still in it, or a
+/// hole with the character it starts on and what's in it
+#[derive(PartialEq, Debug, Clone)]
+pub enum FormatPiece {
+    Text(String),
+    Hole(usize, String),
+}
+
+/// Split the inside of a string into its text and the holes in braces like `{x}`,
+/// `{{` and `}}` are a brace in the text like in a python f-string. A brace without
+/// its other half gives back the message and which character it's on
+pub fn format_pieces(raw: &str) -> Result<Vec<FormatPiece>, (String, usize)> {
+    let mut pieces: Vec<FormatPiece> = Vec::new();
+    let mut text: String = String::new();
+    let mut chars = raw.chars().enumerate().peekable();
+    while let Some((offset, ch)) = chars.next() {
+        let doubled: bool = matches!(chars.peek(), Some((_, x)) if *x == ch);
+        match ch {
+            '\\' => {
+                text.push(ch);
+                text.extend(chars.next().map(|(_, x)| x));
+            }
+            '{' | '}' if doubled => {
+                chars.next();
+                text.push(ch);
+            }
+            '}' => return Err(("unmatched '}' in a string".to_string(), offset)),
+            '{' => {
+                let mut hole: String = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, '{')) | None => {
+                            return Err(("unmatched '{' in a string".to_string(), offset))
+                        }
+                        Some((_, x)) => hole.push(x),
+                    }
+                }
+                if hole.trim().is_empty() {
+                    return Err(("nothing in the '{}' of a string".to_string(), offset));
+                }
+                if !text.is_empty() {
+                    pieces.push(FormatPiece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(FormatPiece::Hole(offset + 1, hole));
+            }
+            _ => text.push(ch),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(FormatPiece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// Lex what's in a hole of a string, with the tokens and any error moved to where
+/// they are in the source. The column is where the hole starts
+pub fn lex_hole(hole: &str, line_num: i64, column: i64) -> Result<Vec<Token>, LexError> {
+    let mut lexer: Lexer = new_lexer(hole);
+    lexer.lexer().map_err(|error| LexError {
+        line_num,
+        char_num: error.char_num + column - 1,
+        ..error
+    })?;
+    for tok in &mut lexer.tokens {
+        tok.span = Span {
+            start_line: line_num,
+            start_col: tok.span.start_col + column - 1,
+            end_line: line_num,
+            end_col: tok.span.end_col + column - 1,
+        };
+    }
+    Ok(lexer.tokens)
+}
+
+/// The tokens in every hole of a Format token, for checks that go over the tokens
+pub fn hole_tokens(token: &Token) -> Vec<Token> {
+    format_pieces(&token.part)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|piece| match piece {
+            FormatPiece::Hole(offset, hole) => lex_hole(
+                &hole,
+                token.line_num(),
+                token.char_num() + offset as i64 + 1,
+            )
+            .ok(),
+            FormatPiece::Text(_) => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// An error found while lexing, along with where it happened
+#[derive(PartialEq, Debug, Clone)]
+pub struct LexError {
+    pub message: String,
+    /// Which kind of error it is, like `E0004`, so it can be looked up
+    pub code: &'static str,
+    pub line_num: i64,
+    pub char_num: i64,
+}
+
+/// Check for a number that runs straight into letters like `3x`, which is
+/// almost always a missing space or operator
+pub fn is_invalid_number(part: &str) -> bool {
+    part.starts_with(|x: char| is_char_numeric(x)) && tokenize(part) == Tokens::Identifier
+}
+
+/// Remove the boiler plate of making a lexer object
+pub fn new_lexer(contents: &str) -> Lexer {
+    let lexer: Lexer = Lexer {
+        contents: contents.to_string(),
+        tokens: Vec::new(),
+        keep_comments: false,
+        line_starts: find_line_starts(contents),
+        complete: false,
+        interner: Interner::default(),
+    };
+
+    lexer
+}
+
+/// A short explanation of what each token means, for people learning the language
+pub fn describe(token: Tokens) -> &'static str {
+    match token {
+        Tokens::Assign => "gives a variable its value",
+        Tokens::Var => "starts a constant like $PI",
+        Tokens::Set => "variable declaration keyword",
+        Tokens::Jump => "jump keyword",
+        Tokens::Const => "sets a name that can't be set again",
+        Tokens::Print => "prints the value that follows",
+        Tokens::If => "runs the block when the condition is true",
+        Tokens::Else => "runs the block when the condition before it is false",
+        Tokens::While => "runs the block for as long as the condition is true",
+        Tokens::Match => "runs the first arm with a value equal to the one after it",
+        Tokens::Try => "runs the block, going to the catch if there's an error in it",
+        Tokens::Catch => "runs the block with the name set to the error's message",
+        Tokens::For => "runs the block once for each item, with the name set to it",
+        Tokens::Func => "defines a function",
+        Tokens::Return => "gives back a value from a function",
+        Tokens::Break => "leaves the loop it's in",
+        Tokens::Continue => "goes on to the next time through the loop it's in",
+        Tokens::Global => "makes sets of the names change the variables outside of the block",
+        Tokens::Use => "runs another file and lets its names be used like `file.name`",
+        Tokens::Input => "reads a line that the user types",
+        Tokens::Exit => "stops the program with an exit code",
+        Tokens::Assert => "stops the program if the value is false",
+        Tokens::AssertEq => "stops the program if the two values aren't equal",
+        Tokens::Minus => "subtraction operator",
+        Tokens::Plus => "addition operator",
+        Tokens::Divide => "division operator",
+        Tokens::Multiply => "multiplication operator",
+        Tokens::Modulo => "remainder operator",
+        Tokens::Power => "exponent operator",
+        Tokens::LessThan => "less than comparison",
+        Tokens::GreaterThan => "greater than comparison",
+        Tokens::LessEqual => "less than or equal comparison",
+        Tokens::GreaterEqual => "greater than or equal comparison",
+        Tokens::Equal => "equality comparison",
+        Tokens::NotEqual => "inequality comparison",
+        Tokens::PlusAssign => "adds to a variable",
+        Tokens::MinusAssign => "subtracts from a variable",
+        Tokens::MultiplyAssign => "multiplies a variable",
+        Tokens::DivideAssign => "divides a variable",
+        Tokens::Increment => "adds one to a variable",
+        Tokens::Decrement => "subtracts one from a variable",
+        Tokens::Semi => "ends a statement",
+        Tokens::LeftBrace => "starts a block",
+        Tokens::RightBrace => "ends a block",
+        Tokens::LeftParen => "starts a group",
+        Tokens::RightParen => "ends a group",
+        Tokens::LeftBracket => "starts a list or an index",
+        Tokens::RightBracket => "ends a list or an index",
+        Tokens::Comma => "separates names or values",
+        Tokens::Colon => "separates a key from its value in a dict, or the two values after a '?'",
+        Tokens::Question => {
+            "picks the value before the ':' if the condition is true, otherwise the one after"
+        }
+        Tokens::Arrow => "separates what a match arm matches from what it runs",
+        Tokens::In => "checks if a value is in a list, a string or the keys of a dict",
+        Tokens::And => "the right side if the left is true, otherwise the left",
+        Tokens::Or => "the left side if it's true, otherwise the right",
+        Tokens::Not => "True if the value is false, False if it's true",
+        Tokens::Range => "makes the ints from the first number up to before the second",
+        Tokens::Identifier => "the name of a variable",
+        Tokens::Numeric => "a whole number",
+        Tokens::Float => "a number with a fraction or an exponent",
+        Tokens::Str => "a string of text",
+        Tokens::Format => "a string with the values in braces put into it",
+        Tokens::Bool => "true or false",
+        Tokens::Comment => "a comment, which is skipped",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_undefined;
+    use crate::ast::Expr;
+    use crate::codegen::python_expr;
+    use crate::parser::{new_parser, Parse, Parser};
+    use crate::{check, spacer};
+    use std::time::Instant;
+
+    #[test]
+    fn lexer_test() {
+        let mut lexer: Lexer = new_lexer("set a");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            lexer.tokens,
+            vec![
+                Token {
+                    part: Text::from("set"),
+                    token: Tokens::Set,
+                    span: Span {
+                        start_line: 1,
+                        start_col: 1,
+                        end_line: 1,
+                        end_col: 3,
+                    },
+                },
+                Token {
+                    part: Text::from("a"),
+                    token: Tokens::Identifier,
+                    span: Span {
+                        start_line: 1,
+                        start_col: 5,
+                        end_line: 1,
+                        end_col: 5,
+                    },
+                },
+            ]
+        );
+
+        let mut lexer: Lexer = new_lexer("jump -2");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            lexer.tokens,
+            vec![
+                Token {
+                    part: Text::from("jump"),
+                    token: Tokens::Jump,
+                    span: Span {
+                        start_line: 1,
+                        start_col: 1,
+                        end_line: 1,
+                        end_col: 4,
+                    },
+                },
+                Token {
+                    part: Text::from("-"),
+                    token: Tokens::Minus,
+                    span: Span {
+                        start_line: 1,
+                        start_col: 6,
+                        end_line: 1,
+                        end_col: 6,
+                    },
+                },
+                Token {
+                    part: Text::from("2"),
+                    token: Tokens::Numeric,
+                    span: Span {
+                        start_line: 1,
+                        start_col: 7,
+                        end_line: 1,
+                        end_col: 7,
+                    },
+                },
+            ]
+        );
+    }
+
+    /// Make a single line with about `count` tokens that uses lots of variables
+    fn long_line(count: usize) -> String {
+        let mut line: String = String::new();
+        for i in 0..count / 5 {
+            line.push_str(&format!("set v{} = {}; ", i, i));
+        }
+        line
+    }
+
+    #[test]
+    fn long_line_test() {
+        let contents: String = long_line(50_000);
+        let mut lexer: Lexer = new_lexer(&contents);
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens.len(), 50_000);
+        // The last token is the semicolon just before the trailing space
+        assert_eq!(
+            lexer.tokens.last().unwrap().char_num() as usize,
+            contents.len() - 1
+        );
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(parser.parse().unwrap().len(), 10_000);
+    }
+
+    /// Run with `cargo test long_line_bench -- --ignored --nocapture`, doubling
+    /// the size of the line should roughly double the time
+    #[test]
+    #[ignore]
+    fn long_line_bench() {
+        for count in [50_000, 100_000, 200_000].iter() {
+            let contents: String = long_line(*count);
+            let start: Instant = Instant::now();
+            let mut lexer: Lexer = new_lexer(&contents);
+            lexer.lexer().unwrap();
+            check_undefined(&lexer.tokens).unwrap();
+            new_parser(lexer.tokens).parse().unwrap();
+            println!("{} tokens: {:?}", count, start.elapsed());
+        }
+    }
+
+    #[test]
+    fn lex_fast_test() {
+        let contents: String = format!("{}\nif a {{\n  a += (1 - 2) * 3;\n}}", long_line(100));
+        let mut lexer: Lexer = new_lexer(&contents);
+        lexer.lexer().unwrap();
+        let full: Vec<(Tokens, String)> = lexer
+            .tokens
+            .into_iter()
+            .map(|x| (x.token, x.part.to_string()))
+            .collect();
+        assert_eq!(lex_fast(&contents), full);
+    }
+
+    /// Run with `cargo test lex_fast_bench -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn lex_fast_bench() {
+        let contents: String = long_line(200_000);
+
+        let start: Instant = Instant::now();
+        let mut lexer: Lexer = new_lexer(&contents);
+        lexer.lexer().unwrap();
+        println!("lexer: {:?}", start.elapsed());
+
+        let start: Instant = Instant::now();
+        lex_fast(&contents);
+        println!("lex_fast: {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn empty_test() {
+        for contents in ["", "   \n  ", "# just a comment\n"].iter() {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            assert_eq!(lexer.tokens, vec![]);
+            assert_eq!(lex_fast(contents), vec![]);
+
+            let mut parser: Parser = new_parser(lexer.tokens);
+            assert_eq!(parser.python(), Ok(vec![]));
+            assert_eq!(check(contents, None, false), Ok(()));
+        }
+    }
+
+    #[test]
+    fn comment_test() {
+        let contents: &str = "# setup\nset a = 1; # the first\nprint a;#done";
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let parts: Vec<&str> = lexer.tokens.iter().map(|x| x.part.as_str()).collect();
+        assert_eq!(parts, vec!["set", "a", "=", "1", ";", "print", "a", ";"]);
+        assert_eq!(lexer.tokens[5].line_num(), 3);
+        assert_eq!(lex_fast(contents).len(), 8);
+
+        let contents: &str = "set a = 1 /* one\ntwo */ + 2;/**/\nprint a/2;";
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let parts: Vec<&str> = lexer.tokens.iter().map(|x| x.part.as_str()).collect();
+        assert_eq!(
+            parts,
+            vec!["set", "a", "=", "1", "+", "2", ";", "print", "a", "/", "2", ";"]
+        );
+        assert_eq!(lexer.tokens[4].line_num(), 2);
+        assert_eq!(lexer.tokens[7].line_num(), 3);
+        assert_eq!(lex_fast(contents).len(), 12);
+
+        let mut lexer: Lexer = new_lexer("print 1;\n/* never closed\n");
+        assert_eq!(
+            lexer.lexer(),
+            Err(LexError {
+                code: "E0003",
+                message: "unterminated comment".to_string(),
+                line_num: 2,
+                char_num: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn shebang_test() {
+        let contents: &str = "#!/usr/bin/env -S mahou run\nprint 1;";
+        assert_eq!(shebang(contents), Some("#!/usr/bin/env -S mahou run"));
+        assert_eq!(shebang("print 1;\n#!/not/first"), None);
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens.len(), 3);
+    }
+
+    #[test]
+    fn keep_comments_test() {
+        let mut lexer: Lexer = new_lexer("# top\nset a = 1; /* a\nb */");
+        lexer.keep_comments = true;
+        lexer.lexer().unwrap();
+        let comments: Vec<(&str, Span)> = lexer
+            .tokens
+            .iter()
+            .filter(|x| x.token == Tokens::Comment)
+            .map(|x| (x.part.as_str(), x.span))
+            .collect();
+        assert_eq!(
+            comments,
+            vec![
+                (
+                    "# top",
+                    Span {
+                        start_line: 1,
+                        start_col: 1,
+                        end_line: 1,
+                        end_col: 5,
+                    }
+                ),
+                (
+                    "/* a\nb */",
+                    Span {
+                        start_line: 2,
+                        start_col: 12,
+                        end_line: 3,
+                        end_col: 4,
+                    }
+                ),
+            ]
+        );
+        assert_eq!(lexer.tokens.len(), 7);
+    }
+
+    #[test]
+    fn invalid_number_test() {
+        let mut lexer: Lexer = new_lexer("set a = 3x;");
+        assert_eq!(
+            lexer.lexer(),
+            Err(LexError {
+                code: "E0004",
+                message: "invalid number '3x'".to_string(),
+                line_num: 1,
+                char_num: 9,
+            })
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = 3 * x;");
+        assert_eq!(lexer.lexer(), Ok(()));
+
+        let mut lexer: Lexer = new_lexer("set a = 1_000;");
+        assert_eq!(lexer.lexer().unwrap_err().message, "invalid number '1_000'");
+
+        let mut lexer: Lexer = new_lexer("print 1;\nset a@b = 1;");
+        assert_eq!(
+            lexer.lexer(),
+            Err(LexError {
+                code: "E0005",
+                message: "invalid name 'a@b'".to_string(),
+                line_num: 2,
+                char_num: 5,
+            })
+        );
+        let mut lexer: Lexer = new_lexer("print !a;");
+        assert_eq!(lexer.lexer().unwrap_err().message, "invalid name '!'");
+    }
+
+    #[test]
+    fn float_test() {
+        let mut lexer: Lexer = new_lexer("set x = 3.14 + 1e-3 - 2.5E+2 * 1e3-1;");
+        lexer.lexer().unwrap();
+        let tokens: Vec<(Tokens, &str)> = lexer
+            .tokens
+            .iter()
+            .map(|x| (x.token, x.part.as_str()))
+            .collect();
+        assert_eq!(
+            tokens[3..],
+            [
+                (Tokens::Float, "3.14"),
+                (Tokens::Plus, "+"),
+                (Tokens::Float, "1e-3"),
+                (Tokens::Minus, "-"),
+                (Tokens::Float, "2.5E+2"),
+                (Tokens::Multiply, "*"),
+                (Tokens::Float, "1e3"),
+                (Tokens::Minus, "-"),
+                (Tokens::Numeric, "1"),
+                (Tokens::Semi, ";"),
+            ]
+        );
+        assert_eq!(
+            lex_fast("1e-3")
+                .into_iter()
+                .map(|(token, _)| token)
+                .collect::<Vec<Tokens>>(),
+            vec![Tokens::Float]
+        );
+
+        assert_eq!(
+            lex_fast("0..10 1.5")
+                .into_iter()
+                .map(|(token, _)| token)
+                .collect::<Vec<Tokens>>(),
+            vec![
+                Tokens::Numeric,
+                Tokens::Range,
+                Tokens::Numeric,
+                Tokens::Float
+            ]
+        );
+
+        let mut lexer: Lexer = new_lexer("set x = 1e;");
+        assert_eq!(lexer.lexer().unwrap_err().message, "invalid number '1e'");
+    }
+
+    #[test]
+    fn string_escape_test() {
+        let mut lexer: Lexer = new_lexer(r#"print "line1\nline2";"#);
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[1].token, Tokens::Str);
+        assert_eq!(lexer.tokens[1].part, "line1\nline2");
+        assert_eq!(lexer.tokens[2].char_num(), 21);
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![r#"print("line1\nline2")"#.to_string()])
+        );
+
+        for (source, text) in [
+            (r#""a\tb""#, "a\tb"),
+            (r#""a\\b""#, "a\\b"),
+            (r#""say \"hi\"""#, "say \"hi\""),
+            ("\"# not a comment\"", "# not a comment"),
+        ] {
+            let mut lexer: Lexer = new_lexer(source);
+            lexer.lexer().unwrap();
+            assert_eq!(lexer.tokens[0].part, text);
+            assert_eq!(python_expr(&Expr::Str(text.to_string())), source);
+            assert_eq!(lex_fast(source), vec![(Tokens::Str, text.to_string())]);
+        }
+
+        let mut lexer: Lexer = new_lexer(r#"print "a\qb";"#);
+        assert_eq!(
+            lexer.lexer(),
+            Err(LexError {
+                code: "E0002",
+                message: "unknown escape '\\q'".to_string(),
+                line_num: 1,
+                char_num: 9,
+            })
+        );
+        let mut lexer: Lexer = new_lexer("print \"abc;\nprint 1;");
+        assert_eq!(lexer.lexer().unwrap_err().message, "unterminated string");
+    }
+
+    #[test]
+    fn format_string_test() {
+        let mut lexer: Lexer = new_lexer(r#"print "x\t{x + 1} {{y}}";"#);
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[1].token, Tokens::Format);
+        assert_eq!(lexer.tokens[1].part, r#"x\t{x + 1} {{y}}"#);
+        assert_eq!(
+            format_pieces(&lexer.tokens[1].part),
+            Ok(vec![
+                FormatPiece::Text("x\\t".to_string()),
+                FormatPiece::Hole(4, "x + 1".to_string()),
+                FormatPiece::Text(" {y}".to_string()),
+            ])
+        );
+        // The tokens in a hole are where they are in the source
+        let holes: Vec<(&str, i64)> = hole_tokens(&lexer.tokens[1])
+            .iter()
+            .map(|x| (x.token, x.char_num()))
+            .map(|(token, column)| (describe(token), column))
+            .collect();
+        assert_eq!(
+            holes,
+            vec![
+                ("the name of a variable", 12),
+                ("addition operator", 14),
+                ("a whole number", 16)
+            ]
+        );
+        assert_eq!(
+            lex_fast(r#""{a}" "{{a}}""#),
+            vec![
+                (Tokens::Format, "{a}".to_string()),
+                (Tokens::Str, "{a}".to_string())
+            ]
+        );
+
+        let error = |contents: &str| -> (&'static str, String, i64) {
+            let error: LexError = new_lexer(contents).lexer().unwrap_err();
+            (error.code, error.message, error.char_num)
+        };
+        assert_eq!(
+            error("print \"a {b\";"),
+            ("E0006", "unmatched '{' in a string".to_string(), 10)
+        );
+        assert_eq!(
+            error("print \"a}\";"),
+            ("E0006", "unmatched '}' in a string".to_string(), 9)
+        );
+        assert_eq!(
+            error("print \"{ }\";"),
+            ("E0006", "nothing in the '{}' of a string".to_string(), 8)
+        );
+        assert_eq!(
+            error("print \"a {2x}\";"),
+            ("E0004", "invalid number '2x'".to_string(), 11)
+        );
+    }
+
+    /// A small xorshift generator, so the fuzz test is the same every run
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    /// Lex the input, and parse it if it lexes, failing with the input if anything panics
+    fn lex_without_panic(contents: &str) {
+        let result: std::thread::Result<()> = std::panic::catch_unwind(|| {
+            let mut lexer: Lexer = new_lexer(contents);
+            if lexer.lexer().is_ok() {
+                let _ = check_undefined(&lexer.tokens);
+                let _ = new_parser(lexer.tokens).parse();
+            }
+            lex_fast(contents);
+        });
+        assert!(result.is_ok(), "panicked on {:?}", contents);
+    }
+
+    #[test]
+    fn lexer_fuzz_test() {
+        // Biased toward the characters the lexer cares about, so more of the branches get hit
+        let alphabet: Vec<char> = "+-*/%<>=;$#{}(),\"\\ \t\nsetprintifwhileinputax013"
+            .chars()
+            .chain((32u8..127).map(char::from))
+            .collect();
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..3000 {
+            let len: usize = (xorshift(&mut state) % 40) as usize;
+            let contents: String = (0..len)
+                .map(|_| alphabet[(xorshift(&mut state) % alphabet.len() as u64) as usize])
+                .collect();
+            lex_without_panic(&contents);
+        }
+
+        let long_name: String = spacer(10_000, 'a');
+        for contents in [
+            "+-*/%<>=;$#{}(),",
+            "**=+=-=*=/=<<>>",
+            "<==>=!===!!",
+            "$",
+            "\"",
+            "\"\\",
+            "\"\\q\"",
+            "#",
+            "/*",
+            "/*/",
+            "*/",
+            "(((((",
+            ")))))",
+            "}}}}",
+            "set",
+            "set a =",
+            "if {",
+            "print -",
+            "3x",
+            long_name.as_str(),
+        ] {
+            lex_without_panic(contents);
+        }
+    }
+
+    #[test]
+    fn token_stream_test() {
+        // Tokens come out before the error later on has been read
+        let mut stream: TokenStream = new_token_stream("a+=-1 3x");
+        let parts: Vec<String> = stream
+            .by_ref()
+            .take(4)
+            .map(|x| x.unwrap().part.to_string())
+            .collect();
+        assert_eq!(parts, vec!["a", "+=", "-", "1"]);
+        assert_eq!(stream.next().unwrap().unwrap_err().code, "E0004");
+        assert!(stream.next().is_none());
+
+        // The last token can end right at the end of the source
+        let tokens: Vec<Token> = new_token_stream("print a")
+            .collect::<Result<Vec<Token>, LexError>>()
+            .unwrap();
+        assert_eq!(tokens[1].part, "a");
+        assert_eq!(tokens[1].span.end_col, 7);
+    }
+
+    #[test]
+    fn normalize_tokens_test() {
+        let mut first: Lexer = new_lexer("set a=1;\nwhile a<10 {a+=1;}");
+        first.lexer().unwrap();
+        let mut second: Lexer = new_lexer("set a = 1;   # start\nwhile a < 10 {\n    a += 1;\n}\n");
+        second.lexer().unwrap();
+        assert_ne!(first.tokens, second.tokens);
+        assert_eq!(
+            normalize_tokens(&first.tokens),
+            normalize_tokens(&second.tokens)
+        );
+
+        let mut third: Lexer = new_lexer("set b = 1;\nwhile b < 10 {b+=1;}");
+        third.lexer().unwrap();
+        assert_ne!(
+            normalize_tokens(&first.tokens),
+            normalize_tokens(&third.tokens)
+        );
+    }
+
+    #[test]
+    fn span_test() {
+        let mut lexer: Lexer = new_lexer("set abc = \"hi there\";\nprint abc ** 2;");
+        lexer.lexer().unwrap();
+        let spans: Vec<(i64, i64, i64, i64)> = lexer
+            .tokens
+            .iter()
+            .map(|x| {
+                (
+                    x.span.start_line,
+                    x.span.start_col,
+                    x.span.end_line,
+                    x.span.end_col,
+                )
+            })
+            .collect();
+        assert_eq!(spans[0], (1, 1, 1, 3));
+        assert_eq!(spans[1], (1, 5, 1, 7));
+        // The span of a string has both of the quotes
+        assert_eq!(spans[3], (1, 11, 1, 20));
+        assert_eq!(spans[7], (2, 11, 2, 12));
+        assert_eq!(
+            (lexer.tokens[7].line_num(), lexer.tokens[7].char_num()),
+            (2, 11)
+        );
+    }
+
+    #[test]
+    fn column_test() {
+        // Columns start over on each line, and a tab is one character
+        let mut lexer: Lexer = new_lexer("set a = 1;\n\tprint \"x\";\n/* a\nb */ a += 1;");
+        lexer.lexer().unwrap();
+        let columns: Vec<(&str, i64, i64)> = lexer
+            .tokens
+            .iter()
+            .map(|x| (x.part.as_str(), x.line_num(), x.char_num()))
+            .collect();
+        assert_eq!(
+            &columns[5..],
+            &[
+                ("print", 2, 2),
+                ("x", 2, 8),
+                (";", 2, 11),
+                ("a", 4, 6),
+                ("+=", 4, 8),
+                ("1", 4, 11),
+                (";", 4, 12),
+            ]
+        );
+        assert_eq!(lexer.tokens[6].span.end_col, 10);
+
+        let mut lexer: Lexer = new_lexer("print 1;\n  print \"a\\q\";");
+        assert_eq!(
+            lexer.lexer(),
+            Err(LexError {
+                code: "E0002",
+                message: "unknown escape '\\q'".to_string(),
+                line_num: 2,
+                char_num: 11,
+            })
+        );
+    }
+
+    #[test]
+    fn token_start_line_test() {
+        let mut lexer: Lexer = new_lexer("set abc\n= 1;\n\nprint abc;");
+        lexer.lexer().unwrap();
+        let lines: Vec<(&str, i64, i64)> = lexer
+            .tokens
+            .iter()
+            .map(|x| (x.part.as_str(), x.line_num(), x.char_num()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("set", 1, 1),
+                ("abc", 1, 5),
+                ("=", 2, 1),
+                ("1", 2, 3),
+                (";", 2, 4),
+                ("print", 4, 1),
+                ("abc", 4, 7),
+                (";", 4, 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_test() {
+        assert_eq!(tokenize("set"), Tokens::Set);
+        assert_eq!(tokenize("+"), Tokens::Plus);
+        assert_eq!(tokenize("++"), Tokens::Increment);
+        assert_eq!(tokenize("1"), Tokens::Numeric);
+        assert_eq!(tokenize("a"), Tokens::Identifier);
+        // Digits can go in a name after the first character
+        assert_eq!(tokenize("a1"), Tokens::Identifier);
+        assert_eq!(tokenize("_a_2"), Tokens::Identifier);
+        assert_eq!(tokenize("12"), Tokens::Numeric);
+        assert_eq!(tokenize("1.5"), Tokens::Float);
+        assert!(is_identifier("a1") && is_identifier("é"));
+        assert!(!is_identifier("1a") && !is_identifier("a-b") && !is_identifier(""));
+        assert!(is_identifier("shapes.area") && !is_identifier("a.b.c") && !is_identifier("a."));
+        assert_eq!(tokenize("+="), Tokens::PlusAssign);
+        assert_eq!(tokenize("/="), Tokens::DivideAssign);
+        assert_eq!(tokenize("<="), Tokens::LessEqual);
+        assert_eq!(tokenize("!="), Tokens::NotEqual);
+
+        let tokens: Vec<Tokens> = lex_fast("a>=1==b!=c<d")
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Tokens::Identifier,
+                Tokens::GreaterEqual,
+                Tokens::Numeric,
+                Tokens::Equal,
+                Tokens::Identifier,
+                Tokens::NotEqual,
+                Tokens::Identifier,
+                Tokens::LessThan,
+                Tokens::Identifier,
+            ]
+        );
+    }
+
+    #[test]
+    fn token_kind_test() {
+        assert_eq!(TokenKind::from(Tokens::Set), TokenKind::Keyword);
+        assert_eq!(TokenKind::from(Tokens::Print), TokenKind::Keyword);
+        assert_eq!(TokenKind::from(Tokens::PlusAssign), TokenKind::Operator);
+        assert_eq!(TokenKind::from(Tokens::Numeric), TokenKind::Literal);
+        assert_eq!(TokenKind::from(Tokens::Bool), TokenKind::Literal);
+        assert_eq!(TokenKind::from(Tokens::Identifier), TokenKind::Identifier);
+        assert_eq!(TokenKind::from(Tokens::Semi), TokenKind::Punctuation);
+        assert_eq!(TokenKind::from(Tokens::Var), TokenKind::Constant);
+    }
+
+    #[test]
+    fn relex_test() {
+        // Relexing has to end up with the same tokens and error as lexing it all again
+        let same = |lexer: &Lexer, result: Result<(), LexError>| {
+            let mut fresh: Lexer = new_lexer(lexer.contents());
+            assert_eq!(result, fresh.lexer(), "{:?}", lexer.contents());
+            assert_eq!(lexer.tokens, fresh.tokens, "{:?}", lexer.contents());
+            assert_eq!(lexer.line_starts, find_line_starts(lexer.contents()));
+        };
+
+        let mut lexer: Lexer = new_lexer("set a = 1;\nprint a + 22;\n");
+        lexer.lexer().unwrap();
+        let edits: [(Range<usize>, &str); 5] = [
+            (4..5, "total"),
+            (0..0, "# hi\n"),
+            (10..19, "2;\nset b"),
+            (16..16, "\""),
+            (16..17, ""),
+        ];
+        for (range, text) in edits.iter() {
+            let result: Result<(), LexError> = lexer.relex(range.clone(), text);
+            same(&lexer, result);
+        }
+        let lines: Lexer = new_lexer("ab\ncé\nd");
+        assert_eq!(lines.offset(2, 2), 4);
+        assert_eq!(lines.position(4), (2, 2));
+        assert_eq!(lines.offset(2, 9), 6);
+        assert_eq!(lines.position(7), (3, 1));
+
+        // Random edits of a random program, with the pieces that change how the
+        // tokens around them lex
+        let source: String =
+            crate::formatter::ast_source(&crate::generate::new_generator(3).program(30));
+        let mut lexer: Lexer = new_lexer(&source);
+        lexer.lexer().unwrap();
+        let pieces: [&str; 13] = [
+            "a", " ", "\n", "\"", "1", ".", "+", "=", "# ", "/*", "*/", "{", "",
+        ];
+        let mut seed: u64 = 7;
+        let mut next = |count: usize| -> usize {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % count.max(1) as u64) as usize
+        };
+        for _ in 0..500 {
+            let start: usize = next(lexer.contents().len() + 1);
+            let end: usize = (start + next(4)).min(lexer.contents().len());
+            let text: &str = pieces[next(pieces.len())];
+            let result: Result<(), LexError> = lexer.relex(start..end, text);
+            same(&lexer, result);
+        }
+    }
+}
@@ -0,0 +1,761 @@
+//! Mahou is a source-to-source compiler from a small language to python, it can
+//! also run the programs itself. The CLI in main.rs is a thin wrapper over this
+
+pub mod analysis;
+pub mod ast;
+pub mod bench;
+pub mod bytecode;
+pub mod codegen;
+pub mod debugger;
+pub mod diagnostics;
+pub mod doc;
+pub mod dump;
+pub mod embed;
+pub mod error;
+#[cfg(feature = "mahou-ffi")]
+pub mod ffi;
+pub mod formatter;
+pub mod generate;
+pub mod highlight;
+pub mod intern;
+pub mod interpreter;
+pub mod keywords;
+pub mod lexer;
+pub mod log;
+pub mod lsp;
+pub mod man;
+pub mod modules;
+pub mod optimize;
+pub mod parser;
+pub mod profile;
+pub mod project;
+pub mod python;
+pub mod semantic;
+pub mod sourcemap;
+pub mod stdlib;
+pub mod testing;
+pub mod trace;
+pub mod types;
+pub mod watch;
+
+use crate::analysis::{check_division_by_zero, check_undefined};
+use crate::ast::{Expr, Position, Stmt};
+use crate::bytecode::{compile_program, decode_program, encode_program, new_vm, FunctionCode, Vm};
+use crate::codegen::{python_codegen, PrintStyle};
+use crate::debugger::new_debugger;
+use crate::diagnostics::Diagnostic;
+use crate::error::MahouError;
+use crate::formatter::{format_source, keep_shebang};
+use crate::interpreter::{
+    call_stack_size, new_interpreter, runtime_error, Interpret, Interpreter, RuntimeError,
+};
+use crate::lexer::{new_lexer, Lex, Lexer};
+use crate::modules::{load_modules, parse_file, parse_file_with_starts};
+use crate::parser::{new_parser, Parse, ParseError};
+use crate::profile::{new_profiler, report, Profile, Profiler};
+use crate::semantic::{check_constants, check_loops, check_semantics, Consts};
+use crate::types::check_types;
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub fn spacer(num: usize, ch: char) -> String {
+    let mut space: String = String::new();
+    for _ in 0..num {
+        space.push(ch);
+    }
+    space
+}
+
+/// Lex, check and parse the program and the modules it uses, with any error
+/// already rendered for showing
+fn parse_source(
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<Vec<Stmt>, String> {
+    let stmts: Vec<Stmt> = parse_file(contents, filename, zero_based)?;
+    load_modules(stmts, contents, filename, zero_based)
+}
+
+/// Only lex and parse the program without generating anything, for validating in
+/// CI or on every save. Names that aren't set, consts and the modules it uses
+/// aren't looked at, typecheck does those. The filename is only for showing where
+/// an error is
+pub fn check(contents: &str, filename: Option<&str>, zero_based: bool) -> Result<(), MahouError> {
+    let mut lexer: Lexer = new_lexer(contents);
+    if let Err(error) = lexer.lexer() {
+        return Err(Diagnostic::from(&error)
+            .render(contents, filename, zero_based)
+            .into());
+    }
+    let render = |error: &ParseError| -> String {
+        Diagnostic::from(error).render(contents, filename, zero_based)
+    };
+    new_parser(&lexer.tokens)
+        .parse_all()
+        .map(|_| ())
+        .map_err(|errors| MahouError::Diagnostic(errors.iter().map(render).collect()))
+}
+
+/// Check the program like check, then check that the types of everything go
+/// together, giving back every type error found
+pub fn typecheck(
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<(), MahouError> {
+    let stmts: Vec<Stmt> = parse_source(contents, filename, zero_based)?;
+    // It already lexed once, the tokens are only for where the errors are
+    let mut lexer: Lexer = new_lexer(contents);
+    let _ = lexer.lexer();
+    let errors: Vec<ParseError> = check_types(&stmts, &lexer.tokens);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(MahouError::Diagnostic(
+        errors
+            .iter()
+            .map(|error| Diagnostic::from(error).render(contents, filename, zero_based))
+            .collect(),
+    ))
+}
+
+/// Check each program with check, or with typecheck without syntax, on up to jobs
+/// threads at once. The programs are the filename to show and the source, and the
+/// results are in the same order as them. The ones no thread could be started
+/// for are that error
+pub fn check_all(
+    programs: &[(String, String)],
+    syntax: bool,
+    zero_based: bool,
+    jobs: usize,
+) -> Vec<Result<(), MahouError>> {
+    // Each thread takes the next program that no one has taken yet
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<(), MahouError>>>> =
+        Mutex::new(vec![None; programs.len()]);
+    let aliases: BTreeMap<String, String> = keywords::aliases();
+    let mut failed: Option<MahouError> = None;
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, programs.len().max(1)) {
+            let (next, results, aliases) = (&next, &results, &aliases);
+            let started = thread::Builder::new()
+                // The same as the main thread gets, for programs nested deep
+                .stack_size(8 * 1024 * 1024)
+                .spawn_scoped(scope, move || {
+                    keywords::set_aliases(aliases.clone());
+                    loop {
+                        let index: usize = next.fetch_add(1, Ordering::Relaxed);
+                        let (filename, contents) = match programs.get(index) {
+                            Some(program) => program,
+                            None => return,
+                        };
+                        let result: Result<(), MahouError> = if syntax {
+                            check(contents, Some(filename), zero_based)
+                        } else {
+                            typecheck(contents, Some(filename), zero_based)
+                        };
+                        results.lock().unwrap()[index] = Some(result);
+                    }
+                });
+            // The threads that did start still take every program
+            if let Err(error) = started {
+                failed = Some(MahouError::Thread(format!(
+                    "couldn't start a thread to check with: {}",
+                    error
+                )));
+            }
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|x| {
+            x.unwrap_or_else(|| {
+                Err(failed.clone().unwrap_or_else(|| {
+                    MahouError::Thread("couldn't start a thread to check with".to_string())
+                }))
+            })
+        })
+        .collect()
+}
+
+/// Call f on a thread with enough stack for function calls to go max_depth deep, so
+/// a high `--max-depth` ends with the recursion error instead of overflowing the
+/// real stack. It's an error if there isn't that much memory for the stack, and a
+/// panic in f carries on in the thread that called this
+pub fn with_call_stack<T: Send>(
+    max_depth: usize,
+    f: impl FnOnce() -> T + Send,
+) -> Result<T, MahouError> {
+    let aliases: BTreeMap<String, String> = keywords::aliases();
+    thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .stack_size(call_stack_size(max_depth))
+            .spawn_scoped(scope, move || {
+                keywords::set_aliases(aliases);
+                f()
+            })
+            .map_err(|error| {
+                MahouError::Thread(format!(
+                    "couldn't get enough stack for calls {} deep: {}",
+                    max_depth, error
+                ))
+            })?;
+        Ok(handle
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+    })
+}
+
+/// Print the program back out with consistent spacing and indents, only if it parses
+pub fn format(
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<String, MahouError> {
+    let mut lexer: Lexer = new_lexer(contents);
+    if let Err(error) = lexer.lexer() {
+        return Err(Diagnostic::from(&error)
+            .render(contents, filename, zero_based)
+            .into());
+    }
+    new_parser(&lexer.tokens).parse_all().map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| Diagnostic::from(error).render(contents, filename, zero_based))
+            .collect::<String>()
+    })?;
+    Ok(keep_shebang(contents, format_source(&lexer.tokens)))
+}
+
+/// Turn the program into python
+pub fn compile(contents: &str) -> Result<String, MahouError> {
+    let stmts: Vec<Stmt> = parse_source(contents, None, false)?;
+    Ok(python_codegen(&stmts, PrintStyle::Function))
+}
+
+/// Compile the program to bytecode, for writing to a .mhc file
+pub fn build(contents: &str, filename: Option<&str>) -> Result<Vec<u8>, MahouError> {
+    let stmts: Vec<Stmt> = parse_source(contents, filename, false)?;
+    let program: FunctionCode =
+        compile_program(&stmts).map_err(|error| error.render(contents, filename, false))?;
+    Ok(encode_program(&program))
+}
+
+/// The exit code a run ends with, 0 unless the program used `exit`
+fn exit_code(
+    result: Result<(), RuntimeError>,
+    render: impl Fn(&RuntimeError) -> String,
+) -> Result<i32, String> {
+    match result {
+        Ok(()) => Ok(0),
+        Err(RuntimeError {
+            exit: Some(code), ..
+        }) => Ok(code),
+        Err(error) => Err(render(&error)),
+    }
+}
+
+/// Run bytecode from a .mhc file on the vm, giving back the exit code. There's
+/// no source to show, so errors only say the line they happened on
+pub fn run_bytecode<R: BufRead, W: Write>(
+    bytes: &[u8],
+    input: R,
+    output: W,
+) -> Result<i32, MahouError> {
+    run_bytecode_with_args(bytes, &[], None, input, output)
+}
+
+/// Run bytecode like run_bytecode, with the arguments the program gets as `$ARGS`
+/// and the seed for its random numbers if there is one
+pub fn run_bytecode_with_args<R: BufRead, W: Write>(
+    bytes: &[u8],
+    args: &[String],
+    seed: Option<u64>,
+    input: R,
+    output: W,
+) -> Result<i32, MahouError> {
+    let program: FunctionCode =
+        decode_program(bytes).map_err(|message| format!("error: {}\n", message))?;
+    let mut vm: Vm<R, W> = new_vm(input, output);
+    vm.args = args.to_vec();
+    if let Some(seed) = seed {
+        vm.random = seed;
+    }
+    exit_code(vm.run(&program), |error| match error.position {
+        Some(position) => format!(
+            "error[E0300]: {} on line {}\n",
+            error.message, position.line_num
+        ),
+        None => format!("error[E0300]: {}\n", error.message),
+    })
+    .map_err(MahouError::from)
+}
+
+/// Compile the program from source and run it on the vm, with the arguments and
+/// seed like run_bytecode_with_args. Since the source is there, errors show where
+/// they are in it the way the interpreter's do
+pub fn run_on_vm<R: BufRead, W: Write>(
+    contents: &str,
+    filename: Option<&str>,
+    args: &[String],
+    seed: Option<u64>,
+    input: R,
+    output: W,
+) -> Result<i32, MahouError> {
+    let stmts: Vec<Stmt> = parse_source(contents, filename, false)?;
+    let render = |error: &RuntimeError| error.render(contents, filename, false);
+    let program: FunctionCode = compile_program(&stmts).map_err(|error| render(&error))?;
+    let mut vm: Vm<R, W> = new_vm(input, output);
+    vm.args = args.to_vec();
+    if let Some(seed) = seed {
+        vm.random = seed;
+    }
+    Ok(exit_code(vm.run(&program), render)?)
+}
+
+/// Run the program with the interpreter, reading input and writing what it prints,
+/// and give back the exit code
+pub fn run<R: BufRead, W: Write>(contents: &str, input: R, output: W) -> Result<i32, MahouError> {
+    let stmts: Vec<Stmt> = parse_source(contents, None, false)?;
+    let mut interpreter: Interpreter<R, W> = new_interpreter(input, output);
+    Ok(exit_code(interpreter.run(&stmts), |error| {
+        error.render(contents, None, false)
+    })?)
+}
+
+/// Parse the program along with where each statement starts, for a hook that needs
+/// the lines. What is what can't handle modules, for the error
+fn parse_with_starts(
+    contents: &str,
+    filename: Option<&str>,
+    what: &str,
+) -> Result<(Vec<Stmt>, Vec<Position>), String> {
+    let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+        parse_file_with_starts(contents, filename, false)?;
+    // Loading the modules moves the statements, so they'd lose their lines
+    if let Some(Stmt::Use { position, .. }) = stmts.iter().find(|x| matches!(x, Stmt::Use { .. })) {
+        return Err(runtime_error(format!(
+            "{} can't run a program that uses modules yet",
+            what
+        ))
+        .at(*position)
+        .render(contents, filename, false));
+    }
+    Ok((stmts, starts))
+}
+
+/// Run the program in the debugger, which reads its commands from the input along
+/// with the program, and give back the exit code
+pub fn debug<R: BufRead, W: Write>(
+    contents: &str,
+    filename: Option<&str>,
+    seed: Option<u64>,
+    input: R,
+    output: W,
+) -> Result<i32, MahouError> {
+    let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+        parse_with_starts(contents, filename, "the debugger")?;
+    let mut interpreter: Interpreter<R, W> = new_interpreter(input, output);
+    interpreter.hook = Some(Box::new(new_debugger(contents, &stmts, &starts)));
+    if let Some(seed) = seed {
+        interpreter.random = seed;
+    }
+    Ok(exit_code(interpreter.run(&stmts), |error| {
+        error.render(contents, filename, false)
+    })?)
+}
+
+/// Run the program with the interpreter while timing each line, giving back the
+/// exit code along with the report of which lines took the longest. The report is
+/// there even if the program had an error
+pub fn profile<R: BufRead, W: Write>(
+    contents: &str,
+    filename: Option<&str>,
+    args: &[String],
+    seed: Option<u64>,
+    input: R,
+    output: W,
+) -> Result<(Result<i32, MahouError>, String), MahouError> {
+    let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+        parse_with_starts(contents, filename, "the profiler")?;
+    let profiler: Profiler = new_profiler(contents, &stmts, &starts);
+    let lines: Profile = profiler.profile.clone();
+    let mut interpreter: Interpreter<R, W> = new_interpreter(input, output);
+    interpreter.args = args.to_vec();
+    interpreter.hook = Some(Box::new(profiler));
+    if let Some(seed) = seed {
+        interpreter.random = seed;
+    }
+    let code: Result<i32, MahouError> = exit_code(interpreter.run(&stmts), |error| {
+        error.render(contents, filename, false)
+    })
+    .map_err(MahouError::from);
+    let report: String = report(&lines.borrow(), contents);
+    Ok((code, report))
+}
+
+/// Lex anything at all, for fuzzing. It can give back an error, but it should
+/// never panic
+pub fn fuzz_lex(data: &[u8]) {
+    let contents: String = String::from_utf8_lossy(data).into_owned();
+    let mut lexer: Lexer = new_lexer(&contents);
+    let _ = lexer.lexer();
+}
+
+/// Lex, check, parse and format anything at all, for fuzzing. Like fuzz_lex it
+/// should never panic
+pub fn fuzz_parse(data: &[u8]) {
+    let contents: String = String::from_utf8_lossy(data).into_owned();
+    let mut lexer: Lexer = new_lexer(&contents);
+    if lexer.lexer().is_err() {
+        return;
+    }
+    let _ = check_undefined(&lexer.tokens);
+    let _ = format_source(&lexer.tokens);
+    if let Ok(stmts) = new_parser(&lexer.tokens).parse_all() {
+        let _ = check_constants(&stmts, &lexer.tokens, &mut Consts::new());
+        let _ = check_division_by_zero(&stmts);
+        let _ = check_loops(&stmts);
+        let _ = check_semantics(&stmts, &lexer.tokens);
+        let _ = check_types(&stmts, &lexer.tokens);
+    }
+}
+
+/// Print the value of the expression the program ends in, like a calculator, unless
+/// it's calling a function from the program. For a program from the command line
+pub fn print_last(stmts: &mut Vec<Stmt>) {
+    let defines = |name: &str| {
+        stmts
+            .iter()
+            .any(|x| matches!(x, Stmt::Func { name: func, .. } if func == name))
+    };
+    let print: bool = match stmts.last() {
+        Some(Stmt::Expr(Expr::Call { name, .. })) => !defines(name),
+        Some(Stmt::Expr(_)) => true,
+        _ => false,
+    };
+    if print {
+        if let Some(Stmt::Expr(expr)) = stmts.pop() {
+            stmts.push(Stmt::Print { exprs: vec![expr] });
+        }
+    }
+}
+
+/// Run a program from the command line, printing what it ends in like print_last.
+/// The seed is where its random numbers start, if it's given one
+pub fn eval<R: BufRead, W: Write>(
+    contents: &str,
+    seed: Option<u64>,
+    input: R,
+    output: W,
+) -> Result<i32, MahouError> {
+    let filename: Option<&str> = Some("<eval>");
+    let mut stmts: Vec<Stmt> = parse_source(contents, filename, false)?;
+    print_last(&mut stmts);
+
+    let mut interpreter: Interpreter<R, W> = new_interpreter(input, output);
+    if let Some(seed) = seed {
+        interpreter.random = seed;
+    }
+    Ok(exit_code(interpreter.run(&stmts), |error| {
+        error.render(contents, filename, false)
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    /// Output that whatever was reading it has closed, like `| head` after its lines
+    struct Closed;
+
+    impl Write for Closed {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn check_all_test() {
+        let programs: Vec<(String, String)> = (0..20)
+            .map(|x| {
+                let contents: &str = if x % 3 == 0 {
+                    "set a = ;"
+                } else {
+                    "set a = 1;"
+                };
+                (format!("{}.m", x), contents.to_string())
+            })
+            .collect();
+        for jobs in [1, 4] {
+            let results: Vec<Result<(), MahouError>> = check_all(&programs, true, false, jobs);
+            assert_eq!(results.len(), 20);
+            for (index, result) in results.iter().enumerate() {
+                match result {
+                    Err(diagnostic) => {
+                        assert_eq!(index % 3, 0);
+                        assert!(diagnostic.to_string().contains(&format!("{}.m:1:", index)));
+                    }
+                    Ok(()) => assert_ne!(index % 3, 0),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_test() {
+        // Inputs that used to panic
+        for data in ["for", "for "] {
+            fuzz_parse(data.as_bytes());
+        }
+
+        // Pieces of programs and bytes put together at random, the same every time
+        let pieces: [&str; 24] = [
+            "set", "for", "in", "func", "if", "else", "while", "print", "a", "1.5", "\"{a}\"", "(",
+            ")", "[", "]", "{", "}", ";", ",", "=", "+=", "++", "..", "/*",
+        ];
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..2000 {
+            let mut data: Vec<u8> = Vec::new();
+            for _ in 0..next() % 12 {
+                match next() % 4 {
+                    0 => data.push((next() % 256) as u8),
+                    _ => data.extend(pieces[next() as usize % pieces.len()].bytes()),
+                }
+                data.push(b' ');
+            }
+            fuzz_lex(&data);
+            fuzz_parse(&data);
+        }
+    }
+
+    #[test]
+    fn check_test() {
+        assert_eq!(check("set a = 1;\nprint a + 2;", None, false), Ok(()));
+
+        let diagnostic: String = check("set a = (1 + 2;", None, false)
+            .unwrap_err()
+            .to_string();
+        assert!(diagnostic.starts_with("error[E0102]: unmatched '('"));
+
+        let diagnostic: String = check("set a = 3x;", None, false).unwrap_err().to_string();
+        assert!(diagnostic.starts_with("error[E0004]: invalid number '3x'"));
+
+        let diagnostic: String = check("print (b;", Some("main.m"), false)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            diagnostic,
+            "error[E0102]: unmatched '('\n --> main.m:1:7\n  |\n1 | print (b;\n  |       ^\n"
+        );
+
+        // Only the syntax, so a name that isn't set, a const that's set again or a
+        // module that isn't there are fine
+        assert_eq!(check("print b;", None, false), Ok(()));
+        assert_eq!(check("const A = 1;\nset A = 2;", None, false), Ok(()));
+        assert_eq!(check("use nowhere;", None, false), Ok(()));
+        // Every syntax error is there
+        let diagnostic: String = check("set = 1;\nprint (;", None, false)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(diagnostic.matches("error[").count(), 2);
+    }
+
+    #[test]
+    fn typecheck_test() {
+        assert_eq!(typecheck("set a = 1;\nprint a * 2.5;", None, false), Ok(()));
+        assert_eq!(
+            typecheck("set a = \"b\";\nprint a - 1;", Some("main.m"), false),
+            Err(MahouError::Diagnostic("error[E0202]: can't use '-' on a string and an int\n --> main.m:2:9\n  |\n2 | print a - 1;\n  |         ^\n".to_string()))
+        );
+        assert!(typecheck("print b;", None, false)
+            .unwrap_err()
+            .to_string()
+            .starts_with("error[E0200]: variable 'b' is not defined"));
+    }
+
+    #[test]
+    fn format_test() {
+        assert_eq!(
+            format("set a=0 ;\nif a {print a;}", None, false),
+            Ok("set a = 0;\nif a {\n  print a;\n}\n".to_string())
+        );
+        // Undefined variables are fine, it only has to parse
+        assert_eq!(
+            format("print b;", None, false),
+            Ok("print b;\n".to_string())
+        );
+        assert!(format("set a = (1;", None, false)
+            .unwrap_err()
+            .to_string()
+            .starts_with("error[E0102]: unmatched '('"));
+    }
+
+    #[test]
+    fn compile_test() {
+        assert_eq!(
+            compile("set a = 1;\nprint a + 2;"),
+            Ok("a = 1\nprint(a + 2)\n".to_string())
+        );
+        assert!(compile("print 1 % 0;")
+            .unwrap_err()
+            .to_string()
+            .starts_with("error[E0201]: modulo by zero"));
+        assert!(compile("print b;")
+            .unwrap_err()
+            .to_string()
+            .starts_with("error[E0200]: variable 'b' is not defined"));
+    }
+
+    #[test]
+    fn build_test() {
+        let bytes: Vec<u8> = build("set a = 6;\nprint a * 7;", None).unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(run_bytecode(&bytes, &b""[..], &mut output), Ok(0));
+        assert_eq!(String::from_utf8(output).unwrap(), "42\n");
+
+        // Stopping quietly once the output is closed instead of it being an error
+        let bytes: Vec<u8> = build("while 1 { print 1; }", None).unwrap();
+        assert_eq!(run_bytecode(&bytes, &b""[..], Closed), Ok(0));
+
+        let bytes: Vec<u8> = build("while 1 { exit 4; }", None).unwrap();
+        assert_eq!(run_bytecode(&bytes, &b""[..], Vec::new()), Ok(4));
+
+        let bytes: Vec<u8> = build("set a = 0;\nprint 1 / a;", None).unwrap();
+        assert_eq!(
+            run_bytecode(&bytes, &b""[..], Vec::new()),
+            Err(MahouError::Diagnostic(
+                "error[E0300]: division by zero on line 2\n".to_string()
+            ))
+        );
+        // From source it knows the file and the column
+        assert_eq!(
+            run_on_vm(
+                "set a = 0;\nprint 1 / a;",
+                Some("main.m"),
+                &[],
+                None,
+                &b""[..],
+                Vec::new()
+            ),
+            Err(MahouError::Diagnostic(
+                "error[E0300]: division by zero\n --> main.m:2:9\n  |\n2 | print 1 / a;\n  |         ^\n"
+                    .to_string()
+            ))
+        );
+        let mut output: Vec<u8> = Vec::new();
+        let args: Vec<String> = vec!["x".to_string()];
+        assert_eq!(
+            run_on_vm(
+                "print $ARGS;\nexit 2;",
+                None,
+                &args,
+                None,
+                &b""[..],
+                &mut output
+            ),
+            Ok(2)
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), "['x']\n");
+        assert_eq!(
+            run_bytecode(b"print 1;", &b""[..], Vec::new()),
+            Err(MahouError::Diagnostic(
+                "error: not a mahou bytecode file\n".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn eval_test() {
+        let eval_output = |contents: &str| -> Result<String, MahouError> {
+            let mut output: Vec<u8> = Vec::new();
+            eval(contents, None, &b""[..], &mut output)?;
+            Ok(String::from_utf8(output).unwrap())
+        };
+        assert_eq!(
+            eval_output("set a = 2; print a * 3;"),
+            Ok("6\n".to_string())
+        );
+        assert_eq!(eval_output("2 ** 10"), Ok("1024\n".to_string()));
+        assert_eq!(eval_output("max(1, 4)"), Ok("4\n".to_string()));
+        assert_eq!(
+            eval_output("func f() { print 1; }\nf();"),
+            Ok("1\n".to_string())
+        );
+        // The same seed picks the same numbers on the interpreter and the vm
+        let picked = |seed: Option<u64>| -> String {
+            let mut output: Vec<u8> = Vec::new();
+            eval(
+                "print rand_int(1, 1000000), rand();",
+                seed,
+                &b""[..],
+                &mut output,
+            )
+            .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        assert_eq!(picked(Some(7)), picked(Some(7)));
+        assert_ne!(picked(Some(7)), picked(Some(8)));
+        let bytes: Vec<u8> = build("print rand_int(1, 1000000), rand();", None).unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        run_bytecode_with_args(&bytes, &[], Some(7), &b""[..], &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), picked(Some(7)));
+        assert!(eval_output("print b;")
+            .unwrap_err()
+            .to_string()
+            .contains(" --> <eval>:1:7\n"));
+    }
+
+    #[test]
+    fn run_test() {
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(
+            run(
+                "set a = input;\nprint a;\nprint 6 * 7;",
+                &b"hi\n"[..],
+                &mut output
+            ),
+            Ok(0)
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), "hi\n42\n");
+
+        // An exit stops the program, even from inside of a function
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(
+            run(
+                "func stop() { exit 3; }\nprint 1;\nstop();\nprint 2;",
+                &b""[..],
+                &mut output
+            ),
+            Ok(3)
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), "1\n");
+
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(
+            run("set a = 0;\nprint 1 / a;", &b""[..], &mut output),
+            Err(MahouError::Diagnostic(
+                "error[E0300]: division by zero\n --> 2:9\n  |\n2 | print 1 / a;\n  |         ^\n"
+                    .to_string()
+            ))
+        );
+
+        assert_eq!(run("while 1 { print 1; }", &b""[..], Closed), Ok(0));
+    }
+}
@@ -0,0 +1,123 @@
+//! Messages on stderr about what mahou is doing, for finding out where something
+//! goes wrong. `-v` shows each step of getting the program ready, `-vv` each token
+//! and statement that gets made, and `-vvv` every step the program takes when it runs
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// How much gets shown, each level shows everything the ones before it do
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum Level {
+    /// Only what the program prints and errors, not even warnings
+    Quiet,
+    /// Warnings too, which is what's shown without any flags
+    Normal,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The level for how many times `-v` was given, `-q` wins over any of them
+    pub fn from_flags(verbose: u8, quiet: bool) -> Level {
+        match verbose {
+            _ if quiet => Level::Quiet,
+            0 => Level::Normal,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+    fn from_u8(level: u8) -> Level {
+        match level {
+            0 => Level::Quiet,
+            1 => Level::Normal,
+            2 => Level::Info,
+            3 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+    /// What goes before the message, along with the ANSI color it's shown in
+    fn label(self) -> (&'static str, &'static str) {
+        match self {
+            Level::Quiet | Level::Normal => ("", ""),
+            Level::Info => ("info", "\x1b[32m"),
+            Level::Debug => ("debug", "\x1b[34m"),
+            Level::Trace => ("trace", "\x1b[90m"),
+        }
+    }
+}
+
+// There's only one of these for the whole program, like stderr itself
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Color the label of each message, for when stderr is a terminal
+pub fn set_color(color: bool) {
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+/// Check if messages of the level get shown, so the work of making one can be skipped
+pub fn enabled(level: Level) -> bool {
+    self::level() >= level
+}
+
+/// The line a message is shown as, like `debug: parsed (print a)`
+pub fn line(level: Level, message: &str, color: bool) -> String {
+    let (label, code): (&str, &str) = level.label();
+    if color {
+        format!("{}{}\x1b[0m: {}", code, label, message)
+    } else {
+        format!("{}: {}", label, message)
+    }
+}
+
+/// Show the message if its level is on. It's a function so the message is only
+/// made when it's going to be shown
+pub fn log(level: Level, message: impl FnOnce() -> String) {
+    if enabled(level) {
+        eprintln!("{}", line(level, &message(), COLOR.load(Ordering::Relaxed)));
+    }
+}
+
+pub fn info(message: impl FnOnce() -> String) {
+    log(Level::Info, message);
+}
+
+pub fn debug(message: impl FnOnce() -> String) {
+    log(Level::Debug, message);
+}
+
+pub fn trace(message: impl FnOnce() -> String) {
+    log(Level::Trace, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_test() {
+        assert_eq!(Level::from_flags(0, false), Level::Normal);
+        assert_eq!(Level::from_flags(2, false), Level::Debug);
+        assert_eq!(Level::from_flags(5, false), Level::Trace);
+        assert_eq!(Level::from_flags(3, true), Level::Quiet);
+        assert!(Level::Trace > Level::Info && Level::Quiet < Level::Normal);
+
+        assert_eq!(
+            line(Level::Debug, "parsed (print a)", false),
+            "debug: parsed (print a)"
+        );
+        assert_eq!(
+            line(Level::Info, "lexed 4 tokens", true),
+            "\x1b[32minfo\x1b[0m: lexed 4 tokens"
+        );
+    }
+}
@@ -0,0 +1,862 @@
+//! A language server for editors, `mahou lsp` talks the language server protocol
+//! over stdin and stdout. It shows the errors from the lexer and the parser as the
+//! file changes, goes to where a variable is first set, and on hover shows the type
+//! of a variable and where it was last set
+
+use crate::ast::Stmt;
+use crate::diagnostics::underline_len;
+use crate::dump::Json;
+use crate::lexer::{new_lexer, Lex, LexError, Lexer, Token, Tokens};
+use crate::parser::{new_parser, Parse, ParseError};
+use crate::types::{set_targets, variable_types, Type};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// JSON sent by the editor, objects keep their keys in the order they came in
+#[derive(PartialEq, Debug, Clone)]
+pub enum Message {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Message>),
+    Object(Vec<(String, Message)>),
+}
+
+impl Message {
+    /// Follow the keys down through objects, like `["textDocument", "uri"]`
+    pub fn get(&self, keys: &[&str]) -> Option<&Message> {
+        keys.iter().try_fold(self, |message, key| match message {
+            Message::Object(fields) => fields.iter().find(|x| x.0 == *key).map(|x| &x.1),
+            _ => None,
+        })
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Message::Str(text) => Some(text),
+            _ => None,
+        }
+    }
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Message::Number(number) => Some(*number as i64),
+            _ => None,
+        }
+    }
+}
+
+/// Reads JSON a character at a time
+struct JsonReader<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl JsonReader<'_> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(ch) if ch.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(ch) if ch == expected => Ok(()),
+            Some(ch) => Err(format!("expected '{}' but found '{}'", expected, ch)),
+            None => Err(format!("expected '{}' but the message ended", expected)),
+        }
+    }
+    fn word(&mut self, word: &str, value: Message) -> Result<Message, String> {
+        for expected in word.chars() {
+            if self.chars.next() != Some(expected) {
+                return Err(format!("expected '{}'", word));
+            }
+        }
+        Ok(value)
+    }
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut text: String = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('b') => text.push('\u{8}'),
+                    Some('f') => text.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                        let code: u32 = u32::from_str_radix(&hex, 16)
+                            .map_err(|_| format!("bad escape '\\u{}'", hex))?;
+                        // Half of a surrogate pair can't be a char on its own
+                        text.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    Some(ch) => text.push(ch),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(ch) => text.push(ch),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+    fn value(&mut self) -> Result<Message, String> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some('n') => self.word("null", Message::Null),
+            Some('t') => self.word("true", Message::Bool(true)),
+            Some('f') => self.word("false", Message::Bool(false)),
+            Some('"') => Ok(Message::Str(self.string()?)),
+            Some('[') => {
+                self.chars.next();
+                let mut items: Vec<Message> = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&']') {
+                    self.chars.next();
+                    return Ok(Message::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => {}
+                        Some(']') => return Ok(Message::Array(items)),
+                        _ => return Err("expected ',' or ']' in an array".to_string()),
+                    }
+                }
+            }
+            Some('{') => {
+                self.chars.next();
+                let mut fields: Vec<(String, Message)> = Vec::new();
+                self.skip_whitespace();
+                if self.chars.peek() == Some(&'}') {
+                    self.chars.next();
+                    return Ok(Message::Object(fields));
+                }
+                loop {
+                    let key: String = self.string()?;
+                    self.expect(':')?;
+                    fields.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some(',') => {}
+                        Some('}') => return Ok(Message::Object(fields)),
+                        _ => return Err("expected ',' or '}' in an object".to_string()),
+                    }
+                }
+            }
+            Some(ch) if ch == '-' || ch.is_ascii_digit() => {
+                let mut number: String = String::new();
+                while let Some(ch) = self.chars.peek().copied() {
+                    if !(ch.is_ascii_digit() || "+-.eE".contains(ch)) {
+                        break;
+                    }
+                    number.push(ch);
+                    self.chars.next();
+                }
+                number
+                    .parse::<f64>()
+                    .map(Message::Number)
+                    .map_err(|_| format!("bad number '{}'", number))
+            }
+            Some(ch) => Err(format!("unexpected '{}'", ch)),
+            None => Err("expected a value but the message ended".to_string()),
+        }
+    }
+}
+
+/// Read a message the editor sent, which has to be all of the text
+pub fn parse_json(text: &str) -> Result<Message, String> {
+    let mut reader: JsonReader = JsonReader {
+        chars: text.chars().peekable(),
+    };
+    let message: Message = reader.value()?;
+    reader.skip_whitespace();
+    match reader.chars.next() {
+        None => Ok(message),
+        Some(ch) => Err(format!("unexpected '{}' after the message", ch)),
+    }
+}
+
+/// Read the next message, which is a `Content-Length` header, a blank line and then
+/// that many bytes. Gives back None once the editor closes the input
+pub fn read_message(input: &mut dyn BufRead) -> Result<Option<String>, String> {
+    let mut length: Option<usize> = None;
+    loop {
+        let mut line: String = String::new();
+        let read: usize = input
+            .read_line(&mut line)
+            .map_err(|error| format!("could not read a message: {}", error))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let line: &str = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse().ok();
+        }
+    }
+    let length: usize = length.ok_or("a message is missing its Content-Length")?;
+    let mut body: Vec<u8> = vec![0; length];
+    input
+        .read_exact(&mut body)
+        .map_err(|error| format!("could not read a message: {}", error))?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|_| "a message isn't utf-8".to_string())
+}
+
+pub fn write_message(output: &mut dyn Write, message: &Json) -> Result<(), String> {
+    let body: String = message.pretty(0);
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+        .and_then(|_| output.flush())
+        .map_err(|error| format!("could not send a message: {}", error))
+}
+
+/// A position in the protocol, which counts lines and characters from zero
+fn position(line: i64, character: i64) -> Json {
+    Json::Object(vec![
+        ("line", Json::Number(line)),
+        ("character", Json::Number(character)),
+    ])
+}
+
+/// The range a token covers, the span's end is the last character of it
+fn token_range(token: &Token) -> Json {
+    Json::Object(vec![
+        (
+            "start",
+            position(token.line_num() - 1, token.char_num() - 1),
+        ),
+        ("end", position(token.span.end_line - 1, token.span.end_col)),
+    ])
+}
+
+/// Every error the lexer or the parser finds in a file that's been lexed, with the
+/// whole token it's on underlined. The error is the one from lexing it if there was one
+pub fn diagnostics(lexer: &Lexer, error: Option<&LexError>) -> Vec<Json> {
+    let errors: Vec<ParseError> = match error {
+        Some(error) => vec![ParseError {
+            message: error.message.to_owned(),
+            code: error.code,
+            line_num: error.line_num,
+            char_num: error.char_num,
+        }],
+        None => new_parser(&lexer.tokens)
+            .parse_all()
+            .err()
+            .unwrap_or_default(),
+    };
+    let lines: Vec<&str> = lexer.contents().lines().collect();
+    errors
+        .iter()
+        .map(|error| {
+            let line: &str = lines
+                .get(error.line_num as usize - 1)
+                .copied()
+                .unwrap_or("");
+            let length: usize = underline_len(line, error.char_num.max(1) as usize);
+            let start: i64 = error.char_num - 1;
+            Json::Object(vec![
+                (
+                    "range",
+                    Json::Object(vec![
+                        ("start", position(error.line_num - 1, start)),
+                        ("end", position(error.line_num - 1, start + length as i64)),
+                    ]),
+                ),
+                ("severity", Json::Number(1)),
+                ("code", Json::Str(error.code.to_string())),
+                ("source", Json::Str("mahou".to_string())),
+                ("message", Json::Str(error.message.to_owned())),
+            ])
+        })
+        .collect()
+}
+
+/// The name under the cursor, the end of a name counts too since that's where the
+/// cursor is after typing it
+fn name_at(tokens: &[Token], line: i64, character: i64) -> Option<&Token> {
+    tokens.iter().find(|x| {
+        x.token == Tokens::Identifier
+            && x.line_num() - 1 == line
+            && x.char_num() - 1 <= character
+            && character <= x.span.end_col
+    })
+}
+
+/// The names that are defined somewhere, each set, function and parameter, in the
+/// order they are in the source
+fn definitions(tokens: &[Token]) -> Vec<&Token> {
+    let mut found: Vec<&Token> = set_targets(tokens);
+    let mut in_params: bool = false;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.token {
+            Tokens::Identifier if index > 0 && tokens[index - 1].token == Tokens::Func => {
+                found.push(token);
+                in_params = true;
+            }
+            Tokens::Identifier if in_params => found.push(token),
+            Tokens::RightParen | Tokens::LeftBrace => in_params = false,
+            _ => {}
+        }
+    }
+    found.sort_by_key(|x| (x.line_num(), x.char_num()));
+    found
+}
+
+/// Where the name under the cursor is first set, or the function it calls
+pub fn definition(lexer: &Lexer, line: i64, character: i64) -> Option<Json> {
+    let tokens: &[Token] = &lexer.tokens;
+    let name: &Token = name_at(tokens, line, character)?;
+    let definition: &Token = *definitions(tokens).iter().find(|x| x.part == name.part)?;
+    Some(token_range(definition))
+}
+
+/// What the variable under the cursor is, with its type if it's known and the last
+/// place it was set before the cursor
+pub fn hover(lexer: &Lexer, line: i64, character: i64) -> Option<Json> {
+    let tokens: &[Token] = &lexer.tokens;
+    let name: &Token = name_at(tokens, line, character)?;
+    let same: Vec<&Token> = definitions(tokens)
+        .into_iter()
+        .filter(|x| x.part == name.part)
+        .collect();
+    let last: &Token = same
+        .iter()
+        .rev()
+        .find(|x| x.line_num() <= name.line_num())
+        .or_else(|| same.first())?;
+
+    let is_function: bool = tokens
+        .windows(2)
+        .any(|x| x[0].token == Tokens::Func && x[1].part == name.part);
+    let kind: String = if is_function {
+        "a function".to_string()
+    } else {
+        let stmts: Vec<Stmt> = new_parser(tokens).parse().unwrap_or_default();
+        match variable_types(&stmts, tokens).get(name.part.as_str()) {
+            Some(Type::Unknown) | None => "a variable".to_string(),
+            Some(kind) => kind.name().to_string(),
+        }
+    };
+    let source: &str = lexer
+        .contents()
+        .lines()
+        .nth(last.line_num() as usize - 1)
+        .unwrap_or("")
+        .trim();
+    let text: String = format!(
+        "`{}` is {}\n\nset on line {}: `{}`",
+        name.part,
+        kind,
+        last.line_num(),
+        source
+    );
+    Some(Json::Object(vec![
+        (
+            "contents",
+            Json::Object(vec![
+                ("kind", Json::Str("markdown".to_string())),
+                ("value", Json::Str(text)),
+            ]),
+        ),
+        ("range", token_range(name)),
+    ]))
+}
+
+/// A column counted in chars, from one counted in UTF-16 code units on the same
+/// line like the editor sends. Past the end of the line it keeps going one each
+fn char_column(line: &str, units: i64) -> i64 {
+    let mut seen: i64 = 0;
+    for (index, ch) in line.chars().enumerate() {
+        if seen >= units {
+            return index as i64;
+        }
+        seen += ch.len_utf16() as i64;
+    }
+    line.chars().count() as i64 + (units - seen).max(0)
+}
+
+/// A column counted in UTF-16 code units, from one counted in chars
+fn utf16_column(line: &str, chars: i64) -> i64 {
+    let count: i64 = line.chars().count() as i64;
+    let units: usize = line
+        .chars()
+        .take(chars.max(0) as usize)
+        .map(char::len_utf16)
+        .sum();
+    units as i64 + (chars - count).max(0)
+}
+
+/// Change each position in what's sent back to count UTF-16 code units, the
+/// lexer counts chars
+fn to_utf16(json: &mut Json, contents: &str) {
+    match json {
+        Json::Array(items) => items.iter_mut().for_each(|x| to_utf16(x, contents)),
+        Json::Object(fields) => {
+            let line: Option<i64> = fields.iter().find_map(|(key, value)| match value {
+                Json::Number(line) if *key == "line" => Some(*line),
+                _ => None,
+            });
+            for (key, value) in fields.iter_mut() {
+                match (line, *key, &mut *value) {
+                    (Some(line), "character", Json::Number(character)) => {
+                        let text: &str = contents.lines().nth(line as usize).unwrap_or("");
+                        *character = utf16_column(text, *character);
+                    }
+                    _ => to_utf16(value, contents),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An open file, lexed as it changes so a change only lexes again around itself
+struct Document {
+    lexer: Lexer,
+    /// What went wrong the last time it was lexed
+    error: Option<LexError>,
+}
+
+fn open_document(contents: &str) -> Document {
+    let mut lexer: Lexer = new_lexer(contents);
+    let error: Option<LexError> = lexer.lexer().err();
+    Document { lexer, error }
+}
+
+impl Document {
+    /// Make a change from the editor, with a range it's only that part of the file,
+    /// from the line and character it starts at to where it ends. Without one it's
+    /// all of the file. The characters are UTF-16 code units unless `utf16` is off
+    fn change(&mut self, change: &Message, utf16: bool) {
+        let text: &str = match change.get(&["text"]).and_then(Message::as_str) {
+            Some(text) => text,
+            None => return,
+        };
+        let at = |end: &str| -> Option<usize> {
+            let line: i64 = change.get(&["range", end, "line"])?.as_i64()?;
+            let character: i64 = change.get(&["range", end, "character"])?.as_i64()?;
+            let character: i64 = if utf16 {
+                char_column(self.line(line), character)
+            } else {
+                character
+            };
+            Some(self.lexer.offset(line + 1, character + 1))
+        };
+        match (at("start"), at("end")) {
+            (Some(start), Some(end)) if start <= end => {
+                self.error = self.lexer.relex(start..end, text).err();
+            }
+            _ => *self = open_document(text),
+        }
+    }
+    /// The text of a line counting from zero, or nothing past the end
+    fn line(&self, line: i64) -> &str {
+        self.lexer
+            .contents()
+            .lines()
+            .nth(line as usize)
+            .unwrap_or("")
+    }
+}
+
+/// The open files by their uri, and whether the editor has asked it to shut down
+pub struct Server {
+    documents: HashMap<String, Document>,
+    pub shutdown: bool,
+    /// Whether characters in positions are UTF-16 code units, which is what the
+    /// editor uses unless it says it can count chars
+    utf16: bool,
+}
+
+pub fn new_server() -> Server {
+    Server {
+        documents: HashMap::new(),
+        shutdown: false,
+        utf16: true,
+    }
+}
+
+/// The id of a request as it's sent back, which is a number or a string
+fn id_json(id: &Message) -> Json {
+    match id {
+        Message::Number(number) => Json::Number(*number as i64),
+        Message::Str(text) => Json::Str(text.to_owned()),
+        _ => Json::Null,
+    }
+}
+
+fn notification(method: &str, params: Json) -> Json {
+    Json::Object(vec![
+        ("jsonrpc", Json::Str("2.0".to_string())),
+        ("method", Json::Str(method.to_string())),
+        ("params", params),
+    ])
+}
+
+impl Server {
+    /// The errors in a file, or none once it's closed so they go away
+    fn publish(&self, uri: &str) -> Json {
+        let mut found: Json = Json::Array(
+            self.documents
+                .get(uri)
+                .map_or(Vec::new(), |x| diagnostics(&x.lexer, x.error.as_ref())),
+        );
+        if let (true, Some(document)) = (self.utf16, self.documents.get(uri)) {
+            to_utf16(&mut found, document.lexer.contents());
+        }
+        notification(
+            "textDocument/publishDiagnostics",
+            Json::Object(vec![
+                ("uri", Json::Str(uri.to_string())),
+                ("diagnostics", found),
+            ]),
+        )
+    }
+    /// The file and the position in it a request is about
+    fn at(&self, message: &Message) -> Option<(&Lexer, i64, i64)> {
+        let uri: &str = message.get(&["params", "textDocument", "uri"])?.as_str()?;
+        let line: i64 = message.get(&["params", "position", "line"])?.as_i64()?;
+        let character: i64 = message
+            .get(&["params", "position", "character"])?
+            .as_i64()?;
+        let document: &Document = self.documents.get(uri)?;
+        let character: i64 = if self.utf16 {
+            char_column(document.line(line), character)
+        } else {
+            character
+        };
+        Some((&document.lexer, line, character))
+    }
+    /// Send back a position the lexer gave in the units the editor counts in
+    fn encode(&self, message: &Message, mut json: Json) -> Json {
+        let uri: Option<&str> = message
+            .get(&["params", "textDocument", "uri"])
+            .and_then(Message::as_str);
+        if let (true, Some(document)) = (self.utf16, uri.and_then(|x| self.documents.get(x))) {
+            to_utf16(&mut json, document.lexer.contents());
+        }
+        json
+    }
+    /// Handle one message from the editor, giving back the messages to send to it
+    pub fn handle(&mut self, message: &Message) -> Vec<Json> {
+        let method: &str = message
+            .get(&["method"])
+            .and_then(Message::as_str)
+            .unwrap_or("");
+        let uri: Option<String> = message
+            .get(&["params", "textDocument", "uri"])
+            .and_then(Message::as_str)
+            .map(|x| x.to_string());
+        let result: Result<Json, String> = match method {
+            "initialize" => {
+                // Counting chars is what the lexer does, so that's used if the
+                // editor can, otherwise it's UTF-16 which every editor has to have
+                let utf32: bool = match message.get(&[
+                    "params",
+                    "capabilities",
+                    "general",
+                    "positionEncodings",
+                ]) {
+                    Some(Message::Array(offered)) => {
+                        offered.iter().any(|x| x.as_str() == Some("utf-32"))
+                    }
+                    _ => false,
+                };
+                self.utf16 = !utf32;
+                Ok(Json::Object(vec![
+                    (
+                        "capabilities",
+                        Json::Object(vec![
+                            // The editor only sends the part of the text that changed
+                            (
+                                "positionEncoding",
+                                Json::Str(if utf32 { "utf-32" } else { "utf-16" }.to_string()),
+                            ),
+                            ("textDocumentSync", Json::Number(2)),
+                            ("definitionProvider", Json::Bool(true)),
+                            ("hoverProvider", Json::Bool(true)),
+                        ]),
+                    ),
+                    (
+                        "serverInfo",
+                        Json::Object(vec![
+                            ("name", Json::Str("mahou".to_string())),
+                            ("version", Json::Str(env!("CARGO_PKG_VERSION").to_string())),
+                        ]),
+                    ),
+                ]))
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Json::Null)
+            }
+            "textDocument/didOpen" | "textDocument/didChange" | "textDocument/didClose" => {
+                let uri: String = match uri {
+                    Some(uri) => uri,
+                    None => return Vec::new(),
+                };
+                match method {
+                    "textDocument/didOpen" => {
+                        let text: &str = message
+                            .get(&["params", "textDocument", "text"])
+                            .and_then(Message::as_str)
+                            .unwrap_or("");
+                        self.documents.insert(uri.to_owned(), open_document(text));
+                    }
+                    // Each change is from after the ones before it
+                    "textDocument/didChange" => {
+                        let utf16: bool = self.utf16;
+                        if let (Some(document), Some(Message::Array(changes))) = (
+                            self.documents.get_mut(&uri),
+                            message.get(&["params", "contentChanges"]),
+                        ) {
+                            for change in changes {
+                                document.change(change, utf16);
+                            }
+                        }
+                    }
+                    _ => {
+                        self.documents.remove(&uri);
+                    }
+                }
+                return vec![self.publish(&uri)];
+            }
+            "textDocument/definition" => Ok(self
+                .at(message)
+                .and_then(|(contents, line, character)| definition(contents, line, character))
+                .map_or(Json::Null, |range| {
+                    Json::Object(vec![
+                        ("uri", Json::Str(uri.unwrap_or_default())),
+                        ("range", self.encode(message, range)),
+                    ])
+                })),
+            "textDocument/hover" => Ok(self
+                .at(message)
+                .and_then(|(contents, line, character)| hover(contents, line, character))
+                .map_or(Json::Null, |found| self.encode(message, found))),
+            _ => Err(format!("unknown method '{}'", method)),
+        };
+
+        // Only requests have an id and get an answer, notifications don't
+        let id: &Message = match message.get(&["id"]) {
+            Some(id) => id,
+            None => return Vec::new(),
+        };
+        let answer: (&'static str, Json) = match result {
+            Ok(result) => ("result", result),
+            Err(error) => (
+                "error",
+                Json::Object(vec![
+                    ("code", Json::Number(-32601)),
+                    ("message", Json::Str(error)),
+                ]),
+            ),
+        };
+        vec![Json::Object(vec![
+            ("jsonrpc", Json::Str("2.0".to_string())),
+            ("id", id_json(id)),
+            answer,
+        ])]
+    }
+}
+
+/// Answer the editor until it says to exit, giving back the exit code. That's 0
+/// if it asked to shut down first like it should, or 1 if it didn't
+pub fn serve<R: BufRead, W: Write>(mut input: R, mut output: W) -> Result<i32, String> {
+    let mut server: Server = new_server();
+    while let Some(body) = read_message(&mut input)? {
+        let message: Message = match parse_json(&body) {
+            Ok(message) => message,
+            Err(error) => {
+                let error: Json = Json::Object(vec![
+                    ("jsonrpc", Json::Str("2.0".to_string())),
+                    ("id", Json::Null),
+                    (
+                        "error",
+                        Json::Object(vec![
+                            ("code", Json::Number(-32700)),
+                            ("message", Json::Str(error)),
+                        ]),
+                    ),
+                ]);
+                write_message(&mut output, &error)?;
+                continue;
+            }
+        };
+        if message.get(&["method"]).and_then(Message::as_str) == Some("exit") {
+            break;
+        }
+        for answer in server.handle(&message) {
+            write_message(&mut output, &answer)?;
+        }
+    }
+    Ok(if server.shutdown { 0 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dump::json_string;
+
+    fn frame(body: &str) -> String {
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    #[test]
+    fn lsp_test() {
+        assert_eq!(
+            parse_json(r#"{"a": [1, -2.5e1, "x\nA"], "b": {"c": null}}"#)
+                .unwrap()
+                .get(&["b", "c"]),
+            Some(&Message::Null)
+        );
+        assert!(parse_json("{\"a\": }").is_err());
+
+        let contents: &str = "set total = 1;\nset total = total + 2;\nprint (total;";
+        let open: String = format!(
+            r#"{{"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {{"textDocument": {{"uri": "file:///a.m", "text": {}}}}}}}"#,
+            json_string(contents)
+        );
+        let at = |id: i64, method: &str, line: i64, character: i64| -> String {
+            format!(
+                r#"{{"jsonrpc": "2.0", "id": {}, "method": "{}", "params": {{"textDocument": {{"uri": "file:///a.m"}}, "position": {{"line": {}, "character": {}}}}}}}"#,
+                id, method, line, character
+            )
+        };
+        let input: String = [
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#.to_string(),
+            open,
+            // Taking out the parenthesis, then giving all of the text again
+            r#"{"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {"textDocument": {"uri": "file:///a.m"}, "contentChanges": [{"range": {"start": {"line": 2, "character": 6}, "end": {"line": 2, "character": 7}}, "text": ""}]}}"#.to_string(),
+            r#"{"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {"textDocument": {"uri": "file:///a.m"}, "contentChanges": [{"text": "set total = 1;\nset total = total + 2;\nprint total;"}]}}"#.to_string(),
+            at(2, "textDocument/definition", 2, 8),
+            at(3, "textDocument/hover", 1, 12),
+            at(4, "textDocument/hover", 0, 1),
+            r#"{"jsonrpc": "2.0", "id": 5, "method": "shutdown"}"#.to_string(),
+            r#"{"jsonrpc": "2.0", "method": "exit"}"#.to_string(),
+        ]
+        .iter()
+        .map(|x| frame(x))
+        .collect();
+
+        let mut output: Vec<u8> = Vec::new();
+        assert_eq!(serve(input.as_bytes(), &mut output), Ok(0));
+        let mut output: &[u8] = &output;
+        let mut answers: Vec<Message> = Vec::new();
+        while let Some(body) = read_message(&mut output).unwrap() {
+            answers.push(parse_json(&body).unwrap());
+        }
+        assert_eq!(answers.len(), 8);
+
+        let number = |answer: &Message, keys: &[&str]| -> Option<i64> {
+            answer.get(keys).and_then(Message::as_i64)
+        };
+        // The unmatched parenthesis on the third line
+        let diagnostic: &Message = match answers[1].get(&["params", "diagnostics"]) {
+            Some(Message::Array(found)) => &found[0],
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(number(diagnostic, &["range", "start", "line"]), Some(2));
+        assert_eq!(
+            number(diagnostic, &["range", "start", "character"]),
+            Some(6)
+        );
+        assert_eq!(
+            diagnostic.get(&["code"]).and_then(Message::as_str),
+            Some("E0102")
+        );
+        // Fixing it takes the error away
+        for answer in &answers[2..4] {
+            assert_eq!(
+                answer.get(&["params", "diagnostics"]),
+                Some(&Message::Array(Vec::new()))
+            );
+        }
+        // The total on the third line goes to the first one
+        assert_eq!(
+            number(&answers[4], &["result", "range", "start", "line"]),
+            Some(0)
+        );
+        assert_eq!(
+            number(&answers[4], &["result", "range", "start", "character"]),
+            Some(4)
+        );
+        assert_eq!(
+            number(&answers[4], &["result", "range", "end", "character"]),
+            Some(9)
+        );
+        assert_eq!(
+            answers[5]
+                .get(&["result", "contents", "value"])
+                .and_then(Message::as_str),
+            Some("`total` is an int\n\nset on line 2: `set total = total + 2;`")
+        );
+        // Nothing to show over `set`
+        assert_eq!(answers[6].get(&["result"]), Some(&Message::Null));
+        assert_eq!(answers[7].get(&["result"]), Some(&Message::Null));
+    }
+
+    #[test]
+    fn lsp_encoding_test() {
+        let message = |body: &str| -> Message { parse_json(body).unwrap() };
+        let open: Message = message(&format!(
+            r#"{{"method": "textDocument/didOpen", "params": {{"textDocument": {{"uri": "file:///b.m", "text": {}}}}}}}"#,
+            json_string("print \"😀\"; set a = 1;\nprint a;")
+        ));
+        // The 1 is the 20th char but the 21st code unit, the emoji takes two
+        let change = |character: i64| -> Message {
+            message(&format!(
+                r#"{{"method": "textDocument/didChange", "params": {{"textDocument": {{"uri": "file:///b.m"}}, "contentChanges": [{{"range": {{"start": {{"line": 0, "character": {}}}, "end": {{"line": 0, "character": {}}}}}, "text": "22"}}]}}}}"#,
+                character,
+                character + 1
+            ))
+        };
+        let definition: Message = message(
+            r#"{"id": 2, "method": "textDocument/definition", "params": {"textDocument": {"uri": "file:///b.m"}, "position": {"line": 1, "character": 6}}}"#,
+        );
+        let start = |answers: &[Json]| -> Option<i64> {
+            message(&answers[0].pretty(0))
+                .get(&["result", "range", "start", "character"])
+                .and_then(Message::as_i64)
+        };
+
+        let mut server: Server = new_server();
+        let answers: Vec<Json> = server.handle(&message(
+            r#"{"id": 1, "method": "initialize", "params": {"capabilities": {}}}"#,
+        ));
+        assert_eq!(
+            message(&answers[0].pretty(0)).get(&["result", "capabilities", "positionEncoding"]),
+            Some(&Message::Str("utf-16".to_string()))
+        );
+        server.handle(&open);
+        server.handle(&change(20));
+        assert_eq!(
+            server.documents["file:///b.m"].lexer.contents(),
+            "print \"😀\"; set a = 22;\nprint a;"
+        );
+        assert_eq!(start(&server.handle(&definition)), Some(16));
+
+        // An editor that can count chars gets them
+        let mut server: Server = new_server();
+        let answers: Vec<Json> = server.handle(&message(
+            r#"{"id": 1, "method": "initialize", "params": {"capabilities": {"general": {"positionEncodings": ["utf-16", "utf-32"]}}}}"#,
+        ));
+        assert_eq!(
+            message(&answers[0].pretty(0)).get(&["result", "capabilities", "positionEncoding"]),
+            Some(&Message::Str("utf-32".to_string()))
+        );
+        server.handle(&open);
+        server.handle(&change(19));
+        assert_eq!(
+            server.documents["file:///b.m"].lexer.contents(),
+            "print \"😀\"; set a = 22;\nprint a;"
+        );
+        assert_eq!(start(&server.handle(&definition)), Some(15));
+    }
+}
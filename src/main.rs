@@ -1,6 +1,57 @@
+use mahou::analysis::{
+    check_division_by_zero, check_ident_length, check_int_division, check_undefined,
+};
+use mahou::ast::{Position, Stmt};
+use mahou::bench::{bench, corpus, report as bench_report, CountingAllocator, Timings};
+use mahou::bytecode::{
+    compile_program, compile_unoptimized, new_vm, Engine, FunctionCode, Vm, MAGIC,
+};
+use mahou::codegen::{codegen, format_python, Emit, PrintStyle};
+use mahou::debugger::new_line_table;
+use mahou::diagnostics::{new_lints, Diagnostic, LintLevel, LintReport, Lints};
+use mahou::doc::{html as doc_html, markdown as doc_markdown, module_doc, source_files, ModuleDoc};
+use mahou::dump::{
+    ast_json, ast_sexpr, dump_tokens, token_stats, token_table, tokens_json, Format,
+};
+use mahou::error::{install_panic_handler, MahouError};
+use mahou::formatter::{format_source, keep_shebang};
+use mahou::highlight::highlight;
+use mahou::interpreter::{new_interpreter, Interpret, Interpreter, RuntimeError, MAX_CALL_DEPTH};
+use mahou::keywords::{parse_keywords, set_aliases};
+use mahou::lexer::{new_lexer, Lex, Lexer};
+use mahou::log::{self, Level};
+use mahou::lsp::serve;
+use mahou::man::{command_names, man_page};
+use mahou::modules::{load_modules, EXTENSION};
+use mahou::optimize::optimize;
+use mahou::parser::{new_parser, Parse, ParseError, Parser};
+use mahou::project::{
+    build_project, find_project, new_project, parse_manifest, Built, Manifest, MANIFEST,
+};
+use mahou::python::{mapped_python, run_python};
+use mahou::semantic::{check_constants, check_loops, check_semantics, Consts};
+use mahou::sourcemap::{mapped_codegen, SourceMap};
+use mahou::testing::{run_test, test_files, test_report, TestResult};
+use mahou::trace::new_tracer;
+use mahou::{
+    build, check, check_all, debug, format, print_last, run_bytecode_with_args, run_on_vm, spacer,
+    with_call_stack,
+};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use structopt::clap::Shell;
 use structopt::StructOpt;
 
+// Counting is one add, and it lets `mahou bench` say how much lexing and parsing allocate
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 #[doc = "Syntax"]
 /**
     Example:
@@ -10,320 +61,1359 @@ use structopt::StructOpt;
 
     Commands:
     - set
+    - let, same thing as set
+    - const, like set but the name can't be set again
+    - a++; and a--; add and take away one
+    - set a, b = 1, 2; sets both, and set a, b = b, a; swaps them
     - print
+    - input, as a value like set name = input;
+    - if a { ... } else { ... }, with else if for more conditions
+    - while a < 10 { ... }
+    - jump 2, moves by statements in the same block, only with --run.
+      jump 1 goes to the next one and jump -1 goes back to the one before
+    - exit 1; stops the program, and mahou exits with that code
 
     Operators:
     - Plus (+)
     - Minus (-)
     - Divide (/)
     - Multiply (*)
+    - Modulo (%)
+    - Power (**)
+
+    Comparisons, which give True or False:
+    - <, >, <=, >=
+    - ==, !=
+
+    Logic, where the right side only runs if it's needed:
+    - a and b, a or b, not a
+
+    Numbers:
+    - 3, 3.14 and 1e-3
+
+    Bools:
+    - true and false, which are 1 and 0 in math
 
     Constants:
     - $PI
     - $E
+    - $ARGS and $ARGC, what comes after -- like mahou --run test.m -- a b
+
+    Functions:
+    - func add(a, b) { return a + b; }
+    - add(1, 2), or a builtin like len(a), max(a, b) and sqrt(a)
+
+    Input:
+    - input x; reads a line into x, as a number if it is one
+    - read_file(path) and write_file(path, text) for files
+    - env(name) for an environment variable, empty if it isn't set
+
+    Time:
+    - now() for the seconds since 1970, elapsed() for the seconds since the program started
+    - sleep(ms) waits for that many milliseconds
+
+    Random numbers:
+    - rand() for a float from 0 up to 1, rand_int(lo, hi) for an int from lo to hi
+    - --seed 7 picks the same ones every run
+
+    Modules:
+    - use shapes; runs shapes.m, then its names can be used like shapes.area
+
+    Strings:
+    - "hello", with the escapes \n, \t, \\ and \"
+    - "x is {x}" puts the value of x into the string, {{ and }} are a brace
+
+    Comments:
+    - # runs to the end of the line
+    - /* ... */ can go over more than one line
 */
 
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Compile a program to bytecode and write it to a .mhc file. Without a program
+    /// it builds the project from the closest mahou.toml into its build folder
+    Build {
+        /// The program to compile, or `-` to read it from stdin
+        filename: Option<String>,
+
+        /// Where to write the bytecode, the filename with .mhc at the end if it's not given
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+
+        /// Replace the output file if it's already there
+        #[structopt(long)]
+        force: bool,
+    },
+    /// Make a folder for a new project, with a mahou.toml and src/main.m
+    New {
+        /// What to call the project and its folder
+        name: PathBuf,
+    },
+    /// Run a .mhc file made with build, or a program from source on the vm. Anything
+    /// after the file goes to the program, so `#!/usr/bin/env -S mahou run` at the top
+    /// of a script lets it run on its own
+    #[structopt(setting = structopt::clap::AppSettings::TrailingVarArg)]
+    Run {
+        /// The file to run, or `-` to read it from stdin
+        filename: String,
+
+        /// What the program gets as $ARGS and $ARGC
+        args: Vec<String>,
+
+        /// Run it with the interpreter instead and print how many times each line ran
+        /// and how long it took once it's done, the slowest first
+        #[structopt(long)]
+        profile: bool,
+
+        /// Clear the screen and run it again every time it or a module it uses is saved
+        #[structopt(long)]
+        watch: bool,
+
+        /// Where rand() and rand_int() start from, so the same numbers come out every time
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+    /// Work out the type of every variable and show where types don't go together,
+    /// without outputting or running anything. Exits with 1 if any program has an error
+    Check {
+        /// The programs to check, or `-` to read one from stdin
+        #[structopt(required = true)]
+        filenames: Vec<String>,
+
+        /// Only lex and parse, which is quick enough for an editor or a pre-commit hook
+        #[structopt(long)]
+        syntax: bool,
+
+        /// How many files to check at once, one for each cpu if it's not given
+        #[structopt(short, long)]
+        jobs: Option<usize>,
+    },
+    /// Time the lexer, parser, interpreter and vm on each program, or on the ones
+    /// in bench/ if none are given
+    Bench {
+        /// The programs to time
+        filenames: Vec<String>,
+
+        /// How many times to run each part, the middle time is the one shown
+        #[structopt(short = "n", long, default_value = "5")]
+        iterations: usize,
+    },
+    /// Run the program one statement at a time, with breakpoints and a look at the
+    /// variables. `help` once it's stopped shows the commands
+    Debug {
+        /// The program to debug, or `-` to read it from stdin
+        filename: String,
+
+        /// Where rand() and rand_int() start from, so the same numbers come out every time
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+    /// Talk the language server protocol over stdin and stdout, for editors
+    Lsp,
+    /// Run the programs ending in _test.m and report each assert and assert_eq in
+    /// them. Exits with 1 if any of them failed
+    Test {
+        /// The test files, or folders to look through for them, the current folder
+        /// if none are given
+        paths: Vec<PathBuf>,
+
+        /// Where rand() and rand_int() start from in each test, so the same numbers
+        /// come out every time
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+    /// Make a reference for the functions and constants in the programs from the `##`
+    /// comments right above them, as markdown or with --html as a page of HTML
+    Doc {
+        /// The programs, or folders to look through for them, the current folder if
+        /// none are given. Tests are left out
+        paths: Vec<PathBuf>,
+
+        /// Make a page of HTML instead of markdown
+        #[structopt(long)]
+        html: bool,
+
+        /// Write the reference to this file instead of printing it
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+
+        /// Replace the output file if it's already there
+        #[structopt(long)]
+        force: bool,
+    },
+    /// Print a script that completes mahou's commands and flags in the shell, like
+    /// `mahou completions bash > /usr/share/bash-completion/completions/mahou`
+    Completions {
+        /// bash, zsh, fish, powershell or elvish
+        #[structopt(possible_values = &Shell::variants(), case_insensitive = true)]
+        shell: Shell,
+    },
+    /// Print the man page, made from this help, like `mahou man > mahou.1`
+    Man,
+    /// Print the program back out with consistent spacing and indents
+    Fmt {
+        /// The program to format, or `-` to read it from stdin
+        filename: String,
+
+        /// Print nothing and exit non-zero if formatting would change the file
+        #[structopt(long)]
+        check: bool,
+    },
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "mahou", about = "A programming language")]
 struct Opt {
+    #[structopt(subcommand)]
+    command: Option<Command>,
+
+    /// Show the source and the token table before the output, with a header above each
+    /// output. `-vv` and `-vvv` log more and more of what mahou does on stderr
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+
+    /// Show nothing but what the program prints and errors, not even warnings
+    #[structopt(short, long)]
+    quiet: bool,
+
+    /// Show line and character numbers counting from zero, for editors
+    #[structopt(long)]
+    zero_based: bool,
+
+    /// Only lex and parse the program, exiting non-zero if there are syntax errors
+    #[structopt(long)]
+    check: bool,
+
+    /// Warn about identifiers longer than this many characters
+    #[structopt(long)]
+    max_ident_length: Option<usize>,
+
+    /// Format the outputted python with black, needs the black feature
+    #[structopt(long)]
+    format_output: bool,
+
+    /// Print the token table and stop, without parsing
+    #[structopt(long, visible_alias = "tokens")]
+    only_tokens: bool,
+
+    /// Don't color the token table, which is only colored in a terminal anyway
+    #[structopt(long)]
+    no_color: bool,
+
+    /// Run the program with the interpreter instead of outputting python
+    #[structopt(long)]
+    run: bool,
+
+    /// Compile the program to python and run that with python3, with its errors
+    /// pointing at the lines of the program
+    #[structopt(long, conflicts_with = "run")]
+    run_python: bool,
+
+    /// What runs the program with --run, `tree` to walk the statements or `vm`
+    /// to compile them to bytecode first
+    #[structopt(long, default_value = "tree")]
+    backend: Engine,
+
+    /// Leave the bytecode the way it's compiled, without the peephole pass, so
+    /// `--emit bytecode` with and without it shows what the pass changed
+    #[structopt(long)]
+    no_peephole: bool,
+
+    /// Print each statement as it runs with --run or -e, and the variables it changed
+    /// after it
+    #[structopt(long)]
+    trace: bool,
+
+    /// Stop a program run with --run after this many steps, in case it loops forever
+    #[structopt(long)]
+    max_steps: Option<u64>,
+
+    /// How deep function calls can go in a program run with --run before it stops
+    /// with an error, 200 if it isn't given
+    #[structopt(long)]
+    max_depth: Option<usize>,
+
+    /// Where rand() and rand_int() start from in a program run with --run or -e, so
+    /// the same numbers come out every time
+    #[structopt(long)]
+    seed: Option<u64>,
+
+    /// Print how many of each token there are and stop
+    #[structopt(long)]
+    stats: bool,
+
+    /// Print the mahou source back out with consistent spacing and stop
+    #[structopt(long)]
+    fmt: bool,
+
+    /// Print the tokens as JSON and stop
+    #[structopt(long)]
+    json: bool,
+
+    /// How `--emit tokens` shows the tokens, `table` or `json`. Without it they're
+    /// a table in a terminal and JSON anywhere else. `--emit ast` can also be `sexpr`,
+    /// and `--emit highlight` is `ansi` in a terminal or `html` anywhere else
+    #[structopt(long)]
+    format: Option<Format>,
+
+    /// Warn when dividing whole numbers doesn't come out even, since `/` always
+    /// gives a float and `7 / 2` is 3.5
+    #[structopt(long)]
+    warn_int_division: bool,
+
+    /// Explain what each token means in the token table
+    #[structopt(long)]
+    describe: bool,
+
+    /// What to turn the program into, `tokens`, `ast`, `bytecode`, `python`, `js`, `c`,
+    /// `wasm`, `pseudocode` or `highlight`, with a comma between them to output more than one like `python,js`.
+    /// Tokens come out as JSON when the output isn't a terminal
+    #[structopt(
+        long,
+        visible_alias = "target",
+        default_value = "python",
+        require_delimiter = true
+    )]
+    emit: Vec<Emit>,
+
+    /// How print is emitted, either `function` for print(x) or `statement` for print x
+    #[structopt(long, default_value = "function")]
+    print_style: PrintStyle,
+
+    /// Stop without outputting anything if there are any warnings
+    #[structopt(long)]
+    deny_warnings: bool,
+
+    /// Work out constant parts of the program before running or outputting it
+    #[structopt(short = "O", long)]
+    optimize: bool,
+
+    /// Write the output to this file instead of printing it
     #[structopt(short, long)]
-    verbose: bool,
+    output: Option<PathBuf>,
+
+    /// Write each --emit to its own file in this folder instead, named after the
+    /// program like `out/test.py` and `out/test.js`
+    #[structopt(long, conflicts_with = "output")]
+    output_dir: Option<PathBuf>,
+
+    /// Let --output or --output-dir replace a file that's already there
+    #[structopt(long)]
+    force: bool,
 
-    /// The input file to be interpreted
-    filename: String,
+    /// Write a .map next to the --output file, saying which line of the program
+    /// each line of the output came from
+    #[structopt(long, requires = "output", conflicts_with = "format-output")]
+    source_map: bool,
+
+    /// Run this program instead of one from a file, printing the value if it ends
+    /// in an expression like `mahou -e '2 ** 10'`
+    #[structopt(short, long)]
+    eval: Option<String>,
+
+    /// Read the program from stdin, the same as giving `-` as the file
+    #[structopt(long)]
+    stdin: bool,
+
+    /// A toml file with other words for the keywords, like `imprimir = "print"` in a
+    /// `[keywords]` table. Without it they come from the mahou.toml of the project if
+    /// there is one
+    #[structopt(long)]
+    keywords: Option<PathBuf>,
+
+    /// The input file to be interpreted, or `-` to read it from stdin
+    filename: Option<String>,
+
+    /// What comes after `--` goes to the program with --run, as $ARGS and $ARGC
+    #[structopt(last = true)]
+    args: Vec<String>,
 }
 
-/// Check if a given character is whitespace
-fn is_char_whitespace(ch: char) -> bool {
-    match ch {
-        '\t' | ' ' | '\n' => true,
-        _ => false,
+impl Opt {
+    /// Whether the token table gets colors, only when it's going to a terminal
+    fn color(&self) -> bool {
+        !self.no_color && self.for_terminal()
+    }
+    /// Whether the formats that depend on where the output goes, like the token table,
+    /// are for a person instead of another program
+    fn for_terminal(&self) -> bool {
+        self.output.is_none() && self.output_dir.is_none() && io::stdout().is_terminal()
+    }
+    /// Where --output-dir puts the output of one --emit, named after the program
+    fn emit_path(&self, emit: Emit) -> Option<PathBuf> {
+        let stem: &str = match self.filename() {
+            "-" | "<eval>" => "main",
+            filename => Path::new(filename)
+                .file_stem()
+                .and_then(|x| x.to_str())
+                .unwrap_or("main"),
+        };
+        let dir: &PathBuf = self.output_dir.as_ref()?;
+        Some(dir.join(format!("{}.{}", stem, emit.extension())))
     }
+    /// Highlight as HTML unless it's asked for as ANSI or it's going to a terminal
+    fn html(&self) -> bool {
+        match self.format {
+            Some(format) => format == Format::Html,
+            None => !self.for_terminal(),
+        }
+    }
+    /// The file to compile, which only the subcommands can go without
+    fn filename(&self) -> &str {
+        match self.filename_or_error() {
+            Ok(filename) => filename,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+    }
+    /// The filename, `-` for stdin, `<eval>` for -e, or why there isn't one
+    fn filename_or_error(&self) -> Result<&str, &'static str> {
+        match (&self.filename, self.stdin) {
+            (None, false) if self.eval.is_some() => Ok("<eval>"),
+            _ if self.eval.is_some() => Err("-e is the program, so no file can be given"),
+            (Some(filename), false) => Ok(filename),
+            (None, true) => Ok("-"),
+            (Some(_), true) => Err("--stdin reads the program from stdin, so no file can be given"),
+            (None, false) => Err("a file to compile is needed, or `-` to read from stdin"),
+        }
+    }
+}
+
+/// Read the program from the file, or from stdin if the filename is `-`
+fn read_source(filename: &str) -> io::Result<String> {
+    read_source_from(filename, io::stdin())
 }
 
-/// Check if a character is an symbol
-fn is_char_symbol(ch: char) -> bool {
-    match ch {
-        '+' | '-' | '*' | '/' | '>' | '<' | '=' | ';' | '$' => true,
-        _ => false,
+/// The help clap would print for the arguments, laid out for a page that's 80 wide
+fn help_text(args: &[&str]) -> String {
+    match Opt::clap().set_term_width(78).get_matches_from_safe(args) {
+        Err(error) => error.message,
+        Ok(_) => String::new(),
     }
 }
 
-/// Check if a character is in between 0 and 9
-fn is_char_numeric(ch: char) -> bool {
-    return ch.is_digit(10);
+/// The man page from the help of mahou and of each command
+fn man() -> String {
+    let help: String = help_text(&["mahou", "--help"]);
+    let commands: Vec<(String, String)> = command_names(&help)
+        .into_iter()
+        .map(|name| {
+            let text: String = help_text(&["mahou", &name, "--help"]);
+            (name, text)
+        })
+        .collect();
+    man_page(
+        env!("CARGO_PKG_VERSION"),
+        "A programming language",
+        &help,
+        &commands,
+    )
 }
 
-/// Check if the current character or the next character will end the token
-fn ends_token(cur: char, next: char) -> bool {
-    if is_char_whitespace(next) {
-        return true;
-    }
-    if is_char_symbol(cur) {
-        return true;
-    }
-    if is_char_symbol(next) {
-        return true;
-    }
-    if is_char_whitespace(cur) {
-        return false;
-    }
-    return false;
+/// The name in the closest mahou.toml, if there is one and it can be read
+fn project_name() -> Option<String> {
+    let folder: PathBuf = find_project(&std::env::current_dir().ok()?)?;
+    let contents: String = fs::read_to_string(folder.join(MANIFEST)).ok()?;
+    parse_manifest(&contents).ok().map(|x| x.name)
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum Tokens {
-    Assign,
-    Var,
-    Set,
-    Jump,
-    Print,
-    Minus,
-    Plus,
-    Divide,
-    Multiply,
-    Semi,
-    Identifier,
-    Numeric,
+/// The program in the file, or stop with why it couldn't be read
+fn source_or_exit(filename: &str) -> String {
+    read_source(filename).unwrap_or_else(|error| {
+        eprint!(
+            "{}",
+            MahouError::io("read", shown_filename(filename), &error)
+        );
+        process::exit(1);
+    })
 }
 
-/// This is the structure that represents a single token
-#[derive(PartialEq, Debug, Clone)]
-struct Token {
-    part: String,
-    token: Tokens,
-    line_num: i64,
-    char_num: i64,
+/// Read the file as bytes, for bytecode that isn't text
+fn read_bytes(filename: &str) -> io::Result<Vec<u8>> {
+    if filename == "-" {
+        let mut bytes: Vec<u8> = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    } else {
+        fs::read(filename)
+    }
 }
 
-/// Given a string reference that has been identified as a single token, find what token it is
-fn tokenize(part: &str) -> Tokens {
-    let mut token: Tokens = match part {
-        "-" => Tokens::Minus,
-        "+" => Tokens::Plus,
-        "/" => Tokens::Divide,
-        "*" => Tokens::Multiply,
-        "=" => Tokens::Assign,
-        "$" => Tokens::Var,
-        ";" => Tokens::Semi,
-        "set" => Tokens::Set,
-        "jump" => Tokens::Jump,
-        "print" => Tokens::Print,
-        _ => Tokens::Identifier,
-    };
+/// The same as read_source, but with where stdin comes from passed in so it can be tested
+fn read_source_from<R: Read>(filename: &str, mut stdin: R) -> io::Result<String> {
+    if filename == "-" {
+        let mut contents: String = String::new();
+        stdin.read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        fs::read_to_string(filename)
+    }
+}
 
-    // Both identifiers and numeric literals get assigned Identifier
-    // but this check if each character in the identifier is a number
-    if token == Tokens::Identifier {
-        for c in part.chars() {
-            if is_char_numeric(c) {
-                token = Tokens::Numeric;
-                break;
-            }
+/// Print to stdout, stopping quietly once whatever reads it has stopped, like
+/// `| head` does after its lines
+fn print_out(text: &str) {
+    let mut stdout: io::StdoutLock = io::stdout().lock();
+    match stdout
+        .write_all(text.as_bytes())
+        .and_then(|_| stdout.flush())
+    {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => process::exit(0),
+        Err(error) => {
+            eprint!("{}", MahouError::io("write", "<stdout>", &error));
+            process::exit(1);
         }
     }
-    return token;
 }
 
-/// Given a string, find what tokens it's made up of
-trait Lex {
-    fn move_pointer(&mut self);
-    fn next(&mut self);
-    fn lexer(&mut self);
+/// Write the output to the file, making the folders it goes in if they aren't there
+fn write_output<C: AsRef<[u8]>>(path: &Path, output: C, force: bool) -> Result<(), MahouError> {
+    let shown: String = path.display().to_string();
+    if path.exists() && !force {
+        return Err(MahouError::Io {
+            action: "write".to_string(),
+            path: shown,
+            message: "it already exists, use --force to replace it".to_string(),
+        });
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| MahouError::io("make", &parent.display().to_string(), &error))?;
+    }
+    fs::write(path, output).map_err(|error| MahouError::io("write", &shown, &error))
 }
 
-/// The parts of data needed to make tokens
-struct Lexer {
-    contents: String,
-    chars: Vec<char>,
-    index: usize,
-    previous_char: char,
-    current_char: char,
-    next_char: char,
-    tokens: Vec<Token>,
+/// The bytecode for the vm and `--emit bytecode`, after the peephole pass unless
+/// it's turned off
+fn compile_bytecode(stmts: &[Stmt], args: &Opt) -> Result<FunctionCode, RuntimeError> {
+    if args.no_peephole {
+        compile_unoptimized(stmts)
+    } else {
+        compile_program(stmts)
+    }
 }
 
-impl Lex for Lexer {
-    /// Change what the character is by shifting them
-    fn move_pointer(&mut self) {
-        self.previous_char = self.current_char;
-        self.current_char = self.next_char;
-        self.next_char = self.chars[self.index];
-    }
-    fn next(&mut self) {
-        self.move_pointer();
-        self.index += 1;
-    }
-    /// Takes the contents and pushes what the tokenizer returns for each part
-    fn lexer(&mut self) {
-        // Get all the chars from the contents of the file
-        self.chars = self.contents.chars().collect();
-        let mut current_part: String = String::new();
-
-        self.index = 0;
-        let mut line_num: i64 = 1;
-        let chars_len: usize = self.contents.len();
-
-        while self.index + 1 <= chars_len {
-            // Check for newlines
-            if self.current_char == '\n' {
-                line_num += 1;
-                self.next();
-                continue;
-            }
-            // If the character is not whitespace, push it to the current part
-            if !is_char_whitespace(self.current_char) {
-                current_part.push(self.current_char);
-                // If the current character or the next ends the token
-                // push the current part as a token, then reset the part
-                if ends_token(self.current_char, self.next_char) {
-                    let token_type: Tokens = tokenize(&current_part);
-                    // Get size of the part for character num
-                    let char_num: i64 = self.index as i64 - current_part.len() as i64;
-                    let token: Token = Token {
-                        token: token_type,
-                        part: current_part,
-                        line_num,
-                        char_num,
-                    };
-                    self.tokens.push(token);
-                    current_part = String::new();
-                }
-            }
-            self.next();
-        }
+/// The name to show in diagnostics, since `-` doesn't say much
+fn shown_filename(filename: &str) -> &str {
+    if filename == "-" {
+        "<stdin>"
+    } else {
+        filename
     }
 }
 
-trait Parse {
-    fn set(&mut self, line: Vec<&Token>) -> String;
-    fn print(&mut self, line: Vec<&Token>) -> String;
-    fn exec(&mut self, line: Vec<&Token>) -> String;
-    fn parse(&mut self) -> Vec<String>;
+/// Print the error and where it happened, then stop
+fn report_error(contents: &str, args: &Opt, diagnostic: Diagnostic) -> ! {
+    eprint!(
+        "{}",
+        diagnostic.render(
+            contents,
+            Some(shown_filename(args.filename())),
+            args.zero_based
+        )
+    );
+    process::exit(1);
 }
 
-struct Parser {
-    tokens: Vec<Token>,
-}
+fn main() {
+    install_panic_handler();
+    let args: Opt = Opt::from_args();
+    log::set_level(Level::from_flags(args.verbose, args.quiet));
+    log::set_color(!args.no_color && io::stderr().is_terminal());
+    if let Err(message) = load_keywords(&args) {
+        eprint!("{}", message);
+        process::exit(1);
+    }
 
-impl Parse for Parser {
-    fn set(&mut self, line: Vec<&Token>) -> String {
-        let (name, value): (&Token, &Token) = (line[1], line[3]);
-        format!("{} = {}", name.part, value.part)
-    }
-    fn print(&mut self, line: Vec<&Token>) -> String {
-        let name: &Token = line[1];
-        format!("print({})", name.part)
-    }
-    fn exec(&mut self, line: Vec<&Token>) -> String {
-        let mut new: String = line
-            .into_iter()
-            .map(|x| x.part.to_owned())
-            .collect();
-        new.pop();
-        return new;
-    }
-    fn parse(&mut self) -> Vec<String> {
-        let mut current_line: Vec<&Token> = Vec::new();
-        let mut output_lines: Vec<String> = Vec::new();
-        let toks: Vec<Token> = self.tokens.clone();
-        for tok in &toks {
-            current_line.push(&tok);
-            // Check if the line has ended, if the current token is a semicolon
-            if tok.token == Tokens::Semi {
-                let first_token: Tokens = current_line[0].token;
-                let line: String;
-                // If the line starts with set
-                if first_token == Tokens::Set {
-                    line = self.set(current_line.clone());
-                // If the line is a print
-                } else if first_token == Tokens::Print {
-                    line = self.print(current_line.clone());
-                // If the line has no command, just interpret it
-                } else {
-                    line = self.exec(current_line.clone());
+    match &args.command {
+        Some(Command::Build {
+            filename: None,
+            output,
+            force,
+        }) => {
+            let here: PathBuf = std::env::current_dir().unwrap_or_else(|error| {
+                eprintln!("error: couldn't find the current folder: {}", error);
+                process::exit(1);
+            });
+            let folder: PathBuf = match find_project(&here) {
+                Some(folder) => folder,
+                None => {
+                    eprintln!(
+                        "error: no {} here or in a folder above, give a program to build or make a project with mahou new",
+                        MANIFEST
+                    );
+                    process::exit(1);
                 }
-                output_lines.push(line);
-                current_line = Vec::new();
+            };
+            // Shown from here when it can be, like src/main.m
+            let folder: &Path = folder.strip_prefix(&here).unwrap_or(&folder);
+            let built: Built = match build_project(folder) {
+                Ok(built) => built,
+                Err(message) => {
+                    eprint!("{}", message);
+                    process::exit(1);
+                }
+            };
+            if log::enabled(Level::Normal) {
+                eprint!("{}", built.warnings);
+            }
+            // What's in build is always made again, unless it goes somewhere else
+            let (path, force): (PathBuf, bool) = match output {
+                Some(output) => (output.clone(), *force),
+                None => (built.path, true),
+            };
+            if let Err(error) = write_output(&path, &built.bytes, force) {
+                eprint!("{}", error);
+                process::exit(1);
             }
+            return;
         }
-        return output_lines;
+        Some(Command::Build {
+            filename: Some(filename),
+            output,
+            force,
+        }) => {
+            let contents: String = source_or_exit(filename);
+            let bytes: Vec<u8> = match build(&contents, Some(shown_filename(filename))) {
+                Ok(bytes) => bytes,
+                Err(diagnostic) => {
+                    eprint!("{}", diagnostic);
+                    process::exit(1);
+                }
+            };
+            let path: PathBuf = output
+                .clone()
+                .unwrap_or_else(|| Path::new(filename).with_extension("mhc"));
+            if let Err(error) = write_output(&path, &bytes, *force) {
+                eprint!("{}", error);
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::New { name }) => {
+            if let Err(message) = new_project(name) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Run {
+            filename,
+            args: program_args,
+            profile,
+            watch,
+            seed,
+        }) => {
+            if *watch {
+                if filename == "-" {
+                    eprintln!("error: --watch needs a file, stdin can't change");
+                    process::exit(1);
+                }
+                mahou::watch::watch(filename, || {
+                    let code: i32 = run_file(filename, program_args, *seed, *profile)
+                        .unwrap_or_else(|message| {
+                            eprint!("{}", message);
+                            1
+                        });
+                    eprintln!("exited with {}, waiting for {} to change", code, filename);
+                });
+            }
+            match run_file(filename, program_args, *seed, *profile) {
+                Ok(code) => process::exit(code),
+                Err(message) => {
+                    eprint!("{}", message);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Doc {
+            paths,
+            html,
+            output,
+            force,
+        }) => {
+            let paths: Vec<PathBuf> = if paths.is_empty() {
+                vec![PathBuf::from(".")]
+            } else {
+                paths.clone()
+            };
+            let files: Vec<PathBuf> = match source_files(&paths) {
+                Ok(files) => files,
+                Err(error) => {
+                    eprintln!("error: couldn't look for programs: {}", error);
+                    process::exit(1);
+                }
+            };
+            let mut modules: Vec<ModuleDoc> = Vec::new();
+            for file in files {
+                let shown: String = file
+                    .strip_prefix(".")
+                    .unwrap_or(&file)
+                    .display()
+                    .to_string();
+                let contents: String = source_or_exit(&file.display().to_string());
+                match module_doc(&contents, &shown) {
+                    Ok(module) => modules.push(module),
+                    Err(diagnostic) => {
+                        eprint!("{}", diagnostic);
+                        process::exit(1);
+                    }
+                }
+            }
+            let title: String = project_name().unwrap_or_else(|| "Reference".to_string());
+            let reference: String = if *html {
+                doc_html(&title, &modules)
+            } else {
+                doc_markdown(&title, &modules)
+            };
+            match output {
+                Some(path) => {
+                    if let Err(error) = write_output(path, reference, *force) {
+                        eprint!("{}", error);
+                        process::exit(1);
+                    }
+                }
+                None => print_out(&reference),
+            }
+            return;
+        }
+        Some(Command::Completions { shell }) => {
+            Opt::clap().gen_completions_to("mahou", *shell, &mut io::stdout());
+            return;
+        }
+        Some(Command::Man) => {
+            print_out(&man());
+            return;
+        }
+        Some(Command::Fmt { filename, check }) => {
+            let contents: String = source_or_exit(filename);
+            let formatted: String =
+                match format(&contents, Some(shown_filename(filename)), args.zero_based) {
+                    Ok(formatted) => formatted,
+                    Err(diagnostic) => {
+                        eprint!("{}", diagnostic);
+                        process::exit(1);
+                    }
+                };
+            if !*check {
+                print_out(&formatted);
+            } else if formatted != contents {
+                eprintln!("{} is not formatted", shown_filename(filename));
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Bench {
+            filenames,
+            iterations,
+        }) => {
+            let programs: Vec<(String, String)> = if filenames.is_empty() {
+                corpus()
+                    .into_iter()
+                    .map(|(name, program)| (name.to_string(), program))
+                    .collect()
+            } else {
+                filenames
+                    .iter()
+                    .map(|x| (x.clone(), source_or_exit(x)))
+                    .collect()
+            };
+            let mut results: Vec<(String, Timings)> = Vec::new();
+            for (name, program) in programs {
+                match bench(&program, *iterations) {
+                    Ok(timings) => results.push((name, timings)),
+                    Err(message) => {
+                        eprintln!("error: {} failed: {}", name, message);
+                        process::exit(1);
+                    }
+                }
+            }
+            print_out(&bench_report(&results));
+            return;
+        }
+        Some(Command::Lsp) => {
+            let stdin: io::Stdin = io::stdin();
+            match serve(stdin.lock(), io::stdout()) {
+                Ok(code) => process::exit(code),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Debug { filename, seed }) => {
+            let contents: String = source_or_exit(filename);
+            let stdin: io::Stdin = io::stdin();
+            match debug(
+                &contents,
+                Some(shown_filename(filename)),
+                *seed,
+                stdin.lock(),
+                io::stdout(),
+            ) {
+                Ok(code) => process::exit(code),
+                Err(diagnostic) => {
+                    eprint!("{}", diagnostic);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Test { paths, seed }) => {
+            let paths: Vec<PathBuf> = if paths.is_empty() {
+                vec![PathBuf::from(".")]
+            } else {
+                paths.clone()
+            };
+            let files: Vec<PathBuf> = match test_files(&paths) {
+                Ok(files) => files,
+                Err(error) => {
+                    eprintln!("error: couldn't look for tests: {}", error);
+                    process::exit(1);
+                }
+            };
+            if files.is_empty() {
+                eprintln!("error: there are no _test.{} files", EXTENSION);
+                process::exit(1);
+            }
+            let mut results: Vec<TestResult> = Vec::new();
+            for file in files {
+                let shown: String = file
+                    .strip_prefix(".")
+                    .unwrap_or(&file)
+                    .display()
+                    .to_string();
+                match fs::read_to_string(&file) {
+                    Ok(contents) => results.push(run_test(&contents, &shown, *seed)),
+                    Err(error) => {
+                        eprint!("{}", MahouError::io("read", &shown, &error));
+                        process::exit(1);
+                    }
+                }
+            }
+            print_out(&test_report(&results));
+            process::exit(if results.iter().all(|x| x.passed()) {
+                0
+            } else {
+                1
+            });
+        }
+        Some(Command::Check {
+            filenames,
+            syntax,
+            jobs,
+        }) => {
+            let mut failed: usize = 0;
+            let mut programs: Vec<(String, String)> = Vec::new();
+            for filename in filenames {
+                match read_source(filename) {
+                    Ok(contents) => programs.push((shown_filename(filename).to_string(), contents)),
+                    Err(error) => {
+                        eprint!(
+                            "{}",
+                            MahouError::io("read", shown_filename(filename), &error)
+                        );
+                        failed += 1;
+                    }
+                }
+            }
+            let jobs: usize =
+                jobs.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |x| x.get()));
+            let results: Vec<Result<(), MahouError>> =
+                check_all(&programs, *syntax, args.zero_based, jobs);
+            for diagnostic in results.iter().filter_map(|x| x.as_ref().err()) {
+                eprint!("{}", diagnostic);
+                failed += 1;
+            }
+            if filenames.len() > 1 {
+                eprintln!(
+                    "checked {} files, {} passed and {} failed",
+                    filenames.len(),
+                    filenames.len() - failed,
+                    failed
+                );
+            }
+            process::exit(if failed > 0 { 1 } else { 0 });
+        }
+        None => {}
     }
-}
 
-/// Remove the boiler plate of making a lexer object
-fn new_lexer(contents: &str) -> Lexer {
-    let contents: String = contents.to_string() + "    ";
-    let lexer: Lexer = Lexer {
-        contents: contents.to_string(),
-        chars: Vec::new(),
-        index: 0,
-        previous_char: ' ',
-        current_char: ' ',
-        next_char: ' ',
-        tokens: Vec::new(),
+    // A program from -e goes the same way as one from a file, and then runs
+    let running: bool = args.run || args.eval.is_some();
+    if args.trace && !running {
+        eprintln!("error: --trace only works with --run or -e");
+        process::exit(1);
+    }
+    let filename: &str = args.filename();
+    let contents: String = match &args.eval {
+        Some(contents) => contents.clone(),
+        None => source_or_exit(filename),
     };
 
-    return lexer;
-}
+    if args.check {
+        match check(
+            &contents,
+            Some(shown_filename(args.filename())),
+            args.zero_based,
+        ) {
+            Ok(()) => process::exit(0),
+            Err(diagnostic) => {
+                eprint!("{}", diagnostic);
+                process::exit(1);
+            }
+        }
+    }
 
-fn spacer(num: usize, ch: char) -> String {
-    let mut space: String = String::new();
-    for _ in 0..num {
-        space.push(ch);
+    // Both of these only need the tokens
+    if args.stats || args.fmt {
+        let mut lexer: Lexer = new_lexer(&contents);
+        if let Err(error) = lexer.lexer() {
+            report_error(&contents, &args, Diagnostic::from(&error));
+        }
+        if args.fmt {
+            print_out(&keep_shebang(&contents, format_source(&lexer.tokens)));
+        } else {
+            print_out(&format!("{}\n", token_stats(&lexer.tokens)));
+        }
+        return;
     }
-    return space;
-}
 
-fn print(tok: &Token) {
-    let token_text: String = format!("{:?}", tok.token);
-    let first: String = spacer(14 - token_text.len(), ' ');
-    let second: String = spacer(10 - tok.part.len(), ' ');
-    println!(
-        "{}{}{}{}{}:{}",
-        token_text, first, tok.part, second, tok.line_num, tok.char_num
-    );
-}
+    // Stop after lexing, so the tokens can be seen even if the program can't be parsed
+    if args.only_tokens || args.json {
+        match dump_tokens(
+            &contents,
+            args.json,
+            args.describe,
+            args.zero_based,
+            args.color(),
+        ) {
+            Ok(dump) => print_out(&format!("{}\n", dump)),
+            Err(error) => report_error(&contents, &args, Diagnostic::from(&error)),
+        }
+        return;
+    }
 
-fn main() {
-    let args: Opt = Opt::from_args();
+    // Highlighting only needs to lex, so a piece of a program for docs can be shown
+    // even if it uses names that aren't set
+    if args.emit.iter().all(|x| *x == Emit::Highlight) {
+        match highlight(&contents, args.html()) {
+            Ok(highlighted) => write_or_print(&args, &highlighted),
+            Err(error) => report_error(&contents, &args, Diagnostic::from(&error)),
+        }
+        return;
+    }
 
-    let contents: String = fs::read_to_string(args.filename).expect("Error reading file");
     let mut lexer: Lexer = new_lexer(&contents);
-    lexer.lexer();
-
-    // Print source code header
-    println!("Source code:");
-    println!("{}", spacer(28, '-'));
-    print!("{}", contents);
-    println!("{}\n", spacer(28, '-'));
-
-    // Print the table column names
-    let label: String = format!(
-        "Type{}Part{}Line",
-        spacer(14 - "Type".len(), ' '),
-        spacer(10 - "Part".len(), ' ')
+    if let Err(error) = lexer.lexer() {
+        report_error(&contents, &args, Diagnostic::from(&error));
+    }
+
+    // Running the program only prints what the program prints
+    if args.verbose > 0 && !args.quiet && !running && !args.run_python {
+        // Print source code header
+        print_out(&format!(
+            "Source code:\n{}\n{}{}\n\n",
+            spacer(28, '-'),
+            contents,
+            spacer(28, '-')
+        ));
+        print_out(&token_table(
+            &lexer.tokens,
+            args.describe,
+            args.zero_based,
+            args.color(),
+        ));
+    }
+
+    if let Err(error) = check_undefined(&lexer.tokens) {
+        report_error(&contents, &args, Diagnostic::from(&error));
+    }
+
+    let mut warnings: Vec<ParseError> = Vec::new();
+    if let Some(max) = args.max_ident_length {
+        warnings.extend(check_ident_length(&lexer.tokens, max));
+    }
+
+    let mut parser: Parser = new_parser(&lexer.tokens);
+    // Every syntax error gets shown, not just the first one
+    let mut stmts: Vec<Stmt> = match parser.parse_all() {
+        Ok(stmts) => stmts,
+        Err(errors) => {
+            for error in &errors {
+                eprint!(
+                    "{}",
+                    Diagnostic::from(error).render(
+                        &contents,
+                        Some(shown_filename(args.filename())),
+                        args.zero_based
+                    )
+                );
+            }
+            process::exit(1);
+        }
+    };
+
+    if args.eval.is_some() {
+        print_last(&mut stmts);
+    }
+
+    if let Err(error) = check_constants(&stmts, &lexer.tokens, &mut Consts::new()) {
+        report_error(&contents, &args, Diagnostic::from(&error));
+    }
+    if let Err(error) = check_division_by_zero(&stmts) {
+        report_error(&contents, &args, Diagnostic::from(&error));
+    }
+    if let Err(error) = check_loops(&stmts) {
+        report_error(&contents, &args, Diagnostic::from(&error));
+    }
+
+    warnings.extend(check_semantics(&stmts, &lexer.tokens));
+    if args.warn_int_division {
+        warnings.extend(check_int_division(&stmts));
+    }
+    let levels: BTreeMap<String, LintLevel> = match project_manifest() {
+        Ok(manifest) => manifest.map(|x| x.lints).unwrap_or_default(),
+        Err(message) => {
+            eprint!("{}", message);
+            process::exit(1);
+        }
+    };
+    let lints: Lints = new_lints(&contents, levels);
+    let report: LintReport = lints.report(
+        &warnings,
+        &contents,
+        Some(shown_filename(args.filename())),
+        args.zero_based,
+        log::enabled(Level::Normal),
     );
-    println!("{}", label);
-    println!("{}", spacer(28, '-'));
+    eprint!("{}", report.rendered);
+    if report.denied > 0 {
+        process::exit(1);
+    }
+    let warning_count: usize = report.warnings;
+    if args.deny_warnings && warning_count > 0 {
+        eprintln!(
+            "error: stopping because of {} warning{} with --deny-warnings",
+            warning_count,
+            if warning_count == 1 { "" } else { "s" }
+        );
+        process::exit(1);
+    }
+
+    // Loading modules or optimizing moves the statements around, so the lines from
+    // the parser would go with the wrong ones
+    let keeps_lines: bool = !args.optimize && !stmts.iter().any(|x| matches!(x, Stmt::Use { .. }));
+
+    // The warnings are only for this file, the modules it uses get added after
+    stmts = match load_modules(
+        stmts,
+        &contents,
+        Some(shown_filename(args.filename())),
+        args.zero_based,
+    ) {
+        Ok(stmts) => stmts,
+        Err(diagnostic) => {
+            eprint!("{}", diagnostic);
+            process::exit(1);
+        }
+    };
+
+    if args.optimize {
+        let removed: usize = optimize(&mut stmts);
+        // On stderr so it doesn't get mixed in with what the program prints with --run
+        log::info(|| {
+            format!(
+                "eliminated {} statement{}",
+                removed,
+                if removed == 1 { "" } else { "s" }
+            )
+        });
+    }
+
+    if args.run_python {
+        let starts: &[Position] = if keeps_lines { &parser.starts } else { &[] };
+        let (python, map): (String, SourceMap) = mapped_python(
+            &stmts,
+            &new_line_table(&contents, &stmts, starts),
+            shown_filename(args.filename()),
+        );
+        match run_python(&python, &map, &contents, &args.args) {
+            Ok(code) => process::exit(code),
+            Err(error) => {
+                eprintln!("error: {}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    if running {
+        let stdin: io::Stdin = io::stdin();
+        let max_depth: usize = args.max_depth.unwrap_or(MAX_CALL_DEPTH);
+        let starts: &[Position] = if keeps_lines { &parser.starts } else { &[] };
+        // Run on a thread with a stack big enough for the calls to go that deep
+        let ran: Result<Result<(), RuntimeError>, MahouError> =
+            with_call_stack(max_depth, || match args.backend {
+                Engine::Tree => {
+                    let mut interpreter: Interpreter<io::StdinLock, io::Stdout> =
+                        new_interpreter(stdin.lock(), io::stdout());
+                    interpreter.max_steps = args.max_steps;
+                    interpreter.max_depth = max_depth;
+                    interpreter.args = args.args.clone();
+                    if let Some(seed) = args.seed {
+                        interpreter.random = seed;
+                    }
+                    if args.trace {
+                        interpreter.hook = Some(Box::new(new_tracer(&contents, &stmts, starts)));
+                    }
+                    interpreter.run(&stmts)
+                }
+                Engine::Vm if args.trace => {
+                    eprintln!("error: --trace only works with --backend tree");
+                    process::exit(1);
+                }
+                Engine::Vm => compile_bytecode(&stmts, &args).and_then(|program| {
+                    let mut vm: Vm<io::StdinLock, io::Stdout> = new_vm(stdin.lock(), io::stdout());
+                    vm.max_steps = args.max_steps;
+                    vm.max_depth = max_depth;
+                    vm.args = args.args.clone();
+                    if let Some(seed) = args.seed {
+                        vm.random = seed;
+                    }
+                    vm.run(&program)
+                }),
+            });
+        let result: Result<(), RuntimeError> = ran.unwrap_or_else(|error| {
+            eprint!("{}", error);
+            process::exit(1);
+        });
+        match result {
+            Ok(()) => return,
+            Err(RuntimeError {
+                exit: Some(code), ..
+            }) => process::exit(code),
+            Err(error) => {
+                eprint!(
+                    "{}",
+                    error.render(
+                        &contents,
+                        Some(shown_filename(args.filename())),
+                        args.zero_based
+                    )
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    // Piping the output somewhere gets the tokens in a form other programs can read
+    let table: bool = match args.format {
+        Some(format) => format == Format::Table,
+        None => args.for_terminal(),
+    };
+    let mut output: String = String::new();
+    if args.output.is_some() && args.emit.len() > 1 {
+        eprintln!("error: -o can only write one --emit, --output-dir writes a file for each");
+        process::exit(1);
+    }
+    // Headers are only needed to tell more than one output apart, which is
+    // already done when each one goes in its own file
+    let headers: bool =
+        args.output_dir.is_none() && ((args.verbose > 0 && !args.quiet) || args.emit.len() > 1);
+    if args.source_map && headers {
+        eprintln!("error: --source-map only works with one --emit and without --verbose");
+        process::exit(1);
+    }
+    let mut source_map: Option<SourceMap> = None;
+    for emit in &args.emit {
+        let mut lines: String = match emit.backend(args.print_style, &stmts) {
+            Some(backend) if args.source_map => {
+                let starts: &[Position] = if keeps_lines { &parser.starts } else { &[] };
+                if !keeps_lines && log::enabled(Level::Normal) {
+                    eprintln!("warning: the source map is empty with modules or -O");
+                }
+                let file: String = args
+                    .output
+                    .as_ref()
+                    .and_then(|x| x.file_name())
+                    .map(|x| x.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let (lines, map): (String, SourceMap) = mapped_codegen(
+                    &stmts,
+                    backend.as_ref(),
+                    &new_line_table(&contents, &stmts, starts),
+                    &file,
+                    shown_filename(args.filename()),
+                );
+                source_map = Some(map);
+                lines
+            }
+            Some(backend) => codegen(&stmts, backend.as_ref()),
+            None if *emit == Emit::Tokens && table => {
+                token_table(&lexer.tokens, args.describe, args.zero_based, args.color())
+            }
+            None if *emit == Emit::Tokens => {
+                format!(
+                    "{}\n",
+                    tokens_json(&contents, &lexer.tokens, args.zero_based)
+                )
+            }
+            None if *emit == Emit::Highlight => match highlight(&contents, args.html()) {
+                Ok(highlighted) => highlighted,
+                Err(error) => report_error(&contents, &args, Diagnostic::from(&error)),
+            },
+            None if *emit == Emit::Bytecode => match compile_bytecode(&stmts, &args) {
+                Ok(program) => program.to_string(),
+                Err(error) => {
+                    eprint!(
+                        "{}",
+                        error.render(
+                            &contents,
+                            Some(shown_filename(args.filename())),
+                            args.zero_based
+                        )
+                    );
+                    process::exit(1);
+                }
+            },
+            None => match args.format {
+                Some(Format::Json) => format!("{}\n", ast_json(&stmts)),
+                Some(Format::Sexpr) => ast_sexpr(&stmts),
+                _ => format!("{:#?}\n", stmts),
+            },
+        };
+        if args.format_output && *emit == Emit::Python {
+            match format_python(&lines) {
+                Ok(formatted) => lines = formatted,
+                Err(message) => eprintln!("warning: {}, printing unformatted python", message),
+            }
+        }
 
-    // Print all the tokens
-    for tok in lexer.tokens.iter() {
-        print(tok);
+        if let Some(path) = args.emit_path(*emit) {
+            if let Err(error) = write_output(&path, &lines, args.force) {
+                eprint!("{}", error);
+                process::exit(1);
+            }
+        } else if headers {
+            output.push_str(&format!("\nOutputted {}\n", emit.name()));
+            output.push_str(&format!("{}\n", spacer(28, '-')));
+            output.push_str(&format!("{}\n", lines));
+        } else {
+            output.push_str(&lines);
+        }
+    }
+
+    if args.output_dir.is_none() {
+        write_or_print(&args, &output);
+    }
+    if let (Some(map), Some(path)) = (source_map, &args.output) {
+        let map_path: PathBuf = PathBuf::from(format!("{}.map", path.display()));
+        if let Err(error) = write_output(&map_path, map.json().pretty(0) + "\n", args.force) {
+            eprint!("{}", error);
+            process::exit(1);
+        }
     }
+}
 
-    let mut parser = Parser {
-        tokens: lexer.tokens,
+/// Use the other words for keywords from --keywords, or from the project's manifest
+/// if there's one
+fn load_keywords(args: &Opt) -> Result<(), String> {
+    let aliases: BTreeMap<String, String> = match &args.keywords {
+        Some(path) => {
+            let contents: String = fs::read_to_string(path)
+                .map_err(|error| format!("error: couldn't read {}: {}\n", path.display(), error))?;
+            parse_keywords(&contents, &path.display().to_string())?
+        }
+        None => match project_manifest()? {
+            Some(manifest) => manifest.keywords,
+            None => return Ok(()),
+        },
     };
+    set_aliases(aliases);
+    Ok(())
+}
 
-    println!("{}", spacer(28, '-'));
-    println!("\nOutputted python");
-    println!("{}", spacer(28, '-'));
-    let lines: String = parser
-        .parse()
-        .iter().map(|x| x.to_owned() + "\n")
-        .collect();
-    println!("{}", lines);
+/// The manifest of the project the current folder is in, if it's in one
+fn project_manifest() -> Result<Option<Manifest>, String> {
+    let here: PathBuf = std::env::current_dir().unwrap_or_default();
+    match find_project(&here) {
+        Some(folder) => {
+            let path: PathBuf = folder.join(MANIFEST);
+            let contents: String = fs::read_to_string(&path)
+                .map_err(|error| format!("error: couldn't read {}: {}\n", path.display(), error))?;
+            Ok(Some(parse_manifest(&contents)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Run a source or bytecode file for `mahou run`, giving back its exit code. With
+/// profile it's run with the interpreter and the report goes to stderr
+fn run_file(
+    filename: &str,
+    program_args: &[String],
+    seed: Option<u64>,
+    profile: bool,
+) -> Result<i32, MahouError> {
+    let bytes: Vec<u8> = read_bytes(filename)
+        .map_err(|error| MahouError::io("read", shown_filename(filename), &error))?;
+    // A .mhc file that doesn't start like one got broken, it isn't source to compile
+    if !bytes.starts_with(MAGIC) && Path::new(filename).extension().is_some_and(|x| x == "mhc") {
+        return Err(MahouError::Diagnostic(format!(
+            "error: {} is not a mahou bytecode file\n",
+            filename
+        )));
+    }
+    let stdin: io::Stdin = io::stdin();
+    if profile {
+        if bytes.starts_with(MAGIC) {
+            return Err(MahouError::Diagnostic(
+                "error: --profile needs the program's source, a .mhc file doesn't know its lines\n"
+                    .to_string(),
+            ));
+        }
+        let contents: String = String::from_utf8_lossy(&bytes).into_owned();
+        let (result, report): (Result<i32, MahouError>, String) = mahou::profile(
+            &contents,
+            Some(shown_filename(filename)),
+            program_args,
+            seed,
+            stdin.lock(),
+            io::stdout(),
+        )?;
+        // On stderr so it doesn't get mixed in with what the program prints
+        eprint!("{}", report);
+        return result;
+    }
+    // Anything that isn't bytecode is compiled from source and run straight away,
+    // only a .mhc has to be decoded
+    if bytes.starts_with(MAGIC) {
+        run_bytecode_with_args(&bytes, program_args, seed, stdin.lock(), io::stdout())
+    } else {
+        let contents: String = String::from_utf8_lossy(&bytes).into_owned();
+        run_on_vm(
+            &contents,
+            Some(shown_filename(filename)),
+            program_args,
+            seed,
+            stdin.lock(),
+            io::stdout(),
+        )
+    }
+}
+
+/// Write to the file from --output, or print it if there isn't one
+fn write_or_print(args: &Opt, output: &str) {
+    match &args.output {
+        Some(path) => {
+            if let Err(error) = write_output(path, output, args.force) {
+                eprint!("{}", error);
+                process::exit(1);
+            }
+        }
+        None => print_out(output),
+    }
 }
 
 #[cfg(test)]
@@ -331,59 +1421,159 @@ mod tests {
     use super::*;
 
     #[test]
-    fn lexer_test() {
-        let mut lexer: Lexer = new_lexer("set a");
-        lexer.lexer();
+    fn read_source_test() {
+        let stdin: &[u8] = b"set a = 1;\nprint a;";
         assert_eq!(
-            lexer.tokens,
-            vec![
-                Token {
-                    part: "set".to_string(),
-                    token: Tokens::Set,
-                    line_num: 1,
-                    char_num: 1,
-                },
-                Token {
-                    part: "a".to_string(),
-                    token: Tokens::Identifier,
-                    line_num: 1,
-                    char_num: 5,
-                },
-            ]
+            read_source_from("-", stdin).unwrap(),
+            "set a = 1;\nprint a;"
         );
 
-        let mut lexer: Lexer = new_lexer("jump -2");
-        lexer.lexer();
+        // Anything else is a file, and stdin doesn't get touched
+        let path: std::path::PathBuf = std::env::temp_dir().join("mahou_read_source_test.m");
+        fs::write(&path, "print 2;").unwrap();
+        let filename: &str = path.to_str().unwrap();
+        assert_eq!(read_source_from(filename, stdin).unwrap(), "print 2;");
+        fs::remove_file(&path).unwrap();
+        assert!(read_source_from(filename, stdin).is_err());
+    }
+
+    #[test]
+    fn output_dir_test() {
+        let args: Opt = Opt::from_iter(&[
+            "mahou",
+            "src/test.m",
+            "--target",
+            "python,js",
+            "--output-dir",
+            "out",
+        ]);
+        assert_eq!(args.emit, vec![Emit::Python, Emit::Js]);
+        assert_eq!(args.emit_path(Emit::Js), Some(PathBuf::from("out/test.js")));
+        assert!(!args.for_terminal());
+        let args: Opt = Opt::from_iter(&["mahou", "-e", "print 1;", "--output-dir", "out"]);
         assert_eq!(
-            lexer.tokens,
-            vec![
-                Token {
-                    part: "jump".to_string(),
-                    token: Tokens::Jump,
-                    line_num: 1,
-                    char_num: 1,
-                },
-                Token {
-                    part: "-".to_string(),
-                    token: Tokens::Minus,
-                    line_num: 1,
-                    char_num: 6,
-                },
-                Token {
-                    part: "2".to_string(),
-                    token: Tokens::Numeric,
-                    line_num: 1,
-                    char_num: 7,
-                },
-            ]
+            args.emit_path(Emit::Python),
+            Some(PathBuf::from("out/main.py"))
+        );
+        assert_eq!(
+            Opt::from_iter(&["mahou", "test.m"]).emit_path(Emit::Python),
+            None
+        );
+        // Only one of them says where the output goes
+        assert!(
+            Opt::from_iter_safe(&["mahou", "test.m", "-o", "a.py", "--output-dir", "out"]).is_err()
         );
     }
 
     #[test]
-    fn tokenize_test() {
-        assert_eq!(tokenize("set"), Tokens::Set);
-        assert_eq!(tokenize("+"), Tokens::Plus);
-        assert_eq!(tokenize("1"), Tokens::Numeric);
-        assert_eq!(tokenize("a"), Tokens::Identifier);
+    fn filename_test() {
+        let filename = |args: &[&str]| -> Result<String, &'static str> {
+            let args: Opt = Opt::from_iter(args);
+            args.filename_or_error().map(|x| x.to_string())
+        };
+        assert_eq!(filename(&["mahou", "test.m"]), Ok("test.m".to_string()));
+        assert_eq!(filename(&["mahou", "-"]), Ok("-".to_string()));
+        assert_eq!(filename(&["mahou", "--stdin"]), Ok("-".to_string()));
+        // A program from -e is shown as <eval>, and can't have a file too
+        assert_eq!(
+            filename(&["mahou", "-e", "print 1;", "--seed", "7", "--", "a"]),
+            Ok("<eval>".to_string())
+        );
+        assert!(filename(&["mahou", "-e", "print 1;", "test.m"]).is_err());
+        assert!(filename(&["mahou", "--stdin", "test.m"]).is_err());
+        assert!(filename(&["mahou"]).is_err());
+
+        // Everything after -- is for the program, even if it looks like a flag
+        let args: Opt = Opt::from_iter(&["mahou", "--run", "test.m", "--", "a", "--run"]);
+        assert_eq!(args.filename_or_error(), Ok("test.m"));
+        assert_eq!(args.args, vec!["a".to_string(), "--run".to_string()]);
+
+        // A script's shebang runs `mahou run` with the script and its arguments
+        let args: Opt = Opt::from_iter(&["mahou", "run", "./greet.m", "ann", "-b"]);
+        match args.command {
+            Some(Command::Run { filename, args, .. }) => {
+                assert_eq!(filename, "./greet.m");
+                assert_eq!(args, vec!["ann".to_string(), "-b".to_string()]);
+            }
+            _ => panic!("expected run"),
+        }
+        // A seed right after the file is for mahou, one after an argument is the program's
+        let args: Opt = Opt::from_iter(&["mahou", "run", "a.m", "--seed", "7", "b", "--seed"]);
+        match args.command {
+            Some(Command::Run { seed, args, .. }) => {
+                assert_eq!(seed, Some(7));
+                assert_eq!(args, vec!["b".to_string(), "--seed".to_string()]);
+            }
+            _ => panic!("expected run"),
+        }
+    }
+
+    #[test]
+    fn emit_test() {
+        // A space after one emit goes on to the file instead of being another emit
+        let args: Opt = Opt::from_iter(&["mahou", "--emit", "js", "test.m"]);
+        assert_eq!(args.emit, vec![Emit::Js]);
+        assert_eq!(args.filename_or_error(), Ok("test.m"));
+
+        let args: Opt = Opt::from_iter(&["mahou", "--emit", "python,js", "test.m"]);
+        assert_eq!(args.emit, vec![Emit::Python, Emit::Js]);
+        assert_eq!(args.filename_or_error(), Ok("test.m"));
+
+        let args: Opt = Opt::from_iter(&["mahou", "test.m", "--emit=tokens"]);
+        assert_eq!(args.emit, vec![Emit::Tokens]);
+        assert_eq!(
+            Opt::from_iter(&["mahou", "test.m"]).emit,
+            vec![Emit::Python]
+        );
+    }
+
+    #[test]
+    fn write_output_test() {
+        let folder: PathBuf = std::env::temp_dir().join("mahou_write_output_test");
+        let path: PathBuf = folder.join("out").join("main.py");
+        let _ = fs::remove_dir_all(&folder);
+
+        // The folders get made, then the file is only replaced with force
+        assert_eq!(write_output(&path, "print(1)\n", false), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "print(1)\n");
+        assert!(write_output(&path, "print(2)\n", false)
+            .unwrap_err()
+            .to_string()
+            .ends_with("it already exists, use --force to replace it\n"));
+        assert_eq!(write_output(&path, "print(2)\n", true), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "print(2)\n");
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn run_file_test() {
+        // Source with the bytecode extension is a broken file, not a program
+        let path: PathBuf = std::env::temp_dir().join("mahou_run_file_test.mhc");
+        fs::write(&path, "print 1;").unwrap();
+        let filename: &str = path.to_str().unwrap();
+        assert_eq!(
+            run_file(filename, &[], None, false),
+            Err(MahouError::Diagnostic(format!(
+                "error: {} is not a mahou bytecode file\n",
+                filename
+            )))
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn man_test() {
+        let page: String = man();
+        assert!(page.starts_with(".TH MAHOU 1"));
+        for command in ["build", "doc", "completions", "man", "test"] {
+            assert!(page.contains(&format!(".SS mahou {}\n", command)));
+        }
+        assert!(!page.contains(".SS mahou help"));
+        assert!(page.contains("\\-\\-no\\-peephole"));
+
+        let mut script: Vec<u8> = Vec::new();
+        Opt::clap().gen_completions_to("mahou", Shell::Bash, &mut script);
+        assert!(String::from_utf8(script).unwrap().contains("completions"));
     }
 }
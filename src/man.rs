@@ -0,0 +1,83 @@
+//! The man page for `mahou man`, made from the help of the CLI so the two can't
+//! end up saying different things. It's roff, for `man -l` or for going in man1/
+//! when mahou gets packaged
+
+/// Escape the text for roff, where a backslash starts an escape and a line that
+/// starts with `.` or `'` is a request
+fn roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line: String = line.replace('\\', "\\e").replace('-', "\\-");
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{}\n", line)
+            } else {
+                format!("{}\n", line)
+            }
+        })
+        .collect()
+}
+
+/// The help without its first line, which is only the name and the version
+fn body(help: &str) -> &str {
+    help.split_once('\n')
+        .map_or("", |(_, rest)| rest)
+        .trim_matches('\n')
+}
+
+/// The page from the help of mahou and of each of its commands. The help is
+/// already laid out, so it's kept the way it is instead of roff filling it
+pub fn man_page(version: &str, about: &str, help: &str, commands: &[(String, String)]) -> String {
+    let mut page: String = format!(
+        ".TH MAHOU 1 \"\" \"mahou {}\" \"User Commands\"\n.SH NAME\nmahou \\- {}.SH DESCRIPTION\n.nf\n{}.fi\n",
+        version,
+        roff(about),
+        roff(body(help))
+    );
+    if !commands.is_empty() {
+        page.push_str(".SH COMMANDS\n");
+    }
+    for (name, help) in commands {
+        page.push_str(&format!(
+            ".SS mahou {}\n.nf\n{}.fi\n",
+            name,
+            roff(body(help))
+        ));
+    }
+    page
+}
+
+/// The names in the SUBCOMMANDS part of the help, besides `help` itself
+pub fn command_names(help: &str) -> Vec<String> {
+    help.lines()
+        .skip_while(|x| *x != "SUBCOMMANDS:")
+        .skip(1)
+        .take_while(|x| x.starts_with(' '))
+        // A description that goes over more than one line is indented further
+        .filter_map(|x| x.strip_prefix("    "))
+        .filter(|x| !x.starts_with(' '))
+        .filter_map(|x| x.split_whitespace().next())
+        .filter(|x| *x != "help")
+        .map(|x| x.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn man_page_test() {
+        let help: &str = "mahou 0.1.0\nRuns programs\n\nFLAGS:\n    -O, --optimize    Work out .5 + 1\n\nSUBCOMMANDS:\n    build    Compile a program, with\n             more after\n    help     Prints this message\n    run      Run one\n";
+        assert_eq!(command_names(help), vec!["build", "run"]);
+        assert_eq!(
+            man_page(
+                "0.1.0",
+                "A programming language",
+                help,
+                &[("run".to_string(), "mahou-run 0.1.0\nRun a\\b\n".to_string())]
+            ),
+            ".TH MAHOU 1 \"\" \"mahou 0.1.0\" \"User Commands\"\n.SH NAME\nmahou \\- A programming language\n.SH DESCRIPTION\n.nf\nRuns programs\n\nFLAGS:\n    \\-O, \\-\\-optimize    Work out .5 + 1\n\nSUBCOMMANDS:\n    build    Compile a program, with\n             more after\n    help     Prints this message\n    run      Run one\n.fi\n.SH COMMANDS\n.SS mahou run\n.nf\nRun a\\eb\n.fi\n"
+        );
+        assert_eq!(roff(".hidden\n'quote"), "\\&.hidden\n\\&'quote\n");
+    }
+}
@@ -0,0 +1,526 @@
+//! Loads the files a program uses with `use shapes;`. The statements of each one go
+//! where it's first used, so it only runs once, and the names it sets get the name
+//! of the module in front of them so they can't clash with the program's. The
+//! program gets to them like `shapes.area`
+
+use crate::analysis::{check_division_by_zero, check_undefined};
+use crate::ast::{walk_expr_mut, walk_stmt_mut, Expr, Position, Stmt, VisitMut};
+use crate::codegen::{assigned_names, global_names};
+use crate::diagnostics::Diagnostic;
+use crate::lexer::{hole_tokens, new_lexer, Lex, Lexer, Token, Tokens};
+use crate::parser::{new_parser, Parse, ParseError, Parser};
+use crate::semantic::{check_constants, check_loops, Consts};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What the file of a module ends with, `use shapes;` loads shapes.m
+pub const EXTENSION: &str = "m";
+
+/// Lex, check and parse one file without loading what it uses, with any error
+/// already rendered for showing
+pub fn parse_file(
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<Vec<Stmt>, String> {
+    parse_file_with_starts(contents, filename, zero_based).map(|(stmts, _)| stmts)
+}
+
+/// The same as parse_file, along with where each statement starts like the parser's starts
+pub fn parse_file_with_starts(
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<(Vec<Stmt>, Vec<Position>), String> {
+    let mut lexer: Lexer = new_lexer(contents);
+    if let Err(error) = lexer.lexer() {
+        return Err(Diagnostic::from(&error).render(contents, filename, zero_based));
+    }
+
+    let render = |error: &ParseError| -> String {
+        Diagnostic::from(error).render(contents, filename, zero_based)
+    };
+    check_undefined(&lexer.tokens).map_err(|error| render(&error))?;
+    let mut parser: Parser = new_parser(&lexer.tokens);
+    let stmts: Vec<Stmt> = parser
+        .parse_all()
+        .map_err(|errors| errors.iter().map(render).collect::<String>())?;
+    check_constants(&stmts, &lexer.tokens, &mut Consts::new()).map_err(|error| render(&error))?;
+    check_division_by_zero(&stmts).map_err(|error| render(&error))?;
+    check_loops(&stmts).map_err(|error| render(&error))?;
+    Ok((stmts, parser.starts))
+}
+
+/// What a name from a module is called once it's loaded, `shapes.area` is `shapes__area`
+pub fn module_name(module: &str, name: &str) -> String {
+    format!("{}__{}", module, name)
+}
+
+/// Put the statements of every module the program uses in place of its use,
+/// the filename is where the modules are looked for
+pub fn load_modules(
+    stmts: Vec<Stmt>,
+    contents: &str,
+    filename: Option<&str>,
+    zero_based: bool,
+) -> Result<Vec<Stmt>, String> {
+    let mut loader: Loader = new_loader(zero_based);
+    // So a module using the program back is found as a cycle too
+    if let Some(path) = filename.and_then(|x| fs::canonicalize(x).ok()) {
+        let name: String = path
+            .file_stem()
+            .map_or(String::new(), |x| x.to_string_lossy().into_owned());
+        loader.loading.push((path, name));
+    }
+    loader.load(stmts, contents, filename, None)
+}
+
+/// The file and every module it uses that can be found, and the ones they use, by
+/// only lexing them so a file that doesn't parse yet still has its modules found
+pub fn module_files(filename: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = vec![PathBuf::from(filename)];
+    let mut index: usize = 0;
+    while let Some(file) = files.get(index).cloned() {
+        index += 1;
+        let contents: String = match fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(_) => continue,
+        };
+        let folder: &Path = file.parent().unwrap_or_else(|| Path::new(""));
+        let mut lexer: Lexer = new_lexer(&contents);
+        let _ = lexer.lexer();
+        for pair in lexer.tokens.windows(2) {
+            if pair[0].token == Tokens::Use && pair[1].token == Tokens::Identifier {
+                let path: PathBuf = folder.join(format!("{}.{}", pair[1].part, EXTENSION));
+                if path.exists() && !files.contains(&path) {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Keeps track of the files that have been loaded, so each one only runs once
+/// and none of them can end up using itself
+pub struct Loader {
+    zero_based: bool,
+    /// The names each file that's been loaded sets, by where the file is
+    loaded: HashMap<PathBuf, Vec<String>>,
+    /// The files being loaded right now and the names they were used by, each
+    /// one is using the one after it
+    loading: Vec<(PathBuf, String)>,
+}
+
+/// Start with nothing loaded
+pub fn new_loader(zero_based: bool) -> Loader {
+    Loader {
+        zero_based,
+        loaded: HashMap::new(),
+        loading: Vec::new(),
+    }
+}
+
+impl Loader {
+    /// Load what a file uses. The names of a module get its name in front of them,
+    /// the program's own names stay the same
+    pub fn load(
+        &mut self,
+        stmts: Vec<Stmt>,
+        contents: &str,
+        filename: Option<&str>,
+        module: Option<&str>,
+    ) -> Result<Vec<Stmt>, String> {
+        let zero_based: bool = self.zero_based;
+        let render = |error: ParseError| -> String {
+            Diagnostic::from(&error).render(contents, filename, zero_based)
+        };
+        let folder: &Path = filename
+            .and_then(|x| Path::new(x).parent())
+            .unwrap_or_else(|| Path::new(""));
+
+        // What each module this file uses sets, and the statements it adds
+        let mut modules: HashMap<String, Vec<String>> = HashMap::new();
+        let mut bodies: Vec<Vec<Stmt>> = Vec::new();
+        for stmt in &stmts {
+            if let Stmt::Use { name, position } = stmt {
+                let here = |message: String| -> String {
+                    render(ParseError {
+                        code: "E0203",
+                        message,
+                        line_num: position.line_num,
+                        char_num: position.char_num,
+                    })
+                };
+                let (names, body): (Vec<String>, Vec<Stmt>) =
+                    self.use_module(folder, name, here)?;
+                modules.insert(name.to_owned(), names);
+                bodies.push(body);
+            }
+        }
+
+        // The lexer already worked, this is only for where the names are
+        let mut lexer: Lexer = new_lexer(contents);
+        let _ = lexer.lexer();
+        check_module_names(&lexer.tokens, &modules).map_err(render)?;
+
+        let mut rename: Rename = Rename {
+            module,
+            names: top_level_names(&stmts).into_iter().collect(),
+            locals: HashSet::new(),
+        };
+        let mut bodies = bodies.into_iter();
+        let mut loaded: Vec<Stmt> = Vec::new();
+        for mut stmt in stmts {
+            if let Stmt::Use { .. } = stmt {
+                loaded.extend(bodies.next().unwrap_or_default());
+            } else {
+                rename.visit_stmt_mut(&mut stmt);
+                loaded.push(stmt);
+            }
+        }
+        Ok(loaded)
+    }
+    /// Load the module in the folder, giving back the names it sets and its statements,
+    /// which are empty if it's already been loaded. Here makes an error at the use
+    fn use_module(
+        &mut self,
+        folder: &Path,
+        name: &str,
+        here: impl Fn(String) -> String,
+    ) -> Result<(Vec<String>, Vec<Stmt>), String> {
+        let path: PathBuf = folder.join(format!("{}.{}", name, EXTENSION));
+        let canonical: PathBuf = fs::canonicalize(&path).map_err(|_| {
+            here(format!(
+                "can't find the module '{}', there's no {}",
+                name,
+                path.display()
+            ))
+        })?;
+        if let Some(start) = self.loading.iter().position(|(x, _)| *x == canonical) {
+            let mut cycle: Vec<&str> = self.loading[start..]
+                .iter()
+                .map(|(_, x)| x.as_str())
+                .collect();
+            cycle.push(name);
+            return Err(here(format!(
+                "module '{}' ends up using itself, {}",
+                name,
+                cycle.join(" -> ")
+            )));
+        }
+        if let Some(names) = self.loaded.get(&canonical) {
+            return Ok((names.to_vec(), Vec::new()));
+        }
+
+        let contents: String = fs::read_to_string(&path)
+            .map_err(|error| here(format!("can't read the module '{}': {}", name, error)))?;
+        let filename: String = path.display().to_string();
+        let stmts: Vec<Stmt> = parse_file(&contents, Some(&filename), self.zero_based)?;
+        let names: Vec<String> = top_level_names(&stmts);
+
+        self.loading.push((canonical.clone(), name.to_owned()));
+        let loaded: Result<Vec<Stmt>, String> =
+            self.load(stmts, &contents, Some(&filename), Some(name));
+        self.loading.pop();
+        self.loaded.insert(canonical, names.to_vec());
+        Ok((names, loaded?))
+    }
+}
+
+/// Every variable and function a file sets outside of functions, or from inside
+/// of one with `global`, which are the names another file can get to
+pub fn top_level_names(stmts: &[Stmt]) -> Vec<String> {
+    let mut names: Vec<String> = assigned_names(stmts);
+    let functions = stmts.iter().filter_map(|x| match x {
+        Stmt::Func { name, .. } => Some(name.to_owned()),
+        _ => None,
+    });
+    for name in functions.chain(global_names(stmts)) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Make sure each name like `shapes.area` is from a module the file uses, and that
+/// the module sets it
+fn check_module_names(
+    tokens: &[Token],
+    modules: &HashMap<String, Vec<String>>,
+) -> Result<(), ParseError> {
+    let holes: Vec<Token> = tokens
+        .iter()
+        .filter(|x| x.token == Tokens::Format)
+        .flat_map(hole_tokens)
+        .collect();
+    for token in tokens.iter().chain(holes.iter()) {
+        let (module, name): (&str, &str) = match token.part.split_once('.') {
+            Some(parts) if token.token == Tokens::Identifier => parts,
+            _ => continue,
+        };
+        let message: String = match modules.get(module) {
+            None => format!(
+                "'{}' isn't a module this file uses, it needs 'use {};' first",
+                module, module
+            ),
+            Some(names) if !names.iter().any(|x| x == name) => {
+                format!("module '{}' has no '{}'", module, name)
+            }
+            Some(_) => continue,
+        };
+        return Err(ParseError {
+            code: "E0200",
+            message,
+            line_num: token.line_num(),
+            char_num: token.char_num(),
+        });
+    }
+    Ok(())
+}
+
+/// Gives the names of a module its name in front, and turns names like
+/// `shapes.area` into the name of what they point to
+pub struct Rename<'a> {
+    /// The module being loaded, the program itself doesn't have one
+    module: Option<&'a str>,
+    /// The names the module sets, which are the only ones that change
+    names: HashSet<String>,
+    /// The variables of the function being gone through, which stay as they are
+    locals: HashSet<String>,
+}
+
+impl Rename<'_> {
+    fn rename(&self, name: &mut String) {
+        if let Some((module, item)) = name.split_once('.') {
+            *name = module_name(module, item);
+        } else if let Some(module) = self.module {
+            if self.names.contains(name) && !self.locals.contains(name) {
+                *name = module_name(module, name);
+            }
+        }
+    }
+}
+
+impl VisitMut for Rename<'_> {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Set { name, .. }
+            | Stmt::Const { name, .. }
+            | Stmt::SetIndex { name, .. }
+            | Stmt::AugAssign { name, .. }
+            | Stmt::For { name, .. } => self.rename(name),
+            Stmt::SetChain { names, .. } | Stmt::SetMany { names, .. } | Stmt::Global { names } => {
+                for name in names {
+                    self.rename(name);
+                }
+            }
+            // What a function sets is its own, unless it says global
+            Stmt::Func { name, params, body } => {
+                self.rename(name);
+                let mut locals: HashSet<String> = params.iter().cloned().collect();
+                locals.extend(assigned_names(body));
+                for name in global_names(body) {
+                    locals.remove(&name);
+                }
+                let outside: HashSet<String> = std::mem::replace(&mut self.locals, locals);
+                for stmt in body {
+                    self.visit_stmt_mut(stmt);
+                }
+                self.locals = outside;
+                return;
+            }
+            _ => {}
+        }
+        walk_stmt_mut(self, stmt);
+    }
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Identifier(name, _) | Expr::Call { name, .. } = expr {
+            self.rename(name);
+        }
+        walk_expr_mut(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Position;
+
+    /// Write the files to a folder of their own and load main.m from it
+    fn load(folder: &str, files: &[(&str, &str)]) -> Result<Vec<Stmt>, String> {
+        let folder: PathBuf = std::env::temp_dir().join(folder);
+        fs::create_dir_all(&folder).unwrap();
+        for (name, contents) in files {
+            fs::write(folder.join(name), contents).unwrap();
+        }
+        let filename: String = folder.join("main.m").display().to_string();
+        let contents: String = fs::read_to_string(&filename).unwrap();
+        let stmts: Vec<Stmt> = parse_file(&contents, Some(&filename), false)?;
+        load_modules(stmts, &contents, Some(&filename), false)
+    }
+
+    fn position(line_num: i64, char_num: i64) -> Position {
+        Position { line_num, char_num }
+    }
+
+    #[test]
+    fn module_files_test() {
+        let folder: PathBuf = std::env::temp_dir().join("mahou_module_files_test");
+        load(
+            "mahou_module_files_test",
+            &[
+                ("main.m", "use a;\nuse a;\nprint a.b;"),
+                ("a.m", "use b;\nset b = b.c;"),
+                ("b.m", "use main;\nset c = 1;"),
+            ],
+        )
+        .unwrap_err();
+        let filename: String = folder.join("main.m").display().to_string();
+        let files: Vec<PathBuf> = module_files(&filename);
+        assert_eq!(
+            files,
+            vec![
+                folder.join("main.m"),
+                folder.join("a.m"),
+                folder.join("b.m")
+            ]
+        );
+    }
+
+    #[test]
+    fn load_modules_test() {
+        let stmts: Vec<Stmt> = load(
+            "mahou_load_modules_test",
+            &[
+                (
+                    "main.m",
+                    "use shapes;\nuse shapes;\nset area = 2;\nprint shapes.square(shapes.side);",
+                ),
+                (
+                    "shapes.m",
+                    "set side = 3;\nfunc square(side) { return side * side; }",
+                ),
+            ],
+        )
+        .unwrap();
+        // The module runs once where it's first used, with its names changed
+        assert_eq!(
+            stmts,
+            vec![
+                Stmt::Set {
+                    name: "shapes__side".to_string(),
+                    value: Expr::Numeric("3".to_string()),
+                },
+                Stmt::Func {
+                    name: "shapes__square".to_string(),
+                    params: vec!["side".to_string()],
+                    body: vec![Stmt::Return {
+                        value: Some(Expr::Binary {
+                            left: Box::new(Expr::Identifier("side".to_string(), position(2, 28))),
+                            operator: Tokens::Multiply,
+                            right: Box::new(Expr::Identifier("side".to_string(), position(2, 35))),
+                            position: position(2, 33),
+                        }),
+                    }],
+                },
+                Stmt::Set {
+                    name: "area".to_string(),
+                    value: Expr::Numeric("2".to_string()),
+                },
+                Stmt::Print {
+                    exprs: vec![Expr::Call {
+                        name: "shapes__square".to_string(),
+                        args: vec![Expr::Identifier(
+                            "shapes__side".to_string(),
+                            position(4, 21)
+                        )],
+                        position: position(4, 7),
+                    }],
+                },
+            ]
+        );
+
+        // A function's own variables stay the same, unless it says global
+        let stmts: Vec<Stmt> = load(
+            "mahou_load_modules_global_test",
+            &[
+                ("main.m", "use count;\ncount.add();\nprint count.total;"),
+                (
+                    "count.m",
+                    "set total = 0;\nfunc add() { global total; set total = total + 1; set step = 1; }",
+                ),
+            ],
+        )
+        .unwrap();
+        match &stmts[1] {
+            Stmt::Func { body, .. } => assert_eq!(
+                (&body[0], &body[2]),
+                (
+                    &Stmt::Global {
+                        names: vec!["count__total".to_string()]
+                    },
+                    &Stmt::Set {
+                        name: "step".to_string(),
+                        value: Expr::Numeric("1".to_string()),
+                    }
+                )
+            ),
+            stmt => panic!("expected a function, found {:?}", stmt),
+        }
+    }
+
+    #[test]
+    fn load_modules_error_test() {
+        let error: String = load(
+            "mahou_load_modules_missing_test",
+            &[("main.m", "set a = 1;\nuse nothing;")],
+        )
+        .unwrap_err();
+        assert!(error.starts_with("error[E0203]: can't find the module 'nothing'"));
+        assert!(error.contains("main.m:2:5\n"));
+
+        let error: String = load(
+            "mahou_load_modules_cycle_test",
+            &[("main.m", "use a;"), ("a.m", "use b;"), ("b.m", "use a;")],
+        )
+        .unwrap_err();
+        assert!(error.starts_with("error[E0203]: module 'a' ends up using itself, a -> b -> a\n"));
+        assert!(error.contains("b.m:1:5\n"));
+
+        let error: String = load(
+            "mahou_load_modules_main_cycle_test",
+            &[("main.m", "use a;"), ("a.m", "use main;")],
+        )
+        .unwrap_err();
+        assert!(error.contains("module 'main' ends up using itself, main -> a -> main\n"));
+
+        let error: String = load(
+            "mahou_load_modules_name_test",
+            &[("main.m", "use a;\nprint a.y;"), ("a.m", "set x = 1;")],
+        )
+        .unwrap_err();
+        assert!(error.starts_with("error[E0200]: module 'a' has no 'y'\n"));
+        assert!(error.contains("main.m:2:7\n"));
+
+        let error: String = load(
+            "mahou_load_modules_unused_test",
+            &[("main.m", "print \"{b.x}\";")],
+        )
+        .unwrap_err();
+        assert!(error.starts_with(
+            "error[E0200]: 'b' isn't a module this file uses, it needs 'use b;' first\n"
+        ));
+
+        // Errors in a module are shown in its own file
+        let error: String = load(
+            "mahou_load_modules_parse_test",
+            &[("main.m", "use a;"), ("a.m", "print b;")],
+        )
+        .unwrap_err();
+        assert!(error.starts_with("error[E0200]: variable 'b' is not defined\n"));
+        assert!(error.contains("a.m:1:7\n"));
+    }
+}
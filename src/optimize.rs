@@ -0,0 +1,413 @@
+//! Passes that make the program simpler before any backend sees it, turned on with `-O`
+
+use crate::analysis::jump_target;
+use crate::ast::{walk_expr, walk_expr_mut, walk_stmt, Expr, Stmt, Visit, VisitMut};
+use crate::interpreter::{binary, negate, parse_number, unary, Value};
+use crate::lexer::{is_float_literal, Tokens};
+use std::collections::{HashMap, HashSet};
+
+/// Run every pass over the program, giving back how many statements were taken out
+pub fn optimize(stmts: &mut Vec<Stmt>) -> usize {
+    fold_constants(stmts);
+    eliminate_dead_code(stmts)
+}
+
+/// Work out the parts of expressions that are only literals, so `2 * 3 + 1` becomes `7`
+pub fn fold_constants(stmts: &mut [Stmt]) {
+    for stmt in stmts {
+        ConstantFolder.visit_stmt_mut(stmt);
+    }
+}
+
+/// Folds from the bottom up, so a literal made by folding can be folded again
+pub struct ConstantFolder;
+
+impl VisitMut for ConstantFolder {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+        // A condition that's already known picks its side, whatever that side is
+        if let Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } = expr
+        {
+            if let Some(condition) = literal(condition) {
+                let picked: Expr = if condition.is_truthy() {
+                    (**then).clone()
+                } else {
+                    (**otherwise).clone()
+                };
+                *expr = picked;
+                return;
+            }
+        }
+        // Anything that would be an error when it runs is left for it to happen then
+        let value: Option<Value> = match expr {
+            Expr::Unary {
+                operator: Tokens::Minus,
+                expr: inner,
+            } if !matches!(**inner, Expr::Numeric(_)) => {
+                literal(inner).and_then(|x| negate(x).ok())
+            }
+            Expr::Unary {
+                operator: Tokens::Not,
+                expr: inner,
+            } => literal(inner).and_then(|x| unary(Tokens::Not, x).ok()),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => match (literal(left), literal(right)) {
+                (Some(left), Some(right)) => binary(left, *operator, right).ok(),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(folded) = value.and_then(value_expr) {
+            *expr = folded;
+        }
+    }
+}
+
+/// Take out statements that can never run and sets of variables that are never
+/// read, going again until there's nothing left to take out since taking out one
+/// set can leave the variable it read unused. Gives back how many were taken out
+pub fn eliminate_dead_code(stmts: &mut Vec<Stmt>) -> usize {
+    let mut removed: usize = 0;
+    loop {
+        let mut reads: ReadNames = ReadNames {
+            names: HashSet::new(),
+            lines: false,
+        };
+        for stmt in stmts.iter() {
+            reads.visit_stmt(stmt);
+        }
+        let count: usize = eliminate_block(stmts, &reads);
+        if count == 0 {
+            return removed;
+        }
+        removed += count;
+    }
+}
+
+/// Every variable the program reads. Lines from custom statements could read
+/// anything, so when there are some every set is kept
+pub struct ReadNames {
+    names: HashSet<String>,
+    lines: bool,
+}
+
+impl ReadNames {
+    fn is_read(&self, name: &str) -> bool {
+        self.lines || self.names.contains(name)
+    }
+}
+
+impl Visit for ReadNames {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            // Setting an item changes the list the variable already has
+            Stmt::AugAssign { name, .. } | Stmt::SetIndex { name, .. } => {
+                self.names.insert(name.to_owned());
+            }
+            Stmt::Lines(_) => self.lines = true,
+            _ => {}
+        }
+        walk_stmt(self, stmt);
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Identifier(name, _) = expr {
+            self.names.insert(name.to_owned());
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Take the dead statements out of a block and the blocks inside of it. Jumps
+/// count statements, so a block with a jump that can't be worked out is left how it is
+fn eliminate_block(stmts: &mut Vec<Stmt>, reads: &ReadNames) -> usize {
+    let mut removed: usize = 0;
+    for stmt in stmts.iter_mut() {
+        match stmt {
+            Stmt::If {
+                body, else_body, ..
+            } => {
+                removed += eliminate_block(body, reads);
+                if let Some(else_body) = else_body {
+                    removed += eliminate_block(else_body, reads);
+                }
+            }
+            Stmt::Match { arms, .. } => {
+                for arm in arms {
+                    removed += eliminate_block(&mut arm.body, reads);
+                }
+            }
+            Stmt::Try { body, handler, .. } => {
+                removed += eliminate_block(body, reads);
+                removed += eliminate_block(handler, reads);
+            }
+            Stmt::While { body, .. } | Stmt::For { body, .. } | Stmt::Func { body, .. } => {
+                removed += eliminate_block(body, reads)
+            }
+            _ => {}
+        }
+    }
+
+    let mut targets: HashMap<usize, usize> = HashMap::new();
+    for (index, stmt) in stmts.iter().enumerate() {
+        if let Stmt::Jump { offset } = stmt {
+            match jump_target(index, offset, stmts.len()) {
+                Some(target) => targets.insert(index, target),
+                None => return removed,
+            };
+        }
+    }
+
+    // Follow the block from the start, a jump goes straight to where it lands
+    let mut reached: Vec<bool> = vec![false; stmts.len()];
+    let mut next: Option<usize> = Some(0);
+    while let Some(index) = next {
+        if index >= stmts.len() || reached[index] {
+            break;
+        }
+        reached[index] = true;
+        next = Some(targets.get(&index).copied().unwrap_or(index + 1));
+    }
+    let keep: Vec<bool> = stmts
+        .iter()
+        .zip(reached)
+        .map(|(stmt, reached)| reached && !is_unused_set(stmt, reads))
+        .collect();
+
+    // Where each statement ends up, with one more for the end of the block, so
+    // a jump to a statement that's taken out lands on the one after it
+    let mut moved: Vec<usize> = Vec::new();
+    let mut kept: usize = 0;
+    for keep in &keep {
+        moved.push(kept);
+        if *keep {
+            kept += 1;
+        }
+    }
+    moved.push(kept);
+    for (index, target) in targets {
+        let offset: i64 = moved[target] as i64 - moved[index] as i64;
+        if keep[index] && jump_target(index, &int_expr(offset), stmts.len()) != Some(target) {
+            stmts[index] = Stmt::Jump {
+                offset: int_expr(offset),
+            };
+        }
+    }
+
+    let mut index: usize = 0;
+    stmts.retain(|_| {
+        index += 1;
+        keep[index - 1]
+    });
+    removed + keep.len() - kept
+}
+
+/// A set that only gives variables nobody reads a value that can't do anything
+/// when it's worked out. Calls, input and division are kept since running them can
+fn is_unused_set(stmt: &Stmt, reads: &ReadNames) -> bool {
+    let (names, values): (Vec<&String>, Vec<&Expr>) = match stmt {
+        Stmt::Set { name, value } | Stmt::Const { name, value } => (vec![name], vec![value]),
+        Stmt::SetChain { names, value } => (names.iter().collect(), vec![value]),
+        Stmt::SetMany { names, values } => (names.iter().collect(), values.iter().collect()),
+        _ => return false,
+    };
+    names.iter().all(|x| !reads.is_read(x)) && values.iter().all(|x| is_pure(x))
+}
+
+fn is_pure(expr: &Expr) -> bool {
+    match expr {
+        Expr::Numeric(_)
+        | Expr::Str(_)
+        | Expr::Bool(_)
+        | Expr::Identifier(..)
+        | Expr::Constant(_) => true,
+        // An index can be out of range, and a range can be of something that isn't an int
+        Expr::Input | Expr::Call { .. } | Expr::Index { .. } | Expr::Range { .. } => false,
+        Expr::List(items) | Expr::Format(items) => items.iter().all(is_pure),
+        // A key can be a list, which is an error
+        Expr::Dict(_) => false,
+        Expr::Unary { expr, .. } => is_pure(expr),
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => {
+            !matches!(operator, Tokens::Divide | Tokens::Modulo) && is_pure(left) && is_pure(right)
+        }
+        Expr::Ternary {
+            condition,
+            then,
+            otherwise,
+        } => is_pure(condition) && is_pure(then) && is_pure(otherwise),
+    }
+}
+
+/// An int as an expression, a negative one has a minus in front
+fn int_expr(number: i64) -> Expr {
+    value_expr(Value::Int(number)).unwrap_or_else(|| Expr::Numeric(number.to_string()))
+}
+
+/// The value of an expression that's already a literal, a negative number is a
+/// minus in front of one
+fn literal(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Numeric(number) => parse_number(number).ok(),
+        Expr::Str(text) => Some(Value::Str(text.as_str().into())),
+        Expr::Bool(value) => Some(Value::Bool(*value)),
+        Expr::Unary {
+            operator: Tokens::Minus,
+            expr,
+        } if matches!(**expr, Expr::Numeric(_)) => negate(literal(expr)?).ok(),
+        _ => None,
+    }
+}
+
+/// Write a value as a literal, if it fits in one
+fn value_expr(value: Value) -> Option<Expr> {
+    let negative = |number: String| Expr::Unary {
+        operator: Tokens::Minus,
+        expr: Box::new(Expr::Numeric(number)),
+    };
+    match value {
+        Value::Int(number) if number < 0 => Some(negative(number.checked_neg()?.to_string())),
+        Value::Int(number) => Some(Expr::Numeric(number.to_string())),
+        Value::Float(number) => {
+            let text: String = format!("{:?}", number.abs());
+            if !is_float_literal(&text) || !number.is_finite() {
+                None
+            } else if number.is_sign_negative() {
+                Some(negative(text))
+            } else {
+                Some(Expr::Numeric(text))
+            }
+        }
+        Value::Str(text) => Some(Expr::Str(text.to_string())),
+        Value::Bool(value) => Some(Expr::Bool(value)),
+        Value::List(_) | Value::Dict(_) | Value::Range(..) | Value::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::{python_codegen, PrintStyle};
+    use crate::interpreter::{new_interpreter, Interpret, Interpreter};
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    fn folded(contents: &str) -> String {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let mut stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        fold_constants(&mut stmts);
+        python_codegen(&stmts, PrintStyle::Function)
+    }
+
+    fn optimized(contents: &str) -> String {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let mut stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        optimize(&mut stmts);
+        python_codegen(&stmts, PrintStyle::Function)
+    }
+
+    /// How many statements were taken out, the python, and what running it prints
+    /// before and after, which should be the same
+    fn eliminated(contents: &str) -> (usize, String, String, String) {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let mut stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        let before: String = run(&stmts);
+        let removed: usize = optimize(&mut stmts);
+        (
+            removed,
+            python_codegen(&stmts, PrintStyle::Function),
+            before,
+            run(&stmts),
+        )
+    }
+
+    fn run(stmts: &[Stmt]) -> String {
+        let mut output: Vec<u8> = Vec::new();
+        let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+            new_interpreter(&b""[..], &mut output);
+        interpreter.max_steps = Some(1000);
+        interpreter.run(stmts).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn fold_constants_test() {
+        assert_eq!(folded("set a = 2 * 3 + 1;"), "a = 7\n");
+        assert_eq!(folded("set a = b * (2 + 3);"), "a = b * 5\n");
+        assert_eq!(
+            folded("print 7 / 2;\nprint 2 - 5;"),
+            "print(3.5)\nprint(-3)\n"
+        );
+        assert_eq!(
+            folded("print -(1 + 1);\nprint 2 ** -1;"),
+            "print(-2)\nprint(0.5)\n"
+        );
+        assert_eq!(folded("print \"a\" + \"b\";"), "print(\"ab\")\n");
+        assert_eq!(
+            folded("print 1 > 2 ? a : b + 1;\nprint a ? 1 : 2 + 2;"),
+            "print(b + 1)\nprint(1 if a else 4)\n"
+        );
+        assert_eq!(
+            folded("print 1 < 2;\nprint not true or 2 == 3;"),
+            "print(True)\nprint(False)\n"
+        );
+        assert_eq!(
+            folded("while a < 2 * 5 {\n  a += 1 + 1;\n}"),
+            "while a < 10:\n    a += 2\n"
+        );
+
+        // These would be errors or can't be written as a literal, so they're left alone
+        assert_eq!(folded("print 1 + \"a\";"), "print(1 + \"a\")\n");
+        assert_eq!(
+            folded("print 9223372036854775807 + 1;"),
+            "print(9223372036854775807 + 1)\n"
+        );
+    }
+
+    #[test]
+    fn eliminate_dead_code_test() {
+        // b is never read, and the jump goes over the print
+        let (removed, python, before, after) =
+            eliminated("set a = 1;\nset b = 2;\njump 2;\nprint 3;\nprint a;");
+        assert_eq!(removed, 2);
+        assert_eq!(
+            python,
+            "a = 1\nraise NotImplementedError(\"jump 1 only works with mahou --run\")\nprint(a)\n"
+        );
+        assert_eq!(before, after);
+
+        // Taking out d leaves c unused too, and the jump that landed on
+        // the set it took out lands on the print after it
+        let (removed, _, before, after) = eliminated(
+            "set c = 5;\nset d = c;\nif 1 { jump 2; print 7; set e = 1; print 8; }\nprint 9;",
+        );
+        assert_eq!(removed, 4);
+        assert_eq!(before, after);
+        assert_eq!(after, "8\n9\n");
+
+        // Calls, input and division could do something, so they stay
+        assert_eq!(
+            optimized("func f() { print 1; }\nset a = f();\nset b = 1 / 0;\nset c = input;"),
+            "def f():\n    print(1)\na = f()\nb = 1 / 0\nc = input()\n"
+        );
+        // A jump that can't be worked out leaves its block alone
+        assert_eq!(
+            optimized("set a = 1;\njump a;\nset b = 2;"),
+            "a = 1\nraise NotImplementedError(\"jump a only works with mahou --run\")\nb = 2\n"
+        );
+    }
+}
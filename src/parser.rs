@@ -0,0 +1,2124 @@
+//! Builds the statements out of the tokens from the lexer
+
+use crate::ast::{Expr, MatchArm, Position, Stmt};
+use crate::codegen::{python_codegen, PrintStyle};
+use crate::dump::stmt_sexpr;
+use crate::keywords::alias;
+use crate::lexer::{format_pieces, lex_hole, unescape, FormatPiece, Token, TokenKind, Tokens};
+use crate::log;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// An error found in the tokens, along with where it happened
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseError {
+    pub message: String,
+    /// Which kind of error it is, like `E0101`, so it can be looked up
+    pub code: &'static str,
+    pub line_num: i64,
+    pub char_num: i64,
+}
+
+/// Check if a token is one of the compound assignment operators
+pub fn is_compound_assign(token: Tokens) -> bool {
+    matches!(
+        token,
+        Tokens::PlusAssign | Tokens::MinusAssign | Tokens::MultiplyAssign | Tokens::DivideAssign
+    )
+}
+
+/// How tightly a binary operator binds, a higher number binds tighter
+pub fn precedence(token: Tokens) -> Option<u8> {
+    match token {
+        Tokens::Or => Some(1),
+        Tokens::And => Some(2),
+        Tokens::LessThan
+        | Tokens::GreaterThan
+        | Tokens::LessEqual
+        | Tokens::GreaterEqual
+        | Tokens::Equal
+        | Tokens::NotEqual
+        | Tokens::In => Some(NOT_PRECEDENCE),
+        // A range goes around math but inside of comparisons, like `a in 0..n + 1`
+        Tokens::Range => Some(4),
+        Tokens::Plus | Tokens::Minus => Some(5),
+        Tokens::Multiply | Tokens::Divide | Tokens::Modulo => Some(6),
+        Tokens::Power => Some(POWER_PRECEDENCE),
+        _ => None,
+    }
+}
+
+/// `not` goes around comparisons like in python, so `not a == b` is `not (a == b)`,
+/// but inside of `and` and `or`
+pub const NOT_PRECEDENCE: u8 = 3;
+
+/// Power binds tighter than any other operator
+pub const POWER_PRECEDENCE: u8 = 7;
+
+/// Check if an operator groups from the right, like `2 ** 3 ** 2`
+pub fn is_right_associative(token: Tokens) -> bool {
+    token == Tokens::Power
+}
+
+/// Check if a token compares two values
+pub fn is_comparison(token: Tokens) -> bool {
+    matches!(
+        token,
+        Tokens::LessThan
+            | Tokens::GreaterThan
+            | Tokens::LessEqual
+            | Tokens::GreaterEqual
+            | Tokens::Equal
+            | Tokens::NotEqual
+            | Tokens::In
+    )
+}
+
+/// The source text of an operator token
+pub fn operator_text(token: Tokens) -> &'static str {
+    match token {
+        Tokens::Plus => "+",
+        Tokens::Minus => "-",
+        Tokens::Multiply => "*",
+        Tokens::Divide => "/",
+        Tokens::Modulo => "%",
+        Tokens::Power => "**",
+        Tokens::LessThan => "<",
+        Tokens::GreaterThan => ">",
+        Tokens::LessEqual => "<=",
+        Tokens::GreaterEqual => ">=",
+        Tokens::Equal => "==",
+        Tokens::NotEqual => "!=",
+        Tokens::In => "in",
+        Tokens::And => "and",
+        Tokens::Or => "or",
+        Tokens::Not => "not",
+        Tokens::Range => "..",
+        Tokens::Question => "?",
+        Tokens::PlusAssign => "+=",
+        Tokens::MinusAssign => "-=",
+        Tokens::MultiplyAssign => "*=",
+        Tokens::DivideAssign => "/=",
+        _ => "",
+    }
+}
+
+/// The constants that can be used with `$`, like `$PI`, and the value each one stands for
+pub const CONSTANTS: [(&str, f64); 2] = [("PI", std::f64::consts::PI), ("E", std::f64::consts::E)];
+
+/// The constants that come from the arguments after `--` when the program is run,
+/// `$ARGS` is a list of them as strings and `$ARGC` is how many there are
+pub const ARGUMENT_CONSTANTS: [&str; 2] = ["ARGS", "ARGC"];
+
+/// Look up the value of a constant by its name without the `$`
+pub fn constant_value(name: &str) -> Option<f64> {
+    CONSTANTS
+        .iter()
+        .find(|(constant, _)| *constant == name)
+        .map(|(_, value)| *value)
+}
+
+/// Check if a token can be the first one of an expression
+pub fn starts_expression(token: Tokens) -> bool {
+    matches!(
+        token,
+        Tokens::Numeric
+            | Tokens::Float
+            | Tokens::Bool
+            | Tokens::Str
+            | Tokens::Format
+            | Tokens::Var
+            | Tokens::Identifier
+            | Tokens::Input
+            | Tokens::LeftParen
+            | Tokens::LeftBracket
+            | Tokens::LeftBrace
+            | Tokens::Minus
+            | Tokens::Not
+    )
+}
+
+/// Only the first line of something, so a block doesn't fill up a log message
+pub fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
+}
+
+/// Where the token is, for the parts of the tree that keep track of it
+pub fn position(token: &Token) -> Position {
+    Position {
+        line_num: token.line_num(),
+        char_num: token.char_num(),
+    }
+}
+
+/// Check if an expression is a number, a string or a bool written out, which is
+/// all a match arm can match. A negative number is a minus in front of one
+pub fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Numeric(_) | Expr::Str(_) | Expr::Bool(_) => true,
+        Expr::Unary {
+            operator: Tokens::Minus,
+            expr,
+        } => matches!(**expr, Expr::Numeric(_)),
+        _ => false,
+    }
+}
+
+/// The error for a binary operator missing the value on one of its sides
+pub fn missing_operand(operator: &Token) -> ParseError {
+    ParseError {
+        code: "E0103",
+        message: format!("missing operand for '{}'", operator.part),
+        line_num: operator.line_num(),
+        char_num: operator.char_num(),
+    }
+}
+
+/// Given the tokens from the lexer, build the statements they make up
+pub trait Parse {
+    fn peek(&self) -> Option<&Token>;
+    fn advance(&mut self) -> Option<Token>;
+    fn expect(&mut self, token: Tokens, what: &str) -> Result<Token, ParseError>;
+    fn error(&self, message: String) -> ParseError;
+    fn missing_semi(&self, message: String) -> ParseError;
+    fn set(&mut self) -> Result<Stmt, ParseError>;
+    fn print(&mut self) -> Result<Stmt, ParseError>;
+    fn jump(&mut self) -> Result<Stmt, ParseError>;
+    fn compound(&mut self) -> Result<Stmt, ParseError>;
+    fn step(&mut self) -> Result<Stmt, ParseError>;
+    fn exec(&mut self) -> Result<Stmt, ParseError>;
+    fn if_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn while_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn match_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn try_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn for_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn func(&mut self) -> Result<Stmt, ParseError>;
+    fn return_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn global(&mut self) -> Result<Stmt, ParseError>;
+    fn use_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn exit_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn loop_exit_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn assert_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn const_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn input_statement(&mut self) -> Result<Stmt, ParseError>;
+    fn block(&mut self) -> Result<Vec<Stmt>, ParseError>;
+    fn block_body(&mut self) -> Result<Vec<Stmt>, ParseError>;
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ParseError>;
+    fn postfix(&mut self) -> Result<Expr, ParseError>;
+    fn primary(&mut self) -> Result<Expr, ParseError>;
+    fn call(&mut self) -> Result<Expr, ParseError>;
+    fn list(&mut self) -> Result<Expr, ParseError>;
+    fn dict(&mut self) -> Result<Expr, ParseError>;
+    fn format_string(&mut self) -> Result<Expr, ParseError>;
+    fn index(&mut self) -> Result<(Expr, Position), ParseError>;
+    fn statement(&mut self) -> Result<Stmt, ParseError>;
+    fn parse(&mut self) -> Result<Vec<Stmt>, ParseError>;
+    fn parse_all(&mut self) -> Result<Vec<Stmt>, Vec<ParseError>>;
+    fn synchronize(&mut self, start: usize);
+    fn register(&mut self, keyword: &str, handler: Handler);
+    #[allow(dead_code)]
+    fn python(&mut self) -> Result<Vec<String>, ParseError>;
+}
+
+/// Parses a statement that starts with a keyword, the parser is left on the keyword
+/// so the handler can check it and move past it. The semicolon is handled after
+pub type Handler = Rc<dyn Fn(&mut Parser) -> Result<Stmt, ParseError>>;
+
+/// Parses tokens it owns or borrows, so a caller that still needs the lexer's
+/// tokens after can hand it `&lexer.tokens` instead of a copy of all of them
+pub struct Parser<'a> {
+    tokens: Cow<'a, [Token]>,
+    index: usize,
+    pub print_style: PrintStyle,
+    handlers: HashMap<String, Handler>,
+    /// How many functions the parser is inside of, a return only works in one
+    function_depth: usize,
+    /// How many blocks the parser is inside of, a use only works outside of all of them
+    block_depth: usize,
+    /// Where each statement starts, in the order they were parsed, so one comes
+    /// before the ones inside of it
+    pub starts: Vec<Position>,
+}
+
+impl Parse for Parser<'_> {
+    /// Look at the current token without moving past it
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+    /// Move past the current token and return it
+    fn advance(&mut self) -> Option<Token> {
+        let tok: Option<Token> = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        tok
+    }
+    /// Move past the current token if it's the expected type, otherwise error
+    fn expect(&mut self, token: Tokens, what: &str) -> Result<Token, ParseError> {
+        match self.peek() {
+            Some(tok) if tok.token == token => Ok(self.advance().unwrap()),
+            _ => Err(self.error(format!("expected {}", what))),
+        }
+    }
+    /// Make an error at the current token, or the last one if the input ran out
+    fn error(&self, message: String) -> ParseError {
+        let (line_num, char_num): (i64, i64) = match self.peek().or_else(|| self.tokens.last()) {
+            Some(tok) => (tok.line_num(), tok.char_num()),
+            None => (1, 1),
+        };
+        ParseError {
+            code: "E0100",
+            message,
+            line_num,
+            char_num,
+        }
+    }
+    /// Errors for a missing semicolon point just past the end of the statement,
+    /// which is where the semicolon belongs
+    fn missing_semi(&self, message: String) -> ParseError {
+        let last: &Token = match self.index.checked_sub(1).and_then(|x| self.tokens.get(x)) {
+            Some(last) => last,
+            None => return self.error(message),
+        };
+        ParseError {
+            code: "E0101",
+            message,
+            line_num: last.line_num(),
+            char_num: last.char_num() + last.part.chars().count() as i64,
+        }
+    }
+    fn set(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Set, "'set'")?;
+        let mut names: Vec<String> = vec![self
+            .expect(Tokens::Identifier, "a variable name after 'set'")?
+            .part
+            .to_string()];
+
+        // Setting an item of a list, like `set xs[0] = 1;` or `set grid[1][2] = 0;`
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::LeftBracket) {
+            let mut indexes: Vec<Expr> = Vec::new();
+            let (index, position): (Expr, Position) = self.index()?;
+            indexes.push(index);
+            while matches!(self.peek(), Some(tok) if tok.token == Tokens::LeftBracket) {
+                indexes.push(self.index()?.0);
+            }
+            self.expect(Tokens::Assign, "'=' after the index")?;
+            let value: Expr = self.expression(0)?;
+            return Ok(Stmt::SetIndex {
+                name: names.remove(0),
+                indexes,
+                value,
+                position,
+            });
+        }
+
+        // Several names separated by commas get one value each, like `set a, b = 1, 2;`
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+            while matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+                self.advance();
+                names.push(
+                    self.expect(Tokens::Identifier, "a variable name after ','")?
+                        .part
+                        .to_string(),
+                );
+            }
+            self.expect(Tokens::Assign, "'=' after the variable names")?;
+            let mut values: Vec<Expr> = vec![self.expression(0)?];
+            while matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+                self.advance();
+                values.push(self.expression(0)?);
+            }
+            if names.len() != values.len() {
+                return Err(self.error(format!(
+                    "expected {} values for the names in the set, found {}",
+                    names.len(),
+                    values.len()
+                )));
+            }
+            return Ok(Stmt::SetMany { names, values });
+        }
+
+        // Otherwise every `name =` before the value is part of a chain, like `set a = b = 0;`
+        self.expect(Tokens::Assign, "'=' after the variable name")?;
+        loop {
+            let second_token: Option<Tokens> = self.tokens.get(self.index + 1).map(|x| x.token);
+            match (self.peek().map(|x| x.token), second_token) {
+                (Some(Tokens::Identifier), Some(Tokens::Assign)) => {
+                    names.push(self.advance().unwrap().part.to_string());
+                    self.advance();
+                }
+                (Some(Tokens::Assign), _) => {
+                    return Err(
+                        self.error("expected a name or a value between '=' and '='".to_string())
+                    )
+                }
+                _ => break,
+            }
+        }
+        let value: Expr = self.expression(0)?;
+        if names.len() > 1 {
+            return Ok(Stmt::SetChain { names, value });
+        }
+        Ok(Stmt::Set {
+            name: names.remove(0),
+            value,
+        })
+    }
+    fn print(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Print, "'print'")?;
+        let mut exprs: Vec<Expr> = vec![self.expression(0)?];
+        while matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+            self.advance();
+            exprs.push(self.expression(0)?);
+        }
+        Ok(Stmt::Print { exprs })
+    }
+    fn jump(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Jump, "'jump'")?;
+        let offset: Expr = self.expression(0)?;
+        Ok(Stmt::Jump { offset })
+    }
+    fn compound(&mut self) -> Result<Stmt, ParseError> {
+        let name: Token = self.expect(Tokens::Identifier, "a variable name")?;
+        let operator: Tokens = self.advance().unwrap().token;
+        let value: Expr = self.expression(0)?;
+        Ok(Stmt::AugAssign {
+            name: name.part.to_string(),
+            operator,
+            value,
+        })
+    }
+    /// `a++` is `a += 1` and `a--` is `a -= 1`
+    fn step(&mut self) -> Result<Stmt, ParseError> {
+        let name: Token = self.expect(Tokens::Identifier, "a variable name")?;
+        let operator: Tokens = match self.advance().unwrap().token {
+            Tokens::Increment => Tokens::PlusAssign,
+            _ => Tokens::MinusAssign,
+        };
+        Ok(Stmt::AugAssign {
+            name: name.part.to_string(),
+            operator,
+            value: Expr::Numeric("1".to_string()),
+        })
+    }
+    fn exec(&mut self) -> Result<Stmt, ParseError> {
+        Ok(Stmt::Expr(self.expression(0)?))
+    }
+    fn if_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::If, "'if'")?;
+        let condition: Expr = self.expression(0)?;
+        let body: Vec<Stmt> = self.block()?;
+        let else_body: Option<Vec<Stmt>> = match self.peek() {
+            Some(tok) if tok.token == Tokens::Else => {
+                self.advance();
+                match self.peek() {
+                    // An else if is a statement of its own inside of the else
+                    Some(tok) if tok.token == Tokens::If => {
+                        self.starts.push(position(tok));
+                        Some(vec![self.if_statement()?])
+                    }
+                    _ => Some(self.block()?),
+                }
+            }
+            _ => None,
+        };
+        Ok(Stmt::If {
+            condition,
+            body,
+            else_body,
+        })
+    }
+    fn while_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::While, "'while'")?;
+        let condition: Expr = self.expression(0)?;
+        let body: Vec<Stmt> = self.block()?;
+        Ok(Stmt::While { condition, body })
+    }
+    /// Parse `match x { 1 => print "one"; _ => { ... } }`, each arm runs a block or
+    /// a single statement and can have a comma after it
+    fn match_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.expect(Tokens::Match, "'match'")?;
+        let value: Expr = self.expression(0)?;
+        self.expect(Tokens::LeftBrace, "'{' to start the arms")?;
+        let mut arms: Vec<MatchArm> = Vec::new();
+        loop {
+            let start: Token = match self.peek() {
+                Some(tok) if tok.token == Tokens::RightBrace => break,
+                Some(tok) => tok.clone(),
+                None => return Err(self.error("expected '}' to end the match".to_string())),
+            };
+            let pattern: Option<Expr> = if start.token == Tokens::Identifier && start.part == "_" {
+                self.advance();
+                None
+            } else {
+                let pattern: Expr = self.expression(0)?;
+                if !is_literal(&pattern) {
+                    return Err(ParseError {
+                        code: "E0108",
+                        message: "a match arm can only match a number, a string, a bool or '_'"
+                            .to_string(),
+                        line_num: start.line_num(),
+                        char_num: start.char_num(),
+                    });
+                }
+                Some(pattern)
+            };
+            self.expect(Tokens::Arrow, "'=>' after what the arm matches")?;
+            self.block_depth += 1;
+            let body: Result<Vec<Stmt>, ParseError> = match self.peek() {
+                Some(tok) if tok.token == Tokens::LeftBrace => {
+                    self.advance();
+                    self.block_body()
+                }
+                _ => self.statement().map(|x| vec![x]),
+            };
+            self.block_depth -= 1;
+            arms.push(MatchArm {
+                pattern,
+                body: body?,
+                position: position(&start),
+            });
+            if matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+                self.advance();
+            }
+        }
+        self.advance();
+        Ok(Stmt::Match {
+            value,
+            arms,
+            position: position(&keyword),
+        })
+    }
+    /// Parse `for x in xs { ... }`, the `in` here isn't the operator
+    /// Parse `try { ... } catch err { ... }`, the name after catch is what the
+    /// error's message goes in
+    fn try_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Try, "'try'")?;
+        let body: Vec<Stmt> = self.block()?;
+        self.expect(Tokens::Catch, "'catch' after the try block")?;
+        let name: Token = self.expect(Tokens::Identifier, "a variable name after 'catch'")?;
+        let handler: Vec<Stmt> = self.block()?;
+        Ok(Stmt::Try {
+            body,
+            name: name.part.to_string(),
+            handler,
+        })
+    }
+    fn for_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::For, "'for'")?;
+        let name: Token = self.expect(Tokens::Identifier, "a variable name after 'for'")?;
+        let keyword: Token = self.expect(Tokens::In, "'in' after the variable name")?;
+        let iterable: Expr = self.expression(0)?;
+        let body: Vec<Stmt> = self.block()?;
+        Ok(Stmt::For {
+            name: name.part.to_string(),
+            iterable,
+            body,
+            position: position(&keyword),
+        })
+    }
+    fn func(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Func, "'func'")?;
+        let name: Token = self.expect(Tokens::Identifier, "a function name")?;
+        self.expect(Tokens::LeftParen, "'(' to start the parameters")?;
+        let mut params: Vec<String> = Vec::new();
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::RightParen) {
+            self.advance();
+        } else {
+            loop {
+                let param: Token = self.expect(Tokens::Identifier, "a parameter name")?;
+                if params.iter().any(|x| param.part == *x) {
+                    return Err(ParseError {
+                        code: "E0105",
+                        message: format!("duplicate parameter '{}'", param.part),
+                        line_num: param.line_num(),
+                        char_num: param.char_num(),
+                    });
+                }
+                params.push(param.part.to_string());
+                match self.advance() {
+                    Some(tok) if tok.token == Tokens::Comma => {}
+                    Some(tok) if tok.token == Tokens::RightParen => break,
+                    _ => {
+                        return Err(self.error("expected ',' or ')' after a parameter".to_string()))
+                    }
+                }
+            }
+        }
+        self.function_depth += 1;
+        let body: Result<Vec<Stmt>, ParseError> = self.block();
+        self.function_depth -= 1;
+        Ok(Stmt::Func {
+            name: name.part.to_string(),
+            params,
+            body: body?,
+        })
+    }
+    fn return_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.expect(Tokens::Return, "'return'")?;
+        if self.function_depth == 0 {
+            return Err(ParseError {
+                code: "E0106",
+                message: "'return' outside of a function".to_string(),
+                line_num: keyword.line_num(),
+                char_num: keyword.char_num(),
+            });
+        }
+        let value: Option<Expr> = match self.peek() {
+            Some(tok) if starts_expression(tok.token) => Some(self.expression(0)?),
+            _ => None,
+        };
+        Ok(Stmt::Return { value })
+    }
+    /// Parse `global a, b;`, which can go anywhere a set can
+    fn global(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Global, "'global'")?;
+        let mut names: Vec<String> = vec![self
+            .expect(Tokens::Identifier, "a variable name after 'global'")?
+            .part
+            .to_string()];
+        while matches!(self.peek(), Some(tok) if tok.token == Tokens::Comma) {
+            self.advance();
+            names.push(
+                self.expect(Tokens::Identifier, "a variable name after ','")?
+                    .part
+                    .to_string(),
+            );
+        }
+        Ok(Stmt::Global { names })
+    }
+    /// Parse `use shapes;`, which runs shapes.m from the same folder
+    fn use_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.expect(Tokens::Use, "'use'")?;
+        if self.block_depth > 0 {
+            return Err(ParseError {
+                code: "E0107",
+                message: "'use' inside of a block, it only works outside of them".to_string(),
+                line_num: keyword.line_num(),
+                char_num: keyword.char_num(),
+            });
+        }
+        let name: Token = self.expect(Tokens::Identifier, "a module name after 'use'")?;
+        if name.part.contains('.') {
+            return Err(ParseError {
+                code: "E0100",
+                message: format!("a module name can't have a '.' in it like '{}'", name.part),
+                line_num: name.line_num(),
+                char_num: name.char_num(),
+            });
+        }
+        Ok(Stmt::Use {
+            position: position(&name),
+            name: name.part.to_string(),
+        })
+    }
+    /// Parse `exit 1;`, which can go anywhere, even inside of a function
+    fn exit_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.expect(Tokens::Exit, "'exit'")?;
+        let code: Option<Expr> = match self.peek() {
+            Some(tok) if starts_expression(tok.token) => Some(self.expression(0)?),
+            _ => None,
+        };
+        Ok(Stmt::Exit {
+            code,
+            position: position(&keyword),
+        })
+    }
+    /// Parse `break;` or `continue;`, whether it's in a loop is checked after parsing
+    fn loop_exit_statement(&mut self) -> Result<Stmt, ParseError> {
+        match self.advance() {
+            Some(tok) if tok.token == Tokens::Break => Ok(Stmt::Break {
+                position: position(&tok),
+            }),
+            Some(tok) if tok.token == Tokens::Continue => Ok(Stmt::Continue {
+                position: position(&tok),
+            }),
+            _ => Err(self.error("expected 'break' or 'continue'".to_string())),
+        }
+    }
+    /// Parse `assert a > 0;` or `assert_eq a, 1;`
+    fn assert_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = match self.advance() {
+            Some(tok) if matches!(tok.token, Tokens::Assert | Tokens::AssertEq) => tok,
+            _ => return Err(self.error("expected 'assert' or 'assert_eq'".to_string())),
+        };
+        let value: Expr = self.expression(0)?;
+        let expected: Option<Expr> = match keyword.token {
+            Tokens::AssertEq => {
+                self.expect(Tokens::Comma, "',' between the two values of 'assert_eq'")?;
+                Some(self.expression(0)?)
+            }
+            _ => None,
+        };
+        Ok(Stmt::Assert {
+            value,
+            expected,
+            position: position(&keyword),
+        })
+    }
+    fn const_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.expect(Tokens::Const, "'const'")?;
+        let name: Token = self.expect(Tokens::Identifier, "a name after 'const'")?;
+        self.expect(Tokens::Assign, "'=' after the const name")?;
+        let value: Expr = self.expression(0)?;
+        Ok(Stmt::Const {
+            name: name.part.to_string(),
+            value,
+        })
+    }
+    /// Parse `input x;`, which is the same as `set x = parse(input);` so a number
+    /// that gets typed is a number
+    fn input_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword: Token = self.expect(Tokens::Input, "'input'")?;
+        let name: Token = self.expect(Tokens::Identifier, "a variable name after 'input'")?;
+        Ok(Stmt::Set {
+            name: name.part.to_string(),
+            value: Expr::Call {
+                name: "parse".to_string(),
+                args: vec![Expr::Input],
+                position: position(&keyword),
+            },
+        })
+    }
+    /// Parse statements between a pair of curly braces
+    fn block(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        self.expect(Tokens::LeftBrace, "'{' to start the block")?;
+        self.block_depth += 1;
+        let stmts: Result<Vec<Stmt>, ParseError> = self.block_body();
+        self.block_depth -= 1;
+        stmts
+    }
+    /// The statements of a block once its `{` has been moved past
+    fn block_body(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut stmts: Vec<Stmt> = Vec::new();
+        loop {
+            match self.peek() {
+                Some(tok) if tok.token == Tokens::RightBrace => break,
+                Some(_) => stmts.push(self.statement()?),
+                None => return Err(self.error("expected '}' to end the block".to_string())),
+            }
+        }
+        self.advance();
+        Ok(stmts)
+    }
+    /// Parse binary operators by precedence climbing, only operators that bind
+    /// at least as tight as min_precedence are consumed at this level
+    fn expression(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let mut left: Expr = self.postfix()?;
+        while let Some(tok) = self.peek() {
+            let operator: Tokens = tok.token;
+            let prec: u8 = match precedence(operator) {
+                Some(prec) if prec >= min_precedence => prec,
+                _ => break,
+            };
+            // Newlines are only whitespace to the parser, so a line that ends with
+            // an operator like `set a = 1 +` just continues onto the next line
+            let operator_token: Token = self.advance().unwrap();
+            if !matches!(self.peek(), Some(tok) if starts_expression(tok.token)) {
+                return Err(missing_operand(&operator_token));
+            }
+            // Power is right associative, so `2 ** 3 ** 2` is `2 ** (3 ** 2)`,
+            // the rest of the operators are left associative
+            let right: Expr = if is_right_associative(operator) {
+                self.expression(prec)?
+            } else {
+                self.expression(prec + 1)?
+            };
+            left = match operator {
+                Tokens::Range => Expr::Range {
+                    start: Box::new(left),
+                    end: Box::new(right),
+                    position: position(&operator_token),
+                },
+                _ => Expr::Binary {
+                    left: Box::new(left),
+                    operator,
+                    right: Box::new(right),
+                    position: position(&operator_token),
+                },
+            };
+        }
+        // `?` binds looser than every operator, and `a ? b : c ? d : e` is
+        // `a ? b : (c ? d : e)`
+        if min_precedence == 0 && matches!(self.peek(), Some(tok) if tok.token == Tokens::Question)
+        {
+            let question: Token = self.advance().unwrap();
+            if !matches!(self.peek(), Some(tok) if starts_expression(tok.token)) {
+                return Err(missing_operand(&question));
+            }
+            let then: Expr = self.expression(0)?;
+            self.expect(Tokens::Colon, "':' after the value for when it's true")?;
+            let otherwise: Expr = self.expression(0)?;
+            left = Expr::Ternary {
+                condition: Box::new(left),
+                then: Box::new(then),
+                otherwise: Box::new(otherwise),
+            };
+        }
+        Ok(left)
+    }
+    /// Parse a value and any indexes after it, like `xs[0]` or `grid[1][2]`
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr: Expr = self.primary()?;
+        while matches!(self.peek(), Some(tok) if tok.token == Tokens::LeftBracket) {
+            let (index, position): (Expr, Position) = self.index()?;
+            expr = Expr::Index {
+                target: Box::new(expr),
+                index: Box::new(index),
+                position,
+            };
+        }
+        Ok(expr)
+    }
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            Some(tok) if tok.token == Tokens::Numeric || tok.token == Tokens::Float => {
+                Ok(Expr::Numeric(self.advance().unwrap().part.to_string()))
+            }
+            // A name right before a parenthesis is a function call, like `len(a)`
+            Some(tok)
+                if tok.token == Tokens::Identifier
+                    && matches!(self.tokens.get(self.index + 1), Some(x) if x.token == Tokens::LeftParen) =>
+            {
+                self.call()
+            }
+            Some(tok) if tok.token == Tokens::Identifier => {
+                let name: Token = self.advance().unwrap();
+                Ok(Expr::Identifier(name.part.to_string(), position(&name)))
+            }
+            Some(tok) if tok.token == Tokens::Str => {
+                Ok(Expr::Str(self.advance().unwrap().part.to_string()))
+            }
+            Some(tok) if tok.token == Tokens::Bool => {
+                Ok(Expr::Bool(self.advance().unwrap().part == "true"))
+            }
+            Some(tok) if tok.token == Tokens::Format => self.format_string(),
+            Some(tok) if tok.token == Tokens::Var => {
+                self.advance();
+                let name: Token = self.expect(Tokens::Identifier, "a constant name after '$'")?;
+                if constant_value(&name.part).is_none()
+                    && !ARGUMENT_CONSTANTS.contains(&name.part.as_str())
+                {
+                    return Err(ParseError {
+                        code: "E0104",
+                        message: format!("unknown constant '${}'", name.part),
+                        line_num: name.line_num(),
+                        char_num: name.char_num(),
+                    });
+                }
+                Ok(Expr::Constant(name.part.to_string()))
+            }
+            // Reading from stdin is a value, so it works as `set name = input;`,
+            // or it can be called like python with a prompt
+            Some(tok)
+                if tok.token == Tokens::Input
+                    && matches!(self.tokens.get(self.index + 1), Some(x) if x.token == Tokens::LeftParen) =>
+            {
+                self.call()
+            }
+            Some(tok) if tok.token == Tokens::Input => {
+                self.advance();
+                Ok(Expr::Input)
+            }
+            Some(tok) if tok.token == Tokens::LeftParen => {
+                let open: Token = self.advance().unwrap();
+                let expr: Expr = self.expression(0)?;
+                match self.peek() {
+                    Some(tok) if tok.token == Tokens::RightParen => {
+                        self.advance();
+                        Ok(expr)
+                    }
+                    _ => Err(ParseError {
+                        code: "E0102",
+                        message: "unmatched '('".to_string(),
+                        line_num: open.line_num(),
+                        char_num: open.char_num(),
+                    }),
+                }
+            }
+            Some(tok) if tok.token == Tokens::LeftBracket => self.list(),
+            // A brace where a value should be starts a dict, a block only comes
+            // after a value like the condition of an if
+            Some(tok) if tok.token == Tokens::LeftBrace => self.dict(),
+            Some(tok) if tok.token == Tokens::RightParen => {
+                Err(self.error("unmatched ')'".to_string()))
+            }
+            Some(tok) if tok.token == Tokens::RightBracket => {
+                Err(self.error("unmatched ']'".to_string()))
+            }
+            // A binary operator with nothing before it, like `set a = + 5;`
+            Some(tok) if tok.token != Tokens::Minus && precedence(tok.token).is_some() => {
+                Err(missing_operand(tok))
+            }
+            // A minus where a value should be is negation, like `set a = -5;`,
+            // a minus after a value is subtraction and is handled by expression
+            Some(tok) if tok.token == Tokens::Minus => {
+                self.advance();
+                // Negation binds looser than power, like in python `-2 ** 2` is `-(2 ** 2)`
+                let expr: Expr = self.expression(POWER_PRECEDENCE)?;
+                Ok(Expr::Unary {
+                    operator: Tokens::Minus,
+                    expr: Box::new(expr),
+                })
+            }
+            Some(tok) if tok.token == Tokens::Not => {
+                self.advance();
+                let expr: Expr = self.expression(NOT_PRECEDENCE)?;
+                Ok(Expr::Unary {
+                    operator: Tokens::Not,
+                    expr: Box::new(expr),
+                })
+            }
+            Some(tok) => Err(self.error(format!("unexpected '{}'", tok.part))),
+            None => Err(self.error("expected an expression".to_string())),
+        }
+    }
+    /// Parse what's in each hole of a string like `"x is {x}"` on its own, the lexer
+    /// already made sure the braces match
+    fn format_string(&mut self) -> Result<Expr, ParseError> {
+        let tok: Token = self.advance().unwrap();
+        let mut parts: Vec<Expr> = Vec::new();
+        for piece in format_pieces(&tok.part).unwrap_or_default() {
+            match piece {
+                FormatPiece::Text(text) => {
+                    parts.push(Expr::Str(unescape(&text).unwrap_or(text)));
+                }
+                FormatPiece::Hole(offset, hole) => {
+                    let column: i64 = tok.char_num() + offset as i64 + 1;
+                    let tokens: Vec<Token> =
+                        lex_hole(&hole, tok.line_num(), column).map_err(|error| ParseError {
+                            code: error.code,
+                            message: error.message,
+                            line_num: error.line_num,
+                            char_num: error.char_num,
+                        })?;
+                    let mut parser: Parser = new_parser(tokens);
+                    parts.push(parser.expression(0)?);
+                    if let Some(extra) = parser.peek() {
+                        let message: String =
+                            format!("unexpected '{}' in the braces of a string", extra.part);
+                        return Err(parser.error(message));
+                    }
+                }
+            }
+        }
+        Ok(Expr::Format(parts))
+    }
+    /// Parse the name of the function and the arguments separated by commas
+    fn call(&mut self) -> Result<Expr, ParseError> {
+        let name: Token = self.advance().unwrap();
+        let at: Position = position(&name);
+        let name: String = name.part.to_string();
+        let open: Token = self.advance().unwrap();
+        let unmatched: ParseError = ParseError {
+            code: "E0102",
+            message: "unmatched '('".to_string(),
+            line_num: open.line_num(),
+            char_num: open.char_num(),
+        };
+
+        let mut args: Vec<Expr> = Vec::new();
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::RightParen) {
+            self.advance();
+            return Ok(Expr::Call {
+                name,
+                args,
+                position: at,
+            });
+        }
+        loop {
+            args.push(self.expression(0)?);
+            match self.peek() {
+                Some(tok) if tok.token == Tokens::Comma => {
+                    self.advance();
+                }
+                Some(tok) if tok.token == Tokens::RightParen => {
+                    self.advance();
+                    return Ok(Expr::Call {
+                        name,
+                        args,
+                        position: at,
+                    });
+                }
+                _ => return Err(unmatched),
+            }
+        }
+    }
+    /// Parse the values of a list separated by commas, like `[1, 2, 3]`
+    fn list(&mut self) -> Result<Expr, ParseError> {
+        let open: Token = self.advance().unwrap();
+        let unmatched: ParseError = ParseError {
+            code: "E0102",
+            message: "unmatched '['".to_string(),
+            line_num: open.line_num(),
+            char_num: open.char_num(),
+        };
+
+        let mut items: Vec<Expr> = Vec::new();
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::RightBracket) {
+            self.advance();
+            return Ok(Expr::List(items));
+        }
+        loop {
+            items.push(self.expression(0)?);
+            match self.peek() {
+                Some(tok) if tok.token == Tokens::Comma => {
+                    self.advance();
+                }
+                Some(tok) if tok.token == Tokens::RightBracket => {
+                    self.advance();
+                    return Ok(Expr::List(items));
+                }
+                _ => return Err(unmatched),
+            }
+        }
+    }
+    /// Parse the keys and values of a dict, like `{"a": 1, "b": 2}`
+    fn dict(&mut self) -> Result<Expr, ParseError> {
+        let open: Token = self.advance().unwrap();
+        let unmatched: ParseError = ParseError {
+            code: "E0102",
+            message: "unmatched '{'".to_string(),
+            line_num: open.line_num(),
+            char_num: open.char_num(),
+        };
+
+        let mut entries: Vec<(Expr, Expr)> = Vec::new();
+        if matches!(self.peek(), Some(tok) if tok.token == Tokens::RightBrace) {
+            self.advance();
+            return Ok(Expr::Dict(entries));
+        }
+        loop {
+            let key: Expr = self.expression(0)?;
+            self.expect(Tokens::Colon, "':' after the key")?;
+            entries.push((key, self.expression(0)?));
+            match self.peek() {
+                Some(tok) if tok.token == Tokens::Comma => {
+                    self.advance();
+                }
+                Some(tok) if tok.token == Tokens::RightBrace => {
+                    self.advance();
+                    return Ok(Expr::Dict(entries));
+                }
+                _ => return Err(unmatched),
+            }
+        }
+    }
+    /// Parse a single index in brackets, giving back where the `[` is for errors
+    /// like an index out of range
+    fn index(&mut self) -> Result<(Expr, Position), ParseError> {
+        let open: Token = self.advance().unwrap();
+        let index: Expr = self.expression(0)?;
+        match self.peek() {
+            Some(tok) if tok.token == Tokens::RightBracket => {
+                self.advance();
+                Ok((index, position(&open)))
+            }
+            _ => Err(ParseError {
+                code: "E0102",
+                message: "unmatched '['".to_string(),
+                line_num: open.line_num(),
+                char_num: open.char_num(),
+            }),
+        }
+    }
+    fn statement(&mut self) -> Result<Stmt, ParseError> {
+        let start: Position = position(self.peek().unwrap());
+        self.starts.push(start);
+        let first: &Token = self.peek().unwrap();
+        let first_token: Tokens = first.token;
+        // Handlers are by the keyword, so another word for it from the keywords table works too
+        let handler: Option<Handler> = self
+            .handlers
+            .get(alias(&first.part).unwrap_or(&first.part))
+            .cloned();
+        let second_token: Option<Tokens> = self.tokens.get(self.index + 1).map(|x| x.token);
+        let stmt: Stmt = match (first_token, second_token) {
+            // Blocks end with a curly brace instead of a semicolon
+            (Tokens::If, _) => return self.if_statement(),
+            (Tokens::While, _) => return self.while_statement(),
+            (Tokens::Match, _) => return self.match_statement(),
+            (Tokens::Try, _) => return self.try_statement(),
+            (Tokens::For, _) => return self.for_statement(),
+            (Tokens::Func, _) => return self.func(),
+            (Tokens::Input, Some(Tokens::Identifier)) => self.input_statement()?,
+            // If the line is a compound assignment like `a += 1`, which comes before
+            // the handlers so a name like `log` can still be changed
+            (Tokens::Identifier, Some(second)) if is_compound_assign(second) => self.compound()?,
+            (Tokens::Identifier, Some(Tokens::Increment | Tokens::Decrement)) => self.step()?,
+            // If the line starts with a keyword like set or print
+            _ if handler.is_some() => handler.unwrap()(self)?,
+            // If the line has no command, just interpret it
+            _ => self.exec()?,
+        };
+        match self.peek() {
+            Some(tok) if tok.token == Tokens::Semi => {
+                self.advance();
+                Ok(stmt)
+            }
+            Some(tok) if tok.token == Tokens::RightParen => {
+                Err(self.error("unmatched ')'".to_string()))
+            }
+            Some(tok) if tok.token == Tokens::RightBracket => {
+                Err(self.error("unmatched ']'".to_string()))
+            }
+            Some(tok) => {
+                let message: String = if tok.token == Tokens::RightBrace {
+                    "missing ';' before the '}' that ends the block".to_string()
+                } else if TokenKind::from(tok.token) == TokenKind::Keyword {
+                    format!("missing ';' before the next statement '{}'", tok.part)
+                } else {
+                    format!(
+                        "expected ';' at the end of the statement, found '{}'",
+                        tok.part
+                    )
+                };
+                Err(self.missing_semi(message))
+            }
+            // The semicolon is optional on the last statement, like `print a`
+            None => Ok(stmt),
+        }
+    }
+    /// Parse all of the statements, stopping at the first error
+    fn parse(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        self.parse_all().map_err(|mut errors| errors.remove(0))
+    }
+    /// Parse all of the statements, skipping past any that have an error
+    /// so every error in the program gets found at once
+    fn parse_all(&mut self) -> Result<Vec<Stmt>, Vec<ParseError>> {
+        let mut stmts: Vec<Stmt> = Vec::new();
+        let mut errors: Vec<ParseError> = Vec::new();
+        self.index = 0;
+        self.starts.clear();
+        while self.peek().is_some() {
+            let start: usize = self.index;
+            match self.statement() {
+                Ok(stmt) => {
+                    log::debug(|| format!("parsed {}", first_line(&stmt_sexpr(&stmt, 0))));
+                    stmts.push(stmt);
+                }
+                Err(error) => {
+                    errors.push(error);
+                    self.synchronize(start);
+                }
+            }
+        }
+        if errors.is_empty() {
+            log::info(|| format!("parsed {} statements", stmts.len()));
+            Ok(stmts)
+        } else {
+            Err(errors)
+        }
+    }
+    /// Skip the statement that starts at the index, up to its semicolon or the end
+    /// of its block, so the next one can be parsed
+    fn synchronize(&mut self, start: usize) {
+        self.index = start;
+        let mut depth: usize = 0;
+        while let Some(tok) = self.advance() {
+            match tok.token {
+                Tokens::LeftBrace => depth += 1,
+                // An else goes with the if before it, so it gets skipped too
+                Tokens::RightBrace if depth <= 1 => {
+                    if !matches!(self.peek(), Some(x) if x.token == Tokens::Else) {
+                        break;
+                    }
+                    depth = 0;
+                }
+                Tokens::RightBrace => depth -= 1,
+                Tokens::Semi if depth == 0 => break,
+                _ => {}
+            }
+        }
+    }
+    /// Add a handler for statements that start with the keyword,
+    /// replacing the one that was there before
+    fn register(&mut self, keyword: &str, handler: Handler) {
+        self.handlers.insert(keyword.to_string(), handler);
+    }
+    /// Parse and then generate python, one string per line
+    fn python(&mut self) -> Result<Vec<String>, ParseError> {
+        let stmts: Vec<Stmt> = self.parse()?;
+        Ok(python_codegen(&stmts, self.print_style)
+            .lines()
+            .map(|x| x.to_owned())
+            .collect())
+    }
+}
+
+/// Remove the boiler plate of making a parser object
+pub fn new_parser<'a>(tokens: impl Into<Cow<'a, [Token]>>) -> Parser<'a> {
+    let mut parser: Parser = Parser {
+        tokens: tokens.into(),
+        index: 0,
+        print_style: PrintStyle::Function,
+        handlers: HashMap::new(),
+        function_depth: 0,
+        block_depth: 0,
+        starts: Vec::new(),
+    };
+    // The built in statements go through the same handlers as custom ones
+    let set: Handler = Rc::new(|parser: &mut Parser| parser.set());
+    parser.register("set", set.clone());
+    parser.register("let", set);
+    parser.register("print", Rc::new(|parser: &mut Parser| parser.print()));
+    parser.register("jump", Rc::new(|parser: &mut Parser| parser.jump()));
+    parser.register(
+        "return",
+        Rc::new(|parser: &mut Parser| parser.return_statement()),
+    );
+    parser.register("global", Rc::new(|parser: &mut Parser| parser.global()));
+    parser.register("use", Rc::new(|parser: &mut Parser| parser.use_statement()));
+    parser.register(
+        "exit",
+        Rc::new(|parser: &mut Parser| parser.exit_statement()),
+    );
+    let loop_exit: Handler = Rc::new(|parser: &mut Parser| parser.loop_exit_statement());
+    parser.register("break", loop_exit.clone());
+    parser.register("continue", loop_exit);
+    let assert: Handler = Rc::new(|parser: &mut Parser| parser.assert_statement());
+    parser.register("assert", assert.clone());
+    parser.register("assert_eq", assert);
+    parser.register(
+        "const",
+        Rc::new(|parser: &mut Parser| parser.const_statement()),
+    );
+    parser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_undefined;
+    use crate::codegen::python_expr;
+    use crate::lexer::{new_lexer, tokenize, Lex, Lexer};
+
+    fn at(line_num: i64, char_num: i64) -> Position {
+        Position { line_num, char_num }
+    }
+
+    #[test]
+    fn borrowed_tokens_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1;\nprint a;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(&lexer.tokens);
+        assert!(matches!(parser.tokens, Cow::Borrowed(_)));
+        let borrowed: Vec<Stmt> = parser.parse().unwrap();
+        // The lexer still has its tokens, and owning them parses the same
+        assert_eq!(lexer.tokens.len(), 8);
+        assert_eq!(new_parser(lexer.tokens).parse(), Ok(borrowed));
+    }
+
+    #[test]
+    fn compound_assign_test() {
+        let mut lexer: Lexer = new_lexer("a += 1;");
+        lexer.lexer().unwrap();
+        let kinds: Vec<Tokens> = lexer.tokens.iter().map(|x| x.token).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Tokens::Identifier,
+                Tokens::PlusAssign,
+                Tokens::Numeric,
+                Tokens::Semi
+            ]
+        );
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(parser.python(), Ok(vec!["a += 1".to_string()]));
+
+        // A plain `+` should still be lexed on its own
+        let mut lexer: Lexer = new_lexer("a + 1;");
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[1].token, Tokens::Plus);
+
+        let mut lexer: Lexer = new_lexer("a++;\nb--;\nset c = a - -b;");
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[1].token, Tokens::Increment);
+        assert_eq!(lexer.tokens[4].token, Tokens::Decrement);
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a += 1".to_string(),
+                "b -= 1".to_string(),
+                "c = a - -b".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_all_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = ;\nprint 1;\nwhile a { set b = ; }\nset c = (1;\nprint 2;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        let errors: Vec<ParseError> = parser.parse_all().unwrap_err();
+        let lines: Vec<i64> = errors.iter().map(|x| x.line_num).collect();
+        assert_eq!(lines, vec![1, 3, 4]);
+
+        // The first error is the same one parse gives back
+        let mut lexer: Lexer = new_lexer("set a = ;\nset b = ;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        let first: ParseError = parser.parse().unwrap_err();
+        assert_eq!(first.line_num, 1);
+
+        // An if with an else gets skipped as one statement
+        let mut lexer: Lexer = new_lexer("if a { print ; } else { print 1; }\nprint 2;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(parser.parse_all().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse(),
+            Ok(vec![Stmt::Set {
+                name: "a".to_string(),
+                value: Expr::Numeric("1".to_string()),
+            }])
+        );
+
+        // Multiply binds tighter than plus
+        let mut lexer: Lexer = new_lexer("print a + b * 2;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse(),
+            Ok(vec![Stmt::Print {
+                exprs: vec![Expr::Binary {
+                    left: Box::new(Expr::Identifier("a".to_string(), at(1, 7))),
+                    operator: Tokens::Plus,
+                    right: Box::new(Expr::Binary {
+                        left: Box::new(Expr::Identifier("b".to_string(), at(1, 11))),
+                        operator: Tokens::Multiply,
+                        right: Box::new(Expr::Numeric("2".to_string())),
+                        position: at(1, 13),
+                    }),
+                    position: at(1, 9),
+                }],
+            }])
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = ;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(parser.parse().unwrap_err().message, "unexpected ';'");
+    }
+
+    #[test]
+    fn negative_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = -5;\nset a = b - 5;\nset a = b * -c;\nset a = - -b;\njump -2;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = -5".to_string(),
+                "a = b - 5".to_string(),
+                "a = b * -c".to_string(),
+                "a = -(-b)".to_string(),
+                "raise NotImplementedError(\"jump -2 only works with mahou --run\")".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = -5;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse(),
+            Ok(vec![Stmt::Set {
+                name: "a".to_string(),
+                value: Expr::Unary {
+                    operator: Tokens::Minus,
+                    expr: Box::new(Expr::Numeric("5".to_string())),
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn paren_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = (1 + 2) * 3;\nset b = 1 - (2 - 3);\nset c = -(a + b);\nset d = (a);",
+        );
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = (1 + 2) * 3".to_string(),
+                "b = 1 - (2 - 3)".to_string(),
+                "c = -(a + b)".to_string(),
+                "d = a".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = (1 + 2;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        let error: ParseError = parser.parse().unwrap_err();
+        assert_eq!(error.message, "unmatched '('");
+        assert_eq!(error.char_num, 9);
+
+        let mut lexer: Lexer = new_lexer("set a = 1 + 2);");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(parser.parse().unwrap_err().message, "unmatched ')'");
+    }
+
+    #[test]
+    fn missing_operand_test() {
+        let parse_error = |contents: &str| -> ParseError {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            new_parser(lexer.tokens).parse().unwrap_err()
+        };
+
+        // Missing the left side
+        let error: ParseError = parse_error("set a = + 5;");
+        assert_eq!(error.message, "missing operand for '+'");
+        assert_eq!((error.line_num, error.char_num), (1, 9));
+        assert_eq!(parse_error("print * 2;").message, "missing operand for '*'");
+
+        // Missing the right side
+        let error: ParseError = parse_error("set a = 5 +;");
+        assert_eq!(error.message, "missing operand for '+'");
+        assert_eq!((error.line_num, error.char_num), (1, 11));
+        assert_eq!(
+            parse_error("set a = (5 **);").message,
+            "missing operand for '**'"
+        );
+        assert_eq!(
+            parse_error("set a = 5 <").message,
+            "missing operand for '<'"
+        );
+
+        // A minus with nothing before it is still negation
+        let mut lexer: Lexer = new_lexer("set a = 1 - -5;");
+        lexer.lexer().unwrap();
+        assert!(new_parser(lexer.tokens).parse().is_ok());
+    }
+
+    #[test]
+    fn handler_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1;\nlog a + 1;\nlog += 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        parser.register(
+            "log",
+            Rc::new(|parser: &mut Parser| {
+                parser.advance();
+                let expr: Expr = parser.expression(0)?;
+                Ok(Stmt::Lines(vec![
+                    format!("print(\"log:\", {})", python_expr(&expr)),
+                    "print(\"-\" * 10)".to_string(),
+                ]))
+            }),
+        );
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 1".to_string(),
+                "print(\"log:\", a + 1)".to_string(),
+                "print(\"-\" * 10)".to_string(),
+                "log += 1".to_string(),
+            ])
+        );
+
+        // The built in keywords can be replaced too
+        let mut lexer: Lexer = new_lexer("print 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        parser.register(
+            "print",
+            Rc::new(|parser: &mut Parser| Err(parser.error("print is turned off".to_string()))),
+        );
+        assert_eq!(parser.parse().unwrap_err().message, "print is turned off");
+    }
+
+    #[test]
+    fn call_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = 1;\nset n = len(a);\nset m = max(abs(-a), f(), n * 2) + 1;\nprint(f(g(a)));",
+        );
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 1".to_string(),
+                "n = len(a)".to_string(),
+                "m = max(abs(-a), f(), n * 2) + 1".to_string(),
+                "print(f(g(a)))".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("set n = len(a;");
+        lexer.lexer().unwrap();
+        let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+        assert_eq!(
+            (error.message.as_str(), error.char_num),
+            ("unmatched '('", 12)
+        );
+
+        // The arguments still get checked
+        let mut lexer: Lexer = new_lexer("print len(b);");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            check_undefined(&lexer.tokens).unwrap_err().message,
+            "variable 'b' is not defined"
+        );
+    }
+
+    #[test]
+    fn list_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set xs = [1, 2 + 3, []];\nset xs[0] = xs[1] * -xs[-1];\nset grid = [xs];\nset grid[0][1] = (xs[0] + 1) * 2;",
+        );
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "xs = [1, 2 + 3, []]".to_string(),
+                "xs[0] = xs[1] * -xs[-1]".to_string(),
+                "grid = [xs]".to_string(),
+                "grid[0][1] = (xs[0] + 1) * 2".to_string(),
+            ])
+        );
+
+        let error = |contents: &str| -> (String, i64) {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+            (error.message, error.char_num)
+        };
+        assert_eq!(error("set xs = [1, 2;"), ("unmatched '['".to_string(), 10));
+        assert_eq!(error("print xs[0;"), ("unmatched '['".to_string(), 9));
+        assert_eq!(error("print xs];"), ("unmatched ']'".to_string(), 9));
+
+        // Setting an item reads the list, so it has to be set already
+        let mut lexer: Lexer = new_lexer("set xs[0] = 1;");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            check_undefined(&lexer.tokens).unwrap_err().message,
+            "variable 'xs' is not defined"
+        );
+    }
+
+    #[test]
+    fn dict_test() {
+        let mut lexer: Lexer =
+            new_lexer("set m = {\"a\": 1, 2: {}};\nif \"a\" in m { set m[\"b\"] = m; }");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "m = {\"a\": 1, 2: {}}".to_string(),
+                "if \"a\" in m:".to_string(),
+                "    m[\"b\"] = m".to_string(),
+            ])
+        );
+
+        let error = |contents: &str| -> (String, i64) {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+            (error.message, error.char_num)
+        };
+        assert_eq!(error("set m = {1: 2;"), ("unmatched '{'".to_string(), 9));
+        assert_eq!(
+            error("set m = {1 2};"),
+            ("expected ':' after the key".to_string(), 12)
+        );
+    }
+
+    #[test]
+    fn format_string_test() {
+        let mut lexer: Lexer =
+            new_lexer("set x = 1;\nprint \"x is {x}, {{x}} is {x * 2 + len([1])}\";");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "x = 1".to_string(),
+                "print(f\"x is {x}, {{x}} is {x * 2 + len([1])}\")".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("print \"{a} {b}\";");
+        lexer.lexer().unwrap();
+        let error: ParseError = check_undefined(&lexer.tokens).unwrap_err();
+        assert_eq!(
+            (error.message, error.char_num),
+            ("variable 'a' is not defined".to_string(), 9)
+        );
+
+        let mut lexer: Lexer = new_lexer("print \"ab {1 2}\";");
+        lexer.lexer().unwrap();
+        let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+        assert_eq!(
+            (error.message, error.char_num),
+            ("unexpected '2' in the braces of a string".to_string(), 14)
+        );
+    }
+
+    #[test]
+    fn for_test() {
+        let mut lexer: Lexer =
+            new_lexer("for i in 0..n + 1 {\n  for c in \"ab\" { print c * i; }\n}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "for i in range(0, n + 1):".to_string(),
+                "    for c in \"ab\":".to_string(),
+                "        print(c * i)".to_string(),
+            ])
+        );
+
+        let error = |contents: &str| -> (String, i64) {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+            (error.message, error.char_num)
+        };
+        assert_eq!(
+            error("for 1 in xs {}").0,
+            "expected a variable name after 'for'"
+        );
+        assert_eq!(
+            error("for x xs {}").0,
+            "expected 'in' after the variable name"
+        );
+    }
+
+    #[test]
+    fn use_test() {
+        let mut lexer: Lexer = new_lexer(
+            "use shapes;
+print shapes.area(2);",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            stmts[0],
+            Stmt::Use {
+                name: "shapes".to_string(),
+                position: Position {
+                    line_num: 1,
+                    char_num: 5
+                },
+            }
+        );
+
+        let error = |contents: &str| -> (&'static str, String) {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+            (error.code, error.message)
+        };
+        assert_eq!(
+            error("if a { use shapes; }"),
+            (
+                "E0107",
+                "'use' inside of a block, it only works outside of them".to_string()
+            )
+        );
+        assert_eq!(
+            error("use a.b;").1,
+            "a module name can't have a '.' in it like 'a.b'"
+        );
+    }
+
+    #[test]
+    fn exit_test() {
+        let mut lexer: Lexer = new_lexer("exit;\nif a { exit a + 1; }");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            stmts[0],
+            Stmt::Exit {
+                code: None,
+                position: at(1, 1),
+            }
+        );
+        assert_eq!(
+            crate::dump::ast_sexpr(&stmts),
+            "(exit)\n(if a\n  (then\n    (exit (+ a 1))))\n"
+        );
+    }
+
+    #[test]
+    fn assert_test() {
+        let mut lexer: Lexer = new_lexer(
+            "assert a > 1;
+assert_eq f(a), [1, 2];",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            crate::dump::ast_sexpr(&stmts),
+            "(assert (> a 1))\n(assert_eq (call f a) (list 1 2))\n"
+        );
+
+        let mut lexer: Lexer = new_lexer("assert_eq a b;");
+        lexer.lexer().unwrap();
+        assert!(new_parser(lexer.tokens).parse().is_err());
+    }
+
+    #[test]
+    fn ternary_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = b > 1 or c ? 1 : d ? 2 : 3;
+print (a ? b : c) + 1, {a ? 1 : 2: 3};",
+        );
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+        assert_eq!(
+            crate::dump::ast_sexpr(&stmts),
+            "(set a (? (or (> b 1) c) 1 (? d 2 3)))\n\
+             (print (+ (? a b c) 1) (dict ((? a 1 2) 3)))\n"
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = b ? 1;");
+        lexer.lexer().unwrap();
+        let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected ':' after the value for when it's true"
+        );
+    }
+
+    #[test]
+    fn exec_test() {
+        let mut lexer: Lexer = new_lexer("a + 1;\na * b;\n-a;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a + 1".to_string(),
+                "a * b".to_string(),
+                "-a".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn continuation_test() {
+        let mut lexer: Lexer = new_lexer("set a = 1 +\n  2;\nprint a *\n  a;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec!["a = 1 + 2".to_string(), "print(a * a)".to_string()])
+        );
+    }
+
+    #[test]
+    fn let_test() {
+        assert_eq!(tokenize("let"), Tokens::Set);
+        let mut lexer: Lexer = new_lexer("let a = 1;\nprint a;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let let_stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        let mut lexer: Lexer = new_lexer("set a = 1;\nprint a;");
+        lexer.lexer().unwrap();
+        let set_stmts: Vec<Stmt> = new_parser(lexer.tokens).parse().unwrap();
+
+        assert_eq!(let_stmts, set_stmts);
+        assert_eq!(
+            python_codegen(&let_stmts, PrintStyle::Function),
+            "a = 1\nprint(a)\n"
+        );
+    }
+
+    #[test]
+    fn set_chain_test() {
+        let mut lexer: Lexer = new_lexer("set a = b = 0;\nset c, d = 1, a + b;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse(),
+            Ok(vec![
+                Stmt::SetChain {
+                    names: vec!["a".to_string(), "b".to_string()],
+                    value: Expr::Numeric("0".to_string()),
+                },
+                Stmt::SetMany {
+                    names: vec!["c".to_string(), "d".to_string()],
+                    values: vec![
+                        Expr::Numeric("1".to_string()),
+                        Expr::Binary {
+                            left: Box::new(Expr::Identifier("a".to_string(), at(2, 15))),
+                            operator: Tokens::Plus,
+                            right: Box::new(Expr::Identifier("b".to_string(), at(2, 19))),
+                            position: at(2, 17),
+                        },
+                    ],
+                },
+            ])
+        );
+        parser.index = 0;
+        assert_eq!(
+            parser.python(),
+            Ok(vec!["a = b = 0".to_string(), "c, d = 1, a + b".to_string()])
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = = 0;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "expected a name or a value between '=' and '='"
+        );
+
+        let mut lexer: Lexer = new_lexer("set a, b = 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "expected 2 values for the names in the set, found 1"
+        );
+
+        let mut lexer: Lexer = new_lexer("set a = b = a;");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            check_undefined(&lexer.tokens).unwrap_err().message,
+            "variable 'a' used in its own initializer"
+        );
+    }
+
+    #[test]
+    fn comparison_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = 1 + 2 == 3;\nset b = a != (1 <= 2);\nset c = 1 >= 2 == 0;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 1 + 2 == 3".to_string(),
+                "b = a != (1 <= 2)".to_string(),
+                "c = (1 >= 2) == 0".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn logical_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = not b == c or d and e;\nset a = (b or c) and not (d < e);\nset a = (not b) + 1;",
+        );
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[3].token, Tokens::Not);
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = not (b == c) or d and e".to_string(),
+                "a = (b or c) and not (d < e)".to_string(),
+                "a = (not b) + 1".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn bool_test() {
+        let mut lexer: Lexer = new_lexer("set a = true;\nset b = [false, a == true];");
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[3].token, Tokens::Bool);
+        assert_eq!(lexer.tokens[3].part, "true");
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = True".to_string(),
+                "b = [False, a == True]".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn modulo_power_test() {
+        let mut lexer: Lexer = new_lexer(
+            "set a = 7 % 3;\nset a = 2 ** 8;\nset a = 2 * 3 ** 2;\nset a = 2 ** 3 ** 2;\nset a = (2 ** 3) ** 2;\nset a = -2 ** 2;\nset a = (-2) ** 2;\nset a = 2 ** -1;",
+        );
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[4].token, Tokens::Modulo);
+        assert_eq!(lexer.tokens[11].token, Tokens::Power);
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 7 % 3".to_string(),
+                "a = 2 ** 8".to_string(),
+                "a = 2 * 3 ** 2".to_string(),
+                "a = 2 ** 3 ** 2".to_string(),
+                "a = (2 ** 3) ** 2".to_string(),
+                "a = -2 ** 2".to_string(),
+                "a = (-2) ** 2".to_string(),
+                "a = 2 ** -1".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn input_test() {
+        let mut lexer: Lexer = new_lexer("set x = input;\nset y = x + input;");
+        lexer.lexer().unwrap();
+        assert_eq!(lexer.tokens[3].token, Tokens::Input);
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "x = input()".to_string(),
+                "y = x + input()".to_string()
+            ])
+        );
+
+        // Or with parentheses, like python
+        let mut lexer: Lexer = new_lexer("set x = input(\"x? \") + input();");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec!["x = input(\"x? \") + input()".to_string()])
+        );
+
+        // `input x;` reads a line into x, as a number if it's one
+        let mut lexer: Lexer = new_lexer("input x;\nprint x * 2;");
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let python: Vec<String> = new_parser(lexer.tokens).python().unwrap();
+        assert_eq!(
+            python[python.len() - 2..],
+            ["x = parse(input())".to_string(), "print(x * 2)".to_string()]
+        );
+    }
+
+    #[test]
+    fn missing_semi_test() {
+        let mut lexer: Lexer = new_lexer("if a { print b }");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse(),
+            Err(ParseError {
+                code: "E0101",
+                message: "missing ';' before the '}' that ends the block".to_string(),
+                line_num: 1,
+                char_num: 15,
+            })
+        );
+
+        let mut lexer: Lexer = new_lexer("while a {\n  a -= 1\n  print a;\n}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "missing ';' before the next statement 'print'"
+        );
+
+        let mut lexer: Lexer = new_lexer("print a set b = 1;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "missing ';' before the next statement 'set'"
+        );
+    }
+
+    #[test]
+    fn optional_last_semi_test() {
+        for contents in ["set a = 1;\nprint a", "set a = 1;\nprint a;\n"] {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+            let mut parser: Parser = new_parser(lexer.tokens);
+            assert_eq!(
+                parser.python(),
+                Ok(vec!["a = 1".to_string(), "print(a)".to_string()])
+            );
+        }
+
+        // The last statement still gets checked without its semicolon
+        let mut lexer: Lexer = new_lexer("set a = 1;\nprint b");
+        lexer.lexer().unwrap();
+        assert_eq!(
+            check_undefined(&lexer.tokens).unwrap_err().message,
+            "variable 'b' is not defined"
+        );
+    }
+
+    #[test]
+    fn if_else_test() {
+        let mut lexer: Lexer =
+            new_lexer("if a < 1 {\n  print 1;\n} else if a < 2 {\n  print 2;\n} else {}\nprint 3;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "if a < 1:".to_string(),
+                "    print(1)".to_string(),
+                "else:".to_string(),
+                "    if a < 2:".to_string(),
+                "        print(2)".to_string(),
+                "    else:".to_string(),
+                "        pass".to_string(),
+                "print(3)".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("if a {} else print a;");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "expected '{' to start the block"
+        );
+    }
+
+    #[test]
+    fn func_test() {
+        let mut lexer: Lexer = new_lexer(
+            "func add(a, b) {\n  return a + b;\n}\nfunc nothing() { return; }\nprint add(1, 2);",
+        );
+        lexer.lexer().unwrap();
+        assert_eq!(check_undefined(&lexer.tokens), Ok(()));
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "def add(a, b):".to_string(),
+                "    return a + b".to_string(),
+                "def nothing():".to_string(),
+                "    return".to_string(),
+                "print(add(1, 2))".to_string(),
+            ])
+        );
+
+        let error = |contents: &str| -> String {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            new_parser(lexer.tokens).parse().unwrap_err().message
+        };
+        assert_eq!(error("return 1;"), "'return' outside of a function");
+        assert_eq!(error("func f(a, a) {}"), "duplicate parameter 'a'");
+        assert_eq!(
+            error("func f(a b) {}"),
+            "expected ',' or ')' after a parameter"
+        );
+    }
+
+    #[test]
+    fn while_test() {
+        let mut lexer: Lexer =
+            new_lexer("set a = 0;\nwhile a < 10 {\n  a += 1;\n  while a > 5 + 1 { a -= 1; }\n}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "a = 0".to_string(),
+                "while a < 10:".to_string(),
+                "    a += 1".to_string(),
+                "    while a > 5 + 1:".to_string(),
+                "        a -= 1".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("while (a < b) < c {}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "while (a < b) < c:".to_string(),
+                "    pass".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn match_test() {
+        let mut lexer: Lexer = new_lexer(
+            "match a + 1 {\n  1 => print \"one\";\n  -2 => { set a = 0; },\n  _ => {}\n}",
+        );
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "match_1_1 = a + 1".to_string(),
+                "if match_1_1 == 1:".to_string(),
+                "    print(\"one\")".to_string(),
+                "elif match_1_1 == -2:".to_string(),
+                "    a = 0".to_string(),
+                "else:".to_string(),
+                "    pass".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("match a { b => print 1; }");
+        lexer.lexer().unwrap();
+        let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+        assert_eq!(
+            (error.code, error.message.as_str()),
+            (
+                "E0108",
+                "a match arm can only match a number, a string, a bool or '_'"
+            )
+        );
+    }
+
+    #[test]
+    fn try_test() {
+        let mut lexer: Lexer =
+            new_lexer("try {\n  print 1 / a;\n} catch err { print err; }\ntry {} catch e {}");
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(lexer.tokens);
+        assert_eq!(
+            parser.python(),
+            Ok(vec![
+                "try:".to_string(),
+                "    print(1 / a)".to_string(),
+                "except Exception as err:".to_string(),
+                "    err = str(err)".to_string(),
+                "    print(err)".to_string(),
+                "try:".to_string(),
+                "    pass".to_string(),
+                "except Exception as e:".to_string(),
+                "    e = str(e)".to_string(),
+                "    pass".to_string(),
+            ])
+        );
+
+        let mut lexer: Lexer = new_lexer("try { print 1; } print 2;");
+        lexer.lexer().unwrap();
+        let error: ParseError = new_parser(lexer.tokens).parse().unwrap_err();
+        assert_eq!(error.message, "expected 'catch' after the try block");
+    }
+}
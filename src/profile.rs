@@ -0,0 +1,129 @@
+//! Counts how many times each line runs and how long it takes, for
+//! `mahou run --profile`. It's a hook on the interpreter like the debugger
+
+use crate::ast::{Position, Stmt};
+use crate::debugger::{new_line_table, LineTable};
+use crate::interpreter::{Hook, Inspect, RuntimeError};
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// How one line of the program did
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct LineProfile {
+    pub line: i64,
+    pub runs: u64,
+    /// All of the time spent on it, including the statements inside of it
+    pub time: Duration,
+}
+
+/// The lines so far, kept outside of the profiler so they can be read after the
+/// interpreter has it
+pub type Profile = Rc<RefCell<HashMap<i64, LineProfile>>>;
+
+pub struct Profiler {
+    lines: LineTable,
+    /// When each statement that's running started
+    started: Vec<Instant>,
+    pub profile: Profile,
+}
+
+/// Make a profiler for the statements, starts is where each one starts from the parser
+pub fn new_profiler(contents: &str, stmts: &[Stmt], starts: &[Position]) -> Profiler {
+    Profiler {
+        lines: new_line_table(contents, stmts, starts),
+        started: Vec::new(),
+        profile: Rc::new(RefCell::new(HashMap::new())),
+    }
+}
+
+impl Hook for Profiler {
+    fn before(&mut self, interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError> {
+        self.lines.visit(interpreter, stmt);
+        self.started.push(Instant::now());
+        Ok(())
+    }
+    fn after(&mut self, _interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let time: Duration = match self.started.pop() {
+            Some(started) => started.elapsed(),
+            None => return Ok(()),
+        };
+        if let Some(line) = self.lines.line(stmt) {
+            let mut profile: RefMut<HashMap<i64, LineProfile>> = self.profile.borrow_mut();
+            let entry: &mut LineProfile = profile.entry(line).or_insert(LineProfile {
+                line,
+                runs: 0,
+                time: Duration::ZERO,
+            });
+            entry.runs += 1;
+            entry.time += time;
+        }
+        Ok(())
+    }
+}
+
+/// The lines that took the longest first, with how many times they ran and the line
+pub fn report(profile: &HashMap<i64, LineProfile>, contents: &str) -> String {
+    let source: Vec<&str> = contents.lines().collect();
+    let mut lines: Vec<&LineProfile> = profile.values().collect();
+    lines.sort_by(|a, b| b.time.cmp(&a.time).then(a.line.cmp(&b.line)));
+
+    let mut report: String = format!("{:>6} {:>8} {:>12}  source\n", "line", "runs", "time");
+    for entry in lines {
+        let text: &str = source.get(entry.line as usize - 1).map_or("", |x| x.trim());
+        report.push_str(&format!(
+            "{:>6} {:>8} {:>10.3}ms  {}\n",
+            entry.line,
+            entry.runs,
+            entry.time.as_secs_f64() * 1000.0,
+            text
+        ));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{new_interpreter, Interpret, Interpreter};
+    use crate::modules::parse_file_with_starts;
+
+    #[test]
+    fn profile_test() {
+        let contents: &str = "func f(x) {\n    return x;\n}\nfor i in 0..3 {\n    f(i);\n}";
+        let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+            parse_file_with_starts(contents, None, false).unwrap();
+        let profiler: Profiler = new_profiler(contents, &stmts, &starts);
+        let profile: Profile = profiler.profile.clone();
+        let mut interpreter: Interpreter<&[u8], Vec<u8>> = new_interpreter(&b""[..], Vec::new());
+        interpreter.hook = Some(Box::new(profiler));
+        interpreter.run(&stmts).unwrap();
+
+        let mut runs: Vec<(i64, u64)> = profile
+            .borrow()
+            .values()
+            .map(|x| (x.line, x.runs))
+            .collect();
+        runs.sort();
+        assert_eq!(runs, vec![(1, 1), (2, 3), (4, 1), (5, 3)]);
+
+        let mut profile: HashMap<i64, LineProfile> = HashMap::new();
+        for (line, millis) in [(1, 2), (2, 5)] {
+            profile.insert(
+                line,
+                LineProfile {
+                    line,
+                    runs: 1,
+                    time: Duration::from_millis(millis),
+                },
+            );
+        }
+        assert_eq!(
+            report(&profile, "set a = 1;\nprint a;"),
+            "  line     runs         time  source\n     \
+             2        1      5.000ms  print a;\n     \
+             1        1      2.000ms  set a = 1;\n"
+        );
+    }
+}
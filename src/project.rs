@@ -0,0 +1,424 @@
+//! Projects with a mahou.toml, so a program of more than one file can be built
+//! with just `mahou build`. The manifest says which file to start from, what to
+//! compile it to and how much to optimize, and `mahou new` makes one to start with.
+//! Only the little bit of toml a manifest needs is read, tables of strings and numbers
+
+use crate::ast::Stmt;
+use crate::bytecode::{compile_program, encode_program};
+use crate::codegen::{codegen, Emit, PrintStyle};
+use crate::diagnostics::{new_lints, LintLevel, LintReport, LINTS};
+use crate::keywords::{add_alias, set_aliases};
+use crate::lexer::{new_lexer, Lex, Lexer};
+use crate::modules::{load_modules, parse_file, EXTENSION};
+use crate::optimize::optimize;
+use crate::semantic::check_semantics;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What the manifest at the top of a project is called
+pub const MANIFEST: &str = "mahou.toml";
+
+/// Where `mahou build` puts what it makes, in the project's folder
+pub const BUILD: &str = "build";
+
+/// What a project is built from and into
+#[derive(Debug, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    /// The file the program starts in, from the project's folder
+    pub entry: PathBuf,
+    /// bytecode, python, js, c or wasm
+    pub target: Emit,
+    /// 0 builds it as it is and 1 runs the optimizer on it first
+    pub opt_level: u8,
+    /// Other words for keywords from the `[keywords]` table, like `imprimir = "print"`
+    pub keywords: BTreeMap<String, String>,
+    /// What to do about each warning from the `[lints]` table, like `unused_variable = "deny"`
+    pub lints: BTreeMap<String, LintLevel>,
+}
+
+/// A manifest with everything but the name left to the defaults
+pub fn new_manifest(name: &str) -> Manifest {
+    Manifest {
+        name: name.to_string(),
+        entry: PathBuf::from("src").join(format!("main.{}", EXTENSION)),
+        target: Emit::Bytecode,
+        opt_level: 0,
+        keywords: BTreeMap::new(),
+        lints: BTreeMap::new(),
+    }
+}
+
+/// What the file for a target ends with, the targets that don't make a program
+/// can't be built
+fn extension(target: Emit) -> Option<&'static str> {
+    match target {
+        Emit::Bytecode => Some("mhc"),
+        Emit::Python => Some("py"),
+        Emit::Js => Some("js"),
+        Emit::C => Some("c"),
+        Emit::Wasm => Some("wat"),
+        _ => None,
+    }
+}
+
+/// A string in quotes or a number, and whatever comment comes after it
+fn value(text: &str) -> Option<String> {
+    let text: &str = text.trim();
+    let (value, rest): (&str, &str) = match text.strip_prefix('"') {
+        Some(quoted) => {
+            let end: usize = quoted.find('"')?;
+            (&quoted[..end], &quoted[end + 1..])
+        }
+        None => match text.find('#') {
+            Some(end) => (text[..end].trim(), &text[end..]),
+            None => (text, ""),
+        },
+    };
+    let rest: &str = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+/// One `key = value` from a toml file, with the table it's in and its line
+pub struct Entry {
+    pub table: String,
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// The keys and values of a toml file in order. Errors start with the file and
+/// the line, and so do the ones made with at
+pub fn read_toml(contents: &str, filename: &str) -> Result<Vec<Entry>, String> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut table: String = String::new();
+    for (index, line) in contents.lines().enumerate() {
+        let error = |message: String| -> String { at(filename, index + 1, &message) };
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            match name.strip_suffix(']') {
+                Some(name) => table = name.trim().to_string(),
+                None => return Err(error(format!("expected ] after {}", line))),
+            }
+            continue;
+        }
+        let (key, text): (&str, &str) = match line.split_once('=') {
+            Some((key, text)) => (key.trim(), text),
+            None => return Err(error(format!("expected key = value, got {}", line))),
+        };
+        match value(text) {
+            Some(value) => entries.push(Entry {
+                table: table.clone(),
+                key: key.to_string(),
+                value,
+                line: index + 1,
+            }),
+            None => return Err(error(format!("can't read the value of {}", key))),
+        }
+    }
+    Ok(entries)
+}
+
+/// An error on a line of a toml file
+pub fn at(filename: &str, line: usize, message: &str) -> String {
+    format!("error: {} line {}: {}\n", filename, line, message)
+}
+
+/// Read a manifest, the errors say which line of it is wrong
+pub fn parse_manifest(contents: &str) -> Result<Manifest, String> {
+    let mut manifest: Manifest = new_manifest("");
+    for entry in read_toml(contents, MANIFEST)? {
+        let line: usize = entry.line;
+        let error = |message: String| -> String { at(MANIFEST, line, &message) };
+        let value: String = entry.value;
+        match (entry.table.as_str(), entry.key.as_str()) {
+            ("project", "name") => manifest.name = value,
+            ("project", "entry") => manifest.entry = PathBuf::from(value),
+            ("project", "target") => {
+                manifest.target = value.parse().map_err(error)?;
+                if extension(manifest.target).is_none() {
+                    return Err(error(format!(
+                        "can't build to {}, expected bytecode, python, js, c or wasm",
+                        value
+                    )));
+                }
+            }
+            ("project", "opt-level") => {
+                manifest.opt_level = match value.as_str() {
+                    "0" => 0,
+                    "1" => 1,
+                    _ => return Err(error(format!("opt-level is 0 or 1, not {}", value))),
+                }
+            }
+            ("project", key) => {
+                return Err(error(format!(
+                    "unknown key {}, expected name, entry, target or opt-level",
+                    key
+                )))
+            }
+            ("keywords", alias) => {
+                add_alias(&mut manifest.keywords, alias, &value).map_err(error)?;
+            }
+            ("lints", name) => {
+                if !LINTS.iter().any(|(_, x)| *x == name) {
+                    let names: Vec<&str> = LINTS.iter().map(|(_, x)| *x).collect();
+                    return Err(error(format!(
+                        "unknown lint {}, expected {}",
+                        name,
+                        names.join(", ")
+                    )));
+                }
+                manifest
+                    .lints
+                    .insert(name.to_string(), value.parse().map_err(error)?);
+            }
+            ("", _) => return Err(error("keys go in the [project] table".to_string())),
+            (table, _) => {
+                return Err(error(format!(
+                    "unknown table [{}], expected [project], [keywords] or [lints]",
+                    table
+                )))
+            }
+        }
+    }
+    if manifest.name.is_empty() {
+        return Err(format!("error: {} needs a name\n", MANIFEST));
+    }
+    Ok(manifest)
+}
+
+/// The manifest as toml, how `mahou new` writes it
+pub fn manifest_toml(manifest: &Manifest) -> String {
+    format!(
+        "[project]\nname = \"{}\"\nentry = \"{}\"\n# bytecode, python, js, c or wasm\ntarget = \"{}\"\n# 1 runs the optimizer first\nopt-level = {}\n",
+        manifest.name,
+        manifest.entry.display(),
+        manifest.target.name(),
+        manifest.opt_level
+    )
+}
+
+/// The closest folder with a manifest in it, from this one up
+pub fn find_project(folder: &Path) -> Option<PathBuf> {
+    folder
+        .ancestors()
+        .find(|x| x.join(MANIFEST).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Make a folder for a new project with a manifest and a program that says hi
+pub fn new_project(folder: &Path) -> Result<(), String> {
+    let name: String = folder
+        .file_name()
+        .map(|x| x.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|x| x.is_alphanumeric() || x == '_' || x == '-')
+    {
+        return Err(format!(
+            "{} can't be a project name, use letters, numbers, _ and -",
+            folder.display()
+        ));
+    }
+    if folder.exists() {
+        return Err(format!("{} already exists", folder.display()));
+    }
+
+    let manifest: Manifest = new_manifest(&name);
+    let entry: PathBuf = folder.join(&manifest.entry);
+    let write = |path: &Path, contents: &str| -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("couldn't make {}: {}", parent.display(), error))?;
+        }
+        fs::write(path, contents)
+            .map_err(|error| format!("couldn't write {}: {}", path.display(), error))
+    };
+    write(&folder.join(MANIFEST), &manifest_toml(&manifest))?;
+    write(&entry, &format!("print \"hello from {}\";\n", name))?;
+    write(&folder.join(".gitignore"), &format!("/{}\n", BUILD))
+}
+
+/// What building a project made
+#[derive(Debug, PartialEq)]
+pub struct Built {
+    /// Where the output goes and what it is
+    pub path: PathBuf,
+    pub bytes: Vec<u8>,
+    /// The entry's warnings that the `[lints]` table leaves as warnings, rendered
+    pub warnings: String,
+}
+
+/// Build the project in the folder by its manifest. The entry's warnings go
+/// through the `[lints]` table like they do for a single file, and a denied one
+/// stops the build. Errors are already rendered for showing
+pub fn build_project(folder: &Path) -> Result<Built, String> {
+    let manifest_path: PathBuf = folder.join(MANIFEST);
+    let contents: String = fs::read_to_string(&manifest_path).map_err(|error| {
+        format!(
+            "error: couldn't read {}: {}\n",
+            manifest_path.display(),
+            error
+        )
+    })?;
+    let manifest: Manifest = parse_manifest(&contents)?;
+    set_aliases(manifest.keywords.clone());
+
+    let entry: PathBuf = folder.join(&manifest.entry);
+    let filename: String = entry.display().to_string();
+    let source: String = fs::read_to_string(&entry)
+        .map_err(|error| format!("error: couldn't read {}: {}\n", filename, error))?;
+    let stmts: Vec<Stmt> = parse_file(&source, Some(&filename), false)?;
+    // It already lexed once, the tokens are only for where the warnings are
+    let mut lexer: Lexer = new_lexer(&source);
+    let _ = lexer.lexer();
+    let report: LintReport = new_lints(&source, manifest.lints.clone()).report(
+        &check_semantics(&stmts, &lexer.tokens),
+        &source,
+        Some(&filename),
+        false,
+        true,
+    );
+    if report.denied > 0 {
+        return Err(report.rendered);
+    }
+    let mut stmts: Vec<Stmt> = load_modules(stmts, &source, Some(&filename), false)?;
+    if manifest.opt_level > 0 {
+        optimize(&mut stmts);
+    }
+
+    let output: Vec<u8> = match manifest.target.backend(PrintStyle::Function, &stmts) {
+        Some(backend) => codegen(&stmts, backend.as_ref()).into_bytes(),
+        None => {
+            let program = compile_program(&stmts)
+                .map_err(|error| error.render(&source, Some(&filename), false))?;
+            encode_program(&program)
+        }
+    };
+    let path: PathBuf = folder.join(BUILD).join(format!(
+        "{}.{}",
+        manifest.name,
+        extension(manifest.target).unwrap_or("mhc")
+    ));
+    Ok(Built {
+        path,
+        bytes: output,
+        warnings: report.rendered,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_test() {
+        let manifest: Manifest = new_manifest("hello");
+        assert_eq!(parse_manifest(&manifest_toml(&manifest)), Ok(manifest));
+        assert_eq!(
+            parse_manifest(
+                "[project]\nname = \"app\" # the name\ntarget = \"python\"\nopt-level = 1\n"
+            ),
+            Ok(Manifest {
+                target: Emit::Python,
+                opt_level: 1,
+                ..new_manifest("app")
+            })
+        );
+        assert_eq!(
+            parse_manifest("[project]\nname = \"app\"\ntarget = \"ast\"\n"),
+            Err("error: mahou.toml line 3: can't build to ast, expected bytecode, python, js, c or wasm\n".to_string())
+        );
+        assert_eq!(
+            parse_manifest("[project]\nname = \"app\"\nopt-level = 2\n"),
+            Err("error: mahou.toml line 3: opt-level is 0 or 1, not 2\n".to_string())
+        );
+        assert_eq!(
+            parse_manifest("[project]\n"),
+            Err("error: mahou.toml needs a name\n".to_string())
+        );
+    }
+
+    #[test]
+    fn lints_test() {
+        let manifest: Manifest =
+            parse_manifest("[project]\nname = \"app\"\n[lints]\nunused_variable = \"deny\"\n")
+                .unwrap();
+        assert_eq!(
+            manifest
+                .lints
+                .into_iter()
+                .collect::<Vec<(String, LintLevel)>>(),
+            vec![("unused_variable".to_string(), LintLevel::Deny)]
+        );
+        assert_eq!(
+            parse_manifest("[project]\nname = \"app\"\n[lints]\nunused_variable = \"off\"\n"),
+            Err("error: mahou.toml line 4: a lint is allow, warn or deny, not off\n".to_string())
+        );
+        assert!(
+            parse_manifest("[project]\nname = \"app\"\n[lints]\nunused = \"allow\"\n")
+                .unwrap_err()
+                .contains("unknown lint unused, expected long_identifier")
+        );
+    }
+
+    #[test]
+    fn build_project_test() {
+        let folder: PathBuf = std::env::temp_dir().join("mahou_build_project_test");
+        let _ = fs::remove_dir_all(&folder);
+        assert_eq!(new_project(&folder), Ok(()));
+        assert!(new_project(&folder)
+            .unwrap_err()
+            .ends_with("already exists"));
+        assert_eq!(find_project(&folder.join("src")), Some(folder.clone()));
+
+        let built: Built = build_project(&folder).unwrap();
+        assert_eq!(
+            built.path,
+            folder.join("build").join("mahou_build_project_test.mhc")
+        );
+        assert!(built.bytes.starts_with(crate::bytecode::MAGIC));
+        assert_eq!(built.warnings, "");
+
+        // Modules are found next to the entry
+        fs::write(folder.join("src/greet.m"), "set who = \"you\";\n").unwrap();
+        fs::write(folder.join("src/main.m"), "use greet;\nprint greet.who;\n").unwrap();
+        let manifest: String = fs::read_to_string(folder.join(MANIFEST)).unwrap();
+        fs::write(
+            folder.join(MANIFEST),
+            manifest.replace("\"bytecode\"", "\"python\""),
+        )
+        .unwrap();
+        let built: Built = build_project(&folder).unwrap();
+        assert_eq!(built.path.extension().unwrap(), "py");
+        assert!(String::from_utf8(built.bytes)
+            .unwrap()
+            .contains("greet__who = \"you\""));
+
+        // The lints table goes for a build too, a warning doesn't stop it but deny does
+        fs::write(folder.join("src/main.m"), "set unused = 1;\nprint 2;\n").unwrap();
+        let built: Built = build_project(&folder).unwrap();
+        assert!(built.warnings.starts_with("warning[W0003]"));
+        let manifest: String = fs::read_to_string(folder.join(MANIFEST)).unwrap();
+        fs::write(
+            folder.join(MANIFEST),
+            format!("{}\n[lints]\nunused_variable = \"deny\"\n", manifest),
+        )
+        .unwrap();
+        let error: String = build_project(&folder).unwrap_err();
+        assert!(error.starts_with("error[W0003]"));
+        assert!(error.contains("main.m:1"));
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
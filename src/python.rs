@@ -0,0 +1,156 @@
+//! Runs the python a program compiles to with python3, for `mahou --run-python`.
+//! The python gets a comment at the end saying which mahou line each of its lines
+//! came from, and its source map is used so a traceback can point at the program
+//! instead of at the python
+
+use crate::ast::Stmt;
+use crate::codegen::{PrintStyle, PythonBackend};
+use crate::debugger::LineTable;
+use crate::sourcemap::{mapped_codegen, SourceMap};
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+/// How the comment with the lines starts
+pub const MAP_COMMENT: &str = "# mahou source map:";
+
+/// The comment for the end of the python, like `# mahou source map: 1=1 3=2`
+/// for python line 1 from mahou line 1 and python line 3 from mahou line 2
+pub fn source_map_comment(map: &SourceMap) -> String {
+    let lines: Vec<String> = map
+        .statements
+        .iter()
+        .map(|(python, source)| format!("{}={}", python, source))
+        .collect();
+    format!("{} {}\n", MAP_COMMENT, lines.join(" "))
+}
+
+/// Read the comment back out of the python, no comment is an empty map
+pub fn read_source_map(python: &str) -> Vec<(usize, i64)> {
+    let comment: &str = match python
+        .lines()
+        .rev()
+        .find_map(|x| x.strip_prefix(MAP_COMMENT))
+    {
+        Some(comment) => comment,
+        None => return Vec::new(),
+    };
+    comment
+        .split_whitespace()
+        .filter_map(|x| {
+            let (python, source) = x.split_once('=')?;
+            Some((python.parse().ok()?, source.parse().ok()?))
+        })
+        .collect()
+}
+
+/// The python for the statements with the comment at the end, and its map
+pub fn mapped_python(stmts: &[Stmt], table: &LineTable, filename: &str) -> (String, SourceMap) {
+    let backend: PythonBackend = PythonBackend::new(PrintStyle::Function, stmts);
+    let (mut python, map): (String, SourceMap) =
+        mapped_codegen(stmts, &backend, table, "<string>", filename);
+    python.push_str(&source_map_comment(&map));
+    (python, map)
+}
+
+/// A line of python's error output with the place in the python changed to the
+/// place in the program, and the program's line put after it like python would
+pub fn map_traceback(line: &str, map: &SourceMap, source: &str) -> String {
+    let rest: &str = match line.trim_start().strip_prefix("File \"<string>\", line ") {
+        Some(rest) => rest,
+        None => return line.to_string(),
+    };
+    let digits: usize = rest
+        .find(|x: char| !x.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let mapped: Option<i64> = rest[..digits].parse().ok().and_then(|x| map.line(x));
+    match mapped {
+        Some(mapped) => {
+            let indent: &str = &line[..line.len() - line.trim_start().len()];
+            let mut text: String = format!(
+                "{}File \"{}\", line {}{}",
+                indent,
+                map.source,
+                mapped,
+                &rest[digits..]
+            );
+            if let Some(code) = source.lines().nth(mapped as usize - 1) {
+                text.push_str(&format!("\n{}  {}", indent, code.trim()));
+            }
+            text
+        }
+        None => line.to_string(),
+    }
+}
+
+/// Run the python with the arguments, with the program's input and output going
+/// straight to it and its errors mapped back to the program by the map. Gives back
+/// the code python exited with
+pub fn run_python(
+    python: &str,
+    map: &SourceMap,
+    source: &str,
+    args: &[String],
+) -> Result<i32, String> {
+    let mut child = Command::new("python3")
+        .arg("-c")
+        .arg(python)
+        .args(args)
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("couldn't run python3: {}", error))?;
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            eprintln!("{}", map_traceback(&line, map, source));
+        }
+    }
+    let status = child
+        .wait()
+        .map_err(|error| format!("couldn't run python3: {}", error))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::new_line_table;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse, Parser};
+
+    #[test]
+    fn source_map_test() {
+        let map: SourceMap = SourceMap {
+            file: "<string>".to_string(),
+            source: "t.m".to_string(),
+            statements: vec![(1, 1), (2, 3), (5, 4)],
+            lines: 5,
+        };
+        let python: String = format!("a = 1\n{}", source_map_comment(&map));
+        assert_eq!(read_source_map(&python), map.statements);
+
+        let source: &str = "set a = 1;\n\nset b = [a];\nprint b[2];\n";
+        assert_eq!(
+            map_traceback("  File \"<string>\", line 5, in <module>", &map, source),
+            "  File \"t.m\", line 4, in <module>\n    print b[2];"
+        );
+        assert_eq!(
+            map_traceback("IndexError: list index out of range", &map, source),
+            "IndexError: list index out of range"
+        );
+    }
+
+    #[test]
+    fn mapped_python_test() {
+        let source: &str =
+            "set total = 0;\nfunc add(n) {\n    global total;\n\n    total += n;\n}\nadd(2);\n";
+        let mut lexer: Lexer = new_lexer(source);
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(&lexer.tokens);
+        let stmts: Vec<Stmt> = parser.parse().unwrap();
+        let table: LineTable = new_line_table(source, &stmts, &parser.starts);
+        assert_eq!(
+            mapped_python(&stmts, &table, "t.m").0,
+            "total = 0\ndef add(n):\n    global total\n    total += n\nadd(2)\n\
+             # mahou source map: 1=1 2=2 3=3 4=5 5=7\n"
+        );
+    }
+}
@@ -0,0 +1,754 @@
+//! Walks the tree before any output is made, warning about variables read before
+//! they're set, variables that are set and never read, things defined twice and
+//! match arms that can't run
+
+use crate::ast::{walk_expr, walk_stmt, Expr, MatchArm, Position, Stmt, Visit};
+use crate::codegen::assigned_names;
+use crate::formatter::expr_source;
+use crate::lexer::{Token, Tokens};
+use crate::parser::{position, ParseError};
+use crate::types::set_targets;
+use std::collections::{HashMap, HashSet};
+
+/// Give back every warning for the program, in the order they are in the source.
+/// The tokens are for where the warnings go, since statements don't keep that
+pub fn check_semantics(stmts: &[Stmt], tokens: &[Token]) -> Vec<ParseError> {
+    let mut semantic: Semantic = Semantic {
+        tokens,
+        globals: assigned_names(stmts),
+        reads: HashSet::new(),
+        warned: HashSet::new(),
+        functions: 0,
+        warnings: Vec::new(),
+    };
+    let mut assigned: HashSet<String> = HashSet::new();
+    semantic.block(stmts, &mut assigned);
+
+    let mut sets: SetNames = SetNames {
+        names: Vec::new(),
+        lines: false,
+    };
+    for stmt in stmts {
+        sets.visit_stmt(stmt);
+    }
+    // Lines from custom statements could read anything
+    if !sets.lines {
+        for name in sets.names {
+            // Starting a name with _ says it's meant to go unused
+            if !semantic.reads.contains(&name) && !name.starts_with('_') {
+                let message: String = format!("variable '{}' is set but never read", name);
+                let token: Option<&Token> = tokens
+                    .iter()
+                    .find(|x| x.token == Tokens::Identifier && x.part == name);
+                semantic.warn("W0003", message, token);
+            }
+        }
+    }
+
+    semantic.warnings.sort_by_key(|x| (x.line_num, x.char_num));
+    semantic.warnings
+}
+
+/// The consts made outside of every block and function and where each one is,
+/// which stay consts for everything after, like the next `eval` of an engine
+pub type Consts = HashMap<String, Position>;
+
+/// Goes through the program in order keeping the consts of each block that's
+/// open, the same way the interpreter keeps its variables
+struct ConstCheck<'a> {
+    tokens: &'a [Token],
+    /// The tokens each name gets set at and how many of them have been gone
+    /// through, since statements don't keep where their names are
+    targets: HashMap<&'a str, (Vec<&'a Token>, usize)>,
+    globals: Consts,
+    /// The consts of each block in the function being gone through, or outside
+    /// of functions. A function only sees its own and the globals
+    blocks: Vec<Consts>,
+    /// How many functions have been gone through, to find the tokens of the next one
+    functions: usize,
+    error: Option<ParseError>,
+}
+
+impl ConstCheck<'_> {
+    /// The const that the name is where the program is, if it's one
+    fn find(&self, name: &str) -> Option<Position> {
+        self.blocks
+            .iter()
+            .rev()
+            .find_map(|x| x.get(name))
+            .or_else(|| self.globals.get(name))
+            .copied()
+    }
+    fn fail(&mut self, message: String, at: Position) {
+        if self.error.is_none() {
+            self.error = Some(ParseError {
+                code: "E0204",
+                message,
+                line_num: at.line_num,
+                char_num: at.char_num,
+            });
+        }
+    }
+    /// Check setting the name, giving back where it's set
+    fn set(&mut self, name: &str) -> Position {
+        let token: Option<&Token> = self.targets.get_mut(name).and_then(|(tokens, next)| {
+            *next += 1;
+            tokens.get(*next - 1).copied()
+        });
+        let at: Position = token.map_or(Position::default(), position);
+        if let Some(first) = self.find(name) {
+            let message: String = format!(
+                "can't set '{}' again, it's a const from line {}:{}",
+                name, first.line_num, first.char_num
+            );
+            self.fail(message, at);
+        }
+        at
+    }
+    fn block(&mut self, stmts: &[Stmt]) {
+        self.blocks.push(Consts::new());
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+        self.blocks.pop();
+    }
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Set { name, .. } | Stmt::AugAssign { name, .. } => {
+                self.set(name);
+            }
+            Stmt::Const { name, .. } => {
+                let at: Position = self.set(name);
+                self.blocks
+                    .last_mut()
+                    .unwrap_or(&mut self.globals)
+                    .insert(name.to_owned(), at);
+            }
+            Stmt::SetChain { names, .. } | Stmt::SetMany { names, .. } => {
+                for name in names {
+                    self.set(name);
+                }
+            }
+            Stmt::If {
+                body, else_body, ..
+            } => {
+                self.block(body);
+                if let Some(else_body) = else_body {
+                    self.block(else_body);
+                }
+            }
+            Stmt::While { body, .. } => self.block(body),
+            Stmt::Match { arms, .. } => {
+                for arm in arms {
+                    self.block(&arm.body);
+                }
+            }
+            Stmt::Try { body, handler, .. } => {
+                self.block(body);
+                self.block(handler);
+            }
+            Stmt::For { name, body, .. } => {
+                self.set(name);
+                self.block(body);
+            }
+            Stmt::Func { params, body, .. } => {
+                let func: Option<usize> = self
+                    .tokens
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, x)| x.token == Tokens::Func)
+                    .nth(self.functions)
+                    .map(|(index, _)| index);
+                self.functions += 1;
+                let blocks: Vec<Consts> = std::mem::take(&mut self.blocks);
+                for param in params {
+                    if let Some(first) = self.find(param) {
+                        // The parameters are the names between the parens after it
+                        let at: Position = func
+                            .and_then(|x| {
+                                self.tokens[x..]
+                                    .iter()
+                                    .take_while(|x| x.token != Tokens::RightParen)
+                                    .find(|x| x.token == Tokens::Identifier && x.part == *param)
+                            })
+                            .map_or(Position::default(), position);
+                        let message: String = format!(
+                            "can't have a parameter '{}', it's a const from line {}:{}",
+                            param, first.line_num, first.char_num
+                        );
+                        self.fail(message, at);
+                    }
+                }
+                self.block(body);
+                self.blocks = blocks;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Give back an error for the first time a const gets set again, saying where the
+/// const is. A const lasts until the end of the block it's in, and one outside of
+/// every block is one everywhere after it, inside of functions too. The consts
+/// from before are the ones outside of blocks that came before the program, and
+/// the program's get added to them. The tokens are only for where the error goes
+pub fn check_constants(
+    stmts: &[Stmt],
+    tokens: &[Token],
+    consts: &mut Consts,
+) -> Result<(), ParseError> {
+    let mut targets: HashMap<&str, (Vec<&Token>, usize)> = HashMap::new();
+    for target in set_targets(tokens) {
+        targets.entry(&*target.part).or_default().0.push(target);
+    }
+    let mut check: ConstCheck = ConstCheck {
+        tokens,
+        targets,
+        globals: std::mem::take(consts),
+        blocks: Vec::new(),
+        functions: 0,
+        error: None,
+    };
+    for stmt in stmts {
+        check.stmt(stmt);
+    }
+    *consts = check.globals;
+    match check.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Finds the first break or continue that isn't inside of a loop. A function
+/// starts out outside of every loop, even when it's defined in one
+struct LoopCheck {
+    loops: usize,
+    error: Option<ParseError>,
+}
+
+impl Visit for LoopCheck {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::While { .. } | Stmt::For { .. } => {
+                self.loops += 1;
+                walk_stmt(self, stmt);
+                self.loops -= 1;
+            }
+            Stmt::Func { .. } => {
+                let loops: usize = std::mem::take(&mut self.loops);
+                walk_stmt(self, stmt);
+                self.loops = loops;
+            }
+            Stmt::Break { position } | Stmt::Continue { position }
+                if self.loops == 0 && self.error.is_none() =>
+            {
+                let keyword: &str = match stmt {
+                    Stmt::Break { .. } => "break",
+                    _ => "continue",
+                };
+                self.error = Some(ParseError {
+                    code: "E0205",
+                    message: format!("'{}' outside of a loop", keyword),
+                    line_num: position.line_num,
+                    char_num: position.char_num,
+                });
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+}
+
+/// Give back an error for the first break or continue that has no loop to leave
+pub fn check_loops(stmts: &[Stmt]) -> Result<(), ParseError> {
+    let mut check: LoopCheck = LoopCheck {
+        loops: 0,
+        error: None,
+    };
+    for stmt in stmts {
+        check.visit_stmt(stmt);
+    }
+    match check.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Keeps track of what's been set while going through the program in order
+pub struct Semantic<'a> {
+    tokens: &'a [Token],
+    /// The variables set outside of functions, which a function can read
+    /// whenever it's called
+    globals: Vec<String>,
+    /// Every variable that's read anywhere
+    reads: HashSet<String>,
+    /// The variables that already have a warning for being read before they're set
+    warned: HashSet<String>,
+    /// How many functions have been gone through, to find the tokens of the next one
+    functions: usize,
+    warnings: Vec<ParseError>,
+}
+
+impl Semantic<'_> {
+    fn warn(&mut self, code: &'static str, message: String, token: Option<&Token>) {
+        self.warnings.push(ParseError {
+            code,
+            message,
+            line_num: token.map_or(0, |x| x.line_num()),
+            char_num: token.map_or(0, |x| x.char_num()),
+        });
+    }
+    /// Go through a block, adding to assigned what is always set by the end of it
+    fn block(&mut self, stmts: &[Stmt], assigned: &mut HashSet<String>) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                    self.read(value, assigned);
+                    assigned.insert(name.to_owned());
+                }
+                Stmt::SetChain { names, value } => {
+                    self.read(value, assigned);
+                    assigned.extend(names.iter().cloned());
+                }
+                Stmt::SetMany { names, values } => {
+                    for value in values {
+                        self.read(value, assigned);
+                    }
+                    for (index, name) in names.iter().enumerate() {
+                        if names[..index].contains(name) {
+                            let token: Option<&Token> = self
+                                .tokens
+                                .iter()
+                                .find(|x| x.token == Tokens::Identifier && x.part == *name);
+                            let message: String =
+                                format!("variable '{}' is set twice in the same set", name);
+                            self.warn("W0004", message, token);
+                        }
+                    }
+                    assigned.extend(names.iter().cloned());
+                }
+                // The list has to be set before an item of it can be
+                Stmt::SetIndex {
+                    name,
+                    indexes,
+                    value,
+                    position,
+                } => {
+                    self.read(value, assigned);
+                    // The name is the token right before the first `[`
+                    let at: Position = self
+                        .tokens
+                        .windows(2)
+                        .find(|x| {
+                            x[1].line_num() == position.line_num
+                                && x[1].char_num() == position.char_num
+                        })
+                        .map_or(*position, |x| crate::parser::position(&x[0]));
+                    self.read(&Expr::Identifier(name.to_owned(), at), assigned);
+                    for index in indexes {
+                        self.read(index, assigned);
+                    }
+                }
+                Stmt::Print { exprs } => {
+                    for expr in exprs {
+                        self.read(expr, assigned);
+                    }
+                }
+                Stmt::Jump { offset: expr } | Stmt::Expr(expr) => self.read(expr, assigned),
+                // `ys += [2]` reads ys to add to it
+                Stmt::AugAssign { name, value, .. } => {
+                    self.reads.insert(name.to_owned());
+                    self.read(value, assigned);
+                }
+                Stmt::Return { value } | Stmt::Exit { code: value, .. } => {
+                    if let Some(value) = value {
+                        self.read(value, assigned);
+                    }
+                }
+                Stmt::Assert {
+                    value, expected, ..
+                } => {
+                    self.read(value, assigned);
+                    if let Some(expected) = expected {
+                        self.read(expected, assigned);
+                    }
+                }
+                // What a block sets for the first time goes away when it ends
+                Stmt::If {
+                    condition,
+                    body,
+                    else_body,
+                } => {
+                    self.read(condition, assigned);
+                    self.block(body, &mut assigned.clone());
+                    if let Some(else_body) = else_body {
+                        self.block(else_body, &mut assigned.clone());
+                    }
+                }
+                Stmt::Match {
+                    value,
+                    arms,
+                    position,
+                } => {
+                    self.read(value, assigned);
+                    self.match_arms(arms, *position, assigned);
+                }
+                Stmt::While { condition, body } => {
+                    self.read(condition, assigned);
+                    self.block(body, &mut assigned.clone());
+                }
+                Stmt::For {
+                    name,
+                    iterable,
+                    body,
+                    ..
+                } => {
+                    self.read(iterable, assigned);
+                    let mut inner: HashSet<String> = assigned.clone();
+                    inner.insert(name.to_owned());
+                    self.block(body, &mut inner);
+                }
+                // The name is only set in the handler
+                Stmt::Try {
+                    body,
+                    name,
+                    handler,
+                } => {
+                    self.block(body, &mut assigned.clone());
+                    let mut inner: HashSet<String> = assigned.clone();
+                    inner.insert(name.to_owned());
+                    self.block(handler, &mut inner);
+                }
+                Stmt::Func { name, params, body } => {
+                    self.function(name);
+                    let mut inner: HashSet<String> = self.globals.iter().cloned().collect();
+                    inner.extend(params.iter().cloned());
+                    self.block(body, &mut inner);
+                }
+                Stmt::Global { names } => assigned.extend(names.iter().cloned()),
+                Stmt::Break { .. } | Stmt::Continue { .. } | Stmt::Use { .. } | Stmt::Lines(_) => {}
+            }
+        }
+    }
+    /// Warn about an arm after the `_` or after one with the same value, which can
+    /// never run, and about a match with no `_` that doesn't cover both bools
+    fn match_arms(&mut self, arms: &[MatchArm], position: Position, assigned: &HashSet<String>) {
+        let mut seen: Vec<String> = Vec::new();
+        let mut wildcard: bool = false;
+        for arm in arms {
+            let pattern: Option<String> = arm.pattern.as_ref().map(expr_source);
+            let message: Option<&str> = if wildcard {
+                Some("this arm can't run, the '_' before it matches everything")
+            } else if pattern.as_ref().is_some_and(|x| seen.contains(x)) {
+                Some("this arm can't run, an arm before it matches the same value")
+            } else {
+                None
+            };
+            if let Some(message) = message {
+                self.warnings.push(ParseError {
+                    code: "W0005",
+                    message: message.to_string(),
+                    line_num: arm.position.line_num,
+                    char_num: arm.position.char_num,
+                });
+            }
+            match pattern {
+                Some(pattern) => seen.push(pattern),
+                None => wildcard = true,
+            }
+            self.block(&arm.body, &mut assigned.clone());
+        }
+        let both_bools: bool =
+            seen.iter().any(|x| x == "true") && seen.iter().any(|x| x == "false");
+        if !wildcard && !both_bools {
+            self.warnings.push(ParseError {
+                code: "W0006",
+                message:
+                    "this match has no '_' arm, so nothing runs for a value the arms don't have"
+                        .to_string(),
+                line_num: position.line_num,
+                char_num: position.char_num,
+            });
+        }
+    }
+    /// Warn about a function with the name of one before it, the parser already
+    /// stops at a parameter that's there twice
+    fn function(&mut self, name: &str) {
+        let definitions: Vec<usize> = self
+            .tokens
+            .windows(2)
+            .enumerate()
+            .filter(|(_, x)| x[0].token == Tokens::Func)
+            .map(|(index, _)| index + 1)
+            .collect();
+        let token: Option<&Token> = definitions.get(self.functions).map(|x| &self.tokens[*x]);
+        let defined_before: bool = definitions[..self.functions.min(definitions.len())]
+            .iter()
+            .any(|x| self.tokens[*x].part == name);
+        self.functions += 1;
+
+        if defined_before {
+            self.warn(
+                "W0004",
+                format!("function '{}' is already defined", name),
+                token,
+            );
+        }
+    }
+    fn read(&mut self, expr: &Expr, assigned: &HashSet<String>) {
+        let mut reads: ReadNames = ReadNames { names: Vec::new() };
+        reads.visit_expr(expr);
+        for (name, position) in reads.names {
+            // A name from a module like `shapes.area` is set when the module is loaded
+            let from_module: bool = name.contains('.');
+            if !assigned.contains(&name) && !from_module && self.warned.insert(name.to_owned()) {
+                self.warnings.push(ParseError {
+                    code: "W0002",
+                    message: format!("variable '{}' might be read before it's set", name),
+                    line_num: position.line_num,
+                    char_num: position.char_num,
+                });
+            }
+            self.reads.insert(name);
+        }
+    }
+}
+
+/// The variables an expression reads and where
+pub struct ReadNames {
+    names: Vec<(String, Position)>,
+}
+
+impl Visit for ReadNames {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Identifier(name, position) = expr {
+            self.names.push((name.to_owned(), *position));
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Every variable that gets set, inside of functions too, in the order they're first set
+pub struct SetNames {
+    names: Vec<String>,
+    lines: bool,
+}
+
+impl Visit for SetNames {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        let names: &[String] = match stmt {
+            Stmt::Set { name, .. } | Stmt::Const { name, .. } | Stmt::For { name, .. } => {
+                std::slice::from_ref(name)
+            }
+            Stmt::SetChain { names, .. } | Stmt::SetMany { names, .. } => names,
+            Stmt::Lines(_) => {
+                self.lines = true;
+                &[]
+            }
+            _ => &[],
+        };
+        for name in names {
+            if !self.names.contains(name) {
+                self.names.push(name.to_owned());
+            }
+        }
+        walk_stmt(self, stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    fn warnings(contents: &str) -> Vec<(&'static str, String, i64, i64)> {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+        check_semantics(&stmts, &lexer.tokens)
+            .into_iter()
+            .map(|x| (x.code, x.message, x.line_num, x.char_num))
+            .collect()
+    }
+
+    #[test]
+    fn check_semantics_test() {
+        assert_eq!(warnings("set a = 1;\nprint a;"), vec![]);
+
+        // A variable set for the first time in a block goes away when it ends
+        assert_eq!(
+            warnings("set a = 1;\nif a { set b = 2; }\nprint b;\nprint b;"),
+            vec![(
+                "W0002",
+                "variable 'b' might be read before it's set".to_string(),
+                3,
+                7
+            )]
+        );
+        assert_eq!(
+            warnings("set a = 1;\nif a { set b = 2; } else { set b = 3; }\nprint b;"),
+            vec![(
+                "W0002",
+                "variable 'b' might be read before it's set".to_string(),
+                3,
+                7
+            )]
+        );
+        assert_eq!(
+            warnings("set b = 1;\nif b { set b = 2; }\nprint b;"),
+            vec![]
+        );
+
+        // A function can read a variable that's set after it
+        assert_eq!(
+            warnings("func f() { return a; }\nset a = 1;\nprint f();"),
+            vec![]
+        );
+
+        assert_eq!(
+            warnings("set a = 1;\nset _b = 2;\nset c, d = 3, a;\nprint c;"),
+            vec![(
+                "W0003",
+                "variable 'd' is set but never read".to_string(),
+                3,
+                8
+            )]
+        );
+        assert_eq!(warnings("set ys = [];\nys += [2];"), vec![]);
+        assert_eq!(
+            warnings("func f(a) { return a; }\nfunc f() { return 1; }\nprint f();"),
+            vec![("W0004", "function 'f' is already defined".to_string(), 2, 6)]
+        );
+    }
+
+    #[test]
+    fn check_match_test() {
+        assert_eq!(
+            warnings(
+                "set a = 1;\nmatch a {\n  1 => print 1;\n  1 => print 2;\n  _ => {}\n  2 => {}\n}"
+            ),
+            vec![
+                (
+                    "W0005",
+                    "this arm can't run, an arm before it matches the same value".to_string(),
+                    4,
+                    3
+                ),
+                (
+                    "W0005",
+                    "this arm can't run, the '_' before it matches everything".to_string(),
+                    6,
+                    3
+                )
+            ]
+        );
+        assert_eq!(
+            warnings("set a = 1;\nmatch a { 1 => {} }\nmatch a > 1 { true => {} false => {} }"),
+            vec![(
+                "W0006",
+                "this match has no '_' arm, so nothing runs for a value the arms don't have"
+                    .to_string(),
+                2,
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn check_constants_test() {
+        let check = |contents: &str| -> Result<(), (String, i64, i64)> {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+            check_constants(&stmts, &lexer.tokens, &mut Consts::new())
+                .map_err(|x| (x.message, x.line_num, x.char_num))
+        };
+        assert_eq!(
+            check("const X = 10;\nset y = X + 1;\nset xs = [X];"),
+            Ok(())
+        );
+        // Setting it before the const is fine, it's only a const from there on
+        assert_eq!(check("set X = 1;\nconst X = X + 1;"), Ok(()));
+
+        let error = |line_num: i64, char_num: i64| -> Result<(), (String, i64, i64)> {
+            Err((
+                "can't set 'X' again, it's a const from line 1:7".to_string(),
+                line_num,
+                char_num,
+            ))
+        };
+        assert_eq!(check("const X = 10;\nset X = 11;"), error(2, 5));
+        assert_eq!(check("const X = 10;\nif 1 {\n  X += 1;\n}"), error(3, 3));
+        assert_eq!(check("const X = 10;\nset a, X = 1, 2;"), error(2, 8));
+        assert_eq!(check("const X = 10;\nfor X in 0..3 {}"), error(2, 5));
+        assert_eq!(check("const X = 10;\nconst X = 11;"), error(2, 7));
+        assert_eq!(check("const X = 10;\nX++;"), error(2, 1));
+        assert_eq!(
+            check("const X = 10;\nfunc f() {\n  set X = 1;\n}"),
+            error(3, 7)
+        );
+
+        // A const in a block or a function is only one inside of it
+        assert_eq!(
+            check("func f() {\n  const X = 1;\n}\nfunc g() {\n  set X = 2;\n}"),
+            Ok(())
+        );
+        assert_eq!(check("if 1 {\n  const X = 1;\n}\nset X = 2;"), Ok(()));
+        assert_eq!(
+            check("if 1 {\n  const Y = 1;\n  if 2 { Y += 1; }\n}"),
+            Err((
+                "can't set 'Y' again, it's a const from line 2:9".to_string(),
+                3,
+                10
+            ))
+        );
+        // A parameter can't take the name of a global const
+        assert_eq!(
+            check("const X = 10;\nfunc f(a, X) {\n  return X;\n}"),
+            Err((
+                "can't have a parameter 'X', it's a const from line 1:7".to_string(),
+                2,
+                11
+            ))
+        );
+
+        // The consts from before stay consts
+        let mut consts: Consts = Consts::new();
+        let mut lexer: Lexer = new_lexer("const X = 10;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+        assert_eq!(check_constants(&stmts, &lexer.tokens, &mut consts), Ok(()));
+        let mut lexer: Lexer = new_lexer("set X = 11;");
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+        assert_eq!(
+            check_constants(&stmts, &lexer.tokens, &mut consts)
+                .unwrap_err()
+                .message,
+            "can't set 'X' again, it's a const from line 1:7"
+        );
+    }
+
+    #[test]
+    fn check_loops_test() {
+        let check = |contents: &str| -> Result<(), (String, i64, i64)> {
+            let mut lexer: Lexer = new_lexer(contents);
+            lexer.lexer().unwrap();
+            let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+            check_loops(&stmts).map_err(|x| (x.message, x.line_num, x.char_num))
+        };
+        assert_eq!(
+            check("while a {\n  if b { break; }\n  for x in xs { continue; }\n}"),
+            Ok(())
+        );
+        assert_eq!(
+            check("set a = 1;\nif a {\n  break;\n}"),
+            Err(("'break' outside of a loop".to_string(), 3, 3))
+        );
+        // A function can't leave the loop it's defined or called in
+        assert_eq!(
+            check("while a {\n  func f() { continue; }\n}"),
+            Err(("'continue' outside of a loop".to_string(), 2, 14))
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Which line of a program each line of its python, javascript or c came from, for
+//! `--source-map` and for showing errors from python at the program's lines. The
+//! `.map` file is JSON with a line of the program for every line of the output
+//! starting from the first, or null for lines like the imports that aren't from one
+
+use crate::ast::Stmt;
+use crate::codegen::{codegen_mapped, Backend};
+use crate::debugger::LineTable;
+use crate::dump::Json;
+
+/// The lines of one output file and the program it was made from
+#[derive(PartialEq, Debug, Clone)]
+pub struct SourceMap {
+    /// What the output is called, like test.py
+    pub file: String,
+    /// The program it came from, like test.m
+    pub source: String,
+    /// The output line each statement starts on and its line in the program
+    pub statements: Vec<(usize, i64)>,
+    /// How many lines the output is
+    pub lines: usize,
+}
+
+/// Run the backend on the statements, giving back the output and its map
+pub fn mapped_codegen(
+    stmts: &[Stmt],
+    backend: &dyn Backend,
+    table: &LineTable,
+    file: &str,
+    source: &str,
+) -> (String, SourceMap) {
+    let (output, statements): (String, Vec<(usize, i64)>) = codegen_mapped(stmts, backend, table);
+    let map: SourceMap = SourceMap {
+        file: file.to_string(),
+        source: source.to_string(),
+        statements,
+        lines: output.lines().count(),
+    };
+    (output, map)
+}
+
+impl SourceMap {
+    /// The line of the program a line of the output came from, which is the
+    /// statement that started closest before it
+    pub fn line(&self, line: usize) -> Option<i64> {
+        self.statements
+            .iter()
+            .take_while(|(output, _)| *output <= line)
+            .last()
+            .map(|(_, source)| *source)
+    }
+    pub fn json(&self) -> Json {
+        let lines: Vec<Json> = (1..=self.lines)
+            .map(|x| self.line(x).map_or(Json::Null, Json::Number))
+            .collect();
+        Json::Object(vec![
+            ("version", Json::Number(1)),
+            ("file", Json::Str(self.file.clone())),
+            ("source", Json::Str(self.source.clone())),
+            ("lines", Json::Array(lines)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::JsBackend;
+    use crate::debugger::new_line_table;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse, Parser};
+
+    #[test]
+    fn source_map_test() {
+        let source: &str = "set a = 1;\n\nwhile a < 3 {\n    a += 1;\n}\nprint $PI;\n";
+        let mut lexer: Lexer = new_lexer(source);
+        lexer.lexer().unwrap();
+        let mut parser: Parser = new_parser(&lexer.tokens);
+        let stmts: Vec<Stmt> = parser.parse().unwrap();
+        let table: LineTable = new_line_table(source, &stmts, &parser.starts);
+
+        let (output, map): (String, SourceMap) =
+            mapped_codegen(&stmts, &JsBackend::new(&stmts), &table, "t.js", "t.m");
+        assert_eq!(
+            output,
+            "let a;\na = 1;\nwhile (a < 3) {\n    a += 1;\n}\nconsole.log(Math.PI);\n"
+        );
+        assert_eq!(map.statements, vec![(2, 1), (3, 3), (4, 4), (6, 6)]);
+        // The } goes with the statement before it
+        assert_eq!(map.line(5), Some(4));
+        assert_eq!(map.line(1), None);
+        assert_eq!(
+            map.json(),
+            Json::Object(vec![
+                ("version", Json::Number(1)),
+                ("file", Json::Str("t.js".to_string())),
+                ("source", Json::Str("t.m".to_string())),
+                (
+                    "lines",
+                    Json::Array(vec![
+                        Json::Null,
+                        Json::Number(1),
+                        Json::Number(3),
+                        Json::Number(4),
+                        Json::Number(4),
+                        Json::Number(6)
+                    ])
+                ),
+            ])
+        );
+    }
+}
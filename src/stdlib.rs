@@ -0,0 +1,553 @@
+//! The functions every program can call without defining them. The interpreter
+//! and the vm run them from here, and each backend turns them into what its
+//! language calls them
+
+use crate::ast::Stmt;
+use crate::interpreter::{
+    binary, loop_item, new_list, range_len, runtime_error, show, RuntimeError, Value,
+};
+use crate::lexer::Tokens;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Every builtin, a function from the program with one of these names is called instead
+pub const BUILTINS: &[&str] = &[
+    "abs",
+    "elapsed",
+    "env",
+    "float",
+    "floor",
+    "fmt",
+    "input",
+    "int",
+    "len",
+    "list",
+    "max",
+    "min",
+    "now",
+    "parse",
+    "pow",
+    "rand",
+    "rand_int",
+    "range",
+    "read_file",
+    "sleep",
+    "sqrt",
+    "str",
+    "write_file",
+];
+
+pub fn is_builtin(name: &str) -> bool {
+    BUILTINS.contains(&name)
+}
+
+/// The names of the functions the program defines, which come before the builtins
+pub fn program_functions(stmts: &[Stmt]) -> Vec<String> {
+    stmts
+        .iter()
+        .filter_map(|x| match x {
+            Stmt::Func { name, .. } => Some(name.to_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Read a line like python's input, without the newline. A prompt gets printed
+/// first without one, and is the only argument input can take
+pub fn input<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    args: &[Value],
+) -> Result<Value, RuntimeError> {
+    match args {
+        [] => {}
+        [prompt] => write!(output, "{}", prompt)
+            .and_then(|_| output.flush())
+            .map_err(|error| runtime_error(format!("could not print: {}", error)))?,
+        _ => {
+            return Err(runtime_error(format!(
+                "input() can't take {} arguments",
+                args.len()
+            )))
+        }
+    }
+    let mut line: String = String::new();
+    input
+        .read_line(&mut line)
+        .map_err(|error| runtime_error(format!("could not read input: {}", error)))?;
+    Ok(Value::Str(line.trim_end_matches(&['\r', '\n'][..]).into()))
+}
+
+/// Whether there's a clock to read and a thread to sleep. In a browser on
+/// wasm32-unknown-unknown there isn't, and std panics when asked for the time
+pub const HAS_CLOCK: bool = !cfg!(all(target_arch = "wasm32", target_os = "unknown"));
+
+/// When the program starts, for elapsed, if there's a clock to say
+pub fn start_time() -> Option<Instant> {
+    if HAS_CLOCK {
+        Some(Instant::now())
+    } else {
+        None
+    }
+}
+
+fn no_clock(name: &str) -> RuntimeError {
+    runtime_error(format!(
+        "{}() needs a clock, which this build doesn't have",
+        name
+    ))
+}
+
+/// How many seconds it's been since the program started, which is when its
+/// interpreter or vm was made
+pub fn elapsed(started: Option<Instant>, args: &[Value]) -> Result<Value, RuntimeError> {
+    match (args, started) {
+        ([], Some(started)) => Ok(Value::Float(started.elapsed().as_secs_f64())),
+        ([], None) => Err(no_clock("elapsed")),
+        (_, _) => Err(runtime_error(format!(
+            "elapsed() can't take {} argument{}",
+            args.len(),
+            if args.len() == 1 { "" } else { "s" }
+        ))),
+    }
+}
+
+/// The int or float the text is if it's a number, otherwise the text itself,
+/// for reading a value the user typed
+pub fn parse_value(text: &str) -> Value {
+    if let Ok(number) = text.trim().parse::<i64>() {
+        return Value::Int(number);
+    }
+    match text.trim().parse::<f64>() {
+        Ok(number) => Value::Float(number),
+        Err(_) => Value::Str(text.into()),
+    }
+}
+
+/// Where a program's random numbers start when it isn't given a seed, from the
+/// time if there's a clock
+pub fn start_random() -> u64 {
+    if !HAS_CLOCK {
+        return 0;
+    }
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_nanos() as u64)
+}
+
+/// The next random number, with splitmix64, moving the state along
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut number: u64 = (*state ^ (*state >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    number = (number ^ (number >> 27)).wrapping_mul(0x94d049bb133111eb);
+    number ^ (number >> 31)
+}
+
+/// Run rand or rand_int, picking from the state the interpreter or vm keeps so
+/// each program has its own. A float from 0 up to before 1, and an int that can
+/// be either end like python's randint
+pub fn random(state: &mut u64, name: &str, args: &[Value]) -> Result<Value, RuntimeError> {
+    match (name, args) {
+        ("rand", []) => Ok(Value::Float(
+            (next_random(state) >> 11) as f64 / (1u64 << 53) as f64,
+        )),
+        ("rand_int", [Value::Int(low), Value::Int(high)]) if low <= high => {
+            let count: u128 = (*high as i128 - *low as i128 + 1) as u128;
+            Ok(Value::Int(
+                (*low as i128 + (next_random(state) as u128 % count) as i128) as i64,
+            ))
+        }
+        ("rand_int", [Value::Int(low), Value::Int(high)]) => Err(runtime_error(format!(
+            "rand_int() can't pick from {} up to {}",
+            low, high
+        ))),
+        _ => Err(runtime_error(format!(
+            "{}() can't take {} argument{}",
+            name,
+            args.len(),
+            if args.len() == 1 { "" } else { "s" }
+        ))),
+    }
+}
+
+/// Why a file couldn't be read or written, without the number of the error
+fn io_reason(error: &io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::NotFound => "there's no file there".to_string(),
+        io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+        _ => error.to_string(),
+    }
+}
+
+/// A whole float as an int, or an error when there's no int for it instead of
+/// the nearest one that `as` would give
+fn whole_to_int(name: &str, number: f64) -> Result<Value, RuntimeError> {
+    // i64::MAX is 2 ** 63 as a float, which is one more than fits
+    if number >= i64::MIN as f64 && number < i64::MAX as f64 {
+        Ok(Value::Int(number as i64))
+    } else {
+        Err(runtime_error(format!(
+            "{}() of {} doesn't fit in an int",
+            name,
+            Value::Float(number)
+        )))
+    }
+}
+
+/// Run a builtin other than input, which needs something to read from
+pub fn call(name: &str, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    let wrong_args = || {
+        runtime_error(format!(
+            "{}() can't take {} argument{}",
+            name,
+            args.len(),
+            if args.len() == 1 { "" } else { "s" }
+        ))
+    };
+    match (name, args.as_slice()) {
+        ("abs", [Value::Int(number)]) => number
+            .checked_abs()
+            .map(Value::Int)
+            .ok_or_else(|| runtime_error("integer overflow".to_string())),
+        ("abs", [Value::Float(number)]) => Ok(Value::Float(number.abs())),
+        ("len", [Value::Str(text)]) => Ok(Value::Int(text.chars().count() as i64)),
+        ("len", [Value::List(items)]) => Ok(Value::Int(items.borrow().len() as i64)),
+        ("len", [Value::Dict(entries)]) => Ok(Value::Int(entries.borrow().len() as i64)),
+        ("len", [Value::Range(start, end)]) => Ok(Value::Int(range_len(*start, *end) as i64)),
+        // Like python, `range(n)` starts at 0
+        ("range", [Value::Int(end)]) => Ok(Value::Range(0, *end)),
+        ("range", [Value::Int(start), Value::Int(end)]) => Ok(Value::Range(*start, *end)),
+        ("list", [range @ Value::Range(..)]) => {
+            Ok(new_list((0..).map_while(|x| loop_item(range, x)).collect()))
+        }
+        // A list of the keys of a dict, the characters of a string, or a copy of a list
+        ("list", [Value::List(items)]) => Ok(new_list(items.borrow().to_vec())),
+        ("list", [Value::Dict(entries)]) => Ok(new_list(
+            entries
+                .borrow()
+                .iter()
+                .map(|(key, _)| key.clone())
+                .collect(),
+        )),
+        ("list", [Value::Str(text)]) => Ok(new_list(
+            text.chars()
+                .map(|x| Value::Str(x.to_string().into()))
+                .collect(),
+        )),
+        // With only a list, python gives back the smallest or biggest item in it
+        ("min", [Value::List(items)]) | ("max", [Value::List(items)]) => {
+            let mut items: Vec<Value> = items.borrow().to_vec();
+            match items.len() {
+                0 => Err(runtime_error(format!("{}() of an empty list", name))),
+                1 => Ok(items.remove(0)),
+                _ => call(name, items),
+            }
+        }
+        // And with only a dict, the smallest or biggest key, or the same for the ints of a range
+        ("min", [many @ Value::Dict(_)])
+        | ("max", [many @ Value::Dict(_)])
+        | ("min", [many @ Value::Range(..)])
+        | ("max", [many @ Value::Range(..)]) => call(name, vec![call("list", vec![many.clone()])?]),
+        ("str", [value]) => Ok(Value::Str(show(value)?.into())),
+        ("int", [Value::Str(text)]) => text
+            .trim()
+            .parse()
+            .map(Value::Int)
+            .map_err(|_| runtime_error(format!("'{}' is not an int", text))),
+        ("int", [Value::Int(number)]) => Ok(Value::Int(*number)),
+        ("int", [value]) => value
+            .as_float()
+            .ok_or_else(wrong_args)
+            .and_then(|x| whole_to_int(name, x.trunc())),
+        ("float", [Value::Str(text)]) => text
+            .trim()
+            .parse()
+            .map(Value::Float)
+            .map_err(|_| runtime_error(format!("'{}' is not a float", text))),
+        ("float", [value]) => value.as_float().map(Value::Float).ok_or_else(wrong_args),
+        ("parse", [Value::Str(text)]) => Ok(parse_value(text)),
+        ("parse", [value]) => Ok(value.clone()),
+        ("read_file", [Value::Str(path)]) => fs::read_to_string(&**path)
+            .map(|x| Value::Str(x.into()))
+            .map_err(|error| runtime_error(format!("can't read {}: {}", path, io_reason(&error)))),
+        ("write_file", [Value::Str(path), Value::Str(text)]) => fs::write(&**path, &**text)
+            .map(|_| Value::None)
+            .map_err(|error| runtime_error(format!("can't write {}: {}", path, io_reason(&error)))),
+        // The seconds since 1970 like python's time.time, and waiting for some milliseconds
+        ("now" | "sleep", _) if !HAS_CLOCK => Err(no_clock(name)),
+        ("now", []) => Ok(Value::Float(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0.0, |x| x.as_secs_f64()),
+        )),
+        ("sleep", [ms @ (Value::Int(_) | Value::Float(_))]) => match ms.as_float() {
+            Some(ms) if ms >= 0.0 && ms.is_finite() => {
+                thread::sleep(Duration::from_secs_f64(ms / 1000.0));
+                Ok(Value::None)
+            }
+            _ => Err(runtime_error(format!("sleep() can't wait for {} ms", ms))),
+        },
+        // A variable that isn't set is the same as an empty one, like in a shell
+        ("env", [Value::Str(name)]) => Ok(Value::Str(
+            std::env::var(&**name).unwrap_or_default().into(),
+        )),
+        ("pow", [base, exponent]) => binary(base.clone(), Tokens::Power, exponent.clone()),
+        ("sqrt", [value]) => match value.as_float() {
+            Some(number) if number < 0.0 => Err(runtime_error("math domain error".to_string())),
+            Some(number) => Ok(Value::Float(number.sqrt())),
+            None => Err(wrong_args()),
+        },
+        // Like python's math.floor, it gives back an int
+        ("floor", [Value::Int(number)]) => Ok(Value::Int(*number)),
+        ("floor", [value]) => value
+            .as_float()
+            .ok_or_else(wrong_args)
+            .and_then(|x| whole_to_int(name, x.floor())),
+        // Like python's format(number, ".2f"), the number as text with that many places
+        // Rust can't show more places than fit in a u16
+        ("fmt", [value, Value::Int(places)]) if (0..=i64::from(u16::MAX)).contains(places) => value
+            .as_float()
+            .map(|x| Value::Str(format!("{:.*}", *places as usize, x).into()))
+            .ok_or_else(wrong_args),
+        ("fmt", [_, Value::Int(places)]) => Err(runtime_error(format!(
+            "fmt() can't show {} decimal places",
+            places
+        ))),
+        ("min", [first, rest @ ..]) | ("max", [first, rest @ ..]) if !rest.is_empty() => {
+            let mut best: Value = first.clone();
+            for value in rest {
+                let compare: Tokens = if name == "min" {
+                    Tokens::LessThan
+                } else {
+                    Tokens::GreaterThan
+                };
+                if binary(value.clone(), compare, best.clone())?.is_truthy() {
+                    best = value.clone();
+                }
+            }
+            Ok(best)
+        }
+        ("abs", _)
+        | ("len", _)
+        | ("str", _)
+        | ("int", _)
+        | ("float", _)
+        | ("min", _)
+        | ("max", _)
+        | ("list", _)
+        | ("range", _)
+        | ("parse", _)
+        | ("pow", _)
+        | ("read_file", _)
+        | ("write_file", _)
+        | ("env", _)
+        | ("now", _)
+        | ("sleep", _)
+        | ("sqrt", _)
+        | ("floor", _)
+        | ("fmt", _) => Err(wrong_args()),
+        _ => Err(runtime_error(format!("unknown function '{}'", name))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_test() {
+        assert_eq!(
+            call("pow", vec![Value::Int(2), Value::Int(10)]),
+            Ok(Value::Int(1024))
+        );
+        assert_eq!(call("sqrt", vec![Value::Int(16)]), Ok(Value::Float(4.0)));
+        assert_eq!(call("floor", vec![Value::Float(-2.5)]), Ok(Value::Int(-3)));
+        assert_eq!(call("abs", vec![Value::Int(-3)]), Ok(Value::Int(3)));
+        assert_eq!(
+            call("fmt", vec![Value::Float(1.2468), Value::Int(2)]),
+            Ok(Value::Str("1.25".into()))
+        );
+        assert_eq!(
+            call("fmt", vec![Value::Int(2), Value::Int(-1)])
+                .unwrap_err()
+                .message,
+            "fmt() can't show -1 decimal places"
+        );
+        assert_eq!(
+            call("parse", vec![Value::Str(" 42 ".into())]),
+            Ok(Value::Int(42))
+        );
+        assert_eq!(parse_value("2.5"), Value::Float(2.5));
+        assert_eq!(parse_value("bob"), Value::Str("bob".into()));
+        assert_eq!(call("parse", vec![Value::Int(3)]), Ok(Value::Int(3)));
+        assert_eq!(
+            call("sqrt", vec![Value::Int(-1)]).unwrap_err().message,
+            "math domain error"
+        );
+        assert_eq!(
+            call("floor", vec![Value::Str("a".into())])
+                .unwrap_err()
+                .message,
+            "floor() can't take 1 argument"
+        );
+        // A float too big for an int is an error instead of the biggest int
+        assert_eq!(call("int", vec![Value::Float(-2.5)]), Ok(Value::Int(-2)));
+        assert_eq!(
+            call("int", vec![Value::Int(i64::MAX)]),
+            Ok(Value::Int(i64::MAX))
+        );
+        assert_eq!(
+            call("int", vec![Value::Float(1e300)]).unwrap_err().message,
+            "int() of 1e300 doesn't fit in an int"
+        );
+        assert_eq!(
+            call("floor", vec![Value::Float(f64::NAN)])
+                .unwrap_err()
+                .message,
+            "floor() of nan doesn't fit in an int"
+        );
+        assert_eq!(
+            call("floor", vec![Value::Float(-(2f64.powi(63)))]),
+            Ok(Value::Int(i64::MIN))
+        );
+        assert!(call("int", vec![Value::Float(2f64.powi(63))]).is_err());
+        assert_eq!(
+            call("pow", vec![Value::Int(2)]).unwrap_err().message,
+            "pow() can't take 1 argument"
+        );
+        assert!(BUILTINS.iter().all(|x| is_builtin(x)));
+        assert!(!is_builtin("print"));
+    }
+
+    #[test]
+    fn rand_test() {
+        let mut state: u64 = 7;
+        let picked: Vec<Value> = (0..20)
+            .map(|_| random(&mut state, "rand_int", &[Value::Int(-2), Value::Int(2)]).unwrap())
+            .collect();
+        assert!(picked.iter().all(|x| matches!(x, Value::Int(-2..=2))));
+        assert!(
+            matches!(random(&mut state, "rand", &[]), Ok(Value::Float(x)) if (0.0..1.0).contains(&x))
+        );
+        // The same seed picks the same numbers again, and another state doesn't move it
+        let mut state: u64 = 7;
+        let mut other: u64 = 7;
+        let _ = random(&mut other, "rand", &[]);
+        assert_eq!(
+            (0..20)
+                .map(|_| random(&mut state, "rand_int", &[Value::Int(-2), Value::Int(2)]).unwrap())
+                .collect::<Vec<Value>>(),
+            picked
+        );
+        assert!(random(
+            &mut state,
+            "rand_int",
+            &[Value::Int(i64::MIN), Value::Int(i64::MAX)]
+        )
+        .is_ok());
+        assert_eq!(
+            random(&mut state, "rand_int", &[Value::Int(3), Value::Int(1)])
+                .unwrap_err()
+                .message,
+            "rand_int() can't pick from 3 up to 1"
+        );
+        assert_eq!(
+            random(&mut state, "rand", &[Value::Int(1)])
+                .unwrap_err()
+                .message,
+            "rand() can't take 1 argument"
+        );
+    }
+
+    #[test]
+    fn time_test() {
+        let started: Option<Instant> = start_time();
+        assert!(matches!(call("now", vec![]), Ok(Value::Float(x)) if x > 1.6e9));
+        assert_eq!(call("sleep", vec![Value::Int(5)]), Ok(Value::None));
+        assert!(matches!(elapsed(started, &[]), Ok(Value::Float(x)) if x >= 0.005));
+        assert_eq!(
+            call("sleep", vec![Value::Float(-1.5)]).unwrap_err().message,
+            "sleep() can't wait for -1.5 ms"
+        );
+        assert_eq!(
+            elapsed(started, &[Value::Int(1)]).unwrap_err().message,
+            "elapsed() can't take 1 argument"
+        );
+        // What a build without a clock says instead of panicking
+        assert_eq!(
+            elapsed(None, &[]).unwrap_err().message,
+            "elapsed() needs a clock, which this build doesn't have"
+        );
+    }
+
+    #[test]
+    fn input_test() {
+        let mut output: Vec<u8> = Vec::new();
+        let value: Value = input(
+            &mut &b"bo\nextra\n"[..],
+            &mut output,
+            &[Value::Str("name? ".into())],
+        )
+        .unwrap();
+        assert_eq!(value, Value::Str("bo".into()));
+        assert_eq!(String::from_utf8(output).unwrap(), "name? ");
+
+        assert_eq!(
+            input(
+                &mut &b""[..],
+                &mut Vec::new(),
+                &[Value::Int(1), Value::Int(2)]
+            )
+            .unwrap_err()
+            .message,
+            "input() can't take 2 arguments"
+        );
+    }
+
+    #[test]
+    fn env_test() {
+        std::env::set_var("MAHOU_ENV_TEST", "on");
+        assert_eq!(
+            call("env", vec![Value::Str("MAHOU_ENV_TEST".into())]),
+            Ok(Value::Str("on".into()))
+        );
+        // One that isn't set is empty, so it can be checked with len
+        assert_eq!(
+            call("env", vec![Value::Str("MAHOU_ENV_TEST_UNSET".into())]),
+            Ok(Value::Str("".into()))
+        );
+        assert_eq!(
+            call("env", vec![Value::Int(1)]).unwrap_err().message,
+            "env() can't take 1 argument"
+        );
+    }
+
+    #[test]
+    fn file_test() {
+        let path: String = std::env::temp_dir()
+            .join("mahou_file_test.txt")
+            .to_string_lossy()
+            .to_string();
+        assert_eq!(
+            call(
+                "write_file",
+                vec![Value::Str(path.as_str().into()), Value::Str("hi\n".into())]
+            ),
+            Ok(Value::None)
+        );
+        assert_eq!(
+            call("read_file", vec![Value::Str(path.as_str().into())]),
+            Ok(Value::Str("hi\n".into()))
+        );
+        fs::remove_file(&path).unwrap();
+
+        let error: RuntimeError =
+            call("read_file", vec![Value::Str(path.as_str().into())]).unwrap_err();
+        assert_eq!(
+            error.message,
+            format!("can't read {}: there's no file there", path)
+        );
+    }
+}
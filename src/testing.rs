@@ -0,0 +1,203 @@
+//! Runs the test files for `mahou test`, which are the programs ending in `_test.m`.
+//! Each `assert` and `assert_eq` in them is kept instead of the first failed one
+//! stopping the program, so every one of them gets reported with its line
+
+use crate::ast::Stmt;
+use crate::interpreter::{new_interpreter, Assertion, Interpret, Interpreter, RuntimeError};
+use crate::modules::EXTENSION;
+use crate::parse_source;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What happened when one test file ran
+#[derive(PartialEq, Debug, Clone)]
+pub struct TestResult {
+    pub file: String,
+    pub assertions: Vec<Assertion>,
+    /// What the program printed, which is only shown if something in it failed
+    pub output: String,
+    /// The error that stopped the file, already rendered, like one from parsing
+    pub error: Option<String>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.assertions.iter().all(|x| x.failure.is_none())
+    }
+}
+
+/// Whether the file is a test, like `math_test.m`
+pub fn is_test_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|x| x.to_str())
+        .is_some_and(|x| x.ends_with(&format!("_test.{}", EXTENSION)))
+}
+
+/// The test files in the paths sorted, a folder is looked through all the way down
+/// besides the hidden folders in it. A file that's given is always run
+pub fn test_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            find_tests(path, &mut files)?;
+        } else {
+            files.push(path.to_owned());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn find_tests(folder: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(folder)? {
+        let path: PathBuf = entry?.path();
+        let hidden: bool = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .is_some_and(|x| x.starts_with('.'));
+        if path.is_dir() && !hidden {
+            find_tests(&path, files)?;
+        } else if is_test_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Run the test program, with no input and keeping what it prints. The seed is
+/// where its random numbers start, if it's given one
+pub fn run_test(contents: &str, filename: &str, seed: Option<u64>) -> TestResult {
+    let mut result: TestResult = TestResult {
+        file: filename.to_string(),
+        assertions: Vec::new(),
+        output: String::new(),
+        error: None,
+    };
+    let stmts: Vec<Stmt> = match parse_source(contents, Some(filename), false) {
+        Ok(stmts) => stmts,
+        Err(diagnostic) => {
+            result.error = Some(diagnostic);
+            return result;
+        }
+    };
+    let mut output: Vec<u8> = Vec::new();
+    let mut interpreter: Interpreter<io::Empty, &mut Vec<u8>> =
+        new_interpreter(io::empty(), &mut output);
+    interpreter.assertions = Some(Vec::new());
+    if let Some(seed) = seed {
+        interpreter.random = seed;
+    }
+    let ran: Result<(), RuntimeError> = interpreter.run(&stmts);
+    result.assertions = interpreter.assertions.take().unwrap_or_default();
+    result.error = match ran {
+        Ok(()) => None,
+        Err(RuntimeError { exit: Some(0), .. }) => None,
+        Err(error) => Some(error.render(contents, Some(filename), false)),
+    };
+    result.output = String::from_utf8_lossy(&output).to_string();
+    result
+}
+
+/// A line for each assertion and error, what the files that failed printed, and
+/// how many passed at the end
+pub fn test_report(results: &[TestResult]) -> String {
+    let mut report: String = String::new();
+    for result in results {
+        for assertion in &result.assertions {
+            let line: i64 = assertion.position.line_num;
+            match &assertion.failure {
+                Some(message) => {
+                    report.push_str(&format!("FAIL {}:{}: {}\n", result.file, line, message))
+                }
+                None => report.push_str(&format!("ok {}:{}\n", result.file, line)),
+            }
+        }
+        if let Some(error) = &result.error {
+            report.push_str(&format!(
+                "FAIL {} stopped with an error\n{}",
+                result.file, error
+            ));
+        }
+        if !result.passed() && !result.output.is_empty() {
+            report.push_str(&format!(
+                "--- what {} printed\n{}",
+                result.file, result.output
+            ));
+        }
+    }
+    let assertions: Vec<&Assertion> = results.iter().flat_map(|x| &x.assertions).collect();
+    let failed: usize = assertions.iter().filter(|x| x.failure.is_some()).count();
+    report.push_str(&format!(
+        "{} assertions, {} passed and {} failed",
+        assertions.len(),
+        assertions.len() - failed,
+        failed
+    ));
+    let errors: usize = results.iter().filter(|x| x.error.is_some()).count();
+    if errors > 0 {
+        report.push_str(&format!(
+            ", {} of {} files stopped with an error",
+            errors,
+            results.len()
+        ));
+    }
+    report.push('\n');
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_test_test() {
+        let result: TestResult = run_test(
+            "set a = 2;\nassert a > 1;\nprint a;\nassert_eq [a, \"b\"], [a, \"c\"];\nassert a == 3;\n",
+            "math_test.m",
+            None,
+        );
+        assert!(!result.passed());
+        assert_eq!(result.error, None);
+        assert_eq!(
+            test_report(&[result]),
+            "ok math_test.m:2\n\
+             FAIL math_test.m:4: assertion failed: [2, 'b'] != [2, 'c']\n\
+             FAIL math_test.m:5: assertion failed: a == 3\n\
+             --- what math_test.m printed\n2\n\
+             3 assertions, 1 passed and 2 failed\n"
+        );
+
+        let result: TestResult = run_test("assert true;\nprint 1 / 0;\n", "zero_test.m", None);
+        assert!(!result.passed());
+        assert!(result.error.unwrap().contains("zero_test.m:2"));
+        assert!(run_test("assert_eq 1, 1.0;\nexit;\n", "t_test.m", None).passed());
+    }
+
+    #[test]
+    fn test_files_test() {
+        let folder: PathBuf = std::env::temp_dir().join("mahou_test_files_test");
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(folder.join("more")).unwrap();
+        fs::create_dir_all(folder.join(".hidden")).unwrap();
+        for file in [
+            "a_test.m",
+            "a.m",
+            "more/b_test.m",
+            ".hidden/c_test.m",
+            "test.m",
+        ] {
+            fs::write(folder.join(file), "assert true;\n").unwrap();
+        }
+        assert_eq!(
+            test_files(&[folder.clone(), folder.join("a.m")]).unwrap(),
+            vec![
+                folder.join("a.m"),
+                folder.join("a_test.m"),
+                folder.join("more/b_test.m")
+            ]
+        );
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+//! Prints each statement as it runs with `--trace`, and after it the variables it
+//! changed. It's a hook on the interpreter like the debugger
+
+use crate::ast::{Position, Stmt};
+use crate::debugger::{new_line_table, LineTable};
+use crate::interpreter::{repr, runtime_error, Hook, Inspect, RuntimeError};
+use std::collections::HashMap;
+
+pub struct Tracer {
+    lines: LineTable,
+    /// What each variable was before the statements that are running, as it's
+    /// shown, so a list changing inside counts too
+    before: Vec<HashMap<String, String>>,
+}
+
+/// Make a tracer for the statements, starts is where each one starts from the parser
+pub fn new_tracer(contents: &str, stmts: &[Stmt], starts: &[Position]) -> Tracer {
+    Tracer {
+        lines: new_line_table(contents, stmts, starts),
+        before: Vec::new(),
+    }
+}
+
+/// Every variable that can be seen, shown like print shows it in a list
+fn variables(interpreter: &dyn Inspect) -> HashMap<String, String> {
+    interpreter
+        .names()
+        .into_iter()
+        .filter_map(|name| {
+            let value: String = repr(&interpreter.variable(&name).ok()?);
+            Some((name, value))
+        })
+        .collect()
+}
+
+/// The variables that are new or different now, sorted by name
+pub fn changes(before: &HashMap<String, String>, after: &HashMap<String, String>) -> Vec<String> {
+    let mut changes: Vec<String> = after
+        .iter()
+        .filter(|(name, value)| before.get(*name) != Some(value))
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    changes.sort();
+    changes
+}
+
+impl Tracer {
+    /// Statements inside of blocks are indented under the one they're in
+    fn indent(&self) -> String {
+        "  ".repeat(self.before.len())
+    }
+    fn write(&self, interpreter: &mut dyn Inspect, text: &str) -> Result<(), RuntimeError> {
+        write!(interpreter.output(), "{}", text)
+            .map_err(|error| runtime_error(format!("could not print: {}", error)))
+    }
+}
+
+impl Hook for Tracer {
+    fn before(&mut self, interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError> {
+        self.lines.visit(interpreter, stmt);
+        let text: String = format!("{}{}\n", self.indent(), self.lines.location(stmt));
+        self.write(interpreter, &text)?;
+        self.before.push(variables(interpreter));
+        Ok(())
+    }
+    fn after(&mut self, interpreter: &mut dyn Inspect, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let before: HashMap<String, String> = self.before.pop().unwrap_or_default();
+        // What a block changed was already shown by the statements inside of it
+        if let Stmt::If { .. }
+        | Stmt::Match { .. }
+        | Stmt::Try { .. }
+        | Stmt::While { .. }
+        | Stmt::For { .. }
+        | Stmt::Func { .. } = stmt
+        {
+            return Ok(());
+        }
+        let text: String = changes(&before, &variables(interpreter))
+            .iter()
+            .map(|change| format!("{}  {}\n", self.indent(), change))
+            .collect();
+        self.write(interpreter, &text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{new_interpreter, Interpret, Interpreter};
+    use crate::modules::parse_file_with_starts;
+
+    #[test]
+    fn trace_test() {
+        let contents: &str =
+            "set a = 1;\nset xs = [a];\nwhile a < 3 {\n    a += 1;\n}\nset xs[0] = 5;";
+        let (stmts, starts): (Vec<Stmt>, Vec<Position>) =
+            parse_file_with_starts(contents, None, false).unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut interpreter: Interpreter<&[u8], &mut Vec<u8>> =
+                new_interpreter(&b""[..], &mut output);
+            interpreter.hook = Some(Box::new(new_tracer(contents, &stmts, &starts)));
+            interpreter.run(&stmts).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "line 1: set a = 1;\n  a = 1\nline 2: set xs = [a];\n  xs = [1]\n\
+             line 3: while a < 3 {\n  line 4: a += 1;\n    a = 2\n  line 4: a += 1;\n    a = 3\n\
+             line 6: set xs[0] = 5;\n  xs = [5]\n"
+        );
+    }
+}
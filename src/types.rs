@@ -0,0 +1,701 @@
+//! Works out the type of every variable from what it's set to, and finds operators
+//! used on types that don't go together before anything is outputted or run
+
+use crate::ast::{Expr, Position, Stmt};
+use crate::codegen::global_names;
+use crate::interpreter::{binary, negate, new_dict, new_list, new_range, parse_number, Value};
+use crate::lexer::{Token, Tokens};
+use crate::parser::ParseError;
+use crate::stdlib::call;
+use std::collections::{HashMap, HashSet};
+
+/// The type of a value, as far as it can be known without running anything
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Type {
+    Int,
+    Float,
+    Str,
+    Bool,
+    /// The items of a list aren't kept track of, each one could be anything
+    List,
+    /// Same for the keys and values of a dict
+    Dict,
+    Range,
+    /// Could be anything, like a parameter or what a function gives back
+    Unknown,
+}
+
+impl Type {
+    /// The type of an actual value
+    pub fn of(value: &Value) -> Type {
+        match value {
+            Value::Int(_) => Type::Int,
+            Value::Float(_) => Type::Float,
+            Value::Str(_) => Type::Str,
+            Value::Bool(_) => Type::Bool,
+            Value::List(_) => Type::List,
+            Value::Dict(_) => Type::Dict,
+            Value::Range(..) => Type::Range,
+            Value::None => Type::Unknown,
+        }
+    }
+    /// A value of the type, so the interpreter's own math can say what an operator
+    /// gives back. The string is a number so int() and float() work on it
+    fn sample(self) -> Option<Value> {
+        match self {
+            Type::Int => Some(Value::Int(1)),
+            Type::Float => Some(Value::Float(1.0)),
+            Type::Str => Some(Value::Str("1".into())),
+            Type::Bool => Some(Value::Bool(true)),
+            Type::List => Some(new_list(Vec::new())),
+            Type::Dict => Some(new_dict(Vec::new())),
+            Type::Range => Some(Value::Range(0, 1)),
+            Type::Unknown => None,
+        }
+    }
+    /// The type for error messages, like `an int`
+    pub fn name(self) -> &'static str {
+        self.sample().map_or("anything", |x| x.type_name())
+    }
+    /// The type a variable has when it's set to both, an int can become a float
+    /// but nothing can become a string
+    pub fn join(self, other: Type) -> Option<Type> {
+        match (self, other) {
+            _ if self == other => Some(self),
+            (Type::Unknown, _) | (_, Type::Unknown) => Some(Type::Unknown),
+            (Type::Int, Type::Bool) | (Type::Bool, Type::Int) => Some(Type::Int),
+            (Type::Float, Type::Int | Type::Bool) | (Type::Int | Type::Bool, Type::Float) => {
+                Some(Type::Float)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Give back every type error in the program, in the order they are in the source.
+/// The tokens are for where set statements and calls are, since those don't keep that
+pub fn check_types(stmts: &[Stmt], tokens: &[Token]) -> Vec<ParseError> {
+    let mut checker: TypeChecker = new_type_checker(stmts, tokens);
+    let mut types: HashMap<String, Type> = HashMap::new();
+    checker.block(stmts, &mut types);
+
+    checker.errors.sort_by_key(|x| (x.line_num, x.char_num));
+    checker.errors
+}
+
+/// The type of each variable outside of every block and function once the
+/// whole program has run, for showing in an editor
+pub fn variable_types(stmts: &[Stmt], tokens: &[Token]) -> HashMap<String, Type> {
+    let mut types: HashMap<String, Type> = HashMap::new();
+    new_type_checker(stmts, tokens).block(stmts, &mut types);
+    types
+}
+
+fn new_type_checker<'a>(stmts: &[Stmt], tokens: &'a [Token]) -> TypeChecker<'a> {
+    TypeChecker {
+        targets: set_targets(tokens),
+        calls: tokens
+            .windows(2)
+            .filter(|x| x[0].token == Tokens::Identifier && x[1].token == Tokens::LeftParen)
+            .map(|x| &x[0])
+            .collect(),
+        functions: stmts
+            .iter()
+            .filter_map(|x| match x {
+                Stmt::Func { name, .. } => Some(name.to_owned()),
+                _ => None,
+            })
+            .collect(),
+        seen: HashMap::new(),
+        errors: Vec::new(),
+    }
+}
+
+/// The name tokens that get set, in the order they are in the source. That's the
+/// name before `=` or `+=` in a set or const, and the names before the `=` in
+/// `set a, b = 1, 2;`. Names inside of an index like `set xs[i] = 1;` are only
+/// read. The name after `for` or `input` is set too
+pub fn set_targets(tokens: &[Token]) -> Vec<&Token> {
+    let mut targets: Vec<&Token> = Vec::new();
+    let mut in_set: bool = false;
+    let mut assigned: bool = false;
+    let mut brackets: usize = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.token {
+            Tokens::Set | Tokens::Const => {
+                in_set = true;
+                assigned = false;
+            }
+            Tokens::LeftBracket => brackets += 1,
+            Tokens::RightBracket => brackets = brackets.saturating_sub(1),
+            Tokens::Assign => assigned = true,
+            Tokens::Semi => in_set = false,
+            Tokens::Identifier => {
+                let next: Option<Tokens> = tokens.get(index + 1).map(|x| x.token);
+                let after_for: bool =
+                    index > 0 && matches!(tokens[index - 1].token, Tokens::For | Tokens::Input);
+                let target: bool = match next {
+                    _ if after_for => true,
+                    _ if brackets > 0 => false,
+                    Some(Tokens::Assign) => in_set,
+                    Some(Tokens::Comma) => in_set && !assigned,
+                    Some(
+                        Tokens::PlusAssign
+                        | Tokens::MinusAssign
+                        | Tokens::MultiplyAssign
+                        | Tokens::DivideAssign
+                        | Tokens::Increment
+                        | Tokens::Decrement,
+                    ) => true,
+                    _ => false,
+                };
+                if target {
+                    targets.push(token);
+                }
+            }
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// Keeps track of the errors while going through the program in order
+pub struct TypeChecker<'a> {
+    targets: Vec<&'a Token>,
+    calls: Vec<&'a Token>,
+    /// The functions in the program, which can give back anything
+    functions: HashSet<String>,
+    /// How many times each name has been set or called so far, to find the token of the next one
+    seen: HashMap<(String, bool), usize>,
+    errors: Vec<ParseError>,
+}
+
+impl TypeChecker<'_> {
+    fn error(&mut self, message: String, position: Position) {
+        self.errors.push(ParseError {
+            code: "E0202",
+            message,
+            line_num: position.line_num,
+            char_num: position.char_num,
+        });
+    }
+    /// Where the next set of the name is, or the next call of it
+    fn next_position(&mut self, name: &str, call: bool) -> Position {
+        let count: &mut usize = self.seen.entry((name.to_owned(), call)).or_insert(0);
+        let tokens: &[&Token] = if call { &self.calls } else { &self.targets };
+        let position: Position =
+            tokens
+                .iter()
+                .filter(|x| x.part == name)
+                .nth(*count)
+                .map_or(Position::default(), |x| Position {
+                    line_num: x.line_num(),
+                    char_num: x.char_num(),
+                });
+        *count += 1;
+        position
+    }
+    /// Go through a block, updating the types of the variables set in it
+    fn block(&mut self, stmts: &[Stmt], types: &mut HashMap<String, Type>) {
+        for stmt in stmts {
+            match stmt {
+                Stmt::Set { name, value } | Stmt::Const { name, value } => {
+                    let value: Type = self.expr(value, types);
+                    self.assign(name, value, types);
+                }
+                Stmt::SetChain { names, value } => {
+                    let value: Type = self.expr(value, types);
+                    for name in names {
+                        self.assign(name, value, types);
+                    }
+                }
+                Stmt::SetMany { names, values } => {
+                    let values: Vec<Type> = values.iter().map(|x| self.expr(x, types)).collect();
+                    for (name, value) in names.iter().zip(values) {
+                        self.assign(name, value, types);
+                    }
+                }
+                Stmt::AugAssign {
+                    name,
+                    operator,
+                    value,
+                } => {
+                    let value: Type = self.expr(value, types);
+                    let current: Type = types.get(name).copied().unwrap_or(Type::Unknown);
+                    let position: Position = self.next_position(name, false);
+                    let operator: Tokens = match operator {
+                        Tokens::PlusAssign => Tokens::Plus,
+                        Tokens::MinusAssign => Tokens::Minus,
+                        Tokens::MultiplyAssign => Tokens::Multiply,
+                        _ => Tokens::Divide,
+                    };
+                    let result: Type = self.binary(current, operator, value, position);
+                    types.insert(name.to_owned(), result);
+                }
+                Stmt::SetIndex {
+                    name,
+                    indexes,
+                    value,
+                    position,
+                } => {
+                    self.expr(value, types);
+                    let mut target: Type = types.get(name).copied().unwrap_or(Type::Unknown);
+                    let (last, rest) = indexes.split_last().unwrap();
+                    for index in rest {
+                        target = self.index(target, index, *position, types);
+                    }
+                    self.expr(last, types);
+                    if !matches!(target, Type::List | Type::Dict | Type::Unknown) {
+                        self.error(
+                            format!("can't set an index of {}", target.name()),
+                            *position,
+                        );
+                    }
+                }
+                Stmt::Print { exprs } => {
+                    for expr in exprs {
+                        self.expr(expr, types);
+                    }
+                }
+                Stmt::Jump { offset: expr } | Stmt::Expr(expr) => {
+                    self.expr(expr, types);
+                }
+                Stmt::Return { value } => {
+                    if let Some(value) = value {
+                        self.expr(value, types);
+                    }
+                }
+                Stmt::Exit {
+                    code: Some(code),
+                    position,
+                } => {
+                    let code: Type = self.expr(code, types);
+                    if !matches!(code, Type::Int | Type::Unknown) {
+                        self.error(format!("exit needs an int, not {}", code.name()), *position);
+                    }
+                }
+                // A variable set for the first time in a block goes away when it ends,
+                // the ones from before have the type of either side
+                Stmt::If {
+                    condition,
+                    body,
+                    else_body,
+                } => {
+                    self.expr(condition, types);
+                    let mut then_types: HashMap<String, Type> = types.clone();
+                    self.block(body, &mut then_types);
+                    let mut else_types: HashMap<String, Type> = types.clone();
+                    if let Some(else_body) = else_body {
+                        self.block(else_body, &mut else_types);
+                    }
+                    merge(types, &then_types, &else_types);
+                }
+                // The body might stop anywhere, so the handler starts with either
+                // side of it, and the message is always a string
+                Stmt::Try {
+                    body,
+                    name,
+                    handler,
+                } => {
+                    let mut body_types: HashMap<String, Type> = types.clone();
+                    self.block(body, &mut body_types);
+                    let mut handler_types: HashMap<String, Type> = types.clone();
+                    merge(&mut handler_types, types, &body_types);
+                    self.assign(name, Type::Str, &mut handler_types);
+                    self.block(handler, &mut handler_types);
+                    merge(types, &body_types, &handler_types);
+                }
+                // Any arm could be the one that runs, or none of them
+                Stmt::Match { value, arms, .. } => {
+                    self.expr(value, types);
+                    let before: HashMap<String, Type> = types.clone();
+                    for arm in arms {
+                        let mut arm_types: HashMap<String, Type> = before.clone();
+                        self.block(&arm.body, &mut arm_types);
+                        let so_far: HashMap<String, Type> = types.clone();
+                        merge(types, &so_far, &arm_types);
+                    }
+                }
+                Stmt::While { condition, body } => {
+                    self.expr(condition, types);
+                    let mut body_types: HashMap<String, Type> = types.clone();
+                    self.block(body, &mut body_types);
+                    let before: HashMap<String, Type> = types.clone();
+                    merge(types, &body_types, &before);
+                }
+                // The name is set at the start of the body, so it's in the block's types
+                Stmt::For {
+                    name,
+                    iterable,
+                    body,
+                    position,
+                } => {
+                    let iterable: Type = self.expr(iterable, types);
+                    let item: Type = match iterable {
+                        Type::Range => Type::Int,
+                        Type::Str => Type::Str,
+                        Type::List | Type::Dict | Type::Unknown => Type::Unknown,
+                        _ => {
+                            self.error(format!("can't loop over {}", iterable.name()), *position);
+                            Type::Unknown
+                        }
+                    };
+                    let mut body_types: HashMap<String, Type> = types.clone();
+                    self.assign(name, item, &mut body_types);
+                    self.block(body, &mut body_types);
+                    let before: HashMap<String, Type> = types.clone();
+                    merge(types, &body_types, &before);
+                }
+                // Parameters could be anything, and so could globals that get set
+                // somewhere else while the function is running
+                Stmt::Func { params, body, .. } => {
+                    let mut inner: HashMap<String, Type> = HashMap::new();
+                    for name in params.iter().chain(global_names(body).iter()) {
+                        inner.insert(name.to_owned(), Type::Unknown);
+                    }
+                    self.block(body, &mut inner);
+                }
+                Stmt::Global { .. }
+                | Stmt::Break { .. }
+                | Stmt::Continue { .. }
+                | Stmt::Use { .. }
+                | Stmt::Exit { code: None, .. }
+                | Stmt::Lines(_) => {}
+                Stmt::Assert {
+                    value, expected, ..
+                } => {
+                    self.expr(value, types);
+                    if let Some(expected) = expected {
+                        self.expr(expected, types);
+                    }
+                }
+            }
+        }
+    }
+    /// Set the type of a variable, which has to go with the type it already has
+    fn assign(&mut self, name: &str, value: Type, types: &mut HashMap<String, Type>) {
+        let position: Position = self.next_position(name, false);
+        let joined: Type = match types.get(name) {
+            Some(current) => match current.join(value) {
+                Some(joined) => joined,
+                None => {
+                    let message: String = format!(
+                        "variable '{}' is {} but is set to {}",
+                        name,
+                        current.name(),
+                        value.name()
+                    );
+                    self.error(message, position);
+                    Type::Unknown
+                }
+            },
+            None => value,
+        };
+        types.insert(name.to_owned(), joined);
+    }
+    fn binary(&mut self, left: Type, operator: Tokens, right: Type, position: Position) -> Type {
+        // Either side can be what comes out, so it's only known if they're the same
+        if matches!(operator, Tokens::And | Tokens::Or) {
+            return if left == right { left } else { Type::Unknown };
+        }
+        match (left.sample(), right.sample()) {
+            (Some(left), Some(right)) => match binary(left, operator, right) {
+                Ok(value) => Type::of(&value),
+                Err(error) => {
+                    self.error(error.message, position);
+                    Type::Unknown
+                }
+            },
+            // Comparing always gives a bool, if it works at all
+            _ => match operator {
+                Tokens::LessThan
+                | Tokens::GreaterThan
+                | Tokens::LessEqual
+                | Tokens::GreaterEqual
+                | Tokens::Equal
+                | Tokens::NotEqual => Type::Bool,
+                _ => Type::Unknown,
+            },
+        }
+    }
+    /// The type of an item of the target, a string's items are strings but a
+    /// list's could be anything
+    fn index(
+        &mut self,
+        target: Type,
+        index: &Expr,
+        position: Position,
+        types: &HashMap<String, Type>,
+    ) -> Type {
+        let index: Type = self.expr(index, types);
+        let (item, what): (Type, &str) = match target {
+            // Any key that isn't there is only found out when it runs
+            Type::Dict => {
+                if matches!(index, Type::List | Type::Dict) {
+                    self.error(
+                        format!("{} can't be the key of a dict", index.name()),
+                        position,
+                    );
+                }
+                return Type::Unknown;
+            }
+            Type::List => (Type::Unknown, "list"),
+            Type::Str => (Type::Str, "string"),
+            Type::Unknown => return Type::Unknown,
+            _ => {
+                self.error(format!("can't index {}", target.name()), position);
+                return Type::Unknown;
+            }
+        };
+        if !matches!(index, Type::Int | Type::Bool | Type::Unknown) {
+            self.error(
+                format!("{} indexes must be ints, not {}", what, index.name()),
+                position,
+            );
+        }
+        item
+    }
+    /// Work out the type of an expression, adding an error for any part that can't work
+    fn expr(&mut self, expr: &Expr, types: &HashMap<String, Type>) -> Type {
+        match expr {
+            Expr::Numeric(number) => parse_number(number).map_or(Type::Unknown, |x| Type::of(&x)),
+            Expr::Str(_) | Expr::Input => Type::Str,
+            Expr::Bool(_) => Type::Bool,
+            Expr::Constant(name) => match name.as_str() {
+                "ARGS" => Type::List,
+                "ARGC" => Type::Int,
+                _ => Type::Float,
+            },
+            Expr::Identifier(name, _) => types.get(name).copied().unwrap_or(Type::Unknown),
+            Expr::Call { name, args, .. } => {
+                let position: Position = self.next_position(name, true);
+                let args: Vec<Type> = args.iter().map(|x| self.expr(x, types)).collect();
+                // The smallest or biggest item of a list could be anything, same
+                // for the smallest or biggest key of a dict
+                if self.functions.contains(name) {
+                    return Type::Unknown;
+                }
+                // Reading files and the environment is left for when the program runs
+                match name.as_str() {
+                    "input" | "read_file" | "env" => return Type::Str,
+                    "write_file" => return Type::Unknown,
+                    // Picking a number here would move the seed along, and sleeping would wait
+                    "rand" | "now" | "elapsed" => return Type::Float,
+                    "sleep" => return Type::Unknown,
+                    "rand_int" => return Type::Int,
+                    _ => {}
+                }
+                // What the user types could be a number or not
+                if name == "parse" && args.contains(&Type::Str) {
+                    return Type::Unknown;
+                }
+                if (matches!(name.as_str(), "min" | "max")
+                    && (args.contains(&Type::List) || args.contains(&Type::Dict)))
+                {
+                    return Type::Unknown;
+                }
+                let samples: Option<Vec<Value>> = args.iter().map(|x| x.sample()).collect();
+                match samples.map(|x| call(name, x)) {
+                    Some(Ok(value)) => Type::of(&value),
+                    Some(Err(_)) if !args.is_empty() => {
+                        let names: Vec<&str> = args.iter().map(|x| x.name()).collect();
+                        self.error(
+                            format!("{}() can't take {}", name, names.join(" and ")),
+                            position,
+                        );
+                        Type::Unknown
+                    }
+                    _ => Type::Unknown,
+                }
+            }
+            Expr::List(items) => {
+                for item in items {
+                    self.expr(item, types);
+                }
+                Type::List
+            }
+            Expr::Format(parts) => {
+                for part in parts {
+                    self.expr(part, types);
+                }
+                Type::Str
+            }
+            Expr::Dict(entries) => {
+                for (key, value) in entries {
+                    self.expr(key, types);
+                    self.expr(value, types);
+                }
+                Type::Dict
+            }
+            Expr::Index {
+                target,
+                index,
+                position,
+            } => {
+                let target: Type = self.expr(target, types);
+                self.index(target, index, *position, types)
+            }
+            Expr::Range {
+                start,
+                end,
+                position,
+            } => {
+                let start: Option<Value> = self.expr(start, types).sample();
+                let end: Option<Value> = self.expr(end, types).sample();
+                if let (Some(start), Some(end)) = (start, end) {
+                    if let Err(error) = new_range(start, end) {
+                        self.error(error.message, *position);
+                    }
+                }
+                Type::Range
+            }
+            Expr::Unary {
+                operator: Tokens::Not,
+                expr,
+            } => {
+                self.expr(expr, types);
+                Type::Bool
+            }
+            Expr::Unary { expr, .. } => {
+                let value: Type = self.expr(expr, types);
+                match value.sample().map(negate) {
+                    Some(Ok(value)) => Type::of(&value),
+                    Some(Err(error)) => {
+                        let position: Position = match expr.as_ref() {
+                            Expr::Identifier(_, position) | Expr::Binary { position, .. } => {
+                                *position
+                            }
+                            _ => Position::default(),
+                        };
+                        self.error(error.message, position);
+                        Type::Unknown
+                    }
+                    None => Type::Unknown,
+                }
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                position,
+            } => {
+                let left: Type = self.expr(left, types);
+                let right: Type = self.expr(right, types);
+                self.binary(left, *operator, right, *position)
+            }
+            // Either one could be picked, so it's what they both fit in
+            Expr::Ternary {
+                condition,
+                then,
+                otherwise,
+            } => {
+                self.expr(condition, types);
+                let then: Type = self.expr(then, types);
+                let otherwise: Type = self.expr(otherwise, types);
+                then.join(otherwise).unwrap_or(Type::Unknown)
+            }
+        }
+    }
+}
+
+/// After a block, only the variables from before it are kept, with the type
+/// from either way it could have gone
+fn merge(
+    types: &mut HashMap<String, Type>,
+    first: &HashMap<String, Type>,
+    second: &HashMap<String, Type>,
+) {
+    for (name, current) in types.iter_mut() {
+        let a: Type = first.get(name).copied().unwrap_or(*current);
+        let b: Type = second.get(name).copied().unwrap_or(*current);
+        *current = a.join(b).unwrap_or(Type::Unknown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{new_lexer, Lex, Lexer};
+    use crate::parser::{new_parser, Parse};
+
+    fn errors(contents: &str) -> Vec<(String, i64, i64)> {
+        let mut lexer: Lexer = new_lexer(contents);
+        lexer.lexer().unwrap();
+        let stmts: Vec<Stmt> = new_parser(&lexer.tokens).parse().unwrap();
+        check_types(&stmts, &lexer.tokens)
+            .into_iter()
+            .map(|x| (x.message, x.line_num, x.char_num))
+            .collect()
+    }
+
+    #[test]
+    fn join_test() {
+        assert_eq!(Type::Int.join(Type::Float), Some(Type::Float));
+        assert_eq!(Type::Bool.join(Type::Int), Some(Type::Int));
+        assert_eq!(Type::Str.join(Type::Unknown), Some(Type::Unknown));
+        assert_eq!(Type::Str.join(Type::Int), None);
+    }
+
+    #[test]
+    fn check_types_test() {
+        assert_eq!(
+            errors("set a = 1;\nset b = a / 2;\nprint b + 2.5;\nprint \"a\" * a;"),
+            vec![]
+        );
+
+        assert_eq!(
+            errors("set a = 1;\nset s = \"hi\";\nprint s + a;"),
+            vec![("can't use '+' on a string and an int".to_string(), 3, 9)]
+        );
+        // An int can become a float, but not a string
+        assert_eq!(
+            errors("set a = 1;\na /= 2;\nset a = \"x\";\nset a = 2;"),
+            vec![(
+                "variable 'a' is a float but is set to a string".to_string(),
+                3,
+                5
+            )]
+        );
+        assert_eq!(
+            errors("set a = 1;\nif a { set a = 2.5; }\nprint len(a);"),
+            vec![("len() can't take a float".to_string(), 3, 7)]
+        );
+
+        assert_eq!(
+            errors(
+                "set xs = [1, \"a\"];\nset n = len(xs);\nprint n + xs[0];\nprint xs[\"0\"];\nset n[0] = 1;"
+            ),
+            vec![
+                ("list indexes must be ints, not a string".to_string(), 4, 9),
+                ("can't set an index of an int".to_string(), 5, 6)
+            ]
+        );
+        assert_eq!(
+            errors(
+                "set m = {\"a\": 1};\nset m[\"b\"] = m[\"a\"] + 1;\nprint m[[]];\nprint 1 in 2;"
+            ),
+            vec![
+                ("a list can't be the key of a dict".to_string(), 3, 8),
+                ("can't use 'in' on an int and an int".to_string(), 4, 9)
+            ]
+        );
+
+        assert_eq!(
+            errors("for i in 0..3 { set s = \"a\" * i; }\nfor x in 1.5 {}\nprint 0.5..2;"),
+            vec![
+                ("can't loop over a float".to_string(), 2, 7),
+                (
+                    "a range needs ints, not a float and an int".to_string(),
+                    3,
+                    10
+                )
+            ]
+        );
+
+        // What functions give back and their parameters could be anything
+        assert_eq!(
+            errors("func f(x) { return x + 1; }\nset a = f(\"a\");\nprint a + 1;"),
+            vec![]
+        );
+    }
+}
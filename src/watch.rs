@@ -0,0 +1,103 @@
+//! Runs the program again each time it or a module it uses changes, for
+//! `mahou run --watch`. It looks at when the files were last changed a few
+//! times a second instead of asking the system to say, so it only needs std
+
+use crate::modules::module_files;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How long to wait between looking at the files
+pub const POLL: Duration = Duration::from_millis(200);
+
+/// Clears the terminal and moves to the top, between runs
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// When a file was last changed and how big it is, a file that's gone is None
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &PathBuf) -> Stamp {
+    let metadata: fs::Metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// The files to watch with how each one was the last time it looked
+pub struct Watcher {
+    filename: String,
+    pub files: Vec<PathBuf>,
+    stamps: Vec<Stamp>,
+}
+
+/// Watch the file and the modules it uses
+pub fn new_watcher(filename: &str) -> Watcher {
+    let mut watcher: Watcher = Watcher {
+        filename: filename.to_string(),
+        files: Vec::new(),
+        stamps: Vec::new(),
+    };
+    watcher.refresh();
+    watcher
+}
+
+impl Watcher {
+    /// Find the files again, since a change can add or take out a use
+    fn refresh(&mut self) {
+        self.files = module_files(&self.filename);
+        self.stamps = self.files.iter().map(stamp).collect();
+    }
+    /// Whether any of the files changed since the last time it looked
+    pub fn changed(&mut self) -> bool {
+        let stamps: Vec<Stamp> = self.files.iter().map(stamp).collect();
+        if stamps == self.stamps {
+            return false;
+        }
+        self.refresh();
+        true
+    }
+    /// Wait until a file changes
+    pub fn wait(&mut self) {
+        while !self.changed() {
+            thread::sleep(POLL);
+        }
+    }
+}
+
+/// Clear the screen and run, then do it again every time a file changes. This only
+/// stops when mahou is stopped, like with ctrl-c
+pub fn watch(filename: &str, mut run: impl FnMut()) -> ! {
+    let mut watcher: Watcher = new_watcher(filename);
+    loop {
+        print!("{}", CLEAR);
+        run();
+        watcher.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watcher_test() {
+        let folder: PathBuf = std::env::temp_dir().join("mahou_watcher_test");
+        fs::create_dir_all(&folder).unwrap();
+        let main: PathBuf = folder.join("main.m");
+        fs::write(&main, "print 1;").unwrap();
+        fs::write(folder.join("shapes.m"), "set side = 1;").unwrap();
+
+        let mut watcher: Watcher = new_watcher(&main.display().to_string());
+        assert_eq!(watcher.files.len(), 1);
+        assert!(!watcher.changed());
+
+        // Using a module starts watching it too
+        fs::write(&main, "use shapes;\nprint shapes.side;").unwrap();
+        assert!(watcher.changed());
+        assert_eq!(watcher.files.len(), 2);
+        assert!(!watcher.changed());
+        fs::write(folder.join("shapes.m"), "set side = 22;").unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+//! Runs every program in tests/programs and checks it against the files next to it.
+//! `name.out` is what the interpreter prints, with the exit code or the error after
+//! it, and `name.py` is what the program compiles to. `name.in` is given as stdin if
+//! it's there. Run with `BLESS=1` to write what the programs do now as the expected files.
+//! The python is run too when there's a python3, and has to print the same thing
+
+use mahou::error::MahouError;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// What running the program gave, the output and then how it ended if it
+/// didn't just finish
+fn run(contents: &str, input: &str) -> String {
+    let mut output: Vec<u8> = Vec::new();
+    let result: Result<i32, MahouError> = mahou::run(contents, input.as_bytes(), &mut output);
+    let mut text: String = String::from_utf8_lossy(&output).into_owned();
+    match result {
+        Ok(0) => {}
+        Ok(code) => text.push_str(&format!("exit {}\n", code)),
+        Err(error) => text.push_str(&error.to_string()),
+    }
+    text
+}
+
+fn compile(contents: &str) -> String {
+    mahou::compile(contents).unwrap_or_else(|error| error.to_string())
+}
+
+/// The lines that are the same by a longest common subsequence, with `-` before
+/// lines only in what was expected and `+` before lines only in what was given
+fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    // How long the common part is from old[i..] and new[j..]
+    let mut common: Vec<Vec<usize>> = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut text: String = String::new();
+    let (mut i, mut j): (usize, usize) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            text.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            text.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            text.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    text
+}
+
+/// Compare with the expected file, or write it when blessing. Gives back what
+/// went wrong if anything did
+fn expect(path: &Path, actual: &str, bless: bool) -> Option<String> {
+    if bless {
+        fs::write(path, actual).unwrap();
+        return None;
+    }
+    let expected: String = fs::read_to_string(path).unwrap_or_default();
+    if expected == actual {
+        return None;
+    }
+    Some(format!(
+        "{} is different:\n{}",
+        path.display(),
+        diff(&expected, actual)
+    ))
+}
+
+/// Every program in tests/programs, in order
+fn programs() -> Vec<PathBuf> {
+    let directory: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs");
+    let mut programs: Vec<PathBuf> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|x| x == "m"))
+        .collect();
+    programs.sort();
+    assert!(
+        !programs.is_empty(),
+        "no programs in {}",
+        directory.display()
+    );
+    programs
+}
+
+/// Run python with the input, giving back what it printed and the code it exited
+/// with, or None if there's no python3 to run it with
+fn run_python(python: &str, input: &str) -> Option<(String, i32)> {
+    let mut child = Command::new("python3")
+        .args(["-c", python])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    Some((
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        output.status.code().unwrap_or(-1),
+    ))
+}
+
+#[test]
+fn programs_test() {
+    let bless: bool = env::var_os("BLESS").is_some();
+    let programs: Vec<PathBuf> = programs();
+
+    let mut failures: Vec<String> = Vec::new();
+    for program in &programs {
+        let contents: String = fs::read_to_string(program).unwrap();
+        let input: String = fs::read_to_string(program.with_extension("in")).unwrap_or_default();
+        failures.extend(expect(
+            &program.with_extension("out"),
+            &run(&contents, &input),
+            bless,
+        ));
+        failures.extend(expect(
+            &program.with_extension("py"),
+            &compile(&contents),
+            bless,
+        ));
+    }
+    assert!(
+        failures.is_empty(),
+        "{} files for {} programs didn't match, run with BLESS=1 if the changes are right\n\n{}",
+        failures.len(),
+        programs.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn python_test() {
+    let mut failures: Vec<String> = Vec::new();
+    for program in programs() {
+        let contents: String = fs::read_to_string(&program).unwrap();
+        let input: String = fs::read_to_string(program.with_extension("in")).unwrap_or_default();
+        let python: String = match mahou::compile(&contents) {
+            Ok(python) => python,
+            Err(_) => continue,
+        };
+        let (printed, code): (String, i32) = match run_python(&python, &input) {
+            Some(ran) => ran,
+            None => {
+                eprintln!("python3 isn't installed, so the python wasn't run");
+                return;
+            }
+        };
+
+        let mut output: Vec<u8> = Vec::new();
+        let result: Result<i32, MahouError> = mahou::run(&contents, input.as_bytes(), &mut output);
+        let expected: String = String::from_utf8_lossy(&output).into_owned();
+        // An error while running is a traceback in python, so only the code has to be a failure
+        let same_code: bool = match result {
+            Ok(expected) => code == expected,
+            Err(_) => code != 0,
+        };
+        if printed != expected || !same_code {
+            failures.push(format!(
+                "{} ran differently in python, exit {}:\n{}",
+                program.display(),
+                code,
+                diff(&expected, &printed)
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn diff_test() {
+    assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n- b\n+ x\n  c\n");
+    assert_eq!(diff("", "a\n"), "+ a\n");
+}
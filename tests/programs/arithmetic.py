@@ -0,0 +1,9 @@
+print(1 + 2 * 3)
+print((1 + 2) * 3)
+print(2 ** 3 ** 2)
+print(-2 ** 2)
+print(7 % 3)
+print(7 / 2)
+print(1.5 + 2.25)
+print(10 - 4 - 3)
+print(not (1 == 2) and True)
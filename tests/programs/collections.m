@@ -0,0 +1,14 @@
+set xs = [3, 1, 2];
+set xs[0] = 5;
+print xs;
+print len(xs);
+print xs[1] + xs[2];
+print 2 in xs;
+
+set ages = {"ann": 31, "bo": 4};
+print ages["bo"];
+print "ann" in ages;
+
+set grid = [[0, 0], [0, 0]];
+set grid[1][0] = 7;
+print grid;
@@ -0,0 +1,12 @@
+xs = [3, 1, 2]
+xs[0] = 5
+print(xs)
+print(len(xs))
+print(xs[1] + xs[2])
+print(2 in xs)
+ages = {"ann": 31, "bo": 4}
+print(ages["bo"])
+print("ann" in ages)
+grid = [[0, 0], [0, 0]]
+grid[1][0] = 7
+print(grid)
@@ -0,0 +1,14 @@
+n = 0
+while n < 3:
+    if n == 0:
+        print("zero")
+    else:
+        if n == 1:
+            print("one")
+        else:
+            print("more")
+    n += 1
+for i in range(0, 3):
+    print(i * i)
+for word in ["a", "b"]:
+    print(word)
@@ -0,0 +1,3 @@
+set xs = [1, 2];
+print xs[0];
+print xs[5];
@@ -0,0 +1,3 @@
+print "leaving early";
+exit 3;
+print "never printed";
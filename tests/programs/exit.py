@@ -0,0 +1,3 @@
+print("leaving early")
+raise SystemExit(3)
+print("never printed")
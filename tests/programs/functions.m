@@ -0,0 +1,21 @@
+func fact(n) {
+    if n < 2 {
+        return 1;
+    }
+    return n * fact(n - 1);
+}
+print fact(10);
+
+set count = 0;
+func bump() {
+    global count;
+    set count = count + 1;
+}
+bump();
+bump();
+print count;
+
+func nothing() {
+    return;
+}
+print nothing();
@@ -0,0 +1,15 @@
+def fact(n):
+    if n < 2:
+        return 1
+    return n * fact(n - 1)
+print(fact(10))
+count = 0
+def bump():
+    global count
+    count = count + 1
+bump()
+bump()
+print(count)
+def nothing():
+    return
+print(nothing())
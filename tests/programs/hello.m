@@ -0,0 +1,5 @@
+# The smallest program, and strings with values put into them
+print "hello world";
+set name = "mahou";
+print "hi {name}, {1 + 2} is three";
+print "braces are {{doubled}}";
@@ -0,0 +1,4 @@
+print("hello world")
+name = "mahou"
+print(f"hi {name}, {1 + 2} is three")
+print("braces are {doubled}")
@@ -0,0 +1,3 @@
+set first = input;
+set second = input;
+print "{first} and {second}";
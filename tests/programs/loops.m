@@ -0,0 +1,22 @@
+# break leaves the loop and continue goes on to the next time through
+set n = 0;
+while true {
+    n += 1;
+    if n % 3 == 0 {
+        continue;
+    }
+    if n > 8 {
+        break;
+    }
+    print n;
+}
+
+# Only the inner loop is left
+for x in 0..3 {
+    for y in 0..3 {
+        if y > x {
+            break;
+        }
+        print x, y;
+    }
+}
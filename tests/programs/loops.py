@@ -0,0 +1,13 @@
+n = 0
+while True:
+    n += 1
+    if n % 3 == 0:
+        continue
+    if n > 8:
+        break
+    print(n)
+for x in range(0, 3):
+    for y in range(0, 3):
+        if y > x:
+            break
+        print(x, y)
@@ -0,0 +1,32 @@
+# Each arm is checked in order and only the first one that's equal runs
+func name(n) {
+    match n {
+        1 => return "one";
+        2 => {
+            set word = "two";
+            return word;
+        },
+        _ => return "many";
+    }
+}
+
+for i in 0..5 {
+    # The value is only worked out once, and no arm matching does nothing
+    match i % 3 {
+        0 => print "skip", i;
+        1 => {
+            continue;
+        }
+    }
+    print name(i);
+}
+
+match "b" {
+    "a" => print "not this";
+    "b" => print "this";
+    _ => print "or this";
+}
+match 1 > 0 {
+    true => print "yes";
+    false => print "no";
+}
@@ -0,0 +1,26 @@
+def name(n):
+    if n == 1:
+        return "one"
+    elif n == 2:
+        word = "two"
+        return word
+    else:
+        return "many"
+for i in range(0, 5):
+    match_15_5 = i % 3
+    if match_15_5 == 0:
+        print("skip", i)
+    elif match_15_5 == 1:
+        continue
+    print(name(i))
+if "b" == "a":
+    print("not this")
+elif "b" == "b":
+    print("this")
+else:
+    print("or this")
+match_29_1 = 1 > 0
+if match_29_1 == True:
+    print("yes")
+elif match_29_1 == False:
+    print("no")
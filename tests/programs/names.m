@@ -0,0 +1,21 @@
+# Names that python uses for something else still work
+set class = 2;
+set lambda = class + 1;
+set len = len([class, lambda]);
+print class * lambda + len;
+
+func count() {
+    return 10;
+}
+set count = count() + 1;
+print count;
+
+func pass(from) {
+    return from * 2;
+}
+set range = 3;
+for None in 0..range {
+    print pass(None);
+}
+set math = $PI > 3;
+print "math is {math}";
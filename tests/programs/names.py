@@ -0,0 +1,16 @@
+import math
+class_ = 2
+lambda_ = class_ + 1
+len_ = len([class_, lambda_])
+print(class_ * lambda_ + len_)
+def count():
+    return 10
+count_ = count() + 1
+print(count_)
+def pass__(from_):
+    return from_ * 2
+range_ = 3
+for None_ in range(0, range_):
+    print(pass__(None_))
+math_ = math.pi > 3
+print(f"math is {math_}")
@@ -0,0 +1,10 @@
+# print takes more than one value and puts a space between them
+set name = "ann";
+set scores = [3, 4.5];
+print name, "|", scores;
+print "total:", 3 + 4.5;
+
+# fmt shows a number with that many places, as text
+print fmt($PI, 2), fmt(2, 1);
+set places = 3;
+print "pi is {fmt($PI, places)}";
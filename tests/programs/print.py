@@ -0,0 +1,8 @@
+import math
+name = "ann"
+scores = [3, 4.5]
+print(name, "|", scores)
+print("total:", 3 + 4.5)
+print(format(math.pi, ".2f"), format(2, ".1f"))
+places = 3
+print(f"pi is {math.pi:.{places}f}")
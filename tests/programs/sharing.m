@@ -0,0 +1,26 @@
+# Lists and dicts are shared, not copied
+set a = [1, 2];
+set b = a;
+set b[0] = 5;
+a += [3];
+print a, b;
+
+func add(items, item) {
+    items += [item];
+}
+add(a, 4);
+print b;
+
+# A list can have itself in it
+set a[1] = a;
+print a;
+print a == b, a[1][1][0];
+
+set d = {"name": "d"};
+set d["self"] = d;
+print d;
+
+set s = "hi";
+set t = s;
+s += "!";
+print s, t;
@@ -0,0 +1,19 @@
+a = [1, 2]
+b = a
+b[0] = 5
+a += [3]
+print(a, b)
+def add(items, item):
+    items += [item]
+add(a, 4)
+print(b)
+a[1] = a
+print(a)
+print(a == b, a[1][1][0])
+d = {"name": "d"}
+d["self"] = d
+print(d)
+s = "hi"
+t = s
+s += "!"
+print(s, t)
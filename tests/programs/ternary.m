@@ -0,0 +1,13 @@
+# Picking between two values without an if
+set n = 7;
+set parity = n % 2 == 0 ? "even" : "odd";
+print n, "is", parity;
+
+for i in 0..4 {
+    print i == 0 ? "none" : i == 1 ? "one" : "many";
+}
+
+# Only the side that's picked runs, so this never divides by zero
+set d = 0;
+print d == 0 ? 0 : 10 / d;
+print "bigger: {n > 5 ? n : 5}";
@@ -0,0 +1,8 @@
+n = 7
+parity = "even" if n % 2 == 0 else "odd"
+print(n, "is", parity)
+for i in range(0, 4):
+    print("none" if i == 0 else "one" if i == 1 else "many")
+d = 0
+print(0 if d == 0 else 10 / d)
+print(f"bigger: {n if n > 5 else 5}")
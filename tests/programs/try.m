@@ -0,0 +1,33 @@
+# An error in the try goes to the catch with its message instead of stopping
+func average(xs) {
+    set total = 0;
+    for x in xs {
+        total += x;
+    }
+    return total / len(xs);
+}
+
+for xs in [[1, 2, 3], [], [4]] {
+    try {
+        print average(xs);
+    } catch err {
+        print "no average:", err;
+    }
+}
+
+# It also comes back out of loops and function calls
+set found = false;
+try {
+    for i in 0..10 {
+        set grid = [[0, 1], [2, 3]];
+        print grid[i][i];
+    }
+    set found = true;
+} catch err {
+    print "stopped:", err;
+}
+print found;
+
+# Dividing by a zero that's written out isn't stopped before it runs in a try,
+# since the catch is there for it
+try { print 1 / 0; } catch e { print e; }
@@ -0,0 +1,26 @@
+def average(xs):
+    total = 0
+    for x in xs:
+        total += x
+    return total / len(xs)
+for xs in [[1, 2, 3], [], [4]]:
+    try:
+        print(average(xs))
+    except Exception as err:
+        err = str(err)
+        print("no average:", err)
+found = False
+try:
+    for i in range(0, 10):
+        grid = [[0, 1], [2, 3]]
+        print(grid[i][i])
+    found = True
+except Exception as err:
+    err = str(err)
+    print("stopped:", err)
+print(found)
+try:
+    print(1 / 0)
+except Exception as e:
+    e = str(e)
+    print(e)